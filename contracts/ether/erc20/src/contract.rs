@@ -5,10 +5,14 @@ use cosmwasm_std::{
     MessageInfo, Response, StdResult, Storage, Uint128,
 };
 use cosmwasm_storage::{PrefixedStorage, ReadonlyPrefixedStorage};
+use cw2::set_contract_version;
+use migration::migrate_version;
 use std::convert::TryInto;
 
 use crate::error::ContractError;
-use crate::msg::{AllowanceResponse, BalanceResponse, ExecuteMsg, InstantiateMsg, QueryMsg};
+use crate::msg::{
+    AllowanceResponse, BalanceResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg,
+};
 use crate::state::Constants;
 
 pub const PREFIX_CONFIG: &[u8] = b"config";
@@ -18,6 +22,10 @@ pub const PREFIX_ALLOWANCES: &[u8] = b"allowances";
 pub const KEY_CONSTANTS: &[u8] = b"constants";
 pub const KEY_TOTAL_SUPPLY: &[u8] = b"total_supply";
 
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:erc20";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
@@ -25,6 +33,7 @@ pub fn instantiate(
     _info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     let mut total_supply: u128 = 0;
     {
         // Initial balances
@@ -104,6 +113,17 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> Result<Binary, ContractErr
     }
 }
 
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    Ok(migrate_version(
+        deps,
+        &env,
+        CONTRACT_NAME,
+        CONTRACT_VERSION,
+        &[],
+    )?)
+}
+
 fn try_transfer(
     deps: DepsMut,
     _env: Env,
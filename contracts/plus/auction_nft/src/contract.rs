@@ -14,8 +14,10 @@ use cosmwasm_std::{
     DepsMut, Env, MessageInfo, Order, Response, StdError, StdResult, Uint128, WasmMsg,
 };
 use cosmwasm_std::{Addr, Record};
+use cw2::set_contract_version;
 use cw721::{Cw721ExecuteMsg, Cw721ReceiveMsg};
 use cw_storage_plus::Bound;
+use migration::migrate_version;
 use std::convert::TryInto;
 use std::ops::{Add, Mul};
 use std::usize;
@@ -34,6 +36,10 @@ fn sanitize_fee(fee: u64, limit: u64, name: &str) -> Result<u64, ContractError>
     Ok(fee)
 }
 
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:auction_nft";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
 // Note, you can use StdResult in some functions where you do not
 // make use of the custom errors
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -43,6 +49,7 @@ pub fn instantiate(
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     let info = ContractInfo {
         name: msg.name,
         creator: info.sender.to_string(),
@@ -746,6 +753,6 @@ fn parse_auction(
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
-    Ok(Response::default())
+pub fn migrate(deps: DepsMut, env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+    migrate_version(deps, &env, CONTRACT_NAME, CONTRACT_VERSION, &[])
 }
@@ -1,7 +1,7 @@
 use crate::errors::ContractError;
 use crate::msg::{
-    AggregateSig, DistributedShareData, ExecuteMsg, InstantiateMsg, MemberMsg, QueryMsg, ShareMsg,
-    ShareSig, UpdateShareSigMsg,
+    AggregateSig, DistributedShareData, ExecuteMsg, InstantiateMsg, MemberMsg, MigrateMsg,
+    QueryMsg, ShareMsg, ShareSig, UpdateShareSigMsg,
 };
 use crate::state::{
     beacons_handle_storage, beacons_handle_storage_read, beacons_storage, beacons_storage_read,
@@ -14,6 +14,8 @@ use cosmwasm_std::{
     attr, coins, from_json, to_json_binary, BankMsg, Binary, Coin, CosmosMsg, Deps, DepsMut, Env,
     MessageInfo, Order, Response,
 };
+use cw2::set_contract_version;
+use migration::migrate_version;
 
 use sha2::{Digest, Sha256};
 
@@ -21,6 +23,10 @@ use sha2::{Digest, Sha256};
 const MAX_LIMIT: u8 = 30;
 const DEFAULT_LIMIT: u8 = 10;
 
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:dkg";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
@@ -28,6 +34,7 @@ pub fn instantiate(
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     // init with a signature, pubkey and denom for bounty
     config(deps.storage).save(&Config {
         threshold: msg.threshold,
@@ -408,6 +415,17 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> Result<Binary, ContractErr
     Ok(response)
 }
 
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    Ok(migrate_version(
+        deps,
+        &env,
+        CONTRACT_NAME,
+        CONTRACT_VERSION,
+        &[],
+    )?)
+}
+
 fn query_member(deps: Deps, address: &str) -> Result<MemberMsg, ContractError> {
     let beacons = members_storage_read(deps.storage);
     let value = beacons
@@ -39,6 +39,9 @@ pub struct InstantiateMsg {
     pub fee: Option<Coin>,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct UpdateShareSigMsg {
     pub sig: Binary,
@@ -5,15 +5,23 @@ use cosmwasm_std::{
     coins, from_json, to_json_binary, BankMsg, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo,
     Order, Response, StdResult, Storage,
 };
+use cw2::set_contract_version;
 use drand_verify::{derive_randomness, g1_from_variable, verify};
+use migration::migrate_version;
 
 use crate::errors::{HandleError, QueryError};
-use crate::msg::{BountiesResponse, Bounty, ExecuteMsg, InstantiateMsg, QueryMsg, RandomData};
+use crate::msg::{
+    BountiesResponse, Bounty, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, RandomData,
+};
 use crate::state::{
     beacons_storage, beacons_storage_read, bounties_storage, bounties_storage_read, config,
     config_read, Config,
 };
 
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:drand";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
@@ -21,6 +29,7 @@ pub fn instantiate(
     _info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, HandleError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     // verify signature for genesis round
     let pk = g1_from_variable(&msg.pubkey).map_err(|_| HandleError::InvalidPubkey {})?;
     let valid = verify(&pk, 0, &vec![], msg.signature.as_slice()).unwrap_or(false);
@@ -149,6 +158,17 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> Result<Binary, QueryError>
     Ok(response)
 }
 
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, env: Env, _msg: MigrateMsg) -> Result<Response, HandleError> {
+    Ok(migrate_version(
+        deps,
+        &env,
+        CONTRACT_NAME,
+        CONTRACT_VERSION,
+        &[],
+    )?)
+}
+
 fn query_get(deps: Deps, round: u64) -> Result<RandomData, QueryError> {
     let beacons = beacons_storage_read(deps.storage);
     let value = beacons
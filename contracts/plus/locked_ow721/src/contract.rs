@@ -16,6 +16,8 @@ use cosmwasm_std::{
     attr, from_json, to_json_binary, to_json_vec, Addr, Api, Binary, CosmosMsg, Deps, DepsMut, Env,
     MessageInfo, Order, Record, Response, StdError, StdResult, WasmMsg,
 };
+use cw2::set_contract_version;
+use migration::migrate_version;
 
 // ******************************** TODO: ADD change allowed pub key **************************
 
@@ -31,6 +33,10 @@ const MAX_LIMIT: u8 = 100;
 const DELIMITER: &'static str = "&";
 const DEFAULT_LIMIT: u8 = 100;
 
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:locked_ow721";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
 // make use of the custom errors
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -39,6 +45,7 @@ pub fn instantiate(
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> StdResult<Response> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     let state = Owner {
         owner: info.sender.to_string(),
     };
@@ -539,8 +546,8 @@ fn parse_pubkey(_api: &dyn Api, item: StdResult<Record<bool>>) -> StdResult<PubK
     })
 }
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
-    Ok(Response::default())
+pub fn migrate(deps: DepsMut, env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+    migrate_version(deps, &env, CONTRACT_NAME, CONTRACT_VERSION, &[])
 }
 
 #[cfg(test)]
@@ -1,27 +1,34 @@
 use crate::contract::{
-    get_asset_info, get_handle_msg, get_royalties, query_payment_auction_asset_info, query_storage,
-    verify_funds, verify_nft, PAYMENT_STORAGE,
+    get_asset_info, get_royalties, get_sent_amount, query_payment_auction_asset_info, verify_funds,
+    verify_nft,
 };
 use crate::error::ContractError;
-use crate::msg::AskNftMsg;
+use crate::msg::{AskNftMsg, ClearingPriceResponse};
 // use crate::offering::OFFERING_STORAGE;
-use crate::state::{ContractInfo, CONTRACT_INFO, MARKET_FEES};
+use crate::state::{
+    increment_bids, AuctionBid, ContractInfo, AUCTION_BIDS, CONTRACT_INFO, MARKET_FEES,
+};
 use cosmwasm_std::{
-    attr, to_json_binary, Decimal, DepsMut, Env, MessageInfo, Response, StdResult, Uint128, WasmMsg,
+    attr, to_json_binary, CosmosMsg, Decimal, Deps, DepsMut, Env, MessageInfo, Order, Response,
+    StdResult, Storage, Uint128, WasmMsg,
 };
 use cosmwasm_std::{Addr, Coin};
 use cw1155::Cw1155ExecuteMsg;
-use market::{AssetInfo, Funds};
+use market::{
+    validate_reserved_for, verify_reserved_buyer, AssetInfo, Funds, PAYMENT_1155_STORAGE,
+};
+use market_1155::BidAmountMsg;
 use market_ai_royalty::{parse_transfer_msg, pay_royalties};
-use market_auction_extend::{Auction, AuctionExecuteMsg, AuctionQueryMsg};
-use market_payment::{Payment, PaymentExecuteMsg};
+use market_auction_extend::{Auction, AuctionExtendStorage};
+use market_payment::{Payment, PaymentStorage};
 // use market_royalty::OfferingQueryMsg;
 use std::ops::{Add, Mul, Sub};
 
-pub const AUCTION_STORAGE: &str = "auction_extend";
 // const MAX_ROYALTY_PERCENT: u64 = 50;
 // pub const OFFERING_STORAGE: &str = "offering";
 pub const DEFAULT_AUCTION_BLOCK: u64 = 50000;
+/// bidders of a multi winner auction, all of them are settled when claiming
+pub const MAX_AUCTION_BIDS: usize = 50;
 
 /// update bidder, return previous price of previous bidder, update current price of current bidder
 pub fn try_bid_nft(
@@ -39,12 +46,13 @@ pub fn try_bid_nft(
     } = CONTRACT_INFO.load(deps.storage)?;
 
     // check if auction exists, when return StdError => it will show EOF while parsing a JSON value.
-    let mut off: Auction = query_storage(
-        deps.as_ref(),
-        AUCTION_STORAGE,
-        AuctionQueryMsg::GetAuctionRaw { auction_id },
-    )
-    .map_err(|_op| ContractError::AuctionNotFound {})?;
+    let mut off = governance
+        .get_auction_raw(&deps.querier, auction_id)
+        .map_err(|_op| ContractError::AuctionNotFound {})?;
+    if off.multi_winner {
+        return Err(ContractError::InvalidAuctionBid {});
+    }
+    verify_reserved_buyer(&off.reserved_for, &sender)?;
 
     // check auction started or finished, both means auction not started anymore
     if off.start.gt(&env.block.height) {
@@ -71,7 +79,7 @@ pub fn try_bid_nft(
     let token_id = off.token_id.clone();
     let asset_info = query_payment_auction_asset_info(
         deps.as_ref(),
-        governance.addr().as_str(),
+        &governance,
         deps.api.addr_humanize(&off.contract_addr)?,
         &token_id,
         deps.api.addr_humanize(&off.asker)?.as_str(),
@@ -128,11 +136,7 @@ pub fn try_bid_nft(
         off.bidder = deps.api.addr_canonicalize(sender.as_str()).ok();
         off.per_price = per_price;
         // push save message to auction_storage
-        cosmos_msgs.push(get_handle_msg(
-            &governance,
-            AUCTION_STORAGE,
-            AuctionExecuteMsg::UpdateAuction { auction: off },
-        )?);
+        cosmos_msgs.push(governance.update_auction(off)?);
     } else {
         return Err(ContractError::InvalidZeroAmount {});
     }
@@ -148,6 +152,125 @@ pub fn try_bid_nft(
         ]))
 }
 
+/// place or replace the bid of the sender in the bid book of a multi winner auction
+pub fn try_bid_nft_amount(
+    deps: DepsMut,
+    sender: Addr,
+    env: Env,
+    msg: BidAmountMsg,
+    funds: Funds,
+    cw20_addr: Option<Addr>,
+) -> Result<Response, ContractError> {
+    let BidAmountMsg {
+        auction_id,
+        amount,
+        per_price,
+    } = msg;
+    let ContractInfo { governance, .. } = CONTRACT_INFO.load(deps.storage)?;
+
+    let off = governance
+        .get_auction_raw(&deps.querier, auction_id)
+        .map_err(|_op| ContractError::AuctionNotFound {})?;
+    if !off.multi_winner {
+        return Err(ContractError::InvalidAuctionBid {});
+    }
+    verify_reserved_buyer(&off.reserved_for, &sender)?;
+
+    if off.start.gt(&env.block.height) {
+        return Err(ContractError::AuctionNotStarted {});
+    }
+    if off.end.lt(&env.block.height) {
+        return Err(ContractError::AuctionHasEnded {});
+    }
+    if amount.is_zero() {
+        return Err(ContractError::InvalidZeroAmount {});
+    }
+    if amount.gt(&off.amount) {
+        return Err(ContractError::InsufficientAmount {});
+    }
+    // the asking per price is the reserve price of every bid
+    if per_price.lt(&off.orig_per_price) {
+        return Err(ContractError::InsufficientFunds {});
+    }
+
+    let asset_info = query_payment_auction_asset_info(
+        deps.as_ref(),
+        &governance,
+        deps.api.addr_humanize(&off.contract_addr)?,
+        &off.token_id,
+        deps.api.addr_humanize(&off.asker)?.as_str(),
+    )?;
+    // the deposit is refunded in the auction asset when claimed, it can only be paid in it
+    let deposit = get_sent_amount(&funds, cw20_addr.as_ref(), &asset_info)?;
+    if deposit.lt(&calculate_price(per_price, amount)) {
+        return Err(ContractError::InsufficientFunds {});
+    }
+
+    let mut cosmos_msgs = vec![];
+    let mut attributes = vec![];
+    // refund the bid being replaced
+    if let Some(prev_bid) =
+        AUCTION_BIDS.may_load(deps.storage, (&auction_id.to_be_bytes(), sender.as_bytes()))?
+    {
+        cosmos_msgs.push(parse_transfer_msg(
+            asset_info,
+            prev_bid.deposit,
+            env.contract.address.as_str(),
+            sender.clone(),
+        )?);
+    } else {
+        // the whole bid book is settled in the claim transaction, once full a bid must beat the lowest one,
+        // which is evicted and refunded
+        let bids = get_auction_bids(deps.storage, auction_id)?;
+        if bids.len() >= MAX_AUCTION_BIDS {
+            let lowest_bid = match bids.last() {
+                Some(lowest_bid) if per_price.gt(&lowest_bid.per_price) => lowest_bid,
+                _ => {
+                    return Err(ContractError::TooManyBids {
+                        max: MAX_AUCTION_BIDS,
+                    })
+                }
+            };
+            AUCTION_BIDS.remove(
+                deps.storage,
+                (&auction_id.to_be_bytes(), lowest_bid.bidder.as_bytes()),
+            );
+            cosmos_msgs.push(parse_transfer_msg(
+                asset_info,
+                lowest_bid.deposit,
+                env.contract.address.as_str(),
+                lowest_bid.bidder.clone(),
+            )?);
+            attributes.push(attr("evicted_bidder", lowest_bid.bidder.clone()));
+        }
+    }
+
+    let seq = increment_bids(deps.storage)?;
+    AUCTION_BIDS.save(
+        deps.storage,
+        (&auction_id.to_be_bytes(), sender.as_bytes()),
+        &AuctionBid {
+            bidder: sender.clone(),
+            amount,
+            per_price,
+            deposit,
+            seq,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_messages(cosmos_msgs)
+        .add_attributes(vec![
+            attr("action", "bid_nft_amount"),
+            attr("bidder", sender),
+            attr("auction_id", auction_id.to_string()),
+            attr("token_id", off.token_id),
+            attr("amount", amount),
+            attr("per_price", per_price),
+        ])
+        .add_attributes(attributes))
+}
+
 /// anyone can claim
 pub fn try_claim_winner(
     deps: DepsMut,
@@ -155,20 +278,16 @@ pub fn try_claim_winner(
     env: Env,
     auction_id: u64,
 ) -> Result<Response, ContractError> {
-    let ContractInfo {
-        fee,
-        governance,
-        decimal_point,
-        ..
-    } = CONTRACT_INFO.load(deps.storage)?;
+    let ContractInfo { governance, .. } = CONTRACT_INFO.load(deps.storage)?;
 
     // check if auction exists
-    let off: Auction = query_storage(
-        deps.as_ref(),
-        AUCTION_STORAGE,
-        AuctionQueryMsg::GetAuctionRaw { auction_id },
-    )
-    .map_err(|_| ContractError::AuctionNotFound {})?;
+    let off = governance
+        .get_auction_raw(&deps.querier, auction_id)
+        .map_err(|_| ContractError::AuctionNotFound {})?;
+
+    if off.multi_winner {
+        return try_claim_multi_winner(deps, info, env, auction_id, off);
+    }
 
     let price = calculate_price(off.per_price, off.amount);
     // check is auction finished
@@ -184,24 +303,22 @@ pub fn try_claim_winner(
     }
 
     let asker_addr = deps.api.addr_humanize(&off.asker)?;
-    let contract_addr = deps.api.addr_humanize(&off.contract_addr)?;
 
-    // get royalties
     let mut rsp = Response::default();
     rsp.attributes.extend(vec![attr("action", "claim_winner")]);
     let mut cosmos_msgs = vec![];
 
-    let token_id = off.token_id;
+    let token_id = off.token_id.clone();
     let asset_info = query_payment_auction_asset_info(
         deps.as_ref(),
-        governance.addr().as_str(),
+        &governance,
         deps.api.addr_humanize(&off.contract_addr)?,
         &token_id,
         deps.api.addr_humanize(&off.asker)?.as_str(),
     )?;
 
-    if let Some(bidder) = off.bidder {
-        let bidder_addr = deps.api.addr_humanize(&bidder)?;
+    if let Some(bidder) = &off.bidder {
+        let bidder_addr = deps.api.addr_humanize(bidder)?;
         // transfer token to bidder
         cosmos_msgs.push(
             WasmMsg::Execute {
@@ -218,59 +335,196 @@ pub fn try_claim_winner(
             .into(),
         );
 
-        let mut fund_amount = price;
-        // minus market fees
-        let fee_amount = price.mul(Decimal::permille(fee));
-        MARKET_FEES.update(deps.storage, |current_fees| -> StdResult<_> {
-            Ok(current_fees.add(fee_amount))
-        })?;
-        fund_amount = fund_amount.mul(Decimal::permille(1000 - fee));
-
-        let remaining_for_royalties = fund_amount;
-
-        // pay for creator, ai provider and others
-        if let Ok(royalties) = get_royalties(deps.as_ref(), contract_addr.as_str(), &token_id) {
-            pay_royalties(
-                &royalties,
-                &remaining_for_royalties,
-                decimal_point,
-                &mut fund_amount,
-                &mut cosmos_msgs,
-                &mut rsp,
-                env.contract.address.as_str(),
-                &to_json_binary(&asset_info)?.to_base64(),
-                asset_info.clone(),
-            )?;
+        cosmos_msgs.extend(pay_auction_proceeds(
+            deps, &env, &off, price, asset_info, &mut rsp,
+        )?);
+    };
+
+    // push save message to auction_storage
+    cosmos_msgs.push(governance.remove_auction(auction_id)?);
+
+    rsp = rsp.add_messages(cosmos_msgs);
+    rsp.attributes.extend(vec![
+        attr("claimer", info.sender),
+        attr("auction_id", auction_id.to_string()),
+        attr("total_price", price),
+    ]);
+
+    Ok(rsp)
+}
+
+/// fill the amount from the highest bids, every winner pays the lowest per price that got a share
+pub fn allocate_bids(bids: &[AuctionBid], amount: Uint128) -> (Option<Uint128>, Vec<Uint128>) {
+    let mut remaining = amount;
+    let mut clearing_price = None;
+    let allocations = bids
+        .iter()
+        .map(|bid| {
+            let allocated = bid.amount.min(remaining);
+            if !allocated.is_zero() {
+                clearing_price = Some(bid.per_price);
+            }
+            remaining -= allocated;
+            allocated
+        })
+        .collect();
+    (clearing_price, allocations)
+}
+
+/// bids of an auction, highest per price first then the earliest bid
+pub fn get_auction_bids(storage: &dyn Storage, auction_id: u64) -> StdResult<Vec<AuctionBid>> {
+    let mut bids = AUCTION_BIDS
+        .prefix(&auction_id.to_be_bytes())
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, bid)| bid))
+        .collect::<StdResult<Vec<AuctionBid>>>()?;
+    bids.sort_by(|a, b| b.per_price.cmp(&a.per_price).then(a.seq.cmp(&b.seq)));
+    Ok(bids)
+}
+
+/// send the nft shares at the clearing price, refund the rest of every deposit and pay the asker
+fn try_claim_multi_winner(
+    deps: DepsMut,
+    info: MessageInfo,
+    env: Env,
+    auction_id: u64,
+    off: Auction,
+) -> Result<Response, ContractError> {
+    let ContractInfo { governance, .. } = CONTRACT_INFO.load(deps.storage)?;
+
+    // there is no buyout, a multi winner auction is only settled once finished
+    if off.end.gt(&env.block.height) {
+        return Err(ContractError::AuctionNotFinished {});
+    }
+
+    let asker_addr = deps.api.addr_humanize(&off.asker)?;
+    let contract_addr = deps.api.addr_humanize(&off.contract_addr)?;
+    let asset_info = query_payment_auction_asset_info(
+        deps.as_ref(),
+        &governance,
+        contract_addr.clone(),
+        &off.token_id,
+        asker_addr.as_str(),
+    )?;
+
+    let mut rsp = Response::default();
+    rsp.attributes.extend(vec![attr("action", "claim_winner")]);
+    let mut cosmos_msgs = vec![];
+
+    let bids = get_auction_bids(deps.storage, auction_id)?;
+    let (clearing_price, allocations) = allocate_bids(&bids, off.amount);
+    let clearing_price = clearing_price.unwrap_or_default();
+    let mut filled_amount = Uint128::zero();
+    for (bid, allocated) in bids.into_iter().zip(allocations) {
+        AUCTION_BIDS.remove(
+            deps.storage,
+            (&auction_id.to_be_bytes(), bid.bidder.as_bytes()),
+        );
+        if !allocated.is_zero() {
+            cosmos_msgs.push(
+                WasmMsg::Execute {
+                    contract_addr: contract_addr.to_string(),
+                    msg: to_json_binary(&Cw1155ExecuteMsg::SendFrom {
+                        from: asker_addr.to_string(),
+                        to: bid.bidder.to_string(),
+                        value: allocated,
+                        token_id: off.token_id.clone(),
+                        msg: None,
+                    })?,
+                    funds: vec![],
+                }
+                .into(),
+            );
+            filled_amount += allocated;
         }
-        // send fund the asker
-        // only send when fund is greater than zero
-        if !fund_amount.is_zero() {
+        // losing bids get everything back, winning bids get back what is above the clearing price
+        let refund = bid
+            .deposit
+            .checked_sub(calculate_price(clearing_price, allocated))?;
+        if !refund.is_zero() {
             cosmos_msgs.push(parse_transfer_msg(
-                asset_info,
-                fund_amount,
+                asset_info.clone(),
+                refund,
                 env.contract.address.as_str(),
-                asker_addr,
+                bid.bidder,
             )?);
         }
-    };
+    }
 
-    // push save message to auction_storage
-    cosmos_msgs.push(get_handle_msg(
-        &governance,
-        AUCTION_STORAGE,
-        AuctionExecuteMsg::RemoveAuction { id: auction_id },
-    )?);
+    let price = calculate_price(clearing_price, filled_amount);
+    if !price.is_zero() {
+        cosmos_msgs.extend(pay_auction_proceeds(
+            deps, &env, &off, price, asset_info, &mut rsp,
+        )?);
+    }
+
+    cosmos_msgs.push(governance.remove_auction(auction_id)?);
 
     rsp = rsp.add_messages(cosmos_msgs);
     rsp.attributes.extend(vec![
         attr("claimer", info.sender),
         attr("auction_id", auction_id.to_string()),
         attr("total_price", price),
+        attr("clearing_price", clearing_price),
+        attr("filled_amount", filled_amount),
     ]);
 
     Ok(rsp)
 }
 
+/// collect the market fee, then pay the royalties and the asker with the rest of the auction price
+fn pay_auction_proceeds(
+    deps: DepsMut,
+    env: &Env,
+    off: &Auction,
+    price: Uint128,
+    asset_info: AssetInfo,
+    rsp: &mut Response,
+) -> Result<Vec<CosmosMsg>, ContractError> {
+    let ContractInfo {
+        fee, decimal_point, ..
+    } = CONTRACT_INFO.load(deps.storage)?;
+    let asker_addr = deps.api.addr_humanize(&off.asker)?;
+    let contract_addr = deps.api.addr_humanize(&off.contract_addr)?;
+    let mut cosmos_msgs = vec![];
+
+    let mut fund_amount = price;
+    // minus market fees
+    let fee_amount = price.mul(Decimal::permille(fee));
+    MARKET_FEES.update(deps.storage, |current_fees| -> StdResult<_> {
+        Ok(current_fees.add(fee_amount))
+    })?;
+    fund_amount = fund_amount.mul(Decimal::permille(1000 - fee));
+
+    let remaining_for_royalties = fund_amount;
+
+    // pay for creator, ai provider and others
+    if let Ok(royalties) = get_royalties(deps.as_ref(), contract_addr.as_str(), &off.token_id) {
+        pay_royalties(
+            &royalties,
+            &remaining_for_royalties,
+            decimal_point,
+            &mut fund_amount,
+            &mut cosmos_msgs,
+            rsp,
+            env.contract.address.as_str(),
+            &to_json_binary(&asset_info)?.to_base64(),
+            asset_info.clone(),
+        )?;
+    }
+    // send fund the asker
+    // only send when fund is greater than zero
+    if !fund_amount.is_zero() {
+        cosmos_msgs.push(parse_transfer_msg(
+            asset_info,
+            fund_amount,
+            env.contract.address.as_str(),
+            asker_addr,
+        )?);
+    }
+    Ok(cosmos_msgs)
+}
+
 pub fn handle_ask_auction(
     deps: DepsMut,
     info: MessageInfo,
@@ -331,6 +585,14 @@ pub fn handle_ask_auction(
         return Err(ContractError::InvalidBlockNumberArgument { start, end });
     }
 
+    let multi_winner = msg.multi_winner.unwrap_or(false);
+    // the clearing price is only known at the end, so there is nothing to buy out
+    if multi_winner && msg.buyout_per_price.is_some() {
+        return Err(ContractError::InvalidArgument {
+            arg: "buyout_per_price".to_string(),
+        });
+    }
+
     // save Auction, waiting for finished
     let off = Auction {
         id: None,
@@ -348,27 +610,24 @@ pub fn handle_ask_auction(
         end_timestamp,
         step_price: msg.step_price.unwrap_or(step_price),
         amount: msg.amount,
+        reserved_for: validate_reserved_for(deps.api, msg.reserved_for)?,
+        multi_winner,
     };
 
     // add new auctions
     let mut cosmos_msgs = vec![];
     // push save message to auction_storage
-    cosmos_msgs.push(get_handle_msg(
-        &governance,
-        AUCTION_STORAGE,
-        AuctionExecuteMsg::UpdateAuction { auction: off },
-    )?);
+    cosmos_msgs.push(governance.update_auction(off)?);
 
     // push save message to market payment storage
-    cosmos_msgs.push(get_handle_msg(
-        &governance,
-        PAYMENT_STORAGE,
-        PaymentExecuteMsg::UpdateAuctionPayment(Payment {
+    cosmos_msgs.push(governance.update_auction_payment(
+        PAYMENT_1155_STORAGE,
+        Payment {
             contract_addr: msg.contract_addr.clone(),
             token_id: token_id.clone(),
             sender: Some(info.sender.clone()),
             asset_info: asset_info.clone(),
-        }),
+        },
     )?);
 
     Ok(Response::new()
@@ -394,19 +653,16 @@ pub fn try_cancel_bid(
     let ContractInfo { governance, .. } = CONTRACT_INFO.load(deps.storage)?;
 
     // check if auction exists
-    let mut off: Auction = query_storage(
-        deps.as_ref(),
-        AUCTION_STORAGE,
-        AuctionQueryMsg::GetAuctionRaw { auction_id },
-    )
-    .map_err(|_| ContractError::AuctionNotFound {})?;
+    let mut off = governance
+        .get_auction_raw(&deps.querier, auction_id)
+        .map_err(|_| ContractError::AuctionNotFound {})?;
 
     let token_id = off.token_id.clone();
     // check if token_id is currently sold by the requesting address
     if let Some(bidder) = &off.bidder {
         let asset_info = query_payment_auction_asset_info(
             deps.as_ref(),
-            governance.addr().as_str(),
+            &governance,
             deps.api.addr_humanize(&off.contract_addr)?,
             &token_id,
             deps.api.addr_humanize(&off.asker)?.as_str(),
@@ -447,11 +703,7 @@ pub fn try_cancel_bid(
             off.bidder = None;
             off.per_price = off.orig_per_price;
             // push save message to auction_storage
-            cosmos_msgs.push(get_handle_msg(
-                &governance,
-                AUCTION_STORAGE,
-                AuctionExecuteMsg::UpdateAuction { auction: off },
-            )?);
+            cosmos_msgs.push(governance.update_auction(off)?);
 
             return Ok(Response::new()
                 .add_messages(cosmos_msgs)
@@ -487,16 +739,12 @@ pub fn try_emergency_cancel_auction(
     } = CONTRACT_INFO.load(deps.storage)?;
 
     // check if auction exists
-    let off: Auction = query_storage(
-        deps.as_ref(),
-        AUCTION_STORAGE,
-        AuctionQueryMsg::GetAuctionRaw { auction_id },
-    )?;
+    let off = governance.get_auction_raw(&deps.querier, auction_id)?;
 
     let token_id = off.token_id;
     let asset_info = query_payment_auction_asset_info(
         deps.as_ref(),
-        governance.addr().as_str(),
+        &governance,
         deps.api.addr_humanize(&off.contract_addr)?,
         &token_id,
         deps.api.addr_humanize(&off.asker)?.as_str(),
@@ -516,20 +764,30 @@ pub fn try_emergency_cancel_auction(
         let bidder_addr = deps.api.addr_humanize(&bidder)?;
         // transfer money to previous bidder
         cosmos_msgs.push(parse_transfer_msg(
-            asset_info,
+            asset_info.clone(),
             price,
             env.contract.address.as_str(),
             bidder_addr,
         )?);
     }
 
+    // refund the bid book of a multi winner auction
+    for bid in get_auction_bids(deps.storage, auction_id)? {
+        AUCTION_BIDS.remove(
+            deps.storage,
+            (&auction_id.to_be_bytes(), bid.bidder.as_bytes()),
+        );
+        cosmos_msgs.push(parse_transfer_msg(
+            asset_info.clone(),
+            bid.deposit,
+            env.contract.address.as_str(),
+            bid.bidder,
+        )?);
+    }
+
     // remove auction
     // push save message to auction_storage
-    cosmos_msgs.push(get_handle_msg(
-        &governance,
-        AUCTION_STORAGE,
-        AuctionExecuteMsg::RemoveAuction { id: auction_id },
-    )?);
+    cosmos_msgs.push(governance.remove_auction(auction_id)?);
 
     return Ok(Response::new()
         .add_messages(cosmos_msgs)
@@ -563,6 +821,21 @@ pub fn try_emergency_cancel_auction(
 //     .into())
 // }
 
+pub fn query_auction_bids(deps: Deps, auction_id: u64) -> StdResult<Vec<AuctionBid>> {
+    get_auction_bids(deps.storage, auction_id)
+}
+
+pub fn query_clearing_price(deps: Deps, auction_id: u64) -> StdResult<ClearingPriceResponse> {
+    let ContractInfo { governance, .. } = CONTRACT_INFO.load(deps.storage)?;
+    let off = governance.get_auction_raw(&deps.querier, auction_id)?;
+    let bids = get_auction_bids(deps.storage, auction_id)?;
+    let (clearing_price, allocations) = allocate_bids(&bids, off.amount);
+    Ok(ClearingPriceResponse {
+        clearing_price,
+        filled_amount: allocations.into_iter().sum(),
+    })
+}
+
 pub fn calculate_price(per_price: Uint128, amount: Uint128) -> Uint128 {
     return per_price.mul(Decimal::from_ratio(amount.u128(), 1u128));
 }
@@ -0,0 +1,426 @@
+use crate::auction::calculate_price;
+use crate::contract::{get_asset_info, get_sent_amount};
+use crate::error::ContractError;
+use crate::msg::{BookLevel, OrderBookResponse};
+use crate::offering::pay_seller;
+use crate::state::{
+    buy_orders, get_bid_price_id, get_contract_token_id, increment_buy_orders, BuyOrder,
+    ContractInfo, CONTRACT_INFO,
+};
+use cosmwasm_std::{
+    attr, to_json_binary, Addr, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Order, Response,
+    StdResult, Storage, Uint128, WasmMsg,
+};
+use cw1155::{Cw1155ExecuteMsg, Cw1155ReceiveMsg};
+use cw_storage_plus::{Bound, PkOwned};
+use market::{AssetInfo, Funds, PageResponse};
+use market_1155::{BuyOrderMsg, Market1155Storage, MarketQueryMsg, Offering};
+use market_ai_royalty::parse_transfer_msg;
+use market_whitelist::WhitelistStorage;
+
+const MAX_LIMIT: u8 = 100;
+const DEFAULT_LIMIT: u8 = 20;
+/// resting buy orders looked at when a sell offering is crossed with the book
+pub const MAX_MATCHED_BUY_ORDERS: usize = 30;
+
+/// escrow the price of the whole amount, the part sent above it is refunded
+pub fn try_create_buy_order(
+    deps: DepsMut,
+    buyer: Addr,
+    env: Env,
+    msg: BuyOrderMsg,
+    funds: Funds,
+    cw20_addr: Option<Addr>,
+) -> Result<Response, ContractError> {
+    let ContractInfo {
+        denom, governance, ..
+    } = CONTRACT_INFO.load(deps.storage)?;
+    let (asset_info, token_id) = get_asset_info(&msg.token_id, &denom)?;
+
+    if msg.amount.is_zero() || msg.per_price.is_zero() {
+        return Err(ContractError::InvalidZeroAmount {});
+    }
+    let is_approved = governance.is_approved_for_all(&deps.querier, msg.contract_addr.as_str())?;
+    if !is_approved.approved {
+        return Err(ContractError::NotWhilteList {
+            sender: buyer.to_string(),
+        });
+    }
+
+    let price = calculate_price(msg.per_price, msg.amount);
+    // only the payment asset of the order can be escrowed
+    let sent_amount = get_sent_amount(&funds, cw20_addr.as_ref(), &asset_info)?;
+    if sent_amount.lt(&price) {
+        return Err(ContractError::InsufficientFunds {});
+    }
+
+    let order_id = increment_buy_orders(deps.storage)?;
+    buy_orders().save(
+        deps.storage,
+        &order_id.to_be_bytes(),
+        &BuyOrder {
+            id: Some(order_id),
+            buyer: buyer.clone(),
+            contract_addr: msg.contract_addr.clone(),
+            token_id: token_id.clone(),
+            per_price: msg.per_price,
+            amount: msg.amount,
+            asset_info: asset_info.clone(),
+        },
+    )?;
+
+    let mut cosmos_msgs = vec![];
+    if sent_amount.gt(&price) {
+        cosmos_msgs.push(parse_transfer_msg(
+            asset_info,
+            sent_amount.checked_sub(price)?,
+            env.contract.address.as_str(),
+            buyer.clone(),
+        )?);
+    }
+
+    Ok(Response::new()
+        .add_messages(cosmos_msgs)
+        .add_attributes(vec![
+            attr("action", "create_buy_order"),
+            attr("buyer", buyer),
+            attr("order_id", order_id.to_string()),
+            attr("contract_addr", msg.contract_addr),
+            attr("token_id", token_id),
+            attr("amount", msg.amount),
+            attr("per_price", msg.per_price),
+        ]))
+}
+
+/// refund the funds left in escrow
+pub fn try_cancel_buy_order(
+    deps: DepsMut,
+    info: MessageInfo,
+    env: Env,
+    order_id: u64,
+) -> Result<Response, ContractError> {
+    let order = get_buy_order(deps.storage, order_id)?;
+    if order.buyer.ne(&info.sender) {
+        return Err(ContractError::Unauthorized {
+            sender: info.sender.to_string(),
+        });
+    }
+    buy_orders().remove(deps.storage, &order_id.to_be_bytes())?;
+
+    let mut cosmos_msgs = vec![];
+    let escrow = calculate_price(order.per_price, order.amount);
+    if !escrow.is_zero() {
+        cosmos_msgs.push(parse_transfer_msg(
+            order.asset_info,
+            escrow,
+            env.contract.address.as_str(),
+            order.buyer,
+        )?);
+    }
+
+    Ok(Response::new()
+        .add_messages(cosmos_msgs)
+        .add_attributes(vec![
+            attr("action", "cancel_buy_order"),
+            attr("buyer", info.sender),
+            attr("order_id", order_id.to_string()),
+        ]))
+}
+
+/// the holder sent the tokens to the marketplace, forward them to the buyer and pay the holder from the escrow
+pub fn try_fill_buy_order(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    rcv_msg: Cw1155ReceiveMsg,
+    order_id: u64,
+) -> Result<Response, ContractError> {
+    let mut order = get_buy_order(deps.storage, order_id)?;
+    // only the nft contract of the order can send its tokens
+    if info.sender.ne(&order.contract_addr) || rcv_msg.token_id.ne(&order.token_id) {
+        return Err(ContractError::Unauthorized {
+            sender: info.sender.to_string(),
+        });
+    }
+    if rcv_msg.amount.is_zero() {
+        return Err(ContractError::InvalidZeroAmount {});
+    }
+    if rcv_msg.amount.gt(&order.amount) {
+        return Err(ContractError::InsufficientAmount {});
+    }
+    let seller = Addr::unchecked(rcv_msg.from.unwrap_or(rcv_msg.operator));
+
+    let mut rsp = Response::default();
+    rsp.attributes
+        .extend(vec![attr("action", "fill_buy_order")]);
+    let mut cosmos_msgs = vec![send_nft_msg(
+        &order.contract_addr,
+        &env.contract.address,
+        &order.buyer,
+        &order.token_id,
+        rcv_msg.amount,
+    )?];
+    cosmos_msgs.extend(pay_seller(
+        deps.branch(),
+        &env,
+        &order.contract_addr,
+        &order.token_id,
+        calculate_price(order.per_price, rcv_msg.amount),
+        seller.clone(),
+        order.asset_info.clone(),
+        &mut rsp,
+    )?);
+
+    order.amount = order.amount.checked_sub(rcv_msg.amount)?;
+    update_buy_order(deps.storage, &order)?;
+
+    rsp = rsp.add_messages(cosmos_msgs);
+    rsp.attributes.extend(vec![
+        attr("seller", seller),
+        attr("buyer", order.buyer),
+        attr("order_id", order_id.to_string()),
+        attr("per_price", order.per_price),
+        attr("amount", rcv_msg.amount),
+    ]);
+    Ok(rsp)
+}
+
+/// cross a new sell offering with the resting buy orders paying at least its per price, best price first.
+/// At most MAX_MATCHED_BUY_ORDERS orders are looked at, the seller gets the per price of each buy order.
+/// Returns the amount left to offer
+pub fn match_buy_orders(
+    mut deps: DepsMut,
+    env: &Env,
+    off: &Offering,
+    asset_info: &AssetInfo,
+    rsp: &mut Response,
+) -> Result<(Uint128, Vec<CosmosMsg>), ContractError> {
+    // the lowest key at the offering per price, the walk stops there
+    let min = Bound::Inclusive(get_bid_price_id(off.per_price, u64::MAX).0);
+    let orders = token_buy_orders(
+        deps.storage,
+        &off.contract_addr,
+        &off.token_id,
+        asset_info,
+        Some(min),
+    )
+    .take(MAX_MATCHED_BUY_ORDERS)
+    .collect::<StdResult<Vec<BuyOrder>>>()?;
+
+    let mut remaining = off.amount;
+    let mut cosmos_msgs = vec![];
+    for mut order in orders {
+        if remaining.is_zero() {
+            break;
+        }
+        let can_buy = off
+            .reserved_for
+            .as_ref()
+            .is_none_or(|reserved_for| reserved_for.contains(&order.buyer));
+        if order.buyer.eq(&off.seller) || !can_buy {
+            continue;
+        }
+        let amount = order.amount.min(remaining);
+        remaining = remaining.checked_sub(amount)?;
+
+        cosmos_msgs.push(send_nft_msg(
+            &off.contract_addr,
+            &off.seller,
+            &order.buyer,
+            &off.token_id,
+            amount,
+        )?);
+        cosmos_msgs.extend(pay_seller(
+            deps.branch(),
+            env,
+            &off.contract_addr,
+            &off.token_id,
+            calculate_price(order.per_price, amount),
+            off.seller.clone(),
+            order.asset_info.clone(),
+            rsp,
+        )?);
+
+        order.amount = order.amount.checked_sub(amount)?;
+        update_buy_order(deps.storage, &order)?;
+        rsp.attributes.extend(vec![
+            attr(
+                "matched_buy_order",
+                order.id.unwrap_or_default().to_string(),
+            ),
+            attr("matched_amount", amount),
+        ]);
+    }
+    Ok((remaining, cosmos_msgs))
+}
+
+fn send_nft_msg(
+    contract_addr: &Addr,
+    from: &Addr,
+    to: &Addr,
+    token_id: &str,
+    value: Uint128,
+) -> StdResult<CosmosMsg> {
+    Ok(WasmMsg::Execute {
+        contract_addr: contract_addr.to_string(),
+        msg: to_json_binary(&Cw1155ExecuteMsg::SendFrom {
+            from: from.to_string(),
+            to: to.to_string(),
+            token_id: token_id.to_string(),
+            value,
+            msg: None,
+        })?,
+        funds: vec![],
+    }
+    .into())
+}
+
+fn get_buy_order(storage: &dyn Storage, order_id: u64) -> Result<BuyOrder, ContractError> {
+    buy_orders()
+        .load(storage, &order_id.to_be_bytes())
+        .map_err(|_| ContractError::BuyOrderNotFound {})
+}
+
+/// a fully filled order is removed
+fn update_buy_order(storage: &mut dyn Storage, order: &BuyOrder) -> StdResult<()> {
+    let key = order.id.unwrap_or_default().to_be_bytes();
+    if order.amount.is_zero() {
+        buy_orders().remove(storage, &key)
+    } else {
+        buy_orders().save(storage, &key, order)
+    }
+}
+
+/// the buy orders of a token paid in the asset, from the best price then the oldest order
+fn token_buy_orders<'c>(
+    storage: &'c dyn Storage,
+    contract_addr: &Addr,
+    token_id: &str,
+    asset_info: &AssetInfo,
+    min: Option<Bound>,
+) -> impl Iterator<Item = StdResult<BuyOrder>> + 'c {
+    buy_orders()
+        .idx
+        .contract_token_id_price
+        .prefix((
+            PkOwned(get_contract_token_id(contract_addr, token_id)),
+            PkOwned(asset_info.as_key()),
+        ))
+        .range(storage, min, None, Order::Descending)
+        .map(|item| item.map(|(_, order)| order))
+}
+
+// ============================== Query Handlers ==============================
+
+pub fn query_buy_order(deps: Deps, order_id: u64) -> StdResult<BuyOrder> {
+    buy_orders().load(deps.storage, &order_id.to_be_bytes())
+}
+
+pub fn query_buy_orders_by_buyer(
+    deps: Deps,
+    buyer: Addr,
+    offset: Option<u64>,
+    limit: Option<u8>,
+    order: Option<u8>,
+) -> StdResult<Vec<BuyOrder>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let mut min = None;
+    let mut max = None;
+    let order_enum = match order {
+        Some(1) => Order::Ascending,
+        _ => Order::Descending,
+    };
+    if let Some(offset) = offset {
+        let offset_value = Some(Bound::Exclusive(offset.to_be_bytes().to_vec()));
+        match order_enum {
+            Order::Ascending => min = offset_value,
+            Order::Descending => max = offset_value,
+        }
+    };
+    buy_orders()
+        .idx
+        .buyer
+        .items(deps.storage, buyer.as_bytes(), min, max, order_enum)
+        .take(limit)
+        .map(|item| item.map(|(_, order)| order))
+        .collect()
+}
+
+/// the token id is the initial token id, whose payment asset selects the orders of the book
+pub fn query_order_book(
+    deps: Deps,
+    contract_addr: Addr,
+    token_id: String,
+    limit: Option<u8>,
+) -> StdResult<OrderBookResponse> {
+    let ContractInfo {
+        denom, governance, ..
+    } = CONTRACT_INFO.load(deps.storage)?;
+    let (asset_info, token_id) = get_asset_info(&token_id, &denom)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+    let bids = to_book_levels(
+        token_buy_orders(deps.storage, &contract_addr, &token_id, &asset_info, None)
+            .map(|item| item.map(|order| (order.per_price, order.amount))),
+        limit,
+    )?;
+
+    // the cheapest offerings in the asset of the book
+    let offerings: PageResponse<Offering> = governance.query_market_1155(
+        &deps.querier,
+        MarketQueryMsg::GetOfferingsByPriceRange {
+            contract: contract_addr,
+            token_id,
+            min_price: None,
+            max_price: None,
+            asset_info: Some(asset_info),
+            start_after: None,
+            limit: Some(MAX_LIMIT as u32),
+            order: None,
+            with_total: None,
+        },
+    )?;
+    // reserved offerings cannot be bought by everyone, so they are not part of the book
+    let asks = to_book_levels(
+        offerings
+            .items
+            .into_iter()
+            .filter(|off| off.reserved_for.is_none())
+            .map(|off| Ok((off.per_price, off.amount))),
+        limit,
+    )?;
+    Ok(OrderBookResponse {
+        best_bid: bids.first().map(|level| level.per_price),
+        best_ask: asks.first().map(|level| level.per_price),
+        bids,
+        asks,
+    })
+}
+
+/// group the (per price, amount) orders, already sorted from the best price, by per price
+fn to_book_levels(
+    orders: impl Iterator<Item = StdResult<(Uint128, Uint128)>>,
+    limit: usize,
+) -> StdResult<Vec<BookLevel>> {
+    let mut levels: Vec<BookLevel> = vec![];
+    for item in orders {
+        let (per_price, amount) = item?;
+        match levels.last_mut() {
+            Some(level) if level.per_price == per_price => {
+                level.amount += amount;
+                level.count += 1;
+            }
+            _ => {
+                if levels.len() == limit {
+                    break;
+                }
+                levels.push(BookLevel {
+                    per_price,
+                    amount,
+                    count: 1,
+                });
+            }
+        }
+    }
+    Ok(levels)
+}
@@ -2,51 +2,44 @@
 use cosmwasm_std::entry_point;
 
 use crate::auction::{
-    handle_ask_auction, try_bid_nft, try_cancel_bid, try_claim_winner,
-    try_emergency_cancel_auction, AUCTION_STORAGE,
+    handle_ask_auction, query_auction_bids, query_clearing_price, try_bid_nft, try_bid_nft_amount,
+    try_cancel_bid, try_claim_winner, try_emergency_cancel_auction,
+};
+use crate::buy_order::{
+    query_buy_order, query_buy_orders_by_buyer, query_order_book, try_cancel_buy_order,
+    try_create_buy_order, try_fill_buy_order,
 };
 use crate::offering::{
     try_burn, try_buy, try_change_creator, try_handle_mint, try_handle_transfer_directly,
     try_sell_nft, try_withdraw,
 };
-use std::fmt;
 
 use crate::error::ContractError;
-use crate::msg::{
-    ExecuteMsg, InstantiateMsg, MigrateMsg, ProxyExecuteMsg, ProxyQueryMsg, QueryMsg,
-    UpdateContractMsg,
-};
-use crate::state::{ContractInfo, CONTRACT_INFO, MARKET_FEES};
+use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, ReceiveMsg, UpdateContractMsg};
+use crate::state::{ContractInfo, CONTRACT_INFO, CONTRACT_INFO_KEY, MARKET_FEES};
 use cosmwasm_std::{
-    attr, to_json_binary, BankMsg, Binary, Coin, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo,
+    attr, to_json_binary, BankMsg, Binary, Coin, CosmosMsg, Deps, DepsMut, Env, MessageInfo,
     Response, StdError, StdResult, Uint128,
 };
 use cosmwasm_std::{from_json, Addr};
-use cw1155::{BalanceResponse, Cw1155QueryMsg, IsApprovedForAllResponse};
+use cw1155::{BalanceResponse, Cw1155QueryMsg, Cw1155ReceiveMsg, IsApprovedForAllResponse};
+use cw2::set_contract_version;
 use cw20::Cw20ReceiveMsg;
 use market::{
-    parse_token_id, query_proxy, AssetInfo, Funds, MarketHubContract, StorageQueryMsg, TokenInfo,
+    parse_token_id, AssetInfo, Funds, MarketHubContract, TokenInfo, PAYMENT_1155_STORAGE,
 };
-use market_1155::{Cw20HookMsg, ExtraData, MarketQueryMsg, Offering};
-use market_ai_royalty::{AiRoyaltyQueryMsg, Royalty};
-use market_auction_extend::{AuctionQueryMsg, QueryAuctionsResult};
-use market_payment::PaymentQueryMsg;
-use market_rejected::{IsRejectedForAllResponse, MarketRejectedQueryMsg, NftInfo};
-use market_whitelist::{
-    IsApprovedForAllResponse as IsApprovedForAllResponseWhiteList, MarketWhiteListdQueryMsg,
-};
-use schemars::JsonSchema;
-use serde::{de::DeserializeOwned, Serialize};
+use market_1155::{Cw1155HookMsg, Cw20HookMsg, ExtraData, Market1155Storage};
+use market_ai_royalty::{AiRoyaltyStorage, Royalty};
+use market_auction_extend::AuctionExtendStorage;
+use market_payment::PaymentStorage;
+use market_rejected::{NftInfo, RejectedStorage, Rejection};
+use market_whitelist::WhitelistStorage;
+use migration::{migrate_version, move_item, MigrationStep, VERSION_KEY};
 
 pub const MAX_ROYALTY_PERCENT: u64 = 1_000_000_000;
 pub const MAX_DECIMAL_POINT: u64 = 1_000_000_000;
 pub const MAX_FEE_PERMILLE: u64 = 1000;
 pub const EXPIRED_BLOCK_RANGE: u64 = 50000;
-pub const STORAGE_1155: &str = "1155_storage";
-pub const AI_ROYALTY_STORAGE: &str = "ai_royalty";
-pub const REJECTED_STORAGE: &str = "rejected_storage";
-pub const WHITELIST_STORAGE: &str = "whitelist_storage";
-pub const PAYMENT_STORAGE: &str = "market_1155_payment_storage";
 pub const CREATOR_NAME: &str = "creator";
 
 fn sanitize_fee(fee: u64, name: &str) -> Result<u64, ContractError> {
@@ -58,6 +51,10 @@ fn sanitize_fee(fee: u64, name: &str) -> Result<u64, ContractError> {
     Ok(fee)
 }
 
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:market_1155_implementation";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
 // Note, you can use StdResult in some functions where you do not
 // make use of the custom errors
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -67,6 +64,7 @@ pub fn instantiate(
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     let info = ContractInfo {
         name: msg.name,
         creator: info.sender.to_string(),
@@ -92,7 +90,8 @@ pub fn execute(
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
     match msg {
-        ExecuteMsg::Receive(msg) => try_receive_cw20(deps, info, env, msg),
+        ExecuteMsg::Receive(ReceiveMsg::Cw20(msg)) => try_receive_cw20(deps, info, env, msg),
+        ExecuteMsg::Receive(ReceiveMsg::Cw1155(msg)) => try_receive_cw1155(deps, info, env, msg),
         ExecuteMsg::SellNft(msg) => try_sell_nft(deps, info, env, msg),
         ExecuteMsg::WithdrawFunds { funds } => try_withdraw_funds(deps, info, env, funds),
         ExecuteMsg::UpdateInfo(msg) => try_update_info(deps, info, env, msg),
@@ -130,6 +129,14 @@ pub fn execute(
             // None,
             // Some(info.funds),
         ),
+        ExecuteMsg::BidNftAmount(msg) => try_bid_nft_amount(
+            deps,
+            info.sender,
+            env,
+            msg,
+            Funds::Native { fund: info.funds },
+            None,
+        ),
         ExecuteMsg::ClaimWinner { auction_id } => try_claim_winner(deps, info, env, auction_id),
         // ExecuteMsg::WithdrawNft { auction_id } => try_withdraw_nft(deps, info, env, auction_id),
         ExecuteMsg::EmergencyCancelAuction { auction_id } => {
@@ -143,13 +150,31 @@ pub fn execute(
             to,
         } => try_change_creator(deps, info, env, contract_addr, token_id, to),
         ExecuteMsg::TransferNftDirectly(msg) => try_handle_transfer_directly(deps, info, env, msg),
+        ExecuteMsg::CreateBuyOrder(msg) => try_create_buy_order(
+            deps,
+            info.sender,
+            env,
+            msg,
+            Funds::Native { fund: info.funds },
+            None,
+        ),
+        ExecuteMsg::CancelBuyOrder { order_id } => try_cancel_buy_order(deps, info, env, order_id),
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
-    // MARKET_FEES.save(deps.storage, &Uint128::zero())?;
-    Ok(Response::default())
+pub fn migrate(deps: DepsMut, env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+    migrate_version(deps, &env, CONTRACT_NAME, CONTRACT_VERSION, MIGRATIONS)
+}
+
+const MIGRATIONS: &[MigrationStep] = &[MigrationStep {
+    version: "0.2.0",
+    name: "move_contract_info",
+    run: move_contract_info,
+}];
+
+fn move_contract_info(deps: DepsMut, _env: &Env) -> StdResult<()> {
+    move_item(deps.storage, VERSION_KEY, CONTRACT_INFO_KEY)
 }
 
 // ============================== Query Handlers ==============================
@@ -159,11 +184,35 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::GetContractInfo {} => to_json_binary(&query_contract_info(deps)?),
         QueryMsg::GetMarketFees {} => to_json_binary(&query_market_fees(deps)?),
-        QueryMsg::Offering(msg) => query_storage_binary(deps, STORAGE_1155, msg),
+        QueryMsg::Offering(msg) => {
+            query_governance(deps)?.query_market_1155_binary(&deps.querier, msg)
+        }
         QueryMsg::AiRoyalty(ai_royalty_msg) => {
-            query_storage_binary(deps, AI_ROYALTY_STORAGE, ai_royalty_msg)
+            query_governance(deps)?.query_ai_royalty_binary(&deps.querier, ai_royalty_msg)
         }
-        QueryMsg::Auction(auction) => query_storage_binary(deps, AUCTION_STORAGE, auction),
+        QueryMsg::Auction(auction) => {
+            query_governance(deps)?.query_auction_binary(&deps.querier, auction)
+        }
+        QueryMsg::GetAuctionBids { auction_id } => {
+            to_json_binary(&query_auction_bids(deps, auction_id)?)
+        }
+        QueryMsg::GetClearingPrice { auction_id } => {
+            to_json_binary(&query_clearing_price(deps, auction_id)?)
+        }
+        QueryMsg::GetBuyOrder { order_id } => to_json_binary(&query_buy_order(deps, order_id)?),
+        QueryMsg::GetBuyOrdersByBuyer {
+            buyer,
+            offset,
+            limit,
+            order,
+        } => to_json_binary(&query_buy_orders_by_buyer(
+            deps, buyer, offset, limit, order,
+        )?),
+        QueryMsg::GetOrderBook {
+            contract_addr,
+            token_id,
+            limit,
+        } => to_json_binary(&query_order_book(deps, contract_addr, token_id, limit)?),
     }
 }
 
@@ -171,7 +220,7 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
 
 pub fn try_receive_cw20(
     deps: DepsMut,
-    _info: MessageInfo,
+    info: MessageInfo,
     env: Env,
     cw20_msg: Cw20ReceiveMsg,
 ) -> Result<Response, ContractError> {
@@ -206,12 +255,48 @@ pub fn try_receive_cw20(
                 fund: cw20_msg.amount,
             },
         ),
+        Ok(Cw20HookMsg::BidNftAmount(msg)) => try_bid_nft_amount(
+            deps,
+            Addr::unchecked(cw20_msg.sender),
+            env,
+            msg,
+            Funds::Cw20 {
+                fund: cw20_msg.amount,
+            },
+            Some(info.sender),
+        ),
+        Ok(Cw20HookMsg::CreateBuyOrder(msg)) => try_create_buy_order(
+            deps,
+            Addr::unchecked(cw20_msg.sender),
+            env,
+            msg,
+            Funds::Cw20 {
+                fund: cw20_msg.amount,
+            },
+            Some(info.sender),
+        ),
         Err(_) => Err(ContractError::Std(StdError::generic_err(
             "invalid cw20 hook message",
         ))),
     }
 }
 
+pub fn try_receive_cw1155(
+    deps: DepsMut,
+    info: MessageInfo,
+    env: Env,
+    rcv_msg: Cw1155ReceiveMsg,
+) -> Result<Response, ContractError> {
+    match from_json(&rcv_msg.msg) {
+        Ok(Cw1155HookMsg::FillBuyOrder { order_id }) => {
+            try_fill_buy_order(deps, env, info, rcv_msg, order_id)
+        }
+        Err(_) => Err(ContractError::Std(StdError::generic_err(
+            "invalid cw1155 hook message",
+        ))),
+    }
+}
+
 pub fn try_withdraw_funds(
     deps: DepsMut,
     _info: MessageInfo,
@@ -358,6 +443,27 @@ pub fn verify_funds(
     };
 }
 
+/// the amount sent in the payment asset. Deposits are refunded later in this asset, so the funds
+/// cannot hold other coins nor come from another cw20
+pub fn get_sent_amount(
+    funds: &Funds,
+    cw20_addr: Option<&Addr>,
+    asset_info: &AssetInfo,
+) -> Result<Uint128, ContractError> {
+    match (funds, asset_info) {
+        (Funds::Native { fund }, AssetInfo::NativeToken { denom }) => match fund.as_slice() {
+            [coin] if coin.denom.eq(denom) => Ok(coin.amount),
+            _ => Err(ContractError::InvalidDenomAmount {}),
+        },
+        (Funds::Cw20 { fund }, AssetInfo::Token { contract_addr })
+            if cw20_addr == Some(contract_addr) =>
+        {
+            Ok(*fund)
+        }
+        _ => Err(ContractError::InvalidSentFundAmount {}),
+    }
+}
+
 pub fn query_contract_info(deps: Deps) -> StdResult<ContractInfo> {
     CONTRACT_INFO.load(deps.storage)
 }
@@ -366,51 +472,8 @@ pub fn query_market_fees(deps: Deps) -> StdResult<Uint128> {
     MARKET_FEES.load(deps.storage)
 }
 
-// remove recursive by query storage_addr first, then call query_proxy
-pub fn get_storage_addr(deps: Deps, contract: Addr, name: &str) -> StdResult<Addr> {
-    deps.querier.query_wasm_smart(
-        contract,
-        &ProxyQueryMsg::<Empty>::Storage(StorageQueryMsg::QueryStorageAddr {
-            name: name.to_string(),
-        }),
-    )
-}
-
-pub fn get_handle_msg<T>(addr: &MarketHubContract, name: &str, msg: T) -> StdResult<CosmosMsg>
-where
-    T: Clone + fmt::Debug + PartialEq + JsonSchema + Serialize,
-{
-    let binary_msg = to_json_binary(&ProxyExecuteMsg::Msg(msg))?;
-    addr.update_storage(name.to_string(), binary_msg)
-}
-
-pub fn query_storage<
-    U: DeserializeOwned,
-    T: Clone + fmt::Debug + PartialEq + JsonSchema + Serialize,
->(
-    deps: Deps,
-    storage_name: &str,
-    msg: T,
-) -> StdResult<U> {
-    let ContractInfo { governance, .. } = CONTRACT_INFO.load(deps.storage)?;
-    governance.query_storage(
-        storage_name.to_string(),
-        to_json_binary(&ProxyQueryMsg::Msg(msg))?,
-        &deps.querier,
-    )
-}
-
-pub fn query_storage_binary<T: Clone + fmt::Debug + PartialEq + JsonSchema + Serialize>(
-    deps: Deps,
-    name: &str,
-    msg: T,
-) -> StdResult<Binary> {
-    let ContractInfo { governance, .. } = CONTRACT_INFO.load(deps.storage)?;
-    query_proxy(
-        deps,
-        get_storage_addr(deps, governance.addr(), name)?,
-        to_json_binary(&ProxyQueryMsg::Msg(msg))?,
-    )
+fn query_governance(deps: Deps) -> StdResult<MarketHubContract> {
+    Ok(CONTRACT_INFO.load(deps.storage)?.governance)
 }
 
 pub fn get_royalties(
@@ -418,20 +481,16 @@ pub fn get_royalties(
     contract_addr: &str,
     token_id: &str,
 ) -> Result<Vec<Royalty>, ContractError> {
-    let royalties: Vec<Royalty> = query_storage(
-        deps,
-        AI_ROYALTY_STORAGE,
-        AiRoyaltyQueryMsg::GetRoyaltiesContractTokenId {
-            contract_addr: Addr::unchecked(contract_addr),
+    let royalties = query_governance(deps)?
+        .get_royalties_contract_token_id(
+            &deps.querier,
+            Addr::unchecked(contract_addr),
+            token_id,
+            Some(30),
+        )
+        .map_err(|_| ContractError::InvalidGetRoyaltiesTokenId {
             token_id: token_id.to_string(),
-            offset: None,
-            limit: Some(30),
-            order: Some(1),
-        },
-    )
-    .map_err(|_| ContractError::InvalidGetRoyaltiesTokenId {
-        token_id: token_id.to_string(),
-    })?;
+        })?;
     Ok(royalties)
 }
 
@@ -441,16 +500,14 @@ pub fn get_royalty(
     token_id: &str,
     creator: &str,
 ) -> Result<Royalty, ContractError> {
-    let royalty: Royalty = query_storage(
-        deps,
-        AI_ROYALTY_STORAGE,
-        AiRoyaltyQueryMsg::GetRoyalty {
-            contract_addr: Addr::unchecked(contract_addr),
-            token_id: token_id.to_string(),
-            creator: Addr::unchecked(creator),
-        },
-    )
-    .map_err(|_| ContractError::Std(StdError::generic_err("Invalid get unique royalty")))?;
+    let royalty = query_governance(deps)?
+        .get_royalty(
+            &deps.querier,
+            Addr::unchecked(contract_addr),
+            token_id,
+            Addr::unchecked(creator),
+        )
+        .map_err(|_| ContractError::Std(StdError::generic_err("Invalid get unique royalty")))?;
     Ok(royalty)
 }
 
@@ -464,7 +521,7 @@ pub fn verify_nft(
     amount: Option<Uint128>,
 ) -> Result<String, ContractError> {
     // get unique offering. Dont allow a seller to sell when he's already selling
-
+    let governance = query_governance(deps)?;
     let mut final_seller: String = owner.to_string();
 
     // if there's input seller => must check if the seller approves the info sender to sell the nft
@@ -486,31 +543,33 @@ pub fn verify_nft(
     }
 
     // verify if the final seller is in black list or not
-    let is_rejected: IsRejectedForAllResponse = query_storage(
-        deps,
-        REJECTED_STORAGE,
-        MarketRejectedQueryMsg::IsRejectedForAll {
-            nft_info: NftInfo {
-                contract_addr: contract_addr.to_string(),
-                token_id: token_id.to_string(),
-            },
+    let is_rejected = governance.is_rejected_for_all(
+        &deps.querier,
+        NftInfo {
+            contract_addr: contract_addr.to_string(),
+            token_id: token_id.to_string(),
         },
     )?;
 
     if is_rejected.rejected {
+        // legacy rejects carry no details
+        let reason = match is_rejected.rejection {
+            Some(Rejection {
+                reason,
+                note: Some(note),
+                ..
+            }) => format!("{} ({})", reason, note),
+            Some(Rejection { reason, .. }) => reason.to_string(),
+            None => "unspecified".to_string(),
+        };
         return Err(ContractError::Rejected {
             sender: final_seller.to_string(),
+            reason,
         });
     }
 
     // verify if the nft contract address is whitelisted. If not => reject
-    let is_approved: IsApprovedForAllResponseWhiteList = query_storage(
-        deps,
-        WHITELIST_STORAGE,
-        MarketWhiteListdQueryMsg::IsApprovedForAll {
-            nft_addr: contract_addr.to_string(),
-        },
-    )?;
+    let is_approved = governance.is_approved_for_all(&deps.querier, contract_addr)?;
 
     if !is_approved.approved {
         return Err(ContractError::NotWhilteList {
@@ -532,16 +591,14 @@ pub fn verify_nft(
         });
     }
 
-    let offering: Option<Offering> = query_storage(
-        deps,
-        STORAGE_1155,
-        MarketQueryMsg::GetUniqueOffering {
-            contract: Addr::unchecked(contract_addr),
-            token_id: token_id.to_string(),
-            seller: Addr::unchecked(final_seller.as_str()),
-        },
-    )
-    .ok();
+    let offering = governance
+        .get_unique_offering(
+            &deps.querier,
+            Addr::unchecked(contract_addr),
+            token_id,
+            Addr::unchecked(final_seller.as_str()),
+        )
+        .ok();
 
     if offering.is_some() {
         return Err(ContractError::TokenOnSale {
@@ -551,16 +608,14 @@ pub fn verify_nft(
 
     // check if auction exists
     // get unique offering. Dont allow a seller to sell when he's already selling
-    let auction: Option<QueryAuctionsResult> = query_storage(
-        deps,
-        AUCTION_STORAGE,
-        AuctionQueryMsg::GetUniqueAuction {
-            contract: Addr::unchecked(contract_addr),
-            token_id: token_id.to_string(),
-            asker: Addr::unchecked(final_seller.as_str()),
-        },
-    )
-    .ok();
+    let auction = governance
+        .get_unique_auction(
+            &deps.querier,
+            Addr::unchecked(contract_addr),
+            token_id,
+            Addr::unchecked(final_seller.as_str()),
+        )
+        .ok();
 
     if auction.is_some() {
         return Err(ContractError::TokenOnAuction {});
@@ -591,36 +646,34 @@ pub fn verify_nft(
 
 pub fn query_payment_auction_asset_info(
     deps: Deps,
-    governance: &str,
+    governance: &MarketHubContract,
     contract_addr: Addr,
     token_id: &str,
     asker: &str,
 ) -> StdResult<AssetInfo> {
     // collect payment type
-    Ok(deps.querier.query_wasm_smart(
-        get_storage_addr(deps, Addr::unchecked(governance), PAYMENT_STORAGE)?,
-        &ProxyQueryMsg::Msg(PaymentQueryMsg::GetAuctionPayment {
-            contract_addr,
-            token_id: token_id.into(),
-            sender: Some(Addr::unchecked(asker)),
-        }),
-    )?)
+    governance.get_auction_payment(
+        &deps.querier,
+        PAYMENT_1155_STORAGE,
+        contract_addr,
+        token_id,
+        Some(Addr::unchecked(asker)),
+    )
 }
 
 pub fn query_payment_offering_asset_info(
     deps: Deps,
-    governance: &str,
+    governance: &MarketHubContract,
     contract_addr: Addr,
     token_id: &str,
     seller: &str,
 ) -> StdResult<AssetInfo> {
     // collect payment type
-    Ok(deps.querier.query_wasm_smart(
-        get_storage_addr(deps, Addr::unchecked(governance), PAYMENT_STORAGE)?,
-        &ProxyQueryMsg::Msg(PaymentQueryMsg::GetOfferingPayment {
-            contract_addr,
-            token_id: token_id.into(),
-            sender: Some(Addr::unchecked(seller)),
-        }),
-    )?)
+    governance.get_offering_payment(
+        &deps.querier,
+        PAYMENT_1155_STORAGE,
+        contract_addr,
+        token_id,
+        Some(Addr::unchecked(seller)),
+    )
 }
@@ -1,4 +1,5 @@
 use cosmwasm_std::{StdError, Uint128};
+use market::ReservedError;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -14,8 +15,8 @@ pub enum ContractError {
 
     #[error("Unauthorized data hub implementation with sender: {sender}")]
     Unauthorized { sender: String },
-    #[error("Rejected data hub implementation with sender: {sender}. The sender is in marketplace black list")]
-    Rejected { sender: String },
+    #[error("Rejected data hub implementation with sender: {sender}. The sender is in marketplace black list, reason: {reason}")]
+    Rejected { sender: String, reason: String },
     #[error("Rejected data hub implementation with sender: {sender}. The nft contract is not whitelisted. Cannot use it on the marketplace")]
     NotWhilteList { sender: String },
     // Add any other custom errors you like here.
@@ -105,6 +106,18 @@ pub enum ContractError {
 
     #[error("Expected bidder: {bidder}, got: {sender}")]
     InvalidBidder { bidder: String, sender: String },
+
+    #[error("{0}")]
+    Reserved(#[from] ReservedError),
+
+    #[error("The bid does not match the auction type")]
+    InvalidAuctionBid {},
+
+    #[error("The auction already has {max} bids, replace yours or outbid the lowest one")]
+    TooManyBids { max: usize },
+
+    #[error("Cannot find the given buy order")]
+    BuyOrderNotFound {},
 }
 
 impl Into<String> for ContractError {
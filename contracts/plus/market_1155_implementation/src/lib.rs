@@ -1,4 +1,5 @@
 pub mod auction;
+pub mod buy_order;
 pub mod contract;
 pub mod msg;
 pub mod offering;
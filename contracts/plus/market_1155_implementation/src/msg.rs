@@ -1,13 +1,18 @@
 use std::fmt;
 
+use cosmwasm_schema::QueryResponses;
 use cosmwasm_std::{Coin, Empty, Addr, Uint128};
+use cw1155::Cw1155ReceiveMsg;
 use cw20::Cw20ReceiveMsg;
 use market::{StorageExecuteMsg, StorageQueryMsg};
-use market_1155::{MarketQueryMsg, MintMsg};
-use market_ai_royalty::AiRoyaltyQueryMsg;
-use market_auction_extend::AuctionQueryMsg;
+use market_1155::{BidAmountMsg, BuyOrderMsg, MarketQueryMsg, MarketQueryResponse, MintMsg};
+use market_ai_royalty::{AiRoyaltyQueryMsg, AiRoyaltyQueryResponse};
+use market_auction_extend::{AuctionQueryMsg, AuctionQueryResponse};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+
+use crate::state::{AuctionBid, BuyOrder, ContractInfo};
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InstantiateMsg {
     pub name: String,
@@ -24,7 +29,7 @@ pub struct MigrateMsg {}
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
-    Receive(Cw20ReceiveMsg),
+    Receive(ReceiveMsg),
     // Ask an NFT for a minimum price, must pay fee for auction maketplace
     SellNft(SellNft),
 
@@ -59,6 +64,8 @@ pub enum ExecuteMsg {
         auction_id: u64,
         per_price: Uint128,
     },
+    /// bid for a part of a multi winner auction, replacing the previous bid of the sender
+    BidNftAmount(BidAmountMsg),
     ClaimWinner {
         auction_id: u64,
     },
@@ -67,6 +74,19 @@ pub enum ExecuteMsg {
     },
     AskAuctionNft(AskNftMsg),
     TransferNftDirectly(TransferNftDirectlyMsg),
+    /// escrow the funds to buy an amount of a token, filled by holders or by new sell offerings
+    CreateBuyOrder(BuyOrderMsg),
+    CancelBuyOrder {
+        order_id: u64,
+    },
+}
+
+/// cw1155 and cw20 both send their hook under the `receive` variant
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(untagged)]
+pub enum ReceiveMsg {
+    Cw1155(Cw1155ReceiveMsg),
+    Cw20(Cw20ReceiveMsg),
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -84,6 +104,10 @@ pub struct AskNftMsg {
     pub buyout_per_price: Option<Uint128>,
     pub step_price: Option<u64>,
     pub asker: Option<Addr>,
+    /// only these addresses can bid, anyone when None
+    pub reserved_for: Option<Vec<Addr>>,
+    /// sell the amount to the top bids at a uniform clearing price instead of to a single bidder
+    pub multi_winner: Option<bool>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -94,6 +118,8 @@ pub struct SellNft {
     pub token_id: String,
     pub amount: Uint128,
     pub seller: Option<Addr>,
+    /// only these addresses can buy the nft, anyone when None
+    pub reserved_for: Option<Vec<Addr>>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -116,15 +142,74 @@ pub struct UpdateContractMsg {
     pub decimal_point: Option<u64>,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, QueryResponses)]
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
     // Auction info must be queried from auction contract
+    #[returns(ContractInfo)]
     GetContractInfo {},
+    #[returns(Uint128)]
     GetMarketFees {},
+    #[returns(MarketQueryResponse)]
     Offering(MarketQueryMsg),
+    #[returns(AiRoyaltyQueryResponse)]
     AiRoyalty(AiRoyaltyQueryMsg),
+    #[returns(AuctionQueryResponse)]
     Auction(AuctionQueryMsg),
+    /// bids of a multi winner auction, from the highest priority to the lowest
+    #[returns(Vec<AuctionBid>)]
+    GetAuctionBids {
+        auction_id: u64,
+    },
+    #[returns(ClearingPriceResponse)]
+    GetClearingPrice {
+        auction_id: u64,
+    },
+    #[returns(BuyOrder)]
+    GetBuyOrder {
+        order_id: u64,
+    },
+    #[returns(Vec<BuyOrder>)]
+    GetBuyOrdersByBuyer {
+        buyer: Addr,
+        offset: Option<u64>,
+        limit: Option<u8>,
+        order: Option<u8>,
+    },
+    /// price levels of the buy orders and the sell offerings of a token, in the payment asset of the initial token id
+    #[returns(OrderBookResponse)]
+    GetOrderBook {
+        contract_addr: Addr,
+        token_id: String,
+        limit: Option<u8>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ClearingPriceResponse {
+    /// per price paid by every winner, None when there is no bid yet
+    pub clearing_price: Option<Uint128>,
+    /// amount sold if the auction ended now
+    pub filled_amount: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BookLevel {
+    pub per_price: Uint128,
+    /// total amount at this per price
+    pub amount: Uint128,
+    /// number of orders at this per price
+    pub count: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OrderBookResponse {
+    pub best_bid: Option<Uint128>,
+    pub best_ask: Option<Uint128>,
+    /// buy orders, highest per price first
+    pub bids: Vec<BookLevel>,
+    /// sell offerings, lowest per price first
+    pub asks: Vec<BookLevel>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
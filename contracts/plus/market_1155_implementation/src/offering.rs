@@ -1,7 +1,7 @@
+use crate::buy_order::match_buy_orders;
 use crate::contract::{
-    get_asset_info, get_handle_msg, get_royalties, get_royalty, query_payment_offering_asset_info,
-    query_storage, verify_funds, verify_nft, AI_ROYALTY_STORAGE, CREATOR_NAME, PAYMENT_STORAGE,
-    STORAGE_1155,
+    get_asset_info, get_royalties, get_royalty, query_payment_offering_asset_info, verify_funds,
+    verify_nft, CREATOR_NAME,
 };
 use crate::error::ContractError;
 use crate::msg::{SellNft, TransferNftDirectlyMsg};
@@ -12,10 +12,16 @@ use cosmwasm_std::{
 };
 use cosmwasm_std::{Addr, StdError};
 use cw1155::Cw1155ExecuteMsg;
-use market::{Funds, MarketHubContract};
-use market_1155::{MarketExecuteMsg, MarketQueryMsg, MintMsg, Offering};
-use market_ai_royalty::{parse_transfer_msg, pay_royalties, AiRoyaltyExecuteMsg, RoyaltyMsg};
-use market_payment::{Payment, PaymentExecuteMsg};
+use market::{
+    validate_reserved_for, verify_reserved_buyer, AssetInfo, Funds, MarketHubContract,
+    PAYMENT_1155_STORAGE,
+};
+use market_1155::{Market1155Storage, MintMsg, Offering};
+use market_ai_royalty::{
+    parse_transfer_msg, pay_royalties, AiRoyaltyStorage, RoyaltyMsg, RoyaltyPayee,
+    MAX_TOTAL_PAYEE_WEIGHT,
+};
+use market_payment::{Payment, PaymentStorage};
 use std::ops::{Add, Mul, Sub};
 
 pub fn add_msg_royalty(
@@ -24,23 +30,21 @@ pub fn add_msg_royalty(
     msg: MintMsg,
 ) -> StdResult<Vec<CosmosMsg>> {
     let mut cosmos_msgs: Vec<CosmosMsg> = vec![];
+    let payees = get_co_owner_payees(sender, &msg)?;
     let royalty_msg = RoyaltyMsg {
         contract_addr: msg.contract_addr,
         token_id: msg.mint.mint.token_id,
         creator: msg.creator,
         creator_type: Some(msg.creator_type),
         royalty: msg.royalty,
+        payees: None,
     };
 
     // update ai royalty provider
-    cosmos_msgs.push(get_handle_msg(
-        governance,
-        AI_ROYALTY_STORAGE,
-        AiRoyaltyExecuteMsg::UpdateRoyalty(RoyaltyMsg {
-            royalty: None,
-            ..royalty_msg.clone()
-        }),
-    )?);
+    cosmos_msgs.push(governance.update_royalty(RoyaltyMsg {
+        royalty: None,
+        ..royalty_msg.clone()
+    })?);
 
     // providers are the list that the minter wants to share royalty with
     // if let Some(providers) = msg.providers {
@@ -59,18 +63,50 @@ pub fn add_msg_royalty(
     // }
 
     // update creator as the caller of the mint tx
-    cosmos_msgs.push(get_handle_msg(
-        governance,
-        AI_ROYALTY_STORAGE,
-        AiRoyaltyExecuteMsg::UpdateRoyalty(RoyaltyMsg {
-            creator: Addr::unchecked(sender.to_string()),
-            creator_type: Some(String::from(CREATOR_NAME)),
-            ..royalty_msg
-        }),
-    )?);
+    cosmos_msgs.push(governance.update_royalty(RoyaltyMsg {
+        creator: Addr::unchecked(sender.to_string()),
+        creator_type: Some(String::from(CREATOR_NAME)),
+        payees,
+        ..royalty_msg
+    })?);
     Ok(cosmos_msgs)
 }
 
+/// the co owners share the creator royalty with the minter
+pub fn get_co_owner_payees(sender: &str, msg: &MintMsg) -> StdResult<Option<Vec<RoyaltyPayee>>> {
+    let co_owners = match msg.mint.mint.co_owner.as_ref() {
+        Some(co_owners) if !co_owners.is_empty() => co_owners,
+        _ => return Ok(None),
+    };
+    let addresses: Vec<&str> = std::iter::once(sender)
+        .chain(co_owners.iter().map(String::as_str))
+        .collect();
+    let weights = match msg.co_owner_weights.clone() {
+        Some(weights) => {
+            let total_weight = weights
+                .iter()
+                .try_fold(0u64, |total, weight| total.checked_add(*weight));
+            if weights.len() != addresses.len()
+                || !total_weight.is_some_and(|total| total > 0 && total <= MAX_TOTAL_PAYEE_WEIGHT)
+            {
+                return Err(StdError::generic_err("Invalid argument: co_owner_weights"));
+            }
+            weights
+        }
+        None => vec![1; addresses.len()],
+    };
+    Ok(Some(
+        addresses
+            .into_iter()
+            .zip(weights)
+            .map(|(address, weight)| RoyaltyPayee {
+                address: Addr::unchecked(address),
+                weight,
+            })
+            .collect(),
+    ))
+}
+
 pub fn try_handle_mint(
     deps: DepsMut,
     info: MessageInfo,
@@ -167,7 +203,7 @@ pub fn try_handle_transfer_directly(
 }
 
 pub fn try_buy(
-    deps: DepsMut,
+    mut deps: DepsMut,
     sender: Addr,
     env: Env,
     offering_id: u64,
@@ -176,14 +212,11 @@ pub fn try_buy(
     // native_funds: Option<Vec<Coin>>,
     funds: Funds,
 ) -> Result<Response, ContractError> {
-    let ContractInfo {
-        governance,
-        decimal_point,
-        ..
-    } = CONTRACT_INFO.load(deps.storage)?;
+    let ContractInfo { governance, .. } = CONTRACT_INFO.load(deps.storage)?;
 
     // check if offering exists, when return StdError => it will show EOF while parsing a JSON value.
     let mut off: Offering = get_offering(deps.as_ref(), offering_id)?;
+    verify_reserved_buyer(&off.reserved_for, &sender)?;
 
     if amount.gt(&off.amount) {
         return Err(ContractError::InsufficientAmount {});
@@ -193,7 +226,7 @@ pub fn try_buy(
     let token_id = off.token_id.clone();
     let asset_info = query_payment_offering_asset_info(
         deps.as_ref(),
-        governance.addr().as_str(),
+        &governance,
         off.contract_addr.clone(),
         &token_id,
         off.seller.as_str(),
@@ -207,8 +240,6 @@ pub fn try_buy(
     let mut cosmos_msgs = vec![];
     // check for enough coins, if has price then payout to all participants
     if !off.per_price.is_zero() {
-        let contract_info = CONTRACT_INFO.load(deps.storage)?;
-
         let price = off.per_price.mul(Decimal::from_ratio(amount.u128(), 1u128));
 
         verify_funds(
@@ -219,40 +250,16 @@ pub fn try_buy(
             &price,
         )?;
 
-        let mut seller_amount = price;
-
-        // pay for the owner of this minter contract if there is fee set in marketplace
-        let fee_amount = price.mul(Decimal::permille(contract_info.fee));
-        // Rust will automatically floor down the value to 0 if amount is too small => error
-        seller_amount = seller_amount.checked_sub(fee_amount)?;
-        MARKET_FEES.update(deps.storage, |current_fees| -> StdResult<_> {
-            Ok(current_fees.add(fee_amount))
-        })?;
-        let remaining_for_royalties = seller_amount;
-        // pay for creator, ai provider and others
-        if let Ok(royalties) = get_royalties(deps.as_ref(), off.contract_addr.as_str(), &token_id) {
-            pay_royalties(
-                &royalties,
-                &remaining_for_royalties,
-                decimal_point,
-                &mut seller_amount,
-                &mut cosmos_msgs,
-                &mut rsp,
-                env.contract.address.as_str(),
-                &to_json_binary(&asset_info)?.to_base64(),
-                asset_info.clone(),
-            )?;
-        }
-
-        // pay the left to the seller
-        if !seller_amount.is_zero() {
-            cosmos_msgs.push(parse_transfer_msg(
-                asset_info,
-                seller_amount,
-                env.contract.address.as_str(),
-                seller_addr.clone(),
-            )?);
-        }
+        cosmos_msgs.extend(pay_seller(
+            deps.branch(),
+            &env,
+            &off.contract_addr,
+            &token_id,
+            price,
+            seller_addr.clone(),
+            asset_info,
+            &mut rsp,
+        )?);
     } else {
         return Err(ContractError::InvalidSentFundAmount {});
     }
@@ -278,21 +285,11 @@ pub fn try_buy(
     // remove offering when total amount is zero
     if amount.eq(&off.amount) {
         // remove offering in the offering storage
-        cosmos_msgs.push(get_handle_msg(
-            &governance,
-            STORAGE_1155,
-            MarketExecuteMsg::RemoveOffering { id: offering_id },
-        )?);
+        cosmos_msgs.push(governance.remove_offering(offering_id)?);
     } else {
         // if not equal => reduce amount
         off.amount = off.amount.checked_sub(amount)?;
-        cosmos_msgs.push(get_handle_msg(
-            &governance,
-            STORAGE_1155,
-            MarketExecuteMsg::UpdateOffering {
-                offering: off.clone(),
-            },
-        )?);
+        cosmos_msgs.push(governance.update_offering(off.clone())?);
     }
     rsp = rsp.add_messages(cosmos_msgs);
     rsp.attributes.extend(vec![
@@ -306,6 +303,57 @@ pub fn try_buy(
     Ok(rsp)
 }
 
+/// pay the market fee and the royalties of the token from the price, then the left to the seller
+#[allow(clippy::too_many_arguments)]
+pub fn pay_seller(
+    deps: DepsMut,
+    env: &Env,
+    contract_addr: &Addr,
+    token_id: &str,
+    price: Uint128,
+    seller: Addr,
+    asset_info: AssetInfo,
+    rsp: &mut Response,
+) -> Result<Vec<CosmosMsg>, ContractError> {
+    let contract_info = CONTRACT_INFO.load(deps.storage)?;
+    let mut cosmos_msgs = vec![];
+    let mut seller_amount = price;
+
+    // pay for the owner of this minter contract if there is fee set in marketplace
+    let fee_amount = price.mul(Decimal::permille(contract_info.fee));
+    // Rust will automatically floor down the value to 0 if amount is too small => error
+    seller_amount = seller_amount.checked_sub(fee_amount)?;
+    MARKET_FEES.update(deps.storage, |current_fees| -> StdResult<_> {
+        Ok(current_fees.add(fee_amount))
+    })?;
+    let remaining_for_royalties = seller_amount;
+    // pay for creator, ai provider and others
+    if let Ok(royalties) = get_royalties(deps.as_ref(), contract_addr.as_str(), token_id) {
+        pay_royalties(
+            &royalties,
+            &remaining_for_royalties,
+            contract_info.decimal_point,
+            &mut seller_amount,
+            &mut cosmos_msgs,
+            rsp,
+            env.contract.address.as_str(),
+            &to_json_binary(&asset_info)?.to_base64(),
+            asset_info.clone(),
+        )?;
+    }
+
+    // pay the left to the seller
+    if !seller_amount.is_zero() {
+        cosmos_msgs.push(parse_transfer_msg(
+            asset_info,
+            seller_amount,
+            env.contract.address.as_str(),
+            seller,
+        )?);
+    }
+    Ok(cosmos_msgs)
+}
+
 pub fn try_withdraw(
     deps: DepsMut,
     info: MessageInfo,
@@ -326,11 +374,7 @@ pub fn try_withdraw(
         let mut cw1155_cosmos_msg: Vec<CosmosMsg> = vec![];
 
         // remove offering
-        cw1155_cosmos_msg.push(get_handle_msg(
-            &governance,
-            STORAGE_1155,
-            MarketExecuteMsg::RemoveOffering { id: offering_id },
-        )?);
+        cw1155_cosmos_msg.push(governance.remove_offering(offering_id)?);
 
         return Ok(Response::new()
             .add_messages(cw1155_cosmos_msg)
@@ -400,17 +444,27 @@ pub fn try_change_creator(
     )?;
 
     // update creator royalty
-    cosmos_msgs.push(get_handle_msg(
-        &governance,
-        AI_ROYALTY_STORAGE,
-        AiRoyaltyExecuteMsg::UpdateRoyalty(RoyaltyMsg {
-            contract_addr: contract_addr.clone(),
-            token_id: token_id.clone(),
-            creator: Addr::unchecked(to.as_str()),
-            creator_type: Some(royalty.creator_type),
-            royalty: Some(royalty.royalty),
+    cosmos_msgs.push(governance.update_royalty(RoyaltyMsg {
+        contract_addr: contract_addr.clone(),
+        token_id: token_id.clone(),
+        creator: Addr::unchecked(to.as_str()),
+        creator_type: Some(royalty.creator_type),
+        royalty: Some(royalty.royalty),
+        // the new creator takes over the share of the previous one
+        payees: royalty.payees.map(|payees| {
+            payees
+                .into_iter()
+                .map(|payee| RoyaltyPayee {
+                    address: if payee.address.eq(&info.sender) {
+                        Addr::unchecked(to.as_str())
+                    } else {
+                        payee.address
+                    },
+                    weight: payee.weight,
+                })
+                .collect()
         }),
-    )?);
+    })?);
 
     return Ok(Response::new()
         .add_messages(cosmos_msgs)
@@ -453,49 +507,51 @@ pub fn try_sell_nft(
         seller: Addr::unchecked(final_seller),
         per_price: msg.per_price,
         amount: msg.amount,
+        reserved_for: validate_reserved_for(deps.api, msg.reserved_for)?,
+        asset_info: Some(asset_info.clone()),
     };
 
-    let mut cosmos_msgs = vec![];
-    // push save message to datahub storage
-    cosmos_msgs.push(get_handle_msg(
-        &governance,
-        STORAGE_1155,
-        MarketExecuteMsg::UpdateOffering {
-            offering: offering.clone(),
-        },
-    )?);
-
-    // push save message to market payment storage
-    cosmos_msgs.push(get_handle_msg(
-        &governance,
-        PAYMENT_STORAGE,
-        PaymentExecuteMsg::UpdateOfferingPayment(Payment {
-            contract_addr: msg.contract_addr.clone(),
-            token_id: token_id.clone(),
-            sender: Some(info.sender.clone()), // for 721, contract & token id combined is already unique
-            asset_info: asset_info.clone(),
-        }),
-    )?);
+    // cross the resting buy orders first, only the amount left is offered
+    let mut rsp = Response::default();
+    rsp.attributes.extend(vec![attr("action", "sell_nft")]);
+    let (remaining, mut cosmos_msgs) =
+        match_buy_orders(deps, &env, &offering, &asset_info, &mut rsp)?;
+
+    if !remaining.is_zero() {
+        // push save message to datahub storage
+        cosmos_msgs.push(governance.update_offering(Offering {
+            amount: remaining,
+            ..offering.clone()
+        })?);
+
+        // push save message to market payment storage
+        cosmos_msgs.push(governance.update_offering_payment(
+            PAYMENT_1155_STORAGE,
+            Payment {
+                contract_addr: msg.contract_addr.clone(),
+                token_id: token_id.clone(),
+                sender: Some(info.sender.clone()), // for 721, contract & token id combined is already unique
+                asset_info: asset_info.clone(),
+            },
+        )?);
+    }
 
-    Ok(Response::new()
-        .add_messages(cosmos_msgs)
-        .add_attributes(vec![
-            attr("action", "sell_nft"),
-            attr("seller", info.sender),
-            attr("contract_addr", msg.contract_addr),
-            attr("per price", offering.per_price.to_string()),
-            attr("token_id", token_id),
-            attr("initial_token_id", msg.token_id),
-        ]))
+    rsp = rsp.add_messages(cosmos_msgs);
+    rsp.attributes.extend(vec![
+        attr("seller", info.sender),
+        attr("contract_addr", msg.contract_addr),
+        attr("per price", offering.per_price.to_string()),
+        attr("token_id", token_id),
+        attr("initial_token_id", msg.token_id),
+    ]);
+    Ok(rsp)
 }
 
 fn get_offering(deps: Deps, offering_id: u64) -> Result<Offering, ContractError> {
-    let offering: Offering = query_storage(
-        deps,
-        STORAGE_1155,
-        MarketQueryMsg::GetOffering { offering_id },
-    )
-    .map_err(|_| ContractError::InvalidGetOffering {})?;
+    let ContractInfo { governance, .. } = CONTRACT_INFO.load(deps.storage)?;
+    let offering = governance
+        .get_offering(&deps.querier, offering_id)
+        .map_err(|_| ContractError::InvalidGetOffering {})?;
     Ok(offering)
 }
 
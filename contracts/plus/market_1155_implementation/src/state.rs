@@ -1,9 +1,9 @@
-use cosmwasm_std::Uint128;
-use market::MarketHubContract;
+use cosmwasm_std::{Addr, StdResult, Storage, Uint128};
+use market::{AssetInfo, MarketHubContract};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cw_storage_plus::Item;
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex, PkOwned, UniqueIndex};
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
 pub struct ContractInfo {
@@ -21,5 +21,97 @@ pub struct ContractInfo {
     pub decimal_point: u64,
 }
 
-pub const CONTRACT_INFO: Item<ContractInfo> = Item::new("contract_info");
+pub const CONTRACT_INFO_KEY: &str = "market_info";
+pub const CONTRACT_INFO: Item<ContractInfo> = Item::new(CONTRACT_INFO_KEY);
 pub const MARKET_FEES: Item<Uint128> = Item::new("market_fees");
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct AuctionBid {
+    pub bidder: Addr,
+    /// amount of the auction wanted by the bidder
+    pub amount: Uint128,
+    pub per_price: Uint128,
+    /// funds escrowed by the bidder, the part above the clearing price is refunded
+    pub deposit: Uint128,
+    /// earlier bids win ties at the same per price
+    pub seq: u64,
+}
+
+/// bid book of the multi winner auctions, keyed by auction id and bidder
+pub const AUCTION_BIDS: Map<(&[u8], &[u8]), AuctionBid> = Map::new("auction_bids");
+pub const BIDS_COUNT: Item<u64> = Item::new("num_bids");
+
+pub fn increment_bids(storage: &mut dyn Storage) -> StdResult<u64> {
+    let val = BIDS_COUNT.may_load(storage)?.unwrap_or_default() + 1;
+    BIDS_COUNT.save(storage, &val)?;
+    Ok(val)
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct BuyOrder {
+    pub id: Option<u64>,
+    pub buyer: Addr,
+    pub contract_addr: Addr,
+    pub token_id: String,
+    pub per_price: Uint128,
+    /// amount still wanted, the funds left in escrow are amount * per_price
+    pub amount: Uint128,
+    pub asset_info: AssetInfo,
+}
+
+pub const BUY_ORDERS_COUNT: Item<u64> = Item::new("num_buy_orders");
+
+pub fn increment_buy_orders(storage: &mut dyn Storage) -> StdResult<u64> {
+    let val = BUY_ORDERS_COUNT.may_load(storage)?.unwrap_or_default() + 1;
+    BUY_ORDERS_COUNT.save(storage, &val)?;
+    Ok(val)
+}
+
+pub struct BuyOrderIndexes<'a> {
+    pub buyer: MultiIndex<'a, BuyOrder>,
+    // contract + token id, asset => per price + id, so the book of a token can be walked from the best price
+    pub contract_token_id_price: UniqueIndex<'a, (PkOwned, PkOwned, PkOwned), BuyOrder>,
+}
+
+impl<'a> IndexList<BuyOrder> for BuyOrderIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<BuyOrder>> + '_> {
+        let v: Vec<&dyn Index<BuyOrder>> = vec![&self.buyer, &self.contract_token_id_price];
+        Box::new(v.into_iter())
+    }
+}
+
+// contract nft + token id => book of the token
+pub fn get_contract_token_id(contract: &Addr, token_id: &str) -> Vec<u8> {
+    let mut vec = contract.as_bytes().to_vec();
+    vec.extend(token_id.as_bytes());
+    vec
+}
+
+// per price + inverted order id, big endian so a descending walk gives the best price then the oldest order
+pub fn get_bid_price_id(per_price: Uint128, id: u64) -> PkOwned {
+    let mut vec = per_price.u128().to_be_bytes().to_vec();
+    vec.extend_from_slice(&(u64::MAX - id).to_be_bytes());
+    PkOwned(vec)
+}
+
+// this IndexedMap instance has a lifetime
+pub fn buy_orders<'a>() -> IndexedMap<'a, &'a [u8], BuyOrder, BuyOrderIndexes<'a>> {
+    let indexes = BuyOrderIndexes {
+        buyer: MultiIndex::new(
+            |o| o.buyer.as_bytes().to_vec(),
+            "buy_orders",
+            "buy_orders__buyer",
+        ),
+        contract_token_id_price: UniqueIndex::new(
+            |o| {
+                (
+                    PkOwned(get_contract_token_id(&o.contract_addr, &o.token_id)),
+                    PkOwned(o.asset_info.as_key()),
+                    get_bid_price_id(o.per_price, o.id.unwrap_or_default()),
+                )
+            },
+            "buy_orders__contract_tokenid_price",
+        ),
+    };
+    IndexedMap::new("buy_orders", indexes)
+}
@@ -1,24 +1,29 @@
-use crate::auction::calculate_price;
+use crate::auction::{calculate_price, MAX_AUCTION_BIDS};
 use crate::contract::{execute, instantiate, query, MAX_ROYALTY_PERCENT};
 use crate::error::ContractError;
 use crate::msg::*;
-use crate::state::ContractInfo;
+use crate::state::{AuctionBid, BuyOrder, ContractInfo};
 use cosmwasm_std::testing::{mock_info, MockApi, MockStorage};
 use cosmwasm_std::{
-    coin, coins, from_json, to_json_binary, Addr, Binary, ContractResult, CosmosMsg, Decimal, Env,
-    MessageInfo, OwnedDeps, QuerierResult, Response, StdError, StdResult, SystemError,
-    SystemResult, Uint128, WasmMsg, WasmQuery,
+    coin, coins, from_json, to_json_binary, Addr, BankMsg, Binary, ContractResult, CosmosMsg,
+    Decimal, Env, MessageInfo, OwnedDeps, QuerierResult, Response, StdError, StdResult,
+    SystemError, SystemResult, Uint128, WasmMsg, WasmQuery,
 };
-use cw1155::{BalanceResponse, Cw1155ExecuteMsg, Cw1155QueryMsg};
+use cw1155::{BalanceResponse, Cw1155ExecuteMsg, Cw1155QueryMsg, Cw1155ReceiveMsg};
 use cw20::{Cw20Coin, Cw20ExecuteMsg, Cw20ReceiveMsg, MinterResponse};
 use market::mock::{mock_dependencies, mock_env, MockQuerier};
-use market_1155::{Cw20HookMsg, MarketQueryMsg, MintIntermediate, MintMsg, MintStruct, Offering};
-use market_ai_royalty::{AiRoyaltyQueryMsg, Royalty};
+use market::{PageResponse, ReservedError};
+use market_1155::{
+    BidAmountMsg, BuyOrderMsg, Cw1155HookMsg, Cw20HookMsg, MarketQueryMsg, MintIntermediate,
+    MintMsg, MintStruct, Offering,
+};
+use market_ai_royalty::{AiRoyaltyQueryMsg, Royalty, MAX_TOTAL_PAYEE_WEIGHT};
 use market_auction_extend::{
     AuctionQueryMsg, AuctionsResponse, PagingOptions, QueryAuctionsResult,
 };
 use market_rejected::{
     IsRejectedForAllResponse, MarketRejectedExecuteMsg, MarketRejectedQueryMsg, NftInfo,
+    RejectReason,
 };
 use market_whitelist::MarketWhiteListExecuteMsg;
 use std::mem::transmute;
@@ -524,6 +529,7 @@ fn handle_approve(manager: &mut DepsManager) {
             },
             creator_type: String::from("cxacx"),
             royalty: Some(10000000),
+            co_owner_weights: None,
         });
 
         manager
@@ -563,7 +569,7 @@ fn handle_approve(manager: &mut DepsManager) {
 }
 
 fn generate_msg_bid_cw20(auction_id: u64, amount: u64, per_price: u64) -> ExecuteMsg {
-    ExecuteMsg::Receive(Cw20ReceiveMsg {
+    ExecuteMsg::Receive(ReceiveMsg::Cw20(Cw20ReceiveMsg {
         sender: BIDDER.to_string(),
         amount: Uint128::from(amount),
         msg: to_json_binary(&Cw20HookMsg::BidNft {
@@ -571,11 +577,11 @@ fn generate_msg_bid_cw20(auction_id: u64, amount: u64, per_price: u64) -> Execut
             per_price: Uint128::from(per_price),
         })
         .unwrap(),
-    })
+    }))
 }
 
 fn generate_msg_buy_cw20(offering_id: u64, amount: u64, nft_amount: u64) -> ExecuteMsg {
-    ExecuteMsg::Receive(Cw20ReceiveMsg {
+    ExecuteMsg::Receive(ReceiveMsg::Cw20(Cw20ReceiveMsg {
         sender: BIDDER.to_string(),
         amount: Uint128::from(amount),
         msg: to_json_binary(&Cw20HookMsg::BuyNft {
@@ -583,7 +589,7 @@ fn generate_msg_buy_cw20(offering_id: u64, amount: u64, nft_amount: u64) -> Exec
             amount: Uint128::from(nft_amount),
         })
         .unwrap(),
-    })
+    }))
 }
 
 #[test]
@@ -608,6 +614,8 @@ fn sell_auction_happy_path() {
             contract_addr: Addr::unchecked(OW_1155_ADDR),
             token_id: String::from(BIDDABLE_NFT_NATIVE),
             asker: None,
+            reserved_for: None,
+            multi_winner: None,
         };
         let msg = ExecuteMsg::AskAuctionNft(sell_msg);
 
@@ -655,6 +663,8 @@ fn sell_auction_cw20_happy_path() {
             contract_addr: Addr::unchecked(OW_1155_ADDR),
             token_id: String::from(BIDDABLE_NFT_CW20),
             asker: None,
+            reserved_for: None,
+            multi_winner: None,
         };
         let msg = ExecuteMsg::AskAuctionNft(sell_msg);
 
@@ -703,6 +713,8 @@ fn sell_auction_unhappy_path() {
             contract_addr: Addr::unchecked(OW_1155_ADDR),
             token_id: String::from(BIDDABLE_NFT_NATIVE),
             asker: None,
+            reserved_for: None,
+            multi_winner: None,
         });
 
         // insufficient amount case creator
@@ -725,6 +737,8 @@ fn sell_auction_unhappy_path() {
             contract_addr: Addr::unchecked(OW_1155_ADDR),
             token_id: String::from(BIDDABLE_NFT_NATIVE),
             asker: Some(Addr::unchecked("Somebody")),
+            reserved_for: None,
+            multi_winner: None,
         });
 
         assert!(matches!(
@@ -745,6 +759,8 @@ fn sell_auction_unhappy_path() {
             contract_addr: Addr::unchecked(OW_1155_ADDR),
             token_id: String::from(BIDDABLE_NFT_NATIVE),
             asker: None,
+            reserved_for: None,
+            multi_winner: None,
         });
 
         // successful case
@@ -763,6 +779,7 @@ fn sell_auction_unhappy_path() {
             token_id: String::from(BIDDABLE_NFT_NATIVE),
             amount: Uint128::from(100u128),
             seller: None,
+            reserved_for: None,
         });
 
         // failed auction because it is already on auction by the same person
@@ -778,6 +795,9 @@ fn sell_auction_unhappy_path() {
                 token_id: String::from(BIDDABLE_NFT),
             },
             expires: None,
+            reason: Some(RejectReason::Fraud),
+            note: Some("stolen".to_string()),
+            moderator: None,
         };
         market_rejected_storage::contract::execute(
             manager.rejected.as_mut(),
@@ -808,7 +828,7 @@ fn sell_auction_unhappy_path() {
         // failed auction because it is already on auction by the same person
         assert!(matches!(
             manager.execute(provider_info.clone(), msg.clone()),
-            Err(ContractError::Rejected { .. })
+            Err(ContractError::Rejected { reason, .. }) if reason == "fraud (stolen)"
         ));
 
         assert!(matches!(
@@ -827,6 +847,8 @@ fn sell_auction_unhappy_path() {
                     contract_addr: Addr::unchecked("some cute address"),
                     token_id: String::from(BIDDABLE_NFT_NATIVE),
                     asker: None,
+                    reserved_for: None,
+                    multi_winner: None,
                 }),
             ),
             Err(ContractError::NotWhilteList { .. })
@@ -855,6 +877,8 @@ fn cancel_auction_happy_path() {
             contract_addr: Addr::unchecked(OW_1155_ADDR),
             token_id: String::from(BIDDABLE_NFT_NATIVE),
             asker: None,
+            reserved_for: None,
+            multi_winner: None,
         };
 
         let msg = ExecuteMsg::AskAuctionNft(sell_msg);
@@ -908,6 +932,8 @@ fn cancel_auction_cw20_happy_path() {
             contract_addr: Addr::unchecked(OW_1155_ADDR),
             token_id: String::from(BIDDABLE_NFT_CW20),
             asker: None,
+            reserved_for: None,
+            multi_winner: None,
         };
 
         let msg = ExecuteMsg::AskAuctionNft(sell_msg);
@@ -959,6 +985,8 @@ fn cancel_auction_unhappy_path() {
             contract_addr: Addr::unchecked(OW_1155_ADDR),
             token_id: String::from(BIDDABLE_NFT_NATIVE),
             asker: None,
+            reserved_for: None,
+            multi_winner: None,
         };
 
         let msg = ExecuteMsg::AskAuctionNft(sell_msg);
@@ -1003,6 +1031,8 @@ fn cancel_bid_happy_path() {
             contract_addr: Addr::unchecked(OW_1155_ADDR),
             token_id: String::from(BIDDABLE_NFT_NATIVE),
             asker: None,
+            reserved_for: None,
+            multi_winner: None,
         };
 
         let msg = ExecuteMsg::AskAuctionNft(sell_msg);
@@ -1041,6 +1071,8 @@ fn cancel_bid_cw20_happy_path() {
             contract_addr: Addr::unchecked(OW_1155_ADDR),
             token_id: String::from(BIDDABLE_NFT_CW20),
             asker: None,
+            reserved_for: None,
+            multi_winner: None,
         };
 
         let msg = ExecuteMsg::AskAuctionNft(sell_msg);
@@ -1079,6 +1111,8 @@ fn cancel_bid_unhappy_path() {
             contract_addr: Addr::unchecked(OW_1155_ADDR),
             token_id: String::from(BIDDABLE_NFT_NATIVE),
             asker: None,
+            reserved_for: None,
+            multi_winner: None,
         };
 
         let msg = ExecuteMsg::AskAuctionNft(sell_msg);
@@ -1127,6 +1161,8 @@ fn claim_winner_happy_path() {
             contract_addr: Addr::unchecked(OW_1155_ADDR),
             token_id: String::from(BIDDABLE_NFT_NATIVE),
             asker: None,
+            reserved_for: None,
+            multi_winner: None,
         };
 
         let msg = ExecuteMsg::AskAuctionNft(sell_msg.clone());
@@ -1204,6 +1240,8 @@ fn claim_winner_happy_path() {
             contract_addr: Addr::unchecked(OW_1155_ADDR),
             token_id: String::from(BIDDABLE_NFT_NATIVE),
             asker: None,
+            reserved_for: None,
+            multi_winner: None,
         };
         let msg = ExecuteMsg::AskAuctionNft(sell_msg);
 
@@ -1250,6 +1288,8 @@ fn claim_winner_cw20_happy_path() {
             contract_addr: Addr::unchecked(OW_1155_ADDR),
             token_id: String::from(BIDDABLE_NFT_CW20),
             asker: None,
+            reserved_for: None,
+            multi_winner: None,
         };
 
         let msg = ExecuteMsg::AskAuctionNft(sell_msg.clone());
@@ -1297,6 +1337,8 @@ fn claim_winner_cw20_happy_path() {
             contract_addr: Addr::unchecked(OW_1155_ADDR),
             token_id: String::from(BIDDABLE_NFT_CW20),
             asker: None,
+            reserved_for: None,
+            multi_winner: None,
         };
         let msg = ExecuteMsg::AskAuctionNft(sell_msg);
 
@@ -1343,6 +1385,8 @@ fn claim_winner_with_market_fees() {
             contract_addr: Addr::unchecked(OW_1155_ADDR),
             token_id: String::from(BIDDABLE_NFT_NATIVE),
             asker: None,
+            reserved_for: None,
+            multi_winner: None,
         };
 
         let msg = ExecuteMsg::AskAuctionNft(sell_msg.clone());
@@ -1418,6 +1462,8 @@ fn claim_winner_unhappy_path() {
             contract_addr: Addr::unchecked(OW_1155_ADDR),
             token_id: String::from(BIDDABLE_NFT_NATIVE),
             asker: None,
+            reserved_for: None,
+            multi_winner: None,
         };
 
         let msg = ExecuteMsg::AskAuctionNft(sell_msg.clone());
@@ -1498,6 +1544,8 @@ fn claim_winner_cw20_unhappy_path() {
             contract_addr: Addr::unchecked(OW_1155_ADDR),
             token_id: String::from(BIDDABLE_NFT_CW20),
             asker: None,
+            reserved_for: None,
+            multi_winner: None,
         };
 
         let msg = ExecuteMsg::AskAuctionNft(sell_msg.clone());
@@ -1554,6 +1602,246 @@ fn claim_winner_cw20_unhappy_path() {
     }
 }
 
+#[test]
+fn multi_winner_bids_are_capped() {
+    unsafe {
+        let manager = DepsManager::get_new();
+        let contract_env = mock_env(MARKET_ADDR);
+        handle_approve(manager);
+
+        let sell_msg = AskNftMsg {
+            per_price: Uint128::from(1u128),
+            cancel_fee: None,
+            start: None,
+            end: Some(contract_env.block.height + 100),
+            buyout_per_price: None,
+            start_timestamp: None,
+            end_timestamp: None,
+            step_price: None,
+            amount: Uint128::from(10u128),
+            contract_addr: Addr::unchecked(OW_1155_ADDR),
+            token_id: String::from(BIDDABLE_NFT_NATIVE),
+            asker: None,
+            reserved_for: None,
+            multi_winner: Some(true),
+        };
+        manager
+            .execute(mock_info("asker", &[]), ExecuteMsg::AskAuctionNft(sell_msg))
+            .unwrap();
+
+        let bid = |manager: &mut DepsManager, bidder: &str, per_price: u128| {
+            manager.execute(
+                mock_info(bidder, &coins(per_price, DENOM)),
+                ExecuteMsg::BidNftAmount(BidAmountMsg {
+                    auction_id: 1,
+                    amount: Uint128::from(1u64),
+                    per_price: Uint128::from(per_price),
+                }),
+            )
+        };
+        for i in 0..MAX_AUCTION_BIDS {
+            bid(manager, &format!("bidder{}", i), 1).unwrap();
+        }
+        // a full book only takes a bid beating its lowest one
+        assert!(matches!(
+            bid(manager, "late_bidder", 1),
+            Err(ContractError::TooManyBids { .. })
+        ));
+        // the lowest and latest bid is evicted and refunded
+        let results = bid(manager, "late_bidder", 2).unwrap();
+        assert_eq!(
+            results.last().unwrap().messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: format!("bidder{}", MAX_AUCTION_BIDS - 1),
+                amount: coins(1, DENOM),
+            })
+        );
+        let bids: Vec<AuctionBid> = from_json(
+            manager
+                .query(QueryMsg::GetAuctionBids { auction_id: 1 })
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(bids.len(), MAX_AUCTION_BIDS);
+        assert_eq!(bids[0].bidder, Addr::unchecked("late_bidder"));
+        assert!(!bids
+            .iter()
+            .any(|bid| bid.bidder.eq(&format!("bidder{}", MAX_AUCTION_BIDS - 1))));
+        // the bidders can still replace their bids
+        bid(manager, "bidder0", 1).unwrap();
+    }
+}
+
+#[test]
+fn claim_multi_winner_happy_path() {
+    unsafe {
+        let manager = DepsManager::get_new();
+        let contract_env = mock_env(MARKET_ADDR);
+        handle_approve(manager);
+
+        let sell_msg = AskNftMsg {
+            per_price: Uint128::from(5u128),
+            cancel_fee: None,
+            start: None,
+            end: Some(contract_env.block.height + 100),
+            buyout_per_price: None,
+            start_timestamp: None,
+            end_timestamp: None,
+            step_price: None,
+            amount: Uint128::from(10u128),
+            contract_addr: Addr::unchecked(OW_1155_ADDR),
+            token_id: String::from(BIDDABLE_NFT_NATIVE),
+            asker: None,
+            reserved_for: None,
+            multi_winner: Some(true),
+        };
+        manager
+            .execute(
+                mock_info("asker", &vec![]),
+                ExecuteMsg::AskAuctionNft(sell_msg),
+            )
+            .unwrap();
+
+        // a multi winner auction only accepts bids for an amount
+        assert!(matches!(
+            manager.execute(
+                mock_info(BIDDER, &coins(100, DENOM)),
+                ExecuteMsg::BidNft {
+                    auction_id: 1,
+                    per_price: Uint128::from(10u64),
+                }
+            ),
+            Err(ContractError::InvalidAuctionBid {})
+        ));
+        // below the reserve price
+        assert!(matches!(
+            manager.execute(
+                mock_info(BIDDER, &coins(4, DENOM)),
+                ExecuteMsg::BidNftAmount(BidAmountMsg {
+                    auction_id: 1,
+                    amount: Uint128::from(1u64),
+                    per_price: Uint128::from(4u64),
+                })
+            ),
+            Err(ContractError::InsufficientFunds {})
+        ));
+        // the deposit can only be paid in the auction denom, other coins are not counted
+        assert!(matches!(
+            manager.execute(
+                mock_info(BIDDER, &[coin(1000000, "junk"), coin(10, DENOM)]),
+                ExecuteMsg::BidNftAmount(BidAmountMsg {
+                    auction_id: 1,
+                    amount: Uint128::from(1u64),
+                    per_price: Uint128::from(10u64),
+                })
+            ),
+            Err(ContractError::InvalidDenomAmount {})
+        ));
+
+        for (bidder, amount, per_price) in
+            [(PROVIDER, 6u64, 10u64), ("seller", 5, 8), ("sender", 3, 5)]
+        {
+            manager
+                .execute(
+                    mock_info(bidder, &coins((amount * per_price) as u128, DENOM)),
+                    ExecuteMsg::BidNftAmount(BidAmountMsg {
+                        auction_id: 1,
+                        amount: Uint128::from(amount),
+                        per_price: Uint128::from(per_price),
+                    }),
+                )
+                .unwrap();
+        }
+
+        let bids: Vec<AuctionBid> = from_json(
+            &manager
+                .query(QueryMsg::GetAuctionBids { auction_id: 1 })
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            bids.iter()
+                .map(|bid| bid.bidder.as_str())
+                .collect::<Vec<&str>>(),
+            vec![PROVIDER, "seller", "sender"]
+        );
+
+        // the 6 of provider and 4 of the 5 of seller fill the 10 at the per price of seller
+        let clearing: ClearingPriceResponse = from_json(
+            &manager
+                .query(QueryMsg::GetClearingPrice { auction_id: 1 })
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            clearing,
+            ClearingPriceResponse {
+                clearing_price: Some(Uint128::from(8u64)),
+                filled_amount: Uint128::from(10u64),
+            }
+        );
+
+        let claim_msg = ExecuteMsg::ClaimWinner { auction_id: 1 };
+        assert!(matches!(
+            manager.execute(mock_info("claimer", &vec![]), claim_msg.clone()),
+            Err(ContractError::AuctionNotFinished {})
+        ));
+
+        let mut claim_contract_env = contract_env.clone();
+        claim_contract_env.block.height = contract_env.block.height + 100;
+        let res = manager
+            .handle_with_env(claim_contract_env, mock_info("claimer", &vec![]), claim_msg)
+            .unwrap();
+
+        // winners pay the clearing price, the rest of the deposits is refunded
+        let refunds: Vec<(String, Uint128)> = res
+            .last()
+            .unwrap()
+            .messages
+            .iter()
+            .filter_map(|msg| match &msg.msg {
+                CosmosMsg::Bank(BankMsg::Send { to_address, amount }) => {
+                    Some((to_address.clone(), amount[0].amount))
+                }
+                _ => None,
+            })
+            .collect();
+        assert!(refunds.contains(&(PROVIDER.to_string(), Uint128::from(12u64))));
+        assert!(refunds.contains(&("seller".to_string(), Uint128::from(8u64))));
+        assert!(refunds.contains(&("sender".to_string(), Uint128::from(15u64))));
+
+        for (owner, balance) in [
+            (PROVIDER, 506u64),
+            ("seller", 504),
+            ("sender", 500),
+            ("asker", 490),
+        ] {
+            let result: BalanceResponse = from_json(
+                &ow1155::contract::query(
+                    manager.ow1155.as_ref(),
+                    mock_env(OW_1155_ADDR),
+                    Cw1155QueryMsg::Balance {
+                        owner: owner.to_string(),
+                        token_id: String::from(BIDDABLE_NFT),
+                    },
+                )
+                .unwrap(),
+            )
+            .unwrap();
+            assert_eq!(result.balance, Uint128::from(balance));
+        }
+
+        // the bid book is cleared
+        let bids: Vec<AuctionBid> = from_json(
+            &manager
+                .query(QueryMsg::GetAuctionBids { auction_id: 1 })
+                .unwrap(),
+        )
+        .unwrap();
+        assert!(bids.is_empty());
+    }
+}
+
 #[test]
 fn test_bid_nft_happy_path() {
     unsafe {
@@ -1576,6 +1864,8 @@ fn test_bid_nft_happy_path() {
             contract_addr: Addr::unchecked(OW_1155_ADDR),
             token_id: String::from(BIDDABLE_NFT_NATIVE),
             asker: None,
+            reserved_for: None,
+            multi_winner: None,
         };
 
         let msg = ExecuteMsg::AskAuctionNft(sell_msg.clone());
@@ -1625,6 +1915,8 @@ fn test_bid_nft_cw20_happy_path() {
             contract_addr: Addr::unchecked(OW_1155_ADDR),
             token_id: String::from(BIDDABLE_NFT_CW20),
             asker: None,
+            reserved_for: None,
+            multi_winner: None,
         };
 
         let msg = ExecuteMsg::AskAuctionNft(sell_msg.clone());
@@ -1671,6 +1963,8 @@ fn test_bid_nft_unhappy_path() {
             contract_addr: Addr::unchecked(OW_1155_ADDR),
             token_id: String::from(BIDDABLE_NFT_NATIVE),
             asker: None,
+            reserved_for: None,
+            multi_winner: None,
         };
 
         let msg = ExecuteMsg::AskAuctionNft(sell_msg.clone());
@@ -1818,6 +2112,8 @@ fn test_bid_nft_cw20_unhappy_path() {
             contract_addr: Addr::unchecked(OW_1155_ADDR),
             token_id: String::from(BIDDABLE_NFT_CW20),
             asker: None,
+            reserved_for: None,
+            multi_winner: None,
         };
 
         let msg = ExecuteMsg::AskAuctionNft(sell_msg.clone());
@@ -1987,7 +2283,8 @@ fn test_royalties() {
                 },
             },
             creator_type: String::from("cxacx"),
-            royalty: Some(10000000), // 1%
+            royalty: Some(10000000), // 1%,
+            co_owner_weights: None,
         });
 
         manager.execute(provider_info.clone(), mint_msg).unwrap();
@@ -1999,6 +2296,7 @@ fn test_royalties() {
             token_id: String::from(SELLABLE_NFT_NATIVE),
             amount: Uint128::from(100u64),
             seller: None,
+            reserved_for: None,
         });
         manager.execute(provider_info.clone(), msg).unwrap();
 
@@ -2027,6 +2325,7 @@ fn test_royalties() {
             token_id: String::from(SELLABLE_NFT_NATIVE),
             amount: Uint128::from(50u64),
             seller: None,
+            reserved_for: None,
         });
         manager.execute(info_sell.clone(), msg).unwrap();
 
@@ -2052,7 +2351,7 @@ fn test_royalties() {
         let mut total_payment = Uint128::zero();
 
         // query royalties
-        let royalties: Vec<Royalty> = from_json(
+        let royalties: PageResponse<Royalty> = from_json(
             &manager
                 .query(QueryMsg::AiRoyalty(
                     AiRoyaltyQueryMsg::GetRoyaltiesContractTokenId {
@@ -2061,13 +2360,15 @@ fn test_royalties() {
                         offset: None,
                         limit: None,
                         order: Some(1),
+                        start_after: None,
+                        with_total: None,
                     },
                 ))
                 .unwrap(),
         )
         .unwrap();
         println!("royalties are: {:?}", royalties);
-        assert_eq!(royalties.len(), 2);
+        assert_eq!(royalties.items.len(), 2);
 
         // placeholders to verify royalties
         let mut to_addrs: Vec<Addr> = vec![];
@@ -2107,7 +2408,7 @@ fn test_royalties() {
         let remaining_for_royalties = price.mul(Decimal::permille(1000 - contract_info.fee));
 
         // increment royalty to total payment
-        for royalty in royalties {
+        for royalty in royalties.items {
             let index = to_addrs.iter().position(|op| op.eq(&royalty.creator));
             if let Some(index) = index {
                 let amount = amounts[index];
@@ -2125,6 +2426,92 @@ fn test_royalties() {
     }
 }
 
+#[test]
+fn test_royalties_co_owners() {
+    unsafe {
+        let manager = DepsManager::get_new();
+        let creator_info = mock_info("creator", &vec![coin(50, DENOM)]);
+
+        handle_approve(manager);
+
+        // the creator shares the royalty with two co owners at 2:1:1
+        let mint_msg = ExecuteMsg::MintNft(MintMsg {
+            contract_addr: Addr::unchecked(OW_1155_ADDR),
+            creator: Addr::unchecked("creator"),
+            mint: MintIntermediate {
+                mint: MintStruct {
+                    to: String::from("creator"),
+                    value: Uint128::from(100u64),
+                    token_id: String::from(SELLABLE_NFT),
+                    co_owner: Some(vec![String::from("co_owner1"), String::from("co_owner2")]),
+                },
+            },
+            creator_type: String::from("cxacx"),
+            royalty: Some(30000000), // 3%
+            co_owner_weights: Some(vec![2, 1]),
+        });
+        assert!(manager
+            .execute(creator_info.clone(), mint_msg.clone())
+            .is_err());
+        let with_weights = |weights: Vec<u64>| match mint_msg.clone() {
+            ExecuteMsg::MintNft(msg) => ExecuteMsg::MintNft(MintMsg {
+                co_owner_weights: Some(weights),
+                ..msg
+            }),
+            _ => unreachable!(),
+        };
+        // the summed weights are capped, so they can never overflow when splitting
+        for weights in [
+            vec![u64::MAX, u64::MAX, 1],
+            vec![MAX_TOTAL_PAYEE_WEIGHT, 1, 0],
+        ] {
+            assert!(manager
+                .execute(creator_info.clone(), with_weights(weights))
+                .is_err());
+        }
+        let mint_msg = with_weights(vec![2, 1, 1]);
+        manager.execute(creator_info, mint_msg).unwrap();
+
+        let msg = ExecuteMsg::SellNft(SellNft {
+            contract_addr: Addr::unchecked(OW_1155_ADDR),
+            per_price: Uint128::from(100u128),
+            token_id: String::from(SELLABLE_NFT_NATIVE),
+            amount: Uint128::from(10u64),
+            seller: None,
+            reserved_for: None,
+        });
+        manager.execute(mock_info("seller", &[]), msg).unwrap();
+
+        let buy_msg = ExecuteMsg::BuyNft {
+            offering_id: 1,
+            amount: Uint128::from(10u64),
+        };
+        let results = manager
+            .execute(mock_info("buyer", &coins(1000, DENOM)), buy_msg)
+            .unwrap();
+
+        let mut payments: Vec<(String, Uint128)> = vec![];
+        for result in results {
+            for message in result.messages {
+                if let CosmosMsg::Bank(BankMsg::Send { to_address, amount }) = message.msg {
+                    payments.push((to_address, amount[0].amount));
+                }
+            }
+        }
+
+        // price 1000 minus 2% market fee => 980, 3% royalty => 29 split 14, 7, 7 and the dust goes to the creator
+        assert_eq!(
+            payments,
+            vec![
+                (String::from("creator"), Uint128::from(15u64)),
+                (String::from("co_owner1"), Uint128::from(7u64)),
+                (String::from("co_owner2"), Uint128::from(7u64)),
+                (String::from("seller"), Uint128::from(951u64)),
+            ]
+        );
+    }
+}
+
 #[test]
 fn test_royalties_cw20() {
     unsafe {
@@ -2146,7 +2533,8 @@ fn test_royalties_cw20() {
                 },
             },
             creator_type: String::from("cxacx"),
-            royalty: Some(10000000), // 1%
+            royalty: Some(10000000), // 1%,
+            co_owner_weights: None,
         });
 
         manager.execute(provider_info.clone(), mint_msg).unwrap();
@@ -2158,6 +2546,7 @@ fn test_royalties_cw20() {
             token_id: String::from(SELLABLE_NFT_CW20),
             amount: Uint128::from(100u64),
             seller: None,
+            reserved_for: None,
         });
         manager.execute(provider_info.clone(), msg).unwrap();
 
@@ -2183,6 +2572,7 @@ fn test_royalties_cw20() {
             token_id: String::from(SELLABLE_NFT_CW20),
             amount: Uint128::from(50u64),
             seller: None,
+            reserved_for: None,
         });
         manager.execute(info_sell.clone(), msg).unwrap();
 
@@ -2205,7 +2595,7 @@ fn test_royalties_cw20() {
         let mut total_payment = Uint128::zero();
 
         // query royalties
-        let royalties: Vec<Royalty> = from_json(
+        let royalties: PageResponse<Royalty> = from_json(
             &manager
                 .query(QueryMsg::AiRoyalty(
                     AiRoyaltyQueryMsg::GetRoyaltiesContractTokenId {
@@ -2214,13 +2604,15 @@ fn test_royalties_cw20() {
                         offset: None,
                         limit: None,
                         order: Some(1),
+                        start_after: None,
+                        with_total: None,
                     },
                 ))
                 .unwrap(),
         )
         .unwrap();
         println!("royalties are: {:?}", royalties);
-        assert_eq!(royalties.len(), 2);
+        assert_eq!(royalties.items.len(), 2);
 
         // placeholders to verify royalties
         let mut to_addrs: Vec<Addr> = vec![];
@@ -2279,7 +2671,7 @@ fn test_royalties_cw20() {
         let remaining_for_royalties = price.mul(Decimal::permille(1000 - contract_info.fee));
 
         // increment royalty to total payment
-        for royalty in royalties {
+        for royalty in royalties.items {
             let index = to_addrs.iter().position(|op| op.eq(&royalty.creator));
             if let Some(index) = index {
                 let amount = amounts[index];
@@ -2322,7 +2714,8 @@ fn test_buy_market_fee_calculate() {
                 },
             },
             creator_type: String::from("cxacx"),
-            royalty: Some(10000000), // 1%
+            royalty: Some(10000000), // 1%,
+            co_owner_weights: None,
         });
 
         manager.execute(provider_info.clone(), mint_msg).unwrap();
@@ -2334,6 +2727,7 @@ fn test_buy_market_fee_calculate() {
             token_id: String::from(SELLABLE_NFT_NATIVE),
             amount: Uint128::from(100u64),
             seller: None,
+            reserved_for: None,
         });
         manager.execute(provider_info.clone(), msg).unwrap();
 
@@ -2373,6 +2767,7 @@ fn test_sell_nft_unhappy() {
             token_id: String::from(SELLABLE_NFT_NATIVE),
             amount: Uint128::from(10000000000000u64),
             seller: None,
+            reserved_for: None,
         });
 
         // insufficient amount case creator
@@ -2388,6 +2783,7 @@ fn test_sell_nft_unhappy() {
             token_id: String::from(SELLABLE_NFT_NATIVE),
             amount: Uint128::from(10u64),
             seller: Some(Addr::unchecked("Somebody unauthorized")),
+            reserved_for: None,
         });
 
         assert!(matches!(
@@ -2401,6 +2797,7 @@ fn test_sell_nft_unhappy() {
             token_id: String::from(SELLABLE_NFT_NATIVE),
             amount: Uint128::from(10u64),
             seller: None,
+            reserved_for: None,
         });
 
         // successful case
@@ -2425,6 +2822,8 @@ fn test_sell_nft_unhappy() {
             contract_addr: Addr::unchecked(OW_1155_ADDR),
             token_id: String::from(SELLABLE_NFT_NATIVE),
             asker: None,
+            reserved_for: None,
+            multi_winner: None,
         };
         // fail when trying to create an auction
         let mut auction_msg = ExecuteMsg::AskAuctionNft(ask_msg.clone());
@@ -2476,6 +2875,7 @@ fn test_sell_nft_unhappy() {
                     token_id: String::from(SELLABLE_NFT_NATIVE),
                     amount: Uint128::from(100u128),
                     seller: None,
+                    reserved_for: None,
                 })
             ),
             Err(ContractError::NotWhilteList { .. })
@@ -2499,6 +2899,7 @@ fn test_sell_nft_unhappy_cw20() {
             token_id: String::from(SELLABLE_NFT_CW20),
             amount: Uint128::from(10000000000000u64),
             seller: None,
+            reserved_for: None,
         });
 
         // insufficient amount case creator
@@ -2514,6 +2915,7 @@ fn test_sell_nft_unhappy_cw20() {
             token_id: String::from(SELLABLE_NFT_CW20),
             amount: Uint128::from(10u64),
             seller: Some(Addr::unchecked("Somebody unauthorized")),
+            reserved_for: None,
         });
 
         assert!(matches!(
@@ -2527,6 +2929,7 @@ fn test_sell_nft_unhappy_cw20() {
             token_id: String::from(SELLABLE_NFT_CW20),
             amount: Uint128::from(10u64),
             seller: None,
+            reserved_for: None,
         });
 
         // successful case
@@ -2551,6 +2954,8 @@ fn test_sell_nft_unhappy_cw20() {
             contract_addr: Addr::unchecked(OW_1155_ADDR),
             token_id: String::from(SELLABLE_NFT_CW20),
             asker: None,
+            reserved_for: None,
+            multi_winner: None,
         };
         // fail when trying to create an auction
         let mut auction_msg = ExecuteMsg::AskAuctionNft(ask_msg.clone());
@@ -2602,6 +3007,7 @@ fn test_sell_nft_unhappy_cw20() {
                     token_id: String::from(SELLABLE_NFT_CW20),
                     amount: Uint128::from(100u128),
                     seller: None,
+                    reserved_for: None,
                 })
             ),
             Err(ContractError::NotWhilteList { .. })
@@ -2634,26 +3040,29 @@ fn withdraw_offering() {
             token_id: String::from(SELLABLE_NFT_NATIVE),
             amount: Uint128::from(10u64),
             seller: None,
+            reserved_for: None,
         });
         let _res = manager.execute(info, msg).unwrap();
 
         // Offering should be listed
-        let res: Vec<Offering> = from_json(
+        let res: PageResponse<Offering> = from_json(
             &manager
                 .query(QueryMsg::Offering(MarketQueryMsg::GetOfferings {
                     offset: None,
                     limit: None,
                     order: None,
+                    start_after: None,
+                    with_total: None,
                 }))
                 .unwrap(),
         )
         .unwrap();
-        assert_eq!(1, res.len());
+        assert_eq!(1, res.items.len());
 
         // withdraw offering
         let withdraw_info_unauthorized = mock_info("sellerr", &coins(2, DENOM));
         let withdraw_msg = ExecuteMsg::WithdrawNft {
-            offering_id: res[0].id.clone().unwrap(),
+            offering_id: res.items[0].id.unwrap(),
         };
 
         // unhappy path unauthorized
@@ -2666,17 +3075,19 @@ fn withdraw_offering() {
         let _res = manager.execute(withdraw_info, withdraw_msg).unwrap();
 
         // Offering should be removed
-        let res2: Vec<Offering> = from_json(
+        let res2: PageResponse<Offering> = from_json(
             &manager
                 .query(QueryMsg::Offering(MarketQueryMsg::GetOfferings {
                     offset: None,
                     limit: None,
                     order: None,
+                    start_after: None,
+                    with_total: None,
                 }))
                 .unwrap(),
         )
         .unwrap();
-        assert_eq!(0, res2.len());
+        assert_eq!(0, res2.items.len());
     }
 }
 
@@ -2707,6 +3118,7 @@ fn test_buy_nft_unhappy() {
             token_id: String::from(SELLABLE_NFT_NATIVE),
             amount: Uint128::from(10u64),
             seller: None,
+            reserved_for: None,
         });
         let _res = manager.execute(info.clone(), msg.clone()).unwrap();
 
@@ -2752,6 +3164,7 @@ fn test_buy_nft_unhappy_cw20() {
             token_id: String::from(SELLABLE_NFT_CW20),
             amount: Uint128::from(10u64),
             seller: None,
+            reserved_for: None,
         });
         let _res = manager.execute(info.clone(), msg.clone()).unwrap();
 
@@ -2763,6 +3176,326 @@ fn test_buy_nft_unhappy_cw20() {
     }
 }
 
+#[test]
+fn test_buy_reserved_nft() {
+    unsafe {
+        let manager = DepsManager::get_new();
+        let provider_info = mock_info("creator", &vec![coin(50, DENOM)]);
+
+        handle_approve(manager);
+
+        let mint_msg = ExecuteMsg::MintNft(MintMsg {
+            contract_addr: Addr::unchecked(OW_1155_ADDR),
+            creator: Addr::unchecked(PROVIDER),
+            mint: MintIntermediate {
+                mint: MintStruct {
+                    to: String::from(PROVIDER),
+                    value: Uint128::from(100u64),
+                    token_id: String::from(SELLABLE_NFT),
+                    co_owner: None,
+                },
+            },
+            creator_type: String::from("cxacx"),
+            royalty: None,
+            co_owner_weights: None,
+        });
+        manager.execute(provider_info.clone(), mint_msg).unwrap();
+
+        // nobody could buy a sale reserved for an empty list
+        let msg = ExecuteMsg::SellNft(SellNft {
+            contract_addr: Addr::unchecked(OW_1155_ADDR),
+            per_price: Uint128::from(100u128),
+            token_id: String::from(SELLABLE_NFT_NATIVE),
+            amount: Uint128::from(100u64),
+            seller: None,
+            reserved_for: Some(vec![]),
+        });
+        assert!(matches!(
+            manager.execute(provider_info.clone(), msg),
+            Err(ContractError::Reserved(
+                ReservedError::EmptyReservedBuyers {}
+            ))
+        ));
+
+        // private sale for the negotiated buyer only
+        let msg = ExecuteMsg::SellNft(SellNft {
+            contract_addr: Addr::unchecked(OW_1155_ADDR),
+            per_price: Uint128::from(100u128),
+            token_id: String::from(SELLABLE_NFT_NATIVE),
+            amount: Uint128::from(100u64),
+            seller: None,
+            reserved_for: Some(vec![Addr::unchecked("buyer")]),
+        });
+        manager.execute(provider_info, msg).unwrap();
+
+        let offering: Offering = from_json(
+            &manager
+                .query(QueryMsg::Offering(MarketQueryMsg::GetOffering {
+                    offering_id: 1,
+                }))
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(offering.reserved_for, Some(vec![Addr::unchecked("buyer")]));
+
+        let buy_msg = ExecuteMsg::BuyNft {
+            offering_id: 1,
+            amount: Uint128::from(50u64),
+        };
+        assert!(matches!(
+            manager.execute(
+                mock_info("front_runner", &coins(5000, DENOM)),
+                buy_msg.clone()
+            ),
+            Err(ContractError::Reserved(
+                ReservedError::NotReservedBuyer { .. }
+            ))
+        ));
+
+        manager
+            .execute(mock_info("buyer", &coins(5000, DENOM)), buy_msg)
+            .unwrap();
+    }
+}
+
+#[test]
+fn test_buy_order_fill_and_match() {
+    unsafe {
+        let manager = DepsManager::get_new();
+        handle_approve(manager);
+
+        let query_order_book = |manager: &DepsManager| -> OrderBookResponse {
+            from_json(
+                &manager
+                    .query(QueryMsg::GetOrderBook {
+                        contract_addr: Addr::unchecked(OW_1155_ADDR),
+                        token_id: String::from(SELLABLE_NFT_NATIVE),
+                        limit: None,
+                    })
+                    .unwrap(),
+            )
+            .unwrap()
+        };
+        let query_balance = |manager: &DepsManager, owner: &str| -> Uint128 {
+            let result: BalanceResponse = from_json(
+                &ow1155::contract::query(
+                    manager.ow1155.as_ref(),
+                    mock_env(OW_1155_ADDR),
+                    Cw1155QueryMsg::Balance {
+                        owner: owner.to_string(),
+                        token_id: String::from(SELLABLE_NFT),
+                    },
+                )
+                .unwrap(),
+            )
+            .unwrap();
+            result.balance
+        };
+
+        // the buyer escrows 10 units at 20, the extra funds are refunded
+        let results = manager
+            .execute(
+                mock_info("buyer", &coins(300, DENOM)),
+                ExecuteMsg::CreateBuyOrder(BuyOrderMsg {
+                    contract_addr: Addr::unchecked(OW_1155_ADDR),
+                    token_id: String::from(SELLABLE_NFT_NATIVE),
+                    amount: Uint128::from(10u64),
+                    per_price: Uint128::from(20u64),
+                }),
+            )
+            .unwrap();
+        assert_eq!(
+            results.last().unwrap().messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: String::from("buyer"),
+                amount: coins(100, DENOM),
+            })
+        );
+
+        let book = query_order_book(manager);
+        assert_eq!(book.best_bid, Some(Uint128::from(20u64)));
+        assert_eq!(
+            book.bids,
+            vec![BookLevel {
+                per_price: Uint128::from(20u64),
+                amount: Uint128::from(10u64),
+                count: 1,
+            }]
+        );
+
+        // a holder fills 4 units by sending them to the marketplace
+        ow1155::contract::execute(
+            manager.ow1155.as_mut(),
+            mock_env(OW_1155_ADDR),
+            mock_info(PROVIDER, &[]),
+            Cw1155ExecuteMsg::SendFrom {
+                from: String::from(PROVIDER),
+                to: String::from(MARKET_ADDR),
+                token_id: String::from(SELLABLE_NFT),
+                value: Uint128::from(4u64),
+                msg: None,
+            },
+        )
+        .unwrap();
+        let fill_msg = ExecuteMsg::Receive(ReceiveMsg::Cw1155(Cw1155ReceiveMsg {
+            operator: String::from(PROVIDER),
+            from: Some(String::from(PROVIDER)),
+            token_id: String::from(SELLABLE_NFT),
+            amount: Uint128::from(4u64),
+            msg: to_json_binary(&Cw1155HookMsg::FillBuyOrder { order_id: 1 }).unwrap(),
+        }));
+        // only the nft contract can notify the marketplace
+        assert!(matches!(
+            manager.execute(mock_info(PROVIDER, &[]), fill_msg.clone()),
+            Err(ContractError::Unauthorized { .. })
+        ));
+        manager
+            .execute(mock_info(OW_1155_ADDR, &[]), fill_msg)
+            .unwrap();
+        assert_eq!(query_balance(manager, "buyer"), Uint128::from(4u64));
+
+        let order: BuyOrder = from_json(
+            &manager
+                .query(QueryMsg::GetBuyOrder { order_id: 1 })
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(order.amount, Uint128::from(6u64));
+
+        // a new offering below the bid crosses the resting order, only the left is offered
+        manager
+            .execute(
+                mock_info("seller", &[]),
+                ExecuteMsg::SellNft(SellNft {
+                    contract_addr: Addr::unchecked(OW_1155_ADDR),
+                    per_price: Uint128::from(15u64),
+                    token_id: String::from(SELLABLE_NFT_NATIVE),
+                    amount: Uint128::from(8u64),
+                    seller: None,
+                    reserved_for: None,
+                }),
+            )
+            .unwrap();
+        assert_eq!(query_balance(manager, "buyer"), Uint128::from(10u64));
+        assert_eq!(query_balance(manager, "seller"), Uint128::from(494u64));
+        assert!(manager
+            .query(QueryMsg::GetBuyOrder { order_id: 1 })
+            .is_err());
+
+        let book = query_order_book(manager);
+        assert_eq!(book.best_bid, None);
+        assert_eq!(book.best_ask, Some(Uint128::from(15u64)));
+        assert_eq!(book.asks[0].amount, Uint128::from(2u64));
+    }
+}
+
+#[test]
+fn test_buy_order_funds_and_price_priority() {
+    unsafe {
+        let manager = DepsManager::get_new();
+        handle_approve(manager);
+
+        let buy_order = |per_price: u64| {
+            ExecuteMsg::CreateBuyOrder(BuyOrderMsg {
+                contract_addr: Addr::unchecked(OW_1155_ADDR),
+                token_id: String::from(SELLABLE_NFT_NATIVE),
+                amount: Uint128::from(5u64),
+                per_price: Uint128::from(per_price),
+            })
+        };
+
+        // only the payment asset of the order can be escrowed
+        assert!(matches!(
+            manager.execute(
+                mock_info("buyer", &[coin(100, "junk"), coin(100, DENOM)]),
+                buy_order(20),
+            ),
+            Err(ContractError::InvalidDenomAmount {})
+        ));
+        assert!(matches!(
+            manager.execute(mock_info("buyer", &coins(100, "junk")), buy_order(20)),
+            Err(ContractError::InvalidDenomAmount {})
+        ));
+        assert!(matches!(
+            manager.execute(mock_info("buyer", &coins(99, DENOM)), buy_order(20)),
+            Err(ContractError::InsufficientFunds {})
+        ));
+
+        // order 1 at 10, orders 2 and 3 at 20
+        for (buyer, per_price) in [("buyer", 10u64), ("buyer1", 20), ("buyer2", 20)] {
+            manager
+                .execute(
+                    mock_info(buyer, &coins(per_price as u128 * 5, DENOM)),
+                    buy_order(per_price),
+                )
+                .unwrap();
+        }
+        let book: OrderBookResponse = from_json(
+            manager
+                .query(QueryMsg::GetOrderBook {
+                    contract_addr: Addr::unchecked(OW_1155_ADDR),
+                    token_id: String::from(SELLABLE_NFT_NATIVE),
+                    limit: None,
+                })
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            book.bids,
+            vec![
+                BookLevel {
+                    per_price: Uint128::from(20u64),
+                    amount: Uint128::from(10u64),
+                    count: 2,
+                },
+                BookLevel {
+                    per_price: Uint128::from(10u64),
+                    amount: Uint128::from(5u64),
+                    count: 1,
+                }
+            ]
+        );
+
+        // the best price is matched first, then the oldest order, the order below the offering is left
+        let results = manager
+            .execute(
+                mock_info("seller", &[]),
+                ExecuteMsg::SellNft(SellNft {
+                    contract_addr: Addr::unchecked(OW_1155_ADDR),
+                    per_price: Uint128::from(15u64),
+                    token_id: String::from(SELLABLE_NFT_NATIVE),
+                    amount: Uint128::from(8u64),
+                    seller: None,
+                    reserved_for: None,
+                }),
+            )
+            .unwrap();
+        let matched: Vec<String> = results
+            .last()
+            .unwrap()
+            .attributes
+            .iter()
+            .filter(|attr| attr.key.eq("matched_buy_order"))
+            .map(|attr| attr.value.clone())
+            .collect();
+        assert_eq!(matched, vec!["2", "3"]);
+        let order: BuyOrder = from_json(
+            manager
+                .query(QueryMsg::GetBuyOrder { order_id: 3 })
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(order.amount, Uint128::from(2u64));
+        let order: BuyOrder = from_json(
+            manager
+                .query(QueryMsg::GetBuyOrder { order_id: 1 })
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(order.amount, Uint128::from(5u64));
+    }
+}
+
 #[test]
 fn test_mint() {
     unsafe {
@@ -2782,6 +3515,7 @@ fn test_mint() {
             },
             creator_type: String::from("cxacx"),
             royalty: None,
+            co_owner_weights: None,
         };
         let mut mint_msg = ExecuteMsg::MintNft(mint.clone());
         manager
@@ -2911,6 +3645,7 @@ fn test_change_creator_happy() {
             },
             creator_type: String::from("cxacx"),
             royalty: None,
+            co_owner_weights: None,
         };
         let mint_msg = ExecuteMsg::MintNft(mint.clone());
         manager
@@ -2981,6 +3716,7 @@ fn test_change_creator_unhappy() {
             },
             creator_type: String::from("cxacx"),
             royalty: None,
+            co_owner_weights: None,
         };
         let mint_msg = ExecuteMsg::MintNft(mint.clone());
         manager
@@ -3031,6 +3767,7 @@ fn transfer_nft_directly_happy_path() {
             },
             creator_type: String::from("creator"),
             royalty: None,
+            co_owner_weights: None,
         };
         let mint_msg = ExecuteMsg::MintNft(mint.clone());
         manager
@@ -3110,6 +3847,7 @@ fn transfer_nft_directly_unhappy_path() {
             },
             creator_type: String::from("creator"),
             royalty: None,
+            co_owner_weights: None,
         };
         let mint_msg = ExecuteMsg::MintNft(mint.clone());
         manager
@@ -3122,6 +3860,7 @@ fn transfer_nft_directly_unhappy_path() {
             token_id: String::from(token_id),
             amount: Uint128::from(amount),
             seller: None,
+            reserved_for: None,
         });
 
         // insufficient amount case creator
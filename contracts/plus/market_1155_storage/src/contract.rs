@@ -4,23 +4,30 @@ use cosmwasm_std::entry_point;
 use crate::error::ContractError;
 use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, UpdateContractMsg};
 use crate::state::{
-    get_contract_token_id, get_unique_offering, increment_offerings, offerings, ContractInfo,
-    CONTRACT_INFO,
+    get_contract_token_id, get_price_id, get_unique_offering, increment_offerings, offerings,
+    ContractInfo, CONTRACT_INFO, REINDEX_CURSOR,
 };
 use market_1155::{MarketExecuteMsg, MarketQueryMsg, Offering};
 
 use cosmwasm_std::{
     attr, to_json_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Order, Record, Response,
-    StdError, StdResult,
+    StdError, StdResult, Storage, Uint128,
 };
+use cw2::set_contract_version;
+use migration::{migrate_version, MigrationStep};
 
-use cw_storage_plus::Bound;
+use cw_storage_plus::{Bound, PkOwned};
+use market::{
+    count_total, AssetInfo, MarketHubContract, PageResponse, Pagination, DEFAULT_LIMIT, MAX_LIMIT,
+    PAYMENT_1155_STORAGE,
+};
+use market_payment::PaymentStorage;
 use std::convert::TryInto;
 use std::usize;
 
-// settings for pagination
-const MAX_LIMIT: u8 = 100;
-const DEFAULT_LIMIT: u8 = 20;
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:market_1155_storage";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 // Note, you can use StdResult in some functions where you do not
 // make use of the custom errors
@@ -31,6 +38,7 @@ pub fn instantiate(
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     // first time deploy, it will not know about the implementation
     let info = ContractInfo {
         governance: msg.governance,
@@ -56,6 +64,7 @@ pub fn execute(
             MarketExecuteMsg::RemoveOffering { id } => try_withdraw_offering(deps, info, env, id),
         },
         ExecuteMsg::UpdateInfo(msg) => try_update_info(deps, info, env, msg),
+        ExecuteMsg::ReindexOfferings { limit } => try_reindex_offerings(deps, info, limit),
     }
 }
 
@@ -64,34 +73,95 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Msg(auction_query) => match auction_query {
             MarketQueryMsg::GetOfferings {
+                start_after,
+                limit,
+                offset,
+                order,
+                with_total,
+            } => to_json_binary(&query_offerings(
+                deps,
+                start_after,
                 limit,
                 offset,
                 order,
-            } => to_json_binary(&query_offerings(deps, limit, offset, order)?),
+                with_total,
+            )?),
             MarketQueryMsg::GetOfferingsBySeller {
                 seller,
+                start_after,
                 limit,
                 offset,
                 order,
+                with_total,
             } => to_json_binary(&query_offerings_by_seller(
-                deps, seller, limit, offset, order,
+                deps,
+                seller,
+                start_after,
+                limit,
+                offset,
+                order,
+                with_total,
             )?),
             MarketQueryMsg::GetOfferingsByContract {
                 contract,
+                start_after,
                 limit,
                 offset,
                 order,
+                with_total,
             } => to_json_binary(&query_offerings_by_contract(
-                deps, contract, limit, offset, order,
+                deps,
+                contract,
+                start_after,
+                limit,
+                offset,
+                order,
+                with_total,
             )?),
             MarketQueryMsg::GetOfferingsByContractTokenId {
                 contract,
                 token_id,
+                start_after,
                 limit,
                 offset,
                 order,
+                with_total,
             } => to_json_binary(&query_offerings_by_contract_token_id(
-                deps, contract, token_id, limit, offset, order,
+                deps,
+                contract,
+                token_id,
+                start_after,
+                limit,
+                offset,
+                order,
+                with_total,
+            )?),
+            MarketQueryMsg::GetFloorOffering {
+                contract,
+                token_id,
+                asset_info,
+            } => to_json_binary(&query_floor_offering(deps, contract, token_id, asset_info)?),
+            MarketQueryMsg::GetOfferingsByPriceRange {
+                contract,
+                token_id,
+                min_price,
+                max_price,
+                asset_info,
+                start_after,
+                limit,
+                order,
+                with_total,
+            } => to_json_binary(&query_offerings_by_price_range(
+                deps,
+                contract,
+                token_id,
+                min_price,
+                max_price,
+                asset_info,
+                start_after,
+                limit,
+                order,
+                with_total,
             )?),
             MarketQueryMsg::GetOffering { offering_id } => {
                 to_json_binary(&query_offering(deps, offering_id)?)
@@ -185,46 +255,42 @@ pub fn try_withdraw_offering(
 
 // ============================== Query Handlers ==============================
 
-fn _get_range_params(
-    limit: Option<u8>,
+fn offering_pagination(
+    start_after: Option<Binary>,
+    limit: Option<u32>,
     offset: Option<u64>,
     order: Option<u8>,
-) -> (usize, Option<Bound>, Option<Bound>, Order) {
-    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
-    let mut min = None;
-    let mut max = None;
-    let mut order_enum = Order::Ascending;
-    if let Some(num) = order {
-        if num == 2 {
-            order_enum = Order::Descending;
-        }
-    }
-
-    // if there is offset, assign to min or max
-    if let Some(offset) = offset {
-        let offset_value = Some(Bound::Exclusive(offset.to_be_bytes().to_vec()));
-        match order_enum {
-            Order::Ascending => min = offset_value,
-            Order::Descending => max = offset_value,
-        }
-    };
-    (limit, min, max, order_enum)
+) -> Pagination {
+    Pagination::new(
+        start_after,
+        offset.map(|offset| offset.to_be_bytes().to_vec()),
+        limit,
+        order,
+        Order::Ascending,
+    )
 }
 
 pub fn query_offerings(
     deps: Deps,
-    limit: Option<u8>,
+    start_after: Option<Binary>,
+    limit: Option<u32>,
     offset: Option<u64>,
     order: Option<u8>,
-) -> StdResult<Vec<Offering>> {
-    let (limit, min, max, order_enum) = _get_range_params(limit, offset, order);
-
-    let offerings_result: StdResult<Vec<Offering>> = offerings()
-        .range(deps.storage, min, max, order_enum)
-        .take(limit)
-        .map(|kv_item| parse_offering(kv_item))
-        .collect();
-    Ok(offerings_result?)
+    with_total: Option<bool>,
+) -> StdResult<PageResponse<Offering>> {
+    let pagination = offering_pagination(start_after, limit, offset, order);
+    let (min, max, order_enum) = pagination.bounds();
+    let total = count_total(with_total, || {
+        offerings()
+            .range(deps.storage, None, None, Order::Ascending)
+            .count()
+    });
+
+    pagination.page_response(
+        offerings().range(deps.storage, min, max, order_enum),
+        parse_offering,
+        total,
+    )
 }
 
 pub fn query_offering_ids(deps: Deps) -> StdResult<Vec<u64>> {
@@ -239,65 +305,226 @@ pub fn query_offering_ids(deps: Deps) -> StdResult<Vec<u64>> {
 pub fn query_offerings_by_seller(
     deps: Deps,
     seller: Addr,
-    limit: Option<u8>,
+    start_after: Option<Binary>,
+    limit: Option<u32>,
     offset: Option<u64>,
     order: Option<u8>,
-) -> StdResult<Vec<Offering>> {
-    let (limit, min, max, order_enum) = _get_range_params(limit, offset, order);
-    let offerings_result: StdResult<Vec<Offering>> = offerings()
-        .idx
-        .seller
-        .items(deps.storage, seller.as_bytes(), min, max, order_enum)
-        .take(limit)
-        .map(|kv_item| parse_offering(kv_item))
-        .collect();
-
-    Ok(offerings_result?)
+    with_total: Option<bool>,
+) -> StdResult<PageResponse<Offering>> {
+    let pagination = offering_pagination(start_after, limit, offset, order);
+    let (min, max, order_enum) = pagination.bounds();
+    let total = count_total(with_total, || {
+        offerings()
+            .idx
+            .seller
+            .pks(
+                deps.storage,
+                seller.as_bytes(),
+                None,
+                None,
+                Order::Ascending,
+            )
+            .count()
+    });
+
+    pagination.page_response(
+        offerings()
+            .idx
+            .seller
+            .items(deps.storage, seller.as_bytes(), min, max, order_enum),
+        parse_offering,
+        total,
+    )
 }
 
 pub fn query_offerings_by_contract(
     deps: Deps,
     contract: Addr,
-    limit: Option<u8>,
+    start_after: Option<Binary>,
+    limit: Option<u32>,
     offset: Option<u64>,
     order: Option<u8>,
-) -> StdResult<Vec<Offering>> {
-    let (limit, min, max, order_enum) = _get_range_params(limit, offset, order);
-    let offerings_result: StdResult<Vec<Offering>> = offerings()
-        .idx
-        .contract
-        .items(deps.storage, contract.as_bytes(), min, max, order_enum)
-        .take(limit)
-        .map(|kv_item| parse_offering(kv_item))
-        .collect();
-
-    Ok(offerings_result?)
+    with_total: Option<bool>,
+) -> StdResult<PageResponse<Offering>> {
+    let pagination = offering_pagination(start_after, limit, offset, order);
+    let (min, max, order_enum) = pagination.bounds();
+    let total = count_total(with_total, || {
+        offerings()
+            .idx
+            .contract
+            .pks(
+                deps.storage,
+                contract.as_bytes(),
+                None,
+                None,
+                Order::Ascending,
+            )
+            .count()
+    });
+
+    pagination.page_response(
+        offerings()
+            .idx
+            .contract
+            .items(deps.storage, contract.as_bytes(), min, max, order_enum),
+        parse_offering,
+        total,
+    )
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn query_offerings_by_contract_token_id(
     deps: Deps,
     contract: Addr,
     token_id: String,
-    limit: Option<u8>,
+    start_after: Option<Binary>,
+    limit: Option<u32>,
     offset: Option<u64>,
     order: Option<u8>,
-) -> StdResult<Vec<Offering>> {
-    let (limit, min, max, order_enum) = _get_range_params(limit, offset, order);
-    let offerings_result: StdResult<Vec<Offering>> = offerings()
-        .idx
-        .contract_token_id
-        .items(
+    with_total: Option<bool>,
+) -> StdResult<PageResponse<Offering>> {
+    let pagination = offering_pagination(start_after, limit, offset, order);
+    let (min, max, order_enum) = pagination.bounds();
+    let contract_token_id = get_contract_token_id(&contract, &token_id);
+    let total = count_total(with_total, || {
+        offerings()
+            .idx
+            .contract_token_id
+            .pks(
+                deps.storage,
+                &contract_token_id,
+                None,
+                None,
+                Order::Ascending,
+            )
+            .count()
+    });
+
+    pagination.page_response(
+        offerings().idx.contract_token_id.items(
             deps.storage,
-            &get_contract_token_id(&contract, &token_id),
+            &contract_token_id,
             min,
             max,
             order_enum,
-        )
-        .take(limit)
-        .map(|kv_item| parse_offering(kv_item))
-        .collect();
+        ),
+        parse_offering,
+        total,
+    )
+}
+
+// both price bounds are inclusive, the max bound covers every offering id at the max price
+fn price_bounds(
+    min_price: Option<Uint128>,
+    max_price: Option<Uint128>,
+) -> (Option<Bound>, Option<Bound>) {
+    (
+        min_price.map(|price| Bound::Inclusive(price.u128().to_be_bytes().to_vec())),
+        max_price.map(|price| Bound::Inclusive(get_price_id(price, u64::MAX).0)),
+    )
+}
+
+// offerings of the token ordered by per price, keyed by the price index key so it can be used as
+// cursor. an asset selects its own index, so the other assets are never read
+fn offerings_by_price<'c>(
+    storage: &'c dyn Storage,
+    contract: &Addr,
+    token_id: &str,
+    min: Option<Bound>,
+    max: Option<Bound>,
+    order: Order,
+    asset_info: Option<AssetInfo>,
+) -> impl Iterator<Item = StdResult<Record<Record<Offering>>>> + 'c {
+    let records = match asset_info {
+        Some(asset_info) => offerings()
+            .idx
+            .contract_token_id_asset_price
+            .prefix((
+                PkOwned(get_contract_token_id(contract, token_id)),
+                PkOwned(asset_info.as_key()),
+            ))
+            .range(storage, min, max, order),
+        None => offerings()
+            .idx
+            .contract_token_id_price
+            .prefix((
+                PkOwned(contract.as_bytes().to_vec()),
+                PkOwned(token_id.as_bytes().to_vec()),
+            ))
+            .range(storage, min, max, order),
+    };
+    records.map(|item| {
+        item.map(|(k, offering)| {
+            let key = get_price_id(offering.per_price, offering.id.unwrap_or_default());
+            (key.0, (k, offering))
+        })
+    })
+}
 
-    Ok(offerings_result?)
+pub fn query_floor_offering(
+    deps: Deps,
+    contract: Addr,
+    token_id: String,
+    asset_info: Option<AssetInfo>,
+) -> StdResult<Option<Offering>> {
+    offerings_by_price(
+        deps.storage,
+        &contract,
+        &token_id,
+        None,
+        None,
+        Order::Ascending,
+        asset_info,
+    )
+    .next()
+    .map(|item| item.and_then(|(_, kv_item)| parse_offering(kv_item)))
+    .transpose()
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn query_offerings_by_price_range(
+    deps: Deps,
+    contract: Addr,
+    token_id: String,
+    min_price: Option<Uint128>,
+    max_price: Option<Uint128>,
+    asset_info: Option<AssetInfo>,
+    start_after: Option<Binary>,
+    limit: Option<u32>,
+    order: Option<u8>,
+    with_total: Option<bool>,
+) -> StdResult<PageResponse<Offering>> {
+    let mut pagination = Pagination::new(start_after, None, limit, order, Order::Ascending);
+    let (min_bound, max_bound) = price_bounds(min_price, max_price);
+    let total = count_total(with_total, || {
+        offerings_by_price(
+            deps.storage,
+            &contract,
+            &token_id,
+            min_bound.clone(),
+            max_bound.clone(),
+            Order::Ascending,
+            asset_info.clone(),
+        )
+        .count()
+    });
+    // the cursor replaces the price bound on the side the page starts from
+    pagination.min = pagination.min.or(min_bound);
+    pagination.max = pagination.max.or(max_bound);
+    let (min, max, order_enum) = pagination.bounds();
+    pagination.page_response(
+        offerings_by_price(
+            deps.storage,
+            &contract,
+            &token_id,
+            min,
+            max,
+            order_enum,
+            asset_info,
+        ),
+        |(_, kv_item)| parse_offering(kv_item),
+        total,
+    )
 }
 
 pub fn query_offering(deps: Deps, offering_id: u64) -> StdResult<Offering> {
@@ -327,21 +554,89 @@ pub fn query_contract_info(deps: Deps) -> StdResult<ContractInfo> {
     CONTRACT_INFO.load(deps.storage)
 }
 
-fn parse_offering<'a>(item: StdResult<Record<Offering>>) -> StdResult<Offering> {
-    item.and_then(|(k, offering)| {
-        // will panic if length is greater than 8, but we can make sure it is u64
-        // try_into will box vector to fixed array
-        let value = k
-            .try_into()
-            .map_err(|_| StdError::generic_err("Cannot parse offering key"))?;
-        let id: u64 = u64::from_be_bytes(value);
-        Ok(Offering {
-            id: Some(id),
-            ..offering
-        })
+fn parse_offering((k, offering): Record<Offering>) -> StdResult<Offering> {
+    // will panic if length is greater than 8, but we can make sure it is u64
+    // try_into will box vector to fixed array
+    let value = k
+        .try_into()
+        .map_err(|_| StdError::generic_err("Cannot parse offering key"))?;
+    let id: u64 = u64::from_be_bytes(value);
+    Ok(Offering {
+        id: Some(id),
+        ..offering
     })
 }
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
-    Ok(Response::default())
+pub fn migrate(deps: DepsMut, env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+    migrate_version(deps, &env, CONTRACT_NAME, CONTRACT_VERSION, MIGRATIONS)
+}
+
+const MIGRATIONS: &[MigrationStep] = &[MigrationStep {
+    version: "0.2.0",
+    name: "reindex_offerings",
+    run: reindex_offerings,
+}];
+
+fn reindex_offerings(deps: DepsMut, _env: &Env) -> StdResult<()> {
+    // re-saving every offering does not fit in one transaction, the creator runs it in batches
+    // with ReindexOfferings. Until it is done the price queries miss the older offerings
+    REINDEX_CURSOR.save(deps.storage, &vec![])
+}
+
+/// saving the offerings again fills the price indexes for offerings created before them. The
+/// offerings without a recorded asset get the one of their payment, the default denom if none
+pub fn try_reindex_offerings(
+    deps: DepsMut,
+    info: MessageInfo,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let ContractInfo {
+        governance,
+        creator,
+    } = CONTRACT_INFO.load(deps.storage)?;
+    if info.sender.ne(&creator) {
+        return Err(ContractError::Unauthorized {
+            sender: info.sender.to_string(),
+        });
+    }
+    let cursor = match REINDEX_CURSOR.may_load(deps.storage)? {
+        Some(cursor) => cursor,
+        None => {
+            return Ok(Response::new().add_attributes(vec![
+                attr("action", "reindex_offerings"),
+                attr("done", "true"),
+            ]))
+        }
+    };
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let min = (!cursor.is_empty()).then_some(Bound::Exclusive(cursor));
+    let batch = offerings()
+        .range(deps.storage, min, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let governance = MarketHubContract(governance);
+    for (k, mut offering) in batch.iter().cloned() {
+        if offering.asset_info.is_none() {
+            offering.asset_info = Some(governance.get_offering_payment(
+                &deps.querier,
+                PAYMENT_1155_STORAGE,
+                offering.contract_addr.clone(),
+                &offering.token_id,
+                Some(offering.seller.clone()),
+            )?);
+        }
+        offerings().save(deps.storage, &k, &offering)?;
+    }
+    let done = batch.len() < limit;
+    match batch.last() {
+        Some((k, _)) if !done => REINDEX_CURSOR.save(deps.storage, k)?,
+        _ => REINDEX_CURSOR.remove(deps.storage),
+    }
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "reindex_offerings"),
+        attr("reindexed", batch.len().to_string()),
+        attr("done", done.to_string()),
+    ]))
 }
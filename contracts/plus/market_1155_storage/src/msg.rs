@@ -1,8 +1,11 @@
+use cosmwasm_schema::QueryResponses;
 use cosmwasm_std::Addr;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use market_1155::{MarketExecuteMsg, MarketQueryMsg};
+use market_1155::{MarketExecuteMsg, MarketQueryMsg, MarketQueryResponse};
+
+use crate::state::ContractInfo;
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InstantiateMsg {
@@ -14,6 +17,8 @@ pub struct InstantiateMsg {
 pub enum ExecuteMsg {
     Msg(MarketExecuteMsg),
     UpdateInfo(UpdateContractMsg),
+    // re-save the next offerings of the reindex queued by the migration, creator only
+    ReindexOfferings { limit: Option<u32> },
     // other implementation
 }
 
@@ -23,11 +28,13 @@ pub struct UpdateContractMsg {
     pub creator: Option<Addr>,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, QueryResponses)]
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
     // GetOfferings returns a list of all offerings
+    #[returns(MarketQueryResponse)]
     Msg(MarketQueryMsg),
+    #[returns(ContractInfo)]
     GetContractInfo {},
 }
 
@@ -1,8 +1,9 @@
+use market::AssetInfo;
 use market_1155::Offering;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::{Addr, StdResult, Storage};
+use cosmwasm_std::{Addr, StdResult, Storage, Uint128};
 use cw_storage_plus::{Index, IndexList, IndexedMap, Item, MultiIndex, PkOwned, UniqueIndex};
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
@@ -15,6 +16,8 @@ pub struct ContractInfo {
 pub const OFFERINGS_COUNT: Item<u64> = Item::new("num_offerings");
 /// ANNOTATIONS is a map which maps the annotation id to an annotation request. annotation id is derived from ANNOTATION_COUNT.
 pub const CONTRACT_INFO: Item<ContractInfo> = Item::new("marketplace_info");
+/// last offering key re-saved by the running reindex, empty when it has not started yet
+pub const REINDEX_CURSOR: Item<Vec<u8>> = Item::new("reindex_cursor");
 
 pub fn num_offerings(storage: &dyn Storage) -> StdResult<u64> {
     Ok(OFFERINGS_COUNT.may_load(storage)?.unwrap_or_default())
@@ -31,6 +34,10 @@ pub struct OfferingIndexes<'a> {
     pub contract: MultiIndex<'a, Offering>,
     pub contract_token_id: MultiIndex<'a, Offering>,
     pub unique_offering: UniqueIndex<'a, PkOwned, Offering>,
+    // contract + token id => per price + id, so the offerings of a token can be ranged by price
+    pub contract_token_id_price: UniqueIndex<'a, (PkOwned, PkOwned, PkOwned), Offering>,
+    // contract + token id, asset => per price + id, so the offerings paid in an asset are ranged without a scan
+    pub contract_token_id_asset_price: UniqueIndex<'a, (PkOwned, PkOwned, PkOwned), Offering>,
 }
 
 impl<'a> IndexList<Offering> for OfferingIndexes<'a> {
@@ -40,6 +47,8 @@ impl<'a> IndexList<Offering> for OfferingIndexes<'a> {
             &self.contract,
             &self.contract_token_id,
             &self.unique_offering,
+            &self.contract_token_id_price,
+            &self.contract_token_id_asset_price,
         ];
         Box::new(v.into_iter())
     }
@@ -60,6 +69,13 @@ pub fn get_contract_token_id(contract: &Addr, token_id: &str) -> Vec<u8> {
     vec
 }
 
+// per price + offering id => unique id, big endian so the keys sort by price
+pub fn get_price_id(per_price: Uint128, id: u64) -> PkOwned {
+    let mut vec = per_price.u128().to_be_bytes().to_vec();
+    vec.extend(id.to_be_bytes());
+    PkOwned(vec)
+}
+
 // this IndexedMap instance has a lifetime
 pub fn offerings<'a>() -> IndexedMap<'a, &'a [u8], Offering, OfferingIndexes<'a>> {
     let indexes = OfferingIndexes {
@@ -82,6 +98,32 @@ pub fn offerings<'a>() -> IndexedMap<'a, &'a [u8], Offering, OfferingIndexes<'a>
             |o| get_unique_offering(&o.contract_addr, &o.token_id, o.seller.as_str()),
             "offerings__unique",
         ),
+        // the offering id is always set before the offering is saved
+        contract_token_id_price: UniqueIndex::new(
+            |o| {
+                (
+                    PkOwned(o.contract_addr.as_bytes().to_vec()),
+                    PkOwned(o.token_id.as_bytes().to_vec()),
+                    get_price_id(o.per_price, o.id.unwrap_or_default()),
+                )
+            },
+            "offerings__contract_tokenid_price",
+        ),
+        contract_token_id_asset_price: UniqueIndex::new(
+            |o| {
+                (
+                    PkOwned(get_contract_token_id(&o.contract_addr, &o.token_id)),
+                    PkOwned(
+                        o.asset_info
+                            .as_ref()
+                            .map(AssetInfo::as_key)
+                            .unwrap_or_default(),
+                    ),
+                    get_price_id(o.per_price, o.id.unwrap_or_default()),
+                )
+            },
+            "offerings__contract_tokenid_asset_price",
+        ),
     };
     IndexedMap::new("offerings", indexes)
 }
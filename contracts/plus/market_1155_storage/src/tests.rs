@@ -1,12 +1,19 @@
 use std::ops::Mul;
 
 use crate::contract::*;
+use crate::error::ContractError;
 use crate::msg::*;
 use cosmwasm_std::testing::mock_dependencies_with_balance;
 use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockQuerier, MockStorage};
 use cosmwasm_std::Decimal;
-use cosmwasm_std::{coin, coins, from_json, Addr, Order, OwnedDeps, Uint128};
+use cosmwasm_std::{
+    attr, coin, coins, from_json, to_json_binary, Addr, ContractResult, Order, OwnedDeps,
+    SystemResult, Uint128, WasmQuery,
+};
+use cw2::set_contract_version;
+use cw_storage_plus::Map;
 
+use market::{AssetInfo, PageResponse};
 use market_1155::MarketExecuteMsg;
 use market_1155::MarketQueryMsg;
 use market_1155::Offering;
@@ -54,6 +61,8 @@ fn sort_offering() {
             seller: Addr::unchecked("seller"),
             per_price: Uint128::from(1u64),
             amount: Uint128::from(10u64),
+            reserved_for: None,
+            asset_info: None,
         };
         offerings.push(offering);
     }
@@ -71,10 +80,12 @@ fn sort_offering() {
             limit: Some(100),
             offset: Some(50),
             order: Some(Order::Descending as u8),
+            start_after: None,
+            with_total: None,
         }),
     )
     .unwrap();
-    let value: Vec<Offering> = from_json(&res).unwrap();
+    let value: PageResponse<Offering> = from_json(&res).unwrap();
     println!("value query list offerings: {:?}", value);
 
     let res = query(
@@ -85,10 +96,12 @@ fn sort_offering() {
             limit: Some(100),
             offset: Some(1),
             order: Some(Order::Ascending as u8),
+            start_after: None,
+            with_total: None,
         }),
     )
     .unwrap();
-    let value: Vec<Offering> = from_json(&res).unwrap();
+    let value: PageResponse<Offering> = from_json(&res).unwrap();
     println!("value query list offering by seller: {:?}", value);
 
     // query by contract
@@ -100,10 +113,12 @@ fn sort_offering() {
             limit: Some(100),
             offset: Some(1),
             order: Some(Order::Ascending as u8),
+            start_after: None,
+            with_total: None,
         }),
     )
     .unwrap();
-    let value: Vec<Offering> = from_json(&res).unwrap();
+    let value: PageResponse<Offering> = from_json(&res).unwrap();
     println!("value query list offering by contract: {:?}", value);
 
     // query by contract token id
@@ -150,6 +165,8 @@ fn withdraw_offering() {
             seller: Addr::unchecked("seller"),
             per_price: Uint128::from(1u64),
             amount: Uint128::from(1u64),
+            reserved_for: None,
+            asset_info: None,
         };
         offerings.push(offering);
     }
@@ -170,10 +187,235 @@ fn withdraw_offering() {
             limit: Some(100),
             offset: Some(0),
             order: Some(Order::Ascending as u8),
+            start_after: None,
+            with_total: None,
         }),
     )
     .unwrap();
-    let value: Vec<Offering> = from_json(&res).unwrap();
+    let value: PageResponse<Offering> = from_json(&res).unwrap();
     println!("value: {:?}", value);
-    assert_eq!(value.len(), 1);
+    assert_eq!(value.items.len(), 1);
+}
+
+#[test]
+fn paginate_offerings_by_contract_token_id() {
+    let mut deps = setup_contract();
+    let info = mock_info("market_hub", &[coin(50, DENOM)]);
+
+    // the same token is offered by several sellers, next to offerings of another token
+    for i in 1u64..8u64 {
+        let offering = Offering {
+            id: None,
+            contract_addr: Addr::unchecked("xxx"),
+            token_id: if i % 2 == 0 { "other" } else { "token" }.to_string(),
+            seller: Addr::unchecked(format!("seller{}", i)),
+            per_price: Uint128::from(1u64),
+            amount: Uint128::from(10u64),
+            reserved_for: None,
+            asset_info: None,
+        };
+        let msg = ExecuteMsg::Msg(MarketExecuteMsg::UpdateOffering { offering });
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    }
+
+    let query_page = |start_after| -> PageResponse<Offering> {
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Msg(MarketQueryMsg::GetOfferingsByContractTokenId {
+                contract: Addr::unchecked("xxx"),
+                token_id: "token".to_string(),
+                offset: None,
+                start_after,
+                limit: Some(3),
+                order: Some(Order::Descending as u8),
+                with_total: Some(true),
+            }),
+        )
+        .unwrap();
+        from_json(&res).unwrap()
+    };
+
+    let first = query_page(None);
+    assert_eq!(first.total, Some(4));
+    let ids: Vec<u64> = first.items.iter().map(|o| o.id.unwrap()).collect();
+    assert_eq!(ids, vec![7, 5, 3]);
+
+    let second = query_page(first.next_cursor);
+    let ids: Vec<u64> = second.items.iter().map(|o| o.id.unwrap()).collect();
+    assert_eq!(ids, vec![1]);
+    assert_eq!(second.next_cursor, None);
+}
+
+#[test]
+fn query_offerings_by_price() {
+    let mut deps = setup_contract();
+    let info = mock_info("market_hub", &[coin(50, DENOM)]);
+    let orai = AssetInfo::NativeToken {
+        denom: String::from("orai"),
+    };
+    let airi = AssetInfo::Token {
+        contract_addr: Addr::unchecked("airi"),
+    };
+
+    // the token is offered by several sellers, the last offering is another token
+    let listings = vec![
+        ("token", 40u64, Some(orai.clone())),
+        ("token", 15, Some(airi.clone())),
+        ("token", 25, Some(orai.clone())),
+        ("token", 60, None),
+        ("other", 5, Some(orai.clone())),
+    ];
+    for (i, (token_id, per_price, asset_info)) in listings.into_iter().enumerate() {
+        let offering = Offering {
+            id: None,
+            contract_addr: Addr::unchecked("xxx"),
+            token_id: token_id.to_string(),
+            seller: Addr::unchecked(format!("seller{}", i)),
+            per_price: Uint128::from(per_price),
+            amount: Uint128::from(10u64),
+            reserved_for: None,
+            asset_info,
+        };
+        let msg = ExecuteMsg::Msg(MarketExecuteMsg::UpdateOffering { offering });
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    }
+
+    let floor = query_floor_offering(
+        deps.as_ref(),
+        Addr::unchecked("xxx"),
+        "token".to_string(),
+        None,
+    )
+    .unwrap()
+    .unwrap();
+    assert_eq!(floor.id, Some(2));
+    let floor = query_floor_offering(
+        deps.as_ref(),
+        Addr::unchecked("xxx"),
+        "token".to_string(),
+        Some(orai.clone()),
+    )
+    .unwrap()
+    .unwrap();
+    assert_eq!(floor.id, Some(3));
+
+    let query_page = |start_after, asset_info| -> PageResponse<Offering> {
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Msg(MarketQueryMsg::GetOfferingsByPriceRange {
+                contract: Addr::unchecked("xxx"),
+                token_id: "token".to_string(),
+                min_price: Some(Uint128::from(20u64)),
+                max_price: None,
+                asset_info,
+                start_after,
+                limit: Some(2),
+                order: Some(Order::Descending as u8),
+                with_total: Some(true),
+            }),
+        )
+        .unwrap();
+        from_json(&res).unwrap()
+    };
+
+    let first = query_page(None, None);
+    assert_eq!(first.total, Some(3));
+    let ids: Vec<u64> = first.items.iter().map(|o| o.id.unwrap()).collect();
+    assert_eq!(ids, vec![4, 1]);
+    let second = query_page(first.next_cursor, None);
+    let ids: Vec<u64> = second.items.iter().map(|o| o.id.unwrap()).collect();
+    assert_eq!(ids, vec![3]);
+    assert_eq!(second.next_cursor, None);
+
+    // offerings without a recorded asset are left out by the filter
+    let page = query_page(None, Some(orai));
+    let ids: Vec<u64> = page.items.iter().map(|o| o.id.unwrap()).collect();
+    assert_eq!(ids, vec![1, 3]);
+    assert_eq!(page.total, Some(2));
+    assert_eq!(page.next_cursor, None);
+}
+
+#[test]
+fn reindex_legacy_offerings() {
+    let mut deps = setup_contract();
+    let orai = AssetInfo::NativeToken {
+        denom: String::from("orai"),
+    };
+    // the hub resolves the payment storage, which answers the default denom for unknown offerings
+    deps.querier.update_wasm(|query| {
+        let value = match query {
+            WasmQuery::Smart { contract_addr, .. } if contract_addr == "market_hub" => {
+                to_json_binary(&Addr::unchecked("payment_storage"))
+            }
+            _ => to_json_binary(&AssetInfo::NativeToken {
+                denom: String::from("orai"),
+            }),
+        };
+        SystemResult::Ok(ContractResult::Ok(value.unwrap()))
+    });
+
+    // before 0.2.0 the offerings had no asset and no price index
+    for id in 1u64..4 {
+        let offering = Offering {
+            id: Some(id),
+            contract_addr: Addr::unchecked("xxx"),
+            token_id: "token".to_string(),
+            seller: Addr::unchecked(format!("seller{}", id)),
+            per_price: Uint128::from(10u64 * id),
+            amount: Uint128::from(10u64),
+            reserved_for: None,
+            asset_info: None,
+        };
+        Map::<&[u8], Offering>::new("offerings")
+            .save(&mut deps.storage, &id.to_be_bytes(), &offering)
+            .unwrap();
+    }
+    set_contract_version(&mut deps.storage, "crates.io:market_1155_storage", "0.1.0").unwrap();
+    migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+    let floor = |deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>| {
+        query_floor_offering(
+            deps.as_ref(),
+            Addr::unchecked("xxx"),
+            "token".to_string(),
+            Some(orai.clone()),
+        )
+        .unwrap()
+    };
+    assert_eq!(floor(&deps), None);
+
+    let reindex = |limit: u32| ExecuteMsg::ReindexOfferings { limit: Some(limit) };
+    assert!(matches!(
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("anyone", &[]),
+            reindex(2)
+        ),
+        Err(ContractError::Unauthorized { .. })
+    ));
+    let mut batches = vec![];
+    for _ in 0..3 {
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(CREATOR, &[]),
+            reindex(2),
+        )
+        .unwrap();
+        batches.push(res.attributes[1..].to_vec());
+    }
+    assert_eq!(
+        batches,
+        vec![
+            vec![attr("reindexed", "2"), attr("done", "false")],
+            vec![attr("reindexed", "1"), attr("done", "true")],
+            vec![attr("done", "true")],
+        ]
+    );
+
+    let floor = floor(&deps).unwrap();
+    assert_eq!(floor.id, Some(1));
+    assert_eq!(floor.asset_info, Some(orai));
 }
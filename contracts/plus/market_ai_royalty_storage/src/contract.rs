@@ -3,25 +3,35 @@ use cosmwasm_std::entry_point;
 
 use crate::error::ContractError;
 use crate::state::{
-    get_contract_token_id, get_key_royalty, royalties_map, ContractInfo, CONTRACT_INFO, PREFERENCES,
+    get_asset_key, get_contract_token_id, get_key_royalty, royalties_map, ContractInfo,
+    CONTRACT_INFO, CREATOR_TYPE_EARNINGS, PREFERENCES, REINDEX_CURSOR, ROYALTY_BALANCES,
+    ROYALTY_POLICIES, TOKEN_EARNINGS,
 };
 use cosmwasm_std::{
     attr, to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Record, Response, StdError,
-    StdResult,
+    StdResult, Uint128,
 };
 use cosmwasm_std::{Addr, Order};
+use cw2::set_contract_version;
 use cw_storage_plus::{Bound, PkOwned};
+use market::{count_total, PageResponse, Pagination, DEFAULT_LIMIT};
 use market_ai_royalty::{
-    sanitize_royalty, AiRoyaltyExecuteMsg, AiRoyaltyQueryMsg, OffsetMsg, Royalty, RoyaltyMsg,
+    parse_transfer_msg, sanitize_payees, sanitize_royalty, sanitize_total_royalty,
+    AccrueRoyaltiesMsg, AiRoyaltyExecuteMsg, AiRoyaltyQueryMsg, AssetInfo, CreatorTypeEarning,
+    OffsetMsg, Royalty, RoyaltyBalance, RoyaltyMsg, RoyaltyPolicy, TokenRoyaltyEarning,
 };
+use migration::{migrate_version, MigrationStep};
 
 use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, UpdateContractMsg};
 
 pub const MAX_ROYALTY_PERCENT: u64 = 50;
 pub const DEFAULT_ROYALTY_PERCENT: u64 = 10;
 // settings for pagination
-const MAX_LIMIT: u8 = 50;
-const DEFAULT_LIMIT: u8 = 20;
+const MAX_LIMIT: u32 = 50;
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:market_ai_royalty_storage";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 // Note, you can use StdResult in some functions where you do not
 // make use of the custom errors
@@ -32,6 +42,7 @@ pub fn instantiate(
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     // first time deploy, it will not know about the implementation
     let info = ContractInfo {
         governance: msg.governance,
@@ -56,8 +67,20 @@ pub fn execute(
             AiRoyaltyExecuteMsg::UpdateRoyalty(royalty) => try_update_royalty(deps, info, royalty),
             AiRoyaltyExecuteMsg::RemoveRoyalty(royalty) => try_remove_royalty(deps, info, royalty),
             AiRoyaltyExecuteMsg::UpdatePreference(pref) => try_update_preference(deps, info, pref),
+            AiRoyaltyExecuteMsg::AccrueRoyalties(msg) => try_accrue_royalties(deps, info, msg),
+            AiRoyaltyExecuteMsg::ClaimRoyalties {
+                creator,
+                asset_info,
+                amount,
+            } => try_claim_royalties(deps, env, info, creator, asset_info, amount),
+            AiRoyaltyExecuteMsg::UpdatePolicy(policy) => try_update_policy(deps, info, policy),
+            AiRoyaltyExecuteMsg::ExpireRoyalties {
+                contract_addr,
+                token_id,
+            } => try_expire_royalties(deps, info, contract_addr, token_id),
         },
         ExecuteMsg::UpdateInfo(msg) => try_update_info(deps, info, env, msg),
+        ExecuteMsg::ReindexRoyalties { limit } => try_reindex_royalties(deps, info, limit),
     }
 }
 
@@ -75,52 +98,119 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
             }
             AiRoyaltyQueryMsg::GetRoyalties {
                 offset,
+                start_after,
+                limit,
+                order,
+                with_total,
+            } => to_json_binary(&query_royalties(
+                deps,
+                offset,
+                start_after,
                 limit,
                 order,
-            } => to_json_binary(&query_royalties(deps, offset, limit, order)?),
+                with_total,
+            )?),
             AiRoyaltyQueryMsg::GetRoyaltiesTokenId {
                 token_id,
                 offset,
+                start_after,
                 limit,
                 order,
+                with_total,
             } => to_json_binary(&query_royalties_by_token_id(
-                deps, token_id, offset, limit, order,
+                deps,
+                token_id,
+                offset,
+                start_after,
+                limit,
+                order,
+                with_total,
             )?),
             AiRoyaltyQueryMsg::GetRoyaltiesOwner {
                 owner,
                 offset,
+                start_after,
                 limit,
                 order,
+                with_total,
             } => to_json_binary(&query_royalties_by_creator(
-                deps, owner, offset, limit, order,
+                deps,
+                owner,
+                offset,
+                start_after,
+                limit,
+                order,
+                with_total,
             )?),
             AiRoyaltyQueryMsg::GetRoyaltiesContract {
                 contract_addr,
                 offset,
+                start_after,
                 limit,
                 order,
+                with_total,
             } => to_json_binary(&query_royalties_map_by_contract(
                 deps,
                 contract_addr,
                 offset,
+                start_after,
                 limit,
                 order,
+                with_total,
             )?),
             AiRoyaltyQueryMsg::GetRoyaltiesContractTokenId {
                 contract_addr,
                 token_id,
                 offset,
+                start_after,
                 limit,
                 order,
+                with_total,
             } => to_json_binary(&query_royalties_map_by_contract_token_id(
                 deps,
                 contract_addr,
                 token_id,
                 offset,
+                start_after,
                 limit,
                 order,
+                with_total,
             )?),
             AiRoyaltyQueryMsg::GetContractInfo {} => to_json_binary(&query_contract_info(deps)?),
+            AiRoyaltyQueryMsg::GetRoyaltyBalances { creator } => {
+                to_json_binary(&query_royalty_balances(deps, creator)?)
+            }
+            AiRoyaltyQueryMsg::GetTokenRoyaltyEarnings {
+                contract_addr,
+                token_id,
+            } => to_json_binary(&query_token_royalty_earnings(
+                deps,
+                contract_addr,
+                token_id,
+            )?),
+            AiRoyaltyQueryMsg::GetPolicy { creator_type } => {
+                to_json_binary(&ROYALTY_POLICIES.may_load(deps.storage, creator_type.as_bytes())?)
+            }
+            AiRoyaltyQueryMsg::GetPolicies {} => to_json_binary(&query_policies(deps)?),
+            AiRoyaltyQueryMsg::GetRoyaltiesCreatorType {
+                creator_type,
+                offset,
+                start_after,
+                limit,
+                order,
+                with_total,
+            } => to_json_binary(&query_royalties_by_creator_type(
+                deps,
+                creator_type,
+                offset,
+                start_after,
+                limit,
+                order,
+                with_total,
+            )?),
+            AiRoyaltyQueryMsg::GetCreatorTypeEarnings { creator_type } => {
+                to_json_binary(&query_creator_type_earnings(deps, creator_type)?)
+            }
         },
         QueryMsg::GetContractInfo {} => to_json_binary(&query_contract_info(deps)?),
     }
@@ -173,15 +263,56 @@ pub fn try_update_royalty(
     //     });
     // }
 
-    let final_royalty;
+    let creator_type = royalty.creator_type.unwrap_or(String::from("creator"));
+    let policy = ROYALTY_POLICIES.may_load(deps.storage, creator_type.as_bytes())?;
+    let mut final_royalty;
     if let Some(msg_royalty) = royalty.royalty {
         final_royalty = sanitize_royalty(msg_royalty, max_royalty, "ai_royalty")?;
     } else {
         final_royalty = PREFERENCES
             .load(deps.storage, royalty.creator.as_bytes())
-            .unwrap_or(default_royalty);
+            .unwrap_or(
+                policy
+                    .as_ref()
+                    .map_or(default_royalty, |policy| policy.default_royalty),
+            );
+    }
+    // the policy of the creator type bounds the royalty
+    if let Some(policy) = policy {
+        final_royalty = final_royalty.clamp(policy.min_royalty, policy.max_royalty);
     }
 
+    // the royalties of all creators must leave something for the seller
+    let royalties = royalties_map()
+        .idx
+        .contract_token_id
+        .items(
+            deps.storage,
+            &get_contract_token_id(
+                royalty.contract_addr.as_bytes(),
+                royalty.token_id.as_bytes(),
+            ),
+            None,
+            None,
+            Order::Ascending,
+        )
+        .map(|item| item.and_then(parse_royalty))
+        .collect::<StdResult<Vec<Royalty>>>()?;
+    sanitize_total_royalty(&royalties, &royalty.creator, final_royalty, max_royalty)?;
+
+    // the payees are only replaced when the update lists them, an empty list clears them
+    let payees = match royalty.payees {
+        Some(payees) if payees.is_empty() => None,
+        Some(payees) => {
+            sanitize_payees(deps.api, &payees)?;
+            Some(payees)
+        }
+        None => royalties
+            .into_iter()
+            .find(|item| item.creator.eq(&royalty.creator))
+            .and_then(|item| item.payees),
+    };
+
     royalties_map().save(
         deps.storage,
         &get_key_royalty(
@@ -194,7 +325,8 @@ pub fn try_update_royalty(
             token_id: royalty.token_id.clone(),
             creator: royalty.creator.clone(),
             royalty: final_royalty,
-            creator_type: royalty.creator_type.unwrap_or(String::from("creator")),
+            creator_type,
+            payees,
         },
     )?;
 
@@ -237,6 +369,192 @@ pub fn try_remove_royalty(
     ]));
 }
 
+pub fn try_accrue_royalties(
+    deps: DepsMut,
+    info: MessageInfo,
+    msg: AccrueRoyaltiesMsg,
+) -> Result<Response, ContractError> {
+    // must check the sender is implementation contract
+    let contract_info = CONTRACT_INFO.load(deps.storage)?;
+    if contract_info.governance.ne(&info.sender) {
+        return Err(ContractError::Unauthorized {
+            sender: info.sender.to_string(),
+        });
+    };
+
+    let asset_key = get_asset_key(&msg.asset_info)?;
+    let contract_token_id =
+        get_contract_token_id(msg.contract_addr.as_bytes(), msg.token_id.as_bytes());
+    for payout in msg.payouts {
+        ROYALTY_BALANCES.update(
+            deps.storage,
+            (payout.creator.as_bytes(), &asset_key),
+            |balance| -> StdResult<_> {
+                let mut balance = balance.unwrap_or(RoyaltyBalance {
+                    asset_info: msg.asset_info.clone(),
+                    pending: Uint128::zero(),
+                    lifetime: Uint128::zero(),
+                });
+                balance.pending += payout.amount;
+                balance.lifetime += payout.amount;
+                Ok(balance)
+            },
+        )?;
+        CREATOR_TYPE_EARNINGS.update(
+            deps.storage,
+            (payout.creator_type.as_bytes(), &asset_key),
+            |earning| -> StdResult<_> {
+                let mut earning = earning.unwrap_or(CreatorTypeEarning {
+                    asset_info: msg.asset_info.clone(),
+                    amount: Uint128::zero(),
+                });
+                earning.amount += payout.amount;
+                Ok(earning)
+            },
+        )?;
+        TOKEN_EARNINGS.update(
+            deps.storage,
+            (
+                &contract_token_id,
+                &[payout.creator.as_bytes(), &asset_key].concat(),
+            ),
+            |earning| -> StdResult<_> {
+                let mut earning = earning.unwrap_or(TokenRoyaltyEarning {
+                    creator: payout.creator.clone(),
+                    asset_info: msg.asset_info.clone(),
+                    amount: Uint128::zero(),
+                });
+                earning.amount += payout.amount;
+                Ok(earning)
+            },
+        )?;
+    }
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "accrue_royalties"),
+        attr("contract_addr", msg.contract_addr),
+        attr("token_id", msg.token_id),
+    ]))
+}
+
+pub fn try_claim_royalties(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    creator: Addr,
+    asset_info: AssetInfo,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    // must check the sender is implementation contract
+    let contract_info = CONTRACT_INFO.load(deps.storage)?;
+    if contract_info.governance.ne(&info.sender) {
+        return Err(ContractError::Unauthorized {
+            sender: info.sender.to_string(),
+        });
+    };
+
+    let asset_key = get_asset_key(&asset_info)?;
+    let mut balance = ROYALTY_BALANCES.load(deps.storage, (creator.as_bytes(), &asset_key))?;
+    balance.pending = balance
+        .pending
+        .checked_sub(amount)
+        .map_err(|_| ContractError::InsufficientFunds {})?;
+    ROYALTY_BALANCES.save(deps.storage, (creator.as_bytes(), &asset_key), &balance)?;
+
+    // the storage keeps the accrued funds, so the claims do not depend on the implementation that accrued them
+    let transfer_msg = parse_transfer_msg(
+        asset_info,
+        amount,
+        env.contract.address.as_str(),
+        creator.clone(),
+    )?;
+
+    Ok(Response::new()
+        .add_message(transfer_msg)
+        .add_attributes(vec![
+            attr("action", "claim_royalties"),
+            attr("creator", creator),
+            attr("amount", amount),
+        ]))
+}
+
+pub fn try_update_policy(
+    deps: DepsMut,
+    info: MessageInfo,
+    policy: RoyaltyPolicy,
+) -> Result<Response, ContractError> {
+    // must check the sender is implementation contract
+    let contract_info = CONTRACT_INFO.load(deps.storage)?;
+    if contract_info.governance.ne(&info.sender) {
+        return Err(ContractError::Unauthorized {
+            sender: info.sender.to_string(),
+        });
+    };
+    sanitize_royalty(policy.max_royalty, contract_info.max_royalty, "max_royalty")?;
+    sanitize_royalty(policy.min_royalty, policy.max_royalty, "min_royalty")?;
+    if policy.default_royalty < policy.min_royalty || policy.default_royalty > policy.max_royalty {
+        return Err(ContractError::InvalidArgument {
+            arg: String::from("default_royalty"),
+        });
+    }
+    ROYALTY_POLICIES.save(deps.storage, policy.creator_type.as_bytes(), &policy)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "update_royalty_policy"),
+        attr("creator_type", policy.creator_type),
+    ]))
+}
+
+pub fn try_expire_royalties(
+    deps: DepsMut,
+    info: MessageInfo,
+    contract_addr: Addr,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    // must check the sender is implementation contract
+    let contract_info = CONTRACT_INFO.load(deps.storage)?;
+    if contract_info.governance.ne(&info.sender) {
+        return Err(ContractError::Unauthorized {
+            sender: info.sender.to_string(),
+        });
+    };
+
+    let royalties = royalties_map()
+        .idx
+        .contract_token_id
+        .items(
+            deps.storage,
+            &get_contract_token_id(contract_addr.as_bytes(), token_id.as_bytes()),
+            None,
+            None,
+            Order::Ascending,
+        )
+        .map(|item| item.and_then(parse_royalty))
+        .collect::<StdResult<Vec<Royalty>>>()?;
+    let mut rsp = Response::new().add_attributes(vec![
+        attr("action", "expire_royalties"),
+        attr("contract_addr", contract_addr),
+        attr("token_id", token_id),
+    ]);
+    for royalty in royalties {
+        let survives = ROYALTY_POLICIES
+            .may_load(deps.storage, royalty.creator_type.as_bytes())?
+            .is_none_or(|policy| policy.survives_transfer);
+        if !survives {
+            royalties_map().remove(
+                deps.storage,
+                &get_key_royalty(
+                    royalty.contract_addr.as_bytes(),
+                    royalty.token_id.as_bytes(),
+                    royalty.creator.as_bytes(),
+                ),
+            )?;
+            rsp = rsp.add_attribute("expired", royalty.creator);
+        }
+    }
+    Ok(rsp)
+}
+
 pub fn try_update_info(
     deps: DepsMut,
     info: MessageInfo,
@@ -278,6 +596,47 @@ pub fn query_preference(deps: Deps, creator: Addr) -> StdResult<u64> {
     PREFERENCES.load(deps.storage, creator.as_bytes())
 }
 
+pub fn query_royalty_balances(deps: Deps, creator: Addr) -> StdResult<Vec<RoyaltyBalance>> {
+    ROYALTY_BALANCES
+        .prefix(creator.as_bytes())
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, balance)| balance))
+        .collect()
+}
+
+pub fn query_token_royalty_earnings(
+    deps: Deps,
+    contract_addr: Addr,
+    token_id: String,
+) -> StdResult<Vec<TokenRoyaltyEarning>> {
+    TOKEN_EARNINGS
+        .prefix(&get_contract_token_id(
+            contract_addr.as_bytes(),
+            token_id.as_bytes(),
+        ))
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, earning)| earning))
+        .collect()
+}
+
+pub fn query_policies(deps: Deps) -> StdResult<Vec<RoyaltyPolicy>> {
+    ROYALTY_POLICIES
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, policy)| policy))
+        .collect()
+}
+
+pub fn query_creator_type_earnings(
+    deps: Deps,
+    creator_type: String,
+) -> StdResult<Vec<CreatorTypeEarning>> {
+    CREATOR_TYPE_EARNINGS
+        .prefix(creator_type.as_bytes())
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, earning)| earning))
+        .collect()
+}
+
 pub fn query_royalty(
     deps: Deps,
     contract_addr: Addr,
@@ -297,151 +656,310 @@ pub fn query_royalty(
         )
         .transpose()
     {
-        return parse_royalty(kv_item);
+        return parse_royalty(kv_item?);
     }
     Err(StdError::generic_err("Royalty not found"))
 }
 
 // ============================== Query Handlers ==============================
 
-fn _get_range_params(
-    limit: Option<u8>,
+fn royalty_pagination(
     offset: Option<OffsetMsg>,
+    start_after: Option<Binary>,
+    limit: Option<u32>,
     order: Option<u8>,
-) -> (usize, Option<Bound>, Option<Bound>, Order) {
-    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
-    let mut min = None;
-    // let mut max: Option<Bound> = None;
-    let mut order_enum = Order::Descending;
-    if let Some(num) = order {
-        if num == 1 {
-            order_enum = Order::Ascending;
-        }
-    }
-
-    // if there is offset, assign to min or max
-    if let Some(offset) = offset {
-        let offset_value = Some(Bound::Exclusive(get_key_royalty(
-            offset.contract.as_bytes(),
-            offset.token_id.as_bytes(),
-            offset.creator.as_bytes(),
-        )));
-        // match order_enum {
-        //     Order::Ascending => min = offset_value,
-        //     Order::Descending => min = offset_value,
-        // }
-        min = offset_value;
-    };
-    (limit, min, None, order_enum)
+) -> Pagination {
+    Pagination::new(
+        start_after,
+        offset.map(|offset| {
+            get_key_royalty(
+                offset.contract.as_bytes(),
+                offset.token_id.as_bytes(),
+                offset.creator.as_bytes(),
+            )
+        }),
+        limit.map(|limit| limit.min(MAX_LIMIT)),
+        order,
+        Order::Descending,
+    )
 }
 
 pub fn query_royalties(
     deps: Deps,
     offset: Option<OffsetMsg>,
-    limit: Option<u8>,
+    start_after: Option<Binary>,
+    limit: Option<u32>,
     order: Option<u8>,
-) -> StdResult<Vec<Royalty>> {
-    let (limit, min, max, order) = _get_range_params(limit, offset, order);
-    let royalties: StdResult<Vec<Royalty>> = royalties_map()
-        .range(deps.storage, min, max, order)
-        .take(limit)
-        .map(|kv_item| parse_royalty(kv_item))
-        .collect();
-    Ok(royalties?)
+    with_total: Option<bool>,
+) -> StdResult<PageResponse<Royalty>> {
+    let pagination = royalty_pagination(offset, start_after, limit, order);
+    let (min, max, order_enum) = pagination.bounds();
+    let total = count_total(with_total, || {
+        royalties_map()
+            .range(deps.storage, None, None, Order::Ascending)
+            .count()
+    });
+
+    pagination.page_response(
+        royalties_map().range(deps.storage, min, max, order_enum),
+        parse_royalty,
+        total,
+    )
 }
 
 pub fn query_royalties_by_token_id(
     deps: Deps,
     token_id: String,
     offset: Option<OffsetMsg>,
-    limit: Option<u8>,
+    start_after: Option<Binary>,
+    limit: Option<u32>,
     order: Option<u8>,
-) -> StdResult<Vec<Royalty>> {
-    // let (limit, min, max, order) = _get_range_params(limit, offset, order);
-    let (limit, min, max, order) = _get_range_params(limit, offset, order);
-    let royalties: StdResult<Vec<Royalty>> = royalties_map()
-        .idx
-        .token_id
-        .items(deps.storage, token_id.as_bytes(), min, max, order)
-        .take(limit)
-        .map(|kv_item| parse_royalty(kv_item))
-        .collect();
-    Ok(royalties?)
+    with_total: Option<bool>,
+) -> StdResult<PageResponse<Royalty>> {
+    let pagination = royalty_pagination(offset, start_after, limit, order);
+    let (min, max, order_enum) = pagination.bounds();
+    let total = count_total(with_total, || {
+        royalties_map()
+            .idx
+            .token_id
+            .pks(
+                deps.storage,
+                token_id.as_bytes(),
+                None,
+                None,
+                Order::Ascending,
+            )
+            .count()
+    });
+
+    pagination.page_response(
+        royalties_map()
+            .idx
+            .token_id
+            .items(deps.storage, token_id.as_bytes(), min, max, order_enum),
+        parse_royalty,
+        total,
+    )
 }
 
 pub fn query_royalties_by_creator(
     deps: Deps,
     creator: Addr,
     offset: Option<OffsetMsg>,
-    limit: Option<u8>,
+    start_after: Option<Binary>,
+    limit: Option<u32>,
     order: Option<u8>,
-) -> StdResult<Vec<Royalty>> {
-    let (limit, min, max, order) = _get_range_params(limit, offset, order);
-    let royalties: StdResult<Vec<Royalty>> = royalties_map()
-        .idx
-        .creator
-        .items(deps.storage, creator.as_bytes(), min, max, order)
-        .take(limit)
-        .map(|kv_item| parse_royalty(kv_item))
-        .collect();
+    with_total: Option<bool>,
+) -> StdResult<PageResponse<Royalty>> {
+    let pagination = royalty_pagination(offset, start_after, limit, order);
+    let (min, max, order_enum) = pagination.bounds();
+    let total = count_total(with_total, || {
+        royalties_map()
+            .idx
+            .creator
+            .pks(
+                deps.storage,
+                creator.as_bytes(),
+                None,
+                None,
+                Order::Ascending,
+            )
+            .count()
+    });
+
+    pagination.page_response(
+        royalties_map()
+            .idx
+            .creator
+            .items(deps.storage, creator.as_bytes(), min, max, order_enum),
+        parse_royalty,
+        total,
+    )
+}
 
-    Ok(royalties?)
+pub fn query_royalties_by_creator_type(
+    deps: Deps,
+    creator_type: String,
+    offset: Option<OffsetMsg>,
+    start_after: Option<Binary>,
+    limit: Option<u32>,
+    order: Option<u8>,
+    with_total: Option<bool>,
+) -> StdResult<PageResponse<Royalty>> {
+    let pagination = royalty_pagination(offset, start_after, limit, order);
+    let (min, max, order_enum) = pagination.bounds();
+    let total = count_total(with_total, || {
+        royalties_map()
+            .idx
+            .creator_type
+            .pks(
+                deps.storage,
+                creator_type.as_bytes(),
+                None,
+                None,
+                Order::Ascending,
+            )
+            .count()
+    });
+
+    pagination.page_response(
+        royalties_map().idx.creator_type.items(
+            deps.storage,
+            creator_type.as_bytes(),
+            min,
+            max,
+            order_enum,
+        ),
+        parse_royalty,
+        total,
+    )
 }
 
 pub fn query_royalties_map_by_contract(
     deps: Deps,
     contract_addr: Addr,
     offset: Option<OffsetMsg>,
-    limit: Option<u8>,
+    start_after: Option<Binary>,
+    limit: Option<u32>,
     order: Option<u8>,
-) -> StdResult<Vec<Royalty>> {
-    let (limit, min, max, order) = _get_range_params(limit, offset, order);
-    let royalties: StdResult<Vec<Royalty>> = royalties_map()
-        .idx
-        .contract_addr
-        .items(deps.storage, contract_addr.as_bytes(), min, max, order)
-        .take(limit)
-        .map(|kv_item| parse_royalty(kv_item))
-        .collect();
-
-    Ok(royalties?)
+    with_total: Option<bool>,
+) -> StdResult<PageResponse<Royalty>> {
+    let pagination = royalty_pagination(offset, start_after, limit, order);
+    let (min, max, order_enum) = pagination.bounds();
+    let total = count_total(with_total, || {
+        royalties_map()
+            .idx
+            .contract_addr
+            .pks(
+                deps.storage,
+                contract_addr.as_bytes(),
+                None,
+                None,
+                Order::Ascending,
+            )
+            .count()
+    });
+
+    pagination.page_response(
+        royalties_map().idx.contract_addr.items(
+            deps.storage,
+            contract_addr.as_bytes(),
+            min,
+            max,
+            order_enum,
+        ),
+        parse_royalty,
+        total,
+    )
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn query_royalties_map_by_contract_token_id(
     deps: Deps,
     contract: Addr,
     token_id: String,
     offset: Option<OffsetMsg>,
-    limit: Option<u8>,
+    start_after: Option<Binary>,
+    limit: Option<u32>,
     order: Option<u8>,
-) -> StdResult<Vec<Royalty>> {
-    let (limit, min, max, order) = _get_range_params(limit, offset, order);
-    let royalties: StdResult<Vec<Royalty>> = royalties_map()
-        .idx
-        .contract_token_id
-        .items(
+    with_total: Option<bool>,
+) -> StdResult<PageResponse<Royalty>> {
+    let pagination = royalty_pagination(offset, start_after, limit, order);
+    let (min, max, order_enum) = pagination.bounds();
+    let contract_token_id = get_contract_token_id(contract.as_bytes(), token_id.as_bytes());
+    let total = count_total(with_total, || {
+        royalties_map()
+            .idx
+            .contract_token_id
+            .pks(
+                deps.storage,
+                &contract_token_id,
+                None,
+                None,
+                Order::Ascending,
+            )
+            .count()
+    });
+
+    pagination.page_response(
+        royalties_map().idx.contract_token_id.items(
             deps.storage,
-            &get_contract_token_id(contract.as_bytes(), token_id.as_bytes()),
+            &contract_token_id,
             min,
             max,
-            order,
-        )
-        .take(limit)
-        .map(|kv_item| parse_royalty(kv_item))
-        .collect();
-
-    Ok(royalties?)
+            order_enum,
+        ),
+        parse_royalty,
+        total,
+    )
 }
 
-fn parse_royalty<'a>(item: StdResult<Record<Royalty>>) -> StdResult<Royalty> {
-    item.and_then(|(_, payout)| {
-        // will panic if length is greater than 8, but we can make sure it is u64
-        // try_into will box vector to fixed array
-        Ok(payout)
-    })
+fn parse_royalty((_, payout): Record<Royalty>) -> StdResult<Royalty> {
+    Ok(payout)
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
-    Ok(Response::default())
-}
\ No newline at end of file
+pub fn migrate(deps: DepsMut, env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    Ok(migrate_version(
+        deps,
+        &env,
+        CONTRACT_NAME,
+        CONTRACT_VERSION,
+        MIGRATIONS,
+    )?)
+}
+
+const MIGRATIONS: &[MigrationStep] = &[MigrationStep {
+    version: "0.2.0",
+    name: "reindex_royalties",
+    run: reindex_royalties,
+}];
+
+fn reindex_royalties(deps: DepsMut, _env: &Env) -> StdResult<()> {
+    // re-saving every royalty does not fit in one transaction, the creator runs it in batches
+    // with ReindexRoyalties. Until it is done the creator type queries miss the older royalties
+    REINDEX_CURSOR.save(deps.storage, &vec![])
+}
+
+/// saving the royalties again fills the creator type index for royalties created before it
+pub fn try_reindex_royalties(
+    deps: DepsMut,
+    info: MessageInfo,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let ContractInfo { creator, .. } = CONTRACT_INFO.load(deps.storage)?;
+    if info.sender.ne(&creator) {
+        return Err(ContractError::Unauthorized {
+            sender: info.sender.to_string(),
+        });
+    }
+    let cursor = match REINDEX_CURSOR.may_load(deps.storage)? {
+        Some(cursor) => cursor,
+        None => {
+            return Ok(Response::new().add_attributes(vec![
+                attr("action", "reindex_royalties"),
+                attr("done", "true"),
+            ]))
+        }
+    };
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let min = (!cursor.is_empty()).then_some(Bound::Exclusive(cursor));
+    let batch = royalties_map()
+        .range(deps.storage, min, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (key, royalty) in batch.iter() {
+        royalties_map().save(deps.storage, key, royalty)?;
+    }
+    let done = batch.len() < limit;
+    match batch.last() {
+        Some((key, _)) if !done => REINDEX_CURSOR.save(deps.storage, key)?,
+        _ => REINDEX_CURSOR.remove(deps.storage),
+    }
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "reindex_royalties"),
+        attr("reindexed", batch.len().to_string()),
+        attr("done", done.to_string()),
+    ]))
+}
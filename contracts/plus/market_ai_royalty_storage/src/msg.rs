@@ -1,6 +1,6 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::Addr;
-use market_ai_royalty::{AiRoyaltyExecuteMsg, AiRoyaltyQueryMsg, Royalty};
+use market_ai_royalty::{AiRoyaltyExecuteMsg, AiRoyaltyQueryMsg, AiRoyaltyQueryResponse};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -17,6 +17,8 @@ pub enum ExecuteMsg {
     Msg(AiRoyaltyExecuteMsg),
     // other implementation
     UpdateInfo(UpdateContractMsg),
+    // re-save the next royalties of the reindex queued by the migration, creator only
+    ReindexRoyalties { limit: Option<u32> },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -36,17 +38,5 @@ pub enum QueryMsg {
     GetContractInfo {},
 }
 
-#[cw_serde]
-pub enum AiRoyaltyQueryResponse {
-    // GetOfferings returns a list of all offerings
-    GetPreference(u64),
-    GetRoyalty(Royalty),
-    GetRoyalties(Vec<Royalty>),
-    GetRoyaltiesTokenId(Vec<Royalty>),
-    GetRoyaltiesOwner(Vec<Royalty>),
-    GetRoyaltiesContract(Vec<Royalty>),
-    GetRoyaltiesContractTokenId(Vec<Royalty>),
-}
-
 #[cw_serde]
 pub struct MigrateMsg {}
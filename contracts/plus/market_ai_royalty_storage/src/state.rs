@@ -1,9 +1,11 @@
-use market_ai_royalty::Royalty;
+use market_ai_royalty::{
+    AssetInfo, CreatorTypeEarning, Royalty, RoyaltyBalance, RoyaltyPolicy, TokenRoyaltyEarning,
+};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 
-use cosmwasm_std::Addr;
+use cosmwasm_std::{to_json_vec, Addr, StdResult};
 use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex, PkOwned, UniqueIndex};
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
@@ -15,15 +17,31 @@ pub struct ContractInfo {
 }
 
 pub const CONTRACT_INFO: Item<ContractInfo> = Item::new("marketplace_info");
+/// last royalty key re-saved by the running reindex, empty when it has not started yet
+pub const REINDEX_CURSOR: Item<Vec<u8>> = Item::new("reindex_cursor");
 
 pub const PREFERENCES: Map<&[u8], u64> = Map::new("preferences");
 
+/// creator => asset => accrued royalty balance
+pub const ROYALTY_BALANCES: Map<(&[u8], &[u8]), RoyaltyBalance> = Map::new("royalty_balances");
+
+/// contract token id => creator and asset => lifetime accrued royalty
+pub const TOKEN_EARNINGS: Map<(&[u8], &[u8]), TokenRoyaltyEarning> = Map::new("token_earnings");
+
+/// creator type => royalty policy
+pub const ROYALTY_POLICIES: Map<&[u8], RoyaltyPolicy> = Map::new("royalty_policies");
+
+/// creator type => asset => lifetime accrued royalty
+pub const CREATOR_TYPE_EARNINGS: Map<(&[u8], &[u8]), CreatorTypeEarning> =
+    Map::new("creator_type_earnings");
+
 // for structures
 pub struct RoyaltyIndexes<'a> {
     pub contract_addr: MultiIndex<'a, Royalty>,
     pub token_id: MultiIndex<'a, Royalty>,
     pub creator: MultiIndex<'a, Royalty>,
     pub contract_token_id: MultiIndex<'a, Royalty>,
+    pub creator_type: MultiIndex<'a, Royalty>,
     pub unique_royalty: UniqueIndex<'a, PkOwned, Royalty>,
 }
 
@@ -35,6 +53,10 @@ pub fn get_key_royalty<'a>(contract: &'a [u8], token_id: &'a [u8], creator: &'a
     hasher.finalize().to_vec()
 }
 
+pub fn get_asset_key(asset_info: &AssetInfo) -> StdResult<Vec<u8>> {
+    to_json_vec(asset_info)
+}
+
 // contract nft + token id => unique id
 pub fn get_contract_token_id<'a>(contract: &'a [u8], token_id: &'a [u8]) -> Vec<u8> {
     let mut hasher = Sha256::new();
@@ -50,6 +72,7 @@ impl<'a> IndexList<Royalty> for RoyaltyIndexes<'a> {
             &self.token_id,
             &self.creator,
             &self.contract_token_id,
+            &self.creator_type,
             &self.unique_royalty,
         ];
         Box::new(v.into_iter())
@@ -79,6 +102,11 @@ pub fn royalties_map<'a>() -> IndexedMap<'a, &'a [u8], Royalty, RoyaltyIndexes<'
             "royalties",
             "royalties__contract_token_id",
         ),
+        creator_type: MultiIndex::new(
+            |d| d.creator_type.to_owned().into_bytes(),
+            "royalties",
+            "royalties__creator_type",
+        ),
         unique_royalty: UniqueIndex::new(
             |o| {
                 PkOwned(get_key_royalty(
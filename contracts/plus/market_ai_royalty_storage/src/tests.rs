@@ -1,10 +1,16 @@
 use crate::contract::*;
 use crate::error::ContractError;
 use crate::msg::*;
+use crate::state::get_key_royalty;
 use cosmwasm_std::testing::{
     mock_dependencies_with_balance, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
 };
-use cosmwasm_std::{coin, coins, from_json, Addr, OwnedDeps, StdError};
+use cosmwasm_std::{
+    attr, coin, coins, from_json, Addr, BankMsg, OwnedDeps, Response, StdError, SubMsg, Uint128,
+};
+use cw2::set_contract_version;
+use cw_storage_plus::Map;
+use market::PageResponse;
 use market_ai_royalty::*;
 
 const CREATOR: &str = "marketplace";
@@ -41,6 +47,7 @@ fn update_ai_royalty() {
             token_id: i.to_string(),
             creator_type: Some(String::from("sacx")),
             royalty: Some(40),
+            payees: None,
         };
         royalties.push(royalty);
     }
@@ -104,6 +111,7 @@ fn update_ai_royalty() {
         token_id: "1".to_string(),
         creator_type: Some(String::from("sacx")),
         royalty: None,
+        payees: None,
     };
     let mut msg = ExecuteMsg::Msg(AiRoyaltyExecuteMsg::UpdateRoyalty(royalty_msg.clone()));
     let pref_msg_sec = ExecuteMsg::Msg(AiRoyaltyExecuteMsg::UpdatePreference(20));
@@ -137,14 +145,119 @@ fn update_ai_royalty() {
             offset: None,
             limit: None,
             order: Some(1),
+            start_after: None,
+            with_total: None,
         }),
     )
     .unwrap();
-    let value: Vec<Royalty> = from_json(&res).unwrap();
+    let value: PageResponse<Royalty> = from_json(&res).unwrap();
     println!("list royalties: {:?}", value);
 
-    assert_eq!(value[0].royalty, 40);
-    assert_eq!(value[1].royalty, 40);
+    assert_eq!(value.items[0].royalty, 40);
+    assert_eq!(value.items[1].royalty, 40);
+}
+
+fn update_royalty(
+    deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+    creator: &str,
+    royalty: u64,
+) -> Result<Response, ContractError> {
+    let msg = ExecuteMsg::Msg(AiRoyaltyExecuteMsg::UpdateRoyalty(RoyaltyMsg {
+        contract_addr: Addr::unchecked("xxx"),
+        creator: Addr::unchecked(creator),
+        token_id: "1".to_string(),
+        creator_type: None,
+        royalty: Some(royalty),
+        payees: None,
+    }));
+    execute(deps.as_mut(), mock_env(), mock_info("market_hub", &[]), msg)
+}
+
+#[test]
+fn update_ai_royalty_total_cap() {
+    let mut deps = setup_contract();
+    update_royalty(&mut deps, "provider1", 30).unwrap();
+
+    // 30 + 30 is above the max royalty of 50
+    assert_eq!(
+        update_royalty(&mut deps, "provider2", 30)
+            .unwrap_err()
+            .to_string(),
+        "Generic error: Invalid argument: total_royalty"
+    );
+    update_royalty(&mut deps, "provider2", 20).unwrap();
+
+    // the creator's own royalty is replaced, not added
+    update_royalty(&mut deps, "provider1", 30).unwrap();
+    assert!(update_royalty(&mut deps, "provider1", 31).is_err());
+}
+
+fn update_payees(
+    deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+    payees: Option<Vec<RoyaltyPayee>>,
+) -> Result<Response, ContractError> {
+    let msg = ExecuteMsg::Msg(AiRoyaltyExecuteMsg::UpdateRoyalty(RoyaltyMsg {
+        contract_addr: Addr::unchecked("xxx"),
+        creator: Addr::unchecked("provider1"),
+        token_id: "1".to_string(),
+        creator_type: None,
+        royalty: Some(10),
+        payees,
+    }));
+    execute(deps.as_mut(), mock_env(), mock_info("market_hub", &[]), msg)
+}
+
+fn payee(address: &str, weight: u64) -> RoyaltyPayee {
+    RoyaltyPayee {
+        address: Addr::unchecked(address),
+        weight,
+    }
+}
+
+#[test]
+fn update_ai_royalty_payees() {
+    let mut deps = setup_contract();
+
+    // too many payees, zero or overflowing weights, invalid or duplicated addresses
+    for payees in [
+        (0..=MAX_ROYALTY_PAYEES)
+            .map(|i| payee(&format!("payee{}", i), 1))
+            .collect(),
+        vec![payee("provider1", 0), payee("payee1", 0)],
+        vec![payee("provider1", u64::MAX), payee("payee1", 1)],
+        vec![
+            payee("provider1", MAX_TOTAL_PAYEE_WEIGHT),
+            payee("payee1", 1),
+        ],
+        vec![payee("provider1", 1), payee("PAYEE1", 1)],
+        vec![payee("provider1", 1), payee("provider1", 1)],
+    ] {
+        assert!(update_payees(&mut deps, Some(payees)).is_err());
+    }
+    let payees = vec![payee("provider1", 2), payee("payee1", 1)];
+    update_payees(&mut deps, Some(payees.clone())).unwrap();
+
+    // the stored payees are kept unless the update lists them, an empty list clears them
+    let query_payees = |deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>| {
+        let royalty: Royalty = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::Msg(AiRoyaltyQueryMsg::GetRoyalty {
+                    contract_addr: Addr::unchecked("xxx"),
+                    token_id: "1".to_string(),
+                    creator: Addr::unchecked("provider1"),
+                }),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        royalty.payees
+    };
+    update_payees(&mut deps, None).unwrap();
+    assert_eq!(query_payees(&deps), Some(payees));
+    update_payees(&mut deps, Some(vec![])).unwrap();
+    assert_eq!(query_payees(&deps), None);
 }
 
 #[test]
@@ -166,6 +279,7 @@ fn query_royalties() {
             token_id: "1".to_string(),
             creator_type: Some(String::from("sacx")),
             royalty: None,
+            payees: None,
         };
         royalties.push(royalty);
     }
@@ -194,11 +308,13 @@ fn query_royalties() {
         }),
         limit: None,
         order: Some(1),
+        start_after: None,
+        with_total: None,
     });
-    let result: Vec<Royalty> =
+    let result: PageResponse<Royalty> =
         from_json(&query(deps.as_ref(), mock_env(), query_royalties).unwrap()).unwrap();
     println!("result using token id: {:?}", result);
-    assert_eq!(result.len(), 3);
+    assert_eq!(result.items.len(), 3);
 
     // // query royalties using owner
     query_royalties = QueryMsg::Msg(AiRoyaltyQueryMsg::GetRoyaltiesOwner {
@@ -206,11 +322,13 @@ fn query_royalties() {
         offset: None,
         limit: None,
         order: Some(1),
+        start_after: None,
+        with_total: None,
     });
-    let result: Vec<Royalty> =
+    let result: PageResponse<Royalty> =
         from_json(&query(deps.as_ref(), mock_env(), query_royalties).unwrap()).unwrap();
     println!("result using owner: {:?}", result);
-    assert_eq!(result.len(), 1);
+    assert_eq!(result.items.len(), 1);
 
     query_royalties = QueryMsg::Msg(AiRoyaltyQueryMsg::GetRoyalties {
         offset: Some(OffsetMsg {
@@ -220,11 +338,13 @@ fn query_royalties() {
         }),
         limit: None,
         order: Some(1),
+        start_after: None,
+        with_total: None,
     });
-    let result: Vec<Royalty> =
+    let result: PageResponse<Royalty> =
         from_json(&query(deps.as_ref(), mock_env(), query_royalties).unwrap()).unwrap();
     println!("result using map: {:?}", result);
-    assert_eq!(result.len(), 1);
+    assert_eq!(result.items.len(), 1);
 
     query_royalties = QueryMsg::Msg(AiRoyaltyQueryMsg::GetRoyaltiesContractTokenId {
         contract_addr: Addr::unchecked("xxx1"),
@@ -232,11 +352,13 @@ fn query_royalties() {
         offset: None,
         limit: None,
         order: Some(1),
+        start_after: None,
+        with_total: None,
     });
-    let result: Vec<Royalty> =
+    let result: PageResponse<Royalty> =
         from_json(&query(deps.as_ref(), mock_env(), query_royalties).unwrap()).unwrap();
     println!("result using contract token id: {:?}", result);
-    assert_eq!(result.len(), 1);
+    assert_eq!(result.items.len(), 1);
 }
 
 #[test]
@@ -254,6 +376,7 @@ fn remove_ai_royalty() {
             token_id: i.to_string(),
             creator_type: Some(String::from("sacx")),
             royalty: None,
+            payees: None,
         };
         royalties.push(royalty);
     }
@@ -314,3 +437,359 @@ fn query_preference() {
     println!("pref: {}", pref);
     assert_eq!(pref, 1);
 }
+
+#[test]
+fn accrue_and_claim_royalties() {
+    let mut deps = setup_contract();
+    let asset_info = AssetInfo::NativeToken {
+        denom: DENOM.to_string(),
+    };
+    let accrue_msg = ExecuteMsg::Msg(AiRoyaltyExecuteMsg::AccrueRoyalties(AccrueRoyaltiesMsg {
+        contract_addr: Addr::unchecked("xxx"),
+        token_id: "1".to_string(),
+        asset_info: asset_info.clone(),
+        payouts: vec![RoyaltyPayout {
+            creator: Addr::unchecked("provider1"),
+            creator_type: String::from("creator"),
+            royalty: 10,
+            amount: Uint128::from(100u128),
+        }],
+    }));
+
+    // only the implementation can accrue
+    assert!(matches!(
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("theft", &[]),
+            accrue_msg.clone()
+        ),
+        Err(ContractError::Unauthorized { .. })
+    ));
+    for _ in 0..2 {
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("market_hub", &[]),
+            accrue_msg.clone(),
+        )
+        .unwrap();
+    }
+
+    let claim_msg = |amount: u128| {
+        ExecuteMsg::Msg(AiRoyaltyExecuteMsg::ClaimRoyalties {
+            creator: Addr::unchecked("provider1"),
+            asset_info: asset_info.clone(),
+            amount: Uint128::from(amount),
+        })
+    };
+    assert!(execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("market_hub", &[]),
+        claim_msg(201)
+    )
+    .is_err());
+    // the storage holds the accrued funds and pays the creator itself
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("market_hub", &[]),
+        claim_msg(150),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(BankMsg::Send {
+            to_address: "provider1".to_string(),
+            amount: coins(150, DENOM),
+        })]
+    );
+
+    let balances: Vec<RoyaltyBalance> = from_json(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Msg(AiRoyaltyQueryMsg::GetRoyaltyBalances {
+                creator: Addr::unchecked("provider1"),
+            }),
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        balances,
+        vec![RoyaltyBalance {
+            asset_info: asset_info.clone(),
+            pending: Uint128::from(50u128),
+            lifetime: Uint128::from(200u128),
+        }]
+    );
+
+    let earnings: Vec<TokenRoyaltyEarning> = from_json(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Msg(AiRoyaltyQueryMsg::GetTokenRoyaltyEarnings {
+                contract_addr: Addr::unchecked("xxx"),
+                token_id: "1".to_string(),
+            }),
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        earnings,
+        vec![TokenRoyaltyEarning {
+            creator: Addr::unchecked("provider1"),
+            asset_info,
+            amount: Uint128::from(200u128),
+        }]
+    );
+}
+
+#[test]
+fn royalty_policy_by_creator_type() {
+    let mut deps = setup_contract();
+    let hub_info = mock_info("market_hub", &[]);
+    let policy = RoyaltyPolicy {
+        creator_type: String::from("provider"),
+        default_royalty: 5,
+        min_royalty: 2,
+        max_royalty: 8,
+        survives_transfer: false,
+    };
+
+    // default royalty must be within the bounds
+    assert!(matches!(
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            hub_info.clone(),
+            ExecuteMsg::Msg(AiRoyaltyExecuteMsg::UpdatePolicy(RoyaltyPolicy {
+                default_royalty: 9,
+                ..policy.clone()
+            }))
+        ),
+        Err(ContractError::InvalidArgument { .. })
+    ));
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        hub_info.clone(),
+        ExecuteMsg::Msg(AiRoyaltyExecuteMsg::UpdatePolicy(policy.clone())),
+    )
+    .unwrap();
+
+    // the policy default applies without a royalty, an explicit royalty is bounded
+    for (creator, creator_type, royalty) in [
+        ("provider1", "provider", None),
+        ("provider2", "provider", Some(20)),
+        ("creator1", "creator", Some(20)),
+    ] {
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            hub_info.clone(),
+            ExecuteMsg::Msg(AiRoyaltyExecuteMsg::UpdateRoyalty(RoyaltyMsg {
+                contract_addr: Addr::unchecked("xxx"),
+                creator: Addr::unchecked(creator),
+                token_id: "1".to_string(),
+                creator_type: Some(String::from(creator_type)),
+                royalty,
+                payees: None,
+            })),
+        )
+        .unwrap();
+    }
+    let royalties: PageResponse<Royalty> = from_json(
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Msg(AiRoyaltyQueryMsg::GetRoyaltiesCreatorType {
+                creator_type: String::from("provider"),
+                offset: None,
+                limit: None,
+                order: Some(1),
+                start_after: None,
+                with_total: None,
+            }),
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    let mut provider_royalties: Vec<(&str, u64)> = royalties
+        .items
+        .iter()
+        .map(|royalty| (royalty.creator.as_str(), royalty.royalty))
+        .collect();
+    provider_royalties.sort();
+    assert_eq!(provider_royalties, vec![("provider1", 5), ("provider2", 8)]);
+
+    // the provider royalties do not survive the sale of the token
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        hub_info.clone(),
+        ExecuteMsg::Msg(AiRoyaltyExecuteMsg::ExpireRoyalties {
+            contract_addr: Addr::unchecked("xxx"),
+            token_id: "1".to_string(),
+        }),
+    )
+    .unwrap();
+    let royalties: PageResponse<Royalty> = from_json(
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Msg(AiRoyaltyQueryMsg::GetRoyaltiesContractTokenId {
+                contract_addr: Addr::unchecked("xxx"),
+                token_id: "1".to_string(),
+                offset: None,
+                limit: None,
+                order: None,
+                start_after: None,
+                with_total: None,
+            }),
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(royalties.items.len(), 1);
+    assert_eq!(royalties.items[0].creator, Addr::unchecked("creator1"));
+}
+
+#[test]
+fn paginate_royalties_by_contract_token_id() {
+    let mut deps = setup_contract();
+    let info = mock_info("market_hub", &[coin(50, DENOM)]);
+
+    for i in 1u64..5u64 {
+        let msg = ExecuteMsg::Msg(AiRoyaltyExecuteMsg::UpdateRoyalty(RoyaltyMsg {
+            contract_addr: Addr::unchecked("xxx"),
+            creator: Addr::unchecked(format!("provider{}", i)),
+            token_id: "1".to_string(),
+            creator_type: Some(String::from("sacx")),
+            royalty: Some(5),
+            payees: None,
+        }));
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    }
+
+    let mut creators = vec![];
+    let mut start_after = None;
+    loop {
+        let page: PageResponse<Royalty> = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::Msg(AiRoyaltyQueryMsg::GetRoyaltiesContractTokenId {
+                    contract_addr: Addr::unchecked("xxx"),
+                    token_id: "1".to_string(),
+                    offset: None,
+                    start_after,
+                    limit: Some(3),
+                    order: None,
+                    with_total: Some(true),
+                }),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(page.total, Some(4));
+        creators.extend(page.items.into_iter().map(|royalty| royalty.creator));
+        match page.next_cursor {
+            Some(cursor) => start_after = Some(cursor),
+            None => break,
+        }
+    }
+    creators.sort();
+    assert_eq!(
+        creators,
+        (1..5)
+            .map(|i| Addr::unchecked(format!("provider{}", i)))
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn reindex_legacy_royalties() {
+    let mut deps = setup_contract();
+
+    // before 0.2.0 the royalties had no creator type index
+    for i in 1u64..4 {
+        let royalty = Royalty {
+            contract_addr: Addr::unchecked("xxx"),
+            token_id: i.to_string(),
+            creator: Addr::unchecked("provider1"),
+            royalty: 10,
+            creator_type: String::from("creator"),
+            payees: None,
+        };
+        let key = get_key_royalty(
+            royalty.contract_addr.as_bytes(),
+            royalty.token_id.as_bytes(),
+            royalty.creator.as_bytes(),
+        );
+        Map::<&[u8], Royalty>::new("royalties")
+            .save(&mut deps.storage, &key, &royalty)
+            .unwrap();
+    }
+    set_contract_version(
+        &mut deps.storage,
+        "crates.io:market_ai_royalty_storage",
+        "0.1.0",
+    )
+    .unwrap();
+    migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+    let creator_type_royalties = |deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>| {
+        let royalties: PageResponse<Royalty> = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::Msg(AiRoyaltyQueryMsg::GetRoyaltiesCreatorType {
+                    creator_type: String::from("creator"),
+                    offset: None,
+                    start_after: None,
+                    limit: None,
+                    order: None,
+                    with_total: None,
+                }),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        royalties.items.len()
+    };
+    assert_eq!(creator_type_royalties(&deps), 0);
+
+    let reindex = |limit: u32| ExecuteMsg::ReindexRoyalties { limit: Some(limit) };
+    assert!(matches!(
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("anyone", &[]),
+            reindex(2)
+        ),
+        Err(ContractError::Unauthorized { .. })
+    ));
+    let mut batches = vec![];
+    for _ in 0..3 {
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(CREATOR, &[]),
+            reindex(2),
+        )
+        .unwrap();
+        batches.push(res.attributes[1..].to_vec());
+    }
+    assert_eq!(
+        batches,
+        vec![
+            vec![attr("reindexed", "2"), attr("done", "false")],
+            vec![attr("reindexed", "1"), attr("done", "true")],
+            vec![attr("done", "true")],
+        ]
+    );
+    assert_eq!(creator_type_royalties(&deps), 3);
+}
@@ -5,18 +5,20 @@ use crate::error::ContractError;
 use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, UpdateContractMsg};
 use crate::state::{
     auctions, get_contract_token_id, get_unique_key, increment_auctions, ContractInfo,
-    CONTRACT_INFO,
+    CONTRACT_INFO, CONTRACT_INFO_KEY,
 };
 use cosmwasm_std::{
     attr, to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Order, Response, StdError,
     StdResult,
 };
 use cosmwasm_std::{Addr, Api, Record};
+use cw2::set_contract_version;
 use cw_storage_plus::Bound;
 use market_auction_extend::{
     Auction, AuctionExecuteMsg, AuctionQueryMsg, AuctionsResponse, PagingOptions,
     QueryAuctionsResult,
 };
+use migration::{migrate_version, move_item, MigrationStep, VERSION_KEY};
 use std::convert::TryInto;
 use std::usize;
 
@@ -24,6 +26,10 @@ use std::usize;
 const MAX_LIMIT: u8 = 100;
 const DEFAULT_LIMIT: u8 = 20;
 
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:market_auction_extend_storage";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
 // Note, you can use StdResult in some functions where you do not
 // make use of the custom errors
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -33,6 +39,7 @@ pub fn instantiate(
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     // first time deploy, it will not know about the implementation
     let info = ContractInfo {
         governance: msg.governance,
@@ -362,11 +369,23 @@ fn parse_auction(
             buyout_per_price: auction.buyout_per_price,
             step_price: auction.step_price,
             amount: auction.amount,
+            reserved_for: auction.reserved_for,
+            multi_winner: auction.multi_winner,
         })
     })
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
-    Ok(Response::default())
+pub fn migrate(deps: DepsMut, env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+    migrate_version(deps, &env, CONTRACT_NAME, CONTRACT_VERSION, MIGRATIONS)
+}
+
+const MIGRATIONS: &[MigrationStep] = &[MigrationStep {
+    version: "0.2.0",
+    name: "move_contract_info",
+    run: move_contract_info,
+}];
+
+fn move_contract_info(deps: DepsMut, _env: &Env) -> StdResult<()> {
+    move_item(deps.storage, VERSION_KEY, CONTRACT_INFO_KEY)
 }
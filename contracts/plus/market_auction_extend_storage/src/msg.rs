@@ -1,8 +1,11 @@
+use cosmwasm_schema::QueryResponses;
 use cosmwasm_std::Addr;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use market_auction_extend::{AuctionExecuteMsg, AuctionQueryMsg};
+use market_auction_extend::{AuctionExecuteMsg, AuctionQueryMsg, AuctionQueryResponse};
+
+use crate::state::ContractInfo;
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InstantiateMsg {
@@ -23,11 +26,13 @@ pub struct UpdateContractMsg {
     pub creator: Option<Addr>,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, QueryResponses)]
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
     // GetOfferings returns a list of all offerings
+    #[returns(AuctionQueryResponse)]
     Msg(AuctionQueryMsg),
+    #[returns(ContractInfo)]
     GetContractInfo {},
 }
 
@@ -13,7 +13,8 @@ pub struct ContractInfo {
 }
 
 pub const AUCTIONS_COUNT: Item<u64> = Item::new("num_auctions");
-pub const CONTRACT_INFO: Item<ContractInfo> = Item::new("contract_info");
+pub const CONTRACT_INFO_KEY: &str = "market_info";
+pub const CONTRACT_INFO: Item<ContractInfo> = Item::new(CONTRACT_INFO_KEY);
 
 pub fn num_auctions(storage: &dyn Storage) -> StdResult<u64> {
     Ok(AUCTIONS_COUNT.may_load(storage)?.unwrap_or_default())
@@ -53,6 +53,8 @@ fn sort_auction() {
             orig_per_price: Uint128::from(i),
             bidder: None,
             amount: Uint128::from(10u128),
+            reserved_for: None,
+            multi_winner: false,
         };
         let msg = ExecuteMsg::Msg(AuctionExecuteMsg::UpdateAuction { auction });
         let _res = execute(deps.as_mut(), contract_env.clone(), info.clone(), msg).unwrap();
@@ -74,6 +76,8 @@ fn sort_auction() {
         orig_per_price: Uint128::from(1u128),
         bidder: None,
         amount: Uint128::from(10u128),
+        reserved_for: None,
+        multi_winner: false,
     };
     let msg = ExecuteMsg::Msg(AuctionExecuteMsg::UpdateAuction { auction });
     let _res = execute(deps.as_mut(), contract_env.clone(), info.clone(), msg).unwrap();
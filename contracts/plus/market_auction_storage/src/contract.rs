@@ -4,24 +4,28 @@ use cosmwasm_std::entry_point;
 use crate::error::ContractError;
 use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, UpdateContractMsg};
 use crate::state::{
-    auctions, get_contract_token_id, increment_auctions, ContractInfo, CONTRACT_INFO,
+    auctions, get_contract_token_id, get_end_timestamp_id, increment_auctions, ContractInfo,
+    CONTRACT_INFO, CONTRACT_INFO_KEY, REINDEX_CURSOR,
 };
 use cosmwasm_std::{
     attr, to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Order, Record, Response,
-    StdError, StdResult,
+    StdError, StdResult, Uint128,
 };
 use cosmwasm_std::{Addr, Api};
+use cw2::set_contract_version;
 use cw_storage_plus::Bound;
+use market::{count_total, Pagination, DEFAULT_LIMIT, MAX_LIMIT};
 use market_auction::{
     Auction, AuctionExecuteMsg, AuctionQueryMsg, AuctionsResponse, PagingOptions,
     QueryAuctionsResult,
 };
+use migration::{migrate_version, move_item, MigrationStep, VERSION_KEY};
 use std::convert::TryInto;
 use std::usize;
 
-// settings for pagination
-const MAX_LIMIT: u8 = 100;
-const DEFAULT_LIMIT: u8 = 20;
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:market_auction_storage";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 // Note, you can use StdResult in some functions where you do not
 // make use of the custom errors
@@ -32,6 +36,7 @@ pub fn instantiate(
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     // first time deploy, it will not know about the implementation
     let info = ContractInfo {
         governance: msg.governance,
@@ -57,6 +62,7 @@ pub fn execute(
             AuctionExecuteMsg::RemoveAuction { id } => try_remove_auction(deps, info, env, id),
         },
         ExecuteMsg::UpdateInfo(msg) => try_update_info(deps, info, env, msg),
+        ExecuteMsg::ReindexAuctions { limit } => try_reindex_auctions(deps, info, limit),
     }
 }
 
@@ -143,7 +149,7 @@ pub fn try_update_info(
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         // implement Query Auction from market base
         QueryMsg::Auction(auction_query) => match auction_query {
@@ -159,6 +165,12 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
             AuctionQueryMsg::GetAuctionsByContract { contract, options } => {
                 to_json_binary(&query_auctions_by_contract(deps, contract, &options)?)
             }
+            AuctionQueryMsg::GetAuctionsEndingBetween { from, to, options } => {
+                to_json_binary(&query_auctions_ending_between(deps, from, to, &options)?)
+            }
+            AuctionQueryMsg::GetUnclaimedEndedAuctions { options } => {
+                to_json_binary(&query_unclaimed_ended_auctions(deps, env, &options)?)
+            }
             AuctionQueryMsg::GetAuction { auction_id } => {
                 to_json_binary(&query_auction(deps, auction_id)?)
             }
@@ -175,34 +187,34 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
 
 // ============================== Query Handlers ==============================
 
-fn _get_range_params(options: &PagingOptions) -> (usize, Option<Bound>, Option<Bound>, Order) {
-    let limit = options.limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
-    // let mut max: Option<Bound> = None;
-    let mut order_enum = Order::Descending;
-    if let Some(num) = options.order {
-        if num == 1 {
-            order_enum = Order::Ascending;
-        }
-    }
-
-    // if there is offset, assign to min or max
-    let min = options
-        .offset
-        .map(|offset| Bound::Exclusive(offset.to_be_bytes().to_vec()));
-
-    (limit, min, None, order_enum)
+fn auction_pagination(options: &PagingOptions) -> Pagination {
+    Pagination::new(
+        options.start_after.clone(),
+        options.offset.map(|offset| offset.to_be_bytes().to_vec()),
+        options.limit,
+        options.order,
+        Order::Descending,
+    )
 }
 
 pub fn query_auctions(deps: Deps, options: &PagingOptions) -> StdResult<AuctionsResponse> {
-    let (limit, min, max, order_enum) = _get_range_params(options);
-
-    let res: StdResult<Vec<QueryAuctionsResult>> = auctions()
-        .range(deps.storage, min, max, order_enum)
-        .take(limit)
-        .map(|kv_item| parse_auction(deps.api, kv_item))
-        .collect();
-
-    Ok(AuctionsResponse { items: res? })
+    let pagination = auction_pagination(options);
+    let (min, max, order_enum) = pagination.bounds();
+    let total = count_total(options.with_total, || {
+        auctions()
+            .range(deps.storage, None, None, Order::Ascending)
+            .count()
+    });
+    let (items, next_cursor) = pagination.page(
+        auctions().range(deps.storage, min, max, order_enum),
+        |kv_item| parse_auction(deps.api, kv_item),
+    )?;
+
+    Ok(AuctionsResponse {
+        items,
+        next_cursor,
+        total,
+    })
 }
 
 pub fn query_auctions_by_asker(
@@ -210,17 +222,29 @@ pub fn query_auctions_by_asker(
     asker: Addr,
     options: &PagingOptions,
 ) -> StdResult<AuctionsResponse> {
-    let (limit, min, max, order_enum) = _get_range_params(options);
+    let pagination = auction_pagination(options);
+    let (min, max, order_enum) = pagination.bounds();
     let asker_raw = deps.api.addr_canonicalize(asker.as_str())?;
-    let res: StdResult<Vec<QueryAuctionsResult>> = auctions()
-        .idx
-        .asker
-        .items(deps.storage, &asker_raw, min, max, order_enum)
-        .take(limit)
-        .map(|kv_item| parse_auction(deps.api, kv_item))
-        .collect();
-
-    Ok(AuctionsResponse { items: res? })
+    let total = count_total(options.with_total, || {
+        auctions()
+            .idx
+            .asker
+            .pks(deps.storage, &asker_raw, None, None, Order::Ascending)
+            .count()
+    });
+    let (items, next_cursor) = pagination.page(
+        auctions()
+            .idx
+            .asker
+            .items(deps.storage, &asker_raw, min, max, order_enum),
+        |kv_item| parse_auction(deps.api, kv_item),
+    )?;
+
+    Ok(AuctionsResponse {
+        items,
+        next_cursor,
+        total,
+    })
 }
 
 // if bidder is empty, it is pending auctions
@@ -229,20 +253,32 @@ pub fn query_auctions_by_bidder(
     bidder: Option<Addr>,
     options: &PagingOptions,
 ) -> StdResult<AuctionsResponse> {
-    let (limit, min, max, order_enum) = _get_range_params(options);
+    let pagination = auction_pagination(options);
+    let (min, max, order_enum) = pagination.bounds();
     let bidder_raw = match bidder {
         Some(addr) => deps.api.addr_canonicalize(addr.as_str())?.to_vec(),
         None => vec![],
     };
-    let res: StdResult<Vec<QueryAuctionsResult>> = auctions()
-        .idx
-        .bidder
-        .items(deps.storage, &bidder_raw, min, max, order_enum)
-        .take(limit)
-        .map(|kv_item| parse_auction(deps.api, kv_item))
-        .collect();
-
-    Ok(AuctionsResponse { items: res? })
+    let total = count_total(options.with_total, || {
+        auctions()
+            .idx
+            .bidder
+            .pks(deps.storage, &bidder_raw, None, None, Order::Ascending)
+            .count()
+    });
+    let (items, next_cursor) = pagination.page(
+        auctions()
+            .idx
+            .bidder
+            .items(deps.storage, &bidder_raw, min, max, order_enum),
+        |kv_item| parse_auction(deps.api, kv_item),
+    )?;
+
+    Ok(AuctionsResponse {
+        items,
+        next_cursor,
+        total,
+    })
 }
 
 pub fn query_auctions_by_contract(
@@ -250,17 +286,94 @@ pub fn query_auctions_by_contract(
     contract: Addr,
     options: &PagingOptions,
 ) -> StdResult<AuctionsResponse> {
-    let (limit, min, max, order_enum) = _get_range_params(options);
+    let pagination = auction_pagination(options);
+    let (min, max, order_enum) = pagination.bounds();
     let contract_raw = deps.api.addr_canonicalize(contract.as_str())?;
-    let res: StdResult<Vec<QueryAuctionsResult>> = auctions()
+    let total = count_total(options.with_total, || {
+        auctions()
+            .idx
+            .contract
+            .pks(deps.storage, &contract_raw, None, None, Order::Ascending)
+            .count()
+    });
+    let (items, next_cursor) = pagination.page(
+        auctions()
+            .idx
+            .contract
+            .items(deps.storage, &contract_raw, min, max, order_enum),
+        |kv_item| parse_auction(deps.api, kv_item),
+    )?;
+
+    Ok(AuctionsResponse {
+        items,
+        next_cursor,
+        total,
+    })
+}
+
+// auctions with an end timestamp in [from, to). the index is keyed by end timestamp + id,
+// so the index key is used as cursor instead of the auction id
+pub fn query_auctions_ending_between(
+    deps: Deps,
+    from: Uint128,
+    to: Uint128,
+    options: &PagingOptions,
+) -> StdResult<AuctionsResponse> {
+    let mut pagination = Pagination::new(
+        options.start_after.clone(),
+        None,
+        options.limit,
+        options.order,
+        Order::Ascending,
+    );
+    let from_key = from.u128().to_be_bytes().to_vec();
+    let to_key = to.u128().to_be_bytes().to_vec();
+    let total = count_total(options.with_total, || {
+        auctions()
+            .idx
+            .end_timestamp
+            .range(
+                deps.storage,
+                Some(Bound::Inclusive(from_key.clone())),
+                Some(Bound::Exclusive(to_key.clone())),
+                Order::Ascending,
+            )
+            .count()
+    });
+    // the cursor replaces the window bound on the side the page starts from
+    pagination.min.get_or_insert(Bound::Inclusive(from_key));
+    pagination.max.get_or_insert(Bound::Exclusive(to_key));
+    let (min, max, order_enum) = pagination.bounds();
+    let records = auctions()
         .idx
-        .contract
-        .items(deps.storage, &contract_raw, min, max, order_enum)
-        .take(limit)
-        .map(|kv_item| parse_auction(deps.api, kv_item))
-        .collect();
+        .end_timestamp
+        .range(deps.storage, min, max, order_enum)
+        .map(|item| {
+            item.map(|(pk, auction)| {
+                let key =
+                    get_end_timestamp_id(auction.end_timestamp, auction.id.unwrap_or_default());
+                (key.0, (pk, auction))
+            })
+        });
+    let (items, next_cursor) =
+        pagination.page(records, |(_, kv_item)| parse_auction(deps.api, kv_item))?;
 
-    Ok(AuctionsResponse { items: res? })
+    Ok(AuctionsResponse {
+        items,
+        next_cursor,
+        total,
+    })
+}
+
+// claimed auctions are removed from the storage, so every auction that has reached
+// its end timestamp is still waiting for a claim
+pub fn query_unclaimed_ended_auctions(
+    deps: Deps,
+    env: Env,
+    options: &PagingOptions,
+) -> StdResult<AuctionsResponse> {
+    let now = Uint128::from(env.block.time.seconds());
+    query_auctions_ending_between(deps, Uint128::zero(), now + Uint128::one(), options)
 }
 
 pub fn query_auction_raw(deps: Deps, auction_id: u64) -> StdResult<Auction> {
@@ -270,7 +383,7 @@ pub fn query_auction_raw(deps: Deps, auction_id: u64) -> StdResult<Auction> {
 pub fn query_auction(deps: Deps, auction_id: u64) -> StdResult<QueryAuctionsResult> {
     let auction = auctions().load(deps.storage, &auction_id.to_be_bytes())?;
     let kv_item: Record<Auction> = (auction_id.to_be_bytes().to_vec(), auction);
-    return parse_auction(deps.api, Ok(kv_item));
+    parse_auction(deps.api, kv_item)
 }
 
 pub fn query_auction_by_contract_tokenid(
@@ -288,7 +401,7 @@ pub fn query_auction_by_contract_tokenid(
         )
         .transpose()
     {
-        return parse_auction(deps.api, kv_item);
+        return parse_auction(deps.api, kv_item?);
     }
 
     Err(StdError::generic_err("Auction not found"))
@@ -298,37 +411,99 @@ pub fn query_contract_info(deps: Deps) -> StdResult<ContractInfo> {
     CONTRACT_INFO.load(deps.storage)
 }
 
-fn parse_auction(
-    api: &dyn Api,
-    item: StdResult<Record<Auction>>,
-) -> StdResult<QueryAuctionsResult> {
-    item.and_then(|(k, auction)| {
-        // will panic if length is greater than 8, but we can make sure it is u64
-        // try_into will box vector to fixed array
-        let id: u64 = u64::from_be_bytes(k.try_into().unwrap());
-        Ok(QueryAuctionsResult {
-            id,
-            contract_addr: api.addr_humanize(&auction.contract_addr)?,
-            asker: api.addr_humanize(&auction.asker)?,
-            // bidder can be None
-            bidder: auction
-                .bidder
-                .map(|can_addr| api.addr_humanize(&can_addr).unwrap()),
-            token_id: auction.token_id,
-            price: auction.price,
-            orig_price: auction.orig_price,
-            start: auction.start,
-            end: auction.end,
-            start_timestamp: auction.start_timestamp,
-            end_timestamp: auction.end_timestamp,
-            cancel_fee: auction.cancel_fee,
-            buyout_price: auction.buyout_price,
-            step_price: auction.step_price,
-        })
+fn parse_auction(api: &dyn Api, (k, auction): Record<Auction>) -> StdResult<QueryAuctionsResult> {
+    // will panic if length is greater than 8, but we can make sure it is u64
+    // try_into will box vector to fixed array
+    let id: u64 = u64::from_be_bytes(k.try_into().unwrap());
+    Ok(QueryAuctionsResult {
+        id,
+        contract_addr: api.addr_humanize(&auction.contract_addr)?,
+        asker: api.addr_humanize(&auction.asker)?,
+        // bidder can be None
+        bidder: auction
+            .bidder
+            .map(|can_addr| api.addr_humanize(&can_addr).unwrap()),
+        token_id: auction.token_id,
+        price: auction.price,
+        orig_price: auction.orig_price,
+        start: auction.start,
+        end: auction.end,
+        start_timestamp: auction.start_timestamp,
+        end_timestamp: auction.end_timestamp,
+        cancel_fee: auction.cancel_fee,
+        buyout_price: auction.buyout_price,
+        step_price: auction.step_price,
+        reserved_for: auction.reserved_for,
     })
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
-    Ok(Response::default())
+pub fn migrate(deps: DepsMut, env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+    migrate_version(deps, &env, CONTRACT_NAME, CONTRACT_VERSION, MIGRATIONS)
+}
+
+const MIGRATIONS: &[MigrationStep] = &[
+    MigrationStep {
+        version: "0.2.0",
+        name: "move_contract_info",
+        run: move_contract_info,
+    },
+    MigrationStep {
+        version: "0.2.0",
+        name: "reindex_auctions",
+        run: reindex_auctions,
+    },
+];
+
+fn move_contract_info(deps: DepsMut, _env: &Env) -> StdResult<()> {
+    move_item(deps.storage, VERSION_KEY, CONTRACT_INFO_KEY)
+}
+
+fn reindex_auctions(deps: DepsMut, _env: &Env) -> StdResult<()> {
+    // re-saving every auction does not fit in one transaction, the creator runs it in batches
+    // with ReindexAuctions. Until it is done the ending queries miss the older auctions
+    REINDEX_CURSOR.save(deps.storage, &vec![])
+}
+
+/// saving the auctions again fills the end timestamp index for auctions created before it
+pub fn try_reindex_auctions(
+    deps: DepsMut,
+    info: MessageInfo,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let ContractInfo { creator, .. } = CONTRACT_INFO.load(deps.storage)?;
+    if info.sender.ne(&creator) {
+        return Err(ContractError::Unauthorized {
+            sender: info.sender.to_string(),
+        });
+    }
+    let cursor = match REINDEX_CURSOR.may_load(deps.storage)? {
+        Some(cursor) => cursor,
+        None => {
+            return Ok(Response::new().add_attributes(vec![
+                attr("action", "reindex_auctions"),
+                attr("done", "true"),
+            ]))
+        }
+    };
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let min = (!cursor.is_empty()).then_some(Bound::Exclusive(cursor));
+    let batch = auctions()
+        .range(deps.storage, min, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (k, auction) in batch.iter() {
+        auctions().save(deps.storage, k, auction)?;
+    }
+    let done = batch.len() < limit;
+    match batch.last() {
+        Some((k, _)) if !done => REINDEX_CURSOR.save(deps.storage, k)?,
+        _ => REINDEX_CURSOR.remove(deps.storage),
+    }
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "reindex_auctions"),
+        attr("reindexed", batch.len().to_string()),
+        attr("done", done.to_string()),
+    ]))
 }
@@ -1,8 +1,11 @@
+use cosmwasm_schema::QueryResponses;
 use cosmwasm_std::Addr;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use market_auction::{AuctionExecuteMsg, AuctionQueryMsg};
+use market_auction::{AuctionExecuteMsg, AuctionQueryMsg, AuctionQueryResponse};
+
+use crate::state::ContractInfo;
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InstantiateMsg {
@@ -15,6 +18,8 @@ pub enum ExecuteMsg {
     Auction(AuctionExecuteMsg),
     // other implementation
     UpdateInfo(UpdateContractMsg),
+    // re-save the next auctions of the reindex queued by the migration, creator only
+    ReindexAuctions { limit: Option<u32> },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -23,11 +28,13 @@ pub struct UpdateContractMsg {
     pub creator: Option<Addr>,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, QueryResponses)]
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
     // GetOfferings returns a list of all offerings
+    #[returns(AuctionQueryResponse)]
     Auction(AuctionQueryMsg),
+    #[returns(ContractInfo)]
     GetContractInfo {},
 }
 
@@ -1,7 +1,7 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::{Addr, CanonicalAddr, StdResult, Storage};
+use cosmwasm_std::{Addr, CanonicalAddr, StdResult, Storage, Uint128};
 use cw_storage_plus::{Index, IndexList, IndexedMap, Item, MultiIndex, PkOwned, UniqueIndex};
 use market_auction::Auction;
 
@@ -13,7 +13,10 @@ pub struct ContractInfo {
 }
 
 pub const AUCTIONS_COUNT: Item<u64> = Item::new("num_auctions");
-pub const CONTRACT_INFO: Item<ContractInfo> = Item::new("contract_info");
+pub const CONTRACT_INFO_KEY: &str = "market_info";
+pub const CONTRACT_INFO: Item<ContractInfo> = Item::new(CONTRACT_INFO_KEY);
+/// last auction key re-saved by the running reindex, empty when it has not started yet
+pub const REINDEX_CURSOR: Item<Vec<u8>> = Item::new("reindex_cursor");
 
 pub fn num_auctions(storage: &dyn Storage) -> StdResult<u64> {
     Ok(AUCTIONS_COUNT.may_load(storage)?.unwrap_or_default())
@@ -31,6 +34,8 @@ pub struct AuctionIndexes<'a> {
     pub bidder: MultiIndex<'a, Auction>,
     pub contract: MultiIndex<'a, Auction>,
     pub contract_token_id: UniqueIndex<'a, PkOwned, Auction>,
+    // ordered by end time, so it can be ranged over a time window
+    pub end_timestamp: UniqueIndex<'a, PkOwned, Auction>,
 }
 
 impl<'a> IndexList<Auction> for AuctionIndexes<'a> {
@@ -40,6 +45,7 @@ impl<'a> IndexList<Auction> for AuctionIndexes<'a> {
             &self.bidder,
             &self.contract,
             &self.contract_token_id,
+            &self.end_timestamp,
         ];
         Box::new(v.into_iter())
     }
@@ -52,6 +58,13 @@ pub fn get_contract_token_id(contract: &CanonicalAddr, token_id: &str) -> PkOwne
     PkOwned(vec)
 }
 
+// end timestamp + auction id => unique id, big endian so the keys sort by end time
+pub fn get_end_timestamp_id(end_timestamp: Uint128, id: u64) -> PkOwned {
+    let mut vec = end_timestamp.u128().to_be_bytes().to_vec();
+    vec.extend(id.to_be_bytes());
+    PkOwned(vec)
+}
+
 // this IndexedMap instance has a lifetime
 pub fn auctions<'a>() -> IndexedMap<'a, &'a [u8], Auction, AuctionIndexes<'a>> {
     let indexes = AuctionIndexes {
@@ -76,6 +89,11 @@ pub fn auctions<'a>() -> IndexedMap<'a, &'a [u8], Auction, AuctionIndexes<'a>> {
             |o| get_contract_token_id(&o.contract_addr, &o.token_id),
             "request__id",
         ),
+        // the auction id is always set before the auction is saved
+        end_timestamp: UniqueIndex::new(
+            |o| get_end_timestamp_id(o.end_timestamp, o.id.unwrap_or_default()),
+            "auctions__end_timestamp",
+        ),
     };
     IndexedMap::new("auctions", indexes)
 }
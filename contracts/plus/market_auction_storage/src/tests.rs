@@ -1,15 +1,19 @@
 use crate::contract::*;
 
 use crate::msg::*;
+use crate::state::{ContractInfo, CONTRACT_INFO_KEY};
 use cosmwasm_std::testing::{
     mock_dependencies_with_balance, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
 };
 use cosmwasm_std::Api;
-use cosmwasm_std::{coin, coins, from_json, Addr, Env, Order, OwnedDeps, Uint128};
+use cosmwasm_std::{attr, coin, coins, from_json, Addr, Env, Order, OwnedDeps, Uint128};
+use cw2::set_contract_version;
+use cw_storage_plus::Map;
 use market_auction::QueryAuctionsResult;
 use market_auction::{
     Auction, AuctionExecuteMsg, AuctionQueryMsg, AuctionsResponse, PagingOptions,
 };
+use migration::{move_item, VERSION_KEY};
 
 const CREATOR: &str = "owner";
 const DENOM: &str = "orai";
@@ -52,6 +56,7 @@ fn sort_auction() {
             asker: asker.clone(),
             orig_price: Uint128::from(i),
             bidder: None,
+            reserved_for: None,
         };
         let msg = ExecuteMsg::Auction(AuctionExecuteMsg::UpdateAuction { auction });
         let _res = execute(deps.as_mut(), contract_env.clone(), info.clone(), msg).unwrap();
@@ -67,6 +72,8 @@ fn sort_auction() {
                 limit: Some(100),
                 offset: Some(40),
                 order: Some(Order::Ascending as u8),
+                start_after: None,
+                with_total: None,
             },
         }),
     )
@@ -85,3 +92,266 @@ fn sort_auction() {
     let value: QueryAuctionsResult = from_json(&res).unwrap();
     println!("value: {:?}", value);
 }
+
+#[test]
+fn paginate_auctions_by_bidder() {
+    let (mut deps, contract_env) = setup_contract();
+    let info = mock_info(CREATOR, &[coin(50000000, DENOM)]);
+    let contract_addr = deps.api.addr_canonicalize("contract_addr").unwrap();
+    let asker = deps.api.addr_canonicalize("asker").unwrap();
+    let bidder = deps.api.addr_canonicalize("bidder").unwrap();
+
+    // only every third auction has a bid
+    for i in 1..10u128 {
+        let auction = Auction {
+            id: None,
+            price: Uint128::from(i),
+            start: contract_env.block.height + 15,
+            end: contract_env.block.height + 100,
+            cancel_fee: Some(1),
+            buyout_price: Some(Uint128::from(i)),
+            start_timestamp: Uint128::from(0u64),
+            end_timestamp: Uint128::from(0u64),
+            step_price: 1,
+            contract_addr: contract_addr.clone(),
+            token_id: i.to_string(),
+            asker: asker.clone(),
+            orig_price: Uint128::from(i),
+            bidder: if i % 3 == 0 {
+                Some(bidder.clone())
+            } else {
+                None
+            },
+            reserved_for: None,
+        };
+        let msg = ExecuteMsg::Auction(AuctionExecuteMsg::UpdateAuction { auction });
+        execute(deps.as_mut(), contract_env.clone(), info.clone(), msg).unwrap();
+    }
+
+    let mut ids = vec![];
+    let mut start_after = None;
+    loop {
+        let res = query(
+            deps.as_ref(),
+            contract_env.clone(),
+            QueryMsg::Auction(AuctionQueryMsg::GetAuctionsByBidder {
+                bidder: Some(Addr::unchecked("bidder")),
+                options: PagingOptions {
+                    offset: None,
+                    start_after,
+                    limit: Some(2),
+                    order: None,
+                    with_total: Some(true),
+                },
+            }),
+        )
+        .unwrap();
+        let value: AuctionsResponse = from_json(&res).unwrap();
+        assert_eq!(value.total, Some(3));
+        ids.extend(value.items.iter().map(|f| f.id));
+        match value.next_cursor {
+            Some(cursor) => start_after = Some(cursor),
+            None => break,
+        }
+    }
+    // descending by default
+    assert_eq!(ids, vec![9, 6, 3]);
+}
+
+#[test]
+fn query_auctions_by_end_timestamp() {
+    let (mut deps, contract_env) = setup_contract();
+    let info = mock_info(CREATOR, &[coin(50000000, DENOM)]);
+    let contract_addr = deps.api.addr_canonicalize("contract_addr").unwrap();
+    let asker = deps.api.addr_canonicalize("asker").unwrap();
+    let now = contract_env.block.time.seconds();
+
+    // the auctions end these many minutes from now, auction 5 ends right now
+    let minutes: Vec<i64> = vec![30, -10, 5, -20, 0, 90, 45];
+    for (i, minute) in minutes.iter().enumerate() {
+        let auction = Auction {
+            id: None,
+            price: Uint128::from(1u64),
+            start: contract_env.block.height,
+            end: contract_env.block.height + 100,
+            cancel_fee: Some(1),
+            buyout_price: None,
+            start_timestamp: Uint128::from(0u64),
+            end_timestamp: Uint128::from((now as i64 + minute * 60) as u64),
+            step_price: 1,
+            contract_addr: contract_addr.clone(),
+            token_id: i.to_string(),
+            asker: asker.clone(),
+            orig_price: Uint128::from(1u64),
+            bidder: None,
+            reserved_for: None,
+        };
+        let msg = ExecuteMsg::Auction(AuctionExecuteMsg::UpdateAuction { auction });
+        execute(deps.as_mut(), contract_env.clone(), info.clone(), msg).unwrap();
+    }
+
+    // ending within the next hour, soonest first, paged by the index cursor
+    let mut ids = vec![];
+    let mut start_after = None;
+    loop {
+        let res = query(
+            deps.as_ref(),
+            contract_env.clone(),
+            QueryMsg::Auction(AuctionQueryMsg::GetAuctionsEndingBetween {
+                from: Uint128::from(now),
+                to: Uint128::from(now + 3600),
+                options: PagingOptions {
+                    offset: None,
+                    start_after,
+                    limit: Some(2),
+                    order: None,
+                    with_total: Some(true),
+                },
+            }),
+        )
+        .unwrap();
+        let value: AuctionsResponse = from_json(&res).unwrap();
+        assert_eq!(value.total, Some(4));
+        ids.extend(value.items.iter().map(|f| f.id));
+        match value.next_cursor {
+            Some(cursor) => start_after = Some(cursor),
+            None => break,
+        }
+    }
+    assert_eq!(ids, vec![5, 3, 1, 7]);
+
+    // ended and not claimed yet, latest first
+    let res = query(
+        deps.as_ref(),
+        contract_env.clone(),
+        QueryMsg::Auction(AuctionQueryMsg::GetUnclaimedEndedAuctions {
+            options: PagingOptions {
+                offset: None,
+                start_after: None,
+                limit: None,
+                order: Some(Order::Descending as u8),
+                with_total: None,
+            },
+        }),
+    )
+    .unwrap();
+    let value: AuctionsResponse = from_json(&res).unwrap();
+    let ids: Vec<u64> = value.items.iter().map(|f| f.id).collect();
+    assert_eq!(ids, vec![5, 2, 4]);
+
+    // a claimed auction is removed and leaves the index
+    let msg = ExecuteMsg::Auction(AuctionExecuteMsg::RemoveAuction { id: 2 });
+    execute(deps.as_mut(), contract_env.clone(), info, msg).unwrap();
+    let res = query(
+        deps.as_ref(),
+        contract_env,
+        QueryMsg::Auction(AuctionQueryMsg::GetUnclaimedEndedAuctions {
+            options: PagingOptions {
+                offset: None,
+                start_after: None,
+                limit: None,
+                order: None,
+                with_total: Some(true),
+            },
+        }),
+    )
+    .unwrap();
+    let value: AuctionsResponse = from_json(&res).unwrap();
+    let ids: Vec<u64> = value.items.iter().map(|f| f.id).collect();
+    assert_eq!(ids, vec![4, 5]);
+    assert_eq!(value.total, Some(2));
+}
+
+#[test]
+fn migrate_legacy_storage() {
+    let (mut deps, contract_env) = setup_contract();
+    let contract_addr = deps.api.addr_canonicalize("contract_addr").unwrap();
+    let asker = deps.api.addr_canonicalize("asker").unwrap();
+    let now = contract_env.block.time.seconds();
+
+    // before 0.2.0 the contract info was kept under the cw2 key and auctions had no end index
+    move_item(&mut deps.storage, CONTRACT_INFO_KEY, VERSION_KEY).unwrap();
+    let auction = Auction {
+        id: Some(1),
+        price: Uint128::from(1u64),
+        start: contract_env.block.height,
+        end: contract_env.block.height + 100,
+        cancel_fee: Some(1),
+        buyout_price: None,
+        start_timestamp: Uint128::from(0u64),
+        end_timestamp: Uint128::from(now + 60),
+        step_price: 1,
+        contract_addr,
+        token_id: "1".to_string(),
+        asker,
+        orig_price: Uint128::from(1u64),
+        bidder: None,
+        reserved_for: None,
+    };
+    Map::<&[u8], Auction>::new("auctions")
+        .save(&mut deps.storage, &1u64.to_be_bytes(), &auction)
+        .unwrap();
+
+    let res = migrate(deps.as_mut(), contract_env.clone(), MigrateMsg {}).unwrap();
+    let steps: Vec<&str> = res
+        .attributes
+        .iter()
+        .filter(|attr| attr.key == "migration_step")
+        .map(|attr| attr.value.as_str())
+        .collect();
+    assert_eq!(
+        steps,
+        vec!["0.2.0:move_contract_info", "0.2.0:reindex_auctions"]
+    );
+
+    let res = query(
+        deps.as_ref(),
+        contract_env.clone(),
+        QueryMsg::GetContractInfo {},
+    )
+    .unwrap();
+    let value: ContractInfo = from_json(&res).unwrap();
+    assert_eq!(value.governance, Addr::unchecked(CREATOR));
+
+    // the ending query only sees the auction once the creator has run the reindex
+    let res = execute(
+        deps.as_mut(),
+        contract_env.clone(),
+        mock_info(CREATOR, &[]),
+        ExecuteMsg::ReindexAuctions { limit: None },
+    )
+    .unwrap();
+    assert_eq!(
+        res.attributes[1..],
+        [attr("reindexed", "1"), attr("done", "true")]
+    );
+
+    let res = query(
+        deps.as_ref(),
+        contract_env,
+        QueryMsg::Auction(AuctionQueryMsg::GetAuctionsEndingBetween {
+            from: Uint128::from(now),
+            to: Uint128::from(now + 3600),
+            options: PagingOptions {
+                offset: None,
+                start_after: None,
+                limit: None,
+                order: None,
+                with_total: None,
+            },
+        }),
+    )
+    .unwrap();
+    let value: AuctionsResponse = from_json(&res).unwrap();
+    let ids: Vec<u64> = value.items.iter().map(|f| f.id).collect();
+    assert_eq!(ids, vec![1]);
+
+    // a downgrade is refused
+    set_contract_version(
+        &mut deps.storage,
+        "crates.io:market_auction_storage",
+        "0.3.0",
+    )
+    .unwrap();
+    migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap_err();
+}
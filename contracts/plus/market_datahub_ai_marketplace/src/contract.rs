@@ -7,15 +7,21 @@ use cosmwasm_std::{
     attr, to_json_binary, Addr, BankMsg, Binary, Coin, CosmosMsg, Deps, DepsMut, Env, MessageInfo,
     Order, Response, StdResult, Uint128,
 };
+use cw2::set_contract_version;
+use migration::migrate_version;
 
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg};
+use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg};
 use crate::query::AIMarketQueryMsg;
 use crate::state::{
     get_next_package_offering_id, package_offerings, ContractInfo, PackageOffering, CONTRACT_INFO,
 };
 use cw_storage_plus::Bound;
 
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:market_datahub_ai_marketplace";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
@@ -23,6 +29,7 @@ pub fn instantiate(
     _msg_info: MessageInfo,
     msg: InstantiateMsg,
 ) -> StdResult<Response> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     let info = ContractInfo {
         name: msg.name,
         creator: msg.creator,
@@ -75,6 +82,11 @@ pub fn query(deps: Deps, _env: Env, msg: AIMarketQueryMsg) -> StdResult<Binary>
     }
 }
 
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+    migrate_version(deps, &env, CONTRACT_NAME, CONTRACT_VERSION, &[])
+}
+
 /** Command Handler **/
 
 pub fn try_buy_package(
@@ -11,6 +11,9 @@ pub struct InstantiateMsg {
     pub fee: u64,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct UpdateClaimInfoMsg {
     pub owner: Addr,
@@ -1,15 +1,13 @@
 use crate::annotation_result::{
     get_annotation_results_by_annotation_id, get_reviewed_upload_by_annotation_id,
 };
-use crate::contract::{get_handle_msg, query_datahub, DATAHUB_STORAGE};
 use crate::error::ContractError;
 use crate::state::{ContractInfo, CONTRACT_INFO};
 use cosmwasm_std::{
-    attr, coins, from_json, BankMsg, CosmosMsg, Decimal, Deps, DepsMut, Env, MessageInfo, Response,
-    Uint128,
+    attr, coins, BankMsg, CosmosMsg, Decimal, Deps, DepsMut, Env, MessageInfo, Response, Uint128,
 };
 use cosmwasm_std::{Addr, StdError};
-use market_datahub::{Annotation, AnnotationReviewer, DataHubExecuteMsg, DataHubQueryMsg};
+use market_datahub::{Annotation, AnnotationReviewer, DataHubStorage};
 use std::collections::HashMap;
 use std::convert::TryInto;
 use std::ops::{AddAssign, Mul};
@@ -66,11 +64,7 @@ pub fn try_withdraw(
         }
 
         // remove annotation
-        cosmos_msgs.push(get_handle_msg(
-            governance.as_str(),
-            DATAHUB_STORAGE,
-            DataHubExecuteMsg::RemoveAnnotation { id: annotation_id },
-        )?);
+        cosmos_msgs.push(governance.remove_annotation(annotation_id)?);
 
         return Ok(Response::new()
             .add_messages(cosmos_msgs)
@@ -153,13 +147,7 @@ pub fn try_execute_request_annotation(
 
     let mut cosmos_msg = vec![];
 
-    cosmos_msg.push(get_handle_msg(
-        governance.as_str(),
-        DATAHUB_STORAGE,
-        DataHubExecuteMsg::UpdateAnnotation {
-            annotation: annotation.clone(),
-        },
-    )?);
+    cosmos_msg.push(governance.update_annotation(annotation.clone())?);
 
     Ok(Response::new()
         .add_messages(cosmos_msg)
@@ -344,13 +332,7 @@ pub fn try_payout(
 
     // Update annotation pais status
     annotation.is_paid = true;
-    cosmos_msg.push(get_handle_msg(
-        governance.as_str(),
-        DATAHUB_STORAGE,
-        DataHubExecuteMsg::UpdateAnnotation {
-            annotation: annotation.clone(),
-        },
-    )?);
+    cosmos_msg.push(governance.update_annotation(annotation.clone())?);
 
     Ok(Response::new()
         .add_messages(cosmos_msg)
@@ -358,11 +340,10 @@ pub fn try_payout(
 }
 
 pub fn get_annotation(deps: Deps, annotation_id: u64) -> Result<Annotation, ContractError> {
-    let annotation: Annotation = from_json(&query_datahub(
-        deps,
-        DataHubQueryMsg::GetAnnotation { annotation_id },
-    )?)
-    .map_err(|_| ContractError::InvalidGetAnnotation {})?;
+    let ContractInfo { governance, .. } = CONTRACT_INFO.load(deps.storage)?;
+    let annotation = governance
+        .get_annotation(&deps.querier, annotation_id)
+        .map_err(|_| ContractError::InvalidGetAnnotation {})?;
     Ok(annotation)
 }
 
@@ -370,15 +351,14 @@ pub fn get_reviewer_by_annotation_id(
     deps: Deps,
     annotation_id: u64,
 ) -> Result<Vec<AnnotationReviewer>, ContractError> {
-    let reviewers = from_json(&query_datahub(
-        deps,
-        DataHubQueryMsg::GetAnnotationReviewerByAnnotationId { annotation_id },
-    )?)
-    .map_err(|_| {
-        ContractError::Std(StdError::generic_err(
-            "There is an error when collecting reviewers",
-        ))
-    })?;
+    let ContractInfo { governance, .. } = CONTRACT_INFO.load(deps.storage)?;
+    let reviewers = governance
+        .get_annotation_reviewers(&deps.querier, annotation_id)
+        .map_err(|_| {
+            ContractError::Std(StdError::generic_err(
+                "There is an error when collecting reviewers",
+            ))
+        })?;
 
     Ok(reviewers)
 }
@@ -1,15 +1,12 @@
 use std::convert::TryInto;
 
 use cosmwasm_std::{
-    attr, from_json, Addr, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Response, StdError, Uint128,
-};
-use market_datahub::{
-    AnnotationResult, AnnotationReviewer, AnnotatorResult, DataHubExecuteMsg, DataHubQueryMsg,
+    attr, Addr, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Response, StdError, Uint128,
 };
+use market_datahub::{AnnotationResult, AnnotationReviewer, AnnotatorResult, DataHubStorage};
 
 use crate::{
     annotation::get_annotation,
-    contract::{get_handle_msg, query_datahub, DATAHUB_STORAGE},
     error::ContractError,
     state::{ContractInfo, CONTRACT_INFO},
 };
@@ -84,22 +81,16 @@ pub fn try_add_annotation_result(
 
     let mut msg: Vec<CosmosMsg> = vec![];
 
-    msg.push(get_handle_msg(
-        governance.as_str(),
-        DATAHUB_STORAGE,
-        DataHubExecuteMsg::AddAnnotationResult {
-            annotation_result: AnnotationResult {
-                id: if existed_annotation_result_of_reviewer.is_some() {
-                    existed_annotation_result_of_reviewer.unwrap().id
-                } else {
-                    None
-                },
-                annotation_id,
-                reviewer_address: info.sender.clone(),
-                data: annotator_results.clone(),
-            },
+    msg.push(governance.add_annotation_result(AnnotationResult {
+        id: if existed_annotation_result_of_reviewer.is_some() {
+            existed_annotation_result_of_reviewer.unwrap().id
+        } else {
+            None
         },
-    )?);
+        annotation_id,
+        reviewer_address: info.sender.clone(),
+        data: annotator_results.clone(),
+    })?);
 
     Ok(Response::new().add_messages(msg).add_attributes(vec![
         attr("action", "reviewer_commit_result"),
@@ -174,22 +165,16 @@ pub fn try_add_reviewed_upload(
 
     let mut msg: Vec<CosmosMsg> = vec![];
 
-    msg.push(get_handle_msg(
-        governance.as_str(),
-        DATAHUB_STORAGE,
-        DataHubExecuteMsg::AddReviewedUpload {
-            reviewed_result: AnnotationResult {
-                id: if old_reviewed_upload_by_reviewer.is_some() {
-                    old_reviewed_upload_by_reviewer.unwrap().id
-                } else {
-                    None
-                },
-                annotation_id,
-                reviewer_address: info.sender.clone(),
-                data: reviewed_uploads,
-            },
+    msg.push(governance.add_reviewed_upload(AnnotationResult {
+        id: if old_reviewed_upload_by_reviewer.is_some() {
+            old_reviewed_upload_by_reviewer.unwrap().id
+        } else {
+            None
         },
-    )?);
+        annotation_id,
+        reviewer_address: info.sender.clone(),
+        data: reviewed_uploads,
+    })?);
 
     Ok(Response::new().add_messages(msg).add_attributes(vec![
         attr("action", "reviewer_commit_reviewed_upload"),
@@ -229,14 +214,7 @@ pub fn try_add_annotation_reviewer(
 
     let mut cosmos_msg: Vec<CosmosMsg> = vec![];
 
-    cosmos_msg.push(get_handle_msg(
-        governance.as_str(),
-        DATAHUB_STORAGE,
-        DataHubExecuteMsg::AddAnnotationReviewer {
-            annotation_id,
-            reviewer_address: reviewer_address.clone(),
-        },
-    )?);
+    cosmos_msg.push(governance.add_annotation_reviewer(annotation_id, reviewer_address.clone())?);
 
     Ok(Response::new()
         .add_messages(cosmos_msg)
@@ -278,14 +256,8 @@ pub fn try_remove_annotation_reviewer(
 
     let mut cosmos_msg: Vec<CosmosMsg> = vec![];
 
-    cosmos_msg.push(get_handle_msg(
-        governance.as_str(),
-        DATAHUB_STORAGE,
-        DataHubExecuteMsg::RemoveAnnotationReviewer {
-            annotation_id,
-            reviewer_address: reviewer_address.clone(),
-        },
-    )?);
+    cosmos_msg
+        .push(governance.remove_annotation_reviewer(annotation_id, reviewer_address.clone())?);
 
     Ok(Response::new()
         .add_messages(cosmos_msg)
@@ -300,11 +272,11 @@ pub fn get_annotation_results_by_annotation_id(
     deps: Deps,
     annotation_id: u64,
 ) -> Result<Vec<AnnotationResult>, ContractError> {
-    let annotation_results = from_json(&query_datahub(
-        deps,
-        DataHubQueryMsg::GetAnnotationResultsByAnnotationId { annotation_id },
-    )?)
-    .map_err(|_err| ContractError::InvalidGetAnnotationResult {})?;
+    let annotation_results = CONTRACT_INFO
+        .load(deps.storage)?
+        .governance
+        .get_annotation_results(&deps.querier, annotation_id)
+        .map_err(|_err| ContractError::InvalidGetAnnotationResult {})?;
     Ok(annotation_results)
 }
 
@@ -313,18 +285,15 @@ pub fn get_annotation_results_by_annotation_id_and_reviewer(
     annotation_id: u64,
     reviewer_address: Addr,
 ) -> Result<Option<AnnotationResult>, ContractError> {
-    let result = from_json(&query_datahub(
-        deps,
-        DataHubQueryMsg::GetAnnotationResultsByAnnotationIdAndReviewer {
-            annotation_id,
-            reviewer_address,
-        },
-    )?)
-    .map_err(|_| {
-        ContractError::Std(StdError::generic_err(
-            "There is an error while collecting results",
-        ))
-    })?;
+    let result = CONTRACT_INFO
+        .load(deps.storage)?
+        .governance
+        .get_annotation_result(&deps.querier, annotation_id, reviewer_address)
+        .map_err(|_| {
+            ContractError::Std(StdError::generic_err(
+                "There is an error while collecting results",
+            ))
+        })?;
 
     Ok(result)
 }
@@ -334,18 +303,15 @@ pub fn get_annotation_reviewer_by_unique_key(
     annotation_id: u64,
     reviewer_address: Addr,
 ) -> Result<Option<AnnotationReviewer>, ContractError> {
-    let annotation_reviewer = from_json(&query_datahub(
-        deps,
-        DataHubQueryMsg::GetAnnotationReviewerByUniqueKey {
-            annotation_id,
-            reviewer_address,
-        },
-    )?)
-    .map_err(|_| {
-        ContractError::Std(StdError::generic_err(
-            "There is an error while collecting reviewers",
-        ))
-    })?;
+    let annotation_reviewer = CONTRACT_INFO
+        .load(deps.storage)?
+        .governance
+        .get_annotation_reviewer(&deps.querier, annotation_id, reviewer_address)
+        .map_err(|_| {
+            ContractError::Std(StdError::generic_err(
+                "There is an error while collecting reviewers",
+            ))
+        })?;
     Ok(annotation_reviewer)
 }
 
@@ -353,15 +319,15 @@ pub fn get_reviewed_upload_by_annotation_id(
     deps: Deps,
     annotation_id: u64,
 ) -> Result<Vec<AnnotationResult>, ContractError> {
-    let reviewed_upload = from_json(&query_datahub(
-        deps,
-        DataHubQueryMsg::GetReviewedUploadByAnnotationId { annotation_id },
-    )?)
-    .map_err(|_| {
-        ContractError::Std(StdError::generic_err(
-            "There is an error while collecting reviewed upload",
-        ))
-    })?;
+    let reviewed_upload = CONTRACT_INFO
+        .load(deps.storage)?
+        .governance
+        .get_reviewed_uploads(&deps.querier, annotation_id)
+        .map_err(|_| {
+            ContractError::Std(StdError::generic_err(
+                "There is an error while collecting reviewed upload",
+            ))
+        })?;
     Ok(reviewed_upload)
 }
 
@@ -370,17 +336,14 @@ pub fn get_reviewed_upload_by_annotation_and_reviewer(
     annotation_id: u64,
     reviewer_address: Addr,
 ) -> Result<Option<AnnotationResult>, ContractError> {
-    let item = from_json(&query_datahub(
-        deps,
-        DataHubQueryMsg::GetReviewedUploadByAnnotationIdAndReviewer {
-            annotation_id,
-            reviewer_address,
-        },
-    )?)
-    .map_err(|_| {
-        ContractError::Std(StdError::generic_err(
-            "There is an error while collecting reviewed uploads",
-        ))
-    })?;
+    let item = CONTRACT_INFO
+        .load(deps.storage)?
+        .governance
+        .get_reviewed_upload(&deps.querier, annotation_id, reviewer_address)
+        .map_err(|_| {
+            ContractError::Std(StdError::generic_err(
+                "There is an error while collecting reviewed uploads",
+            ))
+        })?;
     Ok(item)
 }
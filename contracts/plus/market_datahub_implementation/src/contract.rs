@@ -1,8 +1,6 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 
-use std::fmt;
-
 use crate::annotation::{
     try_execute_request_annotation, try_payout, try_withdraw as try_withdraw_annotation,
 };
@@ -14,28 +12,25 @@ use crate::offering::{handle_sell_nft, try_buy, try_handle_mint, try_sell, try_w
 
 use crate::error::ContractError;
 use crate::msg::{
-    ExecuteMsg, InstantiateMsg, MigrateMsg, ProxyExecuteMsg, ProxyQueryMsg, QueryMsg, SellRoyalty,
-    UpdateContractMsg,
+    ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, SellRoyalty, UpdateContractMsg,
 };
-use crate::state::{ContractInfo, CONTRACT_INFO};
+use crate::state::{ContractInfo, CONTRACT_INFO, CONTRACT_INFO_KEY};
 use cosmwasm_std::Addr;
 use cosmwasm_std::{
-    attr, from_json, to_json_binary, BankMsg, Binary, Coin, CosmosMsg, Deps, DepsMut, Empty, Env,
+    attr, from_json, to_json_binary, BankMsg, Binary, Coin, CosmosMsg, Deps, DepsMut, Env,
     MessageInfo, Response, StdResult, Uint128, WasmMsg,
 };
 use cw1155::{Cw1155ExecuteMsg, Cw1155ReceiveMsg};
-use market::{query_proxy, StorageExecuteMsg, StorageQueryMsg};
-use market_ai_royalty::{sanitize_royalty, AiRoyaltyQueryMsg};
-use market_datahub::DataHubQueryMsg;
-use schemars::JsonSchema;
-use serde::Serialize;
+use cw2::set_contract_version;
+use market::MarketHubContract;
+use market_ai_royalty::{sanitize_royalty, AiRoyaltyQueryMsg, AiRoyaltyStorage};
+use market_datahub::{DataHubQueryMsg, DataHubStorage};
+use migration::{migrate_version, move_item, MigrationStep, VERSION_KEY};
 
 pub const MAX_ROYALTY_PERCENT: u64 = 1_000_000_000;
 pub const MAX_DECIMAL_POINT: u64 = 1_000_000_000;
 pub const MAX_FEE_PERMILLE: u64 = 100;
 pub const EXPIRED_BLOCK_RANGE: u64 = 50000;
-pub const DATAHUB_STORAGE: &str = "datahub_storage";
-pub const AI_ROYALTY_STORAGE: &str = "ai_royalty";
 pub const CREATOR_NAME: &str = "creator";
 
 fn sanitize_fee(fee: u64, name: &str) -> Result<u64, ContractError> {
@@ -47,6 +42,10 @@ fn sanitize_fee(fee: u64, name: &str) -> Result<u64, ContractError> {
     Ok(fee)
 }
 
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:market_datahub_implementation";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
 // Note, you can use StdResult in some functions where you do not
 // make use of the custom errors
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -56,12 +55,13 @@ pub fn instantiate(
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     let info = ContractInfo {
         name: msg.name,
         creator: info.sender.to_string(),
         denom: msg.denom,
         fee: sanitize_fee(msg.fee, "fee")?,
-        governance: msg.governance,
+        governance: MarketHubContract(msg.governance),
         max_royalty: sanitize_royalty(msg.max_royalty, MAX_ROYALTY_PERCENT, "max_royalty")?,
         expired_block: EXPIRED_BLOCK_RANGE,
         decimal_point: MAX_DECIMAL_POINT,
@@ -218,7 +218,7 @@ pub fn try_update_info(
             contract_info.denom = denom;
         }
         if let Some(governance) = msg.governance {
-            contract_info.governance = governance;
+            contract_info.governance = MarketHubContract(governance);
         }
         if let Some(max_royalty) = msg.max_royalty {
             contract_info.max_royalty =
@@ -302,54 +302,37 @@ pub fn query_contract_info(deps: Deps) -> StdResult<ContractInfo> {
     CONTRACT_INFO.load(deps.storage)
 }
 
-// remove recursive by query storage_addr first, then call query_proxy
-pub fn get_storage_addr(deps: Deps, contract: Addr, name: &str) -> StdResult<Addr> {
-    deps.querier.query_wasm_smart(
-        contract,
-        &ProxyQueryMsg::<Empty>::Storage(StorageQueryMsg::QueryStorageAddr {
-            name: name.to_string(),
-        }),
-    )
-}
-
-pub fn get_handle_msg<T>(addr: &str, name: &str, msg: T) -> StdResult<CosmosMsg>
-where
-    T: Clone + fmt::Debug + PartialEq + JsonSchema + Serialize,
-{
-    let offering_msg = to_json_binary(&ProxyExecuteMsg::Msg(msg))?;
-    let proxy_msg: ProxyExecuteMsg =
-        ProxyExecuteMsg::Storage(StorageExecuteMsg::UpdateStorageData {
-            name: name.to_string(),
-            msg: offering_msg,
-        });
-
-    Ok(WasmMsg::Execute {
-        contract_addr: addr.to_string(),
-        msg: to_json_binary(&proxy_msg)?,
-        funds: vec![],
-    }
-    .into())
-}
-
 pub fn query_datahub(deps: Deps, msg: DataHubQueryMsg) -> StdResult<Binary> {
     let contract_info = CONTRACT_INFO.load(deps.storage)?;
-    query_proxy(
-        deps,
-        get_storage_addr(deps, contract_info.governance, DATAHUB_STORAGE)?,
-        to_json_binary(&ProxyQueryMsg::Msg(msg))?,
-    )
+    contract_info
+        .governance
+        .query_datahub_binary(&deps.querier, msg)
 }
 
 pub fn query_ai_royalty(deps: Deps, msg: AiRoyaltyQueryMsg) -> StdResult<Binary> {
     let contract_info = CONTRACT_INFO.load(deps.storage)?;
-    query_proxy(
-        deps,
-        get_storage_addr(deps, contract_info.governance, AI_ROYALTY_STORAGE)?,
-        to_json_binary(&ProxyQueryMsg::Msg(msg))?,
-    )
+    contract_info
+        .governance
+        .query_ai_royalty_binary(&deps.querier, msg)
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
-    Ok(Response::default())
+pub fn migrate(deps: DepsMut, env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    Ok(migrate_version(
+        deps,
+        &env,
+        CONTRACT_NAME,
+        CONTRACT_VERSION,
+        MIGRATIONS,
+    )?)
+}
+
+const MIGRATIONS: &[MigrationStep] = &[MigrationStep {
+    version: "0.2.0",
+    name: "move_contract_info",
+    run: move_contract_info,
+}];
+
+fn move_contract_info(deps: DepsMut, _env: &Env) -> StdResult<()> {
+    move_item(deps.storage, VERSION_KEY, CONTRACT_INFO_KEY)
 }
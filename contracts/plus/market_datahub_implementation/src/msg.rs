@@ -1,12 +1,16 @@
 use std::fmt;
 
+use cosmwasm_schema::QueryResponses;
 use cosmwasm_std::{Coin, Empty, Addr, Uint128};
 use cw1155::Cw1155ReceiveMsg;
 use market::{StorageExecuteMsg, StorageQueryMsg};
-use market_ai_royalty::AiRoyaltyQueryMsg;
-use market_datahub::{AnnotatorResult, DataHubQueryMsg, MintMsg};
+use market_ai_royalty::{AiRoyaltyQueryMsg, AiRoyaltyQueryResponse};
+use market_datahub::{AnnotatorResult, DataHubQueryMsg, DataHubQueryResponse, MintMsg};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+
+use crate::state::ContractInfo;
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InstantiateMsg {
     pub name: String,
@@ -131,12 +135,15 @@ pub struct UpdateContractMsg {
     pub decimal_point: Option<u64>,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, QueryResponses)]
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
     // Auction info must be queried from auction contract
+    #[returns(ContractInfo)]
     GetContractInfo {},
+    #[returns(DataHubQueryResponse)]
     DataHub(DataHubQueryMsg),
+    #[returns(AiRoyaltyQueryResponse)]
     AiRoyalty(AiRoyaltyQueryMsg),
 }
 
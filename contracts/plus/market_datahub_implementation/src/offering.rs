@@ -1,46 +1,36 @@
-use crate::contract::{
-    get_handle_msg, get_storage_addr, query_ai_royalty, query_datahub, AI_ROYALTY_STORAGE,
-    CREATOR_NAME, DATAHUB_STORAGE,
-};
+use crate::contract::CREATOR_NAME;
 use crate::error::ContractError;
-use crate::msg::{ProxyQueryMsg, SellRoyalty};
+use crate::msg::SellRoyalty;
 use crate::state::{ContractInfo, CONTRACT_INFO};
 use cosmwasm_std::{
-    attr, coins, from_json, to_json_binary, BankMsg, CosmosMsg, Decimal, Deps, DepsMut, Env,
-    MessageInfo, Response, StdResult, Uint128, WasmMsg,
+    attr, coins, to_json_binary, BankMsg, CosmosMsg, Decimal, Deps, DepsMut, Env, MessageInfo,
+    Response, StdResult, Uint128, WasmMsg,
 };
 use cosmwasm_std::{Addr, StdError};
 use cw1155::{BalanceResponse, Cw1155ExecuteMsg, Cw1155QueryMsg, Cw1155ReceiveMsg};
-use market_ai_royalty::{AiRoyaltyExecuteMsg, AiRoyaltyQueryMsg, Royalty, RoyaltyMsg};
-use market_datahub::{DataHubExecuteMsg, DataHubQueryMsg, MintMsg, Offering};
+use market::MarketHubContract;
+use market_ai_royalty::{AiRoyaltyStorage, Royalty, RoyaltyMsg};
+use market_datahub::{DataHubStorage, MintMsg, Offering};
 use std::ops::Mul;
 
 pub fn add_msg_royalty(
     sender: &str,
-    governance: &str,
+    governance: &MarketHubContract,
     msg: RoyaltyMsg,
 ) -> StdResult<Vec<CosmosMsg>> {
     let mut cosmos_msgs: Vec<CosmosMsg> = vec![];
     // update ai royalty provider
-    cosmos_msgs.push(get_handle_msg(
-        governance,
-        AI_ROYALTY_STORAGE,
-        AiRoyaltyExecuteMsg::UpdateRoyalty(RoyaltyMsg {
-            royalty: None,
-            ..msg.clone()
-        }),
-    )?);
+    cosmos_msgs.push(governance.update_royalty(RoyaltyMsg {
+        royalty: None,
+        ..msg.clone()
+    })?);
 
     // update creator as the caller of the mint tx
-    cosmos_msgs.push(get_handle_msg(
-        governance,
-        AI_ROYALTY_STORAGE,
-        AiRoyaltyExecuteMsg::UpdateRoyalty(RoyaltyMsg {
-            creator: Addr::unchecked(sender.to_string()),
-            creator_type: Some(String::from(CREATOR_NAME)),
-            ..msg
-        }),
-    )?);
+    cosmos_msgs.push(governance.update_royalty(RoyaltyMsg {
+        creator: Addr::unchecked(sender.to_string()),
+        creator_type: Some(String::from(CREATOR_NAME)),
+        ..msg
+    })?);
     Ok(cosmos_msgs)
 }
 
@@ -83,13 +73,14 @@ pub fn try_handle_mint(
 
     let mut cosmos_msgs = add_msg_royalty(
         info.sender.as_str(),
-        governance.as_str(),
+        &governance,
         RoyaltyMsg {
             contract_addr: msg.contract_addr,
             token_id: msg.mint.mint.token_id,
             creator: msg.creator,
             creator_type: Some(msg.creator_type),
             royalty: msg.royalty,
+            payees: None,
         },
     )?;
     cosmos_msgs.push(mint_msg);
@@ -117,17 +108,12 @@ pub fn try_sell(
     let sender = info.sender.clone().to_string();
 
     // TODO: This should be commented when we allow multiple owners to sell this nft
-    let offering_result: Result<Offering, ContractError> = deps
-        .querier
-        .query_wasm_smart(
-            get_storage_addr(deps.as_ref(), governance.clone(), DATAHUB_STORAGE)?,
-            &ProxyQueryMsg::Msg(DataHubQueryMsg::GetUniqueOffering {
-                contract: contract_addr.clone(),
-                token_id: token_id.clone(),
-                owner: info.sender.clone(),
-            }),
-        )
-        .map_err(|_| ContractError::InvalidGetOffering {});
+    let offering_result = governance.get_unique_offering(
+        &deps.querier,
+        contract_addr.clone(),
+        &token_id,
+        info.sender.clone(),
+    );
     if offering_result.is_ok() {
         return Err(ContractError::TokenOnSale {});
     }
@@ -164,11 +150,7 @@ pub fn try_sell(
         amount,
     };
 
-    cosmos_msg.push(get_handle_msg(
-        governance.as_str(),
-        DATAHUB_STORAGE,
-        DataHubExecuteMsg::UpdateOffering { offering },
-    )?);
+    cosmos_msg.push(governance.update_offering(offering)?);
 
     return Ok(Response::new()
         .add_messages(cosmos_msg)
@@ -285,21 +267,11 @@ pub fn try_buy(
 
     if off.amount.checked_sub(Uint128::from(1u128))?.is_zero() {
         // remove offering in the offering storage when left amount is 0
-        cosmos_msgs.push(get_handle_msg(
-            governance.as_str(),
-            DATAHUB_STORAGE,
-            DataHubExecuteMsg::RemoveOffering { id: offering_id },
-        )?);
+        cosmos_msgs.push(governance.remove_offering(offering_id)?);
     } else {
         // decrease sell amount by 1
         off.amount = off.amount.checked_sub(Uint128::from(1u64))?;
-        cosmos_msgs.push(get_handle_msg(
-            governance.as_str(),
-            DATAHUB_STORAGE,
-            DataHubExecuteMsg::UpdateOffering {
-                offering: off.clone(),
-            },
-        )?);
+        cosmos_msgs.push(governance.update_offering(off.clone())?);
     }
 
     Ok(Response::new()
@@ -349,11 +321,7 @@ pub fn try_withdraw(
         let mut cw721_transfer_cosmos_msg: Vec<CosmosMsg> = vec![exec_cw721_transfer.into()];
 
         // remove offering
-        cw721_transfer_cosmos_msg.push(get_handle_msg(
-            governance.as_str(),
-            DATAHUB_STORAGE,
-            DataHubExecuteMsg::RemoveOffering { id: offering_id },
-        )?);
+        cw721_transfer_cosmos_msg.push(governance.remove_offering(offering_id)?);
 
         return Ok(Response::new()
             .add_messages(cw721_transfer_cosmos_msg)
@@ -379,17 +347,12 @@ pub fn handle_sell_nft(
     // check if same token Id form same original contract is already on sale
 
     // TODO: This should be commented when we allow multiple owners to sell this nft
-    let offering_result: Result<Offering, ContractError> = deps
-        .querier
-        .query_wasm_smart(
-            get_storage_addr(deps.as_ref(), governance.clone(), DATAHUB_STORAGE)?,
-            &ProxyQueryMsg::Msg(DataHubQueryMsg::GetUniqueOffering {
-                contract: info.sender.clone(),
-                token_id: rcv_msg.token_id.clone(),
-                owner: Addr::unchecked(rcv_msg.operator.as_str()),
-            }),
-        )
-        .map_err(|_| ContractError::InvalidGetOffering {});
+    let offering_result = governance.get_unique_offering(
+        &deps.querier,
+        info.sender.clone(),
+        &rcv_msg.token_id,
+        Addr::unchecked(rcv_msg.operator.as_str()),
+    );
     if offering_result.is_ok() {
         return Err(ContractError::TokenOnSale {});
     }
@@ -405,13 +368,7 @@ pub fn handle_sell_nft(
 
     let mut cosmos_msgs = vec![];
     // push save message to datahub storage
-    cosmos_msgs.push(get_handle_msg(
-        governance.as_str(),
-        DATAHUB_STORAGE,
-        DataHubExecuteMsg::UpdateOffering {
-            offering: offering.clone(),
-        },
-    )?);
+    cosmos_msgs.push(governance.update_offering(offering.clone())?);
 
     Ok(Response::new()
         .add_messages(cosmos_msgs)
@@ -424,11 +381,10 @@ pub fn handle_sell_nft(
 }
 
 fn get_offering(deps: Deps, offering_id: u64) -> Result<Offering, ContractError> {
-    let offering: Offering = from_json(&query_datahub(
-        deps,
-        DataHubQueryMsg::GetOffering { offering_id },
-    )?)
-    .map_err(|_| ContractError::InvalidGetOffering {})?;
+    let ContractInfo { governance, .. } = CONTRACT_INFO.load(deps.storage)?;
+    let offering = governance
+        .get_offering(&deps.querier, offering_id)
+        .map_err(|_| ContractError::InvalidGetOffering {})?;
     Ok(offering)
 }
 
@@ -437,18 +393,16 @@ fn get_royalties(
     contract_addr: &str,
     token_id: &str,
 ) -> Result<Vec<Royalty>, ContractError> {
-    let royalties: Vec<Royalty> = from_json(&query_ai_royalty(
-        deps,
-        AiRoyaltyQueryMsg::GetRoyaltiesContractTokenId {
-            contract_addr: Addr::unchecked(contract_addr),
+    let ContractInfo { governance, .. } = CONTRACT_INFO.load(deps.storage)?;
+    let royalties = governance
+        .get_royalties_contract_token_id(
+            &deps.querier,
+            Addr::unchecked(contract_addr),
+            token_id,
+            None,
+        )
+        .map_err(|_| ContractError::InvalidGetRoyaltiesTokenId {
             token_id: token_id.to_string(),
-            offset: None,
-            limit: None,
-            order: Some(1),
-        },
-    )?)
-    .map_err(|_| ContractError::InvalidGetRoyaltiesTokenId {
-        token_id: token_id.to_string(),
-    })?;
+        })?;
     Ok(royalties)
 }
@@ -1,4 +1,4 @@
-use cosmwasm_std::Addr;
+use market::MarketHubContract;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -13,10 +13,11 @@ pub struct ContractInfo {
     /// the accepted denom
     pub denom: String,
     /// this defines the number of blocks until the end of auction
-    pub governance: Addr,
+    pub governance: MarketHubContract,
     pub max_royalty: u64,
     pub expired_block: u64,
     pub decimal_point: u64,
 }
 
-pub const CONTRACT_INFO: Item<ContractInfo> = Item::new("contract_info");
+pub const CONTRACT_INFO_KEY: &str = "market_info";
+pub const CONTRACT_INFO: Item<ContractInfo> = Item::new(CONTRACT_INFO_KEY);
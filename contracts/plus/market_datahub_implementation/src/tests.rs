@@ -11,6 +11,7 @@ use cosmwasm_std::{
 };
 use cw1155::{BalanceResponse, Cw1155ExecuteMsg, Cw1155QueryMsg, Cw1155ReceiveMsg};
 use market::mock::{mock_dependencies, mock_env, MockQuerier};
+use market::PageResponse;
 use market_ai_royalty::{AiRoyaltyQueryMsg, Royalty};
 use market_datahub::{
     Annotation, AnnotationResult, AnnotationReviewer, AnnotatorResult, DataHubQueryMsg,
@@ -279,7 +280,7 @@ fn update_info_test() {
 
         let query_info = QueryMsg::GetContractInfo {};
         let res_info: ContractInfo = from_json(&manager.query(query_info).unwrap()).unwrap();
-        assert_eq!(res_info.governance.as_str(), HUB_ADDR);
+        assert_eq!(res_info.governance.addr().as_str(), HUB_ADDR);
     }
 }
 
@@ -332,12 +333,14 @@ fn test_royalties() {
 
         println!("offering: {:?}", offering_first);
 
-        let result: Vec<Offering> = from_json(
+        let result: PageResponse<Offering> = from_json(
             &manager
                 .query(QueryMsg::DataHub(DataHubQueryMsg::GetOfferings {
                     offset: None,
                     limit: None,
                     order: None,
+                    start_after: None,
+                    with_total: None,
                 }))
                 .unwrap(),
         )
@@ -382,7 +385,7 @@ fn test_royalties() {
         let mut total_payment = Uint128::zero();
 
         // query royalties
-        let royalties: Vec<Royalty> = from_json(
+        let royalties: PageResponse<Royalty> = from_json(
             &manager
                 .query(QueryMsg::AiRoyalty(
                     AiRoyaltyQueryMsg::GetRoyaltiesTokenId {
@@ -390,13 +393,15 @@ fn test_royalties() {
                         offset: None,
                         limit: None,
                         order: Some(1),
+                        start_after: None,
+                        with_total: None,
                     },
                 ))
                 .unwrap(),
         )
         .unwrap();
         println!("royalties are: {:?}", royalties);
-        assert_eq!(royalties.len(), 2);
+        assert_eq!(royalties.items.len(), 2);
 
         // placeholders to verify royalties
         let mut to_addrs: Vec<Addr> = vec![];
@@ -431,7 +436,7 @@ fn test_royalties() {
             .mul(Decimal::from_ratio(offering.amount.u128(), 1u128));
 
         // increment royalty to total payment
-        for royalty in royalties {
+        for royalty in royalties.items {
             let index = to_addrs.iter().position(|op| op.eq(&royalty.creator));
             if let Some(index) = index {
                 let amount = amounts[index];
@@ -474,22 +479,24 @@ fn withdraw_offering() {
         let _res = manager.execute(info, msg).unwrap();
 
         // Offering should be listed
-        let res: Vec<Offering> = from_json(
+        let res: PageResponse<Offering> = from_json(
             &manager
                 .query(QueryMsg::DataHub(DataHubQueryMsg::GetOfferings {
                     offset: None,
                     limit: None,
                     order: None,
+                    start_after: None,
+                    with_total: None,
                 }))
                 .unwrap(),
         )
         .unwrap();
-        assert_eq!(1, res.len());
+        assert_eq!(1, res.items.len());
 
         // withdraw offering
         let withdraw_info_unauthorized = mock_info("sellerr", &coins(2, DENOM));
         let withdraw_msg = ExecuteMsg::WithdrawNft {
-            offering_id: res[0].id.clone().unwrap(),
+            offering_id: res.items[0].id.unwrap(),
         };
 
         // unhappy path unauthorized
@@ -502,17 +509,19 @@ fn withdraw_offering() {
         let _res = manager.execute(withdraw_info, withdraw_msg).unwrap();
 
         // Offering should be removed
-        let res2: Vec<Offering> = from_json(
+        let res2: PageResponse<Offering> = from_json(
             &manager
                 .query(QueryMsg::DataHub(DataHubQueryMsg::GetOfferings {
                     offset: None,
                     limit: None,
                     order: None,
+                    start_after: None,
+                    with_total: None,
                 }))
                 .unwrap(),
         )
         .unwrap();
-        assert_eq!(0, res2.len());
+        assert_eq!(0, res2.items.len());
     }
 }
 
@@ -724,8 +733,10 @@ fn test_request_annotations() {
             offset: None,
             limit: None,
             order: Some(1),
+            start_after: None,
+            with_total: None,
         });
-        let mut annotations: Vec<Annotation> =
+        let mut annotations: PageResponse<Annotation> =
             from_json(&manager.query(annotation_msg).unwrap()).unwrap();
         println!("list annotations: {:?}\n", annotations);
 
@@ -735,6 +746,8 @@ fn test_request_annotations() {
             offset: None,
             limit: None,
             order: Some(1),
+            start_after: None,
+            with_total: None,
         });
         annotations = from_json(&manager.query(annotation_msg).unwrap()).unwrap();
         println!("list annotations query contract: {:?}\n", annotations);
@@ -746,6 +759,8 @@ fn test_request_annotations() {
             offset: None,
             limit: None,
             order: Some(1),
+            start_after: None,
+            with_total: None,
         });
         annotations = from_json(&manager.query(annotation_msg).unwrap()).unwrap();
         println!("annotation query contract token id: {:?}\n", annotations);
@@ -2,7 +2,7 @@
 use cosmwasm_std::entry_point;
 
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg, UpdateContractMsg};
+use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, UpdateContractMsg};
 use crate::state::{
     annotation_results, annotation_reviewers, annotations, get_contract_token_id,
     get_unique_annotation_reviewer_key, get_unique_key, increment_annotation_result,
@@ -18,13 +18,15 @@ use cosmwasm_std::{
     attr, to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Order, Record, Response,
     StdError, StdResult,
 };
-use cw_storage_plus::Bound;
+use cw2::set_contract_version;
+use market::{count_total, PageResponse, Pagination};
+use migration::migrate_version;
 use std::convert::TryInto;
 use std::usize;
 
-// settings for pagination
-const MAX_LIMIT: u8 = 100;
-const DEFAULT_LIMIT: u8 = 20;
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:market_datahub_storage";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 // Note, you can use StdResult in some functions where you do not
 // make use of the custom errors
@@ -35,6 +37,7 @@ pub fn instantiate(
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     // first time deploy, it will not know about the implementation
     let info = ContractInfo {
         governance: msg.governance,
@@ -89,25 +92,50 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Msg(auction_query) => match auction_query {
             DataHubQueryMsg::GetOfferings {
+                start_after,
                 limit,
                 offset,
                 order,
-            } => to_json_binary(&query_offerings(deps, limit, offset, order)?),
+                with_total,
+            } => to_json_binary(&query_offerings(
+                deps,
+                start_after,
+                limit,
+                offset,
+                order,
+                with_total,
+            )?),
             DataHubQueryMsg::GetOfferingsBySeller {
                 seller,
+                start_after,
                 limit,
                 offset,
                 order,
+                with_total,
             } => to_json_binary(&query_offerings_by_seller(
-                deps, seller, limit, offset, order,
+                deps,
+                seller,
+                start_after,
+                limit,
+                offset,
+                order,
+                with_total,
             )?),
             DataHubQueryMsg::GetOfferingsByContract {
                 contract,
+                start_after,
                 limit,
                 offset,
                 order,
+                with_total,
             } => to_json_binary(&query_offerings_by_contract(
-                deps, contract, limit, offset, order,
+                deps,
+                contract,
+                start_after,
+                limit,
+                offset,
+                order,
+                with_total,
             )?),
             DataHubQueryMsg::GetOffering { offering_id } => {
                 to_json_binary(&query_offering(deps, offering_id)?)
@@ -115,11 +143,20 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
             DataHubQueryMsg::GetOfferingsByContractTokenId {
                 contract,
                 token_id,
+                start_after,
                 limit,
                 offset,
                 order,
+                with_total,
             } => to_json_binary(&query_offerings_by_contract_token_id(
-                deps, contract, token_id, limit, offset, order,
+                deps,
+                contract,
+                token_id,
+                start_after,
+                limit,
+                offset,
+                order,
+                with_total,
             )?),
             DataHubQueryMsg::GetUniqueOffering {
                 contract,
@@ -127,17 +164,34 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
                 owner,
             } => to_json_binary(&query_unique_offering(deps, contract, token_id, owner)?),
             DataHubQueryMsg::GetAnnotations {
+                start_after,
+                limit,
+                offset,
+                order,
+                with_total,
+            } => to_json_binary(&query_annotations(
+                deps,
+                start_after,
                 limit,
                 offset,
                 order,
-            } => to_json_binary(&query_annotations(deps, limit, offset, order)?),
+                with_total,
+            )?),
             DataHubQueryMsg::GetAnnotationsByContract {
                 contract,
+                start_after,
                 limit,
                 offset,
                 order,
+                with_total,
             } => to_json_binary(&query_annotations_by_contract(
-                deps, contract, limit, offset, order,
+                deps,
+                contract,
+                start_after,
+                limit,
+                offset,
+                order,
+                with_total,
             )?),
             DataHubQueryMsg::GetAnnotation { annotation_id } => {
                 to_json_binary(&query_annotation(deps, annotation_id)?)
@@ -145,19 +199,36 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
             DataHubQueryMsg::GetAnnotationsByContractTokenId {
                 contract,
                 token_id,
+                start_after,
                 limit,
                 offset,
                 order,
+                with_total,
             } => to_json_binary(&query_annotations_by_contract_tokenid(
-                deps, contract, token_id, limit, offset, order,
+                deps,
+                contract,
+                token_id,
+                start_after,
+                limit,
+                offset,
+                order,
+                with_total,
             )?),
             DataHubQueryMsg::GetAnnotationsByRequester {
                 requester,
+                start_after,
                 limit,
                 offset,
                 order,
+                with_total,
             } => to_json_binary(&query_annotations_by_requester(
-                deps, requester, limit, offset, order,
+                deps,
+                requester,
+                start_after,
+                limit,
+                offset,
+                order,
+                with_total,
             )?),
             DataHubQueryMsg::GetAnnotationResult {
                 annotation_result_id,
@@ -213,6 +284,17 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     }
 }
 
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    Ok(migrate_version(
+        deps,
+        &env,
+        CONTRACT_NAME,
+        CONTRACT_VERSION,
+        &[],
+    )?)
+}
+
 pub fn get_key_royalty<'a>(token_id: &'a [u8], owner: &'a [u8]) -> Vec<u8> {
     let mut merge_vec = token_id.to_vec();
     let mut owner_vec = owner.to_vec();
@@ -522,46 +604,42 @@ pub fn try_update_info(
 
 // ============================== Query Handlers ==============================
 
-fn _get_range_params(
-    limit: Option<u8>,
+fn datahub_pagination(
+    start_after: Option<Binary>,
+    limit: Option<u32>,
     offset: Option<u64>,
     order: Option<u8>,
-) -> (usize, Option<Bound>, Option<Bound>, Order) {
-    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
-    let mut min = None;
-    let mut max = None;
-    let mut order_enum = Order::Descending;
-    if let Some(num) = order {
-        if num == 1 {
-            order_enum = Order::Ascending;
-        }
-    }
-
-    // if there is offset, assign to min or max
-    if let Some(offset) = offset {
-        let offset_value = Some(Bound::Exclusive(offset.to_be_bytes().to_vec()));
-        match order_enum {
-            Order::Ascending => min = offset_value,
-            Order::Descending => max = offset_value,
-        }
-    };
-    (limit, min, max, order_enum)
+) -> Pagination {
+    Pagination::new(
+        start_after,
+        offset.map(|offset| offset.to_be_bytes().to_vec()),
+        limit,
+        order,
+        Order::Descending,
+    )
 }
 
 pub fn query_offerings(
     deps: Deps,
-    limit: Option<u8>,
+    start_after: Option<Binary>,
+    limit: Option<u32>,
     offset: Option<u64>,
     order: Option<u8>,
-) -> StdResult<Vec<Offering>> {
-    let (limit, min, max, order_enum) = _get_range_params(limit, offset, order);
-
-    let offerings_result: StdResult<Vec<Offering>> = offerings()
-        .range(deps.storage, min, max, order_enum)
-        .take(limit)
-        .map(|kv_item| parse_offering(kv_item))
-        .collect();
-    Ok(offerings_result?)
+    with_total: Option<bool>,
+) -> StdResult<PageResponse<Offering>> {
+    let pagination = datahub_pagination(start_after, limit, offset, order);
+    let (min, max, order_enum) = pagination.bounds();
+    let total = count_total(with_total, || {
+        offerings()
+            .range(deps.storage, None, None, Order::Ascending)
+            .count()
+    });
+
+    pagination.page_response(
+        offerings().range(deps.storage, min, max, order_enum),
+        parse_offering,
+        total,
+    )
 }
 
 pub fn query_offering_ids(deps: Deps) -> StdResult<Vec<u64>> {
@@ -576,65 +654,112 @@ pub fn query_offering_ids(deps: Deps) -> StdResult<Vec<u64>> {
 pub fn query_offerings_by_seller(
     deps: Deps,
     seller: Addr,
-    limit: Option<u8>,
+    start_after: Option<Binary>,
+    limit: Option<u32>,
     offset: Option<u64>,
     order: Option<u8>,
-) -> StdResult<Vec<Offering>> {
-    let (limit, min, max, order_enum) = _get_range_params(limit, offset, order);
-    let offerings_result: StdResult<Vec<Offering>> = offerings()
-        .idx
-        .seller
-        .items(deps.storage, seller.as_bytes(), min, max, order_enum)
-        .take(limit)
-        .map(|kv_item| parse_offering(kv_item))
-        .collect();
-
-    Ok(offerings_result?)
+    with_total: Option<bool>,
+) -> StdResult<PageResponse<Offering>> {
+    let pagination = datahub_pagination(start_after, limit, offset, order);
+    let (min, max, order_enum) = pagination.bounds();
+    let total = count_total(with_total, || {
+        offerings()
+            .idx
+            .seller
+            .pks(
+                deps.storage,
+                seller.as_bytes(),
+                None,
+                None,
+                Order::Ascending,
+            )
+            .count()
+    });
+
+    pagination.page_response(
+        offerings()
+            .idx
+            .seller
+            .items(deps.storage, seller.as_bytes(), min, max, order_enum),
+        parse_offering,
+        total,
+    )
 }
 
 pub fn query_offerings_by_contract(
     deps: Deps,
     contract: Addr,
-    limit: Option<u8>,
+    start_after: Option<Binary>,
+    limit: Option<u32>,
     offset: Option<u64>,
     order: Option<u8>,
-) -> StdResult<Vec<Offering>> {
-    let (limit, min, max, order_enum) = _get_range_params(limit, offset, order);
-    let offerings_result: StdResult<Vec<Offering>> = offerings()
-        .idx
-        .contract
-        .items(deps.storage, contract.as_bytes(), min, max, order_enum)
-        .take(limit)
-        .map(|kv_item| parse_offering(kv_item))
-        .collect();
-
-    Ok(offerings_result?)
+    with_total: Option<bool>,
+) -> StdResult<PageResponse<Offering>> {
+    let pagination = datahub_pagination(start_after, limit, offset, order);
+    let (min, max, order_enum) = pagination.bounds();
+    let total = count_total(with_total, || {
+        offerings()
+            .idx
+            .contract
+            .pks(
+                deps.storage,
+                contract.as_bytes(),
+                None,
+                None,
+                Order::Ascending,
+            )
+            .count()
+    });
+
+    pagination.page_response(
+        offerings()
+            .idx
+            .contract
+            .items(deps.storage, contract.as_bytes(), min, max, order_enum),
+        parse_offering,
+        total,
+    )
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn query_offerings_by_contract_token_id(
     deps: Deps,
     contract: Addr,
     token_id: String,
-    limit: Option<u8>,
+    start_after: Option<Binary>,
+    limit: Option<u32>,
     offset: Option<u64>,
     order: Option<u8>,
-) -> StdResult<Vec<Offering>> {
-    let (limit, min, max, order_enum) = _get_range_params(limit, offset, order);
-    let offerings_result: StdResult<Vec<Offering>> = offerings()
-        .idx
-        .contract_token_id
-        .items(
+    with_total: Option<bool>,
+) -> StdResult<PageResponse<Offering>> {
+    let pagination = datahub_pagination(start_after, limit, offset, order);
+    let (min, max, order_enum) = pagination.bounds();
+    let contract_token_id = get_contract_token_id(&contract, &token_id);
+    let total = count_total(with_total, || {
+        offerings()
+            .idx
+            .contract_token_id
+            .pks(
+                deps.storage,
+                &contract_token_id,
+                None,
+                None,
+                Order::Ascending,
+            )
+            .count()
+    });
+
+    pagination.page_response(
+        offerings().idx.contract_token_id.items(
             deps.storage,
-            &get_contract_token_id(&contract, &token_id),
+            &contract_token_id,
             min,
             max,
             order_enum,
-        )
-        .take(limit)
-        .map(|kv_item| parse_offering(kv_item))
-        .collect();
-
-    Ok(offerings_result?)
+        ),
+        parse_offering,
+        total,
+    )
 }
 
 pub fn query_offering(deps: Deps, offering_id: u64) -> StdResult<Offering> {
@@ -664,35 +789,40 @@ pub fn query_contract_info(deps: Deps) -> StdResult<ContractInfo> {
     CONTRACT_INFO.load(deps.storage)
 }
 
-fn parse_offering<'a>(item: StdResult<Record<Offering>>) -> StdResult<Offering> {
-    item.and_then(|(k, offering)| {
-        // will panic if length is greater than 8, but we can make sure it is u64
-        // try_into will box vector to fixed array
-        let value = k
-            .try_into()
-            .map_err(|_| StdError::generic_err("Cannot parse offering key"))?;
-        let id: u64 = u64::from_be_bytes(value);
-        Ok(Offering {
-            id: Some(id),
-            ..offering
-        })
+fn parse_offering((k, offering): Record<Offering>) -> StdResult<Offering> {
+    // will panic if length is greater than 8, but we can make sure it is u64
+    // try_into will box vector to fixed array
+    let value = k
+        .try_into()
+        .map_err(|_| StdError::generic_err("Cannot parse offering key"))?;
+    let id: u64 = u64::from_be_bytes(value);
+    Ok(Offering {
+        id: Some(id),
+        ..offering
     })
 }
 
 pub fn query_annotations(
     deps: Deps,
-    limit: Option<u8>,
+    start_after: Option<Binary>,
+    limit: Option<u32>,
     offset: Option<u64>,
     order: Option<u8>,
-) -> StdResult<Vec<Annotation>> {
-    let (limit, min, max, order_enum) = _get_range_params(limit, offset, order);
-
-    let annotations_result: StdResult<Vec<Annotation>> = annotations()
-        .range(deps.storage, min, max, order_enum)
-        .take(limit)
-        .map(|kv_item| parse_annotation(kv_item))
-        .collect();
-    Ok(annotations_result?)
+    with_total: Option<bool>,
+) -> StdResult<PageResponse<Annotation>> {
+    let pagination = datahub_pagination(start_after, limit, offset, order);
+    let (min, max, order_enum) = pagination.bounds();
+    let total = count_total(with_total, || {
+        annotations()
+            .range(deps.storage, None, None, Order::Ascending)
+            .count()
+    });
+
+    pagination.page_response(
+        annotations().range(deps.storage, min, max, order_enum),
+        parse_annotation,
+        total,
+    )
 }
 
 pub fn query_annotation_ids(deps: Deps) -> StdResult<Vec<u64>> {
@@ -707,20 +837,36 @@ pub fn query_annotation_ids(deps: Deps) -> StdResult<Vec<u64>> {
 pub fn query_annotations_by_contract(
     deps: Deps,
     contract: Addr,
-    limit: Option<u8>,
+    start_after: Option<Binary>,
+    limit: Option<u32>,
     offset: Option<u64>,
     order: Option<u8>,
-) -> StdResult<Vec<Annotation>> {
-    let (limit, min, max, order_enum) = _get_range_params(limit, offset, order);
-    let annotations_result: StdResult<Vec<Annotation>> = annotations()
-        .idx
-        .contract
-        .items(deps.storage, contract.as_bytes(), min, max, order_enum)
-        .take(limit)
-        .map(|kv_item| parse_annotation(kv_item))
-        .collect();
-
-    Ok(annotations_result?)
+    with_total: Option<bool>,
+) -> StdResult<PageResponse<Annotation>> {
+    let pagination = datahub_pagination(start_after, limit, offset, order);
+    let (min, max, order_enum) = pagination.bounds();
+    let total = count_total(with_total, || {
+        annotations()
+            .idx
+            .contract
+            .pks(
+                deps.storage,
+                contract.as_bytes(),
+                None,
+                None,
+                Order::Ascending,
+            )
+            .count()
+    });
+
+    pagination.page_response(
+        annotations()
+            .idx
+            .contract
+            .items(deps.storage, contract.as_bytes(), min, max, order_enum),
+        parse_annotation,
+        total,
+    )
 }
 
 pub fn query_annotation(deps: Deps, annotation_id: u64) -> StdResult<Annotation> {
@@ -728,63 +874,92 @@ pub fn query_annotation(deps: Deps, annotation_id: u64) -> StdResult<Annotation>
     Ok(off)
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn query_annotations_by_contract_tokenid(
     deps: Deps,
     contract: Addr,
     token_id: String,
-    limit: Option<u8>,
+    start_after: Option<Binary>,
+    limit: Option<u32>,
     offset: Option<u64>,
     order: Option<u8>,
-) -> StdResult<Vec<Annotation>> {
-    let (limit, min, max, order_enum) = _get_range_params(limit, offset, order);
-    let annotations_result: StdResult<Vec<Annotation>> = annotations()
-        .idx
-        .contract_token_id
-        .items(
+    with_total: Option<bool>,
+) -> StdResult<PageResponse<Annotation>> {
+    let pagination = datahub_pagination(start_after, limit, offset, order);
+    let (min, max, order_enum) = pagination.bounds();
+    let contract_token_id = get_contract_token_id(&contract, &token_id);
+    let total = count_total(with_total, || {
+        annotations()
+            .idx
+            .contract_token_id
+            .pks(
+                deps.storage,
+                &contract_token_id,
+                None,
+                None,
+                Order::Ascending,
+            )
+            .count()
+    });
+
+    pagination.page_response(
+        annotations().idx.contract_token_id.items(
             deps.storage,
-            &get_contract_token_id(&contract, &token_id),
+            &contract_token_id,
             min,
             max,
             order_enum,
-        )
-        .take(limit)
-        .map(|kv_item| parse_annotation(kv_item))
-        .collect();
-
-    Ok(annotations_result?)
+        ),
+        parse_annotation,
+        total,
+    )
 }
 
 pub fn query_annotations_by_requester(
     deps: Deps,
     requester: Addr,
-    limit: Option<u8>,
+    start_after: Option<Binary>,
+    limit: Option<u32>,
     offset: Option<u64>,
     order: Option<u8>,
-) -> StdResult<Vec<Annotation>> {
-    let (limit, min, max, order_enum) = _get_range_params(limit, offset, order);
-    let annotations_result: StdResult<Vec<Annotation>> = annotations()
-        .idx
-        .requester
-        .items(deps.storage, requester.as_bytes(), min, max, order_enum)
-        .take(limit)
-        .map(|kv_item| parse_annotation(kv_item))
-        .collect();
-
-    Ok(annotations_result?)
+    with_total: Option<bool>,
+) -> StdResult<PageResponse<Annotation>> {
+    let pagination = datahub_pagination(start_after, limit, offset, order);
+    let (min, max, order_enum) = pagination.bounds();
+    let total = count_total(with_total, || {
+        annotations()
+            .idx
+            .requester
+            .pks(
+                deps.storage,
+                requester.as_bytes(),
+                None,
+                None,
+                Order::Ascending,
+            )
+            .count()
+    });
+
+    pagination.page_response(
+        annotations()
+            .idx
+            .requester
+            .items(deps.storage, requester.as_bytes(), min, max, order_enum),
+        parse_annotation,
+        total,
+    )
 }
 
-fn parse_annotation<'a>(item: StdResult<Record<Annotation>>) -> StdResult<Annotation> {
-    item.and_then(|(k, annotation)| {
-        // will panic if length is greater than 8, but we can make sure it is u64
-        // try_into will box vector to fixed array
-        let value = k
-            .try_into()
-            .map_err(|_| StdError::generic_err("Cannot parse annotation key"))?;
-        let id: u64 = u64::from_be_bytes(value);
-        Ok(Annotation {
-            id: Some(id),
-            ..annotation
-        })
+fn parse_annotation((k, annotation): Record<Annotation>) -> StdResult<Annotation> {
+    // will panic if length is greater than 8, but we can make sure it is u64
+    // try_into will box vector to fixed array
+    let value = k
+        .try_into()
+        .map_err(|_| StdError::generic_err("Cannot parse annotation key"))?;
+    let id: u64 = u64::from_be_bytes(value);
+    Ok(Annotation {
+        id: Some(id),
+        ..annotation
     })
 }
 
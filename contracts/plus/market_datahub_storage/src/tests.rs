@@ -8,6 +8,7 @@ use cosmwasm_std::testing::{
 use cosmwasm_std::Decimal;
 use cosmwasm_std::{coin, coins, from_json, Addr, Order, OwnedDeps, Uint128};
 
+use market::PageResponse;
 use market_datahub::Annotation;
 use market_datahub::AnnotationResult;
 use market_datahub::AnnotationReviewer;
@@ -76,10 +77,12 @@ fn sort_offering() {
             limit: Some(100),
             offset: Some(50),
             order: Some(Order::Descending as u8),
+            start_after: None,
+            with_total: None,
         }),
     )
     .unwrap();
-    let value: Vec<Offering> = from_json(&res).unwrap();
+    let value: PageResponse<Offering> = from_json(&res).unwrap();
     println!("value query list offerings: {:?}", value);
 
     let res = query(
@@ -90,10 +93,12 @@ fn sort_offering() {
             limit: Some(100),
             offset: Some(1),
             order: Some(Order::Ascending as u8),
+            start_after: None,
+            with_total: None,
         }),
     )
     .unwrap();
-    let value: Vec<Offering> = from_json(&res).unwrap();
+    let value: PageResponse<Offering> = from_json(&res).unwrap();
     println!("value query list offering by seller: {:?}", value);
 
     // query by contract
@@ -105,10 +110,12 @@ fn sort_offering() {
             limit: Some(100),
             offset: Some(1),
             order: Some(Order::Ascending as u8),
+            start_after: None,
+            with_total: None,
         }),
     )
     .unwrap();
-    let value: Vec<Offering> = from_json(&res).unwrap();
+    let value: PageResponse<Offering> = from_json(&res).unwrap();
     println!("value query list offering by contract: {:?}", value);
 
     // query by contract token id
@@ -121,11 +128,13 @@ fn sort_offering() {
             limit: None,
             offset: None,
             order: Some(1),
+            start_after: None,
+            with_total: None,
         }),
     )
     .unwrap();
-    let value: Vec<Offering> = from_json(&res).unwrap();
-    assert_eq!(value.len(), 1);
+    let value: PageResponse<Offering> = from_json(&res).unwrap();
+    assert_eq!(value.items.len(), 1);
 
     // query by contract token id
     let res = query(
@@ -191,12 +200,14 @@ fn withdraw_offering() {
             limit: Some(100),
             offset: Some(0),
             order: Some(Order::Ascending as u8),
+            start_after: None,
+            with_total: None,
         }),
     )
     .unwrap();
-    let value: Vec<Offering> = from_json(&res).unwrap();
+    let value: PageResponse<Offering> = from_json(&res).unwrap();
     println!("value: {:?}", value);
-    assert_eq!(value.len(), 1);
+    assert_eq!(value.items.len(), 1);
 }
 
 #[test]
@@ -237,11 +248,13 @@ fn sort_annotations() {
             limit: Some(100),
             offset: Some(50),
             order: Some(Order::Descending as u8),
+            start_after: None,
+            with_total: None,
         }),
     )
     .unwrap();
-    let value: Vec<Annotation> = from_json(&res).unwrap();
-    assert_eq!(value.len(), 2);
+    let value: PageResponse<Annotation> = from_json(&res).unwrap();
+    assert_eq!(value.items.len(), 2);
     println!("value query list annotationss: {:?}\n", value);
 
     // query by contract
@@ -253,11 +266,13 @@ fn sort_annotations() {
             limit: Some(100),
             offset: Some(0),
             order: Some(Order::Ascending as u8),
+            start_after: None,
+            with_total: None,
         }),
     )
     .unwrap();
-    let value: Vec<Annotation> = from_json(&res).unwrap();
-    assert_eq!(value.len(), 2);
+    let value: PageResponse<Annotation> = from_json(&res).unwrap();
+    assert_eq!(value.items.len(), 2);
     println!("value query list annotations by contract: {:?}\n", value);
 
     // query by contract token id
@@ -270,11 +285,13 @@ fn sort_annotations() {
             limit: None,
             offset: None,
             order: Some(Order::Ascending as u8),
+            start_after: None,
+            with_total: None,
         }),
     )
     .unwrap();
-    let value: Vec<Annotation> = from_json(&res).unwrap();
-    assert_eq!(value.len(), 1);
+    let value: PageResponse<Annotation> = from_json(&res).unwrap();
+    assert_eq!(value.items.len(), 1);
     println!(
         "value query annotations by contract token id: {:?}\n",
         value
@@ -289,11 +306,13 @@ fn sort_annotations() {
             limit: None,
             offset: None,
             order: Some(Order::Ascending as u8),
+            start_after: None,
+            with_total: None,
         }),
     )
     .unwrap();
-    let value: Vec<Annotation> = from_json(&res).unwrap();
-    assert_eq!(value.len(), 1);
+    let value: PageResponse<Annotation> = from_json(&res).unwrap();
+    assert_eq!(value.items.len(), 1);
     println!("value query annotations by requester: {:?}\n", value);
 
     // query by contract
@@ -310,6 +329,57 @@ fn sort_annotations() {
     println!("value list ids: {:?}\n", res_second);
 }
 
+#[test]
+fn paginate_annotations_by_contract() {
+    let mut deps = setup_contract();
+    let info = mock_info("market_hub", &[coin(50, DENOM)]);
+
+    for i in 1u64..6u64 {
+        let annotation = Annotation {
+            id: None,
+            contract_addr: Addr::unchecked(if i % 2 == 0 { "yyy" } else { "xxx" }),
+            token_id: i.to_string(),
+            requester: Addr::unchecked("requester"),
+            reward_per_sample: Uint128::from(1u64),
+            number_of_samples: Uint128::from(10u64),
+            max_annotation_per_task: Uint128::from(10u64),
+            expired_block: 1,
+            is_paid: false,
+            max_upload_tasks: Uint128::from(10u128),
+            reward_per_upload_task: Uint128::from(1u128),
+        };
+        let msg = ExecuteMsg::Msg(DataHubExecuteMsg::UpdateAnnotation { annotation });
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    }
+
+    let query_page = |start_after| -> PageResponse<Annotation> {
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Msg(DataHubQueryMsg::GetAnnotationsByContract {
+                contract: Addr::unchecked("xxx"),
+                offset: None,
+                start_after,
+                limit: Some(2),
+                order: None,
+                with_total: Some(true),
+            }),
+        )
+        .unwrap();
+        from_json(&res).unwrap()
+    };
+
+    let first = query_page(None);
+    let ids: Vec<u64> = first.items.iter().map(|a| a.id.unwrap()).collect();
+    assert_eq!(ids, vec![5, 3]);
+    assert_eq!(first.total, Some(3));
+
+    let second = query_page(first.next_cursor);
+    let ids: Vec<u64> = second.items.iter().map(|a| a.id.unwrap()).collect();
+    assert_eq!(ids, vec![1]);
+    assert_eq!(second.next_cursor, None);
+}
+
 #[test]
 fn withdraw_annotations() {
     let mut deps = setup_contract();
@@ -351,12 +421,14 @@ fn withdraw_annotations() {
             limit: Some(100),
             offset: Some(0),
             order: Some(Order::Ascending as u8),
+            start_after: None,
+            with_total: None,
         }),
     )
     .unwrap();
-    let value: Vec<Annotation> = from_json(&res).unwrap();
+    let value: PageResponse<Annotation> = from_json(&res).unwrap();
     println!("value: {:?}", value);
-    assert_eq!(value.len(), 1);
+    assert_eq!(value.items.len(), 1);
 }
 
 #[test]
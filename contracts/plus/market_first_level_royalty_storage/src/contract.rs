@@ -2,23 +2,29 @@
 use cosmwasm_std::entry_point;
 
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg, UpdateContractMsg};
-use crate::state::{first_lv_royalties, get_key_royalty, ContractInfo, CONTRACT_INFO};
+use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, UpdateContractMsg};
+use crate::state::{first_lv_royalties, get_key_royalty, ContractInfo, CONTRACT_INFO, PROVENANCES};
 use cosmwasm_std::{
     attr, to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Order, Response, StdError,
     StdResult,
 };
 use cosmwasm_std::{Addr, Record};
+use cw2::set_contract_version;
 use cw_storage_plus::{Bound, PkOwned};
 use market_first_lv_royalty::{
-    FirstLvRoyalty, FirstLvRoyaltyExecuteMsg, FirstLvRoyaltyQueryMsg, OffsetMsg,
+    FirstLvRoyalty, FirstLvRoyaltyExecuteMsg, FirstLvRoyaltyQueryMsg, OffsetMsg, Provenance,
 };
+use migration::migrate_version;
 use std::usize;
 
 // settings for pagination
 const MAX_LIMIT: u8 = 100;
 const DEFAULT_LIMIT: u8 = 20;
 
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:market_first_level_royalty_storage";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
 // Note, you can use StdResult in some functions where you do not
 // make use of the custom errors
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -28,6 +34,7 @@ pub fn instantiate(
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     // first time deploy, it will not know about the implementation
     let info = ContractInfo {
         governance: msg.governance,
@@ -54,6 +61,9 @@ pub fn execute(
                 contract_addr,
                 token_id,
             } => try_delete_first_lv_royalty(deps, info, env, contract_addr, token_id),
+            FirstLvRoyaltyExecuteMsg::UpdateProvenance { provenance } => {
+                try_update_provenance(deps, info, env, provenance)
+            }
         },
         ExecuteMsg::UpdateInfo(msg) => try_update_info(deps, info, env, msg),
     }
@@ -92,6 +102,9 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
             FirstLvRoyaltyQueryMsg::GetFirstLvRoyalty { contract, token_id } => {
                 to_json_binary(&query_first_lv_royalty(deps, contract, token_id)?)
             }
+            FirstLvRoyaltyQueryMsg::GetProvenance { contract, token_id } => {
+                to_json_binary(&query_provenance(deps, contract, token_id)?)
+            }
             FirstLvRoyaltyQueryMsg::GetContractInfo {} => {
                 to_json_binary(&query_contract_info(deps)?)
             }
@@ -100,6 +113,17 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     }
 }
 
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    Ok(migrate_version(
+        deps,
+        &env,
+        CONTRACT_NAME,
+        CONTRACT_VERSION,
+        &[],
+    )?)
+}
+
 pub fn try_update_first_lv_royalty(
     deps: DepsMut,
     info: MessageInfo,
@@ -152,6 +176,38 @@ pub fn try_delete_first_lv_royalty(
     return Ok(Response::new().add_attributes(vec![attr("action", "remove_offering_royalty")]));
 }
 
+pub fn try_update_provenance(
+    deps: DepsMut,
+    info: MessageInfo,
+    _env: Env,
+    provenance: Provenance,
+) -> Result<Response, ContractError> {
+    // must check the sender is implementation contract
+    let contract_info = CONTRACT_INFO.load(deps.storage)?;
+    if contract_info.governance.ne(&info.sender) {
+        return Err(ContractError::Unauthorized {
+            sender: info.sender.to_string(),
+        });
+    };
+
+    PROVENANCES.save(
+        deps.storage,
+        &get_key_royalty(
+            provenance.contract_addr.as_bytes(),
+            provenance.token_id.as_bytes(),
+        ),
+        &provenance,
+    )?;
+
+    Ok(Response::new()
+        .add_attributes(vec![
+            attr("action", "update_provenance"),
+            attr("contract_addr", provenance.contract_addr.as_str()),
+            attr("token_id", provenance.token_id.as_str()),
+        ])
+        .set_data(to_json_binary(&provenance)?))
+}
+
 pub fn try_update_info(
     deps: DepsMut,
     info: MessageInfo,
@@ -282,6 +338,18 @@ pub fn query_first_lv_royalty(
     }
 }
 
+pub fn query_provenance(deps: Deps, contract: Addr, token_id: String) -> StdResult<Provenance> {
+    let provenance = PROVENANCES.may_load(
+        deps.storage,
+        &get_key_royalty(contract.as_bytes(), token_id.as_bytes()),
+    )?;
+    Ok(provenance.unwrap_or(Provenance {
+        token_id,
+        contract_addr: contract,
+        owners: vec![],
+    }))
+}
+
 pub fn query_contract_info(deps: Deps) -> StdResult<ContractInfo> {
     CONTRACT_INFO.load(deps.storage)
 }
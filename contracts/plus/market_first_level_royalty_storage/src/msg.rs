@@ -1,14 +1,22 @@
+use cosmwasm_schema::QueryResponses;
 use cosmwasm_std::Addr;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use market_first_lv_royalty::{FirstLvRoyaltyExecuteMsg, FirstLvRoyaltyQueryMsg};
+use market_first_lv_royalty::{
+    FirstLvRoyaltyExecuteMsg, FirstLvRoyaltyQueryMsg, FirstLvRoyaltyQueryResponse,
+};
+
+use crate::state::ContractInfo;
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InstantiateMsg {
     pub governance: Addr,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
@@ -23,10 +31,12 @@ pub struct UpdateContractMsg {
     pub creator: Option<Addr>,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, QueryResponses)]
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
     // GetFirstLvRoyaltys returns a list of all FirstLvRoyaltys
+    #[returns(FirstLvRoyaltyQueryResponse)]
     Msg(FirstLvRoyaltyQueryMsg),
+    #[returns(ContractInfo)]
     GetContractInfo {},
 }
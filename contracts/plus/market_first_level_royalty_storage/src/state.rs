@@ -1,10 +1,10 @@
-use market_first_lv_royalty::FirstLvRoyalty;
+use market_first_lv_royalty::{FirstLvRoyalty, Provenance};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 
 use cosmwasm_std::Addr;
-use cw_storage_plus::{Index, IndexList, IndexedMap, Item, MultiIndex, PkOwned, UniqueIndex};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex, PkOwned, UniqueIndex};
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
 pub struct ContractInfo {
@@ -14,6 +14,9 @@ pub struct ContractInfo {
 
 pub const CONTRACT_INFO: Item<ContractInfo> = Item::new("marketplace_info");
 
+/// keyed by get_key_royalty of the contract and token id
+pub const PROVENANCES: Map<&[u8], Provenance> = Map::new("provenances");
+
 pub struct FirstLvRoyaltyIndexes<'a> {
     pub current_owner: MultiIndex<'a, FirstLvRoyalty>,
     pub contract: MultiIndex<'a, FirstLvRoyalty>,
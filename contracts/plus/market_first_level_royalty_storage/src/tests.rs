@@ -12,6 +12,7 @@ use market_first_lv_royalty::FirstLvRoyalty;
 use market_first_lv_royalty::FirstLvRoyaltyExecuteMsg;
 use market_first_lv_royalty::FirstLvRoyaltyQueryMsg;
 use market_first_lv_royalty::OffsetMsg;
+use market_first_lv_royalty::Provenance;
 
 const CREATOR: &str = "marketplace";
 const DENOM: &str = "MGK";
@@ -131,6 +132,40 @@ fn sort_first_lv_royalty() {
     assert_eq!(value.token_id, String::from("2"));
 }
 
+#[test]
+fn update_provenance() {
+    let mut deps = setup_contract();
+    let query_msg = QueryMsg::Msg(FirstLvRoyaltyQueryMsg::GetProvenance {
+        contract: Addr::unchecked("xxx"),
+        token_id: String::from("1"),
+    });
+
+    // never resold
+    let provenance: Provenance =
+        from_json(&query(deps.as_ref(), mock_env(), query_msg.clone()).unwrap()).unwrap();
+    assert_eq!(provenance.owners.len(), 0);
+
+    let provenance = Provenance {
+        contract_addr: Addr::unchecked("xxx"),
+        token_id: String::from("1"),
+        owners: vec![Addr::unchecked("seller2"), Addr::unchecked("seller1")],
+    };
+    let msg = ExecuteMsg::Msg(FirstLvRoyaltyExecuteMsg::UpdateProvenance {
+        provenance: provenance.clone(),
+    });
+    assert!(execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("hacker", &[]),
+        msg.clone()
+    )
+    .is_err());
+    execute(deps.as_mut(), mock_env(), mock_info("market_hub", &[]), msg).unwrap();
+
+    let res: Provenance = from_json(&query(deps.as_ref(), mock_env(), query_msg).unwrap()).unwrap();
+    assert_eq!(res, provenance);
+}
+
 #[test]
 fn update_info_test() {
     let mut deps = setup_contract();
@@ -2,22 +2,44 @@
 use cosmwasm_std::entry_point;
 
 use cosmwasm_std::{
-    attr, to_json_binary, Addr, Api, Binary, CanonicalAddr, CosmosMsg, Deps, DepsMut, Env,
-    MessageInfo, Response, StdError, StdResult, WasmMsg,
+    attr, from_json, to_json_binary, Addr, Api, Binary, CanonicalAddr, CosmosMsg, Deps, DepsMut,
+    Env, MessageInfo, Order, Response, StdError, StdResult, Storage, WasmMsg,
 };
+use cw2::set_contract_version;
+use migration::{migrate_version, MigrationStep};
 
 use crate::error::ContractError;
-use crate::msg::{AdminListResponse, CanExecuteResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
-use crate::state::{admin_list, admin_list_read, registry, registry_read};
-use market::{query_proxy, AdminList, Registry, StorageExecuteMsg, StorageQueryMsg};
+use crate::msg::{
+    AdminListResponse, CanExecuteResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg,
+    StorageAccessResponse,
+};
+use crate::proposal::{
+    check_direct_action, handle_approve_proposal, handle_cancel_proposal, handle_execute_proposal,
+    handle_propose_action, handle_update_min_delay, handle_update_threshold, query_proposal_votes,
+    query_proposals,
+};
+use crate::state::{
+    admin_list, admin_list_read, registry, registry_read, AdminRole, RegistryAction,
+    RegistryChange, StoragePermission, IMPLEMENTATION_PERMISSIONS, MIN_DELAY, PROPOSALS,
+    REGISTRY_CHANGES, REGISTRY_CHANGES_COUNT, ROLES, STORAGE_VERSIONS, THRESHOLD,
+};
+use cw_storage_plus::Bound;
+use market::{query_proxy, AdminList, Registry, StorageExecuteMsg, StorageItem, StorageQueryMsg};
+use serde::de::IgnoredAny;
+use std::collections::BTreeMap;
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:market_hub";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> StdResult<Response> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     // list of whitelist
     let cfg = AdminList {
         admins: map_canonical(deps.api, &msg.admins)?,
@@ -32,10 +54,40 @@ pub fn instantiate(
         implementations: msg.implementations,
     };
     registry(deps.storage).save(&reg)?;
+
+    // initial storages are the first versions
+    for (name, addr) in &reg.storages {
+        record_change(
+            deps.storage,
+            &env,
+            &info.sender,
+            RegistryAction::UpdateStorage,
+            Some(name),
+            None,
+            Some(addr.clone()),
+        )?;
+    }
+    // the initial implementations can update every initial storage
+    for implementation in &reg.implementations {
+        record_change(
+            deps.storage,
+            &env,
+            &info.sender,
+            RegistryAction::UpdateImplementation,
+            None,
+            None,
+            Some(implementation.clone()),
+        )?;
+        IMPLEMENTATION_PERMISSIONS.save(
+            deps.storage,
+            implementation.as_bytes(),
+            &full_permissions(&reg.storages),
+        )?;
+    }
     Ok(Response::default())
 }
 
-fn map_canonical(api: &dyn Api, admins: &[Addr]) -> StdResult<Vec<CanonicalAddr>> {
+pub(crate) fn map_canonical(api: &dyn Api, admins: &[Addr]) -> StdResult<Vec<CanonicalAddr>> {
     admins
         .iter()
         .map(|addr| api.addr_canonicalize(addr.as_str()))
@@ -67,10 +119,35 @@ pub fn execute(
         }
         ExecuteMsg::Freeze {} => handle_freeze(deps, env, info),
         ExecuteMsg::UpdateAdmins { admins } => handle_update_admins(deps, env, info, admins),
+        ExecuteMsg::RollbackStorage { name, version } => {
+            handle_rollback_storage(deps, env, info, name, version)
+        }
+        ExecuteMsg::UpdateRoles { address, roles } => {
+            handle_update_roles(deps, env, info, address, roles)
+        }
+        ExecuteMsg::UpdatePermissions {
+            implementation,
+            storages,
+        } => handle_update_permissions(deps, env, info, implementation, storages),
+        ExecuteMsg::UpdateMinDelay { min_delay } => {
+            handle_update_min_delay(deps, env, info, min_delay)
+        }
+        ExecuteMsg::ProposeAction { action, expires_in } => {
+            handle_propose_action(deps, env, info, action, expires_in)
+        }
+        ExecuteMsg::ApproveProposal { id } => handle_approve_proposal(deps, env, info, id),
+        ExecuteMsg::UpdateThreshold { threshold } => {
+            handle_update_threshold(deps, env, info, threshold)
+        }
+        ExecuteMsg::CancelProposal { id } => handle_cancel_proposal(deps, env, info, id),
+        ExecuteMsg::ExecuteProposal { id } => handle_execute_proposal(deps, env, info, id),
         ExecuteMsg::Storage(storage_msg) => match storage_msg {
             StorageExecuteMsg::UpdateStorageData { name, msg } => {
                 handle_update_storage_data(deps, env, info, name, msg)
             }
+            StorageExecuteMsg::BatchUpdateStorageData { updates } => {
+                handle_batch_update_storage_data(deps, env, info, updates)
+            }
         },
     }
 }
@@ -78,7 +155,7 @@ pub fn execute(
 /// update implementation, and call initilize with storages from the hub
 pub fn handle_update_implementation(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     implementation: Addr,
 ) -> Result<Response, ContractError> {
@@ -87,10 +164,8 @@ pub fn handle_update_implementation(
             sender: info.sender.to_string(),
         })
     } else {
-        registry(deps.storage).update(|mut data| -> StdResult<_> {
-            data.implementations.push(implementation.clone());
-            Ok(data)
-        })?;
+        check_direct_action(deps.storage)?;
+        update_implementation(deps.storage, &env, &info.sender, implementation)?;
 
         // then call initialize with storage as params
         let mut res = Response::default();
@@ -102,7 +177,7 @@ pub fn handle_update_implementation(
 /// update implementation, and call initilize with storages from the hub
 pub fn handle_remove_implementation(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     implementation: Addr,
 ) -> Result<Response, ContractError> {
@@ -123,6 +198,15 @@ pub fn handle_remove_implementation(
             data.implementations.remove(index_of);
             Ok(data)
         })?;
+        record_change(
+            deps.storage,
+            &env,
+            &info.sender,
+            RegistryAction::RemoveImplementation,
+            None,
+            Some(implementation),
+            None,
+        )?;
 
         // then call initialize with storage as params
         let mut res = Response::default();
@@ -133,7 +217,7 @@ pub fn handle_remove_implementation(
 
 pub fn handle_update_storages(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     storages: Vec<(String, Addr)>,
 ) -> Result<Response, ContractError> {
@@ -142,13 +226,8 @@ pub fn handle_update_storages(
             sender: info.sender.to_string(),
         })
     } else {
-        let mut data = registry_read(deps.storage).load()?;
-        for (item_key, addr) in &storages {
-            data.add_storage(item_key, addr.clone());
-        }
-
-        // update new data
-        registry(deps.storage).save(&data)?;
+        check_direct_action(deps.storage)?;
+        update_storages(deps.storage, &env, &info.sender, &storages)?;
 
         let mut res = Response::default();
         res.attributes = vec![attr("action", "update_storages")];
@@ -156,48 +235,248 @@ pub fn handle_update_storages(
     }
 }
 
-pub fn handle_update_storage_data(
+pub(crate) fn update_implementation(
+    storage: &mut dyn Storage,
+    env: &Env,
+    admin: &Addr,
+    implementation: Addr,
+) -> StdResult<()> {
+    registry(storage).update(|mut data| -> StdResult<_> {
+        data.implementations.push(implementation.clone());
+        Ok(data)
+    })?;
+    record_change(
+        storage,
+        env,
+        admin,
+        RegistryAction::UpdateImplementation,
+        None,
+        None,
+        Some(implementation),
+    )?;
+    Ok(())
+}
+
+pub(crate) fn update_storages(
+    storage: &mut dyn Storage,
+    env: &Env,
+    admin: &Addr,
+    storages: &[StorageItem],
+) -> StdResult<()> {
+    let mut data = registry_read(storage).load()?;
+    for (item_key, addr) in storages {
+        let old_addr = data.add_storage(item_key, addr.clone());
+        // only record real changes of the address
+        if old_addr.as_ref() != Some(addr) {
+            record_change(
+                storage,
+                env,
+                admin,
+                RegistryAction::UpdateStorage,
+                Some(item_key),
+                old_addr,
+                Some(addr.clone()),
+            )?;
+        }
+    }
+
+    // update new data
+    registry(storage).save(&data)
+}
+
+pub fn handle_rollback_storage(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     name: String,
-    msg: Binary,
+    version: u64,
 ) -> Result<Response, ContractError> {
-    let registry_obj = registry_read(deps.storage).load()?;
-    let admin_list = admin_list_read(deps.storage).load()?;
-    let sender_canonical = deps.api.addr_canonicalize(info.sender.as_str())?;
-
-    let can_update = registry_obj
-        .implementations
-        .iter()
-        .any(|f| f.eq(&info.sender))
-        || admin_list.admins.iter().any(|f| f.eq(&sender_canonical));
-
-    // can_update = admin_list.admins.iter().any(|f| f.eq(&sender_canonical));
-
-    if !can_update {
+    if !can_execute(deps.as_ref(), &info.sender)? {
         return Err(ContractError::Unauthorized {
             sender: info.sender.to_string(),
         });
     }
+    let new_addr = STORAGE_VERSIONS
+        .may_load(deps.storage, (name.as_bytes(), &version.to_be_bytes()))?
+        .map(|change_id| REGISTRY_CHANGES.load(deps.storage, &change_id.to_be_bytes()))
+        .transpose()?
+        .and_then(|change| change.new_addr)
+        .ok_or(ContractError::VersionNotFound {
+            name: name.clone(),
+            version,
+        })?;
 
-    let storage_addr = get_storage_addr(&registry_obj, &name)?;
-    let mut res = Response::default();
-    let mut messages: Vec<CosmosMsg> = vec![];
+    let mut data = registry_read(deps.storage).load()?;
+    let old_addr = data.add_storage(&name, new_addr.clone());
+    registry(deps.storage).save(&data)?;
+    let change = record_change(
+        deps.storage,
+        &env,
+        &info.sender,
+        RegistryAction::RollbackStorage,
+        Some(&name),
+        old_addr,
+        Some(new_addr.clone()),
+    )?;
 
-    messages.push(
-        WasmMsg::Execute {
-            contract_addr: storage_addr.to_string(),
-            msg,
-            funds: vec![],
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "rollback_storage"),
+        attr("name", name),
+        attr("version", version.to_string()),
+        attr(
+            "new_version",
+            change.version.unwrap_or_default().to_string(),
+        ),
+        attr("new_addr", new_addr),
+    ]))
+}
+
+/// appends a change to the log, storage changes get the next version of the storage name
+fn record_change(
+    storage: &mut dyn Storage,
+    env: &Env,
+    admin: &Addr,
+    action: RegistryAction,
+    name: Option<&str>,
+    old_addr: Option<Addr>,
+    new_addr: Option<Addr>,
+) -> StdResult<RegistryChange> {
+    let id = REGISTRY_CHANGES_COUNT
+        .may_load(storage)?
+        .unwrap_or_default()
+        + 1;
+    REGISTRY_CHANGES_COUNT.save(storage, &id)?;
+
+    let version = match name {
+        Some(name) => {
+            let version = last_storage_version(storage, name)? + 1;
+            STORAGE_VERSIONS.save(storage, (name.as_bytes(), &version.to_be_bytes()), &id)?;
+            Some(version)
         }
-        .into(),
-    );
+        None => None,
+    };
+
+    let change = RegistryChange {
+        id,
+        action,
+        name: name.map(|name| name.to_string()),
+        version,
+        old_addr,
+        new_addr,
+        admin: admin.clone(),
+        height: env.block.height,
+    };
+    REGISTRY_CHANGES.save(storage, &id.to_be_bytes(), &change)?;
+    Ok(change)
+}
+
+/// returns 0 when the storage name has no version yet
+fn last_storage_version(storage: &dyn Storage, name: &str) -> StdResult<u64> {
+    STORAGE_VERSIONS
+        .prefix(name.as_bytes())
+        .range(storage, None, None, Order::Descending)
+        .next()
+        .transpose()
+        .map(|item| {
+            item.map(|(key, _)| u64::from_be_bytes(key.try_into().unwrap_or_default()))
+                .unwrap_or_default()
+        })
+}
+
+pub fn handle_update_storage_data(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    name: String,
+    msg: Binary,
+) -> Result<Response, ContractError> {
+    let registry_obj = registry_read(deps.storage).load()?;
+    let access = load_storage_access(deps.as_ref(), &registry_obj, &info.sender, &name)?;
+    let mut res = Response::default();
+    let messages: Vec<CosmosMsg> = vec![get_storage_update_msg(
+        &registry_obj,
+        &info.sender,
+        &access,
+        name,
+        msg,
+    )?];
+
     res = res.add_messages(messages);
     res = res.add_attributes(vec![attr("action", "update_storage_data")]);
     Ok(res)
 }
 
+/// every update is validated before any of them is dispatched, so the batch is applied atomically
+pub fn handle_batch_update_storage_data(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    updates: Vec<(String, Binary)>,
+) -> Result<Response, ContractError> {
+    let registry_obj = registry_read(deps.storage).load()?;
+    // permissions are loaded once per storage
+    let mut accesses: BTreeMap<String, Option<Vec<String>>> = BTreeMap::new();
+    let mut messages: Vec<CosmosMsg> = vec![];
+    let count = updates.len();
+    for (name, msg) in updates {
+        if !accesses.contains_key(&name) {
+            let access = load_storage_access(deps.as_ref(), &registry_obj, &info.sender, &name)?;
+            accesses.insert(name.clone(), access);
+        }
+        messages.push(get_storage_update_msg(
+            &registry_obj,
+            &info.sender,
+            &accesses[&name],
+            name,
+            msg,
+        )?);
+    }
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        attr("action", "batch_update_storage_data"),
+        attr("count", count.to_string()),
+    ]))
+}
+
+/// returns the allowed message kinds of the sender on the storage, every kind when None
+fn load_storage_access(
+    deps: Deps,
+    registry: &Registry,
+    sender: &Addr,
+    name: &str,
+) -> Result<Option<Vec<String>>, ContractError> {
+    storage_access(deps, registry, sender, name)?.ok_or(ContractError::Unauthorized {
+        sender: sender.to_string(),
+    })
+}
+
+fn get_storage_update_msg(
+    registry: &Registry,
+    sender: &Addr,
+    kinds: &Option<Vec<String>>,
+    name: String,
+    msg: Binary,
+) -> Result<CosmosMsg, ContractError> {
+    if let Some(kinds) = kinds {
+        let kind = get_msg_kind(&msg).unwrap_or_default();
+        if !kinds.iter().any(|allowed| is_kind_allowed(allowed, &kind)) {
+            return Err(ContractError::StorageNotPermitted {
+                sender: sender.to_string(),
+                name,
+                kind,
+            });
+        }
+    }
+
+    let storage_addr = get_storage_addr(registry, &name)?;
+    Ok(WasmMsg::Execute {
+        contract_addr: storage_addr.to_string(),
+        msg,
+        funds: vec![],
+    }
+    .into())
+}
+
 pub fn handle_freeze(
     deps: DepsMut,
     _env: Env,
@@ -230,6 +509,7 @@ pub fn handle_update_admins(
             sender: info.sender.to_string(),
         })
     } else {
+        check_direct_action(deps.storage)?;
         cfg.admins = map_canonical(deps.api, &admins)?;
         admin_list(deps.storage).save(&cfg)?;
 
@@ -239,18 +519,175 @@ pub fn handle_update_admins(
     }
 }
 
-fn can_execute(deps: Deps, sender: &Addr) -> StdResult<bool> {
+pub fn handle_update_roles(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    address: Addr,
+    roles: Vec<AdminRole>,
+) -> Result<Response, ContractError> {
     let cfg = admin_list_read(deps.storage).load()?;
-    let can = cfg.is_admin(&deps.api.addr_canonicalize(sender.as_str())?);
-    Ok(can)
+    if !cfg.can_modify(&deps.api.addr_canonicalize(info.sender.as_str())?) {
+        return Err(ContractError::Unauthorized {
+            sender: info.sender.to_string(),
+        });
+    }
+    if roles.is_empty() {
+        ROLES.remove(deps.storage, address.as_bytes());
+    } else {
+        ROLES.save(deps.storage, address.as_bytes(), &roles)?;
+    }
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "update_roles"),
+        attr("address", address),
+    ]))
+}
+
+pub fn handle_update_permissions(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    implementation: Addr,
+    storages: Option<Vec<StoragePermission>>,
+) -> Result<Response, ContractError> {
+    if !has_role(deps.as_ref(), &info.sender, AdminRole::Permissions)? {
+        return Err(ContractError::Unauthorized {
+            sender: info.sender.to_string(),
+        });
+    }
+    match storages {
+        Some(storages) => {
+            IMPLEMENTATION_PERMISSIONS.save(deps.storage, implementation.as_bytes(), &storages)?
+        }
+        None => IMPLEMENTATION_PERMISSIONS.remove(deps.storage, implementation.as_bytes()),
+    }
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "update_permissions"),
+        attr("implementation", implementation),
+    ]))
+}
+
+/// admins have every role, other addresses only the granted ones
+fn has_role(deps: Deps, sender: &Addr, role: AdminRole) -> StdResult<bool> {
+    let cfg = admin_list_read(deps.storage).load()?;
+    if cfg.is_admin(&deps.api.addr_canonicalize(sender.as_str())?) {
+        return Ok(true);
+    }
+    Ok(ROLES
+        .may_load(deps.storage, sender.as_bytes())?
+        .is_some_and(|roles| roles.contains(&role)))
+}
+
+pub(crate) fn can_execute(deps: Deps, sender: &Addr) -> StdResult<bool> {
+    has_role(deps, sender, AdminRole::Registry)
+}
+
+/// returns None when the sender cannot update the storage, otherwise the allowed message kinds,
+/// every kind when None
+fn storage_access(
+    deps: Deps,
+    registry: &Registry,
+    sender: &Addr,
+    name: &str,
+) -> StdResult<Option<Option<Vec<String>>>> {
+    if has_role(deps, sender, AdminRole::Storage)? {
+        return Ok(Some(None));
+    }
+    if !registry.implementations.contains(sender) {
+        return Ok(None);
+    }
+    // an implementation without permissions cannot update any storage
+    Ok(IMPLEMENTATION_PERMISSIONS
+        .may_load(deps.storage, sender.as_bytes())?
+        .and_then(|storages| {
+            storages
+                .into_iter()
+                .find(|storage| storage.name.eq(name))
+                .map(|storage| storage.kinds)
+        }))
+}
+
+/// every message kind of every storage
+fn full_permissions(storages: &[StorageItem]) -> Vec<StoragePermission> {
+    storages
+        .iter()
+        .map(|(name, _)| StoragePermission {
+            name: name.clone(),
+            kinds: None,
+        })
+        .collect()
+}
+
+/// storage messages are enums wrapped by the storage, so the kind is the path of the top level
+/// keys like "offering.update_offering" or "msg.update_offering_payment"
+fn get_msg_kind(msg: &Binary) -> Option<String> {
+    let kind = from_json::<BTreeMap<String, IgnoredAny>>(msg)
+        .ok()
+        .and_then(|fields| fields.into_keys().next())?;
+    let variant = from_json::<BTreeMap<String, BTreeMap<String, IgnoredAny>>>(msg)
+        .ok()
+        .and_then(|fields| fields.into_values().next())
+        .filter(|fields| fields.len() == 1)
+        .and_then(|fields| fields.into_keys().next());
+    Some(match variant {
+        Some(variant) => format!("{}.{}", kind, variant),
+        None => kind,
+    })
+}
+
+/// "offering" allows every offering message, "offering.update_offering" only this one
+fn is_kind_allowed(allowed: &str, kind: &str) -> bool {
+    kind == allowed
+        || kind
+            .strip_prefix(allowed)
+            .is_some_and(|rest| rest.starts_with('.'))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::AdminList {} => to_json_binary(&query_admin_list(deps)?),
-        QueryMsg::CanExecute { sender } => to_json_binary(&query_can_execute(deps, sender)?),
+        QueryMsg::CanExecute { sender, name } => {
+            to_json_binary(&query_can_execute(deps, sender, name)?)
+        }
+        QueryMsg::Roles { address } => to_json_binary(
+            &ROLES
+                .may_load(deps.storage, address.as_bytes())?
+                .unwrap_or_default(),
+        ),
+        QueryMsg::Permissions { implementation } => to_json_binary(
+            &IMPLEMENTATION_PERMISSIONS.may_load(deps.storage, implementation.as_bytes())?,
+        ),
+        QueryMsg::MinDelay {} => {
+            to_json_binary(&MIN_DELAY.may_load(deps.storage)?.unwrap_or_default())
+        }
+        QueryMsg::Threshold {} => {
+            to_json_binary(&THRESHOLD.may_load(deps.storage)?.unwrap_or_default())
+        }
+        QueryMsg::ProposalVotes { id } => to_json_binary(&query_proposal_votes(deps, id)?),
+        QueryMsg::Proposal { id } => {
+            to_json_binary(&PROPOSALS.load(deps.storage, &id.to_be_bytes())?)
+        }
+        QueryMsg::Proposals {
+            status,
+            offset,
+            limit,
+            order,
+        } => to_json_binary(&query_proposals(deps, status, offset, limit, order)?),
         QueryMsg::Registry {} => to_json_binary(&registry_read(deps.storage).load()?),
+        QueryMsg::RegistryChanges {
+            offset,
+            limit,
+            order,
+        } => to_json_binary(&query_registry_changes(deps, offset, limit, order)?),
+        QueryMsg::StorageVersions {
+            name,
+            offset,
+            limit,
+            order,
+        } => to_json_binary(&query_storage_versions(deps, name, offset, limit, order)?),
         QueryMsg::Storage(storage_msg) => match storage_msg {
             StorageQueryMsg::QueryStorageAddr { name } => {
                 to_json_binary(&query_storage_addr(deps, name)?)
@@ -261,8 +698,64 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
-    Ok(Response::default())
+pub fn migrate(deps: DepsMut, env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    Ok(migrate_version(
+        deps,
+        &env,
+        CONTRACT_NAME,
+        CONTRACT_VERSION,
+        MIGRATIONS,
+    )?)
+}
+
+const MIGRATIONS: &[MigrationStep] = &[
+    MigrationStep {
+        version: "0.7.0",
+        name: "record_storage_versions",
+        run: record_storage_versions,
+    },
+    MigrationStep {
+        version: "0.7.0",
+        name: "grant_implementation_permissions",
+        run: grant_implementation_permissions,
+    },
+];
+
+fn record_storage_versions(deps: DepsMut, env: &Env) -> StdResult<()> {
+    // storages registered before the change log become their first versions, so they can be rolled back to
+    let reg = registry_read(deps.storage).load()?;
+    for (name, addr) in &reg.storages {
+        if last_storage_version(deps.storage, name)? == 0 {
+            record_change(
+                deps.storage,
+                env,
+                &env.contract.address,
+                RegistryAction::UpdateStorage,
+                Some(name),
+                None,
+                Some(addr.clone()),
+            )?;
+        }
+    }
+    Ok(())
+}
+
+fn grant_implementation_permissions(deps: DepsMut, _env: &Env) -> StdResult<()> {
+    // implementations could update every storage before the permissions, they keep this access explicitly
+    let reg = registry_read(deps.storage).load()?;
+    for implementation in &reg.implementations {
+        if IMPLEMENTATION_PERMISSIONS
+            .may_load(deps.storage, implementation.as_bytes())?
+            .is_none()
+        {
+            IMPLEMENTATION_PERMISSIONS.save(
+                deps.storage,
+                implementation.as_bytes(),
+                &full_permissions(&reg.storages),
+            )?;
+        }
+    }
+    Ok(())
 }
 
 pub fn query_admin_list(deps: Deps) -> StdResult<AdminListResponse> {
@@ -274,12 +767,88 @@ pub fn query_admin_list(deps: Deps) -> StdResult<AdminListResponse> {
     })
 }
 
-pub fn query_can_execute(deps: Deps, sender: Addr) -> StdResult<CanExecuteResponse> {
+pub fn query_can_execute(
+    deps: Deps,
+    sender: Addr,
+    name: Option<String>,
+) -> StdResult<CanExecuteResponse> {
+    let storage = match name {
+        Some(name) => {
+            let registry_obj = registry_read(deps.storage).load()?;
+            let access = storage_access(deps, &registry_obj, &sender, &name)?;
+            Some(StorageAccessResponse {
+                name,
+                can_update: access.is_some(),
+                kinds: access.flatten(),
+            })
+        }
+        None => None,
+    };
     Ok(CanExecuteResponse {
         can_execute: can_execute(deps, &sender)?,
+        storage,
     })
 }
 
+const DEFAULT_LIMIT: u8 = 20;
+const MAX_LIMIT: u8 = 50;
+
+/// returns the limit, the range bounds and the order, offset is exclusive
+pub(crate) fn get_range_params(
+    offset: Option<u64>,
+    limit: Option<u8>,
+    order: Option<u8>,
+) -> (usize, Option<Bound>, Option<Bound>, Order) {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let order_enum = match order {
+        Some(1) => Order::Ascending,
+        _ => Order::Descending,
+    };
+    let mut min = None;
+    let mut max = None;
+    if let Some(offset) = offset {
+        match order_enum {
+            Order::Ascending => min = Some(Bound::Exclusive(offset.to_be_bytes().to_vec())),
+            Order::Descending => max = Some(Bound::Exclusive(offset.to_be_bytes().to_vec())),
+        }
+    }
+    (limit, min, max, order_enum)
+}
+
+pub fn query_registry_changes(
+    deps: Deps,
+    offset: Option<u64>,
+    limit: Option<u8>,
+    order: Option<u8>,
+) -> StdResult<Vec<RegistryChange>> {
+    let (limit, min, max, order_enum) = get_range_params(offset, limit, order);
+    REGISTRY_CHANGES
+        .range(deps.storage, min, max, order_enum)
+        .take(limit)
+        .map(|item| item.map(|(_, change)| change))
+        .collect()
+}
+
+pub fn query_storage_versions(
+    deps: Deps,
+    name: String,
+    offset: Option<u64>,
+    limit: Option<u8>,
+    order: Option<u8>,
+) -> StdResult<Vec<RegistryChange>> {
+    let (limit, min, max, order_enum) = get_range_params(offset, limit, order);
+    STORAGE_VERSIONS
+        .prefix(name.as_bytes())
+        .range(deps.storage, min, max, order_enum)
+        .take(limit)
+        .map(|item| {
+            item.and_then(|(_, change_id)| {
+                REGISTRY_CHANGES.load(deps.storage, &change_id.to_be_bytes())
+            })
+        })
+        .collect()
+}
+
 fn get_storage_addr(registry: &Registry, name: &str) -> StdResult<Addr> {
     registry
         .storages
@@ -305,6 +874,7 @@ pub fn query_storage_addr(deps: Deps, name: String) -> StdResult<Addr> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::state::{HubAction, ProposalStatus};
     use cosmwasm_std::testing::{mock_dependencies_with_balance, mock_env, mock_info};
 
     #[test]
@@ -460,11 +1030,695 @@ mod tests {
         instantiate(deps.as_mut(), mock_env(), info, init_msg).unwrap();
 
         // owner can send
-        let res = query_can_execute(deps.as_ref(), alice.clone()).unwrap();
+        let res = query_can_execute(deps.as_ref(), alice.clone(), None).unwrap();
         assert_eq!(res.can_execute, true);
 
         // anyone cannot send
-        let res = query_can_execute(deps.as_ref(), anyone.clone()).unwrap();
+        let res = query_can_execute(deps.as_ref(), anyone.clone(), None).unwrap();
         assert_eq!(res.can_execute, false);
     }
+
+    #[test]
+    fn registry_changes_and_rollback() {
+        let mut deps = mock_dependencies_with_balance(&[]);
+
+        let alice = Addr::unchecked("alice");
+        let anyone = Addr::unchecked("anyone");
+        let owner = Addr::unchecked("tupt");
+        let auction_v1 = Addr::unchecked("auction_v1");
+        let auction_v2 = Addr::unchecked("auction_v2");
+
+        // init the contract
+        let init_msg = InstantiateMsg {
+            admins: vec![alice.clone()],
+            storages: vec![("auction".into(), auction_v1.clone())],
+            implementations: vec![],
+            mutable: true,
+        };
+        let info = mock_info(owner.as_str(), &[]);
+        instantiate(deps.as_mut(), mock_env(), info, init_msg).unwrap();
+
+        // upgrade the auction storage, updating with the same address is not a change
+        let msg = ExecuteMsg::UpdateStorages {
+            storages: vec![
+                ("auction".into(), auction_v2.clone()),
+                ("auction".into(), auction_v2.clone()),
+            ],
+        };
+        let info = mock_info(alice.as_str(), &[]);
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(
+            query_storage_addr(deps.as_ref(), "auction".into()).unwrap(),
+            auction_v2
+        );
+
+        let versions =
+            query_storage_versions(deps.as_ref(), "auction".into(), None, None, Some(1)).unwrap();
+        assert_eq!(versions.len(), 2);
+        assert_eq!(versions[1].old_addr, Some(auction_v1.clone()));
+        assert_eq!(versions[1].new_addr, Some(auction_v2.clone()));
+        assert_eq!(versions[1].admin, alice);
+        assert_eq!(versions[1].version, Some(2));
+
+        // anyone cannot rollback
+        let msg = ExecuteMsg::RollbackStorage {
+            name: "auction".into(),
+            version: 1,
+        };
+        let info = mock_info(anyone.as_str(), &[]);
+        match execute(deps.as_mut(), mock_env(), info, msg.clone()).unwrap_err() {
+            ContractError::Unauthorized { .. } => {}
+            e => panic!("unexpected error: {}", e),
+        }
+
+        // unknown version
+        let info = mock_info(alice.as_str(), &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::RollbackStorage {
+                name: "auction".into(),
+                version: 5,
+            },
+        );
+        match res.unwrap_err() {
+            ContractError::VersionNotFound { .. } => {}
+            e => panic!("unexpected error: {}", e),
+        }
+
+        // alice can rollback to the first version
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(
+            query_storage_addr(deps.as_ref(), "auction".into()).unwrap(),
+            auction_v1
+        );
+
+        // the rollback is the latest change
+        let changes = query_registry_changes(deps.as_ref(), None, Some(1), None).unwrap();
+        assert_eq!(changes[0].id, 3);
+        assert_eq!(changes[0].action, RegistryAction::RollbackStorage);
+        assert_eq!(changes[0].version, Some(3));
+        assert_eq!(changes[0].old_addr, Some(auction_v2));
+        assert_eq!(changes[0].new_addr, Some(auction_v1));
+
+        // paginate the change log from the oldest change
+        let changes = query_registry_changes(deps.as_ref(), Some(1), None, Some(1)).unwrap();
+        assert_eq!(
+            changes.iter().map(|change| change.id).collect::<Vec<u64>>(),
+            vec![2, 3]
+        );
+    }
+
+    #[test]
+    fn implementation_permissions_and_roles() {
+        let mut deps = mock_dependencies_with_balance(&[]);
+
+        let alice = Addr::unchecked("alice");
+        let bob = Addr::unchecked("bob");
+        let owner = Addr::unchecked("tupt");
+        let datahub = Addr::unchecked("datahub");
+
+        // init the contract
+        let init_msg = InstantiateMsg {
+            admins: vec![alice.clone()],
+            storages: vec![
+                ("auction".into(), Addr::unchecked("auction")),
+                ("offering".into(), Addr::unchecked("offering")),
+            ],
+            implementations: vec![datahub.clone()],
+            mutable: true,
+        };
+        let info = mock_info(owner.as_str(), &[]);
+        instantiate(deps.as_mut(), mock_env(), info, init_msg).unwrap();
+
+        let update_storage_data = |name: &str, msg: &str| {
+            ExecuteMsg::Storage(StorageExecuteMsg::UpdateStorageData {
+                name: name.into(),
+                msg: Binary::from(msg.as_bytes()),
+            })
+        };
+
+        // the initial implementations can update every initial storage
+        let info = mock_info(datahub.as_str(), &[]);
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            update_storage_data("auction", r#"{"update_auction":{}}"#),
+        )
+        .unwrap();
+
+        // an implementation without permissions cannot update any storage
+        let market = Addr::unchecked("market");
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(alice.as_str(), &[]),
+            ExecuteMsg::UpdateImplementation {
+                implementation: market.clone(),
+            },
+        )
+        .unwrap();
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(market.as_str(), &[]),
+            update_storage_data("auction", r#"{"update_auction":{}}"#),
+        );
+        match res.unwrap_err() {
+            ContractError::Unauthorized { .. } => {}
+            e => panic!("unexpected error: {}", e),
+        }
+        let permissions: Option<Vec<StoragePermission>> = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::Permissions {
+                    implementation: market,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(permissions, None);
+
+        // bob cannot restrict it without the permissions role
+        let msg = ExecuteMsg::UpdatePermissions {
+            implementation: datahub.clone(),
+            storages: Some(vec![StoragePermission {
+                name: "offering".into(),
+                kinds: Some(vec!["offering.update_offering".into(), "msg".into()]),
+            }]),
+        };
+        let bob_info = mock_info(bob.as_str(), &[]);
+        match execute(deps.as_mut(), mock_env(), bob_info.clone(), msg.clone()).unwrap_err() {
+            ContractError::Unauthorized { .. } => {}
+            e => panic!("unexpected error: {}", e),
+        }
+
+        // only admins that can modify the admin list grant roles
+        let grant_msg = ExecuteMsg::UpdateRoles {
+            address: bob.clone(),
+            roles: vec![AdminRole::Permissions],
+        };
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            bob_info.clone(),
+            grant_msg.clone(),
+        )
+        .unwrap_err();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(alice.as_str(), &[]),
+            grant_msg,
+        )
+        .unwrap();
+        let roles: Vec<AdminRole> = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::Roles {
+                    address: bob.clone(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(roles, vec![AdminRole::Permissions]);
+        execute(deps.as_mut(), mock_env(), bob_info.clone(), msg).unwrap();
+
+        // the permissions role does not grant registry changes
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            bob_info,
+            ExecuteMsg::UpdateImplementation {
+                implementation: bob.clone(),
+            },
+        );
+        match res.unwrap_err() {
+            ContractError::Unauthorized { .. } => {}
+            e => panic!("unexpected error: {}", e),
+        }
+
+        // the datahub cannot update the auction storage anymore
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            update_storage_data("auction", r#"{"auction":{"update_auction":{}}}"#),
+        );
+        match res.unwrap_err() {
+            ContractError::Unauthorized { .. } => {}
+            e => panic!("unexpected error: {}", e),
+        }
+
+        // nor remove offerings
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            update_storage_data("offering", r#"{"offering":{"remove_offering":{"id":1}}}"#),
+        );
+        match res.unwrap_err() {
+            ContractError::StorageNotPermitted { kind, .. } => {
+                assert_eq!(kind, "offering.remove_offering")
+            }
+            e => panic!("unexpected error: {}", e),
+        }
+
+        // but it can still update them, and send any generic message
+        for msg in [
+            r#"{"offering":{"update_offering":{"offering":{}}}}"#,
+            r#"{"msg":{"update_offering_payment":{}}}"#,
+        ] {
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                info.clone(),
+                update_storage_data("offering", msg),
+            )
+            .unwrap();
+        }
+
+        // can execute reports the storage permissions
+        let res =
+            query_can_execute(deps.as_ref(), datahub.clone(), Some("offering".into())).unwrap();
+        assert!(!res.can_execute);
+        assert_eq!(
+            res.storage,
+            Some(StorageAccessResponse {
+                name: "offering".into(),
+                can_update: true,
+                kinds: Some(vec!["offering.update_offering".into(), "msg".into()]),
+            })
+        );
+        let res = query_can_execute(deps.as_ref(), datahub, Some("auction".into())).unwrap();
+        assert!(!res.storage.unwrap().can_update);
+        let res = query_can_execute(deps.as_ref(), alice, Some("auction".into())).unwrap();
+        assert!(res.can_execute);
+        assert_eq!(res.storage.unwrap().kinds, None);
+    }
+
+    #[test]
+    fn timelocked_proposals() {
+        let mut deps = mock_dependencies_with_balance(&[]);
+
+        let alice = Addr::unchecked("alice");
+        let owner = Addr::unchecked("tupt");
+        let anyone = Addr::unchecked("anyone");
+        let market = Addr::unchecked("market");
+
+        // init the contract
+        let init_msg = InstantiateMsg {
+            admins: vec![alice.clone()],
+            storages: vec![],
+            implementations: vec![],
+            mutable: true,
+        };
+        let info = mock_info(owner.as_str(), &[]);
+        instantiate(deps.as_mut(), mock_env(), info.clone(), init_msg).unwrap();
+
+        // the owner sets a timelock of one day
+        let msg = ExecuteMsg::UpdateMinDelay { min_delay: 86400 };
+        execute(deps.as_mut(), mock_env(), info.clone(), msg.clone()).unwrap();
+
+        // admin actions cannot be executed directly anymore
+        let alice_info = mock_info(alice.as_str(), &[]);
+        let update_msg = ExecuteMsg::UpdateImplementation {
+            implementation: market.clone(),
+        };
+        for msg in [update_msg, msg] {
+            match execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap_err() {
+                ContractError::TimelockRequired { min_delay } => assert_eq!(min_delay, 86400),
+                e => panic!("unexpected error: {}", e),
+            }
+        }
+
+        // anyone cannot propose
+        let propose_msg = ExecuteMsg::ProposeAction {
+            action: HubAction::UpdateImplementation {
+                implementation: market.clone(),
+            },
+            expires_in: None,
+        };
+        let anyone_info = mock_info(anyone.as_str(), &[]);
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            anyone_info.clone(),
+            propose_msg.clone(),
+        )
+        .unwrap_err();
+
+        // alice proposes twice, then cancels the second proposal
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            alice_info.clone(),
+            propose_msg.clone(),
+        )
+        .unwrap();
+        execute(deps.as_mut(), mock_env(), alice_info.clone(), propose_msg).unwrap();
+        let cancel_msg = ExecuteMsg::CancelProposal { id: 2 };
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            anyone_info.clone(),
+            cancel_msg.clone(),
+        )
+        .unwrap_err();
+        execute(deps.as_mut(), mock_env(), alice_info, cancel_msg).unwrap();
+
+        // pending proposals are public
+        let pending = query_proposals(
+            deps.as_ref(),
+            Some(ProposalStatus::Pending),
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].id, 1);
+        assert_eq!(pending[0].eta, mock_env().block.time.seconds() + 86400);
+
+        // nobody can execute before the delay
+        let execute_msg = ExecuteMsg::ExecuteProposal { id: 1 };
+        match execute(
+            deps.as_mut(),
+            mock_env(),
+            anyone_info.clone(),
+            execute_msg.clone(),
+        )
+        .unwrap_err()
+        {
+            ContractError::ProposalNotReady { .. } => {}
+            e => panic!("unexpected error: {}", e),
+        }
+
+        // then anyone can
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(86400);
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            anyone_info.clone(),
+            execute_msg.clone(),
+        )
+        .unwrap();
+        let reg: Registry = registry_read(&deps.storage).load().unwrap();
+        assert_eq!(reg.implementations, vec![market.clone()]);
+        match execute(deps.as_mut(), env.clone(), anyone_info, execute_msg).unwrap_err() {
+            ContractError::ProposalNotPending { id } => assert_eq!(id, 1),
+            e => panic!("unexpected error: {}", e),
+        }
+
+        // emergency removal bypasses the timelock
+        execute(
+            deps.as_mut(),
+            env,
+            info,
+            ExecuteMsg::RemoveImplementation {
+                implementation: market,
+            },
+        )
+        .unwrap();
+        let reg: Registry = registry_read(&deps.storage).load().unwrap();
+        assert!(reg.implementations.is_empty());
+    }
+
+    #[test]
+    fn multisig_proposals() {
+        let mut deps = mock_dependencies_with_balance(&[]);
+
+        let alice = Addr::unchecked("alice");
+        let bob = Addr::unchecked("bob");
+        let carl = Addr::unchecked("carl");
+        let owner = Addr::unchecked("tupt");
+        let anyone = Addr::unchecked("anyone");
+        let storages = vec![("auction".to_string(), Addr::unchecked("auction_v2"))];
+
+        // init the contract
+        let init_msg = InstantiateMsg {
+            admins: vec![alice.clone(), bob.clone(), carl.clone()],
+            storages: vec![],
+            implementations: vec![],
+            mutable: true,
+        };
+        let owner_info = mock_info(owner.as_str(), &[]);
+        instantiate(deps.as_mut(), mock_env(), owner_info.clone(), init_msg).unwrap();
+
+        // only the owner changes the threshold, up to the number of admins
+        let alice_info = mock_info(alice.as_str(), &[]);
+        let msg = ExecuteMsg::UpdateThreshold { threshold: 2 };
+        execute(deps.as_mut(), mock_env(), alice_info.clone(), msg.clone()).unwrap_err();
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            owner_info.clone(),
+            ExecuteMsg::UpdateThreshold { threshold: 5 },
+        );
+        match res.unwrap_err() {
+            ContractError::InvalidThreshold { threshold, admins } => {
+                assert_eq!((threshold, admins), (5, 4))
+            }
+            e => panic!("unexpected error: {}", e),
+        }
+        execute(deps.as_mut(), mock_env(), owner_info.clone(), msg).unwrap();
+
+        // a single admin cannot update the storages anymore
+        let update_msg = ExecuteMsg::UpdateStorages {
+            storages: storages.clone(),
+        };
+        match execute(
+            deps.as_mut(),
+            mock_env(),
+            alice_info.clone(),
+            update_msg.clone(),
+        )
+        .unwrap_err()
+        {
+            ContractError::ApprovalRequired { threshold } => assert_eq!(threshold, 2),
+            e => panic!("unexpected error: {}", e),
+        }
+
+        // alice proposes, which counts as her approval
+        let propose_msg = ExecuteMsg::ProposeAction {
+            action: HubAction::UpdateStorages {
+                storages: storages.clone(),
+            },
+            expires_in: Some(100),
+        };
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            alice_info.clone(),
+            propose_msg.clone(),
+        )
+        .unwrap();
+        let execute_msg = ExecuteMsg::ExecuteProposal { id: 1 };
+        match execute(
+            deps.as_mut(),
+            mock_env(),
+            alice_info.clone(),
+            execute_msg.clone(),
+        )
+        .unwrap_err()
+        {
+            ContractError::NotEnoughApprovals { approvals, .. } => assert_eq!(approvals, 1),
+            e => panic!("unexpected error: {}", e),
+        }
+
+        // anyone cannot approve, bob can
+        let approve_msg = ExecuteMsg::ApproveProposal { id: 1 };
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(anyone.as_str(), &[]),
+            approve_msg.clone(),
+        )
+        .unwrap_err();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(bob.as_str(), &[]),
+            approve_msg,
+        )
+        .unwrap();
+        let votes = query_proposal_votes(deps.as_ref(), 1).unwrap();
+        assert_eq!(votes.approvals, vec![alice.clone(), bob.clone()]);
+        assert!(votes.approved);
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(anyone.as_str(), &[]),
+            execute_msg,
+        )
+        .unwrap();
+        assert_eq!(
+            query_storage_addr(deps.as_ref(), "auction".into()).unwrap(),
+            storages[0].1
+        );
+
+        // proposals cannot be approved once expired
+        execute(deps.as_mut(), mock_env(), alice_info, propose_msg).unwrap();
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(100);
+        let res = execute(
+            deps.as_mut(),
+            env,
+            mock_info(carl.as_str(), &[]),
+            ExecuteMsg::ApproveProposal { id: 2 },
+        );
+        match res.unwrap_err() {
+            ContractError::ProposalExpired { id } => assert_eq!(id, 2),
+            e => panic!("unexpected error: {}", e),
+        }
+
+        // a zero threshold goes back to the single admin mode
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            owner_info,
+            ExecuteMsg::UpdateThreshold { threshold: 0 },
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(carl.as_str(), &[]),
+            update_msg,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn batch_update_storage_data() {
+        let mut deps = mock_dependencies_with_balance(&[]);
+
+        let owner = Addr::unchecked("tupt");
+        let market = Addr::unchecked("market");
+
+        // init the contract
+        let init_msg = InstantiateMsg {
+            admins: vec![],
+            storages: vec![
+                ("auction".into(), Addr::unchecked("auction")),
+                ("offering".into(), Addr::unchecked("offering")),
+            ],
+            implementations: vec![market.clone()],
+            mutable: true,
+        };
+        let owner_info = mock_info(owner.as_str(), &[]);
+        instantiate(deps.as_mut(), mock_env(), owner_info.clone(), init_msg).unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            owner_info,
+            ExecuteMsg::UpdatePermissions {
+                implementation: market.clone(),
+                storages: Some(vec![StoragePermission {
+                    name: "offering".into(),
+                    kinds: None,
+                }]),
+            },
+        )
+        .unwrap();
+
+        let offering_msg = Binary::from(br#"{"offering":{"remove_offering":{"id":1}}}"#);
+        let auction_msg = Binary::from(br#"{"auction":{"remove_auction":{"id":1}}}"#);
+        let batch_msg = |updates: Vec<(&str, &Binary)>| {
+            ExecuteMsg::Storage(StorageExecuteMsg::BatchUpdateStorageData {
+                updates: updates
+                    .into_iter()
+                    .map(|(name, msg)| (name.to_string(), msg.clone()))
+                    .collect(),
+            })
+        };
+
+        // one update the market cannot do rejects the whole batch
+        let info = mock_info(market.as_str(), &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            batch_msg(vec![("offering", &offering_msg), ("auction", &auction_msg)]),
+        );
+        match res.unwrap_err() {
+            ContractError::Unauthorized { .. } => {}
+            e => panic!("unexpected error: {}", e),
+        }
+
+        // otherwise every update is dispatched in order
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            batch_msg(vec![
+                ("offering", &offering_msg),
+                ("offering", &offering_msg),
+            ]),
+        )
+        .unwrap();
+        assert_eq!(res.messages.len(), 2);
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "offering".into(),
+                msg: offering_msg,
+                funds: vec![],
+            })
+        );
+    }
+
+    #[test]
+    fn migrate_grants_registered_implementations() {
+        let mut deps = mock_dependencies_with_balance(&[]);
+        let market = Addr::unchecked("market");
+
+        // before 0.7.0 the registered implementations could update every storage
+        let init_msg = InstantiateMsg {
+            admins: vec![],
+            storages: vec![
+                ("auction".into(), Addr::unchecked("auction")),
+                ("offering".into(), Addr::unchecked("offering")),
+            ],
+            implementations: vec![market.clone()],
+            mutable: true,
+        };
+        instantiate(deps.as_mut(), mock_env(), mock_info("tupt", &[]), init_msg).unwrap();
+        IMPLEMENTATION_PERMISSIONS.remove(deps.as_mut().storage, market.as_bytes());
+        set_contract_version(deps.as_mut().storage, CONTRACT_NAME, "0.6.0").unwrap();
+
+        migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+        let permissions: Option<Vec<StoragePermission>> = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::Permissions {
+                    implementation: market,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            permissions,
+            Some(vec![
+                StoragePermission {
+                    name: "auction".into(),
+                    kinds: None,
+                },
+                StoragePermission {
+                    name: "offering".into(),
+                    kinds: None,
+                },
+            ])
+        );
+    }
 }
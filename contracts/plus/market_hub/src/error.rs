@@ -8,4 +8,39 @@ pub enum ContractError {
 
     #[error("Unauthorized market hub with sender: {sender}")]
     Unauthorized { sender: String },
+
+    #[error("Storage {name} has no version {version}")]
+    VersionNotFound { name: String, version: u64 },
+
+    #[error("Action must be proposed, the hub has a timelock of {min_delay} seconds")]
+    TimelockRequired { min_delay: u64 },
+
+    #[error("Action must be proposed, the hub needs {threshold} admin approvals")]
+    ApprovalRequired { threshold: u64 },
+
+    #[error("Proposal {id} has {approvals} of the {threshold} required approvals")]
+    NotEnoughApprovals {
+        id: u64,
+        approvals: u64,
+        threshold: u64,
+    },
+
+    #[error("Proposal {id} has expired")]
+    ProposalExpired { id: u64 },
+
+    #[error("Threshold {threshold} must not exceed the {admins} admins")]
+    InvalidThreshold { threshold: u64, admins: u64 },
+
+    #[error("Proposal {id} is not pending")]
+    ProposalNotPending { id: u64 },
+
+    #[error("Proposal {id} cannot be executed before {eta}")]
+    ProposalNotReady { id: u64, eta: u64 },
+
+    #[error("Sender {sender} is not permitted to execute {kind} on storage {name}")]
+    StorageNotPermitted {
+        sender: String,
+        name: String,
+        kind: String,
+    },
 }
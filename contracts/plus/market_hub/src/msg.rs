@@ -1,8 +1,12 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
 
-use cosmwasm_std::{Addr, Binary};
+use cosmwasm_std::Addr;
 
-use market::{Registry, StorageExecuteMsg, StorageItem, StorageQueryMsg};
+use market::{Registry, StorageExecuteMsg, StorageItem, StorageQueryMsg, StorageQueryResponse};
+
+use crate::state::{
+    AdminRole, HubAction, Proposal, ProposalStatus, RegistryChange, StoragePermission,
+};
 
 #[cw_serde]
 pub struct InstantiateMsg {
@@ -35,6 +39,50 @@ pub enum ExecuteMsg {
     UpdateAdmins {
         admins: Vec<Addr>,
     },
+    /// RollbackStorage points the storage name back to the address of a previous version, must be called by an admin
+    RollbackStorage {
+        name: String,
+        version: u64,
+    },
+    /// UpdateRoles replaces the roles of a non admin address, empty roles revoke them,
+    /// must be called by an admin that can modify the admin list
+    UpdateRoles {
+        address: Addr,
+        roles: Vec<AdminRole>,
+    },
+    /// UpdatePermissions allows an implementation to update these storages, None revokes every permission
+    UpdatePermissions {
+        implementation: Addr,
+        storages: Option<Vec<StoragePermission>>,
+    },
+    /// UpdateMinDelay sets the timelock in seconds, must be proposed once the timelock is set
+    UpdateMinDelay {
+        min_delay: u64,
+    },
+    /// ProposeAction schedules an admin action, it can be executed once the timelock has passed
+    /// and enough admins approved it. The proposer approves it when it is an admin
+    ProposeAction {
+        action: HubAction,
+        /// seconds after which the proposal expires, never when None
+        expires_in: Option<u64>,
+    },
+    /// ApproveProposal adds the vote of an admin to a pending proposal
+    ApproveProposal {
+        id: u64,
+    },
+    /// UpdateThreshold sets the number of admin approvals of the proposals, must be called by the owner.
+    /// Zero keeps the single admin mode
+    UpdateThreshold {
+        threshold: u64,
+    },
+    /// CancelProposal drops a pending proposal, must be called by an admin
+    CancelProposal {
+        id: u64,
+    },
+    /// ExecuteProposal can be called by anyone once the timelock of the proposal has passed
+    ExecuteProposal {
+        id: u64,
+    },
     Storage(StorageExecuteMsg),
 }
 
@@ -47,13 +95,60 @@ pub enum QueryMsg {
     /// Checks permissions of the caller on this proxy.
     /// If CanExecute returns true then a call to `Execute` with the same message,
     /// before any further state changes, should also succeed.
+    /// When a storage name is given, also reports whether the sender can update that storage.
     #[returns(CanExecuteResponse)]
-    CanExecute { sender: Addr },
+    CanExecute { sender: Addr, name: Option<String> },
+
+    #[returns(Vec<AdminRole>)]
+    Roles { address: Addr },
+
+    /// Storage permissions of an implementation, None when it cannot update any storage
+    #[returns(Option<Vec<StoragePermission>>)]
+    Permissions { implementation: Addr },
 
     #[returns(Registry)]
     Registry {},
 
-    #[returns(StorageResponse)]
+    /// Lists every storage and implementation change of the registry
+    #[returns(Vec<RegistryChange>)]
+    RegistryChanges {
+        offset: Option<u64>,
+        limit: Option<u8>,
+        order: Option<u8>,
+    },
+
+    /// Lists the versions of a storage name, offset is the version
+    #[returns(Vec<RegistryChange>)]
+    StorageVersions {
+        name: String,
+        offset: Option<u64>,
+        limit: Option<u8>,
+        order: Option<u8>,
+    },
+
+    /// Timelock in seconds of the admin actions
+    #[returns(u64)]
+    MinDelay {},
+
+    /// Number of admin approvals a proposal needs
+    #[returns(u64)]
+    Threshold {},
+
+    #[returns(Proposal)]
+    Proposal { id: u64 },
+
+    #[returns(ProposalVotesResponse)]
+    ProposalVotes { id: u64 },
+
+    #[returns(Vec<Proposal>)]
+    Proposals {
+        status: Option<ProposalStatus>,
+        offset: Option<u64>,
+        limit: Option<u8>,
+        order: Option<u8>,
+    },
+
+    #[returns(StorageQueryResponse)]
     Storage(StorageQueryMsg),
 }
 
@@ -67,12 +162,23 @@ pub struct AdminListResponse {
 #[cw_serde]
 pub struct CanExecuteResponse {
     pub can_execute: bool,
+    pub storage: Option<StorageAccessResponse>,
+}
+
+#[cw_serde]
+pub struct ProposalVotesResponse {
+    /// approvals of the current admins
+    pub approvals: Vec<Addr>,
+    pub threshold: u64,
+    pub approved: bool,
 }
 
 #[cw_serde]
-pub enum StorageResponse {
-    Addr(Addr),
-    Binary(Binary),
+pub struct StorageAccessResponse {
+    pub name: String,
+    pub can_update: bool,
+    /// allowed message kinds, every kind when None
+    pub kinds: Option<Vec<String>>,
 }
 
 #[cw_serde]
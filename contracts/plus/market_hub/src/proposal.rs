@@ -0,0 +1,315 @@
+use cosmwasm_std::{attr, Addr, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Storage};
+use market::AdminList;
+
+use crate::contract::{
+    can_execute, get_range_params, map_canonical, update_implementation, update_storages,
+};
+use crate::error::ContractError;
+use crate::msg::ProposalVotesResponse;
+use crate::state::{
+    admin_list, admin_list_read, HubAction, Proposal, ProposalStatus, MIN_DELAY, PROPOSALS,
+    PROPOSALS_COUNT, THRESHOLD,
+};
+
+/// admin actions take effect instantly only when there is no timelock nor approval threshold
+pub(crate) fn check_direct_action(storage: &dyn Storage) -> Result<(), ContractError> {
+    let min_delay = MIN_DELAY.may_load(storage)?.unwrap_or_default();
+    if min_delay > 0 {
+        return Err(ContractError::TimelockRequired { min_delay });
+    }
+    let threshold = THRESHOLD.may_load(storage)?.unwrap_or_default();
+    if threshold > 0 {
+        return Err(ContractError::ApprovalRequired { threshold });
+    }
+    Ok(())
+}
+
+/// the owner and the admins can approve proposals
+fn count_voters(cfg: &AdminList) -> u64 {
+    let owner_is_listed = cfg.admins.contains(&cfg.owner);
+    cfg.admins.len() as u64 + u64::from(!owner_is_listed)
+}
+
+fn check_threshold(cfg: &AdminList, threshold: u64) -> Result<(), ContractError> {
+    let admins = count_voters(cfg);
+    if threshold > admins {
+        return Err(ContractError::InvalidThreshold { threshold, admins });
+    }
+    Ok(())
+}
+
+fn is_admin(deps: Deps, cfg: &AdminList, addr: &Addr) -> StdResult<bool> {
+    Ok(cfg.is_admin(&deps.api.addr_canonicalize(addr.as_str())?))
+}
+
+/// approvals of the addresses that are still admins
+fn current_approvals(deps: Deps, proposal: &Proposal) -> StdResult<Vec<Addr>> {
+    let cfg = admin_list_read(deps.storage).load()?;
+    let mut approvals = vec![];
+    for addr in &proposal.approvals {
+        if is_admin(deps, &cfg, addr)? {
+            approvals.push(addr.clone());
+        }
+    }
+    Ok(approvals)
+}
+
+/// admin list changes need an admin that can modify it, registry changes the registry role
+fn check_action_permission(
+    deps: Deps,
+    sender: &Addr,
+    action: &HubAction,
+) -> Result<(), ContractError> {
+    let allowed = match action {
+        HubAction::UpdateAdmins { .. } | HubAction::UpdateMinDelay { .. } => {
+            admin_list_read(deps.storage)
+                .load()?
+                .can_modify(&deps.api.addr_canonicalize(sender.as_str())?)
+        }
+        HubAction::UpdateImplementation { .. } | HubAction::UpdateStorages { .. } => {
+            can_execute(deps, sender)?
+        }
+    };
+    if !allowed {
+        return Err(ContractError::Unauthorized {
+            sender: sender.to_string(),
+        });
+    }
+    Ok(())
+}
+
+fn apply_action(
+    deps: DepsMut,
+    env: &Env,
+    proposer: &Addr,
+    action: HubAction,
+) -> Result<(), ContractError> {
+    match action {
+        HubAction::UpdateImplementation { implementation } => {
+            update_implementation(deps.storage, env, proposer, implementation)?
+        }
+        HubAction::UpdateStorages { storages } => {
+            update_storages(deps.storage, env, proposer, &storages)?
+        }
+        HubAction::UpdateAdmins { admins } => {
+            let mut cfg = admin_list_read(deps.storage).load()?;
+            cfg.admins = map_canonical(deps.api, &admins)?;
+            check_threshold(&cfg, THRESHOLD.may_load(deps.storage)?.unwrap_or_default())?;
+            admin_list(deps.storage).save(&cfg)?;
+        }
+        HubAction::UpdateMinDelay { min_delay } => MIN_DELAY.save(deps.storage, &min_delay)?,
+    }
+    Ok(())
+}
+
+pub fn handle_update_min_delay(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    min_delay: u64,
+) -> Result<Response, ContractError> {
+    let action = HubAction::UpdateMinDelay { min_delay };
+    check_action_permission(deps.as_ref(), &info.sender, &action)?;
+    check_direct_action(deps.storage)?;
+    apply_action(deps, &env, &info.sender, action)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "update_min_delay"),
+        attr("min_delay", min_delay.to_string()),
+    ]))
+}
+
+pub fn handle_propose_action(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    action: HubAction,
+    expires_in: Option<u64>,
+) -> Result<Response, ContractError> {
+    check_action_permission(deps.as_ref(), &info.sender, &action)?;
+    let cfg = admin_list_read(deps.storage).load()?;
+    let approvals = if is_admin(deps.as_ref(), &cfg, &info.sender)? {
+        vec![info.sender.clone()]
+    } else {
+        vec![]
+    };
+
+    let id = PROPOSALS_COUNT.may_load(deps.storage)?.unwrap_or_default() + 1;
+    PROPOSALS_COUNT.save(deps.storage, &id)?;
+    let created_at = env.block.time.seconds();
+    let eta = created_at + MIN_DELAY.may_load(deps.storage)?.unwrap_or_default();
+    PROPOSALS.save(
+        deps.storage,
+        &id.to_be_bytes(),
+        &Proposal {
+            id,
+            action,
+            proposer: info.sender.clone(),
+            created_at,
+            eta,
+            expires_at: expires_in.map(|expires_in| created_at + expires_in),
+            approvals,
+            status: ProposalStatus::Pending,
+        },
+    )?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "propose_action"),
+        attr("proposal_id", id.to_string()),
+        attr("proposer", info.sender),
+        attr("eta", eta.to_string()),
+    ]))
+}
+
+fn load_pending_proposal(storage: &dyn Storage, id: u64) -> Result<Proposal, ContractError> {
+    let proposal = PROPOSALS.load(storage, &id.to_be_bytes())?;
+    if proposal.status != ProposalStatus::Pending {
+        return Err(ContractError::ProposalNotPending { id });
+    }
+    Ok(proposal)
+}
+
+fn check_not_expired(env: &Env, proposal: &Proposal) -> Result<(), ContractError> {
+    if proposal
+        .expires_at
+        .is_some_and(|expires_at| env.block.time.seconds() >= expires_at)
+    {
+        return Err(ContractError::ProposalExpired { id: proposal.id });
+    }
+    Ok(())
+}
+
+pub fn handle_approve_proposal(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    let cfg = admin_list_read(deps.storage).load()?;
+    if !is_admin(deps.as_ref(), &cfg, &info.sender)? {
+        return Err(ContractError::Unauthorized {
+            sender: info.sender.to_string(),
+        });
+    }
+    let mut proposal = load_pending_proposal(deps.storage, id)?;
+    check_not_expired(&env, &proposal)?;
+    if !proposal.approvals.contains(&info.sender) {
+        proposal.approvals.push(info.sender.clone());
+        PROPOSALS.save(deps.storage, &id.to_be_bytes(), &proposal)?;
+    }
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "approve_proposal"),
+        attr("proposal_id", id.to_string()),
+        attr("approver", info.sender),
+    ]))
+}
+
+pub fn handle_update_threshold(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    threshold: u64,
+) -> Result<Response, ContractError> {
+    let cfg = admin_list_read(deps.storage).load()?;
+    if cfg.owner != deps.api.addr_canonicalize(info.sender.as_str())? {
+        return Err(ContractError::Unauthorized {
+            sender: info.sender.to_string(),
+        });
+    }
+    check_threshold(&cfg, threshold)?;
+    THRESHOLD.save(deps.storage, &threshold)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "update_threshold"),
+        attr("threshold", threshold.to_string()),
+    ]))
+}
+
+pub fn handle_cancel_proposal(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    let cfg = admin_list_read(deps.storage).load()?;
+    if !cfg.is_admin(&deps.api.addr_canonicalize(info.sender.as_str())?) {
+        return Err(ContractError::Unauthorized {
+            sender: info.sender.to_string(),
+        });
+    }
+    let mut proposal = load_pending_proposal(deps.storage, id)?;
+    proposal.status = ProposalStatus::Cancelled;
+    PROPOSALS.save(deps.storage, &id.to_be_bytes(), &proposal)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "cancel_proposal"),
+        attr("proposal_id", id.to_string()),
+    ]))
+}
+
+pub fn handle_execute_proposal(
+    deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    let mut proposal = load_pending_proposal(deps.storage, id)?;
+    if env.block.time.seconds() < proposal.eta {
+        return Err(ContractError::ProposalNotReady {
+            id,
+            eta: proposal.eta,
+        });
+    }
+    check_not_expired(&env, &proposal)?;
+    let threshold = THRESHOLD.may_load(deps.storage)?.unwrap_or_default();
+    let approvals = current_approvals(deps.as_ref(), &proposal)?.len() as u64;
+    if approvals < threshold {
+        return Err(ContractError::NotEnoughApprovals {
+            id,
+            approvals,
+            threshold,
+        });
+    }
+    // the proposer may have lost its permission while the proposal was pending
+    check_action_permission(deps.as_ref(), &proposal.proposer, &proposal.action)?;
+
+    proposal.status = ProposalStatus::Executed;
+    PROPOSALS.save(deps.storage, &id.to_be_bytes(), &proposal)?;
+    apply_action(deps, &env, &proposal.proposer, proposal.action)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "execute_proposal"),
+        attr("proposal_id", id.to_string()),
+    ]))
+}
+
+pub fn query_proposals(
+    deps: Deps,
+    status: Option<ProposalStatus>,
+    offset: Option<u64>,
+    limit: Option<u8>,
+    order: Option<u8>,
+) -> StdResult<Vec<Proposal>> {
+    let (limit, min, max, order_enum) = get_range_params(offset, limit, order);
+    PROPOSALS
+        .range(deps.storage, min, max, order_enum)
+        .filter(|item| match (item, &status) {
+            (Ok((_, proposal)), Some(status)) => proposal.status.eq(status),
+            _ => true,
+        })
+        .take(limit)
+        .map(|item| item.map(|(_, proposal)| proposal))
+        .collect()
+}
+
+pub fn query_proposal_votes(deps: Deps, id: u64) -> StdResult<ProposalVotesResponse> {
+    let proposal = PROPOSALS.load(deps.storage, &id.to_be_bytes())?;
+    let approvals = current_approvals(deps, &proposal)?;
+    let threshold = THRESHOLD.may_load(deps.storage)?.unwrap_or_default();
+    Ok(ProposalVotesResponse {
+        approved: approvals.len() as u64 >= threshold,
+        approvals,
+        threshold,
+    })
+}
@@ -1,6 +1,8 @@
-use cosmwasm_std::Storage;
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Storage};
 use cosmwasm_storage::{singleton, singleton_read, ReadonlySingleton, Singleton};
-use market::{AdminList, Registry};
+use cw_storage_plus::{Item, Map};
+use market::{AdminList, Registry, StorageItem};
 
 pub const ADMIN_LIST_KEY: &[u8] = b"admin_list";
 
@@ -24,3 +26,94 @@ pub fn registry(storage: &mut dyn Storage) -> Singleton<Registry> {
 pub fn registry_read(storage: &dyn Storage) -> ReadonlySingleton<Registry> {
     singleton_read(storage, REGISTRY_KEY)
 }
+
+#[cw_serde]
+pub enum RegistryAction {
+    UpdateStorage,
+    RollbackStorage,
+    UpdateImplementation,
+    RemoveImplementation,
+}
+
+/// a change of the registry, storage changes also bump the version of the storage name
+#[cw_serde]
+pub struct RegistryChange {
+    pub id: u64,
+    pub action: RegistryAction,
+    /// storage name, None for implementation changes
+    pub name: Option<String>,
+    pub version: Option<u64>,
+    pub old_addr: Option<Addr>,
+    pub new_addr: Option<Addr>,
+    pub admin: Addr,
+    pub height: u64,
+}
+
+/// REGISTRY_CHANGES is the change log of the registry, keyed by change id
+pub const REGISTRY_CHANGES_COUNT: Item<u64> = Item::new("registry_changes_count");
+pub const REGISTRY_CHANGES: Map<&[u8], RegistryChange> = Map::new("registry_changes");
+/// change ids of a storage name, keyed by storage name and version
+pub const STORAGE_VERSIONS: Map<(&[u8], &[u8]), u64> = Map::new("storage_versions");
+
+/// roles that can be granted to non admin addresses, admins have every role
+#[cw_serde]
+pub enum AdminRole {
+    /// update implementations and storages, rollback storages
+    Registry,
+    /// update the data of any storage
+    Storage,
+    /// update the storage permissions of implementations
+    Permissions,
+}
+
+/// storage an implementation can update, with the allowed message kinds
+#[cw_serde]
+pub struct StoragePermission {
+    pub name: String,
+    /// key paths of the storage messages like "offering" or "offering.update_offering", every kind when None
+    pub kinds: Option<Vec<String>>,
+}
+
+pub const ROLES: Map<&[u8], Vec<AdminRole>> = Map::new("roles");
+/// implementations without permissions cannot update any storage
+pub const IMPLEMENTATION_PERMISSIONS: Map<&[u8], Vec<StoragePermission>> =
+    Map::new("implementation_permissions");
+
+/// admin actions that can be scheduled through a proposal
+#[cw_serde]
+pub enum HubAction {
+    UpdateImplementation { implementation: Addr },
+    UpdateStorages { storages: Vec<StorageItem> },
+    UpdateAdmins { admins: Vec<Addr> },
+    UpdateMinDelay { min_delay: u64 },
+}
+
+#[cw_serde]
+pub enum ProposalStatus {
+    Pending,
+    Executed,
+    Cancelled,
+}
+
+#[cw_serde]
+pub struct Proposal {
+    pub id: u64,
+    pub action: HubAction,
+    pub proposer: Addr,
+    /// block time in seconds
+    pub created_at: u64,
+    /// the proposal can be executed from this block time in seconds
+    pub eta: u64,
+    /// the proposal cannot be approved nor executed from this block time in seconds
+    pub expires_at: Option<u64>,
+    /// admins that approved the proposal
+    pub approvals: Vec<Addr>,
+    pub status: ProposalStatus,
+}
+
+/// MIN_DELAY is the timelock in seconds of the admin actions, they take effect instantly when it is zero
+pub const MIN_DELAY: Item<u64> = Item::new("min_delay");
+/// THRESHOLD is the number of admin approvals a proposal needs, single admin mode when it is zero
+pub const THRESHOLD: Item<u64> = Item::new("threshold");
+pub const PROPOSALS_COUNT: Item<u64> = Item::new("proposals_count");
+pub const PROPOSALS: Map<&[u8], Proposal> = Map::new("proposals");
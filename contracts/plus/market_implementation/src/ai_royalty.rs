@@ -1,64 +1,56 @@
-use crate::contract::{get_handle_msg, get_storage_addr, CREATOR_NAME, FIRST_LV_ROYALTY_STORAGE};
+use crate::contract::CREATOR_NAME;
 use crate::error::ContractError;
-use crate::msg::ProxyQueryMsg;
 use crate::state::{ContractInfo, CONTRACT_INFO};
 use cosmwasm_std::{
-    attr, from_json, to_json_binary, Binary, CosmosMsg, DepsMut, Env, MessageInfo, Response,
-    StdResult,
+    attr, to_json_binary, Binary, CosmosMsg, DepsMut, Env, MessageInfo, QuerierWrapper, Response,
+    StdResult, Uint128,
 };
 use cosmwasm_std::{Addr, Deps};
-use market::query_proxy;
-use market_ai_royalty::{AiRoyaltyExecuteMsg, AiRoyaltyQueryMsg, Royalty, RoyaltyMsg};
-use market_first_lv_royalty::{FirstLvRoyalty, FirstLvRoyaltyQueryMsg};
-
-pub const AI_ROYALTY_STORAGE: &str = "ai_royalty";
-pub const AI_ROYALTY_STORAGE_TEMP: &str = "ai_royalty_temp";
+use market::{AssetInfo, MarketHubContract, StorageMsg, StorageUpdate, AI_ROYALTY_STORAGE_TEMP};
+use market_ai_royalty::{
+    collect_royalties, parse_transfer_msg, pay_royalties, sanitize_total_royalty,
+    AccrueRoyaltiesMsg, AiRoyaltyExecuteMsg, AiRoyaltyQueryMsg, AiRoyaltyStorage, Royalty,
+    RoyaltyMsg, RoyaltyPolicy,
+};
+use market_first_lv_royalty::{
+    FirstLvRoyalty, FirstLvRoyaltyExecuteMsg, FirstLvRoyaltyQueryMsg, FirstLvRoyaltyStorage,
+    Provenance,
+};
+use market_royalty::OfferingRoyalty;
 
 pub fn add_msg_royalty(
     sender: &str,
-    governance: &str,
+    governance: &MarketHubContract,
     msg: RoyaltyMsg,
 ) -> StdResult<Vec<CosmosMsg>> {
     let mut cosmos_msgs: Vec<CosmosMsg> = vec![];
     // update ai royalty provider
-    cosmos_msgs.push(get_handle_msg(
-        governance,
-        AI_ROYALTY_STORAGE,
-        AiRoyaltyExecuteMsg::UpdateRoyalty(RoyaltyMsg {
-            royalty: None,
-            ..msg.clone()
-        }),
-    )?);
+    cosmos_msgs.push(governance.update_royalty(RoyaltyMsg {
+        royalty: None,
+        ..msg.clone()
+    })?);
 
     // update creator as the caller of the mint tx
-    cosmos_msgs.push(get_handle_msg(
-        governance,
-        AI_ROYALTY_STORAGE,
-        AiRoyaltyExecuteMsg::UpdateRoyalty(RoyaltyMsg {
-            creator: Addr::unchecked(sender.to_string()),
-            creator_type: Some(String::from(CREATOR_NAME)),
-            ..msg
-        }),
-    )?);
+    cosmos_msgs.push(governance.update_royalty(RoyaltyMsg {
+        creator: Addr::unchecked(sender.to_string()),
+        creator_type: Some(String::from(CREATOR_NAME)),
+        ..msg
+    })?);
     Ok(cosmos_msgs)
 }
 
 pub fn query_ai_royalty(deps: Deps, msg: AiRoyaltyQueryMsg) -> StdResult<Binary> {
     let contract_info = CONTRACT_INFO.load(deps.storage)?;
-    query_proxy(
-        deps,
-        get_storage_addr(deps, contract_info.governance, AI_ROYALTY_STORAGE)?,
-        to_json_binary(&ProxyQueryMsg::Msg(msg))?,
-    )
+    contract_info
+        .governance
+        .query_ai_royalty_binary(&deps.querier, msg)
 }
 
 pub fn query_first_level_royalty(deps: Deps, msg: FirstLvRoyaltyQueryMsg) -> StdResult<Binary> {
     let contract_info = CONTRACT_INFO.load(deps.storage)?;
-    query_proxy(
-        deps,
-        get_storage_addr(deps, contract_info.governance, FIRST_LV_ROYALTY_STORAGE)?,
-        to_json_binary(&ProxyQueryMsg::Msg(msg))?,
-    )
+    contract_info
+        .governance
+        .query_first_lv_royalty_binary(&deps.querier, msg)
 }
 
 pub fn get_royalties(
@@ -66,19 +58,18 @@ pub fn get_royalties(
     contract_addr: &str,
     token_id: &str,
 ) -> Result<Vec<Royalty>, ContractError> {
-    let royalties: Vec<Royalty> = from_json(&query_ai_royalty(
-        deps,
-        AiRoyaltyQueryMsg::GetRoyaltiesContractTokenId {
-            contract_addr: Addr::unchecked(contract_addr),
+    let contract_info = CONTRACT_INFO.load(deps.storage)?;
+    let royalties = contract_info
+        .governance
+        .get_royalties_contract_token_id(
+            &deps.querier,
+            Addr::unchecked(contract_addr),
+            token_id,
+            None,
+        )
+        .map_err(|_| ContractError::InvalidGetRoyaltiesTokenId {
             token_id: token_id.to_string(),
-            offset: None,
-            limit: None,
-            order: Some(1),
-        },
-    )?)
-    .map_err(|_| ContractError::InvalidGetRoyaltiesTokenId {
-        token_id: token_id.to_string(),
-    })?;
+        })?;
     Ok(royalties)
 }
 
@@ -89,11 +80,7 @@ pub fn try_update_preference(
 ) -> Result<Response, ContractError> {
     let ContractInfo { governance, .. } = CONTRACT_INFO.load(deps.storage)?;
     // check if token_id is currently sold by the requesting address
-    let cosmos_msg = get_handle_msg(
-        governance.as_str(),
-        AI_ROYALTY_STORAGE,
-        AiRoyaltyExecuteMsg::UpdatePreference(pref),
-    )?;
+    let cosmos_msg = governance.update_preference(pref)?;
 
     Ok(Response::new()
         .add_messages(vec![cosmos_msg])
@@ -109,27 +96,45 @@ pub fn try_update_royalty_creator(
     info: MessageInfo,
     royalty_msg: RoyaltyMsg,
 ) -> Result<Response, ContractError> {
-    let ContractInfo { governance, .. } = CONTRACT_INFO.load(deps.storage)?;
-    let royalty: Royalty = from_json(&query_ai_royalty(
-        deps.as_ref(),
-        AiRoyaltyQueryMsg::GetRoyalty {
-            contract_addr: royalty_msg.contract_addr.clone(),
-            token_id: royalty_msg.token_id.clone(),
-            creator: info.sender.clone(), // shall let royalty of info sender only
-        },
-    )?)
-    .map_err(|_| ContractError::InvalidGetCreatorRoyalty {})?;
+    let ContractInfo {
+        governance,
+        max_royalty,
+        ..
+    } = CONTRACT_INFO.load(deps.storage)?;
+    let royalty = governance
+        .get_royalty(
+            &deps.querier,
+            royalty_msg.contract_addr.clone(),
+            &royalty_msg.token_id,
+            info.sender.clone(), // shall let royalty of info sender only
+        )
+        .map_err(|_| ContractError::InvalidGetCreatorRoyalty {})?;
     // decay royalty, only update lower than the current royalty
     let final_new_royalty = royalty_msg.royalty.map(|r| r.min(royalty.royalty));
-    let cosmos_msg = get_handle_msg(
-        governance.as_str(),
-        AI_ROYALTY_STORAGE,
-        AiRoyaltyExecuteMsg::UpdateRoyalty(RoyaltyMsg {
-            royalty: final_new_royalty,
-            creator: info.sender.clone(), // force creator to be info sender instead of specifying the creator
-            ..royalty_msg
-        }),
+    // without a royalty the storage falls back to the creator preference
+    let new_royalty = final_new_royalty.unwrap_or_else(|| {
+        governance
+            .get_preference(&deps.querier, info.sender.clone())
+            .unwrap_or(royalty.royalty)
+    });
+    // the royalties of all creators must leave something for the seller
+    sanitize_total_royalty(
+        &get_royalties(
+            deps.as_ref(),
+            royalty_msg.contract_addr.as_str(),
+            &royalty_msg.token_id,
+        )?,
+        &info.sender,
+        new_royalty,
+        max_royalty,
     )?;
+    let cosmos_msg = governance.update_royalty(RoyaltyMsg {
+        royalty: final_new_royalty,
+        creator: info.sender.clone(), // force creator to be info sender instead of specifying the creator
+        // the co owners sharing the royalty are kept, the creator cannot drop or replace them
+        payees: None,
+        ..royalty_msg
+    })?;
 
     Ok(Response::new()
         .add_messages(vec![cosmos_msg])
@@ -142,23 +147,255 @@ pub fn try_update_royalty_creator(
 // query first level royalty
 pub fn query_first_lv_royalty(
     deps: Deps,
-    governance: &str,
+    governance: &MarketHubContract,
     contract: &str,
     token_id: &str,
 ) -> Result<FirstLvRoyalty, ContractError> {
-    let first_lv_royalty: FirstLvRoyalty = deps
-        .querier
-        .query_wasm_smart(
-            get_storage_addr(deps, Addr::unchecked(governance), FIRST_LV_ROYALTY_STORAGE)?,
-            &ProxyQueryMsg::Msg(FirstLvRoyaltyQueryMsg::GetFirstLvRoyalty {
-                contract: Addr::unchecked(contract),
-                token_id: token_id.to_string(),
-            }) as &ProxyQueryMsg<FirstLvRoyaltyQueryMsg>,
-        )
+    let first_lv_royalty = governance
+        .get_first_lv_royalty(&deps.querier, Addr::unchecked(contract), token_id)
         .map_err(|_| ContractError::InvalidGetFirstLvRoyalty {})?;
     Ok(first_lv_royalty)
 }
 
+/// previous owners of the token, None when the provenance royalties are disabled
+pub fn get_provenance(
+    deps: Deps,
+    contract_info: &ContractInfo,
+    contract_addr: &Addr,
+    token_id: &str,
+) -> StdResult<Option<Provenance>> {
+    if contract_info.provenance_royalties.is_empty() {
+        return Ok(None);
+    }
+    contract_info
+        .governance
+        .get_provenance(&deps.querier, contract_addr.clone(), token_id)
+        .map(Some)
+}
+
+/// the latest owners receive the first levels of the decaying royalties, the seller is never paid for its own sale
+pub fn get_provenance_royalties(
+    contract_info: &ContractInfo,
+    provenance: &Provenance,
+    seller: Option<&Addr>,
+) -> Vec<Royalty> {
+    provenance
+        .owners
+        .iter()
+        .zip(contract_info.provenance_royalties.iter())
+        .filter(|(owner, _)| seller.ne(&Some(*owner)))
+        .map(|(owner, royalty)| Royalty {
+            contract_addr: provenance.contract_addr.clone(),
+            token_id: provenance.token_id.clone(),
+            creator: owner.clone(),
+            royalty: *royalty,
+            creator_type: "previous_owner".into(),
+            payees: None,
+        })
+        .collect()
+}
+
+/// the seller becomes the latest owner of the provenance, only the owners still paid are kept
+pub fn get_provenance_update(
+    contract_info: &ContractInfo,
+    mut provenance: Provenance,
+    seller: Addr,
+) -> StdResult<StorageUpdate> {
+    provenance.owners.insert(0, seller);
+    provenance
+        .owners
+        .truncate(contract_info.provenance_royalties.len());
+    contract_info
+        .governance
+        .first_lv_royalty_update(FirstLvRoyaltyExecuteMsg::UpdateProvenance { provenance })
+}
+
+/// royalties paid when the seller sells the token: the creators, then either the provenance chain or the previous owner
+pub fn get_sale_royalties(
+    deps: Deps,
+    contract_info: &ContractInfo,
+    contract_addr: &Addr,
+    token_id: &str,
+    seller: &Addr,
+    provenance: Option<&Provenance>,
+    offering_royalty: Option<&OfferingRoyalty>,
+) -> Option<Vec<Royalty>> {
+    let mut royalties = get_royalties(deps, contract_addr.as_str(), token_id).ok()?;
+    match (provenance, offering_royalty) {
+        // the provenance chain replaces the single previous owner royalty
+        (Some(provenance), _) => royalties.extend(get_provenance_royalties(
+            contract_info,
+            provenance,
+            Some(seller),
+        )),
+        // payout for the previous owner
+        (None, Some(offering_royalty)) => {
+            if let (Some(previous_owner), Some(prev_royalty)) = (
+                offering_royalty.previous_owner.as_ref(),
+                offering_royalty.prev_royalty,
+            ) {
+                royalties.push(Royalty {
+                    contract_addr: offering_royalty.contract_addr.clone(),
+                    token_id: offering_royalty.token_id.clone(),
+                    creator: previous_owner.clone(),
+                    royalty: prev_royalty,
+                    creator_type: "previous_owner".into(),
+                    payees: None,
+                })
+            }
+        }
+        (None, None) => {}
+    }
+    Some(royalties)
+}
+
+/// the sale the royalties are settled on
+pub struct RoyaltySettlement<'a> {
+    pub contract_info: &'a ContractInfo,
+    pub querier: &'a QuerierWrapper<'a>,
+    /// amount the royalty percentages apply to
+    pub price: Uint128,
+    /// the market, which holds the funds of the sale
+    pub contract_addr: &'a str,
+    pub asset_info: AssetInfo,
+}
+
+/// pay the royalties out of the remaining amount, or credit them to the creator balances in accrual mode
+pub fn settle_royalties(
+    settlement: RoyaltySettlement,
+    royalties: &[Royalty],
+    remaining: &mut Uint128,
+    cosmos_msgs: &mut Vec<CosmosMsg>,
+    storage_updates: &mut Vec<StorageUpdate>,
+    rsp: &mut Response,
+) -> StdResult<()> {
+    let RoyaltySettlement {
+        contract_info,
+        querier,
+        price,
+        contract_addr,
+        asset_info,
+    } = settlement;
+    let denom = to_json_binary(&asset_info)?.to_base64();
+    if !contract_info.accrue_royalties {
+        return pay_royalties(
+            royalties,
+            &price,
+            contract_info.decimal_point,
+            remaining,
+            cosmos_msgs,
+            rsp,
+            contract_addr,
+            &denom,
+            asset_info,
+        );
+    }
+    let payouts = collect_royalties(
+        royalties,
+        &price,
+        contract_info.decimal_point,
+        remaining,
+        rsp,
+        &denom,
+    )?;
+    // the ai royalty storage keeps the funds until the creators claim them
+    if let (Some(royalty), false) = (royalties.first(), payouts.is_empty()) {
+        cosmos_msgs.push(parse_transfer_msg(
+            asset_info.clone(),
+            payouts.iter().map(|payout| payout.amount).sum(),
+            contract_addr,
+            contract_info.governance.ai_royalty_addr(querier)?,
+        )?);
+        storage_updates.push(contract_info.governance.ai_royalty_update(
+            AiRoyaltyExecuteMsg::AccrueRoyalties(AccrueRoyaltiesMsg {
+                contract_addr: royalty.contract_addr.clone(),
+                token_id: royalty.token_id.clone(),
+                asset_info,
+                payouts,
+            }),
+        )?);
+    }
+    Ok(())
+}
+
+pub fn try_claim_royalties(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    let ContractInfo { governance, .. } = CONTRACT_INFO.load(deps.storage)?;
+    let balances = governance.get_royalty_balances(&deps.querier, info.sender.clone())?;
+
+    // the storage pays the claims out of the funds it received on accrual
+    let mut cosmos_msgs: Vec<CosmosMsg> = vec![];
+    for balance in balances {
+        if balance.pending.is_zero() {
+            continue;
+        }
+        cosmos_msgs.push(governance.claim_royalties(
+            info.sender.clone(),
+            balance.asset_info,
+            balance.pending,
+        )?);
+    }
+    if cosmos_msgs.is_empty() {
+        return Err(ContractError::NoRoyaltiesToClaim {});
+    }
+
+    Ok(Response::new()
+        .add_messages(cosmos_msgs)
+        .add_attributes(vec![
+            attr("action", "claim_royalties"),
+            attr("creator", info.sender),
+        ]))
+}
+
+/// the token changes hands, the royalties whose creator type does not survive transfers are dropped
+pub fn get_expire_royalties_update(
+    governance: &MarketHubContract,
+    contract_addr: &Addr,
+    token_id: &str,
+) -> StdResult<StorageUpdate> {
+    governance.ai_royalty_update(AiRoyaltyExecuteMsg::ExpireRoyalties {
+        contract_addr: contract_addr.clone(),
+        token_id: token_id.to_string(),
+    })
+}
+
+pub fn try_update_royalty_policy(
+    deps: DepsMut,
+    info: MessageInfo,
+    policy: RoyaltyPolicy,
+) -> Result<Response, ContractError> {
+    let ContractInfo {
+        creator,
+        governance,
+        ..
+    } = CONTRACT_INFO.load(deps.storage)?;
+    if info.sender.ne(&Addr::unchecked(creator)) {
+        return Err(ContractError::Unauthorized {
+            sender: info.sender.to_string(),
+        });
+    }
+    let creator_type = policy.creator_type.clone();
+    Ok(Response::new()
+        .add_message(governance.update_policy(policy)?)
+        .add_attributes(vec![
+            attr("action", "update_royalty_policy"),
+            attr("creator_type", creator_type),
+        ]))
+}
+
+pub fn query_provenance_royalties(
+    deps: Deps,
+    contract_addr: Addr,
+    token_id: String,
+) -> StdResult<Vec<Royalty>> {
+    let contract_info = CONTRACT_INFO.load(deps.storage)?;
+    Ok(
+        match get_provenance(deps, &contract_info, &contract_addr, &token_id)? {
+            Some(provenance) => get_provenance_royalties(&contract_info, &provenance, None),
+            None => vec![],
+        },
+    )
+}
+
 // TODO: also update preferences for them
 
 pub fn try_update_royalties(
@@ -180,16 +417,16 @@ pub fn try_update_royalties(
     let mut cosmos_msgs: Vec<CosmosMsg> = vec![];
     for royalty in royalties {
         // update creator as the caller of the mint tx
-        cosmos_msgs.push(get_handle_msg(
-            governance.as_str(),
+        cosmos_msgs.push(governance.execute_storage(
             AI_ROYALTY_STORAGE_TEMP,
-            AiRoyaltyExecuteMsg::UpdateRoyalty(RoyaltyMsg {
+            &StorageMsg::Msg(AiRoyaltyExecuteMsg::UpdateRoyalty(RoyaltyMsg {
                 contract_addr: royalty.contract_addr,
                 token_id: royalty.token_id,
                 creator: royalty.creator,
                 creator_type: Some(royalty.creator_type),
                 royalty: Some(royalty.royalty),
-            }),
+                payees: royalty.payees,
+            })),
         )?);
     }
     Ok(Response::new()
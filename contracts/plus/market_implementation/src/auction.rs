@@ -1,12 +1,14 @@
 use crate::contract::{
-    get_asset_info, get_handle_msg, get_storage_addr, query_auction_payment_asset_info,
-    verify_funds, verify_nft, verify_owner, PAYMENT_STORAGE,
+    add_storage_updates, get_asset_info, query_auction_payment_asset_info, verify_funds,
+    verify_nft, verify_owner,
 };
 use crate::error::ContractError;
-use crate::msg::{ProxyExecuteMsg, ProxyQueryMsg};
+use crate::fee_discount::get_effective_fee;
 // use crate::offering::OFFERING_STORAGE;
-use crate::ai_royalty::get_royalties;
-use crate::offering::{get_offering_handle_msg, OFFERING_STORAGE};
+use crate::ai_royalty::{
+    get_expire_royalties_update, get_provenance, get_provenance_update, get_sale_royalties,
+    settle_royalties, RoyaltySettlement,
+};
 use crate::state::{ContractInfo, CONTRACT_INFO, MARKET_FEES};
 use cosmwasm_std::Addr;
 use cosmwasm_std::{
@@ -14,15 +16,18 @@ use cosmwasm_std::{
     StdResult, Uint128, WasmMsg,
 };
 use cw721::Cw721ExecuteMsg;
-use market::{query_proxy, AssetInfo, Funds, StorageExecuteMsg};
-use market_ai_royalty::{parse_transfer_msg, pay_royalties, sanitize_royalty, Royalty};
-use market_auction::{Auction, AuctionExecuteMsg, AuctionQueryMsg};
-use market_payment::{Payment, PaymentExecuteMsg};
-use market_royalty::{OfferingExecuteMsg, OfferingQueryMsg, OfferingRoyalty};
+use market::{
+    validate_reserved_for, verify_reserved_buyer, AssetInfo, Funds, StorageUpdate,
+    PAYMENT_721_STORAGE,
+};
+use market_ai_royalty::{parse_transfer_msg, sanitize_royalty};
+use market_auction::{Auction, AuctionExecuteMsg, AuctionQueryMsg, AuctionStorage};
+use market_payment::{Payment, PaymentStorage};
+use market_royalty::{OfferingExecuteMsg, OfferingRoyalty, OfferingStorage};
+use market_whitelist::ListingType;
 // use market_royalty::OfferingQueryMsg;
 use std::ops::{Add, Mul, Sub};
 
-pub const AUCTION_STORAGE: &str = "auction";
 // const MAX_ROYALTY_PERCENT: u64 = 50;
 // pub const OFFERING_STORAGE: &str = "offering";
 pub const DEFAULT_AUCTION_BLOCK: u64 = 50000;
@@ -42,19 +47,15 @@ pub fn try_bid_nft(
     } = CONTRACT_INFO.load(deps.storage)?;
 
     // check if auction exists, when return StdError => it will show EOF while parsing a JSON value.
-    let mut off: Auction = deps
-        .querier
-        .query_wasm_smart(
-            get_storage_addr(deps.as_ref(), governance.clone(), AUCTION_STORAGE)?,
-            &ProxyQueryMsg::Auction(AuctionQueryMsg::GetAuctionRaw { auction_id })
-                as &ProxyQueryMsg,
-        )
+    let mut off = governance
+        .get_auction_raw(&deps.querier, auction_id)
         .map_err(|_op| ContractError::AuctionNotFound {})?;
+    verify_reserved_buyer(&off.reserved_for, &sender)?;
 
     let token_id = off.token_id.clone();
     let asset_info: AssetInfo = query_auction_payment_asset_info(
         deps.as_ref(),
-        governance.as_str(),
+        &governance,
         deps.api.addr_humanize(&off.contract_addr)?,
         token_id.as_str(),
     )?;
@@ -130,11 +131,7 @@ pub fn try_bid_nft(
         off.bidder = deps.api.addr_canonicalize(sender.as_str()).ok();
         off.price = amount;
         // push save message to auction_storage
-        cosmos_msgs.push(get_auction_handle_msg(
-            governance,
-            AUCTION_STORAGE,
-            AuctionExecuteMsg::UpdateAuction { auction: off },
-        )?);
+        cosmos_msgs.push(governance.update_auction(off)?);
     } else {
         return Err(ContractError::InvalidZeroAmount {});
     }
@@ -156,45 +153,146 @@ pub fn try_claim_winner(
     env: Env,
     auction_id: u64,
 ) -> Result<Response, ContractError> {
-    let ContractInfo {
-        fee,
-        governance,
-        decimal_point,
-        ..
-    } = CONTRACT_INFO.load(deps.storage)?;
+    let contract_info = CONTRACT_INFO.load(deps.storage)?;
 
     // check if auction exists
-    let off: Auction = deps
-        .querier
-        .query_wasm_smart(
-            get_storage_addr(deps.as_ref(), governance.clone(), AUCTION_STORAGE)?,
-            &ProxyQueryMsg::Auction(AuctionQueryMsg::GetAuctionRaw { auction_id })
-                as &ProxyQueryMsg,
-        )
+    let off = contract_info
+        .governance
+        .get_auction_raw(&deps.querier, auction_id)
         .map_err(|_op| ContractError::AuctionNotFound {})?;
 
     // check is auction finished
+    if !is_auction_finished(&off, &env) {
+        return Err(ContractError::AuctionNotFinished {});
+    }
+
+    // get royalties
+    let mut rsp = Response::default();
+    rsp.attributes.extend(vec![attr("action", "claim_winner")]);
+
+    let token_id = off.token_id.clone();
+    let total_price = off.price;
+    let mut cosmos_msgs = vec![];
+    let mut storage_updates = vec![];
+    claim_auction(
+        deps,
+        &env,
+        &contract_info,
+        auction_id,
+        off,
+        &mut cosmos_msgs,
+        &mut storage_updates,
+        &mut rsp,
+    )?;
+
+    add_storage_updates(&contract_info.governance, &mut cosmos_msgs, storage_updates)?;
+    rsp = rsp.add_messages(cosmos_msgs);
+    rsp.attributes.extend(vec![
+        attr("claimer", info.sender),
+        attr("token_id", token_id),
+        attr("auction_id", auction_id.to_string()),
+        attr("total_price", total_price),
+        attr("royalty", "true"),
+    ]);
+
+    Ok(rsp)
+}
+
+/// batch version of claim winner for keepers. auctions that are not found, already claimed
+/// or not finished yet are skipped, so one stale id does not revert the whole batch
+pub fn try_claim_winners(
+    mut deps: DepsMut,
+    info: MessageInfo,
+    env: Env,
+    auction_ids: Vec<u64>,
+) -> Result<Response, ContractError> {
+    let contract_info = CONTRACT_INFO.load(deps.storage)?;
+
+    let mut rsp = Response::default();
+    rsp.attributes.extend(vec![attr("action", "claim_winners")]);
+
+    let mut cosmos_msgs = vec![];
+    let mut storage_updates = vec![];
+    let mut claimed: Vec<u64> = vec![];
+    let mut skipped: Vec<u64> = vec![];
+    for auction_id in auction_ids {
+        // the auction is only removed once the storage messages run, so a duplicated id
+        // would be paid out twice
+        if claimed.contains(&auction_id) || skipped.contains(&auction_id) {
+            continue;
+        }
+        match contract_info
+            .governance
+            .get_auction_raw(&deps.querier, auction_id)
+        {
+            Ok(off) if is_auction_finished(&off, &env) => {
+                claim_auction(
+                    deps.branch(),
+                    &env,
+                    &contract_info,
+                    auction_id,
+                    off,
+                    &mut cosmos_msgs,
+                    &mut storage_updates,
+                    &mut rsp,
+                )?;
+                claimed.push(auction_id);
+            }
+            _ => skipped.push(auction_id),
+        }
+    }
+
+    add_storage_updates(&contract_info.governance, &mut cosmos_msgs, storage_updates)?;
+    rsp = rsp.add_messages(cosmos_msgs);
+    rsp.attributes.extend(vec![
+        attr("claimer", info.sender),
+        attr("claimed", join_ids(&claimed)),
+        attr("skipped", join_ids(&skipped)),
+    ]);
+
+    Ok(rsp)
+}
+
+fn join_ids(ids: &[u64]) -> String {
+    ids.iter()
+        .map(|id| id.to_string())
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+/// an auction can be claimed once it has ended or its buyout price is reached
+fn is_auction_finished(off: &Auction, env: &Env) -> bool {
     if off
         .end_timestamp
         .gt(&Uint128::from(env.block.time.seconds()))
     {
         if let Some(buyout_price) = off.buyout_price {
-            if off.price.lt(&buyout_price) {
-                return Err(ContractError::AuctionNotFinished {});
-            }
-        } else {
-            return Err(ContractError::AuctionNotFinished {});
+            return off.price.ge(&buyout_price);
         }
+        return false;
     }
+    true
+}
 
-    // get royalties
-    let mut rsp = Response::default();
-    rsp.attributes.extend(vec![attr("action", "claim_winner")]);
+/// pays the asker and royalties, hands the nft to the winner and removes the auction
+#[allow(clippy::too_many_arguments)]
+fn claim_auction(
+    deps: DepsMut,
+    env: &Env,
+    contract_info: &ContractInfo,
+    auction_id: u64,
+    off: Auction,
+    cosmos_msgs: &mut Vec<CosmosMsg>,
+    storage_updates: &mut Vec<StorageUpdate>,
+    rsp: &mut Response,
+) -> Result<(), ContractError> {
+    let ContractInfo {
+        fee, governance, ..
+    } = contract_info.clone();
 
     let asker_addr = deps.api.addr_humanize(&off.asker)?;
     let contract_addr = deps.api.addr_humanize(&off.contract_addr)?;
     let token_id = off.token_id;
-    let mut cosmos_msgs = vec![];
     if let Some(bidder) = off.bidder {
         let bidder_addr = deps.api.addr_humanize(&bidder)?;
 
@@ -210,9 +308,15 @@ pub fn try_claim_winner(
             }
             .into(),
         );
+        storage_updates.push(get_expire_royalties_update(
+            &governance,
+            &contract_addr,
+            &token_id,
+        )?);
 
         let mut fund_amount = off.price;
-        // minus market fees
+        // minus market fees, discounted for loyal users
+        let fee = get_effective_fee(deps.as_ref(), fee, &bidder_addr, &asker_addr)?;
         let fee_amount = off.price.mul(Decimal::permille(fee));
 
         MARKET_FEES.update(deps.storage, |current_fees| -> StdResult<_> {
@@ -224,56 +328,60 @@ pub fn try_claim_winner(
 
         let asset_info: AssetInfo = query_auction_payment_asset_info(
             deps.as_ref(),
-            governance.as_str(),
+            &governance,
             deps.api.addr_humanize(&off.contract_addr)?,
             token_id.as_str(),
         )?;
 
-        let mut offering_royalty: OfferingRoyalty = deps
-            .querier
-            .query_wasm_smart(
-                get_storage_addr(deps.as_ref(), governance.clone(), OFFERING_STORAGE)?,
-                &ProxyQueryMsg::Offering(OfferingQueryMsg::GetOfferingRoyaltyByContractTokenId {
-                    contract: deps.api.addr_humanize(&off.contract_addr)?,
-                    token_id: token_id.clone(),
-                }) as &ProxyQueryMsg,
+        let mut offering_royalty = governance
+            .get_offering_royalty_by_contract_token_id(
+                &deps.querier,
+                deps.api.addr_humanize(&off.contract_addr)?,
+                &token_id,
             )
             .map_err(|_| ContractError::InvalidGetOfferingRoyalty {})?;
 
-        // pay for creator, ai provider and others
-        if let Ok(mut royalties) = get_royalties(deps.as_ref(), contract_addr.as_str(), &token_id) {
-            // payout for the previous owner
-            if offering_royalty.previous_owner.is_some() && offering_royalty.prev_royalty.is_some()
-            {
-                royalties.push(Royalty {
-                    contract_addr: offering_royalty.contract_addr.clone(),
-                    token_id: offering_royalty.token_id.clone(),
-                    creator: offering_royalty.previous_owner.unwrap(),
-                    royalty: offering_royalty.prev_royalty.unwrap(),
-                    creator_type: "previous_owner".into(),
-                })
-            }
+        let provenance = get_provenance(deps.as_ref(), &contract_info, &contract_addr, &token_id)?;
 
-            pay_royalties(
+        // pay for creator, ai provider and others
+        if let Some(royalties) = get_sale_royalties(
+            deps.as_ref(),
+            &contract_info,
+            &contract_addr,
+            &token_id,
+            &asker_addr,
+            provenance.as_ref(),
+            Some(&offering_royalty),
+        ) {
+            settle_royalties(
+                RoyaltySettlement {
+                    contract_info,
+                    querier: &deps.querier,
+                    price: remaining_for_royalties,
+                    contract_addr: env.contract.address.as_str(),
+                    asset_info: asset_info.clone(),
+                },
                 &royalties,
-                &remaining_for_royalties,
-                decimal_point,
                 &mut fund_amount,
-                &mut cosmos_msgs,
-                &mut rsp,
-                env.contract.address.as_str(),
-                &to_json_binary(&asset_info)?.to_base64(),
-                asset_info.clone(),
+                cosmos_msgs,
+                storage_updates,
+                rsp,
             )?;
         }
 
+        if let Some(provenance) = provenance {
+            storage_updates.push(get_provenance_update(
+                &contract_info,
+                provenance,
+                asker_addr.clone(),
+            )?);
+        }
+
         // update offering royalty result, current royalty info now turns to prev
         offering_royalty.prev_royalty = offering_royalty.cur_royalty;
         offering_royalty.previous_owner = Some(offering_royalty.current_owner.clone());
         offering_royalty.current_owner = bidder_addr; // new owner will become the bidder
-        cosmos_msgs.push(get_offering_handle_msg(
-            governance.clone(),
-            OFFERING_STORAGE,
+        storage_updates.push(governance.offering_update(
             OfferingExecuteMsg::UpdateOfferingRoyalty {
                 offering: offering_royalty.clone(),
             },
@@ -314,22 +422,10 @@ pub fn try_claim_winner(
     }
 
     // push save message to auction_storage
-    cosmos_msgs.push(get_auction_handle_msg(
-        governance,
-        AUCTION_STORAGE,
-        AuctionExecuteMsg::RemoveAuction { id: auction_id },
-    )?);
+    storage_updates
+        .push(governance.auction_update(AuctionExecuteMsg::RemoveAuction { id: auction_id })?);
 
-    rsp = rsp.add_messages(cosmos_msgs);
-    rsp.attributes.extend(vec![
-        attr("claimer", info.sender),
-        attr("token_id", token_id),
-        attr("auction_id", auction_id.to_string()),
-        attr("total_price", off.price),
-        attr("royalty", "true"),
-    ]);
-
-    Ok(rsp)
+    Ok(())
 }
 
 pub fn try_handle_ask_aution(
@@ -347,6 +443,7 @@ pub fn try_handle_ask_aution(
     buyout_price: Option<Uint128>,
     step_price: Option<u64>,
     royalty: Option<u64>,
+    reserved_for: Option<Vec<Addr>>,
 ) -> Result<Response, ContractError> {
     let ContractInfo {
         auction_duration,
@@ -361,10 +458,11 @@ pub fn try_handle_ask_aution(
 
     verify_nft(
         deps.as_ref(),
-        governance.as_str(),
+        &governance,
         contract_addr.as_str(),
         &token_id,
         info.sender.as_str(),
+        Some((ListingType::Auction, price, &asset_info)),
     )?;
 
     // get Auctions count
@@ -430,6 +528,7 @@ pub fn try_handle_ask_aution(
         start_timestamp,
         end_timestamp,
         step_price: step_price.unwrap_or(default_step_price),
+        reserved_for: validate_reserved_for(deps.api, reserved_for)?,
     };
 
     // add first level royalty
@@ -439,16 +538,8 @@ pub fn try_handle_ask_aution(
         "royalty",
     )?);
 
-    let mut offering_royalty_result: OfferingRoyalty = deps
-        .querier
-        .query_wasm_smart(
-            get_storage_addr(deps.as_ref(), governance.clone(), OFFERING_STORAGE)?,
-            &ProxyQueryMsg::Offering(OfferingQueryMsg::GetOfferingRoyaltyByContractTokenId {
-                contract: contract_addr.clone(),
-                token_id: token_id.clone(),
-            }) as &ProxyQueryMsg,
-        )
-        .map_err(|_| ContractError::InvalidGetOfferingRoyalty {})
+    let mut offering_royalty_result = governance
+        .get_offering_royalty_by_contract_token_id(&deps.querier, contract_addr.clone(), &token_id)
         .unwrap_or(OfferingRoyalty {
             token_id: token_id.clone(),
             contract_addr: contract_addr.clone(),
@@ -463,32 +554,21 @@ pub fn try_handle_ask_aution(
     // add new auctions
     let mut cosmos_msgs = vec![];
     // push save message to auction_storage
-    cosmos_msgs.push(get_auction_handle_msg(
-        governance.clone(),
-        AUCTION_STORAGE,
-        AuctionExecuteMsg::UpdateAuction { auction: off },
-    )?);
+    cosmos_msgs.push(governance.update_auction(off)?);
 
     // push save message to market payment storage
-    cosmos_msgs.push(get_handle_msg(
-        governance.as_str(),
-        PAYMENT_STORAGE,
-        PaymentExecuteMsg::UpdateAuctionPayment(Payment {
+    cosmos_msgs.push(governance.update_auction_payment(
+        PAYMENT_721_STORAGE,
+        Payment {
             contract_addr,
             token_id: token_id.clone(),
             sender: None, // for 721, contract & token id combined is already unique
             asset_info: asset_info.clone(),
-        }),
-    )?);
-
-    cosmos_msgs.push(get_offering_handle_msg(
-        governance.clone(),
-        OFFERING_STORAGE,
-        OfferingExecuteMsg::UpdateOfferingRoyalty {
-            offering: offering_royalty_result.clone(),
         },
     )?);
 
+    cosmos_msgs.push(governance.update_offering_royalty(offering_royalty_result.clone())?);
+
     Ok(Response::new()
         .add_messages(cosmos_msgs)
         .add_attributes(vec![
@@ -510,19 +590,14 @@ pub fn try_cancel_bid(
     let ContractInfo { governance, .. } = CONTRACT_INFO.load(deps.storage)?;
 
     // check if auction exists
-    let mut off: Auction = deps
-        .querier
-        .query_wasm_smart(
-            get_storage_addr(deps.as_ref(), governance.clone(), AUCTION_STORAGE)?,
-            &ProxyQueryMsg::Auction(AuctionQueryMsg::GetAuctionRaw { auction_id })
-                as &ProxyQueryMsg,
-        )
+    let mut off = governance
+        .get_auction_raw(&deps.querier, auction_id)
         .map_err(|_op| ContractError::AuctionNotFound {})?;
 
     let token_id = off.token_id.clone();
     let asset_info: AssetInfo = query_auction_payment_asset_info(
         deps.as_ref(),
-        governance.as_str(),
+        &governance,
         deps.api.addr_humanize(&off.contract_addr)?,
         token_id.as_str(),
     )?;
@@ -565,11 +640,7 @@ pub fn try_cancel_bid(
             off.bidder = None;
             off.price = off.orig_price;
             // push save message to auction_storage
-            cosmos_msgs.push(get_auction_handle_msg(
-                governance,
-                AUCTION_STORAGE,
-                AuctionExecuteMsg::UpdateAuction { auction: off },
-            )?);
+            cosmos_msgs.push(governance.update_auction(off)?);
 
             return Ok(Response::new()
                 .add_messages(cosmos_msgs)
@@ -605,13 +676,8 @@ pub fn try_emergency_cancel_auction(
     } = CONTRACT_INFO.load(deps.storage)?;
 
     // check if auction exists
-    let off: Auction = deps
-        .querier
-        .query_wasm_smart(
-            get_storage_addr(deps.as_ref(), governance.clone(), AUCTION_STORAGE)?,
-            &ProxyQueryMsg::Auction(AuctionQueryMsg::GetAuctionRaw { auction_id })
-                as &ProxyQueryMsg,
-        )
+    let off = governance
+        .get_auction_raw(&deps.querier, auction_id)
         .map_err(|_op| ContractError::AuctionNotFound {})?;
 
     //let asker_addr = deps.api.addr_humanize(&off.asker)?;
@@ -627,7 +693,7 @@ pub fn try_emergency_cancel_auction(
     let token_id = off.token_id;
     let asset_info: AssetInfo = query_auction_payment_asset_info(
         deps.as_ref(),
-        governance.as_str(),
+        &governance,
         deps.api.addr_humanize(&off.contract_addr)?,
         token_id.as_str(),
     )?;
@@ -668,11 +734,7 @@ pub fn try_emergency_cancel_auction(
 
     // remove auction
     // push save message to auction_storage
-    cosmos_msgs.push(get_auction_handle_msg(
-        governance,
-        AUCTION_STORAGE,
-        AuctionExecuteMsg::RemoveAuction { id: auction_id },
-    )?);
+    cosmos_msgs.push(governance.remove_auction(auction_id)?);
 
     return Ok(Response::new()
         .add_messages(cosmos_msgs)
@@ -684,32 +746,9 @@ pub fn try_emergency_cancel_auction(
         ]));
 }
 
-pub fn get_auction_handle_msg(
-    addr: Addr,
-    name: &str,
-    msg: AuctionExecuteMsg,
-) -> StdResult<CosmosMsg> {
-    let msg_auction: ProxyExecuteMsg<AuctionExecuteMsg> = ProxyExecuteMsg::Auction(msg);
-    let auction_msg = to_json_binary(&msg_auction)?;
-    let proxy_msg: ProxyExecuteMsg<StorageExecuteMsg> =
-        ProxyExecuteMsg::Storage(StorageExecuteMsg::UpdateStorageData {
-            name: name.to_string(),
-            msg: auction_msg,
-        });
-
-    Ok(WasmMsg::Execute {
-        contract_addr: addr.to_string(),
-        msg: to_json_binary(&proxy_msg)?,
-        funds: vec![],
-    }
-    .into())
-}
-
 pub fn query_auction(deps: Deps, msg: AuctionQueryMsg) -> StdResult<Binary> {
     let contract_info = CONTRACT_INFO.load(deps.storage)?;
-    query_proxy(
-        deps,
-        get_storage_addr(deps, contract_info.governance, AUCTION_STORAGE)?,
-        to_json_binary(&ProxyQueryMsg::Auction(msg) as &ProxyQueryMsg)?,
-    )
+    contract_info
+        .governance
+        .query_auction_binary(&deps.querier, msg)
 }
@@ -1,50 +1,52 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 
-use std::fmt;
-
 use crate::ai_royalty::{
-    query_ai_royalty, query_first_level_royalty, try_update_royalties, try_update_royalty_creator,
+    query_ai_royalty, query_first_level_royalty, query_provenance_royalties, try_claim_royalties,
+    try_update_royalties, try_update_royalty_creator, try_update_royalty_policy,
 };
 // use crate::ai_royalty::try_update_royalties;
 use crate::auction::{
-    query_auction, try_bid_nft, try_cancel_bid, try_claim_winner, try_emergency_cancel_auction,
-    try_handle_ask_aution, AUCTION_STORAGE,
+    query_auction, try_bid_nft, try_cancel_bid, try_claim_winner, try_claim_winners,
+    try_emergency_cancel_auction, try_handle_ask_aution,
 };
 
+use crate::fee_discount::{query_effective_fee, query_fee_discount, try_update_fee_discount};
 use crate::offering::{
-    query_offering, try_buy, try_handle_mint, try_handle_sell_nft, try_withdraw, OFFERING_STORAGE,
+    query_offering, query_simulate_payout, try_buy, try_handle_mint, try_handle_sell_nft,
+    try_withdraw,
+};
+use crate::order::{
+    get_order_nonce, query_order_hash, try_cancel_order, try_fulfill_order,
+    try_increase_order_nonce, verify_order_native, verify_order_token,
 };
 
 use crate::error::ContractError;
-use crate::msg::{
-    ExecuteMsg, GiftNft, InstantiateMsg, MigrateMsg, ProxyExecuteMsg, ProxyQueryMsg, QueryMsg,
-    UpdateContractMsg,
-};
-use crate::state::{ContractInfo, CONTRACT_INFO, MARKET_FEES};
+use crate::msg::{ExecuteMsg, GiftNft, InstantiateMsg, MigrateMsg, QueryMsg, UpdateContractMsg};
+use crate::state::{ContractInfo, CONTRACT_INFO, CONTRACT_INFO_KEY, MARKET_FEES};
 use cosmwasm_std::{
-    attr, to_json_binary, BankMsg, Binary, Coin, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo,
+    attr, to_json_binary, BankMsg, Binary, Coin, CosmosMsg, Deps, DepsMut, Env, MessageInfo,
     Response, StdError, StdResult, Uint128, WasmMsg,
 };
 use cosmwasm_std::{from_json, Addr};
+use cw2::set_contract_version;
 use cw20::Cw20ReceiveMsg;
 use cw721::{Cw721ExecuteMsg, Cw721QueryMsg, OwnerOfResponse};
-use market::{parse_token_id, AssetInfo, Funds, StorageExecuteMsg, StorageQueryMsg, TokenInfo};
-use market_ai_royalty::sanitize_royalty;
-use market_auction::{AuctionQueryMsg, QueryAuctionsResult};
-use market_payment::PaymentQueryMsg;
-use market_royalty::{Cw20HookMsg, ExtraData, OfferingQueryMsg, QueryOfferingsResult};
-use market_whitelist::{IsApprovedForAllResponse, MarketWhiteListdQueryMsg};
-use schemars::JsonSchema;
-use serde::Serialize;
+use market::{
+    parse_token_id, AssetInfo, Funds, MarketHubContract, StorageUpdate, TokenInfo,
+    PAYMENT_721_STORAGE,
+};
+use market_ai_royalty::{sanitize_royalty, AiRoyaltyStorage};
+use market_auction::AuctionStorage;
+use market_payment::PaymentStorage;
+use market_royalty::{Cw20HookMsg, ExtraData, OfferingStorage};
+use market_whitelist::{ListingType, WhitelistStorage};
+use migration::{migrate_version, move_item, MigrationStep, VERSION_KEY};
 
 pub const MAX_ROYALTY_PERCENT: u64 = 1_000_000_000;
 pub const MAX_DECIMAL_POINT: u64 = 1_000_000_000;
 pub const MAX_FEE_PERMILLE: u64 = 1000;
 pub const CREATOR_NAME: &str = "creator";
-pub const FIRST_LV_ROYALTY_STORAGE: &str = "first_lv_royalty";
-pub const WHITELIST_STORAGE: &str = "whitelist_storage";
-pub const PAYMENT_STORAGE: &str = "market_721_payment_storage";
 
 fn sanitize_fee(fee: u64, limit: u64, name: &str) -> Result<u64, ContractError> {
     if fee > limit {
@@ -55,6 +57,10 @@ fn sanitize_fee(fee: u64, limit: u64, name: &str) -> Result<u64, ContractError>
     Ok(fee)
 }
 
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:market_implementation";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
 // Note, you can use StdResult in some functions where you do not
 // make use of the custom errors
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -64,6 +70,7 @@ pub fn instantiate(
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     let info = ContractInfo {
         name: msg.name,
         creator: info.sender.to_string(),
@@ -71,9 +78,11 @@ pub fn instantiate(
         fee: sanitize_fee(msg.fee, MAX_FEE_PERMILLE, "fee")?,
         auction_duration: msg.auction_duration,
         step_price: msg.step_price,
-        governance: msg.governance,
+        governance: MarketHubContract(msg.governance),
         max_royalty: sanitize_royalty(msg.max_royalty, MAX_ROYALTY_PERCENT, "max_royalty")?,
         decimal_point: msg.max_decimal_point,
+        provenance_royalties: vec![],
+        accrue_royalties: false,
     };
     CONTRACT_INFO.save(deps.storage, &info)?;
     MARKET_FEES.save(deps.storage, &Uint128::zero())?;
@@ -100,6 +109,7 @@ pub fn execute(
             // Some(info.funds),
         ),
         ExecuteMsg::ClaimWinner { auction_id } => try_claim_winner(deps, info, env, auction_id),
+        ExecuteMsg::ClaimWinners { auction_ids } => try_claim_winners(deps, info, env, auction_ids),
         // ExecuteMsg::WithdrawNft { auction_id } => try_withdraw_nft(deps, info, env, auction_id),
         ExecuteMsg::EmergencyCancelAuction { auction_id } => {
             try_emergency_cancel_auction(deps, info, env, auction_id)
@@ -116,6 +126,7 @@ pub fn execute(
             cancel_fee,
             royalty,
             step_price,
+            reserved_for,
         } => try_handle_ask_aution(
             deps,
             info,
@@ -131,13 +142,24 @@ pub fn execute(
             buyout_price,
             step_price,
             royalty,
+            reserved_for,
         ),
         ExecuteMsg::SellNft {
             contract_addr,
             token_id,
             royalty,
             off_price,
-        } => try_handle_sell_nft(deps, env, info, contract_addr, token_id, off_price, royalty),
+            reserved_for,
+        } => try_handle_sell_nft(
+            deps,
+            env,
+            info,
+            contract_addr,
+            token_id,
+            off_price,
+            royalty,
+            reserved_for,
+        ),
         ExecuteMsg::CancelBid { auction_id } => try_cancel_bid(deps, info, env, auction_id),
         ExecuteMsg::WithdrawFunds { funds } => try_withdraw_funds(deps, info, env, funds),
         ExecuteMsg::UpdateInfo(msg) => try_update_info(deps, info, env, msg),
@@ -168,11 +190,28 @@ pub fn execute(
             try_update_royalty_creator(deps, info, royalty_msg)
         }
         ExecuteMsg::UpdateRoyalties { royalty } => try_update_royalties(deps, info, env, royalty),
+        ExecuteMsg::ClaimRoyalties {} => try_claim_royalties(deps, info),
+        ExecuteMsg::UpdateRoyaltyPolicy(policy) => try_update_royalty_policy(deps, info, policy),
         ExecuteMsg::ApproveAll {
             contract_addr,
             operator,
         } => try_approve_all(deps, info, contract_addr, operator),
         ExecuteMsg::TransferNftDirectly(gift_msg) => handle_transfer_nft(deps, info, gift_msg),
+        ExecuteMsg::UpdateFeeDiscount(fee_discount) => {
+            try_update_fee_discount(deps, info, fee_discount)
+        }
+        ExecuteMsg::FulfillOrder(signed_order) => {
+            verify_order_native(&signed_order)?;
+            try_fulfill_order(
+                deps,
+                info.sender,
+                env,
+                signed_order,
+                Funds::Native { fund: info.funds },
+            )
+        }
+        ExecuteMsg::CancelOrder { order_hash } => try_cancel_order(deps, info, order_hash),
+        ExecuteMsg::IncreaseOrderNonce {} => try_increase_order_nonce(deps, info),
     }
 }
 
@@ -187,20 +226,39 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::Offering(offering_msg) => query_offering(deps, offering_msg),
         QueryMsg::AiRoyalty(ai_royalty_msg) => query_ai_royalty(deps, ai_royalty_msg),
         QueryMsg::FirstLvRoyalty(first_lv_msg) => query_first_level_royalty(deps, first_lv_msg),
+        QueryMsg::GetFeeDiscount {} => to_json_binary(&query_fee_discount(deps)?),
+        QueryMsg::GetEffectiveFee { address } => {
+            to_json_binary(&query_effective_fee(deps, address)?)
+        }
+        QueryMsg::GetOrderNonce { seller } => to_json_binary(&get_order_nonce(deps, &seller)?),
+        QueryMsg::GetOrderHash { order } => to_json_binary(&query_order_hash(order)?),
+        QueryMsg::GetProvenanceRoyalties { contract, token_id } => {
+            to_json_binary(&query_provenance_royalties(deps, contract, token_id)?)
+        }
+        QueryMsg::SimulatePayout(msg) => to_json_binary(&query_simulate_payout(deps, msg)?),
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
-    // MARKET_FEES.save(deps.storage, &Uint128::zero())?;
-    Ok(Response::default())
+pub fn migrate(deps: DepsMut, env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+    migrate_version(deps, &env, CONTRACT_NAME, CONTRACT_VERSION, MIGRATIONS)
+}
+
+const MIGRATIONS: &[MigrationStep] = &[MigrationStep {
+    version: "0.2.0",
+    name: "move_contract_info",
+    run: move_contract_info,
+}];
+
+fn move_contract_info(deps: DepsMut, _env: &Env) -> StdResult<()> {
+    move_item(deps.storage, VERSION_KEY, CONTRACT_INFO_KEY)
 }
 
 // ============================== Message Handlers ==============================
 
 pub fn try_receive_cw20(
     deps: DepsMut,
-    _info: MessageInfo,
+    info: MessageInfo,
     env: Env,
     cw20_msg: Cw20ReceiveMsg,
 ) -> Result<Response, ContractError> {
@@ -225,6 +283,18 @@ pub fn try_receive_cw20(
                 fund: cw20_msg.amount,
             },
         ),
+        Ok(Cw20HookMsg::FulfillOrder(signed_order)) => {
+            verify_order_token(&info, &signed_order)?;
+            try_fulfill_order(
+                deps,
+                Addr::unchecked(cw20_msg.sender),
+                env,
+                signed_order,
+                Funds::Cw20 {
+                    fund: cw20_msg.amount,
+                },
+            )
+        }
         Err(_) => Err(ContractError::Std(StdError::generic_err(
             "invalid cw20 hook message",
         ))),
@@ -283,7 +353,7 @@ pub fn try_update_info(
             contract_info.step_price = step_price
         }
         if let Some(governance) = msg.governance {
-            contract_info.governance = governance;
+            contract_info.governance = MarketHubContract(governance);
         }
         if let Some(decimal_point) = msg.decimal_point {
             contract_info.decimal_point = decimal_point;
@@ -291,6 +361,30 @@ pub fn try_update_info(
         if let Some(max_royalty) = msg.max_royalty {
             contract_info.max_royalty = max_royalty;
         }
+        if let Some(provenance_royalties) = msg.provenance_royalties {
+            contract_info.provenance_royalties = provenance_royalties;
+        }
+        if let Some(accrue_royalties) = msg.accrue_royalties {
+            contract_info.accrue_royalties = accrue_royalties;
+        }
+        // royalties must decay as they go back through the previous owners
+        if contract_info
+            .provenance_royalties
+            .windows(2)
+            .any(|pair| pair[1] >= pair[0])
+        {
+            return Err(ContractError::InvalidArgument {
+                arg: "provenance_royalties".to_string(),
+            });
+        }
+        let total = contract_info
+            .provenance_royalties
+            .iter()
+            .try_fold(0u64, |total, royalty| total.checked_add(*royalty))
+            .ok_or_else(|| ContractError::InvalidArgument {
+                arg: "provenance_royalties".to_string(),
+            })?;
+        sanitize_royalty(total, contract_info.max_royalty, "provenance_royalties")?;
         Ok(contract_info)
     })?;
 
@@ -385,10 +479,11 @@ pub fn handle_transfer_nft(
 
     verify_nft(
         deps.as_ref(),
-        &governance.as_str(),
+        &governance,
         &contract_addr.as_str(),
         &token_id,
         &info.sender.as_str(),
+        None,
     )?;
 
     let mut cw721_transfer_cosmos_msg: Vec<CosmosMsg> = vec![];
@@ -404,6 +499,7 @@ pub fn handle_transfer_nft(
     }
     .into();
     cw721_transfer_cosmos_msg.push(exec_cw721_transfer);
+    cw721_transfer_cosmos_msg.push(governance.expire_royalties(contract_addr.clone(), &token_id)?);
     Ok(Response::new()
         .add_messages(cw721_transfer_cosmos_msg)
         .add_attributes(vec![
@@ -480,54 +576,56 @@ pub fn verify_owner(
     }
 }
 
+/// listing is the listing type and price of a new listing, none when the nft is only transferred
 pub fn verify_nft(
     deps: Deps,
-    governance: &str,
+    governance: &MarketHubContract,
     contract_addr: &str,
     token_id: &str,
     sender: &str,
+    listing: Option<(ListingType, Uint128, &AssetInfo)>,
 ) -> Result<(), ContractError> {
     // verify ownership of token id
     verify_owner(deps, contract_addr, token_id, sender)?;
 
     // verify if the nft contract address is whitelisted. If not => reject
-    let is_approved: IsApprovedForAllResponse = deps.querier.query_wasm_smart(
-        get_storage_addr(deps, Addr::unchecked(governance), WHITELIST_STORAGE)?,
-        &ProxyQueryMsg::Msg(MarketWhiteListdQueryMsg::IsApprovedForAll {
-            nft_addr: contract_addr.to_string(),
-        }),
-    )?;
+    let is_approved = governance.is_approved_for_all(&deps.querier, contract_addr)?;
 
     if !is_approved.approved {
         return Err(ContractError::NotWhilteList {});
     }
 
+    // collections approved without the registry have no restrictions
+    if let (Some(collection), Some((listing_type, price, asset_info))) =
+        (is_approved.collection, listing)
+    {
+        if !collection.allows(listing_type) {
+            return Err(ContractError::ListingTypeNotAllowed {
+                listing_type: listing_type.to_string(),
+            });
+        }
+        // the minimum only holds for listings paid in its asset
+        if let Some(min_price) = collection.min_price(asset_info) {
+            if price.lt(&min_price) {
+                return Err(ContractError::PriceBelowCollectionMin { min_price });
+            }
+        }
+    }
+
     // check if offering exists
-    let offering_result: Result<QueryOfferingsResult, ContractError> = deps
-        .querier
-        .query_wasm_smart(
-            get_storage_addr(deps, Addr::unchecked(governance), OFFERING_STORAGE)?,
-            &ProxyQueryMsg::Offering(OfferingQueryMsg::GetOfferingByContractTokenId {
-                contract: Addr::unchecked(contract_addr),
-                token_id: token_id.to_string(),
-            }) as &ProxyQueryMsg,
-        )
-        .map_err(|_| ContractError::InvalidGetOffering {});
+    let offering_result = governance.get_offering_by_contract_token_id(
+        &deps.querier,
+        Addr::unchecked(contract_addr),
+        token_id,
+    );
 
     if offering_result.is_ok() {
         return Err(ContractError::TokenOnSale {});
     }
 
     // check if auction exists
-    let auction: Option<QueryAuctionsResult> = deps
-        .querier
-        .query_wasm_smart(
-            get_storage_addr(deps, Addr::unchecked(governance), AUCTION_STORAGE)?,
-            &ProxyQueryMsg::Auction(AuctionQueryMsg::GetAuctionByContractTokenId {
-                contract: Addr::unchecked(contract_addr),
-                token_id: token_id.to_string(),
-            }) as &ProxyQueryMsg,
-        )
+    let auction = governance
+        .get_auction_by_contract_token_id(&deps.querier, Addr::unchecked(contract_addr), token_id)
         .ok();
 
     if auction.is_some() {
@@ -569,38 +667,24 @@ pub fn parse_asset_info(extra_data: ExtraData) -> AssetInfo {
     };
 }
 
-pub fn verify_funds(
-    // native_funds: Option<&[Coin]>,
-    // token_funds: Option<Uint128>,
-    funds: &Funds,
-    asset_info: AssetInfo,
-    price: &Uint128,
-) -> StdResult<()> {
-    let final_funds = match funds {
-        Funds::Native { fund } => fund.clone(),
-        Funds::Cw20 { fund } => vec![Coin {
-            denom: "placeholder".into(),
-            amount: *fund,
-        }],
-    };
-    match asset_info {
-        AssetInfo::NativeToken { denom } => {
-            return verify_native_funds(&final_funds, &denom, price);
+pub fn verify_funds(funds: &Funds, asset_info: AssetInfo, price: &Uint128) -> StdResult<()> {
+    match (asset_info, funds) {
+        (AssetInfo::NativeToken { denom }, Funds::Native { fund }) => {
+            verify_native_funds(fund, &denom, price)
         }
-        AssetInfo::Token { contract_addr: _ } => {
-            if final_funds.first().is_none() {
-                return Err(StdError::generic_err(
-                    ContractError::InvalidSentFundAmount {}.to_string(),
-                ));
-            }
-            if final_funds.first().unwrap().amount.lt(price) {
+        // only the cw20 hook sends Funds::Cw20, the token itself is checked by the caller
+        (AssetInfo::Token { .. }, Funds::Cw20 { fund }) => {
+            if fund.lt(price) {
                 return Err(StdError::generic_err(
                     ContractError::InsufficientFunds {}.to_string(),
                 ));
             }
-            return Ok(());
+            Ok(())
         }
-    };
+        _ => Err(StdError::generic_err(
+            ContractError::InvalidSentFundAmount {}.to_string(),
+        )),
+    }
 }
 
 pub fn get_asset_info(token_id: &str, default_denom: &str) -> StdResult<(AssetInfo, String)> {
@@ -626,63 +710,45 @@ pub fn query_market_fees(deps: Deps) -> StdResult<Uint128> {
 
 pub fn query_offering_payment_asset_info(
     deps: Deps,
-    governance: &str,
+    governance: &MarketHubContract,
     contract_addr: Addr,
     token_id: &str,
 ) -> StdResult<AssetInfo> {
     // collect payment type
-    Ok(deps.querier.query_wasm_smart(
-        get_storage_addr(deps, Addr::unchecked(governance), PAYMENT_STORAGE)?,
-        &ProxyQueryMsg::Msg(PaymentQueryMsg::GetOfferingPayment {
-            contract_addr,
-            token_id: token_id.into(),
-            sender: None,
-        }),
-    )?)
+    governance.get_offering_payment(
+        &deps.querier,
+        PAYMENT_721_STORAGE,
+        contract_addr,
+        token_id,
+        None,
+    )
 }
 
 pub fn query_auction_payment_asset_info(
     deps: Deps,
-    governance: &str,
+    governance: &MarketHubContract,
     contract_addr: Addr,
     token_id: &str,
 ) -> StdResult<AssetInfo> {
     // collect payment type
-    Ok(deps.querier.query_wasm_smart(
-        get_storage_addr(deps, Addr::unchecked(governance), PAYMENT_STORAGE)?,
-        &ProxyQueryMsg::Msg(PaymentQueryMsg::GetAuctionPayment {
-            contract_addr,
-            token_id: token_id.into(),
-            sender: None,
-        }),
-    )?)
-}
-
-// remove recursive by query storage_addr first, then call query_proxy
-pub fn get_storage_addr(deps: Deps, contract: Addr, name: &str) -> StdResult<Addr> {
-    deps.querier.query_wasm_smart(
-        contract,
-        &ProxyQueryMsg::Storage(StorageQueryMsg::QueryStorageAddr {
-            name: name.to_string(),
-        }) as &ProxyQueryMsg,
+    governance.get_auction_payment(
+        &deps.querier,
+        PAYMENT_721_STORAGE,
+        contract_addr,
+        token_id,
+        None,
     )
 }
 
-pub fn get_handle_msg<T>(addr: &str, name: &str, msg: T) -> StdResult<CosmosMsg>
-where
-    T: Clone + fmt::Debug + PartialEq + JsonSchema + Serialize,
-{
-    let msg = to_json_binary(&ProxyExecuteMsg::Msg(msg))?;
-    let proxy_msg: ProxyExecuteMsg<Empty> =
-        ProxyExecuteMsg::Storage(StorageExecuteMsg::UpdateStorageData {
-            name: name.to_string(),
-            msg,
-        });
-
-    Ok(WasmMsg::Execute {
-        contract_addr: addr.to_string(),
-        msg: to_json_binary(&proxy_msg)?,
-        funds: vec![],
+/// the storage updates of a settlement go to the hub in one batch after its transfers,
+/// so the hub validates them once and applies them atomically
+pub fn add_storage_updates(
+    governance: &MarketHubContract,
+    cosmos_msgs: &mut Vec<CosmosMsg>,
+    storage_updates: Vec<StorageUpdate>,
+) -> StdResult<()> {
+    if !storage_updates.is_empty() {
+        cosmos_msgs.push(governance.batch_update_storage(storage_updates)?);
     }
-    .into())
+    Ok(())
 }
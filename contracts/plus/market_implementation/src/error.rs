@@ -1,4 +1,5 @@
 use cosmwasm_std::{StdError, Uint128};
+use market::ReservedError;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -69,6 +70,12 @@ pub enum ContractError {
     #[error("Rejected data hub implementation. The nft contract is not whitelisted. Cannot use it on the marketplace")]
     NotWhilteList {},
 
+    #[error("The collection does not allow {listing_type} listings")]
+    ListingTypeNotAllowed { listing_type: String },
+
+    #[error("The price is below the collection minimum of {min_price}")]
+    PriceBelowCollectionMin { min_price: Uint128 },
+
     #[error("Expected bidder: {bidder}, got: {sender}")]
     InvalidBidder { bidder: String, sender: String },
 
@@ -92,6 +99,24 @@ pub enum ContractError {
 
     #[error("Token already been sold")]
     TokenOnSale {},
+
+    #[error("The order signature is invalid")]
+    InvalidSignature {},
+
+    #[error("The order has expired")]
+    OrderExpired {},
+
+    #[error("The order has been cancelled by the seller")]
+    OrderCancelled {},
+
+    #[error("The order has already been fulfilled")]
+    OrderFilled {},
+
+    #[error("{0}")]
+    Reserved(#[from] ReservedError),
+
+    #[error("There is no accrued royalty to claim")]
+    NoRoyaltiesToClaim {},
 }
 
 impl Into<String> for ContractError {
@@ -0,0 +1,128 @@
+use crate::contract::MAX_FEE_PERMILLE;
+use crate::error::ContractError;
+use crate::msg::{CollectionStakerInfo, EffectiveFeeResponse, NftStakingQueryMsg};
+use crate::state::{ContractInfo, FeeDiscount, CONTRACT_INFO, FEE_DISCOUNT};
+use cosmwasm_std::{attr, Addr, Deps, DepsMut, MessageInfo, Response, StdResult, Uint128};
+use cw20::{BalanceResponse, Cw20QueryMsg};
+
+// market_nft_staking caps the page size at 100
+const STAKING_PAGE_LIMIT: u8 = 100;
+
+pub fn try_update_fee_discount(
+    deps: DepsMut,
+    info: MessageInfo,
+    fee_discount: FeeDiscount,
+) -> Result<Response, ContractError> {
+    let ContractInfo { creator, .. } = CONTRACT_INFO.load(deps.storage)?;
+    if info.sender.ne(&Addr::unchecked(creator)) {
+        return Err(ContractError::Unauthorized {
+            sender: info.sender.to_string(),
+        });
+    }
+    if fee_discount
+        .tiers
+        .iter()
+        .any(|tier| tier.discount > MAX_FEE_PERMILLE)
+    {
+        return Err(ContractError::InvalidArgument {
+            arg: "discount".to_string(),
+        });
+    }
+    FEE_DISCOUNT.save(deps.storage, &fee_discount)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "update_fee_discount"),
+        attr("tiers", fee_discount.tiers.len().to_string()),
+    ]))
+}
+
+/// total nfts staked by the address across every collection of the staking contract
+fn query_total_staked(deps: Deps, staking_contract: &Addr, address: &Addr) -> StdResult<Uint128> {
+    let mut total_staked = Uint128::zero();
+    let mut offset: Option<u64> = None;
+    loop {
+        let staker_infos: Vec<CollectionStakerInfo> = deps.querier.query_wasm_smart(
+            staking_contract,
+            &NftStakingQueryMsg::GetCollectionStakerInfoByStaker {
+                staker_addr: address.clone(),
+                limit: Some(STAKING_PAGE_LIMIT),
+                offset,
+                order: Some(1),
+            },
+        )?;
+        for staker_info in staker_infos.iter() {
+            total_staked += staker_info.total_staked;
+        }
+        match staker_infos.last() {
+            Some(last) if staker_infos.len() == STAKING_PAGE_LIMIT as usize => offset = last.id,
+            _ => break,
+        }
+    }
+    Ok(total_staked)
+}
+
+/// returns the best permille discount reached by the address, zero if no tier matches
+pub fn get_fee_discount(deps: Deps, address: &Addr) -> StdResult<u64> {
+    let FeeDiscount {
+        staking_contract,
+        token,
+        tiers,
+    } = FEE_DISCOUNT.may_load(deps.storage)?.unwrap_or_default();
+    if tiers.is_empty() {
+        return Ok(0);
+    }
+
+    let total_staked = match &staking_contract {
+        Some(staking_contract) => query_total_staked(deps, staking_contract, address)?,
+        None => Uint128::zero(),
+    };
+    let balance = match &token {
+        Some(token) => {
+            let balance: BalanceResponse = deps.querier.query_wasm_smart(
+                token,
+                &Cw20QueryMsg::Balance {
+                    address: address.to_string(),
+                },
+            )?;
+            balance.balance
+        }
+        None => Uint128::zero(),
+    };
+
+    Ok(tiers
+        .iter()
+        .filter(|tier| {
+            let staked_ok = staking_contract.is_some()
+                && tier.min_staked.is_some_and(|min| total_staked.ge(&min));
+            let balance_ok =
+                token.is_some() && tier.min_balance.is_some_and(|min| balance.ge(&min));
+            staked_ok || balance_ok
+        })
+        .map(|tier| tier.discount)
+        .max()
+        .unwrap_or(0))
+}
+
+fn apply_discount(fee: u64, discount: u64) -> u64 {
+    fee - fee * discount.min(MAX_FEE_PERMILLE) / MAX_FEE_PERMILLE
+}
+
+/// market fee in permille for a trade between the two parties, the best discount of both applies
+pub fn get_effective_fee(deps: Deps, fee: u64, buyer: &Addr, seller: &Addr) -> StdResult<u64> {
+    let discount = get_fee_discount(deps, buyer)?.max(get_fee_discount(deps, seller)?);
+    Ok(apply_discount(fee, discount))
+}
+
+pub fn query_fee_discount(deps: Deps) -> StdResult<FeeDiscount> {
+    Ok(FEE_DISCOUNT.may_load(deps.storage)?.unwrap_or_default())
+}
+
+pub fn query_effective_fee(deps: Deps, address: Addr) -> StdResult<EffectiveFeeResponse> {
+    let ContractInfo { fee, .. } = CONTRACT_INFO.load(deps.storage)?;
+    let discount = get_fee_discount(deps, &address)?;
+    Ok(EffectiveFeeResponse {
+        fee,
+        discount,
+        effective_fee: apply_discount(fee, discount),
+    })
+}
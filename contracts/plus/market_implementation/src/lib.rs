@@ -1,8 +1,10 @@
 pub mod ai_royalty;
 pub mod auction;
 pub mod contract;
+pub mod fee_discount;
 pub mod msg;
 pub mod offering;
+pub mod order;
 pub mod state;
 
 mod error;
@@ -1,11 +1,17 @@
-use cosmwasm_std::{Coin, Empty, Addr, Uint128};
+use crate::state::{ContractInfo, FeeDiscount};
+use cosmwasm_schema::QueryResponses;
+use cosmwasm_std::{Addr, Binary, Coin, Empty, Uint128};
 use cw20::Cw20ReceiveMsg;
 use market::{StorageExecuteMsg, StorageQueryMsg};
-use market_ai_royalty::{AiRoyaltyQueryMsg, Royalty, RoyaltyMsg};
-use market_auction::{AuctionExecuteMsg, AuctionQueryMsg};
-use market_first_lv_royalty::FirstLvRoyaltyQueryMsg;
+use market_ai_royalty::{
+    AiRoyaltyQueryMsg, AiRoyaltyQueryResponse, Royalty, RoyaltyMsg, RoyaltyPayout, RoyaltyPolicy,
+};
+use market_auction::{AuctionExecuteMsg, AuctionQueryMsg, AuctionQueryResponse};
+use market_first_lv_royalty::{FirstLvRoyaltyQueryMsg, FirstLvRoyaltyQueryResponse};
 use market_payment::{PaymentExecuteMsg, PaymentQueryMsg};
-use market_royalty::{MintMsg, OfferingExecuteMsg, OfferingQueryMsg};
+use market_royalty::{
+    MintMsg, OfferingExecuteMsg, OfferingQueryMsg, OfferingQueryResponse, Order, SignedOrder,
+};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::fmt;
@@ -40,6 +46,10 @@ pub enum ExecuteMsg {
     ClaimWinner {
         auction_id: u64,
     },
+    // claim many ended auctions at once, stale ids are skipped
+    ClaimWinners {
+        auction_ids: Vec<u64>,
+    },
     AskNft {
         contract_addr: Addr,
         token_id: String,
@@ -53,12 +63,16 @@ pub enum ExecuteMsg {
         buyout_price: Option<Uint128>,
         step_price: Option<u64>,
         royalty: Option<u64>,
+        /// only these addresses can bid, anyone when None
+        reserved_for: Option<Vec<Addr>>,
     },
     SellNft {
         contract_addr: Addr,
         token_id: String,
         off_price: Uint128,
         royalty: Option<u64>,
+        /// only these addresses can buy the nft, anyone when None
+        reserved_for: Option<Vec<Addr>>,
     },
     // withdraw funds from auction marketplace to the owner wallet
     WithdrawFunds {
@@ -91,6 +105,19 @@ pub enum ExecuteMsg {
         operator: Addr,
     },
     TransferNftDirectly(GiftNft),
+    UpdateFeeDiscount(FeeDiscount),
+    /// buy an nft with an order signed off-chain by its seller
+    FulfillOrder(SignedOrder),
+    /// revoke a single signed order of the sender
+    CancelOrder {
+        order_hash: Binary,
+    },
+    /// cancel every signed order of the sender with a nonce lower than the new nonce
+    IncreaseOrderNonce {},
+    /// withdraw the royalties accrued to the sender
+    ClaimRoyalties {},
+    /// set the royalty rules of a creator type, applied by the ai royalty storage
+    UpdateRoyaltyPolicy(RoyaltyPolicy),
     // UpdateOfferingRoyalties {
     //     royalty: Vec<OfferingRoyalty>,
     // },
@@ -108,6 +135,7 @@ pub struct AskNftMsg {
     pub buyout_price: Option<Uint128>,
     pub step_price: Option<u64>,
     pub royalty: Option<u64>,
+    pub reserved_for: Option<Vec<Addr>>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -115,6 +143,7 @@ pub struct AskNftMsg {
 pub struct SellNft {
     pub off_price: Uint128,
     pub royalty: Option<u64>,
+    pub reserved_for: Option<Vec<Addr>>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -136,18 +165,88 @@ pub struct UpdateContractMsg {
     pub governance: Option<Addr>,
     pub decimal_point: Option<u64>,
     pub max_royalty: Option<u64>,
+    /// decaying royalties of the previous owners, their total is capped by max_royalty
+    pub provenance_royalties: Option<Vec<u64>>,
+    pub accrue_royalties: Option<bool>,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, QueryResponses)]
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
     // Auction info must be queried from auction contract
+    #[returns(ContractInfo)]
     GetContractInfo {},
+    #[returns(Uint128)]
     GetMarketFees {},
+    #[returns(AuctionQueryResponse)]
     Auction(AuctionQueryMsg),
+    #[returns(OfferingQueryResponse)]
     Offering(OfferingQueryMsg),
+    #[returns(AiRoyaltyQueryResponse)]
     AiRoyalty(AiRoyaltyQueryMsg),
+    #[returns(FirstLvRoyaltyQueryResponse)]
     FirstLvRoyalty(FirstLvRoyaltyQueryMsg),
+    #[returns(FeeDiscount)]
+    GetFeeDiscount {},
+    #[returns(EffectiveFeeResponse)]
+    GetEffectiveFee { address: Addr },
+    #[returns(u64)]
+    GetOrderNonce { seller: Addr },
+    #[returns(Binary)]
+    GetOrderHash { order: Order },
+    // previous owners of a token with the royalty each receives on the next resale
+    #[returns(Vec<Royalty>)]
+    GetProvenanceRoyalties { contract: Addr, token_id: String },
+    // fee, royalties and seller amount of a sale before it happens
+    #[returns(PayoutResponse)]
+    SimulatePayout(SimulatePayoutMsg),
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SimulatePayoutMsg {
+    pub contract: Addr,
+    pub token_id: String,
+    pub price: Uint128,
+    pub seller: Addr,
+    /// only the fee discount of the seller applies when None
+    pub buyer: Option<Addr>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PayoutResponse {
+    pub fee: Uint128,
+    /// royalties above the price of legacy tokens are scaled down proportionally
+    pub royalties: Vec<RoyaltyPayout>,
+    pub seller_amount: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct EffectiveFeeResponse {
+    /// market fee in permille before discount
+    pub fee: u64,
+    /// permille discount of the best tier reached by the address
+    pub discount: u64,
+    /// market fee in permille after discount
+    pub effective_fee: u64,
+}
+
+/// subset of the market_nft_staking query messages used to collect staked nfts
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum NftStakingQueryMsg {
+    GetCollectionStakerInfoByStaker {
+        staker_addr: Addr,
+        limit: Option<u8>,
+        offset: Option<u64>,
+        order: Option<u8>,
+    },
+}
+
+/// subset of market_nft_staking CollectionStakerInfo
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CollectionStakerInfo {
+    pub id: Option<u64>,
+    pub total_staked: Uint128,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -1,26 +1,30 @@
-use crate::ai_royalty::{add_msg_royalty, get_royalties};
+use crate::ai_royalty::{
+    add_msg_royalty, get_expire_royalties_update, get_provenance, get_provenance_update,
+    get_sale_royalties, settle_royalties, RoyaltySettlement,
+};
 use crate::contract::{
-    get_asset_info, get_handle_msg, get_storage_addr, query_offering_payment_asset_info,
-    verify_funds, verify_nft, verify_owner, PAYMENT_STORAGE,
+    add_storage_updates, get_asset_info, query_offering_payment_asset_info, verify_funds,
+    verify_nft, verify_owner,
 };
 use crate::error::ContractError;
-use crate::msg::{ProxyExecuteMsg, ProxyQueryMsg};
+use crate::fee_discount::get_effective_fee;
+use crate::msg::{PayoutResponse, SimulatePayoutMsg};
 use crate::state::{ContractInfo, CONTRACT_INFO, MARKET_FEES};
 use cosmwasm_std::{
-    attr, from_json, to_json_binary, Binary, CosmosMsg, Decimal, Deps, DepsMut, Env, MessageInfo,
-    Response, StdResult, Uint128, WasmMsg,
+    attr, to_json_binary, Binary, CosmosMsg, Decimal, Deps, DepsMut, Env, MessageInfo, Response,
+    StdResult, Uint128, WasmMsg,
 };
 use cosmwasm_std::{Addr, Coin};
 use cw721::Cw721ExecuteMsg;
-use market::{query_proxy, AssetInfo, Funds, StorageExecuteMsg};
-use market_ai_royalty::{parse_transfer_msg, pay_royalties, sanitize_royalty, Royalty, RoyaltyMsg};
-use market_payment::{Payment, PaymentExecuteMsg};
-use market_royalty::{MintMsg, Offering, OfferingExecuteMsg, OfferingQueryMsg, OfferingRoyalty};
+use market::{validate_reserved_for, verify_reserved_buyer, AssetInfo, Funds, PAYMENT_721_STORAGE};
+use market_ai_royalty::{get_royalty_payouts, parse_transfer_msg, sanitize_royalty, RoyaltyMsg};
+use market_payment::{Payment, PaymentStorage};
+use market_royalty::{
+    MintMsg, Offering, OfferingExecuteMsg, OfferingQueryMsg, OfferingRoyalty, OfferingStorage,
+};
+use market_whitelist::ListingType;
 use std::ops::{Add, Mul, Sub};
 
-pub const OFFERING_STORAGE: &str = "offering_v1.1";
-pub const OFFERING_STORAGE_TEMP: &str = "offering_temp";
-
 pub fn try_handle_mint(
     deps: DepsMut,
     info: MessageInfo,
@@ -36,13 +40,14 @@ pub fn try_handle_mint(
 
     let mut cosmos_msgs: Vec<CosmosMsg> = add_msg_royalty(
         info.sender.as_str(),
-        governance.as_str(),
+        &governance,
         RoyaltyMsg {
             contract_addr: msg.contract_addr,
             token_id: msg.mint.mint.token_id,
             creator: msg.creator,
             creator_type: Some(msg.creator_type),
             royalty: msg.royalty,
+            payees: None,
         },
     )?;
 
@@ -67,11 +72,7 @@ pub fn try_buy(
     // native_funds: Option<Vec<Coin>>,
     funds: Funds,
 ) -> Result<Response, ContractError> {
-    let ContractInfo {
-        governance,
-        decimal_point,
-        ..
-    } = CONTRACT_INFO.load(deps.storage)?;
+    let ContractInfo { governance, .. } = CONTRACT_INFO.load(deps.storage)?;
 
     // get royalties
     let mut rsp = Response::default();
@@ -79,6 +80,7 @@ pub fn try_buy(
 
     // check if offering exists, when return StdError => it will show EOF while parsing a JSON value.
     let off: Offering = get_offering(deps.as_ref(), offering_id)?;
+    verify_reserved_buyer(&off.reserved_for, &sender)?;
     let seller_addr = deps.api.addr_humanize(&off.seller)?;
     let contract_addr = deps.api.addr_humanize(&off.contract_addr)?;
     let token_id = off.token_id;
@@ -86,20 +88,22 @@ pub fn try_buy(
     // collect payment type
     let asset_info: AssetInfo = query_offering_payment_asset_info(
         deps.as_ref(),
-        governance.as_str(),
+        &governance,
         deps.api.addr_humanize(&off.contract_addr)?,
         token_id.as_str(),
     )?;
 
     let mut cosmos_msgs = vec![];
+    let mut storage_updates = vec![];
     // check for enough coins, if has price then payout to all participants
     if !off.price.is_zero() {
         let contract_info = CONTRACT_INFO.load(deps.storage)?;
 
         let mut seller_amount = off.price;
 
-        // pay for the owner of this minter contract if there is fee set in marketplace
-        let fee_amount = off.price.mul(Decimal::permille(contract_info.fee));
+        // pay for the owner of this minter contract if there is fee set in marketplace, discounted for loyal users
+        let fee = get_effective_fee(deps.as_ref(), contract_info.fee, &sender, &seller_addr)?;
+        let fee_amount = off.price.mul(Decimal::permille(fee));
         MARKET_FEES.update(deps.storage, |current_fees| -> StdResult<_> {
             Ok(current_fees.add(fee_amount))
         })?;
@@ -119,52 +123,55 @@ pub fn try_buy(
         let remaining_for_royalties = seller_amount;
 
         // corner case for 721 which has previous owner
-        let mut offering_royalty_result: OfferingRoyalty = deps
-            .querier
-            .query_wasm_smart(
-                get_storage_addr(deps.as_ref(), governance.clone(), OFFERING_STORAGE)?,
-                &ProxyQueryMsg::Offering(OfferingQueryMsg::GetOfferingRoyaltyByContractTokenId {
-                    contract: deps.api.addr_humanize(&off.contract_addr)?,
-                    token_id: token_id.clone(),
-                }) as &ProxyQueryMsg,
+        let mut offering_royalty_result = governance
+            .get_offering_royalty_by_contract_token_id(
+                &deps.querier,
+                deps.api.addr_humanize(&off.contract_addr)?,
+                &token_id,
             )
             .map_err(|err| ContractError::Std(err))?;
 
+        let provenance = get_provenance(deps.as_ref(), &contract_info, &contract_addr, &token_id)?;
+
         // pay for creator, ai provider and others
-        if let Ok(mut royalties) = get_royalties(deps.as_ref(), contract_addr.as_str(), &token_id) {
-            // payout for the previous owner
-            if offering_royalty_result.previous_owner.is_some()
-                && offering_royalty_result.prev_royalty.is_some()
-            {
-                royalties.push(Royalty {
-                    contract_addr: offering_royalty_result.contract_addr.clone(),
-                    token_id: offering_royalty_result.token_id.clone(),
-                    creator: offering_royalty_result.previous_owner.unwrap(),
-                    royalty: offering_royalty_result.prev_royalty.unwrap(),
-                    creator_type: "previous_owner".into(),
-                })
-            }
-
-            pay_royalties(
+        if let Some(royalties) = get_sale_royalties(
+            deps.as_ref(),
+            &contract_info,
+            &contract_addr,
+            &token_id,
+            &seller_addr,
+            provenance.as_ref(),
+            Some(&offering_royalty_result),
+        ) {
+            settle_royalties(
+                RoyaltySettlement {
+                    contract_info: &contract_info,
+                    querier: &deps.querier,
+                    price: remaining_for_royalties,
+                    contract_addr: env.contract.address.as_str(),
+                    asset_info: asset_info.clone(),
+                },
                 &royalties,
-                &remaining_for_royalties,
-                decimal_point,
                 &mut seller_amount,
                 &mut cosmos_msgs,
+                &mut storage_updates,
                 &mut rsp,
-                env.contract.address.as_str(),
-                &to_json_binary(&asset_info)?.to_base64(),
-                asset_info.clone(),
             )?;
         }
 
+        if let Some(provenance) = provenance {
+            storage_updates.push(get_provenance_update(
+                &contract_info,
+                provenance,
+                seller_addr.clone(),
+            )?);
+        }
+
         // update offering royalty result, current royalty info now turns to prev
         offering_royalty_result.prev_royalty = offering_royalty_result.cur_royalty;
         offering_royalty_result.previous_owner =
             Some(offering_royalty_result.current_owner.clone());
-        cosmos_msgs.push(get_offering_handle_msg(
-            governance.clone(),
-            OFFERING_STORAGE,
+        storage_updates.push(governance.offering_update(
             OfferingExecuteMsg::UpdateOfferingRoyalty {
                 offering: offering_royalty_result.clone(),
             },
@@ -197,13 +204,17 @@ pub fn try_buy(
         .into(),
     );
 
-    // remove offering in the offering storage
-    cosmos_msgs.push(get_offering_handle_msg(
-        governance,
-        OFFERING_STORAGE,
-        OfferingExecuteMsg::RemoveOffering { id: offering_id },
+    storage_updates.push(get_expire_royalties_update(
+        &governance,
+        &contract_addr,
+        &token_id,
     )?);
 
+    // remove offering in the offering storage
+    storage_updates
+        .push(governance.offering_update(OfferingExecuteMsg::RemoveOffering { id: offering_id })?);
+
+    add_storage_updates(&governance, &mut cosmos_msgs, storage_updates)?;
     rsp = rsp.add_messages(cosmos_msgs);
     rsp.attributes.extend(vec![
         attr("buyer", sender),
@@ -290,11 +301,7 @@ pub fn try_withdraw(
     }
 
     // remove offering
-    cosmos_msg.push(get_offering_handle_msg(
-        governance,
-        OFFERING_STORAGE,
-        OfferingExecuteMsg::RemoveOffering { id: offering_id },
-    )?);
+    cosmos_msg.push(governance.remove_offering(offering_id)?);
 
     Ok(Response::new()
         .add_messages(cosmos_msg)
@@ -314,6 +321,7 @@ pub fn try_handle_sell_nft(
     initial_token_id: String,
     off_price: Uint128,
     royalty: Option<u64>,
+    reserved_for: Option<Vec<Addr>>,
 ) -> Result<Response, ContractError> {
     let ContractInfo {
         governance,
@@ -326,10 +334,11 @@ pub fn try_handle_sell_nft(
 
     verify_nft(
         deps.as_ref(),
-        &governance.as_str(),
+        &governance,
         &contract_addr.as_str(),
         &token_id,
         &info.sender.as_str(),
+        Some((ListingType::FixedPrice, off_price, &asset_info)),
     )?;
     let royalty = Some(sanitize_royalty(
         royalty.unwrap_or(0),
@@ -337,15 +346,8 @@ pub fn try_handle_sell_nft(
         "royalty",
     )?);
 
-    let mut offering_royalty_result: OfferingRoyalty = deps
-        .querier
-        .query_wasm_smart(
-            get_storage_addr(deps.as_ref(), governance.clone(), OFFERING_STORAGE)?,
-            &ProxyQueryMsg::Offering(OfferingQueryMsg::GetOfferingRoyaltyByContractTokenId {
-                contract: contract_addr.clone(),
-                token_id: token_id.clone(),
-            }) as &ProxyQueryMsg,
-        )
+    let mut offering_royalty_result = governance
+        .get_offering_royalty_by_contract_token_id(&deps.querier, contract_addr.clone(), &token_id)
         .unwrap_or(OfferingRoyalty {
             token_id: token_id.clone(),
             contract_addr: contract_addr.clone(),
@@ -363,38 +365,27 @@ pub fn try_handle_sell_nft(
         contract_addr: deps.api.addr_canonicalize(contract_addr.as_str())?,
         seller: deps.api.addr_canonicalize(&info.sender.as_str())?,
         price: off_price,
+        reserved_for: validate_reserved_for(deps.api, reserved_for)?,
+        asset_info: Some(asset_info.clone()),
     };
 
     let mut cosmos_msgs = vec![];
     // push save message to auction_storage
-    cosmos_msgs.push(get_offering_handle_msg(
-        governance.clone(),
-        OFFERING_STORAGE,
-        OfferingExecuteMsg::UpdateOffering {
-            offering: offering.clone(),
-        },
-    )?);
+    cosmos_msgs.push(governance.update_offering(offering.clone())?);
 
     // push save message to market payment storage
-    cosmos_msgs.push(get_handle_msg(
-        governance.as_str(),
-        PAYMENT_STORAGE,
-        PaymentExecuteMsg::UpdateOfferingPayment(Payment {
+    cosmos_msgs.push(governance.update_offering_payment(
+        PAYMENT_721_STORAGE,
+        Payment {
             contract_addr,
             token_id: token_id.clone(),
             sender: None, // for 721, contract & token id combined is already unique
             asset_info: asset_info.clone(),
-        }),
+        },
     )?);
 
     // update offering royalty result
-    cosmos_msgs.push(get_offering_handle_msg(
-        governance.clone(),
-        OFFERING_STORAGE,
-        OfferingExecuteMsg::UpdateOfferingRoyalty {
-            offering: offering_royalty_result.clone(),
-        },
-    )?);
+    cosmos_msgs.push(governance.update_offering_royalty(offering_royalty_result.clone())?);
 
     // TEMP: auto add royalty creator default for old nft (if that nft does not have royalty creator)
     // let royalty_result =
@@ -438,41 +429,78 @@ pub fn try_handle_sell_nft(
 
 pub fn query_offering(deps: Deps, msg: OfferingQueryMsg) -> StdResult<Binary> {
     let contract_info = CONTRACT_INFO.load(deps.storage)?;
-    query_proxy(
-        deps,
-        get_storage_addr(deps, contract_info.governance, OFFERING_STORAGE)?,
-        to_json_binary(&ProxyQueryMsg::Offering(msg) as &ProxyQueryMsg)?,
-    )
+    contract_info
+        .governance
+        .query_offering_binary(&deps.querier, msg)
 }
 
-fn get_offering(deps: Deps, offering_id: u64) -> Result<Offering, ContractError> {
-    let offering: Offering = from_json(&query_offering(
+/// the payout of a direct sale of the token at the price, computed the same way as try_buy
+pub fn query_simulate_payout(deps: Deps, msg: SimulatePayoutMsg) -> StdResult<PayoutResponse> {
+    let contract_info = CONTRACT_INFO.load(deps.storage)?;
+    let SimulatePayoutMsg {
+        contract,
+        token_id,
+        price,
+        seller,
+        buyer,
+    } = msg;
+
+    let fee = get_effective_fee(
         deps,
-        OfferingQueryMsg::GetOfferingState { offering_id },
-    )?)
-    .map_err(|_| ContractError::InvalidGetOffering {})?;
-    Ok(offering)
-}
+        contract_info.fee,
+        buyer.as_ref().unwrap_or(&seller),
+        &seller,
+    )?;
+    let fee_amount = price.mul(Decimal::permille(fee));
+    let remaining_for_royalties = price.checked_sub(fee_amount)?;
+
+    // the token may not have been sold on the market yet
+    let offering_royalty = contract_info
+        .governance
+        .get_offering_royalty_by_contract_token_id(&deps.querier, contract.clone(), &token_id)
+        .ok();
+    let provenance = get_provenance(deps, &contract_info, &contract, &token_id)?;
+    let royalties = get_sale_royalties(
+        deps,
+        &contract_info,
+        &contract,
+        &token_id,
+        &seller,
+        provenance.as_ref(),
+        offering_royalty.as_ref(),
+    )
+    .map(|royalties| {
+        get_royalty_payouts(
+            &royalties,
+            &remaining_for_royalties,
+            contract_info.decimal_point,
+            remaining_for_royalties,
+        )
+    })
+    .unwrap_or_default();
 
-pub fn get_offering_handle_msg(
-    addr: Addr,
-    name: &str,
-    msg: OfferingExecuteMsg,
-) -> StdResult<CosmosMsg> {
-    let msg_offering: ProxyExecuteMsg<OfferingExecuteMsg> = ProxyExecuteMsg::Offering(msg);
-    let auction_msg = to_json_binary(&msg_offering)?;
-    let proxy_msg: ProxyExecuteMsg<StorageExecuteMsg> =
-        ProxyExecuteMsg::Storage(StorageExecuteMsg::UpdateStorageData {
-            name: name.to_string(),
-            msg: auction_msg,
+    let seller_amount = royalties
+        .iter()
+        .fold(remaining_for_royalties, |amount, payout| {
+            amount - payout.amount
         });
+    Ok(PayoutResponse {
+        fee: fee_amount,
+        royalties,
+        seller_amount,
+    })
+}
 
-    Ok(WasmMsg::Execute {
-        contract_addr: addr.to_string(),
-        msg: to_json_binary(&proxy_msg)?,
-        funds: vec![],
-    }
-    .into())
+fn get_offering(deps: Deps, offering_id: u64) -> Result<Offering, ContractError> {
+    let contract_info = CONTRACT_INFO.load(deps.storage)?;
+    let offering = contract_info
+        .governance
+        .query_offering(
+            &deps.querier,
+            OfferingQueryMsg::GetOfferingState { offering_id },
+        )
+        .map_err(|_| ContractError::InvalidGetOffering {})?;
+    Ok(offering)
 }
 
 // pub fn try_update_offering_royalties(
@@ -0,0 +1,269 @@
+use crate::ai_royalty::{
+    get_expire_royalties_update, get_provenance, get_provenance_update, get_sale_royalties,
+    settle_royalties, RoyaltySettlement,
+};
+use crate::contract::{add_storage_updates, verify_funds, verify_nft};
+use crate::error::ContractError;
+use crate::fee_discount::get_effective_fee;
+use crate::state::{
+    ContractInfo, CANCELLED_ORDERS, CONTRACT_INFO, FILLED_ORDERS, MARKET_FEES, ORDER_NONCES,
+};
+use bech32::{ToBase32, Variant};
+use cosmwasm_std::{
+    attr, to_json_binary, to_json_vec, Addr, Binary, CosmosMsg, Decimal, Deps, DepsMut, Env,
+    MessageInfo, Response, StdError, StdResult, WasmMsg,
+};
+use cw721::Cw721ExecuteMsg;
+use market::{AssetInfo, Funds};
+use market_ai_royalty::parse_transfer_msg;
+use market_royalty::{Order, SignedOrder};
+use market_whitelist::ListingType;
+use ripemd::Ripemd160;
+use sha2::{Digest, Sha256};
+use std::ops::{Add, Mul};
+
+/// sha256 of the json encoded order, this is the message signed by the seller
+pub fn order_hash(order: &Order) -> StdResult<Vec<u8>> {
+    Ok(Sha256::digest(&to_json_vec(order)?).to_vec())
+}
+
+/// bech32 account address of a compressed secp256k1 public key, using the prefix of the expected address
+fn pubkey_to_addr(pubkey: &[u8], expected: &Addr) -> StdResult<String> {
+    let (prefix, _) = expected
+        .as_str()
+        .split_once('1')
+        .ok_or_else(|| StdError::generic_err("invalid bech32 address"))?;
+    let account = <Ripemd160 as ripemd::Digest>::digest(Sha256::digest(pubkey));
+    bech32::encode(prefix, account.to_base32(), Variant::Bech32)
+        .map_err(|err| StdError::generic_err(err.to_string()))
+}
+
+pub fn get_order_nonce(deps: Deps, seller: &Addr) -> StdResult<u64> {
+    Ok(ORDER_NONCES
+        .may_load(deps.storage, seller.as_bytes())?
+        .unwrap_or_default())
+}
+
+/// check that the order can still be settled and was signed by its seller, returns the order hash
+fn verify_order(
+    deps: Deps,
+    env: &Env,
+    signed_order: &SignedOrder,
+) -> Result<Vec<u8>, ContractError> {
+    let SignedOrder {
+        order,
+        pubkey,
+        signature,
+    } = signed_order;
+
+    if order.market.ne(&env.contract.address) {
+        return Err(ContractError::InvalidArgument {
+            arg: "market".to_string(),
+        });
+    }
+    if env.block.time.seconds() >= order.expiration {
+        return Err(ContractError::OrderExpired {});
+    }
+    if order.price.is_zero() {
+        return Err(ContractError::InvalidZeroAmount {});
+    }
+
+    let hash = order_hash(order)?;
+    if FILLED_ORDERS.may_load(deps.storage, &hash)?.is_some() {
+        return Err(ContractError::OrderFilled {});
+    }
+    if order.nonce < get_order_nonce(deps, &order.seller)?
+        || CANCELLED_ORDERS
+            .may_load(deps.storage, (order.seller.as_bytes(), &hash))?
+            .is_some()
+    {
+        return Err(ContractError::OrderCancelled {});
+    }
+
+    // the public key must belong to the seller and sign the order hash
+    if pubkey_to_addr(pubkey, &order.seller)?.ne(order.seller.as_str())
+        || !deps
+            .api
+            .secp256k1_verify(&hash, signature, pubkey)
+            .unwrap_or(false)
+    {
+        return Err(ContractError::InvalidSignature {});
+    }
+    Ok(hash)
+}
+
+pub fn try_fulfill_order(
+    deps: DepsMut,
+    sender: Addr,
+    env: Env,
+    signed_order: SignedOrder,
+    funds: Funds,
+) -> Result<Response, ContractError> {
+    let contract_info = CONTRACT_INFO.load(deps.storage)?;
+    let ContractInfo {
+        governance, fee, ..
+    } = contract_info.clone();
+    let hash = verify_order(deps.as_ref(), &env, &signed_order)?;
+    let Order {
+        contract_addr,
+        token_id,
+        seller,
+        price,
+        asset_info,
+        ..
+    } = signed_order.order;
+
+    // the seller must still own the nft, which must be whitelisted and not listed on the market
+    verify_nft(
+        deps.as_ref(),
+        &governance,
+        contract_addr.as_str(),
+        &token_id,
+        seller.as_str(),
+        Some((ListingType::FixedPrice, price, &asset_info)),
+    )?;
+    verify_funds(&funds, asset_info.clone(), &price)?;
+    FILLED_ORDERS.save(deps.storage, &hash, &true)?;
+
+    let mut rsp = Response::default();
+    rsp.attributes.extend(vec![attr("action", "fulfill_order")]);
+    let mut cosmos_msgs: Vec<CosmosMsg> = vec![];
+    let mut storage_updates = vec![];
+
+    // pay for the owner of this minter contract if there is fee set in marketplace, discounted for loyal users
+    let fee = get_effective_fee(deps.as_ref(), fee, &sender, &seller)?;
+    let fee_amount = price.mul(Decimal::permille(fee));
+    MARKET_FEES.update(deps.storage, |current_fees| -> StdResult<_> {
+        Ok(current_fees.add(fee_amount))
+    })?;
+    let mut seller_amount = price.checked_sub(fee_amount)?;
+
+    let provenance = get_provenance(deps.as_ref(), &contract_info, &contract_addr, &token_id)?;
+
+    // pay for creator, ai provider, previous owners and others
+    if let Some(royalties) = get_sale_royalties(
+        deps.as_ref(),
+        &contract_info,
+        &contract_addr,
+        &token_id,
+        &seller,
+        provenance.as_ref(),
+        None,
+    ) {
+        settle_royalties(
+            RoyaltySettlement {
+                contract_info: &contract_info,
+                querier: &deps.querier,
+                price: seller_amount,
+                contract_addr: env.contract.address.as_str(),
+                asset_info: asset_info.clone(),
+            },
+            &royalties,
+            &mut seller_amount,
+            &mut cosmos_msgs,
+            &mut storage_updates,
+            &mut rsp,
+        )?;
+    }
+
+    if let Some(provenance) = provenance {
+        storage_updates.push(get_provenance_update(
+            &contract_info,
+            provenance,
+            seller.clone(),
+        )?);
+    }
+
+    // pay the left to the seller
+    if !seller_amount.is_zero() {
+        cosmos_msgs.push(parse_transfer_msg(
+            asset_info,
+            seller_amount,
+            env.contract.address.as_str(),
+            seller.clone(),
+        )?);
+    }
+
+    // the market is approved for all nfts of the seller, so it can transfer the nft to the buyer
+    cosmos_msgs.push(
+        WasmMsg::Execute {
+            contract_addr: contract_addr.to_string(),
+            msg: to_json_binary(&Cw721ExecuteMsg::TransferNft {
+                recipient: sender.clone(),
+                token_id: token_id.clone(),
+            })?,
+            funds: vec![],
+        }
+        .into(),
+    );
+    storage_updates.push(get_expire_royalties_update(
+        &governance,
+        &contract_addr,
+        &token_id,
+    )?);
+
+    add_storage_updates(&governance, &mut cosmos_msgs, storage_updates)?;
+    rsp = rsp.add_messages(cosmos_msgs);
+    rsp.attributes.extend(vec![
+        attr("buyer", sender),
+        attr("seller", seller),
+        attr("contract_addr", contract_addr),
+        attr("token_id", token_id),
+        attr("order_hash", Binary::from(hash).to_base64()),
+        attr("total_price", price),
+    ]);
+    Ok(rsp)
+}
+
+/// only the cw20 token of the order can pay for it
+pub fn verify_order_token(
+    info: &MessageInfo,
+    signed_order: &SignedOrder,
+) -> Result<(), ContractError> {
+    match &signed_order.order.asset_info {
+        AssetInfo::Token { contract_addr } if contract_addr.eq(&info.sender) => Ok(()),
+        _ => Err(ContractError::InvalidSentFundAmount {}),
+    }
+}
+
+/// a cw20 order can only be paid through the cw20 hook, never with native coins
+pub fn verify_order_native(signed_order: &SignedOrder) -> Result<(), ContractError> {
+    match &signed_order.order.asset_info {
+        AssetInfo::NativeToken { .. } => Ok(()),
+        AssetInfo::Token { .. } => Err(ContractError::InvalidSentFundAmount {}),
+    }
+}
+
+pub fn try_cancel_order(
+    deps: DepsMut,
+    info: MessageInfo,
+    order_hash: Binary,
+) -> Result<Response, ContractError> {
+    CANCELLED_ORDERS.save(
+        deps.storage,
+        (info.sender.as_bytes(), order_hash.as_slice()),
+        &true,
+    )?;
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "cancel_order"),
+        attr("seller", info.sender),
+        attr("order_hash", order_hash.to_base64()),
+    ]))
+}
+
+pub fn try_increase_order_nonce(
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let nonce = get_order_nonce(deps.as_ref(), &info.sender)? + 1;
+    ORDER_NONCES.save(deps.storage, info.sender.as_bytes(), &nonce)?;
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "increase_order_nonce"),
+        attr("seller", info.sender),
+        attr("nonce", nonce.to_string()),
+    ]))
+}
+
+pub fn query_order_hash(order: Order) -> StdResult<Binary> {
+    Ok(Binary::from(order_hash(&order)?))
+}
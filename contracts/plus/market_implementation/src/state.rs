@@ -1,8 +1,9 @@
 use cosmwasm_std::{Addr, Uint128};
+use market::MarketHubContract;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cw_storage_plus::Item;
+use cw_storage_plus::{Item, Map};
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
 pub struct ContractInfo {
@@ -15,10 +16,45 @@ pub struct ContractInfo {
     /// this defines the number of blocks until the end of auction
     pub auction_duration: Uint128,
     pub step_price: u64,
-    pub governance: Addr,
+    pub governance: MarketHubContract,
     pub max_royalty: u64,
     pub decimal_point: u64,
+    /// royalties paid to the previous owners on resale, the latest owner first
+    #[serde(default)]
+    pub provenance_royalties: Vec<u64>,
+    /// credit royalties to balances claimed by the creators instead of transferring them on each sale
+    #[serde(default)]
+    pub accrue_royalties: bool,
 }
 
-pub const CONTRACT_INFO: Item<ContractInfo> = Item::new("contract_info");
+pub const CONTRACT_INFO_KEY: &str = "market_info";
+pub const CONTRACT_INFO: Item<ContractInfo> = Item::new(CONTRACT_INFO_KEY);
 pub const MARKET_FEES: Item<Uint128> = Item::new("market_fees");
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct FeeDiscountTier {
+    /// minimum number of nfts staked in the staking contract to reach this tier
+    pub min_staked: Option<Uint128>,
+    /// minimum balance of the discount token to reach this tier
+    pub min_balance: Option<Uint128>,
+    /// permille discount applied on the market fee
+    pub discount: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug, Default)]
+pub struct FeeDiscount {
+    /// market_nft_staking contract used to collect the total staked nfts of an address
+    pub staking_contract: Option<Addr>,
+    /// ow20 token used to collect the token balance of an address
+    pub token: Option<Addr>,
+    pub tiers: Vec<FeeDiscountTier>,
+}
+
+pub const FEE_DISCOUNT: Item<FeeDiscount> = Item::new("fee_discount");
+
+/// current order nonce of every seller, signed orders with a lower nonce are cancelled
+pub const ORDER_NONCES: Map<&[u8], u64> = Map::new("order_nonces");
+/// signed orders revoked by their seller, keyed by seller and order hash
+pub const CANCELLED_ORDERS: Map<(&[u8], &[u8]), bool> = Map::new("cancelled_orders");
+/// hashes of the signed orders already settled
+pub const FILLED_ORDERS: Map<&[u8], bool> = Map::new("filled_orders");
@@ -4,24 +4,25 @@ use crate::contract::{
 };
 use crate::error::ContractError;
 use crate::msg::*;
-use crate::state::ContractInfo;
+use crate::state::{ContractInfo, FeeDiscount, FeeDiscountTier};
 use cosmwasm_std::testing::{mock_info, MockApi, MockStorage};
 use cosmwasm_std::{
-    coin, coins, from_json, to_json_binary, Addr, Binary, ContractResult, CosmosMsg, Decimal, Env,
-    MessageInfo, Order, OwnedDeps, QuerierResult, Response, StdError, StdResult, SystemError,
+    attr, coin, coins, from_json, to_json_binary, Addr, Binary, ContractResult, CosmosMsg, Decimal,
+    Env, MessageInfo, Order, OwnedDeps, QuerierResult, Response, StdError, StdResult, SystemError,
     SystemResult, Uint128, WasmMsg, WasmQuery,
 };
 use cw20::{Cw20Coin, Cw20ReceiveMsg, MinterResponse};
 use cw721::{ApprovedForAllResponse, OwnerOfResponse};
-use market::parse_token_id;
-use market_ai_royalty::{AiRoyaltyQueryMsg, Royalty, RoyaltyMsg};
+use market::{parse_token_id, MarketHubExecuteMsg, StorageExecuteMsg};
+use market::{AssetInfo, PageResponse, ReservedError};
+use market_ai_royalty::{AiRoyaltyQueryMsg, Royalty, RoyaltyBalance, RoyaltyMsg, RoyaltyPolicy};
 use market_auction::mock::{mock_dependencies, mock_env, MockQuerier};
 use market_auction::{AuctionQueryMsg, AuctionsResponse, PagingOptions};
 use market_royalty::{
     Cw20HookMsg, ExtraData, MintIntermediate, MintMsg, MintStruct, OfferingQueryMsg,
     OfferingRoyalty, OfferingsResponse, QueryOfferingsResult,
 };
-use market_whitelist::MarketWhiteListExecuteMsg;
+use market_whitelist::{CollectionMetadata, ListingType, MarketWhiteListExecuteMsg, MinPrice};
 use std::mem::transmute;
 use std::ops::{Add, Mul};
 use std::ptr::null;
@@ -565,6 +566,7 @@ fn sell_auction_happy_path() {
             end_timestamp: None,
             step_price: None,
             royalty: Some(40 * DECIMAL),
+            reserved_for: None,
         };
 
         //manager.handle_wasm(res, ret)
@@ -582,6 +584,8 @@ fn sell_auction_happy_path() {
                         offset: Some(0),
                         limit: Some(3),
                         order: Some(Order::Ascending as u8),
+                        start_after: None,
+                        with_total: None,
                     },
                 }))
                 .unwrap(),
@@ -639,6 +643,7 @@ fn sell_auction_cw20_happy_path() {
             end_timestamp: None,
             step_price: None,
             royalty: Some(40 * DECIMAL),
+            reserved_for: None,
         };
 
         //manager.handle_wasm(res, ret)
@@ -656,6 +661,8 @@ fn sell_auction_cw20_happy_path() {
                         offset: Some(0),
                         limit: Some(3),
                         order: Some(Order::Ascending as u8),
+                        start_after: None,
+                        with_total: None,
                     },
                 }))
                 .unwrap(),
@@ -718,6 +725,7 @@ fn test_royalty_auction_happy_path() {
             end_timestamp: Some(Uint128::from(contract_env.block.time.seconds() + 100)),
             step_price: Some(10),
             royalty: Some(40 * DECIMAL),
+            reserved_for: None,
         };
 
         //manager.handle_wasm(res, ret)
@@ -782,6 +790,7 @@ fn test_royalty_auction_happy_path() {
             end_timestamp: Some(Uint128::from(contract_env.block.time.seconds() + 100)),
             step_price: Some(10),
             royalty: Some(40 * DECIMAL),
+            reserved_for: None,
         };
 
         let _result = manager
@@ -813,6 +822,8 @@ fn test_royalty_auction_happy_path() {
                         offset: Some(0),
                         limit: Some(3),
                         order: Some(Order::Ascending as u8),
+                        start_after: None,
+                        with_total: None,
                     },
                 }))
                 .unwrap(),
@@ -921,6 +932,7 @@ fn test_royalty_auction_cw20_happy_path() {
             end_timestamp: Some(Uint128::from(contract_env.block.time.seconds() + 100)),
             step_price: Some(10),
             royalty: Some(40 * DECIMAL),
+            reserved_for: None,
         };
 
         //manager.handle_wasm(res, ret)
@@ -979,6 +991,7 @@ fn test_royalty_auction_cw20_happy_path() {
             end_timestamp: Some(Uint128::from(contract_env.block.time.seconds() + 100)),
             step_price: Some(10),
             royalty: Some(40 * DECIMAL),
+            reserved_for: None,
         };
 
         let _result = manager
@@ -1004,6 +1017,8 @@ fn test_royalty_auction_cw20_happy_path() {
                         offset: Some(0),
                         limit: Some(3),
                         order: Some(Order::Ascending as u8),
+                        start_after: None,
+                        with_total: None,
                     },
                 }))
                 .unwrap(),
@@ -1052,6 +1067,8 @@ fn update_info_test() {
             governance: None,
             decimal_point: None,
             max_royalty: Some(1000),
+            provenance_royalties: None,
+            accrue_royalties: None,
         };
         let update_info_msg = ExecuteMsg::UpdateInfo(update_info);
 
@@ -1069,7 +1086,7 @@ fn update_info_test() {
 
         let query_info = QueryMsg::GetContractInfo {};
         let res_info: ContractInfo = from_json(&manager.query(query_info).unwrap()).unwrap();
-        assert_eq!(res_info.governance.as_str(), HUB_ADDR);
+        assert_eq!(res_info.governance.addr().as_str(), HUB_ADDR);
 
         assert_eq!(res_info.max_royalty, 1000);
     }
@@ -1123,6 +1140,7 @@ fn cancel_auction_happy_path() {
             end_timestamp: None,
             step_price: None,
             royalty: None,
+            reserved_for: None,
         };
 
         let _result = manager.execute(mock_info(PROVIDER, &vec![]), sell_msg.clone());
@@ -1154,6 +1172,8 @@ fn cancel_auction_happy_path() {
                     limit: None,
                     offset: None,
                     order: None,
+                    start_after: None,
+                    with_total: None,
                 },
             }))
             .unwrap();
@@ -1210,6 +1230,7 @@ fn cancel_auction_unhappy_path() {
             end_timestamp: None,
             step_price: None,
             royalty: None,
+            reserved_for: None,
         };
 
         //manager.handle_wasm(res, ret)
@@ -1290,6 +1311,7 @@ fn cancel_auction_verify_owner() {
             end_timestamp: None,
             step_price: None,
             royalty: None,
+            reserved_for: None,
         };
 
         let _result = manager.execute(mock_info(PROVIDER, &vec![]), sell_msg.clone());
@@ -1329,6 +1351,8 @@ fn cancel_auction_verify_owner() {
                     limit: None,
                     offset: None,
                     order: None,
+                    start_after: None,
+                    with_total: None,
                 },
             }))
             .unwrap();
@@ -1402,6 +1426,7 @@ fn cancel_bid_happy_path() {
             end_timestamp: None,
             step_price: None,
             royalty: None,
+            reserved_for: None,
         };
 
         //manager.handle_wasm(res, ret)
@@ -1434,6 +1459,8 @@ fn cancel_bid_happy_path() {
                     limit: None,
                     offset: None,
                     order: None,
+                    start_after: None,
+                    with_total: None,
                 },
             }))
             .unwrap();
@@ -1490,6 +1517,7 @@ fn cancel_bid_cw20_happy_path() {
             end_timestamp: None,
             step_price: None,
             royalty: None,
+            reserved_for: None,
         };
 
         //manager.handle_wasm(res, ret)
@@ -1529,6 +1557,8 @@ fn cancel_bid_cw20_happy_path() {
                     limit: None,
                     offset: None,
                     order: None,
+                    start_after: None,
+                    with_total: None,
                 },
             }))
             .unwrap();
@@ -1585,6 +1615,7 @@ fn cancel_bid_unhappy_path() {
             end_timestamp: None,
             step_price: None,
             royalty: None,
+            reserved_for: None,
         };
 
         //manager.handle_wasm(res, ret)
@@ -1672,6 +1703,7 @@ fn claim_winner_return_back_to_owner() {
             end_timestamp: Some(Uint128::from(contract_env.block.time.seconds() + 100)),
             step_price: None,
             royalty: None,
+            reserved_for: None,
         };
 
         //manager.handle_wasm(res, ret)
@@ -1769,6 +1801,7 @@ fn claim_winner_return_back_to_owner() {
             end_timestamp: None,
             step_price: None,
             royalty: None,
+            reserved_for: None,
         };
 
         //manager.handle_wasm(res, ret)
@@ -1782,6 +1815,8 @@ fn claim_winner_return_back_to_owner() {
                         offset: Some(0),
                         limit: Some(3),
                         order: Some(Order::Ascending as u8),
+                        start_after: None,
+                        with_total: None,
                     },
                 }))
                 .unwrap(),
@@ -1791,6 +1826,136 @@ fn claim_winner_return_back_to_owner() {
     }
 }
 
+#[test]
+fn claim_winners_skips_stale_auctions() {
+    unsafe {
+        let manager = DepsManager::get_new();
+        let contract_env = mock_env(MARKET_ADDR);
+        handle_whitelist(manager);
+
+        let contract_info: ContractInfo =
+            from_json(manager.query(QueryMsg::GetContractInfo {}).unwrap()).unwrap();
+
+        let creator_info = mock_info("creator", &[coin(50, DENOM)]);
+        let mint = MintMsg {
+            contract_addr: Addr::unchecked(OW721),
+            creator: Addr::unchecked(PROVIDER),
+            mint: MintIntermediate {
+                mint: MintStruct {
+                    token_id: String::from(PROVIDER_NFT),
+                    owner: Addr::unchecked(PROVIDER),
+                    name: String::from("asbv"),
+                    description: None,
+                    image: String::from("baxv"),
+                },
+            },
+            creator_type: String::from("sacx"),
+            royalty: Some(40 * DECIMAL),
+        };
+        manager
+            .execute(creator_info, ExecuteMsg::MintNft(mint))
+            .unwrap();
+
+        let _result = oraichain_nft::contract::execute(
+            manager.ow721.as_mut(),
+            mock_env(OW721),
+            mock_info(PROVIDER, &[]),
+            oraichain_nft::msg::ExecuteMsg::ApproveAll {
+                operator: Addr::unchecked(MARKET_ADDR),
+                expires: None,
+            },
+        );
+
+        let now = contract_env.block.time.seconds();
+        let sell_msg = ExecuteMsg::AskNft {
+            contract_addr: Addr::unchecked(OW721),
+            token_id: String::from(PROVIDER_NFT_NATIVE),
+            price: Uint128::from(10u64),
+            cancel_fee: Some(10),
+            start: None,
+            end: None,
+            buyout_price: None,
+            start_timestamp: Some(Uint128::from(now + 5)),
+            end_timestamp: Some(Uint128::from(now + 100)),
+            step_price: None,
+            royalty: None,
+            reserved_for: None,
+        };
+        manager.execute(mock_info(PROVIDER, &[]), sell_msg).unwrap();
+
+        let bid_info = mock_info(
+            BIDDER,
+            &coins(
+                Uint128::from(10u64)
+                    .add(Uint128::from(contract_info.step_price))
+                    .u128(),
+                DENOM,
+            ),
+        );
+        let mut bid_contract_env = contract_env.clone();
+        bid_contract_env.block.time = contract_env.block.time.plus_seconds(15);
+        manager
+            .handle_with_env(
+                bid_contract_env.clone(),
+                bid_info,
+                ExecuteMsg::BidNft { auction_id: 1 },
+            )
+            .unwrap();
+
+        // the auction ends within the next hour
+        let ending_msg = QueryMsg::Auction(AuctionQueryMsg::GetAuctionsEndingBetween {
+            from: Uint128::from(now),
+            to: Uint128::from(now + 3600),
+            options: PagingOptions {
+                offset: None,
+                start_after: None,
+                limit: None,
+                order: None,
+                with_total: None,
+            },
+        });
+        let result: AuctionsResponse =
+            from_json(manager.query(ending_msg.clone()).unwrap()).unwrap();
+        assert_eq!(result.items.len(), 1);
+        assert_eq!(result.items[0].id, 1);
+
+        // not finished yet, so it is skipped instead of failing
+        let claim_info = mock_info("keeper", &[]);
+        let res = manager
+            .handle_with_env(
+                bid_contract_env,
+                claim_info.clone(),
+                ExecuteMsg::ClaimWinners {
+                    auction_ids: vec![1],
+                },
+            )
+            .unwrap();
+        let attributes = &res.last().unwrap().attributes;
+        assert!(attributes.contains(&attr("claimed", "")));
+        assert!(attributes.contains(&attr("skipped", "1")));
+
+        // duplicated ids are claimed once and unknown ids are skipped
+        let mut claim_contract_env = contract_env.clone();
+        claim_contract_env.block.time = contract_env.block.time.plus_seconds(100);
+        let res = manager
+            .handle_with_env(
+                claim_contract_env,
+                claim_info,
+                ExecuteMsg::ClaimWinners {
+                    auction_ids: vec![1, 1, 99],
+                },
+            )
+            .unwrap();
+        let attributes = &res.last().unwrap().attributes;
+        assert!(attributes.contains(&attr("claimed", "1")));
+        assert!(attributes.contains(&attr("skipped", "99")));
+
+        // the claimed auction is removed from the storage
+        let result: AuctionsResponse = from_json(manager.query(ending_msg).unwrap()).unwrap();
+        assert_eq!(result.items.len(), 0);
+    }
+}
+
 #[test]
 fn claim_winner_verify_owner() {
     unsafe {
@@ -1842,6 +2007,7 @@ fn claim_winner_verify_owner() {
             end_timestamp: Some(Uint128::from(contract_env.block.time.seconds() + 100)),
             step_price: None,
             royalty: None,
+            reserved_for: None,
         };
 
         manager
@@ -1943,6 +2109,7 @@ fn test_royalties() {
             token_id: String::from(SELLABLE_NFT_NATIVE),
             off_price: Uint128::from(50u128),
             royalty: Some(10 * DECIMAL),
+            reserved_for: None,
         };
         manager.execute(info_sell.clone(), msg).unwrap();
 
@@ -1952,6 +2119,8 @@ fn test_royalties() {
                     offset: None,
                     limit: None,
                     order: None,
+                    start_after: None,
+                    with_total: None,
                 }))
                 .unwrap(),
         )
@@ -1978,6 +2147,7 @@ fn test_royalties() {
             token_id: String::from(SELLABLE_NFT_NATIVE),
             off_price: Uint128::from(50u128),
             royalty: Some(10 * DECIMAL),
+            reserved_for: None,
         };
         manager.execute(mock_info("buyer", &vec![]), msg).unwrap();
 
@@ -1987,6 +2157,8 @@ fn test_royalties() {
                     offset: None,
                     limit: None,
                     order: None,
+                    start_after: None,
+                    with_total: None,
                 }))
                 .unwrap(),
         )
@@ -2013,6 +2185,7 @@ fn test_royalties() {
             token_id: String::from(SELLABLE_NFT_NATIVE),
             off_price: Uint128::from(50u128),
             royalty: Some(10 * DECIMAL),
+            reserved_for: None,
         };
         manager.execute(mock_info("buyer1", &vec![]), msg).unwrap();
 
@@ -2044,7 +2217,7 @@ fn test_royalties() {
         let mut royatly_marketplace = Uint128::zero();
 
         // query royalties
-        let royalties: Vec<Royalty> = from_json(
+        let royalties: PageResponse<Royalty> = from_json(
             &manager
                 .query(QueryMsg::AiRoyalty(
                     AiRoyaltyQueryMsg::GetRoyaltiesTokenId {
@@ -2052,13 +2225,15 @@ fn test_royalties() {
                         offset: None,
                         limit: None,
                         order: None,
+                        start_after: None,
+                        with_total: None,
                     },
                 ))
                 .unwrap(),
         )
         .unwrap();
         println!("royalties are: {:?}", royalties);
-        assert_eq!(royalties.len(), 2);
+        assert_eq!(royalties.items.len(), 2);
 
         // query market info to get fees
         let contract_info: ContractInfo =
@@ -2120,7 +2295,7 @@ fn test_royalties() {
         assert_eq!(flag, 2);
 
         // increment royalty to total payment
-        for royalty in royalties {
+        for royalty in royalties.items {
             let index = to_addrs.iter().position(|op| op.eq(&royalty.creator));
             if let Some(index) = index {
                 let amount = amounts[index];
@@ -2184,6 +2359,7 @@ fn test_royalties_ow20() {
             token_id: String::from(SELLABLE_NFT_CW20),
             off_price: Uint128::from(50u128),
             royalty: Some(10 * DECIMAL),
+            reserved_for: None,
         };
         manager.execute(info_sell.clone(), msg).unwrap();
 
@@ -2193,6 +2369,8 @@ fn test_royalties_ow20() {
                     offset: None,
                     limit: None,
                     order: None,
+                    start_after: None,
+                    with_total: None,
                 }))
                 .unwrap(),
         )
@@ -2224,6 +2402,7 @@ fn test_royalties_ow20() {
             token_id: String::from(SELLABLE_NFT_CW20),
             off_price: Uint128::from(50u128),
             royalty: Some(10 * DECIMAL),
+            reserved_for: None,
         };
         manager.execute(mock_info("buyer", &vec![]), msg).unwrap();
 
@@ -2233,6 +2412,8 @@ fn test_royalties_ow20() {
                     offset: None,
                     limit: None,
                     order: None,
+                    start_after: None,
+                    with_total: None,
                 }))
                 .unwrap(),
         )
@@ -2264,6 +2445,7 @@ fn test_royalties_ow20() {
             token_id: String::from(SELLABLE_NFT_CW20),
             off_price: Uint128::from(50u128),
             royalty: Some(10 * DECIMAL),
+            reserved_for: None,
         };
         manager.execute(mock_info("buyer1", &vec![]), msg).unwrap();
 
@@ -2299,7 +2481,7 @@ fn test_royalties_ow20() {
         let mut royatly_marketplace = Uint128::zero();
 
         // query royalties
-        let royalties: Vec<Royalty> = from_json(
+        let royalties: PageResponse<Royalty> = from_json(
             &manager
                 .query(QueryMsg::AiRoyalty(
                     AiRoyaltyQueryMsg::GetRoyaltiesTokenId {
@@ -2307,13 +2489,15 @@ fn test_royalties_ow20() {
                         offset: None,
                         limit: None,
                         order: None,
+                        start_after: None,
+                        with_total: None,
                     },
                 ))
                 .unwrap(),
         )
         .unwrap();
         println!("royalties are: {:?}", royalties);
-        assert_eq!(royalties.len(), 2);
+        assert_eq!(royalties.items.len(), 2);
 
         // query market info to get fees
         let contract_info: ContractInfo =
@@ -2388,7 +2572,7 @@ fn test_royalties_ow20() {
         assert_eq!(flag, 2);
 
         // increment royalty to total payment
-        for royalty in royalties {
+        for royalty in royalties.items {
             let index = to_addrs.iter().position(|op| op.eq(&royalty.creator));
             if let Some(index) = index {
                 let amount = amounts[index];
@@ -2455,6 +2639,7 @@ fn test_buy_market_fee_calculate() {
             token_id: String::from(SELLABLE_NFT_NATIVE),
             off_price: Uint128::from(100u128),
             royalty: Some(10 * DECIMAL),
+            reserved_for: None,
         };
         manager.execute(info_sell.clone(), msg).unwrap();
 
@@ -2464,6 +2649,8 @@ fn test_buy_market_fee_calculate() {
                     offset: None,
                     limit: None,
                     order: None,
+                    start_after: None,
+                    with_total: None,
                 }))
                 .unwrap(),
         )
@@ -2495,6 +2682,179 @@ fn test_buy_market_fee_calculate() {
             after_buy_market_fee,
             current_market_fee + market_fee * Uint128::from(100u128)
         );
+
+        // the storage updates of the sale go through the hub in one batch, after the transfers
+        let messages = &buy_result.last().unwrap().messages;
+        let hub_msgs: Vec<MarketHubExecuteMsg> = messages
+            .iter()
+            .filter_map(|sub_msg| match &sub_msg.msg {
+                CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr, msg, ..
+                }) if contract_addr == HUB_ADDR => Some(from_json(msg).unwrap()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(hub_msgs.len(), 1);
+        assert!(matches!(
+            &messages.last().unwrap().msg,
+            CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, .. }) if contract_addr == HUB_ADDR
+        ));
+        match &hub_msgs[0] {
+            MarketHubExecuteMsg::Storage(StorageExecuteMsg::BatchUpdateStorageData { updates }) => {
+                // the updates keep the order the sale made them in
+                let names: Vec<&str> = updates.iter().map(|(name, _)| name.as_str()).collect();
+                assert_eq!(
+                    names,
+                    vec![OFFERING_STORAGE, AI_ROYALTY_STORAGE, OFFERING_STORAGE]
+                );
+            }
+            msg => panic!("unexpected hub message: {:?}", msg),
+        }
+        let result: OfferingsResponse = from_json(
+            manager
+                .query(QueryMsg::Offering(OfferingQueryMsg::GetOfferings {
+                    offset: None,
+                    limit: None,
+                    order: None,
+                    start_after: None,
+                    with_total: None,
+                }))
+                .unwrap(),
+        )
+        .unwrap();
+        assert!(result.offerings.is_empty());
+    }
+}
+
+#[test]
+fn test_buy_fee_discount() {
+    unsafe {
+        let manager = DepsManager::get_new();
+        handle_whitelist(manager);
+
+        let fee_discount = FeeDiscount {
+            staking_contract: None,
+            token: Some(Addr::unchecked(OW20)),
+            tiers: vec![FeeDiscountTier {
+                min_staked: None,
+                min_balance: Some(Uint128::from(1000u128)),
+                discount: 500,
+            }],
+        };
+
+        // only creator can update the fee discount tiers
+        assert!(matches!(
+            manager.execute(
+                mock_info("anyone", &[]),
+                ExecuteMsg::UpdateFeeDiscount(fee_discount.clone())
+            ),
+            Err(ContractError::Unauthorized { .. })
+        ));
+        // discount cannot exceed 100%
+        assert!(matches!(
+            manager.execute(
+                mock_info(CREATOR, &[]),
+                ExecuteMsg::UpdateFeeDiscount(FeeDiscount {
+                    tiers: vec![FeeDiscountTier {
+                        min_staked: None,
+                        min_balance: None,
+                        discount: 1001,
+                    }],
+                    ..fee_discount.clone()
+                })
+            ),
+            Err(ContractError::InvalidArgument { .. })
+        ));
+        manager
+            .execute(
+                mock_info(CREATOR, &[]),
+                ExecuteMsg::UpdateFeeDiscount(fee_discount.clone()),
+            )
+            .unwrap();
+        let stored: FeeDiscount =
+            from_json(&manager.query(QueryMsg::GetFeeDiscount {}).unwrap()).unwrap();
+        assert_eq!(stored, fee_discount);
+
+        // bidder holds ow20 tokens so it gets half of the fee off
+        let effective_fee: EffectiveFeeResponse = from_json(
+            &manager
+                .query(QueryMsg::GetEffectiveFee {
+                    address: Addr::unchecked(BIDDER),
+                })
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            effective_fee,
+            EffectiveFeeResponse {
+                fee: 20,
+                discount: 500,
+                effective_fee: 10
+            }
+        );
+        let effective_fee: EffectiveFeeResponse = from_json(
+            &manager
+                .query(QueryMsg::GetEffectiveFee {
+                    address: Addr::unchecked(PROVIDER),
+                })
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(effective_fee.effective_fee, 20);
+
+        // Mint new NFT
+        let provider_info = mock_info("creator", &vec![coin(50, DENOM)]);
+        let mint_msg = ExecuteMsg::MintNft(MintMsg {
+            contract_addr: Addr::unchecked(OW721),
+            creator: Addr::unchecked(PROVIDER),
+            mint: MintIntermediate {
+                mint: MintStruct {
+                    token_id: String::from(SELLABLE_NFT),
+                    owner: Addr::unchecked(PROVIDER),
+                    name: String::from("asbv"),
+                    description: None,
+                    image: String::from("baxv"),
+                },
+            },
+            creator_type: String::from("sacx"),
+            royalty: Some(40 * DECIMAL),
+        });
+        manager.execute(provider_info, mint_msg).unwrap();
+
+        let _result = oraichain_nft::contract::execute(
+            manager.ow721.as_mut(),
+            mock_env(OW721),
+            mock_info(PROVIDER, &vec![]),
+            oraichain_nft::msg::ExecuteMsg::ApproveAll {
+                operator: Addr::unchecked(MARKET_ADDR),
+                expires: None,
+            },
+        );
+
+        let msg = ExecuteMsg::SellNft {
+            contract_addr: Addr::unchecked(OW721),
+            token_id: String::from(SELLABLE_NFT_NATIVE),
+            off_price: Uint128::from(1000u128),
+            royalty: None,
+            reserved_for: None,
+        };
+        manager.execute(mock_info(PROVIDER, &[]), msg).unwrap();
+
+        let current_market_fee: Uint128 =
+            from_json(&manager.query(QueryMsg::GetMarketFees {}).unwrap()).unwrap();
+        manager
+            .execute(
+                mock_info(BIDDER, &coins(1000, DENOM)),
+                ExecuteMsg::BuyNft { offering_id: 1 },
+            )
+            .unwrap();
+        let after_buy_market_fee: Uint128 =
+            from_json(&manager.query(QueryMsg::GetMarketFees {}).unwrap()).unwrap();
+        // 1% discounted market fee of 1000 = 10
+        assert_eq!(
+            after_buy_market_fee,
+            current_market_fee + Uint128::from(10u128)
+        );
     }
 }
 
@@ -2548,6 +2908,7 @@ fn withdraw_offering() {
             token_id: String::from(SELLABLE_NFT_NATIVE),
             off_price: Uint128::from(10u64),
             royalty: None,
+            reserved_for: None,
         };
 
         let _res = manager.execute(mock_info(PROVIDER, &vec![]), msg).unwrap();
@@ -2558,12 +2919,28 @@ fn withdraw_offering() {
                     offset: None,
                     limit: None,
                     order: None,
+                    start_after: None,
+                    with_total: None,
                 }))
                 .unwrap(),
         )
         .unwrap();
         assert_eq!(1, res.offerings.len());
 
+        // the payment asset is recorded so the floor can be filtered on it
+        let floor: Option<QueryOfferingsResult> = from_json(
+            manager
+                .query(QueryMsg::Offering(OfferingQueryMsg::GetFloorOffering {
+                    contract: Addr::unchecked(OW721),
+                    asset_info: Some(AssetInfo::NativeToken {
+                        denom: DENOM.to_string(),
+                    }),
+                }))
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(floor.unwrap().id, res.offerings[0].id);
+
         let withdraw_info_unauthorized = mock_info("providerasd", &coins(2, DENOM));
         let withdraw_msg = ExecuteMsg::WithdrawNft {
             offering_id: res.offerings[0].id.clone(),
@@ -2586,6 +2963,8 @@ fn withdraw_offering() {
                     offset: None,
                     limit: None,
                     order: None,
+                    start_after: None,
+                    with_total: None,
                 }))
                 .unwrap(),
         )
@@ -2634,6 +3013,7 @@ fn withdraw_verify_owner() {
             token_id: String::from(SELLABLE_NFT_NATIVE),
             off_price: Uint128::from(10u64),
             royalty: None,
+            reserved_for: None,
         };
 
         let _res = manager.execute(mock_info(PROVIDER, &vec![]), msg).unwrap();
@@ -2669,6 +3049,8 @@ fn withdraw_verify_owner() {
                     offset: None,
                     limit: None,
                     order: None,
+                    start_after: None,
+                    with_total: None,
                 }))
                 .unwrap(),
         )
@@ -2733,6 +3115,7 @@ fn admin_withdraw_offering() {
             token_id: String::from(SELLABLE_NFT_NATIVE),
             off_price: Uint128::from(10u64),
             royalty: None,
+            reserved_for: None,
         };
 
         let _res = manager.execute(mock_info(PROVIDER, &vec![]), msg).unwrap();
@@ -2744,6 +3127,8 @@ fn admin_withdraw_offering() {
                     offset: None,
                     limit: None,
                     order: None,
+                    start_after: None,
+                    with_total: None,
                 }))
                 .unwrap(),
         )
@@ -2766,6 +3151,8 @@ fn admin_withdraw_offering() {
                     offset: None,
                     limit: None,
                     order: None,
+                    start_after: None,
+                    with_total: None,
                 }))
                 .unwrap(),
         )
@@ -2813,6 +3200,7 @@ fn test_sell_nft_unhappy() {
             token_id: String::from(SELLABLE_NFT_NATIVE),
             off_price: Uint128::from(10u64),
             royalty: None,
+            reserved_for: None,
         };
 
         let _res = manager
@@ -2828,21 +3216,55 @@ fn test_sell_nft_unhappy() {
 }
 
 #[test]
-fn test_buy_nft_unhappy() {
+fn test_collection_listing_rules() {
     unsafe {
         let manager = DepsManager::get_new();
-        handle_whitelist(manager);
-        let buy_msg = ExecuteMsg::BuyNft { offering_id: 1 };
-        let info_buy = mock_info("buyer", &coins(10, DENOM));
-
-        // offering not found
-        assert!(matches!(
-            manager.execute(info_buy.clone(), buy_msg.clone()),
-            Err(ContractError::InvalidGetOffering {})
-        ));
+        // the collection is registered for auctions from 100 orai only, and 1000 in airi
+        market_whitelist_storage::contract::execute(
+            manager.whitelist.as_mut(),
+            mock_env(WHITELIST_ADDR),
+            mock_info(PROVIDER, &[]),
+            market_whitelist_storage::msg::ExecuteMsg::Msg(
+                MarketWhiteListExecuteMsg::ApplyCollection {
+                    nft_addr: OW721.to_string(),
+                    metadata: CollectionMetadata {
+                        name: String::from("oraichain nft"),
+                        category: None,
+                        creator: None,
+                    },
+                    listing_types: vec![ListingType::Auction],
+                    min_prices: Some(vec![
+                        MinPrice {
+                            asset_info: AssetInfo::Token {
+                                contract_addr: Addr::unchecked(OW20),
+                            },
+                            amount: Uint128::from(1000u64),
+                        },
+                        MinPrice {
+                            asset_info: AssetInfo::NativeToken {
+                                denom: DENOM.to_string(),
+                            },
+                            amount: Uint128::from(100u64),
+                        },
+                    ]),
+                },
+            ),
+        )
+        .unwrap();
+        market_whitelist_storage::contract::execute(
+            manager.whitelist.as_mut(),
+            mock_env(WHITELIST_ADDR),
+            mock_info(CREATOR, &[]),
+            market_whitelist_storage::msg::ExecuteMsg::Msg(
+                MarketWhiteListExecuteMsg::ApproveCollection {
+                    nft_addr: OW721.to_string(),
+                    verified: true,
+                    expires: None,
+                },
+            ),
+        )
+        .unwrap();
 
-        // beneficiary can release it
-        let provider_info = mock_info("creator", &vec![coin(50, DENOM)]);
         let mint_msg = ExecuteMsg::MintNft(MintMsg {
             contract_addr: Addr::unchecked(OW721),
             creator: Addr::unchecked(PROVIDER),
@@ -2858,28 +3280,111 @@ fn test_buy_nft_unhappy() {
             creator_type: String::from("sacx"),
             royalty: Some(40 * DECIMAL),
         });
-
-        manager.execute(provider_info.clone(), mint_msg).unwrap();
-
+        manager
+            .execute(mock_info("creator", &[coin(50, DENOM)]), mint_msg)
+            .unwrap();
         let _result = oraichain_nft::contract::execute(
             manager.ow721.as_mut(),
             mock_env(OW721),
-            mock_info(PROVIDER, &vec![]),
+            mock_info(PROVIDER, &[]),
             oraichain_nft::msg::ExecuteMsg::ApproveAll {
                 operator: Addr::unchecked(MARKET_ADDR),
                 expires: None,
             },
         );
 
-        let msg = ExecuteMsg::SellNft {
+        assert!(matches!(
+            manager.execute(
+                mock_info(PROVIDER, &[]),
+                ExecuteMsg::SellNft {
+                    contract_addr: Addr::unchecked(OW721),
+                    token_id: String::from(SELLABLE_NFT_NATIVE),
+                    off_price: Uint128::from(100u64),
+                    royalty: None,
+                    reserved_for: None,
+                },
+            ),
+            Err(ContractError::ListingTypeNotAllowed { .. })
+        ));
+
+        let ask_msg = |price: u64| ExecuteMsg::AskNft {
             contract_addr: Addr::unchecked(OW721),
             token_id: String::from(SELLABLE_NFT_NATIVE),
-            off_price: Uint128::from(11u64),
+            price: Uint128::from(price),
+            cancel_fee: None,
+            start: None,
+            end: None,
+            buyout_price: None,
+            start_timestamp: None,
+            end_timestamp: None,
+            step_price: None,
             royalty: None,
+            reserved_for: None,
         };
-
-        let _res = manager
-            .execute(mock_info(PROVIDER, &vec![]), msg.clone())
+        assert!(matches!(
+            manager.execute(mock_info(PROVIDER, &[]), ask_msg(99)),
+            Err(ContractError::PriceBelowCollectionMin { .. })
+        ));
+        manager
+            .execute(mock_info(PROVIDER, &[]), ask_msg(100))
+            .unwrap();
+    }
+}
+
+#[test]
+fn test_buy_nft_unhappy() {
+    unsafe {
+        let manager = DepsManager::get_new();
+        handle_whitelist(manager);
+        let buy_msg = ExecuteMsg::BuyNft { offering_id: 1 };
+        let info_buy = mock_info("buyer", &coins(10, DENOM));
+
+        // offering not found
+        assert!(matches!(
+            manager.execute(info_buy.clone(), buy_msg.clone()),
+            Err(ContractError::InvalidGetOffering {})
+        ));
+
+        // beneficiary can release it
+        let provider_info = mock_info("creator", &vec![coin(50, DENOM)]);
+        let mint_msg = ExecuteMsg::MintNft(MintMsg {
+            contract_addr: Addr::unchecked(OW721),
+            creator: Addr::unchecked(PROVIDER),
+            mint: MintIntermediate {
+                mint: MintStruct {
+                    token_id: String::from(SELLABLE_NFT),
+                    owner: Addr::unchecked(PROVIDER),
+                    name: String::from("asbv"),
+                    description: None,
+                    image: String::from("baxv"),
+                },
+            },
+            creator_type: String::from("sacx"),
+            royalty: Some(40 * DECIMAL),
+        });
+
+        manager.execute(provider_info.clone(), mint_msg).unwrap();
+
+        let _result = oraichain_nft::contract::execute(
+            manager.ow721.as_mut(),
+            mock_env(OW721),
+            mock_info(PROVIDER, &vec![]),
+            oraichain_nft::msg::ExecuteMsg::ApproveAll {
+                operator: Addr::unchecked(MARKET_ADDR),
+                expires: None,
+            },
+        );
+
+        let msg = ExecuteMsg::SellNft {
+            contract_addr: Addr::unchecked(OW721),
+            token_id: String::from(SELLABLE_NFT_NATIVE),
+            off_price: Uint128::from(11u64),
+            royalty: None,
+            reserved_for: None,
+        };
+
+        let _res = manager
+            .execute(mock_info(PROVIDER, &vec![]), msg.clone())
             .unwrap();
 
         // wrong denom
@@ -2900,14 +3405,628 @@ fn test_buy_nft_unhappy() {
 }
 
 #[test]
-fn test_update_decay_royalty() {
+fn test_buy_reserved_nft() {
+    unsafe {
+        let manager = DepsManager::get_new();
+        handle_whitelist(manager);
+
+        let provider_info = mock_info("creator", &vec![coin(50, DENOM)]);
+        let mint_msg = ExecuteMsg::MintNft(MintMsg {
+            contract_addr: Addr::unchecked(OW721),
+            creator: Addr::unchecked(PROVIDER),
+            mint: MintIntermediate {
+                mint: MintStruct {
+                    token_id: String::from(SELLABLE_NFT),
+                    owner: Addr::unchecked(PROVIDER),
+                    name: String::from("asbv"),
+                    description: None,
+                    image: String::from("baxv"),
+                },
+            },
+            creator_type: String::from("sacx"),
+            royalty: Some(40 * DECIMAL),
+        });
+        manager.execute(provider_info, mint_msg).unwrap();
+
+        let _result = oraichain_nft::contract::execute(
+            manager.ow721.as_mut(),
+            mock_env(OW721),
+            mock_info(PROVIDER, &vec![]),
+            oraichain_nft::msg::ExecuteMsg::ApproveAll {
+                operator: Addr::unchecked(MARKET_ADDR),
+                expires: None,
+            },
+        );
+
+        // nobody could buy a sale reserved for an empty list
+        let msg = ExecuteMsg::SellNft {
+            contract_addr: Addr::unchecked(OW721),
+            token_id: String::from(SELLABLE_NFT_NATIVE),
+            off_price: Uint128::from(50u128),
+            royalty: None,
+            reserved_for: Some(vec![]),
+        };
+        assert!(matches!(
+            manager.execute(mock_info(PROVIDER, &[]), msg),
+            Err(ContractError::Reserved(
+                ReservedError::EmptyReservedBuyers {}
+            ))
+        ));
+
+        // private sale for the negotiated buyer only
+        let msg = ExecuteMsg::SellNft {
+            contract_addr: Addr::unchecked(OW721),
+            token_id: String::from(SELLABLE_NFT_NATIVE),
+            off_price: Uint128::from(50u128),
+            royalty: None,
+            reserved_for: Some(vec![Addr::unchecked("buyer")]),
+        };
+        manager.execute(mock_info(PROVIDER, &vec![]), msg).unwrap();
+
+        let offering: QueryOfferingsResult = from_json(
+            &manager
+                .query(QueryMsg::Offering(OfferingQueryMsg::GetOffering {
+                    offering_id: 1,
+                }))
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(offering.reserved_for, Some(vec![Addr::unchecked("buyer")]));
+
+        let buy_msg = ExecuteMsg::BuyNft { offering_id: 1 };
+        assert!(matches!(
+            manager.execute(
+                mock_info("front_runner", &coins(50, DENOM)),
+                buy_msg.clone()
+            ),
+            Err(ContractError::Reserved(
+                ReservedError::NotReservedBuyer { .. }
+            ))
+        ));
+
+        manager
+            .execute(mock_info("buyer", &coins(50, DENOM)), buy_msg)
+            .unwrap();
+    }
+}
+
+#[test]
+fn test_update_decay_royalty() {
+    unsafe {
+        let manager = DepsManager::get_new();
+        handle_whitelist(manager);
+        // try mint nft to get royalty for provider
+        let creator_info = mock_info("creator", &vec![coin(50, DENOM)]);
+        let mint_msg = ExecuteMsg::MintNft(MintMsg {
+            contract_addr: Addr::unchecked("offering"),
+            creator: Addr::unchecked(PROVIDER),
+            mint: MintIntermediate {
+                mint: MintStruct {
+                    token_id: String::from(SELLABLE_NFT),
+                    owner: Addr::unchecked(PROVIDER),
+                    name: String::from("asbv"),
+                    description: None,
+                    image: String::from("baxv"),
+                },
+            },
+            creator_type: String::from("sacx"),
+            royalty: Some(40 * DECIMAL),
+        });
+
+        manager.execute(creator_info.clone(), mint_msg).unwrap();
+
+        let royalties: PageResponse<Royalty> = from_json(
+            &manager
+                .query(QueryMsg::AiRoyalty(AiRoyaltyQueryMsg::GetRoyalties {
+                    offset: None,
+                    limit: None,
+                    order: None,
+                    start_after: None,
+                    with_total: None,
+                }))
+                .unwrap(),
+        )
+        .unwrap();
+        println!("royalties: {:?}", royalties);
+
+        let mut royalty_msg = RoyaltyMsg {
+            contract_addr: Addr::unchecked("offering"),
+            token_id: String::from(SELLABLE_NFT),
+            creator: Addr::unchecked("somebody"),
+            creator_type: None,
+            royalty: Some(10 * DECIMAL),
+            payees: None,
+        };
+
+        // update creator royalty
+        let update_msg = ExecuteMsg::UpdateCreatorRoyalty(royalty_msg.clone());
+        manager
+            .execute(creator_info.clone(), update_msg.clone())
+            .unwrap();
+
+        // try to update royalty 20 now will only be 10
+        royalty_msg.royalty = Some(20 * DECIMAL);
+        manager.execute(creator_info.clone(), update_msg).unwrap();
+
+        // query creator royalty
+        let royalty: Royalty = from_json(
+            &manager
+                .query(QueryMsg::AiRoyalty(AiRoyaltyQueryMsg::GetRoyalty {
+                    contract_addr: Addr::unchecked("offering"),
+                    token_id: String::from(SELLABLE_NFT),
+                    creator: Addr::unchecked("creator"),
+                }))
+                .unwrap(),
+        )
+        .unwrap();
+        println!("new royalty: {:?}", royalty);
+        assert_eq!(royalty.royalty, 10 * DECIMAL);
+    }
+}
+
+#[test]
+fn test_provenance_royalties() {
+    unsafe {
+        let manager = DepsManager::get_new();
+        handle_whitelist(manager);
+        let mint_msg = ExecuteMsg::MintNft(MintMsg {
+            contract_addr: Addr::unchecked(OW721),
+            creator: Addr::unchecked(PROVIDER),
+            mint: MintIntermediate {
+                mint: MintStruct {
+                    token_id: String::from(SELLABLE_NFT),
+                    owner: Addr::unchecked(PROVIDER),
+                    name: String::from("asbv"),
+                    description: None,
+                    image: String::from("baxv"),
+                },
+            },
+            creator_type: String::from("sacx"),
+            royalty: Some(0),
+        });
+        manager
+            .execute(mock_info("creator", &[]), mint_msg)
+            .unwrap();
+
+        // the last owner receives 2%, the one before 1%
+        let mut update_info = UpdateContractMsg {
+            name: None,
+            creator: None,
+            denom: None,
+            fee: None,
+            auction_duration: None,
+            step_price: None,
+            governance: None,
+            decimal_point: None,
+            max_royalty: None,
+            provenance_royalties: Some(vec![MAX_ROYALTY_PERCENT, 1]),
+            accrue_royalties: None,
+        };
+        assert!(manager
+            .execute(
+                mock_info(CREATOR, &[]),
+                ExecuteMsg::UpdateInfo(update_info.clone())
+            )
+            .is_err());
+        // royalties that do not decay are rejected
+        update_info.provenance_royalties = Some(vec![DECIMAL, 2 * DECIMAL]);
+        assert!(matches!(
+            manager.execute(
+                mock_info(CREATOR, &[]),
+                ExecuteMsg::UpdateInfo(update_info.clone())
+            ),
+            Err(ContractError::InvalidArgument { .. })
+        ));
+        update_info.provenance_royalties = Some(vec![DECIMAL, DECIMAL]);
+        assert!(matches!(
+            manager.execute(
+                mock_info(CREATOR, &[]),
+                ExecuteMsg::UpdateInfo(update_info.clone())
+            ),
+            Err(ContractError::InvalidArgument { .. })
+        ));
+        // an overflowing total is an error instead of a panic
+        update_info.provenance_royalties = Some(vec![u64::MAX, u64::MAX - 1]);
+        assert!(matches!(
+            manager.execute(
+                mock_info(CREATOR, &[]),
+                ExecuteMsg::UpdateInfo(update_info.clone())
+            ),
+            Err(ContractError::InvalidArgument { .. })
+        ));
+        update_info.provenance_royalties = Some(vec![2 * DECIMAL, DECIMAL]);
+        manager
+            .execute(mock_info(CREATOR, &[]), ExecuteMsg::UpdateInfo(update_info))
+            .unwrap();
+
+        // the nft goes from the provider to buyer, buyer1 and finally buyer2
+        let owners = [PROVIDER, "buyer", "buyer1", "buyer2"];
+        let mut results = vec![];
+        for (i, seller) in owners[..3].iter().enumerate() {
+            oraichain_nft::contract::execute(
+                manager.ow721.as_mut(),
+                mock_env(OW721),
+                mock_info(seller, &[]),
+                oraichain_nft::msg::ExecuteMsg::ApproveAll {
+                    operator: Addr::unchecked(MARKET_ADDR),
+                    expires: None,
+                },
+            )
+            .unwrap();
+            manager
+                .execute(
+                    mock_info(seller, &[]),
+                    ExecuteMsg::SellNft {
+                        contract_addr: Addr::unchecked(OW721),
+                        token_id: String::from(SELLABLE_NFT_NATIVE),
+                        off_price: Uint128::from(1000u128),
+                        royalty: None,
+                        reserved_for: None,
+                    },
+                )
+                .unwrap();
+
+            if i == 2 {
+                let provenance: Vec<Royalty> = from_json(
+                    &manager
+                        .query(QueryMsg::GetProvenanceRoyalties {
+                            contract: Addr::unchecked(OW721),
+                            token_id: String::from(SELLABLE_NFT),
+                        })
+                        .unwrap(),
+                )
+                .unwrap();
+                assert_eq!(
+                    provenance
+                        .iter()
+                        .map(|royalty| (royalty.creator.as_str(), royalty.royalty))
+                        .collect::<Vec<_>>(),
+                    vec![("buyer", 2 * DECIMAL), (PROVIDER, DECIMAL)]
+                );
+            }
+
+            results = manager
+                .execute(
+                    mock_info(owners[i + 1], &coins(1000, DENOM)),
+                    ExecuteMsg::BuyNft {
+                        offering_id: i as u64 + 1,
+                    },
+                )
+                .unwrap();
+        }
+
+        let mut payments: Vec<(String, Uint128)> = vec![];
+        for result in results {
+            for message in result.messages {
+                if let CosmosMsg::Bank(cosmwasm_std::BankMsg::Send { to_address, amount }) =
+                    message.msg
+                {
+                    payments.push((to_address, amount[0].amount));
+                }
+            }
+        }
+
+        // price 1000 minus 2% market fee => 980, the last owner gets 2% => 19 and the one before 1% => 9
+        assert!(payments.contains(&(String::from("buyer"), Uint128::from(19u128))));
+        assert!(payments.contains(&(String::from(PROVIDER), Uint128::from(9u128))));
+    }
+}
+
+#[test]
+fn test_simulate_payout_total_royalty() {
+    unsafe {
+        let manager = DepsManager::get_new();
+        handle_whitelist(manager);
+        let creator_info = mock_info("creator", &[]);
+        let mint_msg = ExecuteMsg::MintNft(MintMsg {
+            contract_addr: Addr::unchecked(OW721),
+            creator: Addr::unchecked(PROVIDER),
+            mint: MintIntermediate {
+                mint: MintStruct {
+                    token_id: String::from(SELLABLE_NFT),
+                    owner: Addr::unchecked(PROVIDER),
+                    name: String::from("asbv"),
+                    description: None,
+                    image: String::from("baxv"),
+                },
+            },
+            creator_type: String::from("sacx"),
+            royalty: Some(40 * DECIMAL),
+        });
+        manager.execute(creator_info.clone(), mint_msg).unwrap();
+
+        // a token minted before the total cap may carry more royalties than its price
+        market_ai_royalty_storage::state::royalties_map()
+            .save(
+                manager.ai_royalty.as_mut().storage,
+                &market_ai_royalty_storage::state::get_key_royalty(
+                    OW721.as_bytes(),
+                    SELLABLE_NFT.as_bytes(),
+                    b"legacy",
+                ),
+                &Royalty {
+                    contract_addr: Addr::unchecked(OW721),
+                    token_id: String::from(SELLABLE_NFT),
+                    creator: Addr::unchecked("legacy"),
+                    royalty: 90 * DECIMAL,
+                    creator_type: String::from("creator"),
+                    payees: None,
+                },
+            )
+            .unwrap();
+
+        // lowering a royalty is still allowed on an over cap token
+        let royalty_msg = RoyaltyMsg {
+            contract_addr: Addr::unchecked(OW721),
+            token_id: String::from(SELLABLE_NFT),
+            creator: Addr::unchecked("creator"),
+            creator_type: None,
+            royalty: Some(40 * DECIMAL),
+            payees: None,
+        };
+        manager
+            .execute(
+                creator_info.clone(),
+                ExecuteMsg::UpdateCreatorRoyalty(royalty_msg),
+            )
+            .unwrap();
+
+        let payout: PayoutResponse = from_json(
+            &manager
+                .query(QueryMsg::SimulatePayout(SimulatePayoutMsg {
+                    contract: Addr::unchecked(OW721),
+                    token_id: String::from(SELLABLE_NFT),
+                    price: Uint128::from(1000u128),
+                    seller: Addr::unchecked(PROVIDER),
+                    buyer: Some(Addr::unchecked("buyer")),
+                }))
+                .unwrap(),
+        )
+        .unwrap();
+        // 980 after the 2% fee, the royalties of 392 and 882 are scaled down to fit
+        assert_eq!(payout.fee, Uint128::from(20u128));
+        assert_eq!(
+            payout
+                .royalties
+                .iter()
+                .map(|payout| (payout.creator.as_str(), payout.amount.u128()))
+                .collect::<Vec<_>>(),
+            vec![("creator", 301), ("legacy", 678)]
+        );
+        assert_eq!(payout.seller_amount, Uint128::from(1u128));
+
+        // the sale pays exactly the simulated amounts
+        oraichain_nft::contract::execute(
+            manager.ow721.as_mut(),
+            mock_env(OW721),
+            mock_info(PROVIDER, &[]),
+            oraichain_nft::msg::ExecuteMsg::ApproveAll {
+                operator: Addr::unchecked(MARKET_ADDR),
+                expires: None,
+            },
+        )
+        .unwrap();
+        manager
+            .execute(
+                mock_info(PROVIDER, &[]),
+                ExecuteMsg::SellNft {
+                    contract_addr: Addr::unchecked(OW721),
+                    token_id: String::from(SELLABLE_NFT_NATIVE),
+                    off_price: Uint128::from(1000u128),
+                    royalty: None,
+                    reserved_for: None,
+                },
+            )
+            .unwrap();
+        let results = manager
+            .execute(
+                mock_info("buyer", &coins(1000, DENOM)),
+                ExecuteMsg::BuyNft { offering_id: 1 },
+            )
+            .unwrap();
+        let mut payments: Vec<(String, u128)> = vec![];
+        for result in results {
+            for message in result.messages {
+                if let CosmosMsg::Bank(cosmwasm_std::BankMsg::Send { to_address, amount }) =
+                    message.msg
+                {
+                    payments.push((to_address, amount[0].amount.u128()));
+                }
+            }
+        }
+        assert_eq!(
+            payments,
+            vec![
+                (String::from("creator"), 301),
+                (String::from("legacy"), 678),
+                (String::from(PROVIDER), 1)
+            ]
+        );
+    }
+}
+
+#[test]
+fn test_accrue_and_claim_royalties() {
+    unsafe {
+        let manager = DepsManager::get_new();
+        handle_whitelist(manager);
+        let mint_msg = ExecuteMsg::MintNft(MintMsg {
+            contract_addr: Addr::unchecked(OW721),
+            creator: Addr::unchecked(PROVIDER),
+            mint: MintIntermediate {
+                mint: MintStruct {
+                    token_id: String::from(SELLABLE_NFT),
+                    owner: Addr::unchecked(PROVIDER),
+                    name: String::from("asbv"),
+                    description: None,
+                    image: String::from("baxv"),
+                },
+            },
+            creator_type: String::from("sacx"),
+            royalty: Some(40 * DECIMAL),
+        });
+        manager
+            .execute(mock_info("creator", &[]), mint_msg)
+            .unwrap();
+
+        manager
+            .execute(
+                mock_info(CREATOR, &[]),
+                ExecuteMsg::UpdateInfo(UpdateContractMsg {
+                    name: None,
+                    creator: None,
+                    denom: None,
+                    fee: None,
+                    auction_duration: None,
+                    step_price: None,
+                    governance: None,
+                    decimal_point: None,
+                    max_royalty: None,
+                    provenance_royalties: None,
+                    accrue_royalties: Some(true),
+                }),
+            )
+            .unwrap();
+
+        oraichain_nft::contract::execute(
+            manager.ow721.as_mut(),
+            mock_env(OW721),
+            mock_info(PROVIDER, &[]),
+            oraichain_nft::msg::ExecuteMsg::ApproveAll {
+                operator: Addr::unchecked(MARKET_ADDR),
+                expires: None,
+            },
+        )
+        .unwrap();
+        manager
+            .execute(
+                mock_info(PROVIDER, &[]),
+                ExecuteMsg::SellNft {
+                    contract_addr: Addr::unchecked(OW721),
+                    token_id: String::from(SELLABLE_NFT_NATIVE),
+                    off_price: Uint128::from(1000u128),
+                    royalty: None,
+                    reserved_for: None,
+                },
+            )
+            .unwrap();
+        let bank_sends = |results: Vec<Response>| {
+            let mut payments: Vec<(String, u128)> = vec![];
+            for result in results {
+                for message in result.messages {
+                    if let CosmosMsg::Bank(cosmwasm_std::BankMsg::Send { to_address, amount }) =
+                        message.msg
+                    {
+                        payments.push((to_address, amount[0].amount.u128()));
+                    }
+                }
+            }
+            payments
+        };
+
+        // the seller is paid 1000 - 2% fee - 40% royalty, the royalty is escrowed by the ai royalty storage
+        let results = manager
+            .execute(
+                mock_info("buyer", &coins(1000, DENOM)),
+                ExecuteMsg::BuyNft { offering_id: 1 },
+            )
+            .unwrap();
+        assert_eq!(
+            bank_sends(results),
+            vec![
+                (String::from(AI_ROYALTY_ADDR), 392),
+                (String::from(PROVIDER), 588)
+            ]
+        );
+
+        let balances: Vec<RoyaltyBalance> = from_json(
+            &manager
+                .query(QueryMsg::AiRoyalty(AiRoyaltyQueryMsg::GetRoyaltyBalances {
+                    creator: Addr::unchecked("creator"),
+                }))
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(balances[0].pending, Uint128::from(392u128));
+
+        // the hub switches to a new implementation, which never received the royalties
+        let mut new_market =
+            mock_dependencies(Addr::unchecked(MARKET_ADDR), &[], DepsManager::query_wasm);
+        instantiate(
+            new_market.as_mut(),
+            mock_env(MARKET_ADDR),
+            mock_info(CREATOR, &[]),
+            InstantiateMsg {
+                name: String::from(CONTRACT_NAME),
+                denom: DENOM.into(),
+                fee: 20,
+                auction_duration: Uint128::from(10000000000000u64),
+                step_price: 1,
+                governance: Addr::unchecked(HUB_ADDR),
+                max_royalty: MAX_ROYALTY_PERCENT,
+                max_decimal_point: MAX_DECIMAL_POINT,
+            },
+        )
+        .unwrap();
+        manager.deps = new_market;
+
+        // the claim is paid by the storage, the new implementation transfers nothing
+        let results = manager
+            .execute(mock_info("creator", &[]), ExecuteMsg::ClaimRoyalties {})
+            .unwrap();
+        assert!(!results
+            .last()
+            .unwrap()
+            .messages
+            .iter()
+            .any(|msg| matches!(msg.msg, CosmosMsg::Bank(_))));
+        assert_eq!(bank_sends(results), vec![(String::from("creator"), 392)]);
+
+        let balances: Vec<RoyaltyBalance> = from_json(
+            &manager
+                .query(QueryMsg::AiRoyalty(AiRoyaltyQueryMsg::GetRoyaltyBalances {
+                    creator: Addr::unchecked("creator"),
+                }))
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(balances[0].pending, Uint128::zero());
+        assert_eq!(balances[0].lifetime, Uint128::from(392u128));
+        assert!(matches!(
+            manager.execute(mock_info("creator", &[]), ExecuteMsg::ClaimRoyalties {}),
+            Err(ContractError::NoRoyaltiesToClaim {})
+        ));
+    }
+}
+
+#[test]
+fn test_royalty_policy() {
     unsafe {
         let manager = DepsManager::get_new();
         handle_whitelist(manager);
-        // try mint nft to get royalty for provider
-        let creator_info = mock_info("creator", &vec![coin(50, DENOM)]);
+        let policy = RoyaltyPolicy {
+            creator_type: String::from("sacx"),
+            default_royalty: 5 * DECIMAL,
+            min_royalty: 0,
+            max_royalty: 10 * DECIMAL,
+            survives_transfer: false,
+        };
+        assert!(matches!(
+            manager.execute(
+                mock_info("anyone", &[]),
+                ExecuteMsg::UpdateRoyaltyPolicy(policy.clone())
+            ),
+            Err(ContractError::Unauthorized { .. })
+        ));
+        manager
+            .execute(
+                mock_info(CREATOR, &[]),
+                ExecuteMsg::UpdateRoyaltyPolicy(policy),
+            )
+            .unwrap();
+
         let mint_msg = ExecuteMsg::MintNft(MintMsg {
-            contract_addr: Addr::unchecked("offering"),
+            contract_addr: Addr::unchecked(OW721),
             creator: Addr::unchecked(PROVIDER),
             mint: MintIntermediate {
                 mint: MintStruct {
@@ -2921,52 +4040,76 @@ fn test_update_decay_royalty() {
             creator_type: String::from("sacx"),
             royalty: Some(40 * DECIMAL),
         });
-
-        manager.execute(creator_info.clone(), mint_msg).unwrap();
-
-        let royalties: Vec<Royalty> = from_json(
-            &manager
-                .query(QueryMsg::AiRoyalty(AiRoyaltyQueryMsg::GetRoyalties {
-                    offset: None,
-                    limit: None,
-                    order: None,
-                }))
-                .unwrap(),
-        )
-        .unwrap();
-        println!("royalties: {:?}", royalties);
-
-        let mut royalty_msg = RoyaltyMsg {
-            contract_addr: Addr::unchecked("offering"),
-            token_id: String::from(SELLABLE_NFT),
-            creator: Addr::unchecked("somebody"),
-            creator_type: None,
-            royalty: Some(10 * DECIMAL),
-        };
-
-        // update creator royalty
-        let update_msg = ExecuteMsg::UpdateCreatorRoyalty(royalty_msg.clone());
         manager
-            .execute(creator_info.clone(), update_msg.clone())
+            .execute(mock_info("creator", &[]), mint_msg)
             .unwrap();
+        let query_royalties = |manager: &DepsManager| -> Vec<(String, u64)> {
+            let royalties: PageResponse<Royalty> = from_json(
+                manager
+                    .query(QueryMsg::AiRoyalty(
+                        AiRoyaltyQueryMsg::GetRoyaltiesContractTokenId {
+                            contract_addr: Addr::unchecked(OW721),
+                            token_id: String::from(SELLABLE_NFT),
+                            offset: None,
+                            limit: None,
+                            order: Some(1),
+                            start_after: None,
+                            with_total: None,
+                        },
+                    ))
+                    .unwrap(),
+            )
+            .unwrap();
+            let mut royalties: Vec<(String, u64)> = royalties
+                .items
+                .into_iter()
+                .map(|royalty| (royalty.creator.to_string(), royalty.royalty))
+                .collect();
+            royalties.sort();
+            royalties
+        };
+        // the provider gets the default royalty of its creator type
+        assert_eq!(
+            query_royalties(manager),
+            vec![
+                (String::from("creator"), 40 * DECIMAL),
+                (String::from(PROVIDER), 5 * DECIMAL)
+            ]
+        );
 
-        // try to update royalty 20 now will only be 10
-        royalty_msg.royalty = Some(20 * DECIMAL);
-        manager.execute(creator_info.clone(), update_msg).unwrap();
-
-        // query creator royalty
-        let royalty: Royalty = from_json(
-            &manager
-                .query(QueryMsg::AiRoyalty(AiRoyaltyQueryMsg::GetRoyalty {
-                    contract_addr: Addr::unchecked("offering"),
-                    token_id: String::from(SELLABLE_NFT),
-                    creator: Addr::unchecked("creator"),
-                }))
-                .unwrap(),
+        // the provider royalty is paid on the first sale only
+        oraichain_nft::contract::execute(
+            manager.ow721.as_mut(),
+            mock_env(OW721),
+            mock_info(PROVIDER, &[]),
+            oraichain_nft::msg::ExecuteMsg::ApproveAll {
+                operator: Addr::unchecked(MARKET_ADDR),
+                expires: None,
+            },
         )
         .unwrap();
-        println!("new royalty: {:?}", royalty);
-        assert_eq!(royalty.royalty, 10 * DECIMAL);
+        manager
+            .execute(
+                mock_info(PROVIDER, &[]),
+                ExecuteMsg::SellNft {
+                    contract_addr: Addr::unchecked(OW721),
+                    token_id: String::from(SELLABLE_NFT_NATIVE),
+                    off_price: Uint128::from(1000u128),
+                    royalty: None,
+                    reserved_for: None,
+                },
+            )
+            .unwrap();
+        manager
+            .execute(
+                mock_info("buyer", &coins(1000, DENOM)),
+                ExecuteMsg::BuyNft { offering_id: 1 },
+            )
+            .unwrap();
+        assert_eq!(
+            query_royalties(manager),
+            vec![(String::from("creator"), 40 * DECIMAL)]
+        );
     }
 }
 
@@ -3087,6 +4230,7 @@ fn test_transfer_nft_onsale_directly() {
             token_id: String::from(SELLABLE_NFT),
             off_price: Uint128::from(11u64),
             royalty: None,
+            reserved_for: None,
         };
 
         let _res = manager
@@ -3160,3 +4304,339 @@ fn update_approve_all() {
         )
     }
 }
+
+fn order_signer() -> (k256::ecdsa::SigningKey, Binary, Addr) {
+    use bech32::{ToBase32, Variant};
+    use ripemd::{Digest, Ripemd160};
+
+    let signing_key = k256::ecdsa::SigningKey::from_bytes(&[7u8; 32].into()).unwrap();
+    let pubkey = signing_key.verifying_key().to_sec1_bytes().to_vec();
+    let account = Ripemd160::digest(<sha2::Sha256 as sha2::Digest>::digest(&pubkey));
+    let seller = bech32::encode("orai", account.to_base32(), Variant::Bech32).unwrap();
+    (signing_key, Binary::from(pubkey), Addr::unchecked(seller))
+}
+
+fn sign_order(
+    manager: &DepsManager,
+    signing_key: &k256::ecdsa::SigningKey,
+    pubkey: &Binary,
+    order: market_royalty::Order,
+) -> market_royalty::SignedOrder {
+    use k256::ecdsa::signature::hazmat::PrehashSigner;
+
+    let hash: Binary = from_json(
+        &manager
+            .query(QueryMsg::GetOrderHash {
+                order: order.clone(),
+            })
+            .unwrap(),
+    )
+    .unwrap();
+    let signature: k256::ecdsa::Signature = signing_key.sign_prehash(&hash).unwrap();
+    market_royalty::SignedOrder {
+        order,
+        pubkey: pubkey.clone(),
+        signature: Binary::from(signature.to_bytes().to_vec()),
+    }
+}
+
+#[test]
+fn test_fulfill_signed_order() {
+    unsafe {
+        let manager = DepsManager::get_new();
+        handle_whitelist(manager);
+        let (signing_key, pubkey, seller) = order_signer();
+
+        // Mint new NFT to the seller, which only approves the market
+        let mint_msg = ExecuteMsg::MintNft(MintMsg {
+            contract_addr: Addr::unchecked(OW721),
+            creator: Addr::unchecked(PROVIDER),
+            mint: MintIntermediate {
+                mint: MintStruct {
+                    token_id: String::from(SELLABLE_NFT),
+                    owner: seller.clone(),
+                    name: String::from("asbv"),
+                    description: None,
+                    image: String::from("baxv"),
+                },
+            },
+            creator_type: String::from("sacx"),
+            royalty: Some(10 * DECIMAL),
+        });
+        manager
+            .execute(mock_info(PROVIDER, &vec![coin(50, DENOM)]), mint_msg)
+            .unwrap();
+        oraichain_nft::contract::execute(
+            manager.ow721.as_mut(),
+            mock_env(OW721),
+            mock_info(seller.as_str(), &vec![]),
+            oraichain_nft::msg::ExecuteMsg::ApproveAll {
+                operator: Addr::unchecked(MARKET_ADDR),
+                expires: None,
+            },
+        )
+        .unwrap();
+
+        let order = market_royalty::Order {
+            market: Addr::unchecked(MARKET_ADDR),
+            contract_addr: Addr::unchecked(OW721),
+            token_id: String::from(SELLABLE_NFT),
+            seller: seller.clone(),
+            price: Uint128::from(1000u128),
+            asset_info: market::AssetInfo::NativeToken {
+                denom: DENOM.to_string(),
+            },
+            expiration: mock_env(MARKET_ADDR).block.time.seconds() + 100,
+            nonce: 0,
+        };
+        let signed_order = sign_order(manager, &signing_key, &pubkey, order.clone());
+
+        // tampered order
+        let mut tampered_order = signed_order.clone();
+        tampered_order.order.price = Uint128::from(1u128);
+        assert!(matches!(
+            manager.execute(
+                mock_info(BIDDER, &coins(1, DENOM)),
+                ExecuteMsg::FulfillOrder(tampered_order)
+            ),
+            Err(ContractError::InvalidSignature {})
+        ));
+        // signed by another key
+        let mut forged_order = sign_order(
+            manager,
+            &k256::ecdsa::SigningKey::from_bytes(&[8u8; 32].into()).unwrap(),
+            &pubkey,
+            order.clone(),
+        );
+        forged_order.pubkey = signed_order.pubkey.clone();
+        assert!(matches!(
+            manager.execute(
+                mock_info(BIDDER, &coins(1000, DENOM)),
+                ExecuteMsg::FulfillOrder(forged_order)
+            ),
+            Err(ContractError::InvalidSignature {})
+        ));
+        // insufficient funds
+        assert!(manager
+            .execute(
+                mock_info(BIDDER, &coins(999, DENOM)),
+                ExecuteMsg::FulfillOrder(signed_order.clone())
+            )
+            .is_err());
+        // expired order
+        let mut env = mock_env(MARKET_ADDR);
+        env.block.time = env.block.time.plus_seconds(100);
+        assert!(matches!(
+            manager.handle_with_env(
+                env,
+                mock_info(BIDDER, &coins(1000, DENOM)),
+                ExecuteMsg::FulfillOrder(signed_order.clone())
+            ),
+            Err(ContractError::OrderExpired {})
+        ));
+
+        let results = manager
+            .execute(
+                mock_info(BIDDER, &coins(1000, DENOM)),
+                ExecuteMsg::FulfillOrder(signed_order.clone()),
+            )
+            .unwrap();
+        // 2% market fee, then 10% royalty for the creator
+        let bank_msgs: Vec<CosmosMsg> = results
+            .last()
+            .unwrap()
+            .messages
+            .iter()
+            .filter(|msg| matches!(msg.msg, CosmosMsg::Bank(_)))
+            .map(|msg| msg.msg.clone())
+            .collect();
+        assert_eq!(
+            bank_msgs,
+            vec![
+                cosmwasm_std::BankMsg::Send {
+                    to_address: PROVIDER.to_string(),
+                    amount: coins(98, DENOM),
+                }
+                .into(),
+                cosmwasm_std::BankMsg::Send {
+                    to_address: seller.to_string(),
+                    amount: coins(882, DENOM),
+                }
+                .into(),
+            ]
+        );
+        verify_owner(manager.deps.as_ref(), OW721, SELLABLE_NFT, BIDDER).unwrap();
+
+        // an order can only be filled once
+        assert!(matches!(
+            manager.execute(
+                mock_info(BIDDER, &coins(1000, DENOM)),
+                ExecuteMsg::FulfillOrder(signed_order)
+            ),
+            Err(ContractError::OrderFilled {})
+        ));
+
+        // transfer the nft back to the seller, then cancel new orders
+        oraichain_nft::contract::execute(
+            manager.ow721.as_mut(),
+            mock_env(OW721),
+            mock_info(BIDDER, &vec![]),
+            oraichain_nft::msg::ExecuteMsg::TransferNft {
+                recipient: seller.clone(),
+                token_id: String::from(SELLABLE_NFT),
+            },
+        )
+        .unwrap();
+        let signed_order = sign_order(
+            manager,
+            &signing_key,
+            &pubkey,
+            market_royalty::Order {
+                nonce: 1,
+                ..order.clone()
+            },
+        );
+        let order_hash: Binary = from_json(
+            &manager
+                .query(QueryMsg::GetOrderHash {
+                    order: signed_order.order.clone(),
+                })
+                .unwrap(),
+        )
+        .unwrap();
+        // revoking is scoped to the sender
+        manager
+            .execute(
+                mock_info(BIDDER, &[]),
+                ExecuteMsg::CancelOrder {
+                    order_hash: order_hash.clone(),
+                },
+            )
+            .unwrap();
+        manager
+            .execute(
+                mock_info(seller.as_str(), &[]),
+                ExecuteMsg::CancelOrder { order_hash },
+            )
+            .unwrap();
+        assert!(matches!(
+            manager.execute(
+                mock_info(BIDDER, &coins(1000, DENOM)),
+                ExecuteMsg::FulfillOrder(signed_order)
+            ),
+            Err(ContractError::OrderCancelled {})
+        ));
+
+        // bumping the nonce cancels every order signed with a lower nonce
+        let signed_order = sign_order(
+            manager,
+            &signing_key,
+            &pubkey,
+            market_royalty::Order { nonce: 2, ..order },
+        );
+        for _ in 0..3 {
+            manager
+                .execute(
+                    mock_info(seller.as_str(), &[]),
+                    ExecuteMsg::IncreaseOrderNonce {},
+                )
+                .unwrap();
+        }
+        let nonce: u64 = from_json(
+            &manager
+                .query(QueryMsg::GetOrderNonce {
+                    seller: seller.clone(),
+                })
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(nonce, 3);
+        assert!(matches!(
+            manager.execute(
+                mock_info(BIDDER, &coins(1000, DENOM)),
+                ExecuteMsg::FulfillOrder(signed_order)
+            ),
+            Err(ContractError::OrderCancelled {})
+        ));
+    }
+}
+
+#[test]
+fn test_fulfill_cw20_order_with_native_funds() {
+    unsafe {
+        let manager = DepsManager::get_new();
+        handle_whitelist(manager);
+        let (signing_key, pubkey, seller) = order_signer();
+
+        let mint_msg = ExecuteMsg::MintNft(MintMsg {
+            contract_addr: Addr::unchecked(OW721),
+            creator: Addr::unchecked(PROVIDER),
+            mint: MintIntermediate {
+                mint: MintStruct {
+                    token_id: String::from(SELLABLE_NFT),
+                    owner: seller.clone(),
+                    name: String::from("asbv"),
+                    description: None,
+                    image: String::from("baxv"),
+                },
+            },
+            creator_type: String::from("sacx"),
+            royalty: Some(10 * DECIMAL),
+        });
+        manager
+            .execute(mock_info(PROVIDER, &[coin(50, DENOM)]), mint_msg)
+            .unwrap();
+        oraichain_nft::contract::execute(
+            manager.ow721.as_mut(),
+            mock_env(OW721),
+            mock_info(seller.as_str(), &[]),
+            oraichain_nft::msg::ExecuteMsg::ApproveAll {
+                operator: Addr::unchecked(MARKET_ADDR),
+                expires: None,
+            },
+        )
+        .unwrap();
+
+        let signed_order = sign_order(
+            manager,
+            &signing_key,
+            &pubkey,
+            market_royalty::Order {
+                market: Addr::unchecked(MARKET_ADDR),
+                contract_addr: Addr::unchecked(OW721),
+                token_id: String::from(SELLABLE_NFT),
+                seller: seller.clone(),
+                price: Uint128::from(1000u128),
+                asset_info: market::AssetInfo::Token {
+                    contract_addr: Addr::unchecked(OW20),
+                },
+                expiration: mock_env(MARKET_ADDR).block.time.seconds() + 100,
+                nonce: 0,
+            },
+        );
+
+        // a cw20 priced order cannot be paid with native coins, whatever their denom
+        for denom in ["worthless", DENOM] {
+            assert!(matches!(
+                manager.execute(
+                    mock_info(BIDDER, &coins(1000, denom)),
+                    ExecuteMsg::FulfillOrder(signed_order.clone())
+                ),
+                Err(ContractError::InvalidSentFundAmount {})
+            ));
+        }
+        // nor through the hook of another cw20 token
+        assert!(matches!(
+            manager.execute(
+                mock_info("fake_token", &[]),
+                ExecuteMsg::Receive(Cw20ReceiveMsg {
+                    sender: BIDDER.to_string(),
+                    amount: Uint128::from(1000u128),
+                    msg: to_json_binary(&Cw20HookMsg::FulfillOrder(signed_order)).unwrap(),
+                })
+            ),
+            Err(ContractError::InvalidSentFundAmount {})
+        ));
+        verify_owner(manager.deps.as_ref(), OW721, SELLABLE_NFT, seller.as_str()).unwrap();
+    }
+}
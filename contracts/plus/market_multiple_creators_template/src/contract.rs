@@ -6,7 +6,7 @@ use std::rc::Rc;
 
 use crate::error::ContractError;
 use crate::msg::{
-    ApproveAll, ApproveAllMsg, ChangeCreatorMsg, ExecuteMsg, InstantiateMsg, QueryMsg,
+    ApproveAll, ApproveAllMsg, ChangeCreatorMsg, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg,
     RevokeAllMsg, WrapMintMsg, WrapMintMsg721,
 };
 use crate::state::{
@@ -17,9 +17,15 @@ use cosmwasm_std::{
     attr, coins, to_json_binary, Addr, BankMsg, Binary, CosmosMsg, Decimal, Deps, DepsMut, Env,
     MessageInfo, Response, StdError, StdResult, Uint128, WasmMsg,
 };
+use cw2::set_contract_version;
+use migration::migrate_version;
 pub const MAX_REVENUE: u64 = 1_000_000_000;
 pub const DEFAULT_END_HEIGHT: u64 = 300000;
 
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:market_multiple_creators_template";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
 // Note, you can use StdResult in some functions where you do not
 // make use of the custom errors
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -29,6 +35,7 @@ pub fn instantiate(
     info: MessageInfo,
     init: InstantiateMsg,
 ) -> StdResult<Response> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     if init
         .co_founders
         .iter()
@@ -448,6 +455,11 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     }
 }
 
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+    migrate_version(deps, &env, CONTRACT_NAME, CONTRACT_VERSION, &[])
+}
+
 fn query_state(deps: Deps) -> StdResult<State> {
     config_read(deps.storage).load()
 }
@@ -1,3 +1,4 @@
+use cosmwasm_schema::QueryResponses;
 use cosmwasm_std::{Addr, Uint128};
 use cw_utils::Expiration;
 use market_1155::MintMsg;
@@ -5,7 +6,7 @@ use market_royalty::MintMsg as MintMsg721;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use crate::state::Founder;
+use crate::state::{Change, Founder, State};
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InstantiateMsg {
@@ -13,6 +14,9 @@ pub struct InstantiateMsg {
     pub threshold: u64,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
@@ -78,10 +82,13 @@ pub struct WrapMintMsg721 {
     pub mint_nft: MintMsg721,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, QueryResponses)]
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
+    #[returns(State)]
     GetState {},
+    #[returns(Option<Founder>)]
     GetCoFounder { co_founder: Addr },
+    #[returns(Change)]
     GetShareChange { round: u64 },
 }
@@ -0,0 +1,611 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+
+use cosmwasm_std::{
+    attr, from_json, to_json_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo,
+    Order, Record, Response, StdResult, Uint128, Uint64, WasmMsg,
+};
+use cw1155::{Cw1155ExecuteMsg, Cw1155ReceiveMsg};
+use cw2::set_contract_version;
+use cw20::Cw20ReceiveMsg;
+use cw721::{Cw721ExecuteMsg, Cw721ReceiveMsg};
+use cw_storage_plus::Bound;
+use market::{AssetInfo, Funds, MarketHubContract};
+use market_ai_royalty::parse_transfer_msg;
+use market_whitelist::{IsApprovedForAllResponse, MarketWhiteListdQueryMsg};
+use migration::{migrate_version, MigrationStep};
+
+use crate::error::ContractError;
+use crate::msg::{
+    ExecuteMsg, InstantiateMsg, LoanHookMsg, LoanTerms, MigrateMsg, ProxyQueryMsg, QueryMsg,
+    ReceiveMsg, UpdateContractMsg,
+};
+use crate::state::{increment_loans, loans, Collateral, ContractInfo, Loan, CONTRACT_INFO};
+
+pub const WHITELIST_STORAGE: &str = "whitelist_storage";
+// settings for pagination
+const MAX_LIMIT: u8 = 100;
+const DEFAULT_LIMIT: u8 = 20;
+// loans can last at most 5 years
+pub const MAX_LOAN_DURATION: u64 = 5 * 365 * 24 * 3600;
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:market_nft_loan";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    let info = ContractInfo {
+        creator: info.sender,
+        governance: MarketHubContract(msg.governance),
+    };
+    CONTRACT_INFO.save(deps.storage, &info)?;
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::ReceiveNft(msg) => try_receive_nft(deps, env, info, msg),
+        ExecuteMsg::Receive(ReceiveMsg::Cw1155(msg)) => try_receive_1155(deps, env, info, msg),
+        ExecuteMsg::Receive(ReceiveMsg::Cw20(msg)) => try_receive_cw20(deps, env, info, msg),
+        ExecuteMsg::FundLoan { loan_id } => try_fund_loan(
+            deps,
+            env,
+            info.sender,
+            loan_id,
+            Funds::Native { fund: info.funds },
+            None,
+        ),
+        ExecuteMsg::RepayLoan { loan_id } => try_repay_loan(
+            deps,
+            env,
+            info.sender,
+            loan_id,
+            Funds::Native { fund: info.funds },
+            None,
+        ),
+        ExecuteMsg::Foreclose { loan_id } => try_foreclose(deps, env, info, loan_id),
+        ExecuteMsg::CancelLoan { loan_id } => try_cancel_loan(deps, env, info, loan_id),
+        ExecuteMsg::UpdateInfo(msg) => try_update_info(deps, info, msg),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::GetContractInfo {} => to_json_binary(&CONTRACT_INFO.load(deps.storage)?),
+        QueryMsg::GetLoan { loan_id } => to_json_binary(&query_loan(deps, loan_id)?),
+        QueryMsg::GetLoans {
+            offset,
+            limit,
+            order,
+        } => to_json_binary(&query_loans(deps, offset, limit, order)?),
+        QueryMsg::GetLoansByBorrower {
+            borrower,
+            offset,
+            limit,
+            order,
+        } => to_json_binary(&query_loans_by_borrower(
+            deps, borrower, offset, limit, order,
+        )?),
+        QueryMsg::GetLoansByLender {
+            lender,
+            offset,
+            limit,
+            order,
+        } => to_json_binary(&query_loans_by_lender(deps, lender, offset, limit, order)?),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    Ok(migrate_version(
+        deps,
+        &env,
+        CONTRACT_NAME,
+        CONTRACT_VERSION,
+        MIGRATIONS,
+    )?)
+}
+
+const MIGRATIONS: &[MigrationStep] = &[];
+
+// ============================== Message Handlers ==============================
+
+pub fn try_receive_nft(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    rcv_msg: Cw721ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let msg = rcv_msg.msg.ok_or(ContractError::InvalidArgument {
+        arg: "msg".to_string(),
+    })?;
+    match from_json(&msg)? {
+        LoanHookMsg::RequestLoan(terms) => try_request_loan(
+            deps,
+            env,
+            rcv_msg.sender,
+            Collateral {
+                contract_addr: info.sender,
+                token_id: rcv_msg.token_id,
+                amount: None,
+            },
+            terms,
+        ),
+        _ => Err(ContractError::InvalidArgument {
+            arg: "msg".to_string(),
+        }),
+    }
+}
+
+pub fn try_receive_1155(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    rcv_msg: Cw1155ReceiveMsg,
+) -> Result<Response, ContractError> {
+    match from_json(&rcv_msg.msg)? {
+        LoanHookMsg::RequestLoan(terms) => {
+            // tokens are sent from the owner account, which may differ from the operator
+            let borrower = rcv_msg.from.unwrap_or(rcv_msg.operator);
+            try_request_loan(
+                deps,
+                env,
+                Addr::unchecked(borrower),
+                Collateral {
+                    contract_addr: info.sender,
+                    token_id: rcv_msg.token_id,
+                    amount: Some(rcv_msg.amount),
+                },
+                terms,
+            )
+        }
+        _ => Err(ContractError::InvalidArgument {
+            arg: "msg".to_string(),
+        }),
+    }
+}
+
+pub fn try_receive_cw20(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    rcv_msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let funds = Funds::Cw20 {
+        fund: rcv_msg.amount,
+    };
+    match from_json(&rcv_msg.msg)? {
+        LoanHookMsg::FundLoan { loan_id } => try_fund_loan(
+            deps,
+            env,
+            Addr::unchecked(rcv_msg.sender),
+            loan_id,
+            funds,
+            Some(info.sender),
+        ),
+        LoanHookMsg::RepayLoan { loan_id } => try_repay_loan(
+            deps,
+            env,
+            Addr::unchecked(rcv_msg.sender),
+            loan_id,
+            funds,
+            Some(info.sender),
+        ),
+        _ => Err(ContractError::InvalidArgument {
+            arg: "msg".to_string(),
+        }),
+    }
+}
+
+fn verify_whitelist(
+    deps: Deps,
+    governance: &MarketHubContract,
+    nft_addr: &Addr,
+) -> Result<(), ContractError> {
+    let is_approved: IsApprovedForAllResponse = governance.query_storage(
+        WHITELIST_STORAGE.to_string(),
+        to_json_binary(&ProxyQueryMsg::Msg(
+            MarketWhiteListdQueryMsg::IsApprovedForAll {
+                nft_addr: nft_addr.to_string(),
+            },
+        ))?,
+        &deps.querier,
+    )?;
+    if !is_approved.approved {
+        return Err(ContractError::NotWhilteList {});
+    }
+    Ok(())
+}
+
+/// the exact amount of the loan asset must be sent, `token` is the cw20 contract which sent the funds
+fn verify_funds(
+    funds: &Funds,
+    token: Option<&Addr>,
+    asset_info: &AssetInfo,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    let valid = match (funds, asset_info) {
+        (Funds::Native { fund }, AssetInfo::NativeToken { denom }) => {
+            fund.len() == 1 && fund[0].denom.eq(denom) && fund[0].amount.eq(&amount)
+        }
+        (Funds::Cw20 { fund }, AssetInfo::Token { contract_addr }) => {
+            token.is_some_and(|token| token.eq(contract_addr)) && fund.eq(&amount)
+        }
+        _ => false,
+    };
+    if !valid {
+        return Err(ContractError::InvalidSentFundAmount {
+            asset: match asset_info {
+                AssetInfo::NativeToken { denom } => denom.to_string(),
+                AssetInfo::Token { contract_addr } => contract_addr.to_string(),
+            },
+            amount: amount.to_string(),
+        });
+    }
+    Ok(())
+}
+
+fn transfer_collateral_msg(
+    collateral: &Collateral,
+    sender: &str,
+    recipient: &Addr,
+) -> StdResult<CosmosMsg> {
+    let msg = match collateral.amount {
+        None => to_json_binary(&Cw721ExecuteMsg::TransferNft {
+            recipient: recipient.clone(),
+            token_id: collateral.token_id.clone(),
+        })?,
+        Some(value) => to_json_binary(&Cw1155ExecuteMsg::SendFrom {
+            from: sender.to_string(),
+            to: recipient.to_string(),
+            token_id: collateral.token_id.clone(),
+            value,
+            msg: None,
+        })?,
+    };
+    Ok(WasmMsg::Execute {
+        contract_addr: collateral.contract_addr.to_string(),
+        msg,
+        funds: vec![],
+    }
+    .into())
+}
+
+fn load_loan(deps: Deps, loan_id: u64) -> Result<Loan, ContractError> {
+    loans()
+        .load(deps.storage, &loan_id.to_be_bytes())
+        .map_err(|_| ContractError::LoanNotFound {})
+}
+
+pub fn try_request_loan(
+    deps: DepsMut,
+    _env: Env,
+    borrower: Addr,
+    collateral: Collateral,
+    terms: LoanTerms,
+) -> Result<Response, ContractError> {
+    let ContractInfo { governance, .. } = CONTRACT_INFO.load(deps.storage)?;
+    verify_whitelist(deps.as_ref(), &governance, &collateral.contract_addr)?;
+    if terms.principal.is_zero() {
+        return Err(ContractError::InvalidArgument {
+            arg: "principal".to_string(),
+        });
+    }
+    if terms.duration == 0 {
+        return Err(ContractError::InvalidArgument {
+            arg: "duration".to_string(),
+        });
+    }
+    if terms.duration > MAX_LOAN_DURATION {
+        return Err(ContractError::LoanDurationTooLong {
+            max: MAX_LOAN_DURATION,
+        });
+    }
+
+    let id = increment_loans(deps.storage)?;
+    let loan = Loan {
+        id: Some(id),
+        borrower: borrower.clone(),
+        lender: None,
+        collateral,
+        principal: terms.principal,
+        interest: terms.interest,
+        duration: terms.duration,
+        asset_info: terms.asset_info,
+        deadline: None,
+    };
+    // make sure the repay amount does not overflow before anyone funds the loan
+    loan.repay_amount()?;
+    loans().save(deps.storage, &id.to_be_bytes(), &loan)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "request_loan"),
+        attr("borrower", borrower),
+        attr("loan_id", id.to_string()),
+        attr("nft_addr", loan.collateral.contract_addr),
+        attr("token_id", loan.collateral.token_id),
+        attr("principal", loan.principal),
+        attr("interest", loan.interest),
+    ]))
+}
+
+pub fn try_fund_loan(
+    deps: DepsMut,
+    env: Env,
+    lender: Addr,
+    loan_id: u64,
+    funds: Funds,
+    token: Option<Addr>,
+) -> Result<Response, ContractError> {
+    let mut loan = load_loan(deps.as_ref(), loan_id)?;
+    if loan.lender.is_some() {
+        return Err(ContractError::LoanFunded {});
+    }
+    if lender.eq(&loan.borrower) {
+        return Err(ContractError::Unauthorized {
+            sender: lender.to_string(),
+        });
+    }
+    verify_funds(&funds, token.as_ref(), &loan.asset_info, loan.principal)?;
+
+    let deadline = Uint64::from(env.block.time.seconds())
+        .checked_add(Uint64::from(loan.duration))?
+        .u64();
+    loan.lender = Some(lender.clone());
+    loan.deadline = Some(deadline);
+    loans().save(deps.storage, &loan_id.to_be_bytes(), &loan)?;
+
+    // the principal goes straight to the borrower
+    let transfer_msg = parse_transfer_msg(
+        loan.asset_info,
+        loan.principal,
+        env.contract.address.as_str(),
+        loan.borrower.clone(),
+    )?;
+
+    Ok(Response::new()
+        .add_message(transfer_msg)
+        .add_attributes(vec![
+            attr("action", "fund_loan"),
+            attr("lender", lender),
+            attr("borrower", loan.borrower),
+            attr("loan_id", loan_id.to_string()),
+            attr("deadline", deadline.to_string()),
+        ]))
+}
+
+pub fn try_repay_loan(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    loan_id: u64,
+    funds: Funds,
+    token: Option<Addr>,
+) -> Result<Response, ContractError> {
+    let loan = load_loan(deps.as_ref(), loan_id)?;
+    if sender.ne(&loan.borrower) {
+        return Err(ContractError::Unauthorized {
+            sender: sender.to_string(),
+        });
+    }
+    let (lender, deadline) = match (&loan.lender, loan.deadline) {
+        (Some(lender), Some(deadline)) => (lender, deadline),
+        _ => return Err(ContractError::LoanNotFunded {}),
+    };
+    if env.block.time.seconds() >= deadline {
+        return Err(ContractError::LoanExpired {});
+    }
+    let repay_amount = loan.repay_amount()?;
+    verify_funds(&funds, token.as_ref(), &loan.asset_info, repay_amount)?;
+    loans().remove(deps.storage, &loan_id.to_be_bytes())?;
+
+    let contract_addr = env.contract.address.as_str();
+    let cosmos_msgs = vec![
+        parse_transfer_msg(
+            loan.asset_info.clone(),
+            repay_amount,
+            contract_addr,
+            lender.clone(),
+        )?,
+        transfer_collateral_msg(&loan.collateral, contract_addr, &loan.borrower)?,
+    ];
+
+    Ok(Response::new()
+        .add_messages(cosmos_msgs)
+        .add_attributes(vec![
+            attr("action", "repay_loan"),
+            attr("borrower", sender),
+            attr("lender", lender),
+            attr("loan_id", loan_id.to_string()),
+            attr("amount", repay_amount),
+        ]))
+}
+
+pub fn try_foreclose(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    loan_id: u64,
+) -> Result<Response, ContractError> {
+    let loan = load_loan(deps.as_ref(), loan_id)?;
+    let deadline = loan.deadline.ok_or(ContractError::LoanNotFunded {})?;
+    if loan.lender.as_ref().ne(&Some(&info.sender)) {
+        return Err(ContractError::Unauthorized {
+            sender: info.sender.to_string(),
+        });
+    }
+    if env.block.time.seconds() < deadline {
+        return Err(ContractError::LoanNotOverdue {});
+    }
+    loans().remove(deps.storage, &loan_id.to_be_bytes())?;
+
+    Ok(Response::new()
+        .add_message(transfer_collateral_msg(
+            &loan.collateral,
+            env.contract.address.as_str(),
+            &info.sender,
+        )?)
+        .add_attributes(vec![
+            attr("action", "foreclose"),
+            attr("lender", info.sender),
+            attr("borrower", loan.borrower),
+            attr("loan_id", loan_id.to_string()),
+        ]))
+}
+
+pub fn try_cancel_loan(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    loan_id: u64,
+) -> Result<Response, ContractError> {
+    let loan = load_loan(deps.as_ref(), loan_id)?;
+    if info.sender.ne(&loan.borrower) {
+        return Err(ContractError::Unauthorized {
+            sender: info.sender.to_string(),
+        });
+    }
+    if loan.lender.is_some() {
+        return Err(ContractError::LoanFunded {});
+    }
+    loans().remove(deps.storage, &loan_id.to_be_bytes())?;
+
+    Ok(Response::new()
+        .add_message(transfer_collateral_msg(
+            &loan.collateral,
+            env.contract.address.as_str(),
+            &loan.borrower,
+        )?)
+        .add_attributes(vec![
+            attr("action", "cancel_loan"),
+            attr("borrower", info.sender),
+            attr("loan_id", loan_id.to_string()),
+        ]))
+}
+
+pub fn try_update_info(
+    deps: DepsMut,
+    info: MessageInfo,
+    msg: UpdateContractMsg,
+) -> Result<Response, ContractError> {
+    let new_contract_info = CONTRACT_INFO.update(deps.storage, |mut contract_info| {
+        // Unauthorized
+        if !info.sender.eq(&contract_info.creator) {
+            return Err(ContractError::Unauthorized {
+                sender: info.sender.to_string(),
+            });
+        }
+        if let Some(creator) = msg.creator {
+            contract_info.creator = creator;
+        }
+        if let Some(governance) = msg.governance {
+            contract_info.governance = MarketHubContract(governance);
+        }
+        Ok(contract_info)
+    })?;
+
+    Ok(Response::new()
+        .add_attributes(vec![attr("action", "update_info")])
+        .set_data(to_json_binary(&new_contract_info)?))
+}
+
+// ============================== Query Handlers ==============================
+
+fn _get_range_params(
+    offset: Option<u64>,
+    limit: Option<u8>,
+    order: Option<u8>,
+) -> (usize, Option<Bound>, Option<Bound>, Order) {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let mut min = None;
+    let mut max = None;
+    let mut order_enum = Order::Descending;
+    if let Some(num) = order {
+        if num == 1 {
+            order_enum = Order::Ascending;
+        }
+    }
+
+    // if there is offset, assign to min or max
+    if let Some(offset) = offset {
+        let offset_value = Some(Bound::Exclusive(offset.to_be_bytes().to_vec()));
+        match order_enum {
+            Order::Ascending => min = offset_value,
+            Order::Descending => max = offset_value,
+        }
+    };
+    (limit, min, max, order_enum)
+}
+
+fn parse_loan(item: StdResult<Record<Loan>>) -> StdResult<Loan> {
+    item.map(|(_, loan)| loan)
+}
+
+pub fn query_loan(deps: Deps, loan_id: u64) -> StdResult<Loan> {
+    loans().load(deps.storage, &loan_id.to_be_bytes())
+}
+
+pub fn query_loans(
+    deps: Deps,
+    offset: Option<u64>,
+    limit: Option<u8>,
+    order: Option<u8>,
+) -> StdResult<Vec<Loan>> {
+    let (limit, min, max, order_enum) = _get_range_params(offset, limit, order);
+    loans()
+        .range(deps.storage, min, max, order_enum)
+        .take(limit)
+        .map(parse_loan)
+        .collect()
+}
+
+pub fn query_loans_by_borrower(
+    deps: Deps,
+    borrower: Addr,
+    offset: Option<u64>,
+    limit: Option<u8>,
+    order: Option<u8>,
+) -> StdResult<Vec<Loan>> {
+    let (limit, min, max, order_enum) = _get_range_params(offset, limit, order);
+    loans()
+        .idx
+        .borrower
+        .items(deps.storage, borrower.as_bytes(), min, max, order_enum)
+        .take(limit)
+        .map(parse_loan)
+        .collect()
+}
+
+pub fn query_loans_by_lender(
+    deps: Deps,
+    lender: Option<Addr>,
+    offset: Option<u64>,
+    limit: Option<u8>,
+    order: Option<u8>,
+) -> StdResult<Vec<Loan>> {
+    let (limit, min, max, order_enum) = _get_range_params(offset, limit, order);
+    let lender_raw = lender
+        .map(|addr| addr.as_bytes().to_vec())
+        .unwrap_or_default();
+    loans()
+        .idx
+        .lender
+        .items(deps.storage, &lender_raw, min, max, order_enum)
+        .take(limit)
+        .map(parse_loan)
+        .collect()
+}
@@ -0,0 +1,49 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Overflow(#[from] cosmwasm_std::OverflowError),
+
+    #[error("Unauthorized nft loan with sender: {sender}")]
+    Unauthorized { sender: String },
+
+    #[error("Loan is not found")]
+    LoanNotFound {},
+
+    #[error("Loan has already been funded")]
+    LoanFunded {},
+
+    #[error("Loan has not been funded yet")]
+    LoanNotFunded {},
+
+    #[error("Loan has expired, the collateral can only be foreclosed")]
+    LoanExpired {},
+
+    #[error("Loan is not overdue yet")]
+    LoanNotOverdue {},
+
+    #[error("Loan duration can be at most {max} seconds")]
+    LoanDurationTooLong { max: u64 },
+
+    #[error("Rejected nft loan. The nft contract is not whitelisted. Cannot use it as collateral")]
+    NotWhilteList {},
+
+    #[error("The argument {arg} are invalid")]
+    InvalidArgument { arg: String },
+
+    #[error("Expected {amount} {asset} to be sent")]
+    InvalidSentFundAmount { asset: String, amount: String },
+}
+
+impl From<ContractError> for String {
+    /// Utility for explicit conversion to `String`.
+    #[inline]
+    fn from(err: ContractError) -> Self {
+        err.to_string()
+    }
+}
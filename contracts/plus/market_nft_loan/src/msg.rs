@@ -0,0 +1,114 @@
+use std::fmt;
+
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Addr, Empty, Uint128};
+use cw1155::Cw1155ReceiveMsg;
+use cw20::Cw20ReceiveMsg;
+use cw721::Cw721ReceiveMsg;
+use market::AssetInfo;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::state::{ContractInfo, Loan};
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    pub governance: Addr,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Escrow a cw721 collateral with a `LoanHookMsg::RequestLoan` message
+    ReceiveNft(Cw721ReceiveMsg),
+    /// Escrow a cw1155 collateral with a `LoanHookMsg::RequestLoan` message,
+    /// or pay a loan in cw20 with a `LoanHookMsg::FundLoan` or `LoanHookMsg::RepayLoan` message
+    Receive(ReceiveMsg),
+    /// Lend the principal in native coins, which is sent to the borrower right away
+    FundLoan {
+        loan_id: u64,
+    },
+    /// Pay back the principal plus interest in native coins and recover the collateral
+    RepayLoan {
+        loan_id: u64,
+    },
+    /// Take the collateral of an overdue loan, callable by the lender
+    Foreclose {
+        loan_id: u64,
+    },
+    /// Withdraw the collateral of a loan request which has not been funded yet
+    CancelLoan {
+        loan_id: u64,
+    },
+    UpdateInfo(UpdateContractMsg),
+}
+
+/// cw1155 and cw20 both send their hook under the `receive` variant
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(untagged)]
+pub enum ReceiveMsg {
+    Cw1155(Cw1155ReceiveMsg),
+    Cw20(Cw20ReceiveMsg),
+}
+
+#[cw_serde]
+pub enum LoanHookMsg {
+    RequestLoan(LoanTerms),
+    FundLoan { loan_id: u64 },
+    RepayLoan { loan_id: u64 },
+}
+
+#[cw_serde]
+pub struct LoanTerms {
+    pub principal: Uint128,
+    pub interest: Uint128,
+    /// loan duration in seconds
+    pub duration: u64,
+    pub asset_info: AssetInfo,
+}
+
+#[cw_serde]
+pub struct UpdateContractMsg {
+    pub creator: Option<Addr>,
+    pub governance: Option<Addr>,
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(ContractInfo)]
+    GetContractInfo {},
+    #[returns(Loan)]
+    GetLoan { loan_id: u64 },
+    #[returns(Vec<Loan>)]
+    GetLoans {
+        offset: Option<u64>,
+        limit: Option<u8>,
+        order: Option<u8>,
+    },
+    #[returns(Vec<Loan>)]
+    GetLoansByBorrower {
+        borrower: Addr,
+        offset: Option<u64>,
+        limit: Option<u8>,
+        order: Option<u8>,
+    },
+    /// None lender returns the loan requests waiting for funds
+    #[returns(Vec<Loan>)]
+    GetLoansByLender {
+        lender: Option<Addr>,
+        offset: Option<u64>,
+        limit: Option<u8>,
+        order: Option<u8>,
+    },
+}
+
+#[cw_serde]
+pub enum ProxyQueryMsg<T = Empty>
+where
+    T: Clone + fmt::Debug + PartialEq + JsonSchema + Serialize,
+{
+    Msg(T),
+}
+
+#[cw_serde]
+pub struct MigrateMsg {}
@@ -0,0 +1,90 @@
+use cosmwasm_std::{Addr, StdResult, Storage, Uint128};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, MultiIndex};
+use market::{AssetInfo, MarketHubContract};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct ContractInfo {
+    pub creator: Addr,
+    /// market hub used to check the collateral against the whitelist storage
+    pub governance: MarketHubContract,
+}
+
+pub const CONTRACT_INFO: Item<ContractInfo> = Item::new("market_info");
+pub const LOANS_COUNT: Item<u64> = Item::new("num_loans");
+
+pub fn num_loans(storage: &dyn Storage) -> StdResult<u64> {
+    Ok(LOANS_COUNT.may_load(storage)?.unwrap_or_default())
+}
+
+pub fn increment_loans(storage: &mut dyn Storage) -> StdResult<u64> {
+    let val = num_loans(storage)? + 1;
+    LOANS_COUNT.save(storage, &val)?;
+    Ok(val)
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct Collateral {
+    pub contract_addr: Addr,
+    pub token_id: String,
+    /// None for cw721, the escrowed amount for cw1155
+    pub amount: Option<Uint128>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct Loan {
+    pub id: Option<u64>,
+    pub borrower: Addr,
+    /// None until the loan is funded
+    pub lender: Option<Addr>,
+    pub collateral: Collateral,
+    pub principal: Uint128,
+    pub interest: Uint128,
+    /// loan duration in seconds, starting when the loan is funded
+    pub duration: u64,
+    pub asset_info: AssetInfo,
+    /// timestamp in seconds before which the borrower must repay, set when the loan is funded
+    pub deadline: Option<u64>,
+}
+
+impl Loan {
+    pub fn repay_amount(&self) -> StdResult<Uint128> {
+        Ok(self.principal.checked_add(self.interest)?)
+    }
+}
+
+pub struct LoanIndexes<'a> {
+    pub borrower: MultiIndex<'a, Loan>,
+    pub lender: MultiIndex<'a, Loan>,
+}
+
+impl<'a> IndexList<Loan> for LoanIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Loan>> + '_> {
+        let v: Vec<&dyn Index<Loan>> = vec![&self.borrower, &self.lender];
+        Box::new(v.into_iter())
+    }
+}
+
+// this IndexedMap instance has a lifetime
+pub fn loans<'a>() -> IndexedMap<'a, &'a [u8], Loan, LoanIndexes<'a>> {
+    let indexes = LoanIndexes {
+        borrower: MultiIndex::new(
+            |o| o.borrower.as_bytes().to_vec(),
+            "loans",
+            "loans__borrower",
+        ),
+        // if we put None lender, we got all loan requests waiting for funds
+        lender: MultiIndex::new(
+            |o| {
+                o.lender
+                    .as_ref()
+                    .map(|addr| addr.as_bytes().to_vec())
+                    .unwrap_or_default()
+            },
+            "loans",
+            "loans__lender",
+        ),
+    };
+    IndexedMap::new("loans", indexes)
+}
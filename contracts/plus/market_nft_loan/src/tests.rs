@@ -0,0 +1,410 @@
+use crate::contract::{execute, instantiate, query, MAX_LOAN_DURATION};
+use crate::error::ContractError;
+use crate::msg::{
+    ExecuteMsg, InstantiateMsg, LoanHookMsg, LoanTerms, ProxyQueryMsg, QueryMsg, ReceiveMsg,
+};
+use crate::state::Loan;
+use cosmwasm_std::testing::{mock_info, MockApi, MockStorage};
+use cosmwasm_std::{
+    coins, from_json, to_json_binary, Addr, BankMsg, ContractResult, CosmosMsg, Env, OwnedDeps,
+    QuerierResult, Response, SystemResult, Uint128, WasmMsg, WasmQuery,
+};
+use cw1155::{Cw1155ExecuteMsg, Cw1155ReceiveMsg};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use cw721::{Cw721ExecuteMsg, Cw721ReceiveMsg};
+use market::mock::{mock_dependencies, mock_env, MockQuerier};
+use market::{AssetInfo, MarketHubQueryMsg, StorageQueryMsg};
+use market_whitelist::{IsApprovedForAllResponse, MarketWhiteListdQueryMsg};
+
+const CREATOR: &str = "owner";
+const LOAN_ADDR: &str = "loan";
+const HUB_ADDR: &str = "hub";
+const BORROWER: &str = "borrower";
+const LENDER: &str = "lender";
+const OW721: &str = "oraichain_nft";
+const OW1155: &str = "ow1155";
+const OW20: &str = "airi";
+const DENOM: &str = "orai";
+
+// only the ow721 and ow1155 contracts are whitelisted
+fn mock_wasm(request: &WasmQuery) -> QuerierResult {
+    match request {
+        WasmQuery::Smart { contract_addr, msg } if contract_addr.eq(HUB_ADDR) => {
+            let MarketHubQueryMsg::Storage(StorageQueryMsg::QueryStorage { msg, .. }) =
+                from_json(msg).unwrap()
+            else {
+                panic!("unexpected hub query");
+            };
+            let ProxyQueryMsg::Msg(MarketWhiteListdQueryMsg::IsApprovedForAll { nft_addr }) =
+                from_json(&msg).unwrap()
+            else {
+                panic!("unexpected whitelist query");
+            };
+            SystemResult::Ok(ContractResult::Ok(
+                to_json_binary(&IsApprovedForAllResponse {
+                    approved: nft_addr.eq(OW721) || nft_addr.eq(OW1155),
+                    collection: None,
+                })
+                .unwrap(),
+            ))
+        }
+        _ => SystemResult::Ok(ContractResult::Err("not found".to_string())),
+    }
+}
+
+fn setup_contract() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+    let mut deps = mock_dependencies(Addr::unchecked(LOAN_ADDR), &[], mock_wasm);
+    instantiate(
+        deps.as_mut(),
+        mock_env(LOAN_ADDR),
+        mock_info(CREATOR, &[]),
+        InstantiateMsg {
+            governance: Addr::unchecked(HUB_ADDR),
+        },
+    )
+    .unwrap();
+    deps
+}
+
+fn terms(asset_info: AssetInfo) -> LoanTerms {
+    LoanTerms {
+        principal: Uint128::from(1000u64),
+        interest: Uint128::from(100u64),
+        duration: 3600,
+        asset_info,
+    }
+}
+
+fn native() -> AssetInfo {
+    AssetInfo::NativeToken {
+        denom: DENOM.to_string(),
+    }
+}
+
+fn request_nft_loan(
+    deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+    nft_addr: &str,
+    asset_info: AssetInfo,
+) -> Result<Response, ContractError> {
+    execute(
+        deps.as_mut(),
+        mock_env(LOAN_ADDR),
+        mock_info(nft_addr, &[]),
+        ExecuteMsg::ReceiveNft(Cw721ReceiveMsg {
+            sender: Addr::unchecked(BORROWER),
+            token_id: "1".to_string(),
+            msg: Some(to_json_binary(&LoanHookMsg::RequestLoan(terms(asset_info))).unwrap()),
+        }),
+    )
+}
+
+fn query_loan(deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>, loan_id: u64) -> Option<Loan> {
+    query(
+        deps.as_ref(),
+        mock_env(LOAN_ADDR),
+        QueryMsg::GetLoan { loan_id },
+    )
+    .ok()
+    .map(|bin| from_json(bin).unwrap())
+}
+
+fn nft_transfer(recipient: &str) -> CosmosMsg {
+    WasmMsg::Execute {
+        contract_addr: OW721.to_string(),
+        msg: to_json_binary(&Cw721ExecuteMsg::TransferNft {
+            recipient: Addr::unchecked(recipient),
+            token_id: "1".to_string(),
+        })
+        .unwrap(),
+        funds: vec![],
+    }
+    .into()
+}
+
+fn after_deadline() -> Env {
+    let mut env = mock_env(LOAN_ADDR);
+    env.block.time = env.block.time.plus_seconds(3600);
+    env
+}
+
+#[test]
+fn test_loan_repaid() {
+    let mut deps = setup_contract();
+
+    // collateral must be whitelisted
+    assert_eq!(
+        request_nft_loan(&mut deps, "random_nft", native()).unwrap_err(),
+        ContractError::NotWhilteList {}
+    );
+    request_nft_loan(&mut deps, OW721, native()).unwrap();
+
+    // principal must be sent exactly
+    assert!(matches!(
+        execute(
+            deps.as_mut(),
+            mock_env(LOAN_ADDR),
+            mock_info(LENDER, &coins(999, DENOM)),
+            ExecuteMsg::FundLoan { loan_id: 1 },
+        )
+        .unwrap_err(),
+        ContractError::InvalidSentFundAmount { .. }
+    ));
+    let res = execute(
+        deps.as_mut(),
+        mock_env(LOAN_ADDR),
+        mock_info(LENDER, &coins(1000, DENOM)),
+        ExecuteMsg::FundLoan { loan_id: 1 },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        BankMsg::Send {
+            to_address: BORROWER.to_string(),
+            amount: coins(1000, DENOM),
+        }
+        .into()
+    );
+    let loan = query_loan(&deps, 1).unwrap();
+    assert_eq!(loan.lender, Some(Addr::unchecked(LENDER)));
+    assert_eq!(
+        loan.deadline,
+        Some(mock_env(LOAN_ADDR).block.time.seconds() + 3600)
+    );
+
+    // cannot fund twice, nor cancel a funded loan, nor foreclose before the deadline
+    assert_eq!(
+        execute(
+            deps.as_mut(),
+            mock_env(LOAN_ADDR),
+            mock_info("another_lender", &coins(1000, DENOM)),
+            ExecuteMsg::FundLoan { loan_id: 1 },
+        )
+        .unwrap_err(),
+        ContractError::LoanFunded {}
+    );
+    assert_eq!(
+        execute(
+            deps.as_mut(),
+            mock_env(LOAN_ADDR),
+            mock_info(BORROWER, &[]),
+            ExecuteMsg::CancelLoan { loan_id: 1 },
+        )
+        .unwrap_err(),
+        ContractError::LoanFunded {}
+    );
+    assert_eq!(
+        execute(
+            deps.as_mut(),
+            mock_env(LOAN_ADDR),
+            mock_info(LENDER, &[]),
+            ExecuteMsg::Foreclose { loan_id: 1 },
+        )
+        .unwrap_err(),
+        ContractError::LoanNotOverdue {}
+    );
+
+    // principal plus interest
+    let res = execute(
+        deps.as_mut(),
+        mock_env(LOAN_ADDR),
+        mock_info(BORROWER, &coins(1100, DENOM)),
+        ExecuteMsg::RepayLoan { loan_id: 1 },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages
+            .into_iter()
+            .map(|msg| msg.msg)
+            .collect::<Vec<CosmosMsg>>(),
+        vec![
+            BankMsg::Send {
+                to_address: LENDER.to_string(),
+                amount: coins(1100, DENOM),
+            }
+            .into(),
+            nft_transfer(BORROWER),
+        ]
+    );
+    assert_eq!(query_loan(&deps, 1), None);
+}
+
+#[test]
+fn test_loan_foreclosed() {
+    let mut deps = setup_contract();
+    let token = AssetInfo::Token {
+        contract_addr: Addr::unchecked(OW20),
+    };
+    request_nft_loan(&mut deps, OW721, token).unwrap();
+
+    let fund_msg = |loan_id: u64| {
+        ExecuteMsg::Receive(ReceiveMsg::Cw20(Cw20ReceiveMsg {
+            sender: LENDER.to_string(),
+            amount: Uint128::from(1000u64),
+            msg: to_json_binary(&LoanHookMsg::FundLoan { loan_id }).unwrap(),
+        }))
+    };
+    // only the loan token is accepted
+    assert!(matches!(
+        execute(
+            deps.as_mut(),
+            mock_env(LOAN_ADDR),
+            mock_info("fake_token", &[]),
+            fund_msg(1),
+        )
+        .unwrap_err(),
+        ContractError::InvalidSentFundAmount { .. }
+    ));
+    let res = execute(
+        deps.as_mut(),
+        mock_env(LOAN_ADDR),
+        mock_info(OW20, &[]),
+        fund_msg(1),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        WasmMsg::Execute {
+            contract_addr: OW20.to_string(),
+            msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: BORROWER.to_string(),
+                amount: Uint128::from(1000u64),
+            })
+            .unwrap(),
+            funds: vec![],
+        }
+        .into()
+    );
+
+    // too late to repay
+    assert_eq!(
+        execute(
+            deps.as_mut(),
+            after_deadline(),
+            mock_info(OW20, &[]),
+            ExecuteMsg::Receive(ReceiveMsg::Cw20(Cw20ReceiveMsg {
+                sender: BORROWER.to_string(),
+                amount: Uint128::from(1100u64),
+                msg: to_json_binary(&LoanHookMsg::RepayLoan { loan_id: 1 }).unwrap(),
+            })),
+        )
+        .unwrap_err(),
+        ContractError::LoanExpired {}
+    );
+    assert_eq!(
+        execute(
+            deps.as_mut(),
+            after_deadline(),
+            mock_info("stranger", &[]),
+            ExecuteMsg::Foreclose { loan_id: 1 },
+        )
+        .unwrap_err(),
+        ContractError::Unauthorized {
+            sender: "stranger".to_string()
+        }
+    );
+    let res = execute(
+        deps.as_mut(),
+        after_deadline(),
+        mock_info(LENDER, &[]),
+        ExecuteMsg::Foreclose { loan_id: 1 },
+    )
+    .unwrap();
+    assert_eq!(res.messages[0].msg, nft_transfer(LENDER));
+    assert_eq!(query_loan(&deps, 1), None);
+}
+
+#[test]
+fn test_cancel_1155_loan_request() {
+    let mut deps = setup_contract();
+    execute(
+        deps.as_mut(),
+        mock_env(LOAN_ADDR),
+        mock_info(OW1155, &[]),
+        ExecuteMsg::Receive(ReceiveMsg::Cw1155(Cw1155ReceiveMsg {
+            operator: BORROWER.to_string(),
+            from: Some(BORROWER.to_string()),
+            token_id: "a".to_string(),
+            amount: Uint128::from(10u64),
+            msg: to_json_binary(&LoanHookMsg::RequestLoan(terms(native()))).unwrap(),
+        })),
+    )
+    .unwrap();
+
+    let loans: Vec<Loan> = from_json(
+        query(
+            deps.as_ref(),
+            mock_env(LOAN_ADDR),
+            QueryMsg::GetLoansByLender {
+                lender: None,
+                offset: None,
+                limit: None,
+                order: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(loans.len(), 1);
+    assert_eq!(loans[0].collateral.amount, Some(Uint128::from(10u64)));
+
+    assert_eq!(
+        execute(
+            deps.as_mut(),
+            mock_env(LOAN_ADDR),
+            mock_info(LENDER, &[]),
+            ExecuteMsg::CancelLoan { loan_id: 1 },
+        )
+        .unwrap_err(),
+        ContractError::Unauthorized {
+            sender: LENDER.to_string()
+        }
+    );
+    let res = execute(
+        deps.as_mut(),
+        mock_env(LOAN_ADDR),
+        mock_info(BORROWER, &[]),
+        ExecuteMsg::CancelLoan { loan_id: 1 },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        WasmMsg::Execute {
+            contract_addr: OW1155.to_string(),
+            msg: to_json_binary(&Cw1155ExecuteMsg::SendFrom {
+                from: LOAN_ADDR.to_string(),
+                to: BORROWER.to_string(),
+                token_id: "a".to_string(),
+                value: Uint128::from(10u64),
+                msg: None,
+            })
+            .unwrap(),
+            funds: vec![],
+        }
+        .into()
+    );
+    assert_eq!(query_loan(&deps, 1), None);
+}
+
+#[test]
+fn test_loan_duration_is_capped() {
+    let mut deps = setup_contract();
+    let mut terms = terms(native());
+    terms.duration = MAX_LOAN_DURATION + 1;
+    let err = execute(
+        deps.as_mut(),
+        mock_env(LOAN_ADDR),
+        mock_info(OW721, &[]),
+        ExecuteMsg::ReceiveNft(Cw721ReceiveMsg {
+            sender: Addr::unchecked(BORROWER),
+            token_id: "1".to_string(),
+            msg: Some(to_json_binary(&LoanHookMsg::RequestLoan(terms)).unwrap()),
+        }),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::LoanDurationTooLong {
+            max: MAX_LOAN_DURATION
+        }
+    );
+}
@@ -10,8 +10,10 @@ use cosmwasm_std::{
     MessageInfo, Order, Record, Response, StdError, StdResult, Storage, Uint128, WasmMsg,
 };
 use cw1155::Cw1155ReceiveMsg;
+use cw2::set_contract_version;
 use cw721::Cw721ReceiveMsg;
 use cw_storage_plus::Bound;
+use migration::{migrate_version, move_item, MigrationStep, VERSION_KEY};
 
 use crate::{
     error::ContractError,
@@ -22,7 +24,7 @@ use crate::{
     state::{
         collection_staker_infos, get_unique_collection_staker, increment_collection_stakers,
         CollectionPoolInfo, CollectionStakedTokenInfo, CollectionStakerInfo, ContractInfo,
-        COLLECTION_POOL_INFO, CONTRACT_INFO,
+        COLLECTION_POOL_INFO, CONTRACT_INFO, CONTRACT_INFO_KEY,
     },
     utils::verify_stake_msg_signature,
 };
@@ -40,6 +42,7 @@ pub fn instantiate(
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     let mut admin = info.sender;
     if msg.admin.is_some() {
         admin = msg.admin.unwrap();
@@ -911,7 +914,7 @@ pub fn handle_reset_earned_rewards(
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn migrate(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     // new_contract_addr: Addr,
     _msg: MigrateMsg,
 ) -> Result<Response, ContractError> {
@@ -967,7 +970,23 @@ pub fn migrate(
     //         .into(),
     //     );
     // }
-    Ok(Response::new().add_attributes(vec![attr("action", "migrate")]))
+    Ok(migrate_version(
+        deps,
+        &env,
+        CONTRACT_NAME,
+        CONTRACT_VERSION,
+        MIGRATIONS,
+    )?)
+}
+
+const MIGRATIONS: &[MigrationStep] = &[MigrationStep {
+    version: "0.2.0",
+    name: "move_contract_info",
+    run: move_contract_info,
+}];
+
+fn move_contract_info(deps: DepsMut, _env: &Env) -> StdResult<()> {
+    move_item(deps.storage, VERSION_KEY, CONTRACT_INFO_KEY)
 }
 
 fn current_pending(
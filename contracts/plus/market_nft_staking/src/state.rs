@@ -3,7 +3,8 @@ use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex, PkOwn
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-pub const CONTRACT_INFO: Item<ContractInfo> = Item::new("contract_info");
+pub const CONTRACT_INFO_KEY: &str = "market_info";
+pub const CONTRACT_INFO: Item<ContractInfo> = Item::new(CONTRACT_INFO_KEY);
 
 pub const COLLECTION_STAKER_INFO_COUNT: Item<u64> = Item::new("collection_staker_info_count");
 
@@ -0,0 +1,706 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+
+use cosmwasm_std::{
+    attr, from_json, to_json_binary, Addr, Binary, Coin, CosmosMsg, Deps, DepsMut, Env,
+    MessageInfo, Order, Record, Response, StdResult, Uint128, WasmMsg,
+};
+use cw1155::Cw1155ExecuteMsg;
+use cw1155::Cw1155ReceiveMsg;
+use cw2::set_contract_version;
+use cw20::Cw20ReceiveMsg;
+use cw721::{Cw721ExecuteMsg, Cw721ReceiveMsg};
+use cw_storage_plus::Bound;
+use market::{AssetInfo, MarketHubContract, PageResponse};
+use market_ai_royalty::{parse_transfer_msg, pay_royalties, AiRoyaltyQueryMsg, Royalty};
+use migration::{migrate_version, MigrationStep};
+
+use crate::error::ContractError;
+use crate::msg::{
+    CreateSwapMsg, ExecuteMsg, InstantiateMsg, MigrateMsg, ProxyQueryMsg, QueryMsg, ReceiveMsg,
+    SwapHookMsg, UpdateContractMsg,
+};
+use crate::state::{
+    increment_swaps, swaps, ContractInfo, EscrowedAsset, Swap, SwapAsset, CONTRACT_INFO,
+};
+
+pub const MAX_DECIMAL_POINT: u64 = 1_000_000_000;
+pub const AI_ROYALTY_STORAGE: &str = "ai_royalty";
+// settings for pagination
+const MAX_LIMIT: u8 = 100;
+const DEFAULT_LIMIT: u8 = 20;
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:market_nft_swap";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    let info = ContractInfo {
+        creator: info.sender,
+        governance: MarketHubContract(msg.governance),
+        decimal_point: msg.decimal_point.unwrap_or(MAX_DECIMAL_POINT),
+    };
+    CONTRACT_INFO.save(deps.storage, &info)?;
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::CreateSwap(msg) => try_create_swap(deps, env, info, msg),
+        ExecuteMsg::DepositFunds { swap_id } => {
+            let assets = native_assets(&info.funds);
+            try_deposit(deps, env, swap_id, info.sender, assets)
+        }
+        ExecuteMsg::CancelSwap { swap_id } => try_cancel_swap(deps, env, info, swap_id),
+        ExecuteMsg::ReceiveNft(msg) => try_receive_nft(deps, env, info, msg),
+        ExecuteMsg::Receive(ReceiveMsg::Cw1155(msg)) => try_receive_1155(deps, env, info, msg),
+        ExecuteMsg::Receive(ReceiveMsg::Cw20(msg)) => try_receive_cw20(deps, env, info, msg),
+        ExecuteMsg::UpdateInfo(msg) => try_update_info(deps, info, msg),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::GetContractInfo {} => to_json_binary(&CONTRACT_INFO.load(deps.storage)?),
+        QueryMsg::GetSwap { swap_id } => to_json_binary(&query_swap(deps, swap_id)?),
+        QueryMsg::GetSwaps {
+            offset,
+            limit,
+            order,
+        } => to_json_binary(&query_swaps(deps, offset, limit, order)?),
+        QueryMsg::GetSwapsByMaker {
+            maker,
+            offset,
+            limit,
+            order,
+        } => to_json_binary(&query_swaps_by_maker(deps, maker, offset, limit, order)?),
+        QueryMsg::GetSwapsByTaker {
+            taker,
+            offset,
+            limit,
+            order,
+        } => to_json_binary(&query_swaps_by_taker(deps, taker, offset, limit, order)?),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    Ok(migrate_version(
+        deps,
+        &env,
+        CONTRACT_NAME,
+        CONTRACT_VERSION,
+        MIGRATIONS,
+    )?)
+}
+
+const MIGRATIONS: &[MigrationStep] = &[];
+
+// ============================== Message Handlers ==============================
+
+fn native_assets(funds: &[Coin]) -> Vec<SwapAsset> {
+    funds
+        .iter()
+        .map(|coin| SwapAsset::Native {
+            denom: coin.denom.clone(),
+            amount: coin.amount,
+        })
+        .collect()
+}
+
+fn escrow(assets: Vec<SwapAsset>) -> Vec<EscrowedAsset> {
+    assets
+        .into_iter()
+        .map(|asset| EscrowedAsset {
+            asset,
+            deposited: false,
+        })
+        .collect()
+}
+
+fn is_expired(env: &Env, swap: &Swap) -> bool {
+    swap.expiration
+        .is_some_and(|expiration| env.block.time.seconds() >= expiration)
+}
+
+pub fn try_create_swap(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: CreateSwapMsg,
+) -> Result<Response, ContractError> {
+    if msg.maker_assets.is_empty() || msg.taker_assets.is_empty() {
+        return Err(ContractError::EmptySwap {});
+    }
+    if msg
+        .maker_assets
+        .iter()
+        .chain(msg.taker_assets.iter())
+        .any(|asset| match asset {
+            SwapAsset::Cw721 { .. } => false,
+            SwapAsset::Cw1155 { amount, .. }
+            | SwapAsset::Cw20 { amount, .. }
+            | SwapAsset::Native { amount, .. } => amount.is_zero(),
+        })
+    {
+        return Err(ContractError::InvalidArgument {
+            arg: "amount".to_string(),
+        });
+    }
+    if msg
+        .taker
+        .as_ref()
+        .is_some_and(|taker| taker.eq(&info.sender))
+    {
+        return Err(ContractError::InvalidArgument {
+            arg: "taker".to_string(),
+        });
+    }
+    if msg
+        .expiration
+        .is_some_and(|expiration| expiration <= env.block.time.seconds())
+    {
+        return Err(ContractError::InvalidArgument {
+            arg: "expiration".to_string(),
+        });
+    }
+
+    let id = increment_swaps(deps.storage)?;
+    let swap = Swap {
+        id: Some(id),
+        maker: info.sender.clone(),
+        taker: msg.taker,
+        maker_assets: escrow(msg.maker_assets),
+        taker_assets: escrow(msg.taker_assets),
+        expiration: msg.expiration,
+        charge_royalty: msg.charge_royalty,
+    };
+    swaps().save(deps.storage, &id.to_be_bytes(), &swap)?;
+
+    let mut rsp = Response::new().add_attributes(vec![
+        attr("action", "create_swap"),
+        attr("maker", info.sender.clone()),
+        attr("swap_id", id.to_string()),
+    ]);
+    // maker can escrow its native coins right away
+    if !info.funds.is_empty() {
+        let deposit_rsp = try_deposit(deps, env, id, info.sender, native_assets(&info.funds))?;
+        rsp = rsp
+            .add_submessages(deposit_rsp.messages)
+            .add_attributes(deposit_rsp.attributes);
+    }
+    Ok(rsp)
+}
+
+pub fn try_receive_nft(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    rcv_msg: Cw721ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let SwapHookMsg::Deposit { swap_id } =
+        from_json(&rcv_msg.msg.ok_or(ContractError::InvalidArgument {
+            arg: "msg".to_string(),
+        })?)?;
+    try_deposit(
+        deps,
+        env,
+        swap_id,
+        rcv_msg.sender,
+        vec![SwapAsset::Cw721 {
+            contract_addr: info.sender,
+            token_id: rcv_msg.token_id,
+        }],
+    )
+}
+
+pub fn try_receive_1155(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    rcv_msg: Cw1155ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let SwapHookMsg::Deposit { swap_id } = from_json(&rcv_msg.msg)?;
+    // tokens are sent from the owner account, which may differ from the operator
+    let depositor = rcv_msg.from.unwrap_or(rcv_msg.operator);
+    try_deposit(
+        deps,
+        env,
+        swap_id,
+        Addr::unchecked(depositor),
+        vec![SwapAsset::Cw1155 {
+            contract_addr: info.sender,
+            token_id: rcv_msg.token_id,
+            amount: rcv_msg.amount,
+        }],
+    )
+}
+
+pub fn try_receive_cw20(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    rcv_msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let SwapHookMsg::Deposit { swap_id } = from_json(&rcv_msg.msg)?;
+    try_deposit(
+        deps,
+        env,
+        swap_id,
+        Addr::unchecked(rcv_msg.sender),
+        vec![SwapAsset::Cw20 {
+            contract_addr: info.sender,
+            amount: rcv_msg.amount,
+        }],
+    )
+}
+
+/// mark the assets as escrowed on the depositor side, and settle the swap once both sides are funded
+pub fn try_deposit(
+    deps: DepsMut,
+    env: Env,
+    swap_id: u64,
+    depositor: Addr,
+    assets: Vec<SwapAsset>,
+) -> Result<Response, ContractError> {
+    if assets.is_empty() {
+        return Err(ContractError::InvalidArgument {
+            arg: "funds".to_string(),
+        });
+    }
+    let mut swap = swaps()
+        .load(deps.storage, &swap_id.to_be_bytes())
+        .map_err(|_| ContractError::SwapNotFound {})?;
+    if is_expired(&env, &swap) {
+        return Err(ContractError::SwapExpired {});
+    }
+
+    let side = if depositor.eq(&swap.maker) {
+        &mut swap.maker_assets
+    } else {
+        match &swap.taker {
+            Some(taker) if taker.ne(&depositor) => {
+                return Err(ContractError::Unauthorized {
+                    sender: depositor.to_string(),
+                })
+            }
+            // first depositor of an open swap becomes its taker
+            _ => swap.taker = Some(depositor.clone()),
+        }
+        &mut swap.taker_assets
+    };
+
+    for asset in assets {
+        let escrowed = side
+            .iter_mut()
+            .find(|escrowed| !escrowed.deposited && escrowed.asset.eq(&asset))
+            .ok_or_else(|| ContractError::UnexpectedAsset {
+                asset: format!("{:?}", asset),
+            })?;
+        escrowed.deposited = true;
+    }
+
+    let mut rsp = Response::new().add_attributes(vec![
+        attr("action", "deposit_swap"),
+        attr("depositor", depositor),
+        attr("swap_id", swap_id.to_string()),
+    ]);
+
+    if swap.is_funded() {
+        swaps().remove(deps.storage, &swap_id.to_be_bytes())?;
+        rsp = settle_swap(deps.as_ref(), &env, &swap, rsp)?;
+    } else {
+        swaps().save(deps.storage, &swap_id.to_be_bytes(), &swap)?;
+    }
+    Ok(rsp)
+}
+
+fn settle_swap(
+    deps: Deps,
+    env: &Env,
+    swap: &Swap,
+    mut rsp: Response,
+) -> Result<Response, ContractError> {
+    // is_funded guarantees a taker
+    let taker = swap.taker.clone().unwrap();
+    let mut cosmos_msgs: Vec<CosmosMsg> = vec![];
+
+    pay_side(
+        deps,
+        env,
+        swap,
+        &swap.taker_assets,
+        &swap.maker_assets,
+        &swap.maker,
+        &mut cosmos_msgs,
+        &mut rsp,
+    )?;
+    pay_side(
+        deps,
+        env,
+        swap,
+        &swap.maker_assets,
+        &swap.taker_assets,
+        &taker,
+        &mut cosmos_msgs,
+        &mut rsp,
+    )?;
+
+    rsp = rsp.add_messages(cosmos_msgs);
+    rsp.attributes.extend(vec![
+        attr("action", "settle_swap"),
+        attr("maker", swap.maker.clone()),
+        attr("taker", taker),
+        attr("royalty", swap.charge_royalty.to_string()),
+    ]);
+    Ok(rsp)
+}
+
+/// transfer `received` assets to `recipient`, which gives `given` assets in exchange.
+/// When royalties are charged, the cw20 and native assets received are split evenly as the price of every given nft
+#[allow(clippy::too_many_arguments)]
+fn pay_side(
+    deps: Deps,
+    env: &Env,
+    swap: &Swap,
+    received: &[EscrowedAsset],
+    given: &[EscrowedAsset],
+    recipient: &Addr,
+    cosmos_msgs: &mut Vec<CosmosMsg>,
+    rsp: &mut Response,
+) -> Result<(), ContractError> {
+    let ContractInfo {
+        governance,
+        decimal_point,
+        ..
+    } = CONTRACT_INFO.load(deps.storage)?;
+    let contract_addr = env.contract.address.as_str();
+
+    let given_nfts: Vec<(&Addr, &String)> = given
+        .iter()
+        .filter_map(|escrowed| match &escrowed.asset {
+            SwapAsset::Cw721 {
+                contract_addr,
+                token_id,
+            }
+            | SwapAsset::Cw1155 {
+                contract_addr,
+                token_id,
+                ..
+            } => Some((contract_addr, token_id)),
+            _ => None,
+        })
+        .collect();
+    let nft_royalties: Vec<Vec<Royalty>> = if swap.charge_royalty {
+        given_nfts
+            .iter()
+            .map(|(nft_addr, token_id)| get_royalties(deps, &governance, nft_addr, token_id))
+            .collect::<StdResult<_>>()?
+    } else {
+        vec![]
+    };
+
+    for escrowed in received {
+        let (asset_info, amount) = match &escrowed.asset {
+            SwapAsset::Cw20 {
+                contract_addr,
+                amount,
+            } => (
+                AssetInfo::Token {
+                    contract_addr: contract_addr.clone(),
+                },
+                *amount,
+            ),
+            SwapAsset::Native { denom, amount } => (
+                AssetInfo::NativeToken {
+                    denom: denom.clone(),
+                },
+                *amount,
+            ),
+            nft => {
+                cosmos_msgs.push(transfer_nft_msg(nft, contract_addr, recipient)?);
+                continue;
+            }
+        };
+
+        let mut remaining = amount;
+        if !nft_royalties.is_empty() {
+            let price_per_nft = amount / Uint128::from(nft_royalties.len() as u64);
+            for royalties in nft_royalties.iter() {
+                pay_royalties(
+                    royalties,
+                    &price_per_nft,
+                    decimal_point,
+                    &mut remaining,
+                    cosmos_msgs,
+                    rsp,
+                    contract_addr,
+                    &to_json_binary(&asset_info)?.to_base64(),
+                    asset_info.clone(),
+                )?;
+            }
+        }
+        if !remaining.is_zero() {
+            cosmos_msgs.push(parse_transfer_msg(
+                asset_info,
+                remaining,
+                contract_addr,
+                recipient.clone(),
+            )?);
+        }
+    }
+    Ok(())
+}
+
+fn get_royalties(
+    deps: Deps,
+    governance: &MarketHubContract,
+    contract_addr: &Addr,
+    token_id: &str,
+) -> StdResult<Vec<Royalty>> {
+    let msg = to_json_binary(&ProxyQueryMsg::Msg(
+        AiRoyaltyQueryMsg::GetRoyaltiesContractTokenId {
+            contract_addr: contract_addr.clone(),
+            token_id: token_id.to_string(),
+            offset: None,
+            start_after: None,
+            limit: Some(30),
+            order: Some(1),
+            with_total: None,
+        },
+    ))?;
+    // nfts without royalty records get an empty page, any failure of the query aborts the swap
+    // so the royalties are never skipped silently
+    let page: PageResponse<Royalty> =
+        governance.query_storage(AI_ROYALTY_STORAGE.to_string(), msg, &deps.querier)?;
+    Ok(page.items)
+}
+
+fn transfer_nft_msg(asset: &SwapAsset, sender: &str, recipient: &Addr) -> StdResult<CosmosMsg> {
+    let (contract_addr, msg) = match asset {
+        SwapAsset::Cw721 {
+            contract_addr,
+            token_id,
+        } => (
+            contract_addr,
+            to_json_binary(&Cw721ExecuteMsg::TransferNft {
+                recipient: recipient.clone(),
+                token_id: token_id.clone(),
+            })?,
+        ),
+        SwapAsset::Cw1155 {
+            contract_addr,
+            token_id,
+            amount,
+        } => (
+            contract_addr,
+            to_json_binary(&Cw1155ExecuteMsg::SendFrom {
+                from: sender.to_string(),
+                to: recipient.to_string(),
+                token_id: token_id.clone(),
+                value: *amount,
+                msg: None,
+            })?,
+        ),
+        SwapAsset::Cw20 {
+            contract_addr,
+            amount,
+        } => {
+            return parse_transfer_msg(
+                AssetInfo::Token {
+                    contract_addr: contract_addr.clone(),
+                },
+                *amount,
+                sender,
+                recipient.clone(),
+            )
+        }
+        SwapAsset::Native { denom, amount } => {
+            return parse_transfer_msg(
+                AssetInfo::NativeToken {
+                    denom: denom.clone(),
+                },
+                *amount,
+                sender,
+                recipient.clone(),
+            )
+        }
+    };
+    Ok(WasmMsg::Execute {
+        contract_addr: contract_addr.to_string(),
+        msg,
+        funds: vec![],
+    }
+    .into())
+}
+
+pub fn try_cancel_swap(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    swap_id: u64,
+) -> Result<Response, ContractError> {
+    let swap = swaps()
+        .load(deps.storage, &swap_id.to_be_bytes())
+        .map_err(|_| ContractError::SwapNotFound {})?;
+    if info.sender.ne(&swap.maker) && swap.taker.as_ref().ne(&Some(&info.sender)) {
+        return Err(ContractError::Unauthorized {
+            sender: info.sender.to_string(),
+        });
+    }
+
+    let contract_addr = env.contract.address.as_str();
+    let mut cosmos_msgs: Vec<CosmosMsg> = vec![];
+    for escrowed in swap.maker_assets.iter().filter(|a| a.deposited) {
+        cosmos_msgs.push(transfer_nft_msg(
+            &escrowed.asset,
+            contract_addr,
+            &swap.maker,
+        )?);
+    }
+    // taker deposits imply a taker
+    if let Some(taker) = &swap.taker {
+        for escrowed in swap.taker_assets.iter().filter(|a| a.deposited) {
+            cosmos_msgs.push(transfer_nft_msg(&escrowed.asset, contract_addr, taker)?);
+        }
+    }
+    swaps().remove(deps.storage, &swap_id.to_be_bytes())?;
+
+    Ok(Response::new()
+        .add_messages(cosmos_msgs)
+        .add_attributes(vec![
+            attr("action", "cancel_swap"),
+            attr("caller", info.sender),
+            attr("swap_id", swap_id.to_string()),
+        ]))
+}
+
+pub fn try_update_info(
+    deps: DepsMut,
+    info: MessageInfo,
+    msg: UpdateContractMsg,
+) -> Result<Response, ContractError> {
+    let new_contract_info = CONTRACT_INFO.update(deps.storage, |mut contract_info| {
+        // Unauthorized
+        if !info.sender.eq(&contract_info.creator) {
+            return Err(ContractError::Unauthorized {
+                sender: info.sender.to_string(),
+            });
+        }
+        if let Some(creator) = msg.creator {
+            contract_info.creator = creator;
+        }
+        if let Some(governance) = msg.governance {
+            contract_info.governance = MarketHubContract(governance);
+        }
+        if let Some(decimal_point) = msg.decimal_point {
+            contract_info.decimal_point = decimal_point;
+        }
+        Ok(contract_info)
+    })?;
+
+    Ok(Response::new()
+        .add_attributes(vec![attr("action", "update_info")])
+        .set_data(to_json_binary(&new_contract_info)?))
+}
+
+// ============================== Query Handlers ==============================
+
+fn _get_range_params(
+    offset: Option<u64>,
+    limit: Option<u8>,
+    order: Option<u8>,
+) -> (usize, Option<Bound>, Option<Bound>, Order) {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let mut min = None;
+    let mut max = None;
+    let mut order_enum = Order::Descending;
+    if let Some(num) = order {
+        if num == 1 {
+            order_enum = Order::Ascending;
+        }
+    }
+
+    // if there is offset, assign to min or max
+    if let Some(offset) = offset {
+        let offset_value = Some(Bound::Exclusive(offset.to_be_bytes().to_vec()));
+        match order_enum {
+            Order::Ascending => min = offset_value,
+            Order::Descending => max = offset_value,
+        }
+    };
+    (limit, min, max, order_enum)
+}
+
+fn parse_swap(item: StdResult<Record<Swap>>) -> StdResult<Swap> {
+    item.map(|(_, swap)| swap)
+}
+
+pub fn query_swap(deps: Deps, swap_id: u64) -> StdResult<Swap> {
+    swaps().load(deps.storage, &swap_id.to_be_bytes())
+}
+
+pub fn query_swaps(
+    deps: Deps,
+    offset: Option<u64>,
+    limit: Option<u8>,
+    order: Option<u8>,
+) -> StdResult<Vec<Swap>> {
+    let (limit, min, max, order_enum) = _get_range_params(offset, limit, order);
+    swaps()
+        .range(deps.storage, min, max, order_enum)
+        .take(limit)
+        .map(parse_swap)
+        .collect()
+}
+
+pub fn query_swaps_by_maker(
+    deps: Deps,
+    maker: Addr,
+    offset: Option<u64>,
+    limit: Option<u8>,
+    order: Option<u8>,
+) -> StdResult<Vec<Swap>> {
+    let (limit, min, max, order_enum) = _get_range_params(offset, limit, order);
+    swaps()
+        .idx
+        .maker
+        .items(deps.storage, maker.as_bytes(), min, max, order_enum)
+        .take(limit)
+        .map(parse_swap)
+        .collect()
+}
+
+pub fn query_swaps_by_taker(
+    deps: Deps,
+    taker: Option<Addr>,
+    offset: Option<u64>,
+    limit: Option<u8>,
+    order: Option<u8>,
+) -> StdResult<Vec<Swap>> {
+    let (limit, min, max, order_enum) = _get_range_params(offset, limit, order);
+    let taker_raw = taker
+        .map(|addr| addr.as_bytes().to_vec())
+        .unwrap_or_default();
+    swaps()
+        .idx
+        .taker
+        .items(deps.storage, &taker_raw, min, max, order_enum)
+        .take(limit)
+        .map(parse_swap)
+        .collect()
+}
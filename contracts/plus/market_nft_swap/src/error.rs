@@ -0,0 +1,37 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Overflow(#[from] cosmwasm_std::OverflowError),
+
+    #[error("Unauthorized nft swap with sender: {sender}")]
+    Unauthorized { sender: String },
+
+    #[error("Swap is not found")]
+    SwapNotFound {},
+
+    #[error("Swap has expired")]
+    SwapExpired {},
+
+    #[error("Both sides of the swap must offer at least one asset")]
+    EmptySwap {},
+
+    #[error("The argument {arg} are invalid")]
+    InvalidArgument { arg: String },
+
+    #[error("Deposited asset does not match any pending asset of the swap: {asset}")]
+    UnexpectedAsset { asset: String },
+}
+
+impl From<ContractError> for String {
+    /// Utility for explicit conversion to `String`.
+    #[inline]
+    fn from(err: ContractError) -> Self {
+        err.to_string()
+    }
+}
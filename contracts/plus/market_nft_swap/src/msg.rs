@@ -0,0 +1,104 @@
+use std::fmt;
+
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Addr, Empty};
+use cw1155::Cw1155ReceiveMsg;
+use cw20::Cw20ReceiveMsg;
+use cw721::Cw721ReceiveMsg;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::state::{ContractInfo, Swap, SwapAsset};
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    pub governance: Addr,
+    pub decimal_point: Option<u64>,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Propose a swap, native coins of the maker side can be escrowed with the message
+    CreateSwap(CreateSwapMsg),
+    /// Escrow native coins of the caller side
+    DepositFunds {
+        swap_id: u64,
+    },
+    /// Refund every escrowed asset and remove the swap, callable by the maker or the taker
+    CancelSwap {
+        swap_id: u64,
+    },
+    ReceiveNft(Cw721ReceiveMsg),
+    Receive(ReceiveMsg),
+    UpdateInfo(UpdateContractMsg),
+}
+
+/// cw1155 and cw20 both send their hook under the `receive` variant
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(untagged)]
+pub enum ReceiveMsg {
+    Cw1155(Cw1155ReceiveMsg),
+    Cw20(Cw20ReceiveMsg),
+}
+
+#[cw_serde]
+pub enum SwapHookMsg {
+    Deposit { swap_id: u64 },
+}
+
+#[cw_serde]
+pub struct CreateSwapMsg {
+    pub taker: Option<Addr>,
+    pub maker_assets: Vec<SwapAsset>,
+    pub taker_assets: Vec<SwapAsset>,
+    pub expiration: Option<u64>,
+    pub charge_royalty: bool,
+}
+
+#[cw_serde]
+pub struct UpdateContractMsg {
+    pub creator: Option<Addr>,
+    pub governance: Option<Addr>,
+    pub decimal_point: Option<u64>,
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(ContractInfo)]
+    GetContractInfo {},
+    #[returns(Swap)]
+    GetSwap { swap_id: u64 },
+    #[returns(Vec<Swap>)]
+    GetSwaps {
+        offset: Option<u64>,
+        limit: Option<u8>,
+        order: Option<u8>,
+    },
+    #[returns(Vec<Swap>)]
+    GetSwapsByMaker {
+        maker: Addr,
+        offset: Option<u64>,
+        limit: Option<u8>,
+        order: Option<u8>,
+    },
+    /// None taker returns the swaps open to anyone
+    #[returns(Vec<Swap>)]
+    GetSwapsByTaker {
+        taker: Option<Addr>,
+        offset: Option<u64>,
+        limit: Option<u8>,
+        order: Option<u8>,
+    },
+}
+
+#[cw_serde]
+pub enum ProxyQueryMsg<T = Empty>
+where
+    T: Clone + fmt::Debug + PartialEq + JsonSchema + Serialize,
+{
+    Msg(T),
+}
+
+#[cw_serde]
+pub struct MigrateMsg {}
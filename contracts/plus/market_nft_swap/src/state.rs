@@ -0,0 +1,109 @@
+use cosmwasm_std::{Addr, StdResult, Storage, Uint128};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, MultiIndex};
+use market::MarketHubContract;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct ContractInfo {
+    pub creator: Addr,
+    /// market hub used to collect royalties from the ai royalty storage
+    pub governance: MarketHubContract,
+    pub decimal_point: u64,
+}
+
+pub const CONTRACT_INFO: Item<ContractInfo> = Item::new("market_info");
+pub const SWAPS_COUNT: Item<u64> = Item::new("num_swaps");
+
+pub fn num_swaps(storage: &dyn Storage) -> StdResult<u64> {
+    Ok(SWAPS_COUNT.may_load(storage)?.unwrap_or_default())
+}
+
+pub fn increment_swaps(storage: &mut dyn Storage) -> StdResult<u64> {
+    let val = num_swaps(storage)? + 1;
+    SWAPS_COUNT.save(storage, &val)?;
+    Ok(val)
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum SwapAsset {
+    Cw721 {
+        contract_addr: Addr,
+        token_id: String,
+    },
+    Cw1155 {
+        contract_addr: Addr,
+        token_id: String,
+        amount: Uint128,
+    },
+    Cw20 {
+        contract_addr: Addr,
+        amount: Uint128,
+    },
+    Native {
+        denom: String,
+        amount: Uint128,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct EscrowedAsset {
+    pub asset: SwapAsset,
+    /// true once the asset is held by the swap contract
+    pub deposited: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct Swap {
+    pub id: Option<u64>,
+    pub maker: Addr,
+    /// None means anyone can take the swap, the first depositor becomes the taker
+    pub taker: Option<Addr>,
+    pub maker_assets: Vec<EscrowedAsset>,
+    pub taker_assets: Vec<EscrowedAsset>,
+    /// timestamp in seconds after which no more deposits are accepted
+    pub expiration: Option<u64>,
+    /// charge creator royalties of the swapped nfts on the cw20 and native assets of the other side
+    pub charge_royalty: bool,
+}
+
+impl Swap {
+    pub fn is_funded(&self) -> bool {
+        self.maker_assets
+            .iter()
+            .chain(self.taker_assets.iter())
+            .all(|escrowed| escrowed.deposited)
+    }
+}
+
+pub struct SwapIndexes<'a> {
+    pub maker: MultiIndex<'a, Swap>,
+    pub taker: MultiIndex<'a, Swap>,
+}
+
+impl<'a> IndexList<Swap> for SwapIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Swap>> + '_> {
+        let v: Vec<&dyn Index<Swap>> = vec![&self.maker, &self.taker];
+        Box::new(v.into_iter())
+    }
+}
+
+// this IndexedMap instance has a lifetime
+pub fn swaps<'a>() -> IndexedMap<'a, &'a [u8], Swap, SwapIndexes<'a>> {
+    let indexes = SwapIndexes {
+        maker: MultiIndex::new(|o| o.maker.as_bytes().to_vec(), "swaps", "swaps__maker"),
+        // if we put None taker, we got all open swaps
+        taker: MultiIndex::new(
+            |o| {
+                o.taker
+                    .as_ref()
+                    .map(|addr| addr.as_bytes().to_vec())
+                    .unwrap_or_default()
+            },
+            "swaps",
+            "swaps__taker",
+        ),
+    };
+    IndexedMap::new("swaps", indexes)
+}
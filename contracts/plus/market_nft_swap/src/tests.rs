@@ -0,0 +1,462 @@
+use crate::contract::{execute, instantiate, query};
+use crate::error::ContractError;
+use crate::msg::{CreateSwapMsg, ExecuteMsg, InstantiateMsg, QueryMsg, ReceiveMsg, SwapHookMsg};
+use crate::state::{Swap, SwapAsset};
+use cosmwasm_std::testing::{mock_info, MockApi, MockStorage};
+use cosmwasm_std::{
+    coins, from_json, to_json_binary, Addr, BankMsg, ContractResult, CosmosMsg, OwnedDeps,
+    QuerierResult, SystemResult, Uint128, WasmMsg, WasmQuery,
+};
+use cw1155::{Cw1155ExecuteMsg, Cw1155ReceiveMsg};
+use cw20::Cw20ReceiveMsg;
+use cw721::{Cw721ExecuteMsg, Cw721ReceiveMsg};
+use market::mock::{mock_dependencies, mock_env, MockQuerier};
+use market::PageResponse;
+use market_ai_royalty::Royalty;
+
+const CREATOR: &str = "owner";
+const SWAP_ADDR: &str = "swap";
+const HUB_ADDR: &str = "hub";
+const MAKER: &str = "maker";
+const TAKER: &str = "taker";
+const NFT_ADDR: &str = "oraichain_nft";
+const NFT_1155_ADDR: &str = "ow1155";
+const CW20_ADDR: &str = "airi";
+const DENOM: &str = "orai";
+const ROYALTY_CREATOR: &str = "royalty_creator";
+
+// every nft has a 10% royalty
+fn mock_wasm(request: &WasmQuery) -> QuerierResult {
+    match request {
+        WasmQuery::Smart { contract_addr, .. } if contract_addr.eq(HUB_ADDR) => {
+            SystemResult::Ok(ContractResult::Ok(
+                to_json_binary(&PageResponse {
+                    items: vec![Royalty {
+                        contract_addr: Addr::unchecked(NFT_ADDR),
+                        token_id: "1".to_string(),
+                        creator: Addr::unchecked(ROYALTY_CREATOR),
+                        royalty: 100_000_000,
+                        creator_type: "creator".to_string(),
+                        payees: None,
+                    }],
+                    next_cursor: None,
+                    total: None,
+                })
+                .unwrap(),
+            ))
+        }
+        _ => SystemResult::Ok(ContractResult::Err("not found".to_string())),
+    }
+}
+
+fn setup_contract() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+    setup_contract_with(mock_wasm)
+}
+
+fn setup_contract_with(
+    wasm_handler: fn(&WasmQuery) -> QuerierResult,
+) -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+    let mut deps = mock_dependencies(Addr::unchecked(SWAP_ADDR), &[], wasm_handler);
+    instantiate(
+        deps.as_mut(),
+        mock_env(SWAP_ADDR),
+        mock_info(CREATOR, &[]),
+        InstantiateMsg {
+            governance: Addr::unchecked(HUB_ADDR),
+            decimal_point: None,
+        },
+    )
+    .unwrap();
+    deps
+}
+
+fn nft_asset(token_id: &str) -> SwapAsset {
+    SwapAsset::Cw721 {
+        contract_addr: Addr::unchecked(NFT_ADDR),
+        token_id: token_id.to_string(),
+    }
+}
+
+fn create_swap(
+    deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+    taker: Option<&str>,
+    taker_assets: Vec<SwapAsset>,
+    charge_royalty: bool,
+) -> u64 {
+    let res = execute(
+        deps.as_mut(),
+        mock_env(SWAP_ADDR),
+        mock_info(MAKER, &[]),
+        ExecuteMsg::CreateSwap(CreateSwapMsg {
+            taker: taker.map(Addr::unchecked),
+            maker_assets: vec![nft_asset("1")],
+            taker_assets,
+            expiration: None,
+            charge_royalty,
+        }),
+    )
+    .unwrap();
+    res.attributes
+        .iter()
+        .find(|attr| attr.key.eq("swap_id"))
+        .unwrap()
+        .value
+        .parse()
+        .unwrap()
+}
+
+fn deposit_nft(
+    deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+    sender: &str,
+    token_id: &str,
+    swap_id: u64,
+) -> Result<cosmwasm_std::Response, ContractError> {
+    execute(
+        deps.as_mut(),
+        mock_env(SWAP_ADDR),
+        mock_info(NFT_ADDR, &[]),
+        ExecuteMsg::ReceiveNft(Cw721ReceiveMsg {
+            sender: Addr::unchecked(sender),
+            token_id: token_id.to_string(),
+            msg: Some(to_json_binary(&SwapHookMsg::Deposit { swap_id }).unwrap()),
+        }),
+    )
+}
+
+fn query_swap(deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>, swap_id: u64) -> Option<Swap> {
+    query(
+        deps.as_ref(),
+        mock_env(SWAP_ADDR),
+        QueryMsg::GetSwap { swap_id },
+    )
+    .ok()
+    .map(|bin| from_json(&bin).unwrap())
+}
+
+#[test]
+fn test_swap_nft_for_nft() {
+    let mut deps = setup_contract();
+    let swap_id = create_swap(
+        &mut deps,
+        Some(TAKER),
+        vec![SwapAsset::Cw1155 {
+            contract_addr: Addr::unchecked(NFT_1155_ADDR),
+            token_id: "a".to_string(),
+            amount: Uint128::from(5u64),
+        }],
+        true,
+    );
+
+    deposit_nft(&mut deps, MAKER, "1", swap_id).unwrap();
+    let swap = query_swap(&deps, swap_id).unwrap();
+    assert!(swap.maker_assets[0].deposited);
+    assert!(!swap.is_funded());
+
+    // a stranger cannot fill a private swap
+    let err = execute(
+        deps.as_mut(),
+        mock_env(SWAP_ADDR),
+        mock_info(NFT_1155_ADDR, &[]),
+        ExecuteMsg::Receive(ReceiveMsg::Cw1155(Cw1155ReceiveMsg {
+            operator: "stranger".to_string(),
+            from: None,
+            token_id: "a".to_string(),
+            amount: Uint128::from(5u64),
+            msg: to_json_binary(&SwapHookMsg::Deposit { swap_id }).unwrap(),
+        })),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Unauthorized {
+            sender: "stranger".to_string()
+        }
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(SWAP_ADDR),
+        mock_info(NFT_1155_ADDR, &[]),
+        ExecuteMsg::Receive(ReceiveMsg::Cw1155(Cw1155ReceiveMsg {
+            operator: TAKER.to_string(),
+            from: Some(TAKER.to_string()),
+            token_id: "a".to_string(),
+            amount: Uint128::from(5u64),
+            msg: to_json_binary(&SwapHookMsg::Deposit { swap_id }).unwrap(),
+        })),
+    )
+    .unwrap();
+
+    // no fungible asset involved, so no royalty is paid
+    assert_eq!(
+        res.messages
+            .into_iter()
+            .map(|msg| msg.msg)
+            .collect::<Vec<CosmosMsg>>(),
+        vec![
+            WasmMsg::Execute {
+                contract_addr: NFT_1155_ADDR.to_string(),
+                msg: to_json_binary(&Cw1155ExecuteMsg::SendFrom {
+                    from: SWAP_ADDR.to_string(),
+                    to: MAKER.to_string(),
+                    token_id: "a".to_string(),
+                    value: Uint128::from(5u64),
+                    msg: None,
+                })
+                .unwrap(),
+                funds: vec![],
+            }
+            .into(),
+            WasmMsg::Execute {
+                contract_addr: NFT_ADDR.to_string(),
+                msg: to_json_binary(&Cw721ExecuteMsg::TransferNft {
+                    recipient: Addr::unchecked(TAKER),
+                    token_id: "1".to_string(),
+                })
+                .unwrap(),
+                funds: vec![],
+            }
+            .into(),
+        ]
+    );
+    assert_eq!(query_swap(&deps, swap_id), None);
+}
+
+#[test]
+fn test_swap_nft_for_funds_with_royalty() {
+    let mut deps = setup_contract();
+    // open swap, anyone can fill it
+    let swap_id = create_swap(
+        &mut deps,
+        None,
+        vec![
+            SwapAsset::Native {
+                denom: DENOM.to_string(),
+                amount: Uint128::from(1000u64),
+            },
+            SwapAsset::Cw20 {
+                contract_addr: Addr::unchecked(CW20_ADDR),
+                amount: Uint128::from(500u64),
+            },
+        ],
+        true,
+    );
+    deposit_nft(&mut deps, MAKER, "1", swap_id).unwrap();
+
+    // wrong amount is rejected
+    let err = execute(
+        deps.as_mut(),
+        mock_env(SWAP_ADDR),
+        mock_info(TAKER, &coins(999, DENOM)),
+        ExecuteMsg::DepositFunds { swap_id },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::UnexpectedAsset { .. }));
+
+    execute(
+        deps.as_mut(),
+        mock_env(SWAP_ADDR),
+        mock_info(TAKER, &coins(1000, DENOM)),
+        ExecuteMsg::DepositFunds { swap_id },
+    )
+    .unwrap();
+    assert_eq!(
+        query_swap(&deps, swap_id).unwrap().taker,
+        Some(Addr::unchecked(TAKER))
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(SWAP_ADDR),
+        mock_info(CW20_ADDR, &[]),
+        ExecuteMsg::Receive(ReceiveMsg::Cw20(Cw20ReceiveMsg {
+            sender: TAKER.to_string(),
+            amount: Uint128::from(500u64),
+            msg: to_json_binary(&SwapHookMsg::Deposit { swap_id }).unwrap(),
+        })),
+    )
+    .unwrap();
+
+    let msgs: Vec<CosmosMsg> = res.messages.into_iter().map(|msg| msg.msg).collect();
+    let cw20_transfer = |recipient: &str, amount: u64| -> CosmosMsg {
+        WasmMsg::Execute {
+            contract_addr: CW20_ADDR.to_string(),
+            msg: to_json_binary(&cw20::Cw20ExecuteMsg::Transfer {
+                recipient: recipient.to_string(),
+                amount: Uint128::from(amount),
+            })
+            .unwrap(),
+            funds: vec![],
+        }
+        .into()
+    };
+    assert_eq!(
+        msgs,
+        vec![
+            BankMsg::Send {
+                to_address: ROYALTY_CREATOR.to_string(),
+                amount: coins(100, DENOM),
+            }
+            .into(),
+            BankMsg::Send {
+                to_address: MAKER.to_string(),
+                amount: coins(900, DENOM),
+            }
+            .into(),
+            cw20_transfer(ROYALTY_CREATOR, 50),
+            cw20_transfer(MAKER, 450),
+            WasmMsg::Execute {
+                contract_addr: NFT_ADDR.to_string(),
+                msg: to_json_binary(&Cw721ExecuteMsg::TransferNft {
+                    recipient: Addr::unchecked(TAKER),
+                    token_id: "1".to_string(),
+                })
+                .unwrap(),
+                funds: vec![],
+            }
+            .into(),
+        ]
+    );
+}
+
+#[test]
+fn test_swap_fails_when_royalties_cannot_be_queried() {
+    // the hub is unreachable, so the royalties of the nft are unknown
+    let mut deps = setup_contract_with(|_| {
+        SystemResult::Ok(ContractResult::Err("storage not found".to_string()))
+    });
+    let swap_id = create_swap(
+        &mut deps,
+        None,
+        vec![SwapAsset::Native {
+            denom: DENOM.to_string(),
+            amount: Uint128::from(1000u64),
+        }],
+        true,
+    );
+    deposit_nft(&mut deps, MAKER, "1", swap_id).unwrap();
+
+    // the swap is not settled royalty free
+    let err = execute(
+        deps.as_mut(),
+        mock_env(SWAP_ADDR),
+        mock_info(TAKER, &coins(1000, DENOM)),
+        ExecuteMsg::DepositFunds { swap_id },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::Std(..)));
+}
+
+#[test]
+fn test_cancel_swap_refunds_deposits() {
+    let mut deps = setup_contract();
+    let swap_id = create_swap(
+        &mut deps,
+        Some(TAKER),
+        vec![nft_asset("2"), nft_asset("3")],
+        false,
+    );
+    deposit_nft(&mut deps, MAKER, "1", swap_id).unwrap();
+    deposit_nft(&mut deps, TAKER, "2", swap_id).unwrap();
+    // already deposited
+    assert!(matches!(
+        deposit_nft(&mut deps, TAKER, "2", swap_id).unwrap_err(),
+        ContractError::UnexpectedAsset { .. }
+    ));
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(SWAP_ADDR),
+        mock_info("stranger", &[]),
+        ExecuteMsg::CancelSwap { swap_id },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Unauthorized {
+            sender: "stranger".to_string()
+        }
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(SWAP_ADDR),
+        mock_info(TAKER, &[]),
+        ExecuteMsg::CancelSwap { swap_id },
+    )
+    .unwrap();
+    let recipients: Vec<(String, String)> = res
+        .messages
+        .into_iter()
+        .map(|msg| match msg.msg {
+            CosmosMsg::Wasm(WasmMsg::Execute { msg, .. }) => match from_json(&msg).unwrap() {
+                Cw721ExecuteMsg::TransferNft {
+                    recipient,
+                    token_id,
+                } => (recipient.to_string(), token_id),
+                _ => panic!("unexpected msg"),
+            },
+            _ => panic!("unexpected msg"),
+        })
+        .collect();
+    assert_eq!(
+        recipients,
+        vec![
+            (MAKER.to_string(), "1".to_string()),
+            (TAKER.to_string(), "2".to_string())
+        ]
+    );
+    assert_eq!(query_swap(&deps, swap_id), None);
+}
+
+#[test]
+fn test_expired_swap() {
+    let mut deps = setup_contract();
+    let env = mock_env(SWAP_ADDR);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(MAKER, &[]),
+        ExecuteMsg::CreateSwap(CreateSwapMsg {
+            taker: None,
+            maker_assets: vec![nft_asset("1")],
+            taker_assets: vec![nft_asset("2")],
+            expiration: Some(env.block.time.seconds() + 10),
+            charge_royalty: false,
+        }),
+    )
+    .unwrap();
+    assert_eq!(res.attributes[2].value, "1");
+
+    let mut later = env.clone();
+    later.block.time = later.block.time.plus_seconds(10);
+    let err = execute(
+        deps.as_mut(),
+        later,
+        mock_info(NFT_ADDR, &[]),
+        ExecuteMsg::ReceiveNft(Cw721ReceiveMsg {
+            sender: Addr::unchecked(MAKER),
+            token_id: "1".to_string(),
+            msg: Some(to_json_binary(&SwapHookMsg::Deposit { swap_id: 1 }).unwrap()),
+        }),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::SwapExpired {});
+
+    // swaps are still listed by maker until cancelled
+    let swaps: Vec<Swap> = from_json(
+        query(
+            deps.as_ref(),
+            env,
+            QueryMsg::GetSwapsByMaker {
+                maker: Addr::unchecked(MAKER),
+                offset: None,
+                limit: None,
+                order: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(swaps.len(), 1);
+}
@@ -4,25 +4,32 @@ use cosmwasm_std::entry_point;
 use crate::error::ContractError;
 use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, UpdateContractMsg};
 use crate::state::{
-    get_contract_token_id, get_key_royalty, increment_offerings, offerings, offerings_royalty,
-    ContractInfo, CONTRACT_INFO,
+    get_contract_token_id, get_key_royalty, get_price_id, increment_offerings, offerings,
+    offerings_royalty, ContractInfo, CONTRACT_INFO, REINDEX_CURSOR,
 };
 use market_royalty::{OfferingExecuteMsg, OfferingRoyalty, OfferingRoyaltyResponse, OffsetMsg};
 use market_royalty::{OfferingQueryMsg, OfferingsResponse, QueryOfferingsResult};
 
 use cosmwasm_std::Addr;
 use cosmwasm_std::{
-    attr, to_json_binary, Api, Binary, Deps, DepsMut, Env, MessageInfo, Order, Record, Response,
-    StdError, StdResult,
+    attr, to_json_binary, Api, Binary, CanonicalAddr, Deps, DepsMut, Env, MessageInfo, Order,
+    Record, Response, StdError, StdResult, Storage, Uint128,
 };
+use cw2::set_contract_version;
 use cw_storage_plus::{Bound, PkOwned};
+use market::{
+    count_total, AssetInfo, MarketHubContract, PageResponse, Pagination, DEFAULT_LIMIT, MAX_LIMIT,
+    PAYMENT_721_STORAGE,
+};
+use market_payment::PaymentStorage;
 use market_royalty::Offering;
+use migration::{migrate_version, MigrationStep};
 use std::convert::TryInto;
 use std::usize;
 
-// settings for pagination
-const MAX_LIMIT: u8 = 100;
-const DEFAULT_LIMIT: u8 = 20;
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:market_offering_storage";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 // Note, you can use StdResult in some functions where you do not
 // make use of the custom errors
@@ -33,6 +40,7 @@ pub fn instantiate(
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     // first time deploy, it will not know about the implementation
     let info = ContractInfo {
         governance: msg.governance,
@@ -63,6 +71,7 @@ pub fn execute(
               // }
         },
         ExecuteMsg::UpdateInfo(msg) => try_update_info(deps, info, env, msg),
+        ExecuteMsg::ReindexOfferings { limit } => try_reindex_offerings(deps, info, limit),
     }
 }
 
@@ -71,25 +80,50 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Offering(auction_query) => match auction_query {
             OfferingQueryMsg::GetOfferings {
+                start_after,
+                limit,
+                offset,
+                order,
+                with_total,
+            } => to_json_binary(&query_offerings(
+                deps,
+                start_after,
                 limit,
                 offset,
                 order,
-            } => to_json_binary(&query_offerings(deps, limit, offset, order)?),
+                with_total,
+            )?),
             OfferingQueryMsg::GetOfferingsBySeller {
                 seller,
+                start_after,
                 limit,
                 offset,
                 order,
+                with_total,
             } => to_json_binary(&query_offerings_by_seller(
-                deps, seller, limit, offset, order,
+                deps,
+                seller,
+                start_after,
+                limit,
+                offset,
+                order,
+                with_total,
             )?),
             OfferingQueryMsg::GetOfferingsByContract {
                 contract,
+                start_after,
                 limit,
                 offset,
                 order,
+                with_total,
             } => to_json_binary(&query_offerings_by_contract(
-                deps, contract, limit, offset, order,
+                deps,
+                contract,
+                start_after,
+                limit,
+                offset,
+                order,
+                with_total,
             )?),
             OfferingQueryMsg::GetOffering { offering_id } => {
                 to_json_binary(&query_offering(deps, offering_id)?)
@@ -102,39 +136,91 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
                     deps, contract, token_id,
                 )?)
             }
+            OfferingQueryMsg::GetFloorOffering {
+                contract,
+                asset_info,
+            } => to_json_binary(&query_floor_offering(deps, contract, asset_info)?),
+            OfferingQueryMsg::GetOfferingsByPriceRange {
+                contract,
+                min_price,
+                max_price,
+                asset_info,
+                start_after,
+                limit,
+                order,
+                with_total,
+            } => to_json_binary(&query_offerings_by_price_range(
+                deps,
+                contract,
+                min_price,
+                max_price,
+                asset_info,
+                start_after,
+                limit,
+                order,
+                with_total,
+            )?),
             OfferingQueryMsg::GetOfferingsRoyalty {
+                start_after,
                 limit,
                 offset,
                 order,
-            } => to_json_binary(&query_offerings_royalty(deps, limit, offset, order)?),
+                with_total,
+            } => to_json_binary(&query_offerings_royalty(
+                deps,
+                start_after,
+                limit,
+                offset,
+                order,
+                with_total,
+            )?),
 
             OfferingQueryMsg::GetOfferingsRoyaltyWithKeys {
+                start_after,
                 limit,
                 offset,
                 order,
+                with_total,
             } => to_json_binary(&query_offerings_royalty_with_keys(
-                deps, limit, offset, order,
+                deps,
+                start_after,
+                limit,
+                offset,
+                order,
+                with_total,
             )?),
 
             OfferingQueryMsg::GetOfferingsRoyaltyByContract {
                 contract,
+                start_after,
                 limit,
                 offset,
                 order,
+                with_total,
             } => to_json_binary(&query_offerings_royalty_by_contract(
-                deps, contract, limit, offset, order,
+                deps,
+                contract,
+                start_after,
+                limit,
+                offset,
+                order,
+                with_total,
             )?),
             OfferingQueryMsg::GetOfferingsRoyaltyByCurrentOwner {
                 current_owner,
+                start_after,
                 limit,
                 offset,
                 order,
+                with_total,
             } => to_json_binary(&query_offerings_royalty_by_current_owner(
                 deps,
                 current_owner,
+                start_after,
                 limit,
                 offset,
                 order,
+                with_total,
             )?),
             OfferingQueryMsg::GetOfferingRoyalty { offering_id } => {
                 to_json_binary(&query_offering_royalty(deps, offering_id)?)
@@ -284,77 +370,62 @@ pub fn try_update_info(
 
 // ============================== Query Handlers ==============================
 
-fn _get_range_params(
-    limit: Option<u8>,
+fn offering_pagination(
+    start_after: Option<Binary>,
+    limit: Option<u32>,
     offset: Option<u64>,
     order: Option<u8>,
-) -> (usize, Option<Bound>, Option<Bound>, Order) {
-    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
-    let mut min = None;
-    let mut max = None;
-    let mut order_enum = Order::Descending;
-    if let Some(num) = order {
-        if num == 1 {
-            order_enum = Order::Ascending;
-        }
-    }
-
-    // if there is offset, assign to min or max
-    if let Some(offset) = offset {
-        let offset_value = Some(Bound::Exclusive(offset.to_be_bytes().to_vec()));
-        match order_enum {
-            Order::Ascending => min = offset_value,
-            Order::Descending => max = offset_value,
-        }
-    };
-    (limit, min, max, order_enum)
+) -> Pagination {
+    Pagination::new(
+        start_after,
+        offset.map(|id| id.to_be_bytes().to_vec()),
+        limit,
+        order,
+        Order::Descending,
+    )
 }
 
-fn _get_range_params_offering_royalty(
-    limit: Option<u8>,
+fn offering_royalty_pagination(
+    start_after: Option<Binary>,
+    limit: Option<u32>,
     offset: Option<OffsetMsg>,
     order: Option<u8>,
-) -> (usize, Option<Bound>, Option<Bound>, Order) {
-    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
-    let mut min = None;
-    let max = None;
-    let mut order_enum = Order::Ascending;
-    if let Some(num) = order {
-        if num == 2 {
-            order_enum = Order::Descending;
-        }
-    }
-
-    // if there is offset, assign to min or max
-    if let Some(offset) = offset {
-        let offset_value = Some(Bound::Exclusive(get_key_royalty(
-            offset.contract.as_bytes(),
-            offset.token_id.as_bytes(),
-        )));
-        // match order_enum {
-        //     Order::Ascending => min = offset_value,
-        //     Order::Descending => max = offset_value,
-        // }
-        min = offset_value;
-    };
-    (limit, min, max, order_enum)
+) -> Pagination {
+    Pagination::new(
+        start_after,
+        offset
+            .map(|offset| get_key_royalty(offset.contract.as_bytes(), offset.token_id.as_bytes())),
+        limit,
+        order,
+        Order::Ascending,
+    )
 }
 
 pub fn query_offerings(
     deps: Deps,
-    limit: Option<u8>,
+    start_after: Option<Binary>,
+    limit: Option<u32>,
     offset: Option<u64>,
     order: Option<u8>,
+    with_total: Option<bool>,
 ) -> StdResult<OfferingsResponse> {
-    let (limit, min, max, order_enum) = _get_range_params(limit, offset, order);
-
-    let res: StdResult<Vec<QueryOfferingsResult>> = offerings()
-        .range(deps.storage, min, max, order_enum)
-        .take(limit)
-        .map(|kv_item| parse_offering(deps.api, kv_item))
-        .collect();
+    let pagination = offering_pagination(start_after, limit, offset, order);
+    let (min, max, order_enum) = pagination.bounds();
+    let total = count_total(with_total, || {
+        offerings()
+            .range(deps.storage, None, None, Order::Ascending)
+            .count()
+    });
+    let (offerings, next_cursor) = pagination.page(
+        offerings().range(deps.storage, min, max, order_enum),
+        |kv_item| parse_offering(deps.api, kv_item),
+    )?;
 
-    Ok(OfferingsResponse { offerings: res? })
+    Ok(OfferingsResponse {
+        offerings,
+        next_cursor,
+        total,
+    })
 }
 
 pub fn query_offering_ids(deps: Deps) -> StdResult<Vec<u64>> {
@@ -369,41 +440,179 @@ pub fn query_offering_ids(deps: Deps) -> StdResult<Vec<u64>> {
 pub fn query_offerings_by_seller(
     deps: Deps,
     seller: Addr,
-    limit: Option<u8>,
+    start_after: Option<Binary>,
+    limit: Option<u32>,
     offset: Option<u64>,
     order: Option<u8>,
+    with_total: Option<bool>,
 ) -> StdResult<OfferingsResponse> {
-    let (limit, min, max, order_enum) = _get_range_params(limit, offset, order);
+    let pagination = offering_pagination(start_after, limit, offset, order);
+    let (min, max, order_enum) = pagination.bounds();
     let seller_raw = deps.api.addr_canonicalize(seller.as_str())?;
-    let res: StdResult<Vec<QueryOfferingsResult>> = offerings()
-        .idx
-        .seller
-        .items(deps.storage, &seller_raw, min, max, order_enum)
-        .take(limit)
-        .map(|kv_item| parse_offering(deps.api, kv_item))
-        .collect();
+    let total = count_total(with_total, || {
+        offerings()
+            .idx
+            .seller
+            .pks(deps.storage, &seller_raw, None, None, Order::Ascending)
+            .count()
+    });
+    let (offerings, next_cursor) = pagination.page(
+        offerings()
+            .idx
+            .seller
+            .items(deps.storage, &seller_raw, min, max, order_enum),
+        |kv_item| parse_offering(deps.api, kv_item),
+    )?;
 
-    Ok(OfferingsResponse { offerings: res? })
+    Ok(OfferingsResponse {
+        offerings,
+        next_cursor,
+        total,
+    })
 }
 
 pub fn query_offerings_by_contract(
     deps: Deps,
     contract: Addr,
-    limit: Option<u8>,
+    start_after: Option<Binary>,
+    limit: Option<u32>,
     offset: Option<u64>,
     order: Option<u8>,
+    with_total: Option<bool>,
 ) -> StdResult<OfferingsResponse> {
-    let (limit, min, max, order_enum) = _get_range_params(limit, offset, order);
+    let pagination = offering_pagination(start_after, limit, offset, order);
+    let (min, max, order_enum) = pagination.bounds();
     let contract_raw = deps.api.addr_canonicalize(contract.as_str())?;
-    let res: StdResult<Vec<QueryOfferingsResult>> = offerings()
-        .idx
-        .contract
-        .items(deps.storage, &contract_raw, min, max, order_enum)
-        .take(limit)
-        .map(|kv_item| parse_offering(deps.api, kv_item))
-        .collect();
+    let total = count_total(with_total, || {
+        offerings()
+            .idx
+            .contract
+            .pks(deps.storage, &contract_raw, None, None, Order::Ascending)
+            .count()
+    });
+    let (offerings, next_cursor) = pagination.page(
+        offerings()
+            .idx
+            .contract
+            .items(deps.storage, &contract_raw, min, max, order_enum),
+        |kv_item| parse_offering(deps.api, kv_item),
+    )?;
+
+    Ok(OfferingsResponse {
+        offerings,
+        next_cursor,
+        total,
+    })
+}
 
-    Ok(OfferingsResponse { offerings: res? })
+// both price bounds are inclusive, the max bound covers every offering id at the max price
+fn price_bounds(
+    min_price: Option<Uint128>,
+    max_price: Option<Uint128>,
+) -> (Option<Bound>, Option<Bound>) {
+    (
+        min_price.map(|price| Bound::Inclusive(price.u128().to_be_bytes().to_vec())),
+        max_price.map(|price| Bound::Inclusive(get_price_id(price, u64::MAX).0)),
+    )
+}
+
+// offerings of the contract ordered by price, keyed by the price index key so it can be used as
+// cursor. an asset selects its own index, so the other assets are never read
+fn offerings_by_price<'c>(
+    storage: &'c dyn Storage,
+    contract_raw: &CanonicalAddr,
+    min: Option<Bound>,
+    max: Option<Bound>,
+    order: Order,
+    asset_info: Option<AssetInfo>,
+) -> impl Iterator<Item = StdResult<Record<Record<Offering>>>> + 'c {
+    let contract = PkOwned(contract_raw.to_vec());
+    let records = match asset_info {
+        Some(asset_info) => offerings()
+            .idx
+            .contract_asset_price
+            .prefix((contract, PkOwned(asset_info.as_key())))
+            .range(storage, min, max, order),
+        None => offerings()
+            .idx
+            .contract_price
+            .prefix(contract)
+            .range(storage, min, max, order),
+    };
+    records.map(|item| {
+        item.map(|(k, offering)| {
+            let key = get_price_id(offering.price, offering.id.unwrap_or_default());
+            (key.0, (k, offering))
+        })
+    })
+}
+
+pub fn query_floor_offering(
+    deps: Deps,
+    contract: Addr,
+    asset_info: Option<AssetInfo>,
+) -> StdResult<Option<QueryOfferingsResult>> {
+    let contract_raw = deps.api.addr_canonicalize(contract.as_str())?;
+    offerings_by_price(
+        deps.storage,
+        &contract_raw,
+        None,
+        None,
+        Order::Ascending,
+        asset_info,
+    )
+    .next()
+    .map(|item| item.and_then(|(_, kv_item)| parse_offering(deps.api, kv_item)))
+    .transpose()
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn query_offerings_by_price_range(
+    deps: Deps,
+    contract: Addr,
+    min_price: Option<Uint128>,
+    max_price: Option<Uint128>,
+    asset_info: Option<AssetInfo>,
+    start_after: Option<Binary>,
+    limit: Option<u32>,
+    order: Option<u8>,
+    with_total: Option<bool>,
+) -> StdResult<OfferingsResponse> {
+    let mut pagination = Pagination::new(start_after, None, limit, order, Order::Ascending);
+    let contract_raw = deps.api.addr_canonicalize(contract.as_str())?;
+    let (min_bound, max_bound) = price_bounds(min_price, max_price);
+    let total = count_total(with_total, || {
+        offerings_by_price(
+            deps.storage,
+            &contract_raw,
+            min_bound.clone(),
+            max_bound.clone(),
+            Order::Ascending,
+            asset_info.clone(),
+        )
+        .count()
+    });
+    // the cursor replaces the price bound on the side the page starts from
+    pagination.min = pagination.min.or(min_bound);
+    pagination.max = pagination.max.or(max_bound);
+    let (min, max, order_enum) = pagination.bounds();
+    let (offerings, next_cursor) = pagination.page(
+        offerings_by_price(
+            deps.storage,
+            &contract_raw,
+            min,
+            max,
+            order_enum,
+            asset_info,
+        ),
+        |(_, kv_item)| parse_offering(deps.api, kv_item),
+    )?;
+
+    Ok(OfferingsResponse {
+        offerings,
+        next_cursor,
+        total,
+    })
 }
 
 pub fn query_offering(deps: Deps, offering_id: u64) -> StdResult<QueryOfferingsResult> {
@@ -414,6 +623,8 @@ pub fn query_offering(deps: Deps, offering_id: u64) -> StdResult<QueryOfferingsR
         price: offering.price,
         contract_addr: deps.api.addr_humanize(&offering.contract_addr)?,
         seller: deps.api.addr_humanize(&offering.seller)?,
+        reserved_for: offering.reserved_for,
+        asset_info: offering.asset_info,
     })
 }
 
@@ -440,6 +651,8 @@ pub fn query_offering_by_contract_tokenid(
             price: offering_result.price,
             contract_addr: deps.api.addr_humanize(&offering_result.contract_addr)?,
             seller: deps.api.addr_humanize(&offering_result.seller)?,
+            reserved_for: offering_result.reserved_for,
+            asset_info: offering_result.asset_info,
         };
         Ok(offering_resposne)
     } else {
@@ -449,74 +662,124 @@ pub fn query_offering_by_contract_tokenid(
 
 pub fn query_offerings_royalty(
     deps: Deps,
-    limit: Option<u8>,
+    start_after: Option<Binary>,
+    limit: Option<u32>,
     offset: Option<OffsetMsg>,
     order: Option<u8>,
-) -> StdResult<Vec<OfferingRoyalty>> {
-    let (limit, min, max, order_enum) = _get_range_params_offering_royalty(limit, offset, order);
-
-    let res: StdResult<Vec<OfferingRoyalty>> = offerings_royalty()
-        .range(deps.storage, min, max, order_enum)
-        .take(limit)
-        .map(|kv_item| parse_offering_royalty(kv_item))
-        .collect();
-
-    Ok(res?)
+    with_total: Option<bool>,
+) -> StdResult<PageResponse<OfferingRoyalty>> {
+    let pagination = offering_royalty_pagination(start_after, limit, offset, order);
+    let (min, max, order_enum) = pagination.bounds();
+    let total = count_total(with_total, || {
+        offerings_royalty()
+            .range(deps.storage, None, None, Order::Ascending)
+            .count()
+    });
+
+    pagination.page_response(
+        offerings_royalty().range(deps.storage, min, max, order_enum),
+        parse_offering_royalty,
+        total,
+    )
 }
 
 pub fn query_offerings_royalty_with_keys(
     deps: Deps,
-    limit: Option<u8>,
+    start_after: Option<Binary>,
+    limit: Option<u32>,
     offset: Option<OffsetMsg>,
     order: Option<u8>,
-) -> StdResult<Vec<OfferingRoyaltyResponse>> {
-    let (limit, min, max, order_enum) = _get_range_params_offering_royalty(limit, offset, order);
-
-    let res: StdResult<Vec<OfferingRoyaltyResponse>> = offerings_royalty()
-        .range(deps.storage, min, max, order_enum)
-        .take(limit)
-        .map(|kv_item| parse_offering_royalty_response(kv_item))
-        .collect();
-
-    Ok(res?)
+    with_total: Option<bool>,
+) -> StdResult<PageResponse<OfferingRoyaltyResponse>> {
+    let pagination = offering_royalty_pagination(start_after, limit, offset, order);
+    let (min, max, order_enum) = pagination.bounds();
+    let total = count_total(with_total, || {
+        offerings_royalty()
+            .range(deps.storage, None, None, Order::Ascending)
+            .count()
+    });
+
+    pagination.page_response(
+        offerings_royalty().range(deps.storage, min, max, order_enum),
+        parse_offering_royalty_response,
+        total,
+    )
 }
 
 pub fn query_offerings_royalty_by_current_owner(
     deps: Deps,
     current_owner: Addr,
-    limit: Option<u8>,
+    start_after: Option<Binary>,
+    limit: Option<u32>,
     offset: Option<OffsetMsg>,
     order: Option<u8>,
-) -> StdResult<Vec<OfferingRoyalty>> {
-    let (limit, min, max, order_enum) = _get_range_params_offering_royalty(limit, offset, order);
-    let res: StdResult<Vec<OfferingRoyalty>> = offerings_royalty()
-        .idx
-        .current_owner
-        .items(deps.storage, current_owner.as_bytes(), min, max, order_enum)
-        .take(limit)
-        .map(|kv_item| parse_offering_royalty(kv_item))
-        .collect();
-
-    Ok(res?)
+    with_total: Option<bool>,
+) -> StdResult<PageResponse<OfferingRoyalty>> {
+    let pagination = offering_royalty_pagination(start_after, limit, offset, order);
+    let (min, max, order_enum) = pagination.bounds();
+    let total = count_total(with_total, || {
+        offerings_royalty()
+            .idx
+            .current_owner
+            .pks(
+                deps.storage,
+                current_owner.as_bytes(),
+                None,
+                None,
+                Order::Ascending,
+            )
+            .count()
+    });
+
+    pagination.page_response(
+        offerings_royalty().idx.current_owner.items(
+            deps.storage,
+            current_owner.as_bytes(),
+            min,
+            max,
+            order_enum,
+        ),
+        parse_offering_royalty,
+        total,
+    )
 }
 
 pub fn query_offerings_royalty_by_contract(
     deps: Deps,
     contract: Addr,
-    limit: Option<u8>,
+    start_after: Option<Binary>,
+    limit: Option<u32>,
     offset: Option<OffsetMsg>,
     order: Option<u8>,
-) -> StdResult<Vec<OfferingRoyalty>> {
-    let (limit, min, max, order_enum) = _get_range_params_offering_royalty(limit, offset, order);
-    let res: StdResult<Vec<OfferingRoyalty>> = offerings_royalty()
-        .idx
-        .contract
-        .items(deps.storage, contract.as_bytes(), min, max, order_enum)
-        .take(limit)
-        .map(|kv_item| parse_offering_royalty(kv_item))
-        .collect();
-
-    Ok(res?)
+    with_total: Option<bool>,
+) -> StdResult<PageResponse<OfferingRoyalty>> {
+    let pagination = offering_royalty_pagination(start_after, limit, offset, order);
+    let (min, max, order_enum) = pagination.bounds();
+    let total = count_total(with_total, || {
+        offerings_royalty()
+            .idx
+            .contract
+            .pks(
+                deps.storage,
+                contract.as_bytes(),
+                None,
+                None,
+                Order::Ascending,
+            )
+            .count()
+    });
+
+    pagination.page_response(
+        offerings_royalty().idx.contract.items(
+            deps.storage,
+            contract.as_bytes(),
+            min,
+            max,
+            order_enum,
+        ),
+        parse_offering_royalty,
+        total,
+    )
 }
 
 pub fn query_offering_royalty(deps: Deps, offering_id: Binary) -> StdResult<OfferingRoyalty> {
@@ -544,53 +807,119 @@ pub fn query_contract_info(deps: Deps) -> StdResult<ContractInfo> {
     CONTRACT_INFO.load(deps.storage)
 }
 
-fn parse_offering<'a>(
+fn parse_offering(
     api: &dyn Api,
-    item: StdResult<Record<Offering>>,
+    (k, offering): Record<Offering>,
 ) -> StdResult<QueryOfferingsResult> {
-    item.and_then(|(k, offering)| {
-        // will panic if length is greater than 8, but we can make sure it is u64
-        // try_into will box vector to fixed array
-        let id: u64 = u64::from_be_bytes(k.try_into().unwrap());
-        Ok(QueryOfferingsResult {
-            id,
-            token_id: offering.token_id,
-            price: offering.price,
-            contract_addr: api.addr_humanize(&offering.contract_addr)?,
-            seller: api.addr_humanize(&offering.seller)?,
-        })
+    // will panic if length is greater than 8, but we can make sure it is u64
+    // try_into will box vector to fixed array
+    let id: u64 = u64::from_be_bytes(k.try_into().unwrap());
+    Ok(QueryOfferingsResult {
+        id,
+        token_id: offering.token_id,
+        price: offering.price,
+        contract_addr: api.addr_humanize(&offering.contract_addr)?,
+        seller: api.addr_humanize(&offering.seller)?,
+        reserved_for: offering.reserved_for,
+        asset_info: offering.asset_info,
     })
 }
 
-fn parse_offering_royalty<'a>(
-    item: StdResult<Record<OfferingRoyalty>>,
-) -> StdResult<OfferingRoyalty> {
-    item.and_then(|(_, offering)| {
-        // will panic if length is greater than 8, but we can make sure it is u64
-        // try_into will box vector to fixed array
-        Ok(offering)
-    })
+fn parse_offering_royalty((_, offering): Record<OfferingRoyalty>) -> StdResult<OfferingRoyalty> {
+    Ok(offering)
 }
 
-fn parse_offering_royalty_response<'a>(
-    item: StdResult<Record<OfferingRoyalty>>,
+fn parse_offering_royalty_response(
+    (k, offering): Record<OfferingRoyalty>,
 ) -> StdResult<OfferingRoyaltyResponse> {
-    item.and_then(|(k, offering)| {
-        // will panic if length is greater than 8, but we can make sure it is u64
-        // try_into will box vector to fixed array
-        Ok(OfferingRoyaltyResponse {
-            offering_id: to_json_binary(&k)?,
-            token_id: offering.token_id,
-            contract_addr: offering.contract_addr,
-            previous_owner: offering.previous_owner,
-            current_owner: offering.current_owner,
-            prev_royalty: offering.prev_royalty,
-            cur_royalty: offering.cur_royalty,
-        })
+    Ok(OfferingRoyaltyResponse {
+        offering_id: to_json_binary(&k)?,
+        token_id: offering.token_id,
+        contract_addr: offering.contract_addr,
+        previous_owner: offering.previous_owner,
+        current_owner: offering.current_owner,
+        prev_royalty: offering.prev_royalty,
+        cur_royalty: offering.cur_royalty,
     })
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
-    Ok(Response::default())
+pub fn migrate(deps: DepsMut, env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    Ok(migrate_version(
+        deps,
+        &env,
+        CONTRACT_NAME,
+        CONTRACT_VERSION,
+        MIGRATIONS,
+    )?)
+}
+
+const MIGRATIONS: &[MigrationStep] = &[MigrationStep {
+    version: "0.2.0",
+    name: "reindex_offerings",
+    run: reindex_offerings,
+}];
+
+fn reindex_offerings(deps: DepsMut, _env: &Env) -> StdResult<()> {
+    // re-saving every offering does not fit in one transaction, the creator runs it in batches
+    // with ReindexOfferings. Until it is done the price queries miss the older offerings
+    REINDEX_CURSOR.save(deps.storage, &vec![])
+}
+
+/// saving the offerings again fills the price indexes for offerings created before them. The
+/// offerings without a recorded asset get the one of their payment, the default denom if none
+pub fn try_reindex_offerings(
+    deps: DepsMut,
+    info: MessageInfo,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let ContractInfo {
+        governance,
+        creator,
+    } = CONTRACT_INFO.load(deps.storage)?;
+    if info.sender.ne(&creator) {
+        return Err(ContractError::Unauthorized {
+            sender: info.sender.to_string(),
+        });
+    }
+    let cursor = match REINDEX_CURSOR.may_load(deps.storage)? {
+        Some(cursor) => cursor,
+        None => {
+            return Ok(Response::new().add_attributes(vec![
+                attr("action", "reindex_offerings"),
+                attr("done", "true"),
+            ]))
+        }
+    };
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let min = (!cursor.is_empty()).then_some(Bound::Exclusive(cursor));
+    let batch = offerings()
+        .range(deps.storage, min, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let governance = MarketHubContract(governance);
+    for (k, mut offering) in batch.iter().cloned() {
+        if offering.asset_info.is_none() {
+            offering.asset_info = Some(governance.get_offering_payment(
+                &deps.querier,
+                PAYMENT_721_STORAGE,
+                deps.api.addr_humanize(&offering.contract_addr)?,
+                &offering.token_id,
+                None,
+            )?);
+        }
+        offerings().save(deps.storage, &k, &offering)?;
+    }
+    let done = batch.len() < limit;
+    match batch.last() {
+        Some((k, _)) if !done => REINDEX_CURSOR.save(deps.storage, k)?,
+        _ => REINDEX_CURSOR.remove(deps.storage),
+    }
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "reindex_offerings"),
+        attr("reindexed", batch.len().to_string()),
+        attr("done", done.to_string()),
+    ]))
 }
@@ -18,6 +18,8 @@ pub enum ExecuteMsg {
     Offering(OfferingExecuteMsg),
     // other implementation
     UpdateInfo(UpdateContractMsg),
+    // re-save the next offerings of the reindex queued by the migration, creator only
+    ReindexOfferings { limit: Option<u32> },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
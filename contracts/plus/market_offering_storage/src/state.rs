@@ -1,9 +1,10 @@
+use market::AssetInfo;
 use market_royalty::{Offering, OfferingRoyalty};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 
-use cosmwasm_std::{Addr, CanonicalAddr, StdResult, Storage};
+use cosmwasm_std::{Addr, CanonicalAddr, StdResult, Storage, Uint128};
 use cw_storage_plus::{Index, IndexList, IndexedMap, Item, MultiIndex, PkOwned, UniqueIndex};
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
@@ -15,6 +16,8 @@ pub struct ContractInfo {
 /// OFFERINGS is a map which maps the offering_id to an offering. Offering_id is derived from OFFERINGS_COUNT.
 pub const OFFERINGS_COUNT: Item<u64> = Item::new("num_offerings");
 pub const CONTRACT_INFO: Item<ContractInfo> = Item::new("marketplace_info");
+/// last offering key re-saved by the running reindex, empty when it has not started yet
+pub const REINDEX_CURSOR: Item<Vec<u8>> = Item::new("reindex_cursor");
 
 pub fn num_offerings(storage: &dyn Storage) -> StdResult<u64> {
     Ok(OFFERINGS_COUNT.may_load(storage)?.unwrap_or_default())
@@ -30,12 +33,21 @@ pub struct OfferingIndexes<'a> {
     pub seller: MultiIndex<'a, Offering>,
     pub contract: MultiIndex<'a, Offering>,
     pub contract_token_id: UniqueIndex<'a, PkOwned, Offering>,
+    // contract => price + id, so the offerings of a contract can be ranged by price
+    pub contract_price: UniqueIndex<'a, (PkOwned, PkOwned), Offering>,
+    // contract + asset => price + id, so the offerings paid in an asset are ranged without a scan
+    pub contract_asset_price: UniqueIndex<'a, (PkOwned, PkOwned, PkOwned), Offering>,
 }
 
 impl<'a> IndexList<Offering> for OfferingIndexes<'a> {
     fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Offering>> + '_> {
-        let v: Vec<&dyn Index<Offering>> =
-            vec![&self.seller, &self.contract, &self.contract_token_id];
+        let v: Vec<&dyn Index<Offering>> = vec![
+            &self.seller,
+            &self.contract,
+            &self.contract_token_id,
+            &self.contract_price,
+            &self.contract_asset_price,
+        ];
         Box::new(v.into_iter())
     }
 }
@@ -47,6 +59,13 @@ pub fn get_contract_token_id(contract: &CanonicalAddr, token_id: &str) -> PkOwne
     PkOwned(vec)
 }
 
+// price + offering id => unique id, big endian so the keys sort by price
+pub fn get_price_id(price: Uint128, id: u64) -> PkOwned {
+    let mut vec = price.u128().to_be_bytes().to_vec();
+    vec.extend(id.to_be_bytes());
+    PkOwned(vec)
+}
+
 // this IndexedMap instance has a lifetime
 pub fn offerings<'a>() -> IndexedMap<'a, &'a [u8], Offering, OfferingIndexes<'a>> {
     let indexes = OfferingIndexes {
@@ -60,6 +79,31 @@ pub fn offerings<'a>() -> IndexedMap<'a, &'a [u8], Offering, OfferingIndexes<'a>
             |o| get_contract_token_id(&o.contract_addr, &o.token_id),
             "request__id",
         ),
+        // the offering id is always set before the offering is saved
+        contract_price: UniqueIndex::new(
+            |o| {
+                (
+                    PkOwned(o.contract_addr.to_vec()),
+                    get_price_id(o.price, o.id.unwrap_or_default()),
+                )
+            },
+            "offerings__contract_price",
+        ),
+        contract_asset_price: UniqueIndex::new(
+            |o| {
+                (
+                    PkOwned(o.contract_addr.to_vec()),
+                    PkOwned(
+                        o.asset_info
+                            .as_ref()
+                            .map(AssetInfo::as_key)
+                            .unwrap_or_default(),
+                    ),
+                    get_price_id(o.price, o.id.unwrap_or_default()),
+                )
+            },
+            "offerings__contract_asset_price",
+        ),
     };
     IndexedMap::new("offerings", indexes)
 }
@@ -1,20 +1,28 @@
 use std::ops::Mul;
 
 use crate::contract::*;
+use crate::error::ContractError;
 use crate::msg::*;
 use crate::state::ContractInfo;
 use cosmwasm_std::testing::{
     mock_dependencies_with_balance, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
 };
 use cosmwasm_std::Decimal;
-use cosmwasm_std::{coin, coins, from_json, Addr, Order, OwnedDeps, Uint128};
+use cosmwasm_std::{
+    attr, coin, coins, from_json, to_json_binary, Addr, ContractResult, Order, OwnedDeps,
+    SystemResult, Uint128, WasmQuery,
+};
+use cw2::set_contract_version;
+use cw_storage_plus::Map;
 
+use market::{AssetInfo, PageResponse};
 use market_royalty::Offering;
 use market_royalty::OfferingExecuteMsg;
 use market_royalty::OfferingQueryMsg;
 use market_royalty::OfferingRoyalty;
 use market_royalty::OfferingsResponse;
 use market_royalty::OffsetMsg;
+use market_royalty::QueryOfferingsResult;
 
 const CREATOR: &str = "marketplace";
 const DENOM: &str = "MGK";
@@ -58,6 +66,8 @@ fn sort_offering() {
             token_id: i.to_string(),
             seller: deps.as_ref().api.addr_canonicalize("seller").unwrap(),
             price: Uint128::from(1u64),
+            reserved_for: None,
+            asset_info: None,
         };
         offerings.push(offering);
     }
@@ -76,6 +86,8 @@ fn sort_offering() {
             limit: Some(100),
             offset: Some(50),
             order: Some(Order::Descending as u8),
+            start_after: None,
+            with_total: None,
         }),
     )
     .unwrap();
@@ -90,6 +102,8 @@ fn sort_offering() {
             limit: Some(100),
             offset: Some(1),
             order: Some(Order::Ascending as u8),
+            start_after: None,
+            with_total: None,
         }),
     )
     .unwrap();
@@ -135,10 +149,12 @@ fn sort_offering_royalty() {
             limit: None,
             offset: None,
             order: Some(Order::Ascending as u8),
+            start_after: None,
+            with_total: None,
         }),
     )
     .unwrap();
-    let value: Vec<OfferingRoyalty> = from_json(&res).unwrap();
+    let value: PageResponse<OfferingRoyalty> = from_json(&res).unwrap();
     println!("value: {:?}", value);
 
     let res = query(
@@ -152,13 +168,15 @@ fn sort_offering_royalty() {
                 token_id: String::from("2"),
             }),
             order: None,
+            start_after: None,
+            with_total: None,
         }),
     )
     .unwrap();
-    let value: Vec<OfferingRoyalty> = from_json(&res).unwrap();
+    let value: PageResponse<OfferingRoyalty> = from_json(&res).unwrap();
     println!("offering royalties by contract: {:?}\n", value);
 
-    assert_eq!(value.len(), 2);
+    assert_eq!(value.items.len(), 2);
 
     let res = query(
         deps.as_ref(),
@@ -179,10 +197,12 @@ fn sort_offering_royalty() {
             limit: None,
             offset: None,
             order: Some(Order::Ascending as u8),
+            start_after: None,
+            with_total: None,
         }),
     )
     .unwrap();
-    let value: Vec<OfferingRoyalty> = from_json(&res).unwrap();
+    let value: PageResponse<OfferingRoyalty> = from_json(&res).unwrap();
     println!("offering royalties: {:?}", value);
 }
 
@@ -201,6 +221,8 @@ fn withdraw_offering() {
             token_id: i.to_string(),
             seller: deps.as_ref().api.addr_canonicalize("seller").unwrap(),
             price: Uint128::from(1u64),
+            reserved_for: None,
+            asset_info: None,
         };
         offerings.push(offering);
     }
@@ -221,6 +243,8 @@ fn withdraw_offering() {
             limit: Some(100),
             offset: Some(1),
             order: Some(Order::Ascending as u8),
+            start_after: None,
+            with_total: None,
         }),
     )
     .unwrap();
@@ -229,6 +253,250 @@ fn withdraw_offering() {
     assert_eq!(value.offerings.len(), 1);
 }
 
+#[test]
+fn paginate_offerings_by_seller() {
+    let mut deps = setup_contract();
+    let info = mock_info("market_hub", &[coin(50, DENOM)]);
+
+    // every other offering belongs to another seller so the index is not contiguous
+    for i in 1u64..10u64 {
+        let seller = if i % 2 == 0 { "seller" } else { "other" };
+        let offering = Offering {
+            id: Some(i),
+            contract_addr: deps.as_ref().api.addr_canonicalize("xxx").unwrap(),
+            token_id: i.to_string(),
+            seller: deps.as_ref().api.addr_canonicalize(seller).unwrap(),
+            price: Uint128::from(1u64),
+            reserved_for: None,
+            asset_info: None,
+        };
+        let msg = ExecuteMsg::Offering(OfferingExecuteMsg::UpdateOffering { offering });
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    }
+
+    let mut ids = vec![];
+    let mut start_after = None;
+    loop {
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Offering(OfferingQueryMsg::GetOfferingsBySeller {
+                seller: Addr::unchecked("seller"),
+                offset: None,
+                start_after,
+                limit: Some(3),
+                order: Some(Order::Ascending as u8),
+                with_total: Some(true),
+            }),
+        )
+        .unwrap();
+        let value: OfferingsResponse = from_json(&res).unwrap();
+        assert_eq!(value.total, Some(4));
+        ids.extend(value.offerings.iter().map(|o| o.id));
+        match value.next_cursor {
+            Some(cursor) => start_after = Some(cursor),
+            None => break,
+        }
+    }
+    assert_eq!(ids, vec![2, 4, 6, 8]);
+
+    // without with_total the index is not counted
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Offering(OfferingQueryMsg::GetOfferingsBySeller {
+            seller: Addr::unchecked("seller"),
+            offset: Some(6),
+            start_after: None,
+            limit: None,
+            order: None,
+            with_total: None,
+        }),
+    )
+    .unwrap();
+    let value: OfferingsResponse = from_json(&res).unwrap();
+    let ids: Vec<u64> = value.offerings.iter().map(|o| o.id).collect();
+    assert_eq!(ids, vec![4, 2]);
+    assert_eq!(value.next_cursor, None);
+    assert_eq!(value.total, None);
+}
+
+#[test]
+fn paginate_offerings_royalty_descending() {
+    let mut deps = setup_contract();
+    let info = mock_info("market_hub", &[coin(50, DENOM)]);
+
+    for i in 1u64..6u64 {
+        let offering = OfferingRoyalty {
+            contract_addr: Addr::unchecked("xxx"),
+            token_id: i.to_string(),
+            previous_owner: None,
+            prev_royalty: None,
+            current_owner: Addr::unchecked("seller"),
+            cur_royalty: Some(15u64),
+        };
+        let msg = ExecuteMsg::Offering(OfferingExecuteMsg::UpdateOfferingRoyalty { offering });
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    }
+
+    let query_page =
+        |offset: Option<OffsetMsg>, order: Option<u8>| -> PageResponse<OfferingRoyalty> {
+            let res = query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::Offering(OfferingQueryMsg::GetOfferingsRoyaltyByContract {
+                    contract: Addr::unchecked("xxx"),
+                    offset,
+                    start_after: None,
+                    limit: None,
+                    order,
+                    with_total: None,
+                }),
+            )
+            .unwrap();
+            from_json(&res).unwrap()
+        };
+
+    // the legacy offset bounds the descending range from above
+    let all = query_page(None, Some(Order::Descending as u8)).items;
+    assert_eq!(all.len(), 5);
+    let offset = OffsetMsg {
+        contract: all[1].contract_addr.clone(),
+        token_id: all[1].token_id.clone(),
+    };
+    let rest = query_page(Some(offset), Some(Order::Descending as u8)).items;
+    assert_eq!(rest, all[2..].to_vec());
+}
+
+#[test]
+fn query_offerings_by_price() {
+    let mut deps = setup_contract();
+    let info = mock_info("market_hub", &[coin(50, DENOM)]);
+    let orai = AssetInfo::NativeToken {
+        denom: String::from("orai"),
+    };
+    let airi = AssetInfo::Token {
+        contract_addr: Addr::unchecked("airi"),
+    };
+
+    // the last offering is listed on another contract, the fifth has no recorded asset
+    let listings = vec![
+        ("xxx", 50u64, Some(orai.clone())),
+        ("xxx", 10, Some(airi.clone())),
+        ("xxx", 30, Some(orai.clone())),
+        ("xxx", 10, Some(orai.clone())),
+        ("xxx", 70, None),
+        ("xxx", 20, Some(orai.clone())),
+        ("yyy", 1, Some(orai.clone())),
+    ];
+    for (i, (contract, price, asset_info)) in listings.into_iter().enumerate() {
+        let offering = Offering {
+            id: None,
+            contract_addr: deps.as_ref().api.addr_canonicalize(contract).unwrap(),
+            token_id: i.to_string(),
+            seller: deps.as_ref().api.addr_canonicalize("seller").unwrap(),
+            price: Uint128::from(price),
+            reserved_for: None,
+            asset_info,
+        };
+        let msg = ExecuteMsg::Offering(OfferingExecuteMsg::UpdateOffering { offering });
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    }
+
+    let floor = |asset_info: Option<AssetInfo>| -> Option<QueryOfferingsResult> {
+        from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::Offering(OfferingQueryMsg::GetFloorOffering {
+                    contract: Addr::unchecked("xxx"),
+                    asset_info,
+                }),
+            )
+            .unwrap(),
+        )
+        .unwrap()
+    };
+    // ties on price are broken by id
+    assert_eq!(floor(None).unwrap().id, 2);
+    assert_eq!(floor(Some(orai.clone())).unwrap().id, 4);
+    assert_eq!(floor(Some(airi)).unwrap().price, Uint128::from(10u64));
+    assert_eq!(
+        floor(Some(AssetInfo::NativeToken {
+            denom: String::from("atom"),
+        })),
+        None
+    );
+
+    // both bounds are inclusive and the pages follow the price index
+    let mut ids = vec![];
+    let mut start_after = None;
+    loop {
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Offering(OfferingQueryMsg::GetOfferingsByPriceRange {
+                contract: Addr::unchecked("xxx"),
+                min_price: Some(Uint128::from(10u64)),
+                max_price: Some(Uint128::from(50u64)),
+                asset_info: None,
+                start_after,
+                limit: Some(2),
+                order: None,
+                with_total: Some(true),
+            }),
+        )
+        .unwrap();
+        let value: OfferingsResponse = from_json(&res).unwrap();
+        assert_eq!(value.total, Some(5));
+        ids.extend(value.offerings.iter().map(|o| o.id));
+        match value.next_cursor {
+            Some(cursor) => start_after = Some(cursor),
+            None => break,
+        }
+    }
+    assert_eq!(ids, vec![2, 4, 6, 3, 1]);
+
+    // most expensive first, only the offerings paid in orai
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Offering(OfferingQueryMsg::GetOfferingsByPriceRange {
+            contract: Addr::unchecked("xxx"),
+            min_price: None,
+            max_price: Some(Uint128::from(30u64)),
+            asset_info: Some(orai.clone()),
+            start_after: None,
+            limit: None,
+            order: Some(Order::Descending as u8),
+            with_total: Some(true),
+        }),
+    )
+    .unwrap();
+    let value: OfferingsResponse = from_json(&res).unwrap();
+    let ids: Vec<u64> = value.offerings.iter().map(|o| o.id).collect();
+    assert_eq!(ids, vec![3, 6, 4]);
+    assert_eq!(value.total, Some(3));
+    assert_eq!(value.offerings[0].asset_info, Some(orai));
+
+    // a price update moves the offering in the index
+    let offering = query_offering_state(deps.as_ref(), 2).unwrap();
+    let msg = ExecuteMsg::Offering(OfferingExecuteMsg::UpdateOffering {
+        offering: Offering {
+            price: Uint128::from(100u64),
+            ..offering
+        },
+    });
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(
+        query_floor_offering(deps.as_ref(), Addr::unchecked("xxx"), None)
+            .unwrap()
+            .unwrap()
+            .id,
+        4
+    );
+}
+
 #[test]
 fn update_info_test() {
     let mut deps = setup_contract();
@@ -262,3 +530,84 @@ fn update_info_test() {
         from_json(&query(deps.as_ref(), mock_env(), query_info).unwrap()).unwrap();
     assert_eq!(res_info.governance.as_str(), Addr::unchecked("asvx"));
 }
+
+#[test]
+fn reindex_legacy_offerings() {
+    let mut deps = setup_contract();
+    let orai = AssetInfo::NativeToken {
+        denom: String::from("orai"),
+    };
+    // the hub resolves the payment storage, which answers the default denom for unknown offerings
+    deps.querier.update_wasm(|query| {
+        let value = match query {
+            WasmQuery::Smart { contract_addr, .. } if contract_addr == "market_hub" => {
+                to_json_binary(&Addr::unchecked("payment_storage"))
+            }
+            _ => to_json_binary(&AssetInfo::NativeToken {
+                denom: String::from("orai"),
+            }),
+        };
+        SystemResult::Ok(ContractResult::Ok(value.unwrap()))
+    });
+
+    // before 0.2.0 the offerings had no asset and no price index
+    for id in 1u64..4 {
+        let offering = Offering {
+            id: Some(id),
+            contract_addr: deps.as_ref().api.addr_canonicalize("xxx").unwrap(),
+            token_id: id.to_string(),
+            seller: deps.as_ref().api.addr_canonicalize("seller").unwrap(),
+            price: Uint128::from(10u64 * id),
+            reserved_for: None,
+            asset_info: None,
+        };
+        Map::<&[u8], Offering>::new("offerings")
+            .save(&mut deps.storage, &id.to_be_bytes(), &offering)
+            .unwrap();
+    }
+    set_contract_version(
+        &mut deps.storage,
+        "crates.io:market_offering_storage",
+        "0.1.0",
+    )
+    .unwrap();
+    migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+    let floor = |deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>| {
+        query_floor_offering(deps.as_ref(), Addr::unchecked("xxx"), Some(orai.clone())).unwrap()
+    };
+    assert_eq!(floor(&deps), None);
+
+    let reindex = |limit: u32| ExecuteMsg::ReindexOfferings { limit: Some(limit) };
+    assert!(matches!(
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("anyone", &[]),
+            reindex(2)
+        ),
+        Err(ContractError::Unauthorized { .. })
+    ));
+    let mut batches = vec![];
+    for _ in 0..3 {
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(CREATOR, &[]),
+            reindex(2),
+        )
+        .unwrap();
+        batches.push(res.attributes[1..].to_vec());
+    }
+    assert_eq!(
+        batches,
+        vec![
+            vec![attr("reindexed", "2"), attr("done", "false")],
+            vec![attr("reindexed", "1"), attr("done", "true")],
+            vec![attr("done", "true")],
+        ]
+    );
+
+    let floor = floor(&deps).unwrap();
+    assert_eq!(floor.id, 1);
+    assert_eq!(floor.asset_info, Some(orai));
+}
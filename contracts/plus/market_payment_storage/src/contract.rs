@@ -11,15 +11,21 @@ use cosmwasm_std::{
     attr, from_json, to_json_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Order, Record,
     Response, StdError, StdResult,
 };
+use cw2::set_contract_version;
 use cw_storage_plus::Bound;
 use market_payment::{
     AssetInfo, Payment, PaymentExecuteMsg, PaymentMsg, PaymentQueryMsg, PaymentResponse,
 };
+use migration::migrate_version;
 
 // settings for pagination
 const MAX_LIMIT: u8 = 100;
 const DEFAULT_LIMIT: u8 = 20;
 
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:market_payment_storage";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
 // Note, you can use StdResult in some functions where you do not
 // make use of the custom errors
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -29,6 +35,7 @@ pub fn instantiate(
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     // first time deploy, it will not know about the implementation
     let info = ContractInfo {
         governance: msg.governance,
@@ -71,8 +78,8 @@ pub fn execute(
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
-    Ok(Response::default())
+pub fn migrate(deps: DepsMut, env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+    migrate_version(deps, &env, CONTRACT_NAME, CONTRACT_VERSION, &[])
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -1,6 +1,6 @@
 use cosmwasm_schema::QueryResponses;
-use cosmwasm_std::{Addr, Binary};
-use market_payment::{PaymentExecuteMsg, PaymentQueryMsg};
+use cosmwasm_std::Addr;
+use market_payment::{PaymentExecuteMsg, PaymentQueryMsg, PaymentQueryResponse};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -33,7 +33,7 @@ pub struct UpdateContractMsg {
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
     // GetOfferings returns a list of all offerings
-    #[returns(Binary)]
+    #[returns(PaymentQueryResponse)]
     Msg(PaymentQueryMsg),
     #[returns(ContractInfo)]
     GetContractInfo {},
@@ -2,11 +2,15 @@
 use cosmwasm_std::entry_point;
 
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg, UpdateContractMsg};
-use crate::state::{get_key_nft_info, ContractInfo, CONTRACT_INFO, REJECTS};
+use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, UpdateContractMsg};
+use crate::state::{
+    get_key_nft_info, increment_reject_actions, reject_history, rejections, ContractInfo,
+    CONTRACT_INFO, REJECTS,
+};
 use market_rejected::{
-    Event, Expiration, IsRejectedForAllResponse, MarketRejectedExecuteMsg, MarketRejectedQueryMsg,
-    NftInfo, RejectAllEvent, Rejected, RejectedForAllResponse,
+    count_total, Event, Expiration, IsRejectedForAllResponse, MarketRejectedExecuteMsg,
+    MarketRejectedQueryMsg, NftInfo, PageResponse, Pagination, RejectAction, RejectAllEvent,
+    RejectReason, Rejected, RejectedForAllResponse, Rejection,
 };
 
 use cosmwasm_std::Record;
@@ -14,12 +18,18 @@ use cosmwasm_std::{
     attr, from_json, to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Order, Response,
     StdResult,
 };
-use cw_storage_plus::Bound;
+use cw2::set_contract_version;
+use cw_storage_plus::{Bound, MultiIndex};
+use migration::migrate_version;
 use std::usize;
 
 const DEFAULT_LIMIT: u32 = 10;
 const MAX_LIMIT: u32 = 30;
 
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:market_rejected_storage";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
 // Note, you can use StdResult in some functions where you do not
 // make use of the custom errors
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -29,6 +39,7 @@ pub fn instantiate(
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     // first time deploy, it will not know about the implementation
     let info = ContractInfo {
         governance: msg.governance,
@@ -48,12 +59,27 @@ pub fn execute(
 ) -> Result<Response, ContractError> {
     match msg {
         ExecuteMsg::Msg(offering_handle) => match offering_handle {
-            MarketRejectedExecuteMsg::RejectAll { nft_info, expires } => {
-                execute_reject_all(deps, info, env, nft_info, expires)
-            }
-            MarketRejectedExecuteMsg::ReleaseAll { nft_info } => {
-                execute_release_all(deps, info, nft_info)
-            }
+            MarketRejectedExecuteMsg::RejectAll {
+                nft_info,
+                expires,
+                reason,
+                note,
+                moderator,
+            } => execute_reject_all(
+                deps,
+                info,
+                env,
+                nft_info,
+                expires,
+                reason.unwrap_or_default(),
+                note,
+                moderator,
+            ),
+            MarketRejectedExecuteMsg::ReleaseAll {
+                nft_info,
+                note,
+                moderator,
+            } => execute_release_all(deps, info, env, nft_info, note, moderator),
         },
         ExecuteMsg::UpdateInfo(msg) => try_update_info(deps, info, env, msg),
     }
@@ -108,7 +134,22 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::Msg(auction_query) => match auction_query {
             MarketRejectedQueryMsg::IsRejectedForAll { nft_info } => {
                 let rejected = check_reject(deps, &env, &nft_info)?;
-                to_json_binary(&IsRejectedForAllResponse { rejected })
+                // legacy rejects have no details
+                let rejection = if rejected {
+                    rejections().may_load(
+                        deps.storage,
+                        &get_key_nft_info(
+                            nft_info.contract_addr.as_bytes(),
+                            nft_info.token_id.as_bytes(),
+                        ),
+                    )?
+                } else {
+                    None
+                };
+                to_json_binary(&IsRejectedForAllResponse {
+                    rejected,
+                    rejection,
+                })
             }
             MarketRejectedQueryMsg::RejectedForAll {
                 include_expired,
@@ -129,17 +170,61 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
                     limit,
                 )?)
             }
+            MarketRejectedQueryMsg::RejectedByContract {
+                contract_addr,
+                include_expired,
+                start_after,
+                limit,
+                with_total,
+            } => to_json_binary(&query_rejections(
+                deps,
+                &env,
+                &rejections().idx.contract,
+                contract_addr.as_bytes(),
+                include_expired.unwrap_or(false),
+                Pagination::new(start_after, None, limit, None, Order::Ascending),
+                with_total,
+            )?),
+            MarketRejectedQueryMsg::RejectedByModerator {
+                moderator,
+                include_expired,
+                start_after,
+                limit,
+                with_total,
+            } => to_json_binary(&query_rejections(
+                deps,
+                &env,
+                &rejections().idx.moderator,
+                moderator.as_bytes(),
+                include_expired.unwrap_or(false),
+                Pagination::new(start_after, None, limit, None, Order::Ascending),
+                with_total,
+            )?),
+            MarketRejectedQueryMsg::RejectHistory {
+                nft_info,
+                start_after,
+                limit,
+                order,
+            } => to_json_binary(&query_reject_history(
+                deps,
+                nft_info,
+                Pagination::new(start_after, None, limit, order, Order::Descending),
+            )?),
         },
         QueryMsg::GetContractInfo {} => to_json_binary(&query_contract_info(deps)?),
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn execute_reject_all(
     deps: DepsMut,
     info: MessageInfo,
     env: Env,
     nft_info: NftInfo,
     expires: Option<Expiration>,
+    reason: RejectReason,
+    note: Option<String>,
+    moderator: Option<String>,
 ) -> Result<Response, ContractError> {
     let ContractInfo {
         governance,
@@ -159,14 +244,39 @@ pub fn execute_reject_all(
         return Err(ContractError::Expired {});
     }
 
+    let moderator = get_moderator(deps.as_ref(), &info, moderator)?;
+
     // set the nft_info for us
-    REJECTS.save(
+    let key = get_key_nft_info(
+        nft_info.contract_addr.as_bytes(),
+        nft_info.token_id.as_bytes(),
+    );
+    REJECTS.save(deps.storage, &key, &expires)?;
+    rejections().save(
         deps.storage,
-        &get_key_nft_info(
-            nft_info.contract_addr.as_bytes(),
-            nft_info.token_id.as_bytes(),
-        ),
-        &expires,
+        &key,
+        &Rejection {
+            contract_addr: nft_info.contract_addr.clone(),
+            token_id: nft_info.token_id.clone(),
+            expires,
+            reason,
+            note: note.clone(),
+            moderator: moderator.clone(),
+            rejected_at: env.block.time,
+        },
+    )?;
+    save_reject_action(
+        deps,
+        RejectAction {
+            contract_addr: nft_info.contract_addr.clone(),
+            token_id: nft_info.token_id.clone(),
+            rejected: true,
+            reason: Some(reason),
+            note,
+            moderator: moderator.clone(),
+            expires: Some(expires),
+            at: env.block.time,
+        },
     )?;
 
     let mut rsp = Response::default();
@@ -175,6 +285,8 @@ pub fn execute_reject_all(
         contract_addr: &nft_info.contract_addr,
         token_id: &nft_info.token_id,
         rejected: true,
+        moderator: &moderator,
+        reason: Some(reason),
     }
     .add_attributes(&mut rsp);
     Ok(rsp)
@@ -183,7 +295,10 @@ pub fn execute_reject_all(
 pub fn execute_release_all(
     deps: DepsMut,
     info: MessageInfo,
+    env: Env,
     nft_info: NftInfo,
+    note: Option<String>,
+    moderator: Option<String>,
 ) -> Result<Response, ContractError> {
     let ContractInfo {
         governance,
@@ -197,13 +312,27 @@ pub fn execute_release_all(
         });
     };
 
-    REJECTS.remove(
-        deps.storage,
-        &get_key_nft_info(
-            nft_info.contract_addr.as_bytes(),
-            nft_info.token_id.as_bytes(),
-        ),
+    let moderator = get_moderator(deps.as_ref(), &info, moderator)?;
+
+    let key = get_key_nft_info(
+        nft_info.contract_addr.as_bytes(),
+        nft_info.token_id.as_bytes(),
     );
+    REJECTS.remove(deps.storage, &key);
+    rejections().remove(deps.storage, &key)?;
+    save_reject_action(
+        deps,
+        RejectAction {
+            contract_addr: nft_info.contract_addr.clone(),
+            token_id: nft_info.token_id.clone(),
+            rejected: false,
+            reason: None,
+            note,
+            moderator: moderator.clone(),
+            expires: None,
+            at: env.block.time,
+        },
+    )?;
 
     let mut rsp = Response::default();
     RejectAllEvent {
@@ -211,11 +340,26 @@ pub fn execute_release_all(
         contract_addr: &nft_info.contract_addr,
         token_id: &nft_info.token_id,
         rejected: false,
+        moderator: &moderator,
+        reason: None,
     }
     .add_attributes(&mut rsp);
     Ok(rsp)
 }
 
+/// the moderator is passed along by the implementation, since the storage only sees the hub
+fn get_moderator(deps: Deps, info: &MessageInfo, moderator: Option<String>) -> StdResult<String> {
+    match moderator {
+        Some(moderator) => Ok(deps.api.addr_validate(&moderator)?.to_string()),
+        None => Ok(info.sender.to_string()),
+    }
+}
+
+fn save_reject_action(deps: DepsMut, action: RejectAction) -> StdResult<()> {
+    let id = increment_reject_actions(deps.storage)?;
+    reject_history().save(deps.storage, &id.to_be_bytes(), &action)
+}
+
 fn query_all_rejected(
     deps: Deps,
     env: Env,
@@ -247,6 +391,92 @@ fn parse_rejected(item: StdResult<Record<Expiration>>) -> StdResult<Rejected> {
     })
 }
 
+fn query_rejections(
+    deps: Deps,
+    env: &Env,
+    index: &MultiIndex<Rejection>,
+    idx: &[u8],
+    include_expired: bool,
+    pagination: Pagination,
+    with_total: Option<bool>,
+) -> StdResult<PageResponse<Rejection>> {
+    let is_active = |r: &StdResult<Record<Rejection>>| {
+        include_expired || r.is_err() || !r.as_ref().unwrap().1.expires.is_expired(&env.block)
+    };
+    let total = count_total(with_total, || {
+        index
+            .items(deps.storage, idx, None, None, Order::Ascending)
+            .filter(is_active)
+            .count()
+    });
+    let (min, max, order) = pagination.bounds();
+    pagination.page_response(
+        index
+            .items(deps.storage, idx, min, max, order)
+            .filter(is_active),
+        |(_, rejection)| Ok(rejection),
+        total,
+    )
+}
+
+fn query_reject_history(
+    deps: Deps,
+    nft_info: NftInfo,
+    pagination: Pagination,
+) -> StdResult<PageResponse<RejectAction>> {
+    let (min, max, order) = pagination.bounds();
+    pagination.page_response(
+        reject_history().idx.nft.items(
+            deps.storage,
+            &get_key_nft_info(
+                nft_info.contract_addr.as_bytes(),
+                nft_info.token_id.as_bytes(),
+            ),
+            min,
+            max,
+            order,
+        ),
+        |(_, action)| Ok(action),
+        None,
+    )
+}
+
 pub fn query_contract_info(deps: Deps) -> StdResult<ContractInfo> {
     CONTRACT_INFO.load(deps.storage)
 }
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(mut deps: DepsMut, env: Env, msg: MigrateMsg) -> StdResult<Response> {
+    let res = migrate_version(deps.branch(), &env, CONTRACT_NAME, CONTRACT_VERSION, &[])?;
+    // the legacy rejects only stored an expiration, the governance is recorded as their moderator
+    let ContractInfo { governance, .. } = CONTRACT_INFO.load(deps.storage)?;
+    let mut backfilled = 0;
+    for nft_info in msg.legacy_rejects {
+        let key = get_key_nft_info(
+            nft_info.contract_addr.as_bytes(),
+            nft_info.token_id.as_bytes(),
+        );
+        let expires = match REJECTS.may_load(deps.storage, &key)? {
+            Some(expires) => expires,
+            None => continue,
+        };
+        if rejections().may_load(deps.storage, &key)?.is_some() {
+            continue;
+        }
+        rejections().save(
+            deps.storage,
+            &key,
+            &Rejection {
+                contract_addr: nft_info.contract_addr,
+                token_id: nft_info.token_id,
+                expires,
+                reason: RejectReason::Other,
+                note: None,
+                moderator: governance.to_string(),
+                rejected_at: env.block.time,
+            },
+        )?;
+        backfilled += 1;
+    }
+    Ok(res.add_attribute("backfilled_rejections", backfilled.to_string()))
+}
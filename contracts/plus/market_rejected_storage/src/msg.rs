@@ -1,8 +1,13 @@
+use cosmwasm_schema::QueryResponses;
 use cosmwasm_std::Addr;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use market_rejected::{MarketRejectedExecuteMsg, MarketRejectedQueryMsg};
+use market_rejected::{
+    MarketRejectedExecuteMsg, MarketRejectedQueryMsg, MarketRejectedQueryResponse, NftInfo,
+};
+
+use crate::state::ContractInfo;
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InstantiateMsg {
@@ -23,10 +28,19 @@ pub struct UpdateContractMsg {
     pub creator: Option<Addr>,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, QueryResponses)]
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
     // GetOfferings returns a list of all offerings
+    #[returns(MarketRejectedQueryResponse)]
     Msg(MarketRejectedQueryMsg),
+    #[returns(ContractInfo)]
     GetContractInfo {},
 }
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {
+    /// legacy rejected tokens to backfill the details of. their keys do not tell the contract
+    /// from the token id, so they have to be listed
+    pub legacy_rejects: Vec<NftInfo>,
+}
@@ -1,9 +1,9 @@
-use market_rejected::Expiration;
+use market_rejected::{Expiration, RejectAction, Rejection};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::Addr;
-use cw_storage_plus::{Item, Map};
+use cosmwasm_std::{Addr, StdResult, Storage};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
 pub struct ContractInfo {
@@ -24,4 +24,66 @@ pub const OFFERINGS_COUNT: Item<u64> = Item::new("num_offerings");
 /// ANNOTATIONS is a map which maps the annotation id to an annotation request. annotation id is derived from ANNOTATION_COUNT.
 pub const CONTRACT_INFO: Item<ContractInfo> = Item::new("marketplace_info");
 
+/// the expiration of every rejected token, this is what the implementations are checked against.
+/// the details of a rejection are kept in rejections, which has no entry for the legacy rejects
 pub const REJECTS: Map<&[u8], Expiration> = Map::new("rejects");
+
+pub const REJECT_ACTIONS_COUNT: Item<u64> = Item::new("num_reject_actions");
+
+pub fn increment_reject_actions(storage: &mut dyn Storage) -> StdResult<u64> {
+    let val = REJECT_ACTIONS_COUNT.may_load(storage)?.unwrap_or_default() + 1;
+    REJECT_ACTIONS_COUNT.save(storage, &val)?;
+    Ok(val)
+}
+
+pub struct RejectionIndexes<'a> {
+    pub contract: MultiIndex<'a, Rejection>,
+    pub moderator: MultiIndex<'a, Rejection>,
+}
+
+impl<'a> IndexList<Rejection> for RejectionIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Rejection>> + '_> {
+        let v: Vec<&dyn Index<Rejection>> = vec![&self.contract, &self.moderator];
+        Box::new(v.into_iter())
+    }
+}
+
+// keyed the same as REJECTS
+pub fn rejections<'a>() -> IndexedMap<'a, &'a [u8], Rejection, RejectionIndexes<'a>> {
+    let indexes = RejectionIndexes {
+        contract: MultiIndex::new(
+            |r| r.contract_addr.as_bytes().to_vec(),
+            "rejections",
+            "rejections__contract",
+        ),
+        moderator: MultiIndex::new(
+            |r| r.moderator.as_bytes().to_vec(),
+            "rejections",
+            "rejections__moderator",
+        ),
+    };
+    IndexedMap::new("rejections", indexes)
+}
+
+pub struct RejectActionIndexes<'a> {
+    pub nft: MultiIndex<'a, RejectAction>,
+}
+
+impl<'a> IndexList<RejectAction> for RejectActionIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<RejectAction>> + '_> {
+        let v: Vec<&dyn Index<RejectAction>> = vec![&self.nft];
+        Box::new(v.into_iter())
+    }
+}
+
+// action id => action, the ids are big endian so the history of a token is in order
+pub fn reject_history<'a>() -> IndexedMap<'a, &'a [u8], RejectAction, RejectActionIndexes<'a>> {
+    let indexes = RejectActionIndexes {
+        nft: MultiIndex::new(
+            |a| get_key_nft_info(a.contract_addr.as_bytes(), a.token_id.as_bytes()),
+            "reject_history",
+            "reject_history__nft",
+        ),
+    };
+    IndexedMap::new("reject_history", indexes)
+}
@@ -9,7 +9,7 @@ use cosmwasm_std::{coins, from_json, to_json_binary, Addr, OwnedDeps};
 
 use market_rejected::{
     Expiration, IsRejectedForAllResponse, MarketRejectedExecuteMsg, MarketRejectedQueryMsg,
-    NftInfo, RejectedForAllResponse,
+    NftInfo, PageResponse, RejectAction, RejectReason, RejectedForAllResponse, Rejection,
 };
 
 const CREATOR: &str = "marketplace";
@@ -82,6 +82,9 @@ fn test_reject_all() {
                     token_id: "token_id".to_string(),
                 },
                 expires: None,
+                moderator: None,
+                note: None,
+                reason: None,
             }),
         ),
         Err(ContractError::Unauthorized { .. })
@@ -99,6 +102,9 @@ fn test_reject_all() {
                     token_id: "token_id".to_string(),
                 },
                 expires: Some(Expiration::AtHeight(0)),
+                moderator: None,
+                note: None,
+                reason: None,
             }),
         ),
         Err(ContractError::Expired { .. })
@@ -115,6 +121,9 @@ fn test_reject_all() {
                 token_id: "token_id".to_string(),
             },
             expires: Some(Expiration::AtHeight(99999999)),
+            moderator: None,
+            note: None,
+            reason: None,
         }),
     )
     .unwrap();
@@ -184,6 +193,9 @@ fn test_query_rejects() {
             ExecuteMsg::Msg(MarketRejectedExecuteMsg::RejectAll {
                 nft_info,
                 expires: Some(Expiration::AtHeight(99999999)),
+                moderator: None,
+                note: None,
+                reason: None,
             }),
         )
         .unwrap();
@@ -284,6 +296,9 @@ fn test_revoke_all() {
                 token_id: "token_id".to_string(),
             },
             expires: Some(Expiration::AtHeight(99999999)),
+            moderator: None,
+            note: None,
+            reason: None,
         }),
     )
     .unwrap();
@@ -299,6 +314,8 @@ fn test_revoke_all() {
                     contract_addr: "nft_addr".to_string(),
                     token_id: "token_id".to_string(),
                 },
+                moderator: None,
+                note: None,
             }),
         ),
         Err(ContractError::Unauthorized { .. })
@@ -314,6 +331,8 @@ fn test_revoke_all() {
                 contract_addr: "nft_addr".to_string(),
                 token_id: "token_id".to_string(),
             },
+            moderator: None,
+            note: None,
         }),
     )
     .unwrap();
@@ -335,3 +354,200 @@ fn test_revoke_all() {
     .unwrap();
     assert_eq!(reject.rejected, false);
 }
+
+#[test]
+fn test_rejection_details_and_history() {
+    let mut deps = setup_contract();
+    let nft_info = |contract: &str, token_id: &str| NftInfo {
+        contract_addr: contract.to_string(),
+        token_id: token_id.to_string(),
+    };
+    let reject = |nft_info: NftInfo, expires: Option<Expiration>, moderator: Option<&str>| {
+        ExecuteMsg::Msg(MarketRejectedExecuteMsg::RejectAll {
+            nft_info,
+            expires,
+            reason: Some(RejectReason::Copyright),
+            note: Some("reported by the author".to_string()),
+            moderator: moderator.map(String::from),
+        })
+    };
+
+    // the hub relays the moderator, the creator rejects as itself
+    for msg in [
+        reject(nft_info("nft_a", "1"), None, Some("moderator_a")),
+        reject(nft_info("nft_a", "2"), None, Some("moderator_b")),
+        reject(
+            nft_info("nft_a", "3"),
+            Some(Expiration::AtHeight(mock_env().block.height + 1)),
+            Some("moderator_a"),
+        ),
+        reject(nft_info("nft_b", "1"), None, None),
+    ] {
+        execute(deps.as_mut(), mock_env(), mock_info("market_hub", &[]), msg).unwrap();
+    }
+
+    let res: IsRejectedForAllResponse = from_json(
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Msg(MarketRejectedQueryMsg::IsRejectedForAll {
+                nft_info: nft_info("nft_a", "1"),
+            }),
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    let rejection = res.rejection.unwrap();
+    assert_eq!(rejection.reason, RejectReason::Copyright);
+    assert_eq!(rejection.moderator, "moderator_a");
+    assert_eq!(rejection.rejected_at, mock_env().block.time);
+
+    // expired rejections are only listed on demand
+    let by_contract = |include_expired: Option<bool>, start_after| {
+        from_json::<PageResponse<Rejection>>(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::Msg(MarketRejectedQueryMsg::RejectedByContract {
+                    contract_addr: "nft_a".to_string(),
+                    include_expired,
+                    start_after,
+                    limit: Some(1),
+                    with_total: Some(true),
+                }),
+            )
+            .unwrap(),
+        )
+        .unwrap()
+    };
+    let mut env = mock_env();
+    env.block.height += 1;
+    let first = by_contract(None, None);
+    assert_eq!(first.total, Some(3));
+    assert_eq!(first.items[0].token_id, "1");
+    let second = by_contract(None, first.next_cursor);
+    assert_eq!(second.items[0].token_id, "2");
+
+    let expired: PageResponse<Rejection> = from_json(
+        query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::Msg(MarketRejectedQueryMsg::RejectedByContract {
+                contract_addr: "nft_a".to_string(),
+                include_expired: None,
+                start_after: None,
+                limit: None,
+                with_total: Some(true),
+            }),
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(expired.total, Some(2));
+
+    let by_moderator: PageResponse<Rejection> = from_json(
+        query(
+            deps.as_ref(),
+            env,
+            QueryMsg::Msg(MarketRejectedQueryMsg::RejectedByModerator {
+                moderator: "moderator_a".to_string(),
+                include_expired: Some(true),
+                start_after: None,
+                limit: None,
+                with_total: None,
+            }),
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        by_moderator
+            .items
+            .iter()
+            .map(|r| r.token_id.as_str())
+            .collect::<Vec<_>>(),
+        vec!["1", "3"]
+    );
+
+    // release keeps the history of the token
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("market_hub", &[]),
+        ExecuteMsg::Msg(MarketRejectedExecuteMsg::ReleaseAll {
+            nft_info: nft_info("nft_a", "1"),
+            note: Some("appeal accepted".to_string()),
+            moderator: Some("moderator_b".to_string()),
+        }),
+    )
+    .unwrap();
+    let res: IsRejectedForAllResponse = from_json(
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Msg(MarketRejectedQueryMsg::IsRejectedForAll {
+                nft_info: nft_info("nft_a", "1"),
+            }),
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert!(!res.rejected);
+    assert_eq!(res.rejection, None);
+
+    let history: PageResponse<RejectAction> = from_json(
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Msg(MarketRejectedQueryMsg::RejectHistory {
+                nft_info: nft_info("nft_a", "1"),
+                start_after: None,
+                limit: None,
+                order: None,
+            }),
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(history.items.len(), 2);
+    assert!(!history.items[0].rejected);
+    assert_eq!(history.items[0].moderator, "moderator_b");
+    assert_eq!(history.items[1].reason, Some(RejectReason::Copyright));
+}
+
+#[test]
+fn test_migrate_legacy_rejects() {
+    let mut deps = setup_contract();
+    let nft_info = NftInfo {
+        contract_addr: "nft_addr".to_string(),
+        token_id: "token_id".to_string(),
+    };
+    crate::state::REJECTS
+        .save(
+            deps.as_mut().storage,
+            b"nft_addrtoken_id",
+            &Expiration::Never {},
+        )
+        .unwrap();
+
+    migrate(
+        deps.as_mut(),
+        mock_env(),
+        MigrateMsg {
+            legacy_rejects: vec![nft_info.clone()],
+        },
+    )
+    .unwrap();
+
+    let res: IsRejectedForAllResponse = from_json(
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Msg(MarketRejectedQueryMsg::IsRejectedForAll { nft_info }),
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert!(res.rejected);
+    assert_eq!(res.rejection.unwrap().moderator, "market_hub");
+}
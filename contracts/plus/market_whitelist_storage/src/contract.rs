@@ -3,22 +3,31 @@ use cosmwasm_std::entry_point;
 
 use crate::error::ContractError;
 use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, UpdateContractMsg};
-use crate::state::{ContractInfo, APPROVES, CONTRACT_INFO};
+use crate::state::{collections, ContractInfo, APPLICATIONS, APPROVES, CONTRACT_INFO};
 use market_whitelist::{
-    ApproveAllEvent, Approved, ApprovedForAllResponse, Event, Expiration, IsApprovedForAllResponse,
-    MarketWhiteListExecuteMsg, MarketWhiteListdQueryMsg,
+    count_total, ApproveAllEvent, Approved, ApprovedForAllResponse, Collection,
+    CollectionApplication, CollectionMetadata, Event, Expiration, IsApprovedForAllResponse,
+    ListingType, MarketWhiteListExecuteMsg, MarketWhiteListdQueryMsg, MinPrice, PageResponse,
+    Pagination,
 };
 
 use cosmwasm_std::{
     attr, to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Order, Response, StdResult,
+    Uint128,
 };
 use cosmwasm_std::{Addr, Record};
+use cw2::set_contract_version;
 use cw_storage_plus::Bound;
+use migration::migrate_version;
 use std::usize;
 
 const DEFAULT_LIMIT: u32 = 10;
 const MAX_LIMIT: u32 = 50;
 
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:market_whitelist_storage";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
 // Note, you can use StdResult in some functions where you do not
 // make use of the custom errors
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -28,6 +37,7 @@ pub fn instantiate(
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     // first time deploy, it will not know about the implementation
     let info = ContractInfo {
         governance: msg.governance,
@@ -53,6 +63,43 @@ pub fn execute(
             MarketWhiteListExecuteMsg::RevokeAll { nft_addr } => {
                 execute_revoke_all(deps, info, nft_addr)
             }
+            MarketWhiteListExecuteMsg::ApplyCollection {
+                nft_addr,
+                metadata,
+                listing_types,
+                min_prices,
+            } => execute_apply_collection(
+                deps,
+                info,
+                env,
+                nft_addr,
+                metadata,
+                listing_types,
+                min_prices,
+            ),
+            MarketWhiteListExecuteMsg::ApproveCollection {
+                nft_addr,
+                verified,
+                expires,
+            } => execute_approve_collection(deps, info, env, nft_addr, verified, expires),
+            MarketWhiteListExecuteMsg::RejectApplication { nft_addr } => {
+                execute_reject_application(deps, info, nft_addr)
+            }
+            MarketWhiteListExecuteMsg::UpdateCollection {
+                nft_addr,
+                verified,
+                metadata,
+                listing_types,
+                min_prices,
+            } => execute_update_collection(
+                deps,
+                info,
+                nft_addr,
+                verified,
+                metadata,
+                listing_types,
+                min_prices,
+            ),
         },
         ExecuteMsg::UpdateInfo(msg) => try_update_info(deps, info, env, msg),
     }
@@ -101,7 +148,15 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::Msg(auction_query) => match auction_query {
             MarketWhiteListdQueryMsg::IsApprovedForAll { nft_addr } => {
                 let approved = check_can_approve(deps, &env, &nft_addr)?;
-                to_json_binary(&IsApprovedForAllResponse { approved })
+                let collection = if approved {
+                    collections().may_load(deps.storage, nft_addr.as_bytes())?
+                } else {
+                    None
+                };
+                to_json_binary(&IsApprovedForAllResponse {
+                    approved,
+                    collection,
+                })
             }
             MarketWhiteListdQueryMsg::ApprovedForAll {
                 include_expired,
@@ -117,6 +172,29 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
                     limit,
                 )?)
             }
+            MarketWhiteListdQueryMsg::Collection { nft_addr } => {
+                to_json_binary(&collections().may_load(deps.storage, nft_addr.as_bytes())?)
+            }
+            MarketWhiteListdQueryMsg::Collections {
+                category,
+                start_after,
+                limit,
+                with_total,
+            } => to_json_binary(&query_collections(
+                deps,
+                category,
+                Pagination::new(start_after, None, limit, None, Order::Ascending),
+                with_total,
+            )?),
+            MarketWhiteListdQueryMsg::PendingApplications {
+                start_after,
+                limit,
+                with_total,
+            } => to_json_binary(&query_pending_applications(
+                deps,
+                Pagination::new(start_after, None, limit, None, Order::Ascending),
+                with_total,
+            )?),
         },
         QueryMsg::GetContractInfo {} => to_json_binary(&query_contract_info(deps)?),
     }
@@ -178,6 +256,7 @@ pub fn execute_revoke_all(
     };
 
     APPROVES.remove(deps.storage, nft_addr.as_bytes());
+    collections().remove(deps.storage, nft_addr.as_bytes())?;
 
     let mut rsp = Response::default();
     ApproveAllEvent {
@@ -189,6 +268,192 @@ pub fn execute_revoke_all(
     Ok(rsp)
 }
 
+fn check_governance(deps: Deps, info: &MessageInfo) -> Result<(), ContractError> {
+    let ContractInfo {
+        governance,
+        creator,
+        ..
+    } = CONTRACT_INFO.load(deps.storage)?;
+
+    if governance.ne(&info.sender) && creator.ne(&info.sender) {
+        return Err(ContractError::Unauthorized {
+            sender: info.sender.to_string(),
+        });
+    };
+    Ok(())
+}
+
+pub fn execute_apply_collection(
+    deps: DepsMut,
+    info: MessageInfo,
+    env: Env,
+    nft_addr: String,
+    metadata: CollectionMetadata,
+    listing_types: Vec<ListingType>,
+    min_prices: Option<Vec<MinPrice>>,
+) -> Result<Response, ContractError> {
+    if listing_types.is_empty() {
+        return Err(ContractError::NoListingTypes {});
+    }
+    let nft_addr = deps.api.addr_validate(&nft_addr)?.to_string();
+
+    // an applicant can update its own application, but not replace someone else's
+    if let Some(pending) = APPLICATIONS.may_load(deps.storage, nft_addr.as_bytes())? {
+        if pending.applicant.ne(info.sender.as_str()) {
+            return Err(ContractError::ApplicationPending {
+                nft_addr,
+                applicant: pending.applicant,
+            });
+        }
+    }
+
+    APPLICATIONS.save(
+        deps.storage,
+        nft_addr.as_bytes(),
+        &CollectionApplication {
+            nft_addr: nft_addr.clone(),
+            applicant: info.sender.to_string(),
+            metadata,
+            listing_types,
+            min_prices: validate_min_prices(min_prices.unwrap_or_default())?,
+            applied_at: env.block.time,
+        },
+    )?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "apply_collection"),
+        attr("nft_addr", nft_addr),
+        attr("applicant", info.sender),
+    ]))
+}
+
+pub fn execute_approve_collection(
+    deps: DepsMut,
+    info: MessageInfo,
+    env: Env,
+    nft_addr: String,
+    verified: bool,
+    expires: Option<Expiration>,
+) -> Result<Response, ContractError> {
+    check_governance(deps.as_ref(), &info)?;
+
+    let CollectionApplication {
+        applicant,
+        mut metadata,
+        listing_types,
+        min_prices,
+        ..
+    } = APPLICATIONS
+        .may_load(deps.storage, nft_addr.as_bytes())?
+        .ok_or_else(|| ContractError::NoApplication {
+            nft_addr: nft_addr.clone(),
+        })?;
+
+    let expires = expires.unwrap_or_default();
+    if expires.is_expired(&env.block) {
+        return Err(ContractError::Expired {});
+    }
+
+    metadata.creator = metadata.creator.or(Some(applicant));
+    collections().save(
+        deps.storage,
+        nft_addr.as_bytes(),
+        &Collection {
+            nft_addr: nft_addr.clone(),
+            verified,
+            metadata,
+            listing_types,
+            min_prices,
+        },
+    )?;
+    APPROVES.save(deps.storage, nft_addr.as_bytes(), &expires)?;
+    APPLICATIONS.remove(deps.storage, nft_addr.as_bytes());
+
+    let mut rsp = Response::new();
+    rsp.attributes.push(attr("verified", verified.to_string()));
+    ApproveAllEvent {
+        sender: info.sender.as_ref(),
+        nft_addr: &nft_addr,
+        approved: true,
+    }
+    .add_attributes(&mut rsp);
+    Ok(rsp)
+}
+
+pub fn execute_reject_application(
+    deps: DepsMut,
+    info: MessageInfo,
+    nft_addr: String,
+) -> Result<Response, ContractError> {
+    check_governance(deps.as_ref(), &info)?;
+
+    if APPLICATIONS
+        .may_load(deps.storage, nft_addr.as_bytes())?
+        .is_none()
+    {
+        return Err(ContractError::NoApplication { nft_addr });
+    }
+    APPLICATIONS.remove(deps.storage, nft_addr.as_bytes());
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "reject_application"),
+        attr("nft_addr", nft_addr),
+    ]))
+}
+
+pub fn execute_update_collection(
+    deps: DepsMut,
+    info: MessageInfo,
+    nft_addr: String,
+    verified: Option<bool>,
+    metadata: Option<CollectionMetadata>,
+    listing_types: Option<Vec<ListingType>>,
+    min_prices: Option<Vec<MinPrice>>,
+) -> Result<Response, ContractError> {
+    check_governance(deps.as_ref(), &info)?;
+
+    let mut collection = collections()
+        .may_load(deps.storage, nft_addr.as_bytes())?
+        .ok_or_else(|| ContractError::CollectionNotFound {
+            nft_addr: nft_addr.clone(),
+        })?;
+    if let Some(verified) = verified {
+        collection.verified = verified;
+    }
+    if let Some(metadata) = metadata {
+        collection.metadata = metadata;
+    }
+    if let Some(listing_types) = listing_types {
+        if listing_types.is_empty() {
+            return Err(ContractError::NoListingTypes {});
+        }
+        collection.listing_types = listing_types;
+    }
+    if let Some(min_prices) = min_prices {
+        collection.min_prices = validate_min_prices(min_prices)?;
+    }
+    collections().save(deps.storage, nft_addr.as_bytes(), &collection)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "update_collection"),
+        attr("nft_addr", nft_addr),
+    ]))
+}
+
+/// a zero minimum is no minimum, and an asset has at most one
+fn validate_min_prices(mut min_prices: Vec<MinPrice>) -> Result<Vec<MinPrice>, ContractError> {
+    min_prices.retain(|min_price| !min_price.amount.is_zero());
+    for (i, min_price) in min_prices.iter().enumerate() {
+        if min_prices[..i]
+            .iter()
+            .any(|other| other.asset_info.eq(&min_price.asset_info))
+        {
+            return Err(ContractError::DuplicateMinPrice {});
+        }
+    }
+    Ok(min_prices)
+}
+
 fn query_all_approvals(
     deps: Deps,
     env: Env,
@@ -215,11 +480,78 @@ fn parse_approved(item: StdResult<Record<Expiration>>) -> StdResult<Approved> {
     })
 }
 
+fn query_collections(
+    deps: Deps,
+    category: Option<String>,
+    pagination: Pagination,
+    with_total: Option<bool>,
+) -> StdResult<PageResponse<Collection>> {
+    let (min, max, order) = pagination.bounds();
+    match category {
+        Some(category) => {
+            let total = count_total(with_total, || {
+                collections()
+                    .idx
+                    .category
+                    .pks(
+                        deps.storage,
+                        category.as_bytes(),
+                        None,
+                        None,
+                        Order::Ascending,
+                    )
+                    .count()
+            });
+            pagination.page_response(
+                collections().idx.category.items(
+                    deps.storage,
+                    category.as_bytes(),
+                    min,
+                    max,
+                    order,
+                ),
+                |(_, collection)| Ok(collection),
+                total,
+            )
+        }
+        None => {
+            let total = count_total(with_total, || {
+                collections()
+                    .range(deps.storage, None, None, Order::Ascending)
+                    .count()
+            });
+            pagination.page_response(
+                collections().range(deps.storage, min, max, order),
+                |(_, collection)| Ok(collection),
+                total,
+            )
+        }
+    }
+}
+
+fn query_pending_applications(
+    deps: Deps,
+    pagination: Pagination,
+    with_total: Option<bool>,
+) -> StdResult<PageResponse<CollectionApplication>> {
+    let total = count_total(with_total, || {
+        APPLICATIONS
+            .range(deps.storage, None, None, Order::Ascending)
+            .count()
+    });
+    let (min, max, order) = pagination.bounds();
+    pagination.page_response(
+        APPLICATIONS.range(deps.storage, min, max, order),
+        |(_, application)| Ok(application),
+        total,
+    )
+}
+
 pub fn query_contract_info(deps: Deps) -> StdResult<ContractInfo> {
     CONTRACT_INFO.load(deps.storage)
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
-    Ok(Response::default())
+pub fn migrate(deps: DepsMut, env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+    migrate_version(deps, &env, CONTRACT_NAME, CONTRACT_VERSION, &[])
 }
@@ -15,4 +15,19 @@ pub enum ContractError {
     // Look at https://docs.rs/thiserror/1.0.21/thiserror/ for details.
     #[error("Expired")]
     Expired {},
+
+    #[error("No pending application for the collection {nft_addr}")]
+    NoApplication { nft_addr: String },
+
+    #[error("The collection {nft_addr} already has a pending application by {applicant}")]
+    ApplicationPending { nft_addr: String, applicant: String },
+
+    #[error("The collection {nft_addr} is not in the registry")]
+    CollectionNotFound { nft_addr: String },
+
+    #[error("A collection must allow at least one listing type")]
+    NoListingTypes {},
+
+    #[error("A collection has at most one minimum price per asset")]
+    DuplicateMinPrice {},
 }
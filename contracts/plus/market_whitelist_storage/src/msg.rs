@@ -1,8 +1,13 @@
+use cosmwasm_schema::QueryResponses;
 use cosmwasm_std::Addr;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use market_whitelist::{MarketWhiteListExecuteMsg, MarketWhiteListdQueryMsg};
+use market_whitelist::{
+    MarketWhiteListExecuteMsg, MarketWhiteListQueryResponse, MarketWhiteListdQueryMsg,
+};
+
+use crate::state::ContractInfo;
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InstantiateMsg {
@@ -23,11 +28,13 @@ pub struct UpdateContractMsg {
     pub creator: Option<Addr>,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, QueryResponses)]
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
     // GetOfferings returns a list of all offerings
+    #[returns(MarketWhiteListQueryResponse)]
     Msg(MarketWhiteListdQueryMsg),
+    #[returns(ContractInfo)]
     GetContractInfo {},
 }
 
@@ -1,9 +1,9 @@
-use market_whitelist::Expiration;
+use market_whitelist::{Collection, CollectionApplication, Expiration};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use cosmwasm_std::Addr;
-use cw_storage_plus::{Item, Map};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
 pub struct ContractInfo {
@@ -17,3 +17,35 @@ pub const OFFERINGS_COUNT: Item<u64> = Item::new("num_offerings");
 pub const CONTRACT_INFO: Item<ContractInfo> = Item::new("marketplace_info");
 
 pub const APPROVES: Map<&[u8], Expiration> = Map::new("approves");
+
+/// pending applications by nft contract, removed once the governance decides
+pub const APPLICATIONS: Map<&[u8], CollectionApplication> = Map::new("collection_applications");
+
+pub struct CollectionIndexes<'a> {
+    pub category: MultiIndex<'a, Collection>,
+}
+
+impl<'a> IndexList<Collection> for CollectionIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Collection>> + '_> {
+        let v: Vec<&dyn Index<Collection>> = vec![&self.category];
+        Box::new(v.into_iter())
+    }
+}
+
+// nft contract => collection, whitelisted together with APPROVES
+pub fn collections<'a>() -> IndexedMap<'a, &'a [u8], Collection, CollectionIndexes<'a>> {
+    let indexes = CollectionIndexes {
+        category: MultiIndex::new(
+            |c| {
+                c.metadata
+                    .category
+                    .as_ref()
+                    .map(|category| category.as_bytes().to_vec())
+                    .unwrap_or_default()
+            },
+            "collections",
+            "collections__category",
+        ),
+    };
+    IndexedMap::new("collections", indexes)
+}
@@ -5,11 +5,12 @@ use crate::state::ContractInfo;
 use cosmwasm_std::testing::{
     mock_dependencies_with_balance, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
 };
-use cosmwasm_std::{coins, from_json, Addr, OwnedDeps};
+use cosmwasm_std::{coins, from_json, Addr, OwnedDeps, Uint128};
 
 use market_whitelist::{
-    ApprovedForAllResponse, Expiration, IsApprovedForAllResponse, MarketWhiteListExecuteMsg,
-    MarketWhiteListdQueryMsg,
+    ApprovedForAllResponse, AssetInfo, Collection, CollectionApplication, CollectionMetadata,
+    Expiration, IsApprovedForAllResponse, ListingType, MarketWhiteListExecuteMsg,
+    MarketWhiteListdQueryMsg, MinPrice, PageResponse,
 };
 
 const CREATOR: &str = "marketplace";
@@ -280,3 +281,208 @@ fn test_revoke_all() {
     .unwrap();
     assert_eq!(approve.approved, false);
 }
+
+#[test]
+fn test_collection_application() {
+    let mut deps = setup_contract();
+    let apply = |name: &str, category: &str| {
+        ExecuteMsg::Msg(MarketWhiteListExecuteMsg::ApplyCollection {
+            nft_addr: format!("{}_nft", name),
+            metadata: CollectionMetadata {
+                name: name.to_string(),
+                category: Some(category.to_string()),
+                creator: None,
+            },
+            listing_types: vec![ListingType::Auction],
+            min_prices: Some(vec![MinPrice {
+                asset_info: AssetInfo::NativeToken {
+                    denom: "orai".to_string(),
+                },
+                amount: Uint128::from(100u64),
+            }]),
+        })
+    };
+
+    for (applicant, name, category) in [
+        ("artist", "art", "art"),
+        ("gamer", "game", "game"),
+        ("painter", "paint", "art"),
+    ] {
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(applicant, &[]),
+            apply(name, category),
+        )
+        .unwrap();
+    }
+
+    // only the applicant can change its application
+    assert!(matches!(
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("hacker", &[]),
+            apply("art", "art")
+        ),
+        Err(ContractError::ApplicationPending { .. })
+    ));
+
+    // only the governance decides
+    assert!(matches!(
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("artist", &[]),
+            ExecuteMsg::Msg(MarketWhiteListExecuteMsg::ApproveCollection {
+                nft_addr: "art_nft".to_string(),
+                verified: true,
+                expires: None,
+            }),
+        ),
+        Err(ContractError::Unauthorized { .. })
+    ));
+    for nft_addr in ["art_nft", "paint_nft"] {
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("market_hub", &[]),
+            ExecuteMsg::Msg(MarketWhiteListExecuteMsg::ApproveCollection {
+                nft_addr: nft_addr.to_string(),
+                verified: true,
+                expires: None,
+            }),
+        )
+        .unwrap();
+    }
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("market_hub", &[]),
+        ExecuteMsg::Msg(MarketWhiteListExecuteMsg::RejectApplication {
+            nft_addr: "game_nft".to_string(),
+        }),
+    )
+    .unwrap();
+
+    let pending: PageResponse<CollectionApplication> = from_json(
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Msg(MarketWhiteListdQueryMsg::PendingApplications {
+                start_after: None,
+                limit: None,
+                with_total: Some(true),
+            }),
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(pending.total, Some(0));
+
+    // the approval whitelists the collection with its registry details
+    let res: IsApprovedForAllResponse = from_json(
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Msg(MarketWhiteListdQueryMsg::IsApprovedForAll {
+                nft_addr: "art_nft".to_string(),
+            }),
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert!(res.approved);
+    let collection = res.collection.unwrap();
+    assert!(collection.allows(ListingType::Auction));
+    assert!(!collection.allows(ListingType::FixedPrice));
+    assert_eq!(collection.metadata.creator, Some("artist".to_string()));
+
+    let art: PageResponse<Collection> = from_json(
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Msg(MarketWhiteListdQueryMsg::Collections {
+                category: Some("art".to_string()),
+                start_after: None,
+                limit: Some(1),
+                with_total: Some(true),
+            }),
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(art.total, Some(2));
+    assert_eq!(art.items[0].nft_addr, "art_nft");
+    assert!(art.next_cursor.is_some());
+
+    // governance can open the collection to fixed price listings, an asset has one minimum
+    let update = |min_prices: Vec<MinPrice>| {
+        ExecuteMsg::Msg(MarketWhiteListExecuteMsg::UpdateCollection {
+            nft_addr: "art_nft".to_string(),
+            verified: None,
+            metadata: None,
+            listing_types: Some(vec![ListingType::FixedPrice, ListingType::Auction]),
+            min_prices: Some(min_prices),
+        })
+    };
+    let min_price = |amount: u64| MinPrice {
+        asset_info: AssetInfo::Token {
+            contract_addr: Addr::unchecked("airi"),
+        },
+        amount: Uint128::from(amount),
+    };
+    assert!(matches!(
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("market_hub", &[]),
+            update(vec![min_price(10), min_price(20)]),
+        ),
+        Err(ContractError::DuplicateMinPrice {})
+    ));
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("market_hub", &[]),
+        update(vec![min_price(0)]),
+    )
+    .unwrap();
+    let collection: Option<Collection> = from_json(
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Msg(MarketWhiteListdQueryMsg::Collection {
+                nft_addr: "art_nft".to_string(),
+            }),
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    let collection = collection.unwrap();
+    assert!(collection.allows(ListingType::FixedPrice));
+    assert_eq!(collection.min_prices, vec![]);
+
+    // revoking drops the collection from the registry
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("market_hub", &[]),
+        ExecuteMsg::Msg(MarketWhiteListExecuteMsg::RevokeAll {
+            nft_addr: "art_nft".to_string(),
+        }),
+    )
+    .unwrap();
+    let collection: Option<Collection> = from_json(
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Msg(MarketWhiteListdQueryMsg::Collection {
+                nft_addr: "art_nft".to_string(),
+            }),
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(collection, None);
+}
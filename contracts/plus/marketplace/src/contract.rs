@@ -16,8 +16,10 @@ use cosmwasm_std::{
     DepsMut, Env, MessageInfo, Order, Record, Response, StdError, StdResult, Storage, Uint128,
     WasmMsg,
 };
+use cw2::set_contract_version;
 use cw721::{Cw721ExecuteMsg, Cw721ReceiveMsg};
 use cw_storage_plus::Bound;
+use migration::{migrate_version, MigrationStep};
 use std::convert::TryInto;
 use std::ops::{Mul, Sub};
 use std::usize;
@@ -37,6 +39,10 @@ fn sanitize_royalty(royalty: u64, limit: u64, name: &str) -> Result<u64, Contrac
     Ok(royalty)
 }
 
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:marketplace";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
 // Note, you can use StdResult in some functions where you do not
 // make use of the custom errors
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -46,6 +52,7 @@ pub fn instantiate(
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     let info = ContractInfo {
         name: msg.name,
         creator: info.sender.to_string(),
@@ -674,9 +681,19 @@ fn parse_offering<'a>(
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+pub fn migrate(deps: DepsMut, env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+    migrate_version(deps, &env, CONTRACT_NAME, CONTRACT_VERSION, MIGRATIONS)
+}
+
+const MIGRATIONS: &[MigrationStep] = &[MigrationStep {
+    version: "0.1.0",
+    name: "init_contract_info",
+    run: init_contract_info,
+}];
+
+fn init_contract_info(deps: DepsMut, _env: &Env) -> StdResult<()> {
     CONTRACT_INFO.save(
-        _deps.storage,
+        deps.storage,
         &ContractInfo {
             name: "marketplace".to_string(),
             creator: "".to_string(),
@@ -685,5 +702,5 @@ pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Respons
             max_royalty: 0,
         },
     )?;
-    Ok(Response::default())
+    Ok(())
 }
@@ -2,9 +2,11 @@
 use cosmwasm_std::entry_point;
 
 use cosmwasm_std::{
-    attr, to_json_binary, Addr, Api, Binary, BlockInfo, Deps, DepsMut, Env, MessageInfo, Order,
-    Record, Response, StdError, StdResult,
+    attr, to_json_binary, Addr, Api, Binary, BlockInfo, CanonicalAddr, Deps, DepsMut, Env,
+    MessageInfo, Order, Record, Response, StdError, StdResult,
 };
+use cw2::set_contract_version;
+use migration::{migrate_version, MigrationStep};
 
 use cw721::{
     AllNftInfoResponse, ApprovedForAllResponse, ContractInfoResponse, Cw721ReceiveMsg, Expiration,
@@ -16,9 +18,9 @@ use crate::error::ContractError;
 use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, MintMsg, MinterResponse, QueryMsg};
 use crate::state::{
     decrement_tokens, increment_tokens, num_tokens, tokens, Approval, TokenInfo, CONTRACT_INFO,
-    MINTER, OPERATORS, OWNER,
+    MINTER, MINTER_KEY, OPERATORS, OWNER, OWNER_KEY,
 };
-use cw_storage_plus::Bound;
+use cw_storage_plus::{Bound, Item};
 
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:oraichain_nft";
@@ -34,16 +36,16 @@ pub fn instantiate(
     msg_info: MessageInfo,
     msg: InstantiateMsg,
 ) -> StdResult<Response> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     let info = ContractInfoResponse {
         name: msg.name.unwrap_or(CONTRACT_NAME.to_string()),
         symbol: msg.symbol,
         version: msg.version.unwrap_or(CONTRACT_VERSION.to_string()),
     };
     CONTRACT_INFO.save(deps.storage, &info)?;
-    let minter = deps.api.addr_canonicalize(&msg.minter.as_str())?;
-    let owner = deps.api.addr_canonicalize(&msg_info.sender.as_str())?;
+    let minter = deps.api.addr_validate(msg.minter.as_str())?;
     MINTER.save(deps.storage, &minter)?;
-    OWNER.save(deps.storage, &owner)?;
+    OWNER.save(deps.storage, &msg_info.sender)?;
     Ok(Response::default())
 }
 
@@ -89,11 +91,25 @@ pub fn execute(
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
-    Ok(Response::new().add_attributes(vec![
-        attr("new_contract_name", CONTRACT_NAME),
-        attr("new_contract_version", CONTRACT_VERSION),
-    ]))
+pub fn migrate(deps: DepsMut, env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+    migrate_version(deps, &env, CONTRACT_NAME, CONTRACT_VERSION, MIGRATIONS)
+}
+
+const MIGRATIONS: &[MigrationStep] = &[MigrationStep {
+    version: "0.2.0",
+    name: "minter_owner_to_addr",
+    run: minter_owner_to_addr,
+}];
+
+/// the minter and owner were stored as canonical addresses before 0.2.0
+fn minter_owner_to_addr(deps: DepsMut, _env: &Env) -> StdResult<()> {
+    for (key, item) in [(MINTER_KEY, MINTER), (OWNER_KEY, OWNER)] {
+        let legacy: Item<CanonicalAddr> = Item::new(key);
+        if let Some(raw) = legacy.may_load(deps.storage)? {
+            item.save(deps.storage, &deps.api.addr_humanize(&raw)?)?;
+        }
+    }
+    Ok(())
 }
 
 pub fn handle_mint(
@@ -103,9 +119,8 @@ pub fn handle_mint(
     msg: MintMsg,
 ) -> Result<Response, ContractError> {
     let minter = MINTER.load(deps.storage)?;
-    let sender_raw = deps.api.addr_canonicalize(&info.sender.as_str())?;
 
-    if sender_raw != minter {
+    if info.sender != minter {
         return Err(ContractError::Unauthorized {});
     }
 
@@ -383,12 +398,11 @@ pub fn handle_change_minter(
     info: MessageInfo,
     minter: Addr,
 ) -> Result<Response, ContractError> {
-    let owner_raw = deps.api.addr_canonicalize(&info.sender.as_str())?;
     let owner = OWNER.load(deps.storage)?;
-    if !owner.eq(&owner_raw) {
+    if !owner.eq(&info.sender) {
         return Err(ContractError::Unauthorized {});
     }
-    let minter = deps.api.addr_canonicalize(minter.as_str())?;
+    let minter = deps.api.addr_validate(minter.as_str())?;
     MINTER.save(deps.storage, &minter)?;
     Ok(Response::new().add_attributes(vec![
         attr("action", "change_minter"),
@@ -406,8 +420,8 @@ fn check_can_approve(
 ) -> Result<(), ContractError> {
     // owner can approve
     let sender_raw = deps.api.addr_canonicalize(&info.sender.as_str())?;
-    let owner_raw = OWNER.load(deps.storage)?;
-    if sender_raw.eq(&owner_raw) {
+    let owner = OWNER.load(deps.storage)?;
+    if info.sender.eq(&owner) {
         return Ok(());
     }
     if token.owner == sender_raw {
@@ -436,8 +450,8 @@ fn check_can_send(
 ) -> Result<(), ContractError> {
     // owner can send
     let sender_raw = deps.api.addr_canonicalize(&info.sender.as_str())?;
-    let owner_raw = OWNER.load(deps.storage)?;
-    if sender_raw.eq(&owner_raw) {
+    let owner = OWNER.load(deps.storage)?;
+    if info.sender.eq(&owner) {
         return Ok(());
     }
     if token.owner == sender_raw {
@@ -520,8 +534,7 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
 }
 
 fn query_minter(deps: Deps) -> StdResult<MinterResponse> {
-    let minter_raw = MINTER.load(deps.storage)?;
-    let minter = deps.api.addr_humanize(&minter_raw)?;
+    let minter = MINTER.load(deps.storage)?;
     Ok(MinterResponse { minter })
 }
 
@@ -1,7 +1,7 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::{CanonicalAddr, StdResult, Storage};
+use cosmwasm_std::{Addr, CanonicalAddr, StdResult, Storage};
 use cw721::{ContractInfoResponse, Expiration};
 use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
 
@@ -31,8 +31,10 @@ pub struct Approval {
 }
 
 pub const CONTRACT_INFO: Item<ContractInfoResponse> = Item::new("nft_info");
-pub const MINTER: Item<CanonicalAddr> = Item::new("minter");
-pub const OWNER: Item<CanonicalAddr> = Item::new("owner");
+pub const MINTER_KEY: &str = "minter";
+pub const OWNER_KEY: &str = "owner";
+pub const MINTER: Item<Addr> = Item::new(MINTER_KEY);
+pub const OWNER: Item<Addr> = Item::new(OWNER_KEY);
 pub const TOKEN_COUNT: Item<u64> = Item::new("num_tokens");
 
 pub const OPERATORS: Map<(&[u8], &[u8]), Expiration> = Map::new("operators");
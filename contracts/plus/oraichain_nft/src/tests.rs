@@ -5,8 +5,10 @@ use cosmwasm_std::testing::{
     mock_dependencies_with_balance, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
 };
 use cosmwasm_std::{
-    attr, coins, from_json, to_json_binary, Addr, Api, CosmosMsg, OwnedDeps, Response, WasmMsg,
+    attr, coins, from_json, to_json_binary, Addr, Api, CanonicalAddr, CosmosMsg, OwnedDeps,
+    Response, WasmMsg,
 };
+use cw_storage_plus::Item;
 
 use cw721::{
     ApprovedForAllResponse, ContractInfoResponse, Cw721ReceiveMsg, Expiration, NftInfoResponse,
@@ -990,3 +992,44 @@ fn update_nft() {
         }
     );
 }
+
+#[test]
+fn migrate_minter_and_owner_to_addr() {
+    let mut deps = mock_dependencies_with_balance(&coins(100000, "orai"));
+    // the state of a contract deployed before 0.2.0, without a version
+    let minter = deps.api.addr_canonicalize(MINTER).unwrap();
+    let owner = deps.api.addr_canonicalize(OWNER).unwrap();
+    Item::<CanonicalAddr>::new("minter")
+        .save(deps.as_mut().storage, &minter)
+        .unwrap();
+    Item::<CanonicalAddr>::new("owner")
+        .save(deps.as_mut().storage, &owner)
+        .unwrap();
+
+    let res = migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+    assert!(res
+        .attributes
+        .contains(&attr("migration_step", "0.2.0:minter_owner_to_addr")));
+    let res: MinterResponse =
+        from_json(query(deps.as_ref(), mock_env(), QueryMsg::Minter {}).unwrap()).unwrap();
+    assert_eq!(MINTER, res.minter.as_str());
+
+    // the owner can change the minter with the migrated state
+    let info = mock_info(OWNER, &[]);
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::ChangeMinter {
+            minter: Addr::unchecked(OWNER),
+        },
+    )
+    .unwrap();
+
+    // migrating again runs nothing
+    let res = migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+    assert!(!res
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "migration_step"));
+}
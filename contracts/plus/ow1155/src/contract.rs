@@ -5,7 +5,9 @@ use cosmwasm_std::{
     attr, from_json, to_json_binary, Addr, BankMsg, Binary, CosmosMsg, Deps, DepsMut, Env,
     MessageInfo, Order, Record, Response, StdError, StdResult, Uint128,
 };
+use cw2::set_contract_version;
 use cw_storage_plus::Bound;
+use migration::migrate_version;
 
 use cw1155::{
     ApproveAllEvent, ApprovedForAllResponse, BalanceResponse, BatchBalanceResponse,
@@ -21,6 +23,10 @@ use crate::state::{APPROVES, BALANCES, MINTER, OWNER, TOKENS};
 const DEFAULT_LIMIT: u32 = 10;
 const MAX_LIMIT: u32 = 30;
 
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:ow1155";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
@@ -28,6 +34,7 @@ pub fn instantiate(
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> StdResult<Response> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     let minter = Addr::unchecked(msg.minter);
     MINTER.save(deps.storage, &minter)?;
     OWNER.save(deps.storage, &info.sender)?;
@@ -42,8 +49,8 @@ pub struct ExecuteEnv<'a> {
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
-    Ok(Response::default())
+pub fn migrate(deps: DepsMut, env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+    migrate_version(deps, &env, CONTRACT_NAME, CONTRACT_VERSION, &[])
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -5,9 +5,11 @@ use cosmwasm_std::{
     attr, to_json_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Order, Response,
     StdResult, Uint128, WasmMsg,
 };
+use cw2::set_contract_version;
 use cw20::Cw20ExecuteMsg;
 use cw_storage_plus::Bound;
 use cw_utils::Expiration;
+use migration::migrate_version;
 use sha2::Digest;
 use std::convert::TryInto;
 
@@ -23,6 +25,10 @@ use crate::state::{
     STAGE_EXPIRATION, STAGE_METADATA, STAGE_START,
 };
 
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:ow20-merkle-airdrop";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
@@ -30,8 +36,7 @@ pub fn instantiate(
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> StdResult<Response> {
-    // TODO
-    // set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     let owner = msg.owner.unwrap_or(info.sender);
 
     let config = Config {
@@ -503,6 +508,12 @@ pub fn query_claim_key_count(deps: Deps) -> StdResult<ClaimKeyCountResponse> {
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
-    Ok(Response::default())
+pub fn migrate(deps: DepsMut, env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    Ok(migrate_version(
+        deps,
+        &env,
+        CONTRACT_NAME,
+        CONTRACT_VERSION,
+        &[],
+    )?)
 }
@@ -6,8 +6,9 @@ use cosmwasm_std::{
     StdError, StdResult, Uint128,
 };
 
-use cw2::{get_contract_version, set_contract_version};
+use cw2::set_contract_version;
 use cw20::{BalanceResponse, Cw20Coin, Cw20ReceiveMsg, MinterResponse, TokenInfoResponse};
+use migration::{migrate_version, MigrationStep};
 
 use crate::allowances::{
     handle_burn_from, handle_decrease_allowance, handle_increase_allowance, handle_send_from,
@@ -393,30 +394,20 @@ pub fn query_minter(deps: Deps) -> StdResult<Option<MinterResponse>> {
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
-    // let old_version = get_contract_version(deps.storage)?;
-    // if old_version.contract != CONTRACT_NAME {
-    //     return Err(StdError::generic_err(format!(
-    //         "This is {}, cannot migrate from {}",
-    //         CONTRACT_NAME, old_version.contract
-    //     )));
-    // }
-    // // note: v0.1.0 were not auto-generated and started with v0.
-    // // more recent versions do not have the v prefix
-    // if old_version.version.starts_with("v0.1.") {
-    //     migrate_v01_to_v02(deps.storage)?;
-    // } else if old_version.version.starts_with("0.2") {
-    //     // no migration between 0.2 and 0.3, correct?
-    // } else {
-    //     return Err(StdError::generic_err(format!(
-    //         "Unknown version {}",
-    //         old_version.version
-    //     )));
-    // }
-
-    // once we have "migrated", set the new version and return success
-    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
-    Ok(Response::default())
+pub fn migrate(deps: DepsMut, env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+    // note: v0.1.0 were not auto-generated and started with v0.
+    // more recent versions do not have the v prefix
+    migrate_version(deps, &env, CONTRACT_NAME, CONTRACT_VERSION, MIGRATIONS)
+}
+
+const MIGRATIONS: &[MigrationStep] = &[MigrationStep {
+    version: "0.2.0",
+    name: "allowances_v01_to_v02",
+    run: allowances_v01_to_v02,
+}];
+
+fn allowances_v01_to_v02(deps: DepsMut, _env: &Env) -> StdResult<()> {
+    migrate_v01_to_v02(deps.storage)
 }
 
 #[cfg(test)]
@@ -426,7 +417,7 @@ mod tests {
     };
     use cosmwasm_std::{coins, from_json, Api, CosmosMsg, Order, StdError, Timestamp, WasmMsg};
 
-    use cw2::ContractVersion;
+    use cw2::{get_contract_version, ContractVersion};
     use cw20::{AllowanceResponse, Expiration};
 
     use crate::migrations::generate_v01_test_data;
@@ -1040,99 +1031,101 @@ mod tests {
         );
     }
 
-    // #[test]
-    // fn migrate_from_v01() {
-    //     let mut deps = mock_dependencies();
-
-    //     generate_v01_test_data(&mut deps.storage, &deps.api).unwrap();
-    //     // make sure this really is 0.1.0
-    //     assert_eq!(
-    //         get_contract_version(&mut deps.storage).unwrap(),
-    //         ContractVersion {
-    //             contract: CONTRACT_NAME.to_string(),
-    //             version: "v0.1.0".to_string(),
-    //         }
-    //     );
-
-    //     // run the migration
-    //     let info = mock_info("admin", &[]);
-    //     let env = mock_env();
-    //     migrate(deps.as_mut(), env, MigrateMsg {}).unwrap();
-
-    //     // make sure the version is updated
-    //     assert_eq!(
-    //         get_contract_version(&mut deps.storage).unwrap(),
-    //         ContractVersion {
-    //             contract: CONTRACT_NAME.to_string(),
-    //             version: CONTRACT_VERSION.to_string(),
-    //         }
-    //     );
-
-    //     // check all the data (against the spec in generate_v01_test_data)
-    //     let info = token_info_read(&mut deps.storage).load().unwrap();
-    //     assert_eq!(
-    //         info,
-    //         TokenInfo {
-    //             name: "Sample Coin".to_string(),
-    //             symbol: "SAMP".to_string(),
-    //             decimals: 2,
-    //             total_supply: Uint128::from(777777u128),
-    //             mint: None,
-    //         }
-    //     );
-
-    //     // 2 users
-    //     let user1 = deps.api.addr_canonicalize("user1").unwrap();
-    //     let user2 = deps.api.addr_canonicalize("user2").unwrap();
-
-    //     let bal = balances_read(&mut deps.storage);
-    //     assert_eq!(2, bal.range(None, None, Order::Descending).count());
-    //     assert_eq!(
-    //         bal.load(user1.as_slice()).unwrap(),
-    //         Uint128::from(123456u128)
-    //     );
-    //     assert_eq!(
-    //         bal.load(user2.as_slice()).unwrap(),
-    //         Uint128::from(654321u128)
-    //     );
-
-    //     let spender1 = deps.api.addr_canonicalize("spender1").unwrap();
-    //     let spender2 = deps.api.addr_canonicalize("spender2").unwrap();
-
-    //     let num_allows = allowances_read(&mut deps.storage, &user1)
-    //         .range(None, None, Order::Ascending)
-    //         .count();
-    //     assert_eq!(num_allows, 1);
-    //     let allow = allowances_read(&mut deps.storage, &user1)
-    //         .load(spender1.as_slice())
-    //         .unwrap();
-    //     let expect = AllowanceResponse {
-    //         allowance: Uint128::from(5000u128),
-    //         expires: Expiration::AtHeight(5000),
-    //     };
-    //     assert_eq!(allow, expect);
-
-    //     let num_allows = allowances_read(&mut deps.storage, &user2)
-    //         .range(None, None, Order::Ascending)
-    //         .count();
-    //     assert_eq!(num_allows, 2);
-    //     let allow = allowances_read(&mut deps.storage, &user2)
-    //         .load(spender1.as_slice())
-    //         .unwrap();
-    //     let expect = AllowanceResponse {
-    //         allowance: Uint128::from(15000u128),
-    //         expires: Expiration::AtTime(Timestamp::from_seconds(1598647517)),
-    //     };
-    //     assert_eq!(allow, expect);
-    //     let allow = allowances_read(&mut deps.storage, &user2)
-    //         .load(spender2.as_slice())
-    //         .unwrap();
-    //     let expect = AllowanceResponse {
-    //         allowance: Uint128::from(77777u128),
-    //         expires: Expiration::Never {},
-    //     };
-    //     assert_eq!(allow, expect);
-    // }
+    #[test]
+    fn migrate_from_v01() {
+        let mut deps = mock_dependencies();
+
+        generate_v01_test_data(&mut deps.storage, &deps.api).unwrap();
+        // make sure this really is 0.1.0
+        assert_eq!(
+            get_contract_version(&deps.storage).unwrap(),
+            ContractVersion {
+                contract: CONTRACT_NAME.to_string(),
+                version: "v0.1.0".to_string(),
+            }
+        );
+
+        // run the migration
+        let res = migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+        assert!(res
+            .attributes
+            .contains(&attr("migration_step", "0.2.0:allowances_v01_to_v02")));
+
+        // make sure the version is updated
+        assert_eq!(
+            get_contract_version(&deps.storage).unwrap(),
+            ContractVersion {
+                contract: CONTRACT_NAME.to_string(),
+                version: CONTRACT_VERSION.to_string(),
+            }
+        );
+
+        // check all the data (against the spec in generate_v01_test_data)
+        let info = token_info_read(&deps.storage).load().unwrap();
+        assert_eq!(
+            info,
+            TokenInfo {
+                name: "Sample Coin".to_string(),
+                symbol: "SAMP".to_string(),
+                decimals: 2,
+                total_supply: Uint128::from(777777u128),
+                mint: None,
+            }
+        );
+
+        // 2 users
+        let user1 = deps.api.addr_canonicalize("user1").unwrap();
+        let user2 = deps.api.addr_canonicalize("user2").unwrap();
+
+        let bal = balances_read(&deps.storage);
+        assert_eq!(2, bal.range(None, None, Order::Descending).count());
+        assert_eq!(
+            bal.load(user1.as_slice()).unwrap(),
+            Uint128::from(123456u128)
+        );
+        assert_eq!(
+            bal.load(user2.as_slice()).unwrap(),
+            Uint128::from(654321u128)
+        );
+
+        let spender1 = deps.api.addr_canonicalize("spender1").unwrap();
+        let spender2 = deps.api.addr_canonicalize("spender2").unwrap();
+
+        let num_allows = allowances_read(&deps.storage, &user1)
+            .range(None, None, Order::Ascending)
+            .count();
+        assert_eq!(num_allows, 1);
+        let allow = allowances_read(&deps.storage, &user1)
+            .load(spender1.as_slice())
+            .unwrap();
+        let expect = AllowanceResponse {
+            allowance: Uint128::from(5000u128),
+            expires: Expiration::AtHeight(5000),
+        };
+        assert_eq!(allow, expect);
+
+        let num_allows = allowances_read(&deps.storage, &user2)
+            .range(None, None, Order::Ascending)
+            .count();
+        assert_eq!(num_allows, 2);
+        let allow = allowances_read(&deps.storage, &user2)
+            .load(spender1.as_slice())
+            .unwrap();
+        let expect = AllowanceResponse {
+            allowance: Uint128::from(15000u128),
+            expires: Expiration::AtTime(Timestamp::from_seconds(1598647517)),
+        };
+        assert_eq!(allow, expect);
+        let allow = allowances_read(&deps.storage, &user2)
+            .load(spender2.as_slice())
+            .unwrap();
+        let expect = AllowanceResponse {
+            allowance: Uint128::from(77777u128),
+            expires: Expiration::Never {},
+        };
+        assert_eq!(allow, expect);
+    }
+
     #[test]
     fn change_minter_test() {
         let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
@@ -5,18 +5,24 @@ use std::ops::Sub;
 
 use crate::error::ContractError;
 use crate::msg::{
-    ExecuteMsg, InstantiateMsg, QueryMsg, QueryRoundResponse, QuerySingleRoundResponse,
+    ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, QueryRoundResponse, QuerySingleRoundResponse,
 };
 use crate::state::{config, config_read, Member, RoundInfo, State, MAPPED_COUNT};
 use cosmwasm_std::{
     attr, to_json_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Order, Response, StdResult,
 };
+use cw2::set_contract_version;
 use cw_storage_plus::Bound;
+use migration::migrate_version;
 
 const DEFAULT_LIMIT: u8 = 10;
 const MAX_LIMIT: u8 = 30;
 const DEFAULT_ROUND_JUMP: u64 = 300;
 
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:vrf_health_check";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
 // Note, you can use StdResult in some functions where you do not
 // make use of the custom errors
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -26,6 +32,7 @@ pub fn instantiate(
     info: MessageInfo,
     init: InstantiateMsg,
 ) -> StdResult<Response> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     let state = State {
         owner: info.sender.clone(),
         round_jump: DEFAULT_ROUND_JUMP,
@@ -69,6 +76,11 @@ pub fn execute(
     }
 }
 
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+    migrate_version(deps, &env, CONTRACT_NAME, CONTRACT_VERSION, &[])
+}
+
 pub fn change_state(
     deps: DepsMut,
     info: MessageInfo,
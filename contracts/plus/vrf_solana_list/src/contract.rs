@@ -5,15 +5,21 @@ use cosmwasm_std::{
     attr, from_json, to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Order, Response,
     StdResult, Storage,
 };
+use cw2::set_contract_version;
+use migration::migrate_version;
 
 use crate::errors::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg, Member, MemberMsg, QueryMsg};
+use crate::msg::{ExecuteMsg, InstantiateMsg, Member, MemberMsg, MigrateMsg, QueryMsg};
 use crate::state::{clear_store, members_storage, members_storage_read, owner, owner_read, Owner};
 
 // settings for pagination
 const MAX_LIMIT: u8 = 30;
 const DEFAULT_LIMIT: u8 = 5;
 
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:vrf_solana_list";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
@@ -21,6 +27,7 @@ pub fn instantiate(
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     // update owner
     owner(deps.storage).save(&Owner {
         owner: info.sender.to_string(),
@@ -58,6 +65,17 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> Result<Binary, ContractErr
     Ok(response)
 }
 
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    Ok(migrate_version(
+        deps,
+        &env,
+        CONTRACT_NAME,
+        CONTRACT_VERSION,
+        &[],
+    )?)
+}
+
 fn store_members(storage: &mut dyn Storage, members: Vec<MemberMsg>, clear: bool) -> StdResult<()> {
     // store all members by their addresses
 
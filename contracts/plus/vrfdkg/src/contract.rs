@@ -8,6 +8,8 @@ use cosmwasm_std::{
     attr, coins, to_json_binary, BankMsg, Binary, Coin, CosmosMsg, Deps, DepsMut, Env, MessageInfo,
     Order, Response, StdError, StdResult, Storage,
 };
+use cw2::set_contract_version;
+use migration::migrate_version;
 
 use blsdkg::{
     derive_randomness, hash_g2, PublicKey, PublicKeySet, PublicKeyShare, Signature, SignatureShare,
@@ -30,6 +32,10 @@ use cosmwasm_crypto::secp256k1_verify;
 const MAX_LIMIT: u8 = 30;
 const DEFAULT_LIMIT: u8 = 5;
 
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:vrfdkg";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
@@ -37,6 +43,7 @@ pub fn instantiate(
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     let total = msg.members.len() as u16;
     if msg.threshold == 0 || msg.threshold > total {
         return Err(ContractError::InvalidThreshold {});
@@ -719,6 +726,12 @@ pub fn to_hex_string(bytes: Vec<u8>) -> String {
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
-    Ok(Response::default())
+pub fn migrate(deps: DepsMut, env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    Ok(migrate_version(
+        deps,
+        &env,
+        CONTRACT_NAME,
+        CONTRACT_VERSION,
+        &[],
+    )?)
 }
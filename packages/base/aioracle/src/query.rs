@@ -1,66 +1,87 @@
+use cosmwasm_schema::QueryResponses;
 use cosmwasm_std::{Addr, Binary, Empty};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
 use crate::state::AiRequest;
+use crate::{AiOracleProviderContract, AiOracleTestCaseContract};
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, QueryResponses)]
 #[serde(rename_all = "snake_case")]
 pub enum AiOracleQueryMsg {
+    #[returns(Vec<Addr>)]
     GetDataSources {},
+    #[returns(Vec<Addr>)]
     GetTestCases {},
-    GetDataSourcesRequest {
-        request_id: u64,
-    },
-    GetTestCasesRequest {
-        request_id: u64,
-    },
+    #[returns(Vec<AiOracleProviderContract>)]
+    GetDataSourcesRequest { request_id: u64 },
+    #[returns(Vec<AiOracleTestCaseContract>)]
+    GetTestCasesRequest { request_id: u64 },
+    #[returns(u8)]
     GetThreshold {},
-    GetRequest {
-        request_id: u64,
-    },
+    #[returns(AiRequest)]
+    GetRequest { request_id: u64 },
+    #[returns(AiRequestsResponse)]
     GetRequests {
         offset: Option<u64>,
         limit: Option<u8>,
         order: Option<u8>,
     },
-    GetMinFees {
-        validators: Vec<Addr>,
-    },
+    #[returns(u64)]
+    GetMinFees { validators: Vec<Addr> },
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, QueryResponses)]
 #[serde(rename_all = "snake_case")]
 pub enum AiOracleStorageQuery {
+    #[returns(AiRequestsResponse)]
     GetAiRequests(PagingOptions),
+    #[returns(AiRequestsResponse)]
     GetAiRequestsByStatus {
         status: bool,
         options: PagingOptions,
     },
-    GetAiRequestsByReportsCount {
-        count: u64,
-        options: PagingOptions,
-    },
+    #[returns(AiRequestsResponse)]
+    GetAiRequestsByReportsCount { count: u64, options: PagingOptions },
+    #[returns(AiRequestsResponse)]
     GetAiRequestsByDataSources {
         data_sources: Binary,
         options: PagingOptions,
     },
+    #[returns(AiRequestsResponse)]
     GetAiRequestsByTestCases {
         test_cases: Binary,
         options: PagingOptions,
     },
+    #[returns(AiRequestsResponse)]
     GetAiRequestsByImplementations {
         implementation: Addr,
         options: PagingOptions,
     },
-    GetAiRequest {
-        request_id: u64,
-    },
+    #[returns(AiRequest)]
+    GetAiRequest { request_id: u64 },
+    #[returns(Vec<ServiceFeesResponse>)]
     GetListServiceFees(PagingFeesOptions),
+    #[returns(ServiceFeesResponse)]
     GetServiceFees(String),
 }
 
+/// schema of the responses of the wrapped ai oracle storage queries
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(untagged)]
+pub enum AiOracleStorageQueryResponse {
+    GetAiRequests(AiRequestsResponse),
+    GetAiRequestsByStatus(AiRequestsResponse),
+    GetAiRequestsByReportsCount(AiRequestsResponse),
+    GetAiRequestsByDataSources(AiRequestsResponse),
+    GetAiRequestsByTestCases(AiRequestsResponse),
+    GetAiRequestsByImplementations(AiRequestsResponse),
+    GetAiRequest(Box<AiRequest>),
+    GetListServiceFees(Vec<ServiceFeesResponse>),
+    GetServiceFees(ServiceFeesResponse),
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct AiRequestsResponse {
     pub items: Vec<AiRequest>,
@@ -74,10 +95,12 @@ pub struct ServiceFeesResponse {
 }
 
 // for query other contract
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, QueryResponses)]
 #[serde(rename_all = "snake_case")]
 pub enum DataSourceQueryMsg {
+    #[returns(String)]
     Get { input: String },
+    #[returns(u64)]
     GetFees {},
 }
 
@@ -95,20 +118,31 @@ pub struct PagingFeesOptions {
     pub order: Option<u8>,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, QueryResponses)]
 #[serde(rename_all = "snake_case")]
 pub enum StorageQueryMsg {
-    // GetOfferings returns a list of all offerings
+    /// the response of the storage, passed through as is
+    #[returns(Binary)]
     QueryStorage { name: String, msg: Binary },
+    #[returns(Addr)]
     QueryStorageAddr { name: String },
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, QueryResponses)]
 #[serde(rename_all = "snake_case")]
 pub enum AiOracleHubQueryMsg {
+    #[returns(StorageQueryResponse)]
     Storage(StorageQueryMsg),
 }
 
+/// schema of the responses of the wrapped storage queries
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(untagged)]
+pub enum StorageQueryResponse {
+    Addr(Addr),
+    Binary(Binary),
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ProxyQueryMsg<T = Empty>
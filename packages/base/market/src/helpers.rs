@@ -8,8 +8,8 @@ use schemars::JsonSchema;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
 use crate::{
-    MarketHubExecuteMsg, MarketHubQueryMsg, StorageExecuteMsg, StorageQueryMsg, TokenIdInfo,
-    TokenInfo,
+    MarketHubExecuteMsg, MarketHubQueryMsg, StorageExecuteMsg, StorageQueryMsg, StorageUpdate,
+    TokenIdInfo, TokenInfo,
 };
 
 #[cw_serde]
@@ -55,12 +55,13 @@ impl Registry {
         self.storages.iter().find(|x| x.0.eq(item_key))
     }
 
-    pub fn add_storage(&mut self, item_key: &str, addr: Addr) {
+    /// returns the replaced address if the item key was already registered
+    pub fn add_storage(&mut self, item_key: &str, addr: Addr) -> Option<Addr> {
         if let Some(old) = self.storages.iter_mut().find(|x| x.0.eq(item_key)) {
-            old.1 = addr;
-        } else {
-            self.storages.push((item_key.to_string(), addr));
+            return Some(std::mem::replace(&mut old.1, addr));
         }
+        self.storages.push((item_key.to_string(), addr));
+        None
     }
 
     /// returns removed item
@@ -106,9 +107,14 @@ pub fn query_proxy_generic<T: DeserializeOwned>(
 }
 
 pub fn query_proxy(deps: Deps, addr: Addr, msg: Binary) -> StdResult<Binary> {
+    query_proxy_raw(&deps.querier, addr, msg)
+}
+
+/// like query_proxy, but only needs the querier so it can be used outside of the contract entry points
+pub fn query_proxy_raw(querier: &QuerierWrapper, addr: Addr, msg: Binary) -> StdResult<Binary> {
     let raw = get_raw_request(addr, msg)?;
 
-    match deps.querier.raw_query(&raw) {
+    match querier.raw_query(&raw) {
         SystemResult::Err(system_err) => Err(StdError::generic_err(format!(
             "Querier system error: {}",
             system_err
@@ -171,6 +177,13 @@ impl MarketHubContract {
         self.encode_msg(msg)
     }
 
+    /// updates several storages in one message, the hub dispatches them atomically
+    pub fn batch_update_storage(&self, updates: Vec<StorageUpdate>) -> StdResult<CosmosMsg> {
+        let msg =
+            MarketHubExecuteMsg::Storage(StorageExecuteMsg::BatchUpdateStorageData { updates });
+        self.encode_msg(msg)
+    }
+
     fn encode_smart_query(&self, msg: MarketHubQueryMsg) -> StdResult<QueryRequest<Empty>> {
         Ok(WasmQuery::Smart {
             contract_addr: self.addr().to_string(),
@@ -263,9 +276,15 @@ mod tests {
             ],
             implementations: vec![implementation],
         };
-        registry.add_storage("offerings", offerings);
+        assert_eq!(registry.add_storage("offerings", offerings), None);
         let found = registry.get_storage("offerings").unwrap();
         assert_eq!(found.0, "offerings");
+
+        // replacing an item returns the old address
+        let old = registry.add_storage("auctions", Addr::unchecked("auctions_v2"));
+        assert_eq!(old, Some(Addr::unchecked("auctions")));
+        let found = registry.get_storage("auctions").unwrap();
+        assert_eq!(found.1, Addr::unchecked("auctions_v2"));
     }
 
     #[test]
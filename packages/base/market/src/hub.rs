@@ -0,0 +1,142 @@
+use cosmwasm_std::{to_json_binary, Addr, Binary, CosmosMsg, QuerierWrapper, StdResult};
+use schemars::JsonSchema;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{query_proxy_raw, MarketHubContract, MarketHubQueryMsg, StorageQueryMsg};
+
+// canonical names of the storages registered in the hub
+pub const OFFERING_STORAGE: &str = "offering_v1.1";
+pub const OFFERING_STORAGE_TEMP: &str = "offering_temp";
+pub const AUCTION_STORAGE: &str = "auction";
+pub const AUCTION_EXTEND_STORAGE: &str = "auction_extend";
+pub const PAYMENT_721_STORAGE: &str = "market_721_payment_storage";
+pub const PAYMENT_1155_STORAGE: &str = "market_1155_payment_storage";
+pub const AI_ROYALTY_STORAGE: &str = "ai_royalty";
+pub const AI_ROYALTY_STORAGE_TEMP: &str = "ai_royalty_temp";
+pub const FIRST_LV_ROYALTY_STORAGE: &str = "first_lv_royalty";
+pub const WHITELIST_STORAGE: &str = "whitelist_storage";
+pub const REJECTED_STORAGE: &str = "rejected_storage";
+pub const DATAHUB_STORAGE: &str = "datahub_storage";
+pub const STORAGE_1155: &str = "1155_storage";
+
+pub const STORAGE_NAMES: &[&str] = &[
+    OFFERING_STORAGE,
+    OFFERING_STORAGE_TEMP,
+    AUCTION_STORAGE,
+    AUCTION_EXTEND_STORAGE,
+    PAYMENT_721_STORAGE,
+    PAYMENT_1155_STORAGE,
+    AI_ROYALTY_STORAGE,
+    AI_ROYALTY_STORAGE_TEMP,
+    FIRST_LV_ROYALTY_STORAGE,
+    WHITELIST_STORAGE,
+    REJECTED_STORAGE,
+    DATAHUB_STORAGE,
+    STORAGE_1155,
+];
+
+/// returns true if the name is one of the canonical storage names
+pub fn is_storage_name(name: &str) -> bool {
+    STORAGE_NAMES.contains(&name)
+}
+
+/// the storage contracts expect their messages wrapped in one of these variants,
+/// offering and auction storages use their own, all the others use msg
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum StorageMsg<T> {
+    Msg(T),
+    Offering(T),
+    Auction(T),
+}
+
+/// a storage update as the hub dispatches it: the storage name and its wrapped message
+pub type StorageUpdate = (String, Binary);
+
+/// wraps the message for the storage without sending it, so it can be batched with other updates
+pub fn storage_update<T: Serialize>(name: &str, msg: &StorageMsg<T>) -> StdResult<StorageUpdate> {
+    Ok((name.to_string(), to_json_binary(msg)?))
+}
+
+impl MarketHubContract {
+    /// returns the address the hub has registered for the storage
+    pub fn storage_addr(&self, querier: &QuerierWrapper, name: &str) -> StdResult<Addr> {
+        querier.query_wasm_smart(
+            self.addr(),
+            &MarketHubQueryMsg::Storage(StorageQueryMsg::QueryStorageAddr {
+                name: name.to_string(),
+            }),
+        )
+    }
+
+    /// wraps the message for the storage and routes the update through the hub
+    pub fn execute_storage<T: Serialize>(
+        &self,
+        name: &str,
+        msg: &StorageMsg<T>,
+    ) -> StdResult<CosmosMsg> {
+        let (name, msg) = storage_update(name, msg)?;
+        self.update_storage(name, msg)
+    }
+
+    /// queries the storage directly once its address is resolved through the hub
+    pub fn query_storage_msg<T: Serialize, R: DeserializeOwned>(
+        &self,
+        querier: &QuerierWrapper,
+        name: &str,
+        msg: &StorageMsg<T>,
+    ) -> StdResult<R> {
+        querier.query_wasm_smart(self.storage_addr(querier, name)?, msg)
+    }
+
+    /// same as query_storage_msg but returns the raw response, used to forward user queries
+    pub fn query_storage_binary<T: Serialize>(
+        &self,
+        querier: &QuerierWrapper,
+        name: &str,
+        msg: &StorageMsg<T>,
+    ) -> StdResult<Binary> {
+        query_proxy_raw(
+            querier,
+            self.storage_addr(querier, name)?,
+            to_json_binary(msg)?,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::{from_json, Addr, WasmMsg};
+
+    use crate::{MarketHubExecuteMsg, StorageExecuteMsg};
+
+    #[test]
+    fn execute_storage_wraps_msg() {
+        let hub = MarketHubContract::new(Addr::unchecked("hub"));
+        let msg = hub
+            .execute_storage(AUCTION_STORAGE, &StorageMsg::Auction("remove"))
+            .unwrap();
+        let CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr, msg, ..
+        }) = msg
+        else {
+            panic!("expected a wasm execute message");
+        };
+        assert_eq!(contract_addr, "hub");
+        match from_json(&msg).unwrap() {
+            MarketHubExecuteMsg::Storage(StorageExecuteMsg::UpdateStorageData { name, msg }) => {
+                assert_eq!(name, AUCTION_STORAGE);
+                assert_eq!(msg, Binary::from(br#"{"auction":"remove"}"#));
+            }
+            other => panic!("unexpected message {:?}", other),
+        }
+    }
+
+    #[test]
+    fn storage_names() {
+        assert!(is_storage_name(OFFERING_STORAGE));
+        assert!(is_storage_name(PAYMENT_1155_STORAGE));
+        assert!(!is_storage_name("creator"));
+    }
+}
@@ -1,9 +1,15 @@
 mod helpers;
+mod hub;
 mod msg;
+mod pagination;
 mod query;
+mod reserved;
 
 pub use crate::helpers::*;
+pub use crate::hub::*;
 pub use crate::msg::*;
+pub use crate::pagination::*;
 pub use crate::query::*;
+pub use crate::reserved::*;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod mock;
@@ -22,6 +22,16 @@ pub enum AssetInfo {
     NativeToken { denom: String },
 }
 
+impl AssetInfo {
+    /// storage key of the asset, tagged so a denom never collides with a cw20 address
+    pub fn as_key(&self) -> Vec<u8> {
+        match self {
+            AssetInfo::Token { contract_addr } => [b"cw20:", contract_addr.as_bytes()].concat(),
+            AssetInfo::NativeToken { denom } => [b"native:", denom.as_bytes()].concat(),
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum Funds {
@@ -34,6 +44,8 @@ pub enum Funds {
 pub enum StorageExecuteMsg {
     // GetOfferings returns a list of all offerings
     UpdateStorageData { name: String, msg: Binary },
+    // all the storages and permissions are validated before any update is dispatched
+    BatchUpdateStorageData { updates: Vec<(String, Binary)> },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -0,0 +1,174 @@
+use cosmwasm_std::{Binary, Order, Record, StdResult};
+use cw_storage_plus::Bound;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+// settings for pagination of the storage list queries
+pub const MAX_LIMIT: u32 = 100;
+pub const DEFAULT_LIMIT: u32 = 20;
+
+/// a page of a list query, items are ordered by their primary key
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PageResponse<T> {
+    pub items: Vec<T>,
+    /// pass it as start_after to get the next page, none on the last page
+    #[serde(default)]
+    pub next_cursor: Option<Binary>,
+    /// number of items of the whole list, only counted when with_total is set
+    #[serde(default)]
+    pub total: Option<u64>,
+}
+
+/// range params of a list query, shared by the primary map and the secondary indexes
+/// since both are ranged over the primary key
+#[derive(Clone)]
+pub struct Pagination {
+    pub limit: usize,
+    pub min: Option<Bound>,
+    pub max: Option<Bound>,
+    pub order: Order,
+}
+
+impl Pagination {
+    /// order 1 is ascending and 2 is descending, anything else falls back to the default order.
+    /// start_after is the cursor of the previous page, the legacy offset is the primary key
+    /// to start after and is only used without a cursor
+    pub fn new(
+        start_after: Option<Binary>,
+        offset: Option<Vec<u8>>,
+        limit: Option<u32>,
+        order: Option<u8>,
+        default_order: Order,
+    ) -> Self {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let order = match order {
+            Some(1) => Order::Ascending,
+            Some(2) => Order::Descending,
+            _ => default_order,
+        };
+        let bound = start_after
+            .map(|cursor| cursor.to_vec())
+            .or(offset)
+            .map(Bound::Exclusive);
+        let (min, max) = match order {
+            Order::Ascending => (bound, None),
+            Order::Descending => (None, bound),
+        };
+        Self {
+            limit,
+            min,
+            max,
+            order,
+        }
+    }
+
+    /// bounds and order to range the primary map or an index with
+    pub fn bounds(&self) -> (Option<Bound>, Option<Bound>, Order) {
+        (self.min.clone(), self.max.clone(), self.order)
+    }
+
+    /// takes one page of the records, the next cursor is the key of the last item
+    /// and is only returned when there are records left
+    pub fn page<T, U>(
+        &self,
+        records: impl Iterator<Item = StdResult<Record<T>>>,
+        parse: impl Fn(Record<T>) -> StdResult<U>,
+    ) -> StdResult<(Vec<U>, Option<Binary>)> {
+        let mut items = vec![];
+        let mut last_key = None;
+        for record in records.take(self.limit + 1) {
+            let (key, value) = record?;
+            if items.len() == self.limit {
+                return Ok((items, last_key.map(Binary::from)));
+            }
+            last_key = Some(key.clone());
+            items.push(parse((key, value))?);
+        }
+        Ok((items, None))
+    }
+
+    /// same as page but wraps the items in a page response
+    pub fn page_response<T, U>(
+        &self,
+        records: impl Iterator<Item = StdResult<Record<T>>>,
+        parse: impl Fn(Record<T>) -> StdResult<U>,
+        total: Option<u64>,
+    ) -> StdResult<PageResponse<U>> {
+        let (items, next_cursor) = self.page(records, parse)?;
+        Ok(PageResponse {
+            items,
+            next_cursor,
+            total,
+        })
+    }
+}
+
+/// counting walks the whole range, so it is only done when the caller asks for it
+pub fn count_total(with_total: Option<bool>, count: impl FnOnce() -> usize) -> Option<u64> {
+    if with_total.unwrap_or(false) {
+        Some(count() as u64)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn records(keys: &[u64]) -> impl Iterator<Item = StdResult<Record<u64>>> + '_ {
+        keys.iter().map(|k| Ok((k.to_be_bytes().to_vec(), *k)))
+    }
+
+    #[test]
+    fn bounds_follow_order() {
+        let cursor = Binary::from(5u64.to_be_bytes().to_vec());
+        let asc = Pagination::new(Some(cursor.clone()), None, None, Some(1), Order::Descending);
+        assert!(matches!(asc.order, Order::Ascending));
+        assert!(matches!(asc.min, Some(Bound::Exclusive(ref k)) if k == cursor.as_slice()));
+        assert!(asc.max.is_none());
+
+        let desc = Pagination::new(None, Some(cursor.to_vec()), None, None, Order::Descending);
+        assert!(desc.min.is_none());
+        assert!(matches!(desc.max, Some(Bound::Exclusive(ref k)) if k == cursor.as_slice()));
+        assert_eq!(desc.limit, DEFAULT_LIMIT as usize);
+    }
+
+    #[test]
+    fn cursor_takes_precedence_over_offset() {
+        let page = Pagination::new(
+            Some(Binary::from(vec![2])),
+            Some(vec![1]),
+            Some(1000),
+            None,
+            Order::Ascending,
+        );
+        assert!(matches!(page.min, Some(Bound::Exclusive(ref k)) if k == &[2]));
+        assert_eq!(page.limit, MAX_LIMIT as usize);
+    }
+
+    #[test]
+    fn next_cursor_only_when_records_left() {
+        let pagination = Pagination::new(None, None, Some(2), None, Order::Ascending);
+        let (items, next) = pagination
+            .page(records(&[1, 2, 3]), |(_, v)| Ok(v))
+            .unwrap();
+        assert_eq!(items, vec![1, 2]);
+        assert_eq!(next, Some(Binary::from(2u64.to_be_bytes().to_vec())));
+
+        let (items, next) = pagination.page(records(&[3]), |(_, v)| Ok(v)).unwrap();
+        assert_eq!(items, vec![3]);
+        assert_eq!(next, None);
+
+        // a full last page has no cursor either
+        let (_, next) = pagination.page(records(&[1, 2]), |(_, v)| Ok(v)).unwrap();
+        assert_eq!(next, None);
+    }
+
+    #[test]
+    fn total_only_when_asked() {
+        assert_eq!(count_total(None, || 3), None);
+        assert_eq!(count_total(Some(false), || 3), None);
+        assert_eq!(count_total(Some(true), || 3), Some(3));
+    }
+}
@@ -1,17 +1,36 @@
-use cosmwasm_std::Binary;
+use cosmwasm_schema::QueryResponses;
+use cosmwasm_std::{Addr, Binary};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, QueryResponses)]
 #[serde(rename_all = "snake_case")]
 pub enum StorageQueryMsg {
-    // GetOfferings returns a list of all offerings
+    /// the response of the storage, passed through as is
+    #[returns(Binary)]
     QueryStorage { name: String, msg: Binary },
+    #[returns(Addr)]
     QueryStorageAddr { name: String },
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, QueryResponses)]
 #[serde(rename_all = "snake_case")]
 pub enum MarketHubQueryMsg {
+    #[returns(StorageQueryResponse)]
     Storage(StorageQueryMsg),
 }
+
+/// schema of the responses of the wrapped storage queries
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(untagged)]
+pub enum StorageQueryResponse {
+    Addr(Addr),
+    Binary(Binary),
+}
+
+/// contract info of the storages that only keep their governance and creator
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StorageContractInfo {
+    pub governance: Addr,
+    pub creator: Addr,
+}
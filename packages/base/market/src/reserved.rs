@@ -0,0 +1,82 @@
+use cosmwasm_std::{Addr, Api, StdError};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ReservedError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("The nft is reserved for other buyers, got: {sender}")]
+    NotReservedBuyer { sender: String },
+
+    #[error("The reserved buyers cannot be empty, nobody could buy the nft")]
+    EmptyReservedBuyers {},
+}
+
+/// checks the addresses an offering or auction is reserved for when it is created
+pub fn validate_reserved_for(
+    api: &dyn Api,
+    reserved_for: Option<Vec<Addr>>,
+) -> Result<Option<Vec<Addr>>, ReservedError> {
+    let Some(reserved_for) = reserved_for else {
+        return Ok(None);
+    };
+    if reserved_for.is_empty() {
+        return Err(ReservedError::EmptyReservedBuyers {});
+    }
+    Ok(Some(
+        reserved_for
+            .iter()
+            .map(|addr| api.addr_validate(addr.as_str()))
+            .collect::<Result<_, _>>()?,
+    ))
+}
+
+/// a reserved offering or auction can only be bought or bid by one of its reserved addresses
+pub fn verify_reserved_buyer(
+    reserved_for: &Option<Vec<Addr>>,
+    sender: &Addr,
+) -> Result<(), ReservedError> {
+    if let Some(reserved_for) = reserved_for {
+        if !reserved_for.contains(sender) {
+            return Err(ReservedError::NotReservedBuyer {
+                sender: sender.to_string(),
+            });
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::MockApi;
+
+    #[test]
+    fn reserved_for_is_validated() {
+        let api = MockApi::default();
+        assert_eq!(validate_reserved_for(&api, None), Ok(None));
+        assert_eq!(
+            validate_reserved_for(&api, Some(vec![])),
+            Err(ReservedError::EmptyReservedBuyers {})
+        );
+        assert!(matches!(
+            validate_reserved_for(&api, Some(vec![Addr::unchecked("Buyer")])),
+            Err(ReservedError::Std(_))
+        ));
+        let buyers = Some(vec![Addr::unchecked("buyer")]);
+        assert_eq!(
+            validate_reserved_for(&api, buyers.clone()),
+            Ok(buyers.clone())
+        );
+
+        verify_reserved_buyer(&None, &Addr::unchecked("anyone")).unwrap();
+        verify_reserved_buyer(&buyers, &Addr::unchecked("buyer")).unwrap();
+        assert_eq!(
+            verify_reserved_buyer(&buyers, &Addr::unchecked("anyone")),
+            Err(ReservedError::NotReservedBuyer {
+                sender: "anyone".to_string()
+            })
+        );
+    }
+}
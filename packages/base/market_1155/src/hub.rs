@@ -0,0 +1,73 @@
+use cosmwasm_std::{Addr, Binary, CosmosMsg, QuerierWrapper, StdResult};
+use market::{MarketHubContract, StorageMsg, STORAGE_1155};
+use serde::de::DeserializeOwned;
+
+use crate::{MarketExecuteMsg, MarketQueryMsg, Offering};
+
+/// typed client of the 1155 offering storage, every update is routed through the hub
+pub trait Market1155Storage {
+    fn market_1155_msg(&self, msg: MarketExecuteMsg) -> StdResult<CosmosMsg>;
+
+    fn query_market_1155<R: DeserializeOwned>(
+        &self,
+        querier: &QuerierWrapper,
+        msg: MarketQueryMsg,
+    ) -> StdResult<R>;
+
+    fn query_market_1155_binary(
+        &self,
+        querier: &QuerierWrapper,
+        msg: MarketQueryMsg,
+    ) -> StdResult<Binary>;
+
+    fn update_offering(&self, offering: Offering) -> StdResult<CosmosMsg> {
+        self.market_1155_msg(MarketExecuteMsg::UpdateOffering { offering })
+    }
+
+    fn remove_offering(&self, id: u64) -> StdResult<CosmosMsg> {
+        self.market_1155_msg(MarketExecuteMsg::RemoveOffering { id })
+    }
+
+    fn get_offering(&self, querier: &QuerierWrapper, offering_id: u64) -> StdResult<Offering> {
+        self.query_market_1155(querier, MarketQueryMsg::GetOffering { offering_id })
+    }
+
+    fn get_unique_offering(
+        &self,
+        querier: &QuerierWrapper,
+        contract: Addr,
+        token_id: &str,
+        seller: Addr,
+    ) -> StdResult<Offering> {
+        self.query_market_1155(
+            querier,
+            MarketQueryMsg::GetUniqueOffering {
+                contract,
+                token_id: token_id.to_string(),
+                seller,
+            },
+        )
+    }
+}
+
+impl Market1155Storage for MarketHubContract {
+    fn market_1155_msg(&self, msg: MarketExecuteMsg) -> StdResult<CosmosMsg> {
+        self.execute_storage(STORAGE_1155, &StorageMsg::Msg(msg))
+    }
+
+    fn query_market_1155<R: DeserializeOwned>(
+        &self,
+        querier: &QuerierWrapper,
+        msg: MarketQueryMsg,
+    ) -> StdResult<R> {
+        self.query_storage_msg(querier, STORAGE_1155, &StorageMsg::Msg(msg))
+    }
+
+    fn query_market_1155_binary(
+        &self,
+        querier: &QuerierWrapper,
+        msg: MarketQueryMsg,
+    ) -> StdResult<Binary> {
+        self.query_storage_binary(querier, STORAGE_1155, &StorageMsg::Msg(msg))
+    }
+}
@@ -9,6 +9,35 @@ use serde::{Deserialize, Serialize};
 pub enum Cw20HookMsg {
     BuyNft { offering_id: u64, amount: Uint128 },
     BidNft { auction_id: u64, per_price: Uint128 },
+    BidNftAmount(BidAmountMsg),
+    CreateBuyOrder(BuyOrderMsg),
+}
+
+/// sent along with the cw1155 tokens to the marketplace
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw1155HookMsg {
+    /// sell the received amount to a buy order at its per price
+    FillBuyOrder { order_id: u64 },
+}
+
+/// a bid for a part of a multi winner auction
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct BidAmountMsg {
+    pub auction_id: u64,
+    pub amount: Uint128,
+    pub per_price: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct BuyOrderMsg {
+    pub contract_addr: Addr,
+    /// initial token id, with the payment asset as extra data like when selling
+    pub token_id: String,
+    pub amount: Uint128,
+    pub per_price: Uint128,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -25,6 +54,11 @@ pub struct Offering {
     pub seller: Addr,
     pub per_price: Uint128,
     pub amount: Uint128,
+    /// only these addresses can buy the nft, anyone when None
+    pub reserved_for: Option<Vec<Addr>>,
+    /// the asset the price is paid in, None for offerings listed before it was recorded
+    #[serde(default)]
+    pub asset_info: Option<AssetInfo>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -35,6 +69,8 @@ pub struct MintMsg {
     pub creator_type: String,
     pub royalty: Option<u64>,
     pub mint: MintIntermediate,
+    /// royalty share weights of the minter followed by each co owner, equal shares when None
+    pub co_owner_weights: Option<Vec<u64>>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -1,43 +1,95 @@
-use cosmwasm_std::Addr;
+use cosmwasm_schema::QueryResponses;
+use cosmwasm_std::{Addr, Binary, Uint128};
+use market::{AssetInfo, PageResponse, StorageContractInfo};
 
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+use crate::Offering;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, QueryResponses)]
 #[serde(rename_all = "snake_case")]
 pub enum MarketQueryMsg {
     // GetOfferings returns a list of all offerings
+    #[returns(PageResponse<Offering>)]
     GetOfferings {
         offset: Option<u64>,
-        limit: Option<u8>,
+        start_after: Option<Binary>,
+        limit: Option<u32>,
         order: Option<u8>,
+        with_total: Option<bool>,
     },
+    #[returns(PageResponse<Offering>)]
     GetOfferingsBySeller {
         seller: Addr,
         offset: Option<u64>,
-        limit: Option<u8>,
+        start_after: Option<Binary>,
+        limit: Option<u32>,
         order: Option<u8>,
+        with_total: Option<bool>,
     },
+    #[returns(PageResponse<Offering>)]
     GetOfferingsByContract {
         contract: Addr,
         offset: Option<u64>,
-        limit: Option<u8>,
+        start_after: Option<Binary>,
+        limit: Option<u32>,
         order: Option<u8>,
+        with_total: Option<bool>,
     },
+    #[returns(PageResponse<Offering>)]
     GetOfferingsByContractTokenId {
         contract: Addr,
         token_id: String,
         offset: Option<u64>,
-        limit: Option<u8>,
+        start_after: Option<Binary>,
+        limit: Option<u32>,
         order: Option<u8>,
+        with_total: Option<bool>,
     },
-    GetOffering {
-        offering_id: u64,
+    /// cheapest offering of the token, optionally only those paid in the given asset
+    #[returns(Option<Offering>)]
+    GetFloorOffering {
+        contract: Addr,
+        token_id: String,
+        asset_info: Option<AssetInfo>,
     },
+    /// offerings of the token ordered by per price, ascending by default. both bounds are inclusive
+    #[returns(PageResponse<Offering>)]
+    GetOfferingsByPriceRange {
+        contract: Addr,
+        token_id: String,
+        min_price: Option<Uint128>,
+        max_price: Option<Uint128>,
+        asset_info: Option<AssetInfo>,
+        start_after: Option<Binary>,
+        limit: Option<u32>,
+        order: Option<u8>,
+        with_total: Option<bool>,
+    },
+    #[returns(Offering)]
+    GetOffering { offering_id: u64 },
+    #[returns(Offering)]
     GetUniqueOffering {
         contract: Addr,
         token_id: String,
         seller: Addr,
     },
+    #[returns(StorageContractInfo)]
     GetContractInfo {},
 }
+
+/// schema of the responses of the wrapped market queries
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(untagged)]
+pub enum MarketQueryResponse {
+    GetOfferings(PageResponse<Offering>),
+    GetOfferingsBySeller(PageResponse<Offering>),
+    GetOfferingsByContract(PageResponse<Offering>),
+    GetOfferingsByContractTokenId(PageResponse<Offering>),
+    GetFloorOffering(Option<Offering>),
+    GetOfferingsByPriceRange(PageResponse<Offering>),
+    GetOffering(Offering),
+    GetUniqueOffering(Offering),
+    GetContractInfo(StorageContractInfo),
+}
@@ -1,11 +1,15 @@
 use std::ops::{Mul, Sub};
 
 use cosmwasm_std::{
-    coins, to_json_binary, Addr, BankMsg, CosmosMsg, Response, StdError, Uint128, WasmMsg,
+    coins, to_json_binary, Addr, Api, BankMsg, CosmosMsg, Response, StdError, StdResult, Uint128,
+    WasmMsg,
 };
 use market::AssetInfo;
 
-use crate::{Event, RoyaltiesEvent, Royalty, RoyaltyEvent};
+use crate::{
+    Event, RoyaltiesEvent, Royalty, RoyaltyEvent, RoyaltyPayee, RoyaltyPayout, MAX_ROYALTY_PAYEES,
+    MAX_TOTAL_PAYEE_WEIGHT,
+};
 
 pub fn sanitize_royalty(royalty: u64, limit: u64, name: &str) -> Result<u64, StdError> {
     if royalty > limit {
@@ -16,6 +20,47 @@ pub fn sanitize_royalty(royalty: u64, limit: u64, name: &str) -> Result<u64, Std
     Ok(royalty)
 }
 
+/// the royalties of all creators of a token must stay within the limit, a creator can always lower its own royalty
+pub fn sanitize_total_royalty(
+    royalties: &[Royalty],
+    creator: &Addr,
+    royalty: u64,
+    limit: u64,
+) -> Result<u64, StdError> {
+    let mut total = royalty;
+    for item in royalties {
+        if item.creator.ne(creator) {
+            total = total.saturating_add(item.royalty);
+        } else if royalty <= item.royalty {
+            return Ok(royalty);
+        }
+    }
+    sanitize_royalty(total, limit, "total_royalty")?;
+    Ok(royalty)
+}
+
+/// the payees must be valid distinct addresses whose summed weight is positive and within the cap
+pub fn sanitize_payees(api: &dyn Api, payees: &[RoyaltyPayee]) -> StdResult<()> {
+    let total_weight = payees
+        .iter()
+        .try_fold(0u64, |total, payee| total.checked_add(payee.weight));
+    if payees.len() > MAX_ROYALTY_PAYEES
+        || !total_weight.is_some_and(|total| total > 0 && total <= MAX_TOTAL_PAYEE_WEIGHT)
+    {
+        return Err(StdError::generic_err("Invalid argument: payees"));
+    }
+    for (index, payee) in payees.iter().enumerate() {
+        api.addr_validate(payee.address.as_str())?;
+        if payees[..index]
+            .iter()
+            .any(|other| other.address.eq(&payee.address))
+        {
+            return Err(StdError::generic_err("Invalid argument: payees"));
+        }
+    }
+    Ok(())
+}
+
 fn add_royalties_event<'a>(
     nft_addr: &'a str,
     token_id: &'a str,
@@ -56,6 +101,105 @@ pub fn parse_transfer_msg(
     }
 }
 
+/// split the royalty amount among the payees by weight, the rounding dust goes to the creator
+pub fn split_royalty(royalty: &Royalty, amount: Uint128) -> Vec<(&Addr, Uint128)> {
+    let payees = match royalty.payees.as_ref() {
+        Some(payees) if payees.iter().any(|payee| payee.weight > 0) => payees,
+        _ => return vec![(&royalty.creator, amount)],
+    };
+    // summed in u128 so stored weights can never overflow
+    let total_weight: u128 = payees.iter().map(|payee| payee.weight as u128).sum();
+    let mut shares: Vec<(&Addr, Uint128)> = payees
+        .iter()
+        .map(|payee| {
+            (
+                &payee.address,
+                amount.multiply_ratio(payee.weight, total_weight),
+            )
+        })
+        .collect();
+    let dust = amount - shares.iter().map(|(_, share)| *share).sum::<Uint128>();
+    if !dust.is_zero() {
+        match shares
+            .iter_mut()
+            .find(|(address, _)| royalty.creator.eq(*address))
+        {
+            Some((_, share)) => *share += dust,
+            None => shares.push((&royalty.creator, dust)),
+        }
+    }
+    shares.retain(|(_, share)| !share.is_zero());
+    shares
+}
+
+/// the payouts of the royalties on a price, scaled down proportionally when they exceed the limit
+pub fn get_royalty_payouts(
+    royalties: &[Royalty],
+    price: &Uint128,
+    decimal_point: u64,
+    limit: Uint128,
+) -> Vec<RoyaltyPayout> {
+    // royalty = total price * royalty percentage
+    let amounts: Vec<Uint128> = royalties
+        .iter()
+        .map(|royalty| price.mul(Uint128::from(royalty.royalty)) / Uint128::from(decimal_point))
+        .collect();
+    // legacy tokens may have accumulated more royalties than the sale can pay
+    let total: Uint128 = amounts.iter().sum();
+    let mut payouts = vec![];
+    for (royalty, amount) in royalties.iter().zip(amounts) {
+        let amount = if total.gt(&limit) {
+            amount.multiply_ratio(limit, total)
+        } else {
+            amount
+        };
+        if amount.is_zero() {
+            continue;
+        }
+        for (payee, amount) in split_royalty(royalty, amount) {
+            payouts.push(RoyaltyPayout {
+                creator: payee.clone(),
+                creator_type: royalty.creator_type.clone(),
+                royalty: royalty.royalty,
+                amount,
+            })
+        }
+    }
+    payouts
+}
+
+/// deduct the royalties from the remaining amount and add them into the royalties event, the caller settles the payouts
+pub fn collect_royalties(
+    royalties: &[Royalty],
+    price: &Uint128,
+    decimal_point: u64,
+    remaining: &mut Uint128,
+    rsp: &mut Response,
+    denom: &str,
+) -> Result<Vec<RoyaltyPayout>, StdError> {
+    let payouts = get_royalty_payouts(royalties, price, decimal_point, *remaining);
+    let mut royalties_event: Vec<RoyaltyEvent> = vec![];
+    for payout in payouts.iter() {
+        *remaining = remaining.checked_sub(payout.amount)?;
+        royalties_event.push(RoyaltyEvent {
+            creator: payout.creator.as_str(),
+            royalty: payout.royalty,
+            amount: payout.amount,
+            denom,
+        })
+    }
+    // add royalties into the event response
+    if let Some(royalty) = royalties.first() {
+        add_royalties_event(
+            royalty.contract_addr.as_str(),
+            royalty.token_id.as_str(),
+            royalties_event.as_ref(),
+            rsp,
+        );
+    }
+    Ok(payouts)
+}
+
 pub fn pay_royalties(
     royalties: &[Royalty],
     price: &Uint128,
@@ -67,35 +211,13 @@ pub fn pay_royalties(
     denom: &str,
     asset_info: AssetInfo,
 ) -> Result<(), StdError> {
-    let mut royalties_event: Vec<RoyaltyEvent> = vec![];
-    let mut nft_addr: &str = "";
-    let mut token_id: &str = "";
-    for royalty in royalties {
-        if nft_addr.is_empty() && token_id.is_empty() {
-            nft_addr = royalty.contract_addr.as_str();
-            token_id = royalty.token_id.as_str();
-        }
-        // royalty = total price * royalty percentage
-        let creator_amount =
-            price.mul(Uint128::from(royalty.royalty)) / Uint128::from(decimal_point);
-        if creator_amount.gt(&Uint128::zero()) {
-            *remaining = remaining.checked_sub(creator_amount)?;
-            cosmos_msgs.push(parse_transfer_msg(
-                asset_info.clone(),
-                creator_amount,
-                contract_addr,
-                royalty.creator.clone(),
-            )?);
-            // only valid send msgs will be collected to put into royalties event
-            royalties_event.push(RoyaltyEvent {
-                creator: royalty.creator.as_str(),
-                royalty: royalty.royalty,
-                amount: creator_amount,
-                denom,
-            })
-        }
+    for payout in collect_royalties(royalties, price, decimal_point, remaining, rsp, denom)? {
+        cosmos_msgs.push(parse_transfer_msg(
+            asset_info.clone(),
+            payout.amount,
+            contract_addr,
+            payout.creator,
+        )?);
     }
-    // add royalties into the event response
-    add_royalties_event(nft_addr, token_id, royalties_event.as_ref(), rsp);
     Ok(())
 }
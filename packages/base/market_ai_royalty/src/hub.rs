@@ -0,0 +1,155 @@
+use cosmwasm_std::{Addr, Binary, CosmosMsg, QuerierWrapper, StdResult, Uint128};
+use market::{
+    storage_update, AssetInfo, MarketHubContract, PageResponse, StorageMsg, StorageUpdate,
+    AI_ROYALTY_STORAGE,
+};
+use serde::de::DeserializeOwned;
+
+use crate::{
+    AccrueRoyaltiesMsg, AiRoyaltyExecuteMsg, AiRoyaltyQueryMsg, Royalty, RoyaltyBalance,
+    RoyaltyMsg, RoyaltyPolicy,
+};
+
+/// typed client of the ai royalty storage, every update is routed through the hub
+pub trait AiRoyaltyStorage {
+    fn ai_royalty_msg(&self, msg: AiRoyaltyExecuteMsg) -> StdResult<CosmosMsg>;
+
+    /// the update without sending it, so a settlement can batch it with its other updates
+    fn ai_royalty_update(&self, msg: AiRoyaltyExecuteMsg) -> StdResult<StorageUpdate>;
+
+    fn query_ai_royalty<R: DeserializeOwned>(
+        &self,
+        querier: &QuerierWrapper,
+        msg: AiRoyaltyQueryMsg,
+    ) -> StdResult<R>;
+
+    fn query_ai_royalty_binary(
+        &self,
+        querier: &QuerierWrapper,
+        msg: AiRoyaltyQueryMsg,
+    ) -> StdResult<Binary>;
+
+    /// the storage holds the accrued royalties until they are claimed
+    fn ai_royalty_addr(&self, querier: &QuerierWrapper) -> StdResult<Addr>;
+
+    fn update_royalty(&self, royalty: RoyaltyMsg) -> StdResult<CosmosMsg> {
+        self.ai_royalty_msg(AiRoyaltyExecuteMsg::UpdateRoyalty(royalty))
+    }
+
+    fn remove_royalty(&self, royalty: RoyaltyMsg) -> StdResult<CosmosMsg> {
+        self.ai_royalty_msg(AiRoyaltyExecuteMsg::RemoveRoyalty(royalty))
+    }
+
+    fn update_preference(&self, preference: u64) -> StdResult<CosmosMsg> {
+        self.ai_royalty_msg(AiRoyaltyExecuteMsg::UpdatePreference(preference))
+    }
+
+    fn accrue_royalties(&self, accrued: AccrueRoyaltiesMsg) -> StdResult<CosmosMsg> {
+        self.ai_royalty_msg(AiRoyaltyExecuteMsg::AccrueRoyalties(accrued))
+    }
+
+    fn claim_royalties(
+        &self,
+        creator: Addr,
+        asset_info: AssetInfo,
+        amount: Uint128,
+    ) -> StdResult<CosmosMsg> {
+        self.ai_royalty_msg(AiRoyaltyExecuteMsg::ClaimRoyalties {
+            creator,
+            asset_info,
+            amount,
+        })
+    }
+
+    fn update_policy(&self, policy: RoyaltyPolicy) -> StdResult<CosmosMsg> {
+        self.ai_royalty_msg(AiRoyaltyExecuteMsg::UpdatePolicy(policy))
+    }
+
+    fn expire_royalties(&self, contract_addr: Addr, token_id: &str) -> StdResult<CosmosMsg> {
+        self.ai_royalty_msg(AiRoyaltyExecuteMsg::ExpireRoyalties {
+            contract_addr,
+            token_id: token_id.to_string(),
+        })
+    }
+
+    fn get_royalty(
+        &self,
+        querier: &QuerierWrapper,
+        contract_addr: Addr,
+        token_id: &str,
+        creator: Addr,
+    ) -> StdResult<Royalty> {
+        self.query_ai_royalty(
+            querier,
+            AiRoyaltyQueryMsg::GetRoyalty {
+                contract_addr,
+                token_id: token_id.to_string(),
+                creator,
+            },
+        )
+    }
+
+    fn get_royalties_contract_token_id(
+        &self,
+        querier: &QuerierWrapper,
+        contract_addr: Addr,
+        token_id: &str,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<Royalty>> {
+        let page: PageResponse<Royalty> = self.query_ai_royalty(
+            querier,
+            AiRoyaltyQueryMsg::GetRoyaltiesContractTokenId {
+                contract_addr,
+                token_id: token_id.to_string(),
+                offset: None,
+                start_after: None,
+                limit,
+                order: Some(1),
+                with_total: None,
+            },
+        )?;
+        Ok(page.items)
+    }
+
+    fn get_preference(&self, querier: &QuerierWrapper, creator: Addr) -> StdResult<u64> {
+        self.query_ai_royalty(querier, AiRoyaltyQueryMsg::GetPreference { creator })
+    }
+
+    fn get_royalty_balances(
+        &self,
+        querier: &QuerierWrapper,
+        creator: Addr,
+    ) -> StdResult<Vec<RoyaltyBalance>> {
+        self.query_ai_royalty(querier, AiRoyaltyQueryMsg::GetRoyaltyBalances { creator })
+    }
+}
+
+impl AiRoyaltyStorage for MarketHubContract {
+    fn ai_royalty_msg(&self, msg: AiRoyaltyExecuteMsg) -> StdResult<CosmosMsg> {
+        self.execute_storage(AI_ROYALTY_STORAGE, &StorageMsg::Msg(msg))
+    }
+
+    fn ai_royalty_update(&self, msg: AiRoyaltyExecuteMsg) -> StdResult<StorageUpdate> {
+        storage_update(AI_ROYALTY_STORAGE, &StorageMsg::Msg(msg))
+    }
+
+    fn query_ai_royalty<R: DeserializeOwned>(
+        &self,
+        querier: &QuerierWrapper,
+        msg: AiRoyaltyQueryMsg,
+    ) -> StdResult<R> {
+        self.query_storage_msg(querier, AI_ROYALTY_STORAGE, &StorageMsg::Msg(msg))
+    }
+
+    fn query_ai_royalty_binary(
+        &self,
+        querier: &QuerierWrapper,
+        msg: AiRoyaltyQueryMsg,
+    ) -> StdResult<Binary> {
+        self.query_storage_binary(querier, AI_ROYALTY_STORAGE, &StorageMsg::Msg(msg))
+    }
+
+    fn ai_royalty_addr(&self, querier: &QuerierWrapper) -> StdResult<Addr> {
+        self.storage_addr(querier, AI_ROYALTY_STORAGE)
+    }
+}
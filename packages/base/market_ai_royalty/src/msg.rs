@@ -1,4 +1,5 @@
-use cosmwasm_std::Addr;
+use cosmwasm_std::{Addr, Uint128};
+use market::AssetInfo;
 
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -11,6 +12,8 @@ pub struct RoyaltyMsg {
     pub creator: Addr,
     pub creator_type: Option<String>,
     pub royalty: Option<u64>,
+    /// replaces the payees of the royalty, an empty list clears them and None keeps the stored ones
+    pub payees: Option<Vec<RoyaltyPayee>>,
 }
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
 pub struct Royalty {
@@ -19,6 +22,28 @@ pub struct Royalty {
     pub creator: Addr,
     pub royalty: u64,
     pub creator_type: String,
+    /// the royalty is split among the payees by weight, all to the creator when None
+    pub payees: Option<Vec<RoyaltyPayee>>,
+}
+
+/// upper bound of the summed weights of the payees of a royalty
+pub const MAX_TOTAL_PAYEE_WEIGHT: u64 = 1_000_000;
+/// every payee is paid on each sale, so their number is bounded
+pub const MAX_ROYALTY_PAYEES: usize = 20;
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct RoyaltyPayee {
+    pub address: Addr,
+    pub weight: u64,
+}
+
+/// the amount a payee receives from a royalty on a sale
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct RoyaltyPayout {
+    pub creator: Addr,
+    pub creator_type: String,
+    pub royalty: u64,
+    pub amount: Uint128,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -28,4 +53,60 @@ pub enum AiRoyaltyExecuteMsg {
     UpdateRoyalty(RoyaltyMsg),
     RemoveRoyalty(RoyaltyMsg),
     UpdatePreference(u64),
+    // credit the royalties of a sale, whose funds are sent to the storage, to the balances of the creators
+    AccrueRoyalties(AccrueRoyaltiesMsg),
+    // pay the claimed amount out of the accrued funds held by the storage to the creator
+    ClaimRoyalties {
+        creator: Addr,
+        asset_info: AssetInfo,
+        amount: Uint128,
+    },
+    UpdatePolicy(RoyaltyPolicy),
+    // the token has changed hands, drop the royalties whose policy does not survive transfers
+    ExpireRoyalties {
+        contract_addr: Addr,
+        token_id: String,
+    },
+}
+
+/// rules applied to every royalty registered with the creator type
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct RoyaltyPolicy {
+    pub creator_type: String,
+    /// royalty of the creators without a preference
+    pub default_royalty: u64,
+    pub min_royalty: u64,
+    pub max_royalty: u64,
+    /// keep the royalty after the token is sold or transferred, otherwise it is only paid on the first sale
+    pub survives_transfer: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct CreatorTypeEarning {
+    pub asset_info: AssetInfo,
+    pub amount: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AccrueRoyaltiesMsg {
+    pub contract_addr: Addr,
+    pub token_id: String,
+    pub asset_info: AssetInfo,
+    pub payouts: Vec<RoyaltyPayout>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct RoyaltyBalance {
+    pub asset_info: AssetInfo,
+    /// accrued and not claimed yet
+    pub pending: Uint128,
+    /// everything ever accrued, claimed or not
+    pub lifetime: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct TokenRoyaltyEarning {
+    pub creator: Addr,
+    pub asset_info: AssetInfo,
+    pub amount: Uint128,
 }
@@ -1,51 +1,97 @@
-use cosmwasm_std::Addr;
+use cosmwasm_schema::QueryResponses;
+use cosmwasm_std::{Addr, Binary};
 
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+use market::PageResponse;
+
+use crate::{CreatorTypeEarning, Royalty, RoyaltyBalance, RoyaltyPolicy, TokenRoyaltyEarning};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, QueryResponses)]
 #[serde(rename_all = "snake_case")]
 pub enum AiRoyaltyQueryMsg {
     // GetOfferings returns a list of all offerings
-    GetPreference {
-        creator: Addr,
-    },
+    #[returns(u64)]
+    GetPreference { creator: Addr },
+    #[returns(Royalty)]
     GetRoyalty {
         contract_addr: Addr,
         token_id: String,
         creator: Addr,
     },
+    #[returns(PageResponse<Royalty>)]
     GetRoyalties {
         offset: Option<OffsetMsg>,
-        limit: Option<u8>,
+        start_after: Option<Binary>,
+        limit: Option<u32>,
         order: Option<u8>,
+        with_total: Option<bool>,
     },
+    #[returns(PageResponse<Royalty>)]
     GetRoyaltiesTokenId {
         token_id: String,
         offset: Option<OffsetMsg>,
-        limit: Option<u8>,
+        start_after: Option<Binary>,
+        limit: Option<u32>,
         order: Option<u8>,
+        with_total: Option<bool>,
     },
+    #[returns(PageResponse<Royalty>)]
     GetRoyaltiesOwner {
         owner: Addr,
         offset: Option<OffsetMsg>,
-        limit: Option<u8>,
+        start_after: Option<Binary>,
+        limit: Option<u32>,
         order: Option<u8>,
+        with_total: Option<bool>,
     },
+    #[returns(PageResponse<Royalty>)]
     GetRoyaltiesContract {
         contract_addr: Addr,
         offset: Option<OffsetMsg>,
-        limit: Option<u8>,
+        start_after: Option<Binary>,
+        limit: Option<u32>,
         order: Option<u8>,
+        with_total: Option<bool>,
     },
+    #[returns(PageResponse<Royalty>)]
     GetRoyaltiesContractTokenId {
         contract_addr: Addr,
         token_id: String,
         offset: Option<OffsetMsg>,
-        limit: Option<u8>,
+        start_after: Option<Binary>,
+        limit: Option<u32>,
         order: Option<u8>,
+        with_total: Option<bool>,
     },
+    #[returns(AiRoyaltyContractInfo)]
     GetContractInfo {},
+    /// pending and lifetime accrued royalties of the creator per asset
+    #[returns(Vec<RoyaltyBalance>)]
+    GetRoyaltyBalances { creator: Addr },
+    /// lifetime accrued royalties of a token per creator and asset
+    #[returns(Vec<TokenRoyaltyEarning>)]
+    GetTokenRoyaltyEarnings {
+        contract_addr: Addr,
+        token_id: String,
+    },
+    #[returns(Option<RoyaltyPolicy>)]
+    GetPolicy { creator_type: String },
+    #[returns(Vec<RoyaltyPolicy>)]
+    GetPolicies {},
+    #[returns(PageResponse<Royalty>)]
+    GetRoyaltiesCreatorType {
+        creator_type: String,
+        offset: Option<OffsetMsg>,
+        start_after: Option<Binary>,
+        limit: Option<u32>,
+        order: Option<u8>,
+        with_total: Option<bool>,
+    },
+    /// lifetime accrued royalties of all creators of the type per asset
+    #[returns(Vec<CreatorTypeEarning>)]
+    GetCreatorTypeEarnings { creator_type: String },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -54,3 +100,31 @@ pub struct OffsetMsg {
     pub token_id: String,
     pub creator: Addr,
 }
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AiRoyaltyContractInfo {
+    pub governance: Addr,
+    pub creator: Addr,
+    pub default_royalty: u64,
+    pub max_royalty: u64,
+}
+
+/// schema of the responses of the wrapped royalty queries
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(untagged)]
+pub enum AiRoyaltyQueryResponse {
+    GetPreference(u64),
+    GetRoyalty(Royalty),
+    GetRoyalties(PageResponse<Royalty>),
+    GetRoyaltiesTokenId(PageResponse<Royalty>),
+    GetRoyaltiesOwner(PageResponse<Royalty>),
+    GetRoyaltiesContract(PageResponse<Royalty>),
+    GetRoyaltiesContractTokenId(PageResponse<Royalty>),
+    GetContractInfo(AiRoyaltyContractInfo),
+    GetRoyaltyBalances(Vec<RoyaltyBalance>),
+    GetTokenRoyaltyEarnings(Vec<TokenRoyaltyEarning>),
+    GetPolicy(Option<RoyaltyPolicy>),
+    GetPolicies(Vec<RoyaltyPolicy>),
+    GetRoyaltiesCreatorType(PageResponse<Royalty>),
+    GetCreatorTypeEarnings(Vec<CreatorTypeEarning>),
+}
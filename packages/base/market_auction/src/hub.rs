@@ -0,0 +1,78 @@
+use cosmwasm_std::{Addr, Binary, CosmosMsg, QuerierWrapper, StdResult};
+use market::{storage_update, MarketHubContract, StorageMsg, StorageUpdate, AUCTION_STORAGE};
+use serde::de::DeserializeOwned;
+
+use crate::{Auction, AuctionExecuteMsg, AuctionQueryMsg, QueryAuctionsResult};
+
+/// typed client of the auction storage, every update is routed through the hub
+pub trait AuctionStorage {
+    fn auction_msg(&self, msg: AuctionExecuteMsg) -> StdResult<CosmosMsg>;
+
+    /// the update without sending it, so a settlement can batch it with its other updates
+    fn auction_update(&self, msg: AuctionExecuteMsg) -> StdResult<StorageUpdate>;
+
+    fn query_auction<R: DeserializeOwned>(
+        &self,
+        querier: &QuerierWrapper,
+        msg: AuctionQueryMsg,
+    ) -> StdResult<R>;
+
+    fn query_auction_binary(
+        &self,
+        querier: &QuerierWrapper,
+        msg: AuctionQueryMsg,
+    ) -> StdResult<Binary>;
+
+    fn update_auction(&self, auction: Auction) -> StdResult<CosmosMsg> {
+        self.auction_msg(AuctionExecuteMsg::UpdateAuction { auction })
+    }
+
+    fn remove_auction(&self, id: u64) -> StdResult<CosmosMsg> {
+        self.auction_msg(AuctionExecuteMsg::RemoveAuction { id })
+    }
+
+    fn get_auction_raw(&self, querier: &QuerierWrapper, auction_id: u64) -> StdResult<Auction> {
+        self.query_auction(querier, AuctionQueryMsg::GetAuctionRaw { auction_id })
+    }
+
+    fn get_auction_by_contract_token_id(
+        &self,
+        querier: &QuerierWrapper,
+        contract: Addr,
+        token_id: &str,
+    ) -> StdResult<QueryAuctionsResult> {
+        self.query_auction(
+            querier,
+            AuctionQueryMsg::GetAuctionByContractTokenId {
+                contract,
+                token_id: token_id.to_string(),
+            },
+        )
+    }
+}
+
+impl AuctionStorage for MarketHubContract {
+    fn auction_msg(&self, msg: AuctionExecuteMsg) -> StdResult<CosmosMsg> {
+        self.execute_storage(AUCTION_STORAGE, &StorageMsg::Auction(msg))
+    }
+
+    fn auction_update(&self, msg: AuctionExecuteMsg) -> StdResult<StorageUpdate> {
+        storage_update(AUCTION_STORAGE, &StorageMsg::Auction(msg))
+    }
+
+    fn query_auction<R: DeserializeOwned>(
+        &self,
+        querier: &QuerierWrapper,
+        msg: AuctionQueryMsg,
+    ) -> StdResult<R> {
+        self.query_storage_msg(querier, AUCTION_STORAGE, &StorageMsg::Auction(msg))
+    }
+
+    fn query_auction_binary(
+        &self,
+        querier: &QuerierWrapper,
+        msg: AuctionQueryMsg,
+    ) -> StdResult<Binary> {
+        self.query_storage_binary(querier, AUCTION_STORAGE, &StorageMsg::Auction(msg))
+    }
+}
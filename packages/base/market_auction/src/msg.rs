@@ -1,4 +1,4 @@
-use cosmwasm_std::{CanonicalAddr, Uint128};
+use cosmwasm_std::{Addr, Binary, CanonicalAddr, Uint128};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -6,14 +6,25 @@ use crate::QueryAuctionsResult;
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct PagingOptions {
+    /// legacy id bound, start_after takes precedence over it. ignored by the end time queries
+    /// since they are not ordered by id
     pub offset: Option<u64>,
-    pub limit: Option<u8>,
+    /// next_cursor of the previous page
+    pub start_after: Option<Binary>,
+    pub limit: Option<u32>,
     pub order: Option<u8>,
+    pub with_total: Option<bool>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct AuctionsResponse {
     pub items: Vec<QueryAuctionsResult>,
+    /// pass it as start_after to get the next page, none on the last page
+    #[serde(default)]
+    pub next_cursor: Option<Binary>,
+    /// only counted when with_total is set
+    #[serde(default)]
+    pub total: Option<u64>,
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
@@ -36,6 +47,8 @@ pub struct Auction {
     pub start_timestamp: Uint128,
     pub end_timestamp: Uint128,
     pub step_price: u64,
+    /// only these addresses can bid, anyone when None
+    pub reserved_for: Option<Vec<Addr>>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
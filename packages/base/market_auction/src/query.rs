@@ -1,37 +1,43 @@
-use crate::msg::PagingOptions;
+use crate::msg::{Auction, AuctionsResponse, PagingOptions};
+use cosmwasm_schema::QueryResponses;
 use cosmwasm_std::{Addr, Uint128};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, QueryResponses)]
 #[serde(rename_all = "snake_case")]
 pub enum AuctionQueryMsg {
     // GetOfferings returns a list of all offerings
-    GetAuctions {
-        options: PagingOptions,
-    },
-    GetAuctionsByAsker {
-        asker: Addr,
-        options: PagingOptions,
-    },
+    #[returns(AuctionsResponse)]
+    GetAuctions { options: PagingOptions },
+    #[returns(AuctionsResponse)]
+    GetAuctionsByAsker { asker: Addr, options: PagingOptions },
+    #[returns(AuctionsResponse)]
     GetAuctionsByBidder {
         bidder: Option<Addr>,
         options: PagingOptions,
     },
+    #[returns(AuctionsResponse)]
     GetAuctionsByContract {
         contract: Addr,
         options: PagingOptions,
     },
-    GetAuctionRaw {
-        auction_id: u64,
-    },
-    GetAuction {
-        auction_id: u64,
-    },
-    GetAuctionByContractTokenId {
-        contract: Addr,
-        token_id: String,
+    /// auctions with an end timestamp in [from, to), soonest first by default
+    #[returns(AuctionsResponse)]
+    GetAuctionsEndingBetween {
+        from: Uint128,
+        to: Uint128,
+        options: PagingOptions,
     },
+    /// auctions past their end timestamp that nobody has claimed yet, oldest first by default
+    #[returns(AuctionsResponse)]
+    GetUnclaimedEndedAuctions { options: PagingOptions },
+    #[returns(Auction)]
+    GetAuctionRaw { auction_id: u64 },
+    #[returns(QueryAuctionsResult)]
+    GetAuction { auction_id: u64 },
+    #[returns(QueryAuctionsResult)]
+    GetAuctionByContractTokenId { contract: Addr, token_id: String },
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
@@ -50,4 +56,20 @@ pub struct QueryAuctionsResult {
     pub start_timestamp: Uint128,
     pub end_timestamp: Uint128,
     pub step_price: u64,
+    pub reserved_for: Option<Vec<Addr>>,
+}
+
+/// schema of the responses of the wrapped auction queries
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(untagged)]
+pub enum AuctionQueryResponse {
+    GetAuctions(AuctionsResponse),
+    GetAuctionsByAsker(AuctionsResponse),
+    GetAuctionsByBidder(AuctionsResponse),
+    GetAuctionsByContract(AuctionsResponse),
+    GetAuctionsEndingBetween(AuctionsResponse),
+    GetUnclaimedEndedAuctions(AuctionsResponse),
+    GetAuctionRaw(Auction),
+    GetAuction(QueryAuctionsResult),
+    GetAuctionByContractTokenId(QueryAuctionsResult),
 }
@@ -0,0 +1,73 @@
+use cosmwasm_std::{Addr, Binary, CosmosMsg, QuerierWrapper, StdResult};
+use market::{MarketHubContract, StorageMsg, AUCTION_EXTEND_STORAGE};
+use serde::de::DeserializeOwned;
+
+use crate::{Auction, AuctionExecuteMsg, AuctionQueryMsg, QueryAuctionsResult};
+
+/// typed client of the 1155 auction storage, every update is routed through the hub
+pub trait AuctionExtendStorage {
+    fn auction_msg(&self, msg: AuctionExecuteMsg) -> StdResult<CosmosMsg>;
+
+    fn query_auction<R: DeserializeOwned>(
+        &self,
+        querier: &QuerierWrapper,
+        msg: AuctionQueryMsg,
+    ) -> StdResult<R>;
+
+    fn query_auction_binary(
+        &self,
+        querier: &QuerierWrapper,
+        msg: AuctionQueryMsg,
+    ) -> StdResult<Binary>;
+
+    fn update_auction(&self, auction: Auction) -> StdResult<CosmosMsg> {
+        self.auction_msg(AuctionExecuteMsg::UpdateAuction { auction })
+    }
+
+    fn remove_auction(&self, id: u64) -> StdResult<CosmosMsg> {
+        self.auction_msg(AuctionExecuteMsg::RemoveAuction { id })
+    }
+
+    fn get_auction_raw(&self, querier: &QuerierWrapper, auction_id: u64) -> StdResult<Auction> {
+        self.query_auction(querier, AuctionQueryMsg::GetAuctionRaw { auction_id })
+    }
+
+    fn get_unique_auction(
+        &self,
+        querier: &QuerierWrapper,
+        contract: Addr,
+        token_id: &str,
+        asker: Addr,
+    ) -> StdResult<QueryAuctionsResult> {
+        self.query_auction(
+            querier,
+            AuctionQueryMsg::GetUniqueAuction {
+                contract,
+                token_id: token_id.to_string(),
+                asker,
+            },
+        )
+    }
+}
+
+impl AuctionExtendStorage for MarketHubContract {
+    fn auction_msg(&self, msg: AuctionExecuteMsg) -> StdResult<CosmosMsg> {
+        self.execute_storage(AUCTION_EXTEND_STORAGE, &StorageMsg::Msg(msg))
+    }
+
+    fn query_auction<R: DeserializeOwned>(
+        &self,
+        querier: &QuerierWrapper,
+        msg: AuctionQueryMsg,
+    ) -> StdResult<R> {
+        self.query_storage_msg(querier, AUCTION_EXTEND_STORAGE, &StorageMsg::Msg(msg))
+    }
+
+    fn query_auction_binary(
+        &self,
+        querier: &QuerierWrapper,
+        msg: AuctionQueryMsg,
+    ) -> StdResult<Binary> {
+        self.query_storage_binary(querier, AUCTION_EXTEND_STORAGE, &StorageMsg::Msg(msg))
+    }
+}
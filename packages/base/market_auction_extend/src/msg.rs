@@ -1,4 +1,4 @@
-use cosmwasm_std::{CanonicalAddr, Uint128};
+use cosmwasm_std::{Addr, CanonicalAddr, Uint128};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -37,6 +37,11 @@ pub struct Auction {
     pub start_timestamp: Uint128,
     pub end_timestamp: Uint128,
     pub step_price: u64,
+    /// only these addresses can bid, anyone when None
+    pub reserved_for: Option<Vec<Addr>>,
+    /// bidders bid for a part of the amount, and the top bids win at a uniform clearing price
+    #[serde(default)]
+    pub multi_winner: bool,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
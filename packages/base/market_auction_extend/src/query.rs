@@ -1,38 +1,38 @@
-use crate::msg::PagingOptions;
+use crate::msg::{Auction, AuctionsResponse, PagingOptions};
+use cosmwasm_schema::QueryResponses;
 use cosmwasm_std::{Addr, Uint128};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, QueryResponses)]
 #[serde(rename_all = "snake_case")]
 pub enum AuctionQueryMsg {
     // GetOfferings returns a list of all offerings
-    GetAuctions {
-        options: PagingOptions,
-    },
-    GetAuctionsByAsker {
-        asker: Addr,
-        options: PagingOptions,
-    },
+    #[returns(AuctionsResponse)]
+    GetAuctions { options: PagingOptions },
+    #[returns(AuctionsResponse)]
+    GetAuctionsByAsker { asker: Addr, options: PagingOptions },
+    #[returns(AuctionsResponse)]
     GetAuctionsByBidder {
         bidder: Option<Addr>,
         options: PagingOptions,
     },
+    #[returns(AuctionsResponse)]
     GetAuctionsByContract {
         contract: Addr,
         options: PagingOptions,
     },
-    GetAuctionRaw {
-        auction_id: u64,
-    },
-    GetAuction {
-        auction_id: u64,
-    },
+    #[returns(Auction)]
+    GetAuctionRaw { auction_id: u64 },
+    #[returns(QueryAuctionsResult)]
+    GetAuction { auction_id: u64 },
+    #[returns(AuctionsResponse)]
     GetAuctionsByContractTokenId {
         contract: Addr,
         token_id: String,
         options: PagingOptions,
     },
+    #[returns(QueryAuctionsResult)]
     GetUniqueAuction {
         contract: Addr,
         token_id: String,
@@ -61,4 +61,21 @@ pub struct QueryAuctionsResult {
     pub start_timestamp: Uint128,
     pub end_timestamp: Uint128,
     pub step_price: u64,
+    pub reserved_for: Option<Vec<Addr>>,
+    #[serde(default)]
+    pub multi_winner: bool,
+}
+
+/// schema of the responses of the wrapped auction queries
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(untagged)]
+pub enum AuctionQueryResponse {
+    GetAuctions(AuctionsResponse),
+    GetAuctionsByAsker(AuctionsResponse),
+    GetAuctionsByBidder(AuctionsResponse),
+    GetAuctionsByContract(AuctionsResponse),
+    GetAuctionRaw(Auction),
+    GetAuction(QueryAuctionsResult),
+    GetAuctionsByContractTokenId(AuctionsResponse),
+    GetUniqueAuction(QueryAuctionsResult),
 }
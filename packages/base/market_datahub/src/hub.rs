@@ -0,0 +1,203 @@
+use cosmwasm_std::{Addr, Binary, CosmosMsg, QuerierWrapper, StdResult};
+use market::{MarketHubContract, StorageMsg, DATAHUB_STORAGE};
+use serde::de::DeserializeOwned;
+
+use crate::{
+    Annotation, AnnotationResult, AnnotationReviewer, DataHubExecuteMsg, DataHubQueryMsg, Offering,
+};
+
+/// typed client of the datahub storage, every update is routed through the hub
+pub trait DataHubStorage {
+    fn datahub_msg(&self, msg: DataHubExecuteMsg) -> StdResult<CosmosMsg>;
+
+    fn query_datahub<R: DeserializeOwned>(
+        &self,
+        querier: &QuerierWrapper,
+        msg: DataHubQueryMsg,
+    ) -> StdResult<R>;
+
+    fn query_datahub_binary(
+        &self,
+        querier: &QuerierWrapper,
+        msg: DataHubQueryMsg,
+    ) -> StdResult<Binary>;
+
+    fn update_offering(&self, offering: Offering) -> StdResult<CosmosMsg> {
+        self.datahub_msg(DataHubExecuteMsg::UpdateOffering { offering })
+    }
+
+    fn remove_offering(&self, id: u64) -> StdResult<CosmosMsg> {
+        self.datahub_msg(DataHubExecuteMsg::RemoveOffering { id })
+    }
+
+    fn update_annotation(&self, annotation: Annotation) -> StdResult<CosmosMsg> {
+        self.datahub_msg(DataHubExecuteMsg::UpdateAnnotation { annotation })
+    }
+
+    fn remove_annotation(&self, id: u64) -> StdResult<CosmosMsg> {
+        self.datahub_msg(DataHubExecuteMsg::RemoveAnnotation { id })
+    }
+
+    fn add_annotation_result(&self, annotation_result: AnnotationResult) -> StdResult<CosmosMsg> {
+        self.datahub_msg(DataHubExecuteMsg::AddAnnotationResult { annotation_result })
+    }
+
+    fn add_reviewed_upload(&self, reviewed_result: AnnotationResult) -> StdResult<CosmosMsg> {
+        self.datahub_msg(DataHubExecuteMsg::AddReviewedUpload { reviewed_result })
+    }
+
+    fn add_annotation_reviewer(
+        &self,
+        annotation_id: u64,
+        reviewer_address: Addr,
+    ) -> StdResult<CosmosMsg> {
+        self.datahub_msg(DataHubExecuteMsg::AddAnnotationReviewer {
+            annotation_id,
+            reviewer_address,
+        })
+    }
+
+    fn remove_annotation_reviewer(
+        &self,
+        annotation_id: u64,
+        reviewer_address: Addr,
+    ) -> StdResult<CosmosMsg> {
+        self.datahub_msg(DataHubExecuteMsg::RemoveAnnotationReviewer {
+            annotation_id,
+            reviewer_address,
+        })
+    }
+
+    fn remove_annotation_result_data(&self, annotation_id: u64) -> StdResult<CosmosMsg> {
+        self.datahub_msg(DataHubExecuteMsg::RemoveAnnotationResultData { annotation_id })
+    }
+
+    fn get_offering(&self, querier: &QuerierWrapper, offering_id: u64) -> StdResult<Offering> {
+        self.query_datahub(querier, DataHubQueryMsg::GetOffering { offering_id })
+    }
+
+    fn get_unique_offering(
+        &self,
+        querier: &QuerierWrapper,
+        contract: Addr,
+        token_id: &str,
+        owner: Addr,
+    ) -> StdResult<Offering> {
+        self.query_datahub(
+            querier,
+            DataHubQueryMsg::GetUniqueOffering {
+                contract,
+                token_id: token_id.to_string(),
+                owner,
+            },
+        )
+    }
+
+    fn get_annotation(
+        &self,
+        querier: &QuerierWrapper,
+        annotation_id: u64,
+    ) -> StdResult<Annotation> {
+        self.query_datahub(querier, DataHubQueryMsg::GetAnnotation { annotation_id })
+    }
+
+    fn get_annotation_reviewers(
+        &self,
+        querier: &QuerierWrapper,
+        annotation_id: u64,
+    ) -> StdResult<Vec<AnnotationReviewer>> {
+        self.query_datahub(
+            querier,
+            DataHubQueryMsg::GetAnnotationReviewerByAnnotationId { annotation_id },
+        )
+    }
+
+    fn get_annotation_reviewer(
+        &self,
+        querier: &QuerierWrapper,
+        annotation_id: u64,
+        reviewer_address: Addr,
+    ) -> StdResult<Option<AnnotationReviewer>> {
+        self.query_datahub(
+            querier,
+            DataHubQueryMsg::GetAnnotationReviewerByUniqueKey {
+                annotation_id,
+                reviewer_address,
+            },
+        )
+    }
+
+    fn get_annotation_results(
+        &self,
+        querier: &QuerierWrapper,
+        annotation_id: u64,
+    ) -> StdResult<Vec<AnnotationResult>> {
+        self.query_datahub(
+            querier,
+            DataHubQueryMsg::GetAnnotationResultsByAnnotationId { annotation_id },
+        )
+    }
+
+    fn get_annotation_result(
+        &self,
+        querier: &QuerierWrapper,
+        annotation_id: u64,
+        reviewer_address: Addr,
+    ) -> StdResult<Option<AnnotationResult>> {
+        self.query_datahub(
+            querier,
+            DataHubQueryMsg::GetAnnotationResultsByAnnotationIdAndReviewer {
+                annotation_id,
+                reviewer_address,
+            },
+        )
+    }
+
+    fn get_reviewed_uploads(
+        &self,
+        querier: &QuerierWrapper,
+        annotation_id: u64,
+    ) -> StdResult<Vec<AnnotationResult>> {
+        self.query_datahub(
+            querier,
+            DataHubQueryMsg::GetReviewedUploadByAnnotationId { annotation_id },
+        )
+    }
+
+    fn get_reviewed_upload(
+        &self,
+        querier: &QuerierWrapper,
+        annotation_id: u64,
+        reviewer_address: Addr,
+    ) -> StdResult<Option<AnnotationResult>> {
+        self.query_datahub(
+            querier,
+            DataHubQueryMsg::GetReviewedUploadByAnnotationIdAndReviewer {
+                annotation_id,
+                reviewer_address,
+            },
+        )
+    }
+}
+
+impl DataHubStorage for MarketHubContract {
+    fn datahub_msg(&self, msg: DataHubExecuteMsg) -> StdResult<CosmosMsg> {
+        self.execute_storage(DATAHUB_STORAGE, &StorageMsg::Msg(msg))
+    }
+
+    fn query_datahub<R: DeserializeOwned>(
+        &self,
+        querier: &QuerierWrapper,
+        msg: DataHubQueryMsg,
+    ) -> StdResult<R> {
+        self.query_storage_msg(querier, DATAHUB_STORAGE, &StorageMsg::Msg(msg))
+    }
+
+    fn query_datahub_binary(
+        &self,
+        querier: &QuerierWrapper,
+        msg: DataHubQueryMsg,
+    ) -> StdResult<Binary> {
+        self.query_storage_binary(querier, DATAHUB_STORAGE, &StorageMsg::Msg(msg))
+    }
+}
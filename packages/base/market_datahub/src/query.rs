@@ -1,97 +1,150 @@
-use cosmwasm_std::Addr;
+use cosmwasm_schema::QueryResponses;
+use cosmwasm_std::{Addr, Binary};
 
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+use market::{PageResponse, StorageContractInfo};
+
+use crate::{Annotation, AnnotationResult, AnnotationReviewer, Offering};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, QueryResponses)]
 #[serde(rename_all = "snake_case")]
 pub enum DataHubQueryMsg {
     // GetOfferings returns a list of all offerings
+    #[returns(PageResponse<Offering>)]
     GetOfferings {
         offset: Option<u64>,
-        limit: Option<u8>,
+        start_after: Option<Binary>,
+        limit: Option<u32>,
         order: Option<u8>,
+        with_total: Option<bool>,
     },
+    #[returns(PageResponse<Offering>)]
     GetOfferingsBySeller {
         seller: Addr,
         offset: Option<u64>,
-        limit: Option<u8>,
+        start_after: Option<Binary>,
+        limit: Option<u32>,
         order: Option<u8>,
+        with_total: Option<bool>,
     },
+    #[returns(PageResponse<Offering>)]
     GetOfferingsByContract {
         contract: Addr,
         offset: Option<u64>,
-        limit: Option<u8>,
+        start_after: Option<Binary>,
+        limit: Option<u32>,
         order: Option<u8>,
+        with_total: Option<bool>,
     },
-    GetOffering {
-        offering_id: u64,
-    },
+    #[returns(Offering)]
+    GetOffering { offering_id: u64 },
+    #[returns(PageResponse<Offering>)]
     GetOfferingsByContractTokenId {
         contract: Addr,
         token_id: String,
         offset: Option<u64>,
-        limit: Option<u8>,
+        start_after: Option<Binary>,
+        limit: Option<u32>,
         order: Option<u8>,
+        with_total: Option<bool>,
     },
+    #[returns(Offering)]
     GetUniqueOffering {
         contract: Addr,
         token_id: String,
         owner: Addr,
     },
+    #[returns(PageResponse<Annotation>)]
     GetAnnotations {
         offset: Option<u64>,
-        limit: Option<u8>,
+        start_after: Option<Binary>,
+        limit: Option<u32>,
         order: Option<u8>,
+        with_total: Option<bool>,
     },
+    #[returns(PageResponse<Annotation>)]
     GetAnnotationsByContract {
         contract: Addr,
         offset: Option<u64>,
-        limit: Option<u8>,
+        start_after: Option<Binary>,
+        limit: Option<u32>,
         order: Option<u8>,
+        with_total: Option<bool>,
     },
-    GetAnnotation {
-        annotation_id: u64,
-    },
+    #[returns(Annotation)]
+    GetAnnotation { annotation_id: u64 },
+    #[returns(PageResponse<Annotation>)]
     GetAnnotationsByContractTokenId {
         contract: Addr,
         token_id: String,
         offset: Option<u64>,
-        limit: Option<u8>,
+        start_after: Option<Binary>,
+        limit: Option<u32>,
         order: Option<u8>,
+        with_total: Option<bool>,
     },
+    #[returns(PageResponse<Annotation>)]
     GetAnnotationsByRequester {
         requester: Addr,
         offset: Option<u64>,
-        limit: Option<u8>,
+        start_after: Option<Binary>,
+        limit: Option<u32>,
         order: Option<u8>,
-    },
-    GetAnnotationResult {
-        annotation_result_id: u64,
-    },
-    GetAnnotationResultByReviewer {
-        reviewer_address: Addr,
-    },
-    GetAnnotationResultsByAnnotationId {
-        annotation_id: u64,
-    },
+        with_total: Option<bool>,
+    },
+    #[returns(AnnotationResult)]
+    GetAnnotationResult { annotation_result_id: u64 },
+    #[returns(Vec<AnnotationResult>)]
+    GetAnnotationResultByReviewer { reviewer_address: Addr },
+    #[returns(Vec<AnnotationResult>)]
+    GetAnnotationResultsByAnnotationId { annotation_id: u64 },
+    #[returns(Option<AnnotationResult>)]
     GetAnnotationResultsByAnnotationIdAndReviewer {
         annotation_id: u64,
         reviewer_address: Addr,
     },
+    #[returns(Option<AnnotationReviewer>)]
     GetAnnotationReviewerByUniqueKey {
         annotation_id: u64,
         reviewer_address: Addr,
     },
-    GetAnnotationReviewerByAnnotationId {
-        annotation_id: u64,
-    },
-    GetReviewedUploadByAnnotationId {
-        annotation_id: u64,
-    },
+    #[returns(Vec<AnnotationReviewer>)]
+    GetAnnotationReviewerByAnnotationId { annotation_id: u64 },
+    #[returns(Vec<AnnotationResult>)]
+    GetReviewedUploadByAnnotationId { annotation_id: u64 },
+    #[returns(Option<AnnotationResult>)]
     GetReviewedUploadByAnnotationIdAndReviewer {
         annotation_id: u64,
         reviewer_address: Addr,
     },
+    #[returns(StorageContractInfo)]
     GetContractInfo {},
 }
+
+/// schema of the responses of the wrapped datahub queries
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(untagged)]
+pub enum DataHubQueryResponse {
+    GetOfferings(PageResponse<Offering>),
+    GetOfferingsBySeller(PageResponse<Offering>),
+    GetOfferingsByContract(PageResponse<Offering>),
+    GetOffering(Offering),
+    GetOfferingsByContractTokenId(PageResponse<Offering>),
+    GetUniqueOffering(Offering),
+    GetAnnotations(PageResponse<Annotation>),
+    GetAnnotationsByContract(PageResponse<Annotation>),
+    GetAnnotation(Annotation),
+    GetAnnotationsByContractTokenId(PageResponse<Annotation>),
+    GetAnnotationsByRequester(PageResponse<Annotation>),
+    GetAnnotationResult(AnnotationResult),
+    GetAnnotationResultByReviewer(Vec<AnnotationResult>),
+    GetAnnotationResultsByAnnotationId(Vec<AnnotationResult>),
+    GetAnnotationResultsByAnnotationIdAndReviewer(Option<AnnotationResult>),
+    GetAnnotationReviewerByUniqueKey(Option<AnnotationReviewer>),
+    GetAnnotationReviewerByAnnotationId(Vec<AnnotationReviewer>),
+    GetReviewedUploadByAnnotationId(Vec<AnnotationResult>),
+    GetReviewedUploadByAnnotationIdAndReviewer(Option<AnnotationResult>),
+    GetContractInfo(StorageContractInfo),
+}
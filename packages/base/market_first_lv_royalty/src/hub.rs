@@ -0,0 +1,100 @@
+use cosmwasm_std::{Addr, Binary, CosmosMsg, QuerierWrapper, StdResult};
+use market::{
+    storage_update, MarketHubContract, StorageMsg, StorageUpdate, FIRST_LV_ROYALTY_STORAGE,
+};
+use serde::de::DeserializeOwned;
+
+use crate::{FirstLvRoyalty, FirstLvRoyaltyExecuteMsg, FirstLvRoyaltyQueryMsg, Provenance};
+
+/// typed client of the first level royalty storage, every update is routed through the hub
+pub trait FirstLvRoyaltyStorage {
+    fn first_lv_royalty_msg(&self, msg: FirstLvRoyaltyExecuteMsg) -> StdResult<CosmosMsg>;
+
+    /// the update without sending it, so a settlement can batch it with its other updates
+    fn first_lv_royalty_update(&self, msg: FirstLvRoyaltyExecuteMsg) -> StdResult<StorageUpdate>;
+
+    fn query_first_lv_royalty<R: DeserializeOwned>(
+        &self,
+        querier: &QuerierWrapper,
+        msg: FirstLvRoyaltyQueryMsg,
+    ) -> StdResult<R>;
+
+    fn query_first_lv_royalty_binary(
+        &self,
+        querier: &QuerierWrapper,
+        msg: FirstLvRoyaltyQueryMsg,
+    ) -> StdResult<Binary>;
+
+    fn update_first_lv_royalty(&self, first_lv_royalty: FirstLvRoyalty) -> StdResult<CosmosMsg> {
+        self.first_lv_royalty_msg(FirstLvRoyaltyExecuteMsg::UpdateFirstLvRoyalty {
+            first_lv_royalty,
+        })
+    }
+
+    fn remove_first_lv_royalty(&self, contract_addr: Addr, token_id: &str) -> StdResult<CosmosMsg> {
+        self.first_lv_royalty_msg(FirstLvRoyaltyExecuteMsg::RemoveFirstLvRoyalty {
+            contract_addr,
+            token_id: token_id.to_string(),
+        })
+    }
+
+    fn update_provenance(&self, provenance: Provenance) -> StdResult<CosmosMsg> {
+        self.first_lv_royalty_msg(FirstLvRoyaltyExecuteMsg::UpdateProvenance { provenance })
+    }
+
+    fn get_first_lv_royalty(
+        &self,
+        querier: &QuerierWrapper,
+        contract: Addr,
+        token_id: &str,
+    ) -> StdResult<FirstLvRoyalty> {
+        self.query_first_lv_royalty(
+            querier,
+            FirstLvRoyaltyQueryMsg::GetFirstLvRoyalty {
+                contract,
+                token_id: token_id.to_string(),
+            },
+        )
+    }
+
+    fn get_provenance(
+        &self,
+        querier: &QuerierWrapper,
+        contract: Addr,
+        token_id: &str,
+    ) -> StdResult<Provenance> {
+        self.query_first_lv_royalty(
+            querier,
+            FirstLvRoyaltyQueryMsg::GetProvenance {
+                contract,
+                token_id: token_id.to_string(),
+            },
+        )
+    }
+}
+
+impl FirstLvRoyaltyStorage for MarketHubContract {
+    fn first_lv_royalty_msg(&self, msg: FirstLvRoyaltyExecuteMsg) -> StdResult<CosmosMsg> {
+        self.execute_storage(FIRST_LV_ROYALTY_STORAGE, &StorageMsg::Msg(msg))
+    }
+
+    fn first_lv_royalty_update(&self, msg: FirstLvRoyaltyExecuteMsg) -> StdResult<StorageUpdate> {
+        storage_update(FIRST_LV_ROYALTY_STORAGE, &StorageMsg::Msg(msg))
+    }
+
+    fn query_first_lv_royalty<R: DeserializeOwned>(
+        &self,
+        querier: &QuerierWrapper,
+        msg: FirstLvRoyaltyQueryMsg,
+    ) -> StdResult<R> {
+        self.query_storage_msg(querier, FIRST_LV_ROYALTY_STORAGE, &StorageMsg::Msg(msg))
+    }
+
+    fn query_first_lv_royalty_binary(
+        &self,
+        querier: &QuerierWrapper,
+        msg: FirstLvRoyaltyQueryMsg,
+    ) -> StdResult<Binary> {
+        self.query_storage_binary(querier, FIRST_LV_ROYALTY_STORAGE, &StorageMsg::Msg(msg))
+    }
+}
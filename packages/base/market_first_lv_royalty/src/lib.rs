@@ -1,6 +1,8 @@
+mod hub;
 mod msg;
 mod query;
 
+pub use crate::hub::*;
 pub use crate::msg::*;
 pub use crate::query::*;
 pub use market::*;
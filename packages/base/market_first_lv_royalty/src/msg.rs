@@ -13,6 +13,15 @@ pub struct FirstLvRoyalty {
     pub cur_royalty: Option<u64>,
 }
 
+/// owners that sold the token before, paid a decaying royalty on each resale
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct Provenance {
+    pub token_id: String,
+    pub contract_addr: Addr,
+    /// the latest owner first
+    pub owners: Vec<Addr>,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum FirstLvRoyaltyExecuteMsg {
@@ -24,6 +33,9 @@ pub enum FirstLvRoyaltyExecuteMsg {
         contract_addr: Addr,
         token_id: String,
     },
+    UpdateProvenance {
+        provenance: Provenance,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
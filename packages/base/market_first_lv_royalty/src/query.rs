@@ -1,33 +1,43 @@
+use cosmwasm_schema::QueryResponses;
 use cosmwasm_std::{Addr, Uint128};
 
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+use market::StorageContractInfo;
+
+use crate::{FirstLvRoyalty, Provenance};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, QueryResponses)]
 #[serde(rename_all = "snake_case")]
 pub enum FirstLvRoyaltyQueryMsg {
     // GetOfferings returns a list of all offerings
+    #[returns(Vec<FirstLvRoyalty>)]
     GetFirstLvRoyalties {
         offset: Option<OffsetMsg>,
         limit: Option<u8>,
         order: Option<u8>,
     },
+    #[returns(Vec<FirstLvRoyalty>)]
     GetFirstLvRoyaltiesByCurrentOwner {
         current_owner: Addr,
         offset: Option<OffsetMsg>,
         limit: Option<u8>,
         order: Option<u8>,
     },
+    #[returns(Vec<FirstLvRoyalty>)]
     GetFirstLvRoyaltiesByContract {
         contract: Addr,
         offset: Option<OffsetMsg>,
         limit: Option<u8>,
         order: Option<u8>,
     },
-    GetFirstLvRoyalty {
-        contract: Addr,
-        token_id: String,
-    },
+    #[returns(FirstLvRoyalty)]
+    GetFirstLvRoyalty { contract: Addr, token_id: String },
+    /// previous owners of a token, empty when it was never resold
+    #[returns(Provenance)]
+    GetProvenance { contract: Addr, token_id: String },
+    #[returns(StorageContractInfo)]
     GetContractInfo {},
 }
 
@@ -50,3 +60,15 @@ pub struct OffsetMsg {
     pub contract: Addr,
     pub token_id: String,
 }
+
+/// schema of the responses of the wrapped first level royalty queries
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(untagged)]
+pub enum FirstLvRoyaltyQueryResponse {
+    GetFirstLvRoyalties(Vec<FirstLvRoyalty>),
+    GetFirstLvRoyaltiesByCurrentOwner(Vec<FirstLvRoyalty>),
+    GetFirstLvRoyaltiesByContract(Vec<FirstLvRoyalty>),
+    GetFirstLvRoyalty(FirstLvRoyalty),
+    GetProvenance(Provenance),
+    GetContractInfo(StorageContractInfo),
+}
@@ -0,0 +1,129 @@
+use cosmwasm_std::{Addr, Binary, CosmosMsg, QuerierWrapper, StdResult};
+use market::{AssetInfo, MarketHubContract, StorageMsg};
+use serde::de::DeserializeOwned;
+
+use crate::{Payment, PaymentExecuteMsg, PaymentQueryMsg};
+
+/// typed client of the payment storages, the 721 and 1155 markets keep their own,
+/// so every call takes the storage name
+pub trait PaymentStorage {
+    fn payment_msg(&self, storage: &str, msg: PaymentExecuteMsg) -> StdResult<CosmosMsg>;
+
+    fn query_payment<R: DeserializeOwned>(
+        &self,
+        querier: &QuerierWrapper,
+        storage: &str,
+        msg: PaymentQueryMsg,
+    ) -> StdResult<R>;
+
+    fn query_payment_binary(
+        &self,
+        querier: &QuerierWrapper,
+        storage: &str,
+        msg: PaymentQueryMsg,
+    ) -> StdResult<Binary>;
+
+    fn update_offering_payment(&self, storage: &str, payment: Payment) -> StdResult<CosmosMsg> {
+        self.payment_msg(storage, PaymentExecuteMsg::UpdateOfferingPayment(payment))
+    }
+
+    fn update_auction_payment(&self, storage: &str, payment: Payment) -> StdResult<CosmosMsg> {
+        self.payment_msg(storage, PaymentExecuteMsg::UpdateAuctionPayment(payment))
+    }
+
+    fn remove_offering_payment(
+        &self,
+        storage: &str,
+        contract_addr: Addr,
+        token_id: &str,
+        sender: Option<Addr>,
+    ) -> StdResult<CosmosMsg> {
+        self.payment_msg(
+            storage,
+            PaymentExecuteMsg::RemoveOfferingPayment {
+                contract_addr,
+                token_id: token_id.to_string(),
+                sender,
+            },
+        )
+    }
+
+    fn remove_auction_payment(
+        &self,
+        storage: &str,
+        contract_addr: Addr,
+        token_id: &str,
+        sender: Option<Addr>,
+    ) -> StdResult<CosmosMsg> {
+        self.payment_msg(
+            storage,
+            PaymentExecuteMsg::RemoveAuctionPayment {
+                contract_addr,
+                token_id: token_id.to_string(),
+                sender,
+            },
+        )
+    }
+
+    fn get_offering_payment(
+        &self,
+        querier: &QuerierWrapper,
+        storage: &str,
+        contract_addr: Addr,
+        token_id: &str,
+        sender: Option<Addr>,
+    ) -> StdResult<AssetInfo> {
+        self.query_payment(
+            querier,
+            storage,
+            PaymentQueryMsg::GetOfferingPayment {
+                contract_addr,
+                token_id: token_id.to_string(),
+                sender,
+            },
+        )
+    }
+
+    fn get_auction_payment(
+        &self,
+        querier: &QuerierWrapper,
+        storage: &str,
+        contract_addr: Addr,
+        token_id: &str,
+        sender: Option<Addr>,
+    ) -> StdResult<AssetInfo> {
+        self.query_payment(
+            querier,
+            storage,
+            PaymentQueryMsg::GetAuctionPayment {
+                contract_addr,
+                token_id: token_id.to_string(),
+                sender,
+            },
+        )
+    }
+}
+
+impl PaymentStorage for MarketHubContract {
+    fn payment_msg(&self, storage: &str, msg: PaymentExecuteMsg) -> StdResult<CosmosMsg> {
+        self.execute_storage(storage, &StorageMsg::Msg(msg))
+    }
+
+    fn query_payment<R: DeserializeOwned>(
+        &self,
+        querier: &QuerierWrapper,
+        storage: &str,
+        msg: PaymentQueryMsg,
+    ) -> StdResult<R> {
+        self.query_storage_msg(querier, storage, &StorageMsg::Msg(msg))
+    }
+
+    fn query_payment_binary(
+        &self,
+        querier: &QuerierWrapper,
+        storage: &str,
+        msg: PaymentQueryMsg,
+    ) -> StdResult<Binary> {
+        self.query_storage_binary(querier, storage, &StorageMsg::Msg(msg))
+    }
+}
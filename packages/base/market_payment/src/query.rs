@@ -1,32 +1,38 @@
+use cosmwasm_schema::QueryResponses;
 use cosmwasm_std::{Addr, Binary};
 use market::AssetInfo;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, QueryResponses)]
 #[serde(rename_all = "snake_case")]
 pub enum PaymentQueryMsg {
     // GetOfferings returns a list of all offerings
+    #[returns(AssetInfo)]
     GetOfferingPayment {
         contract_addr: Addr,
         token_id: String,
         sender: Option<Addr>,
     },
+    #[returns(Vec<PaymentResponse>)]
     GetOfferingPayments {
         offset: Option<Binary>,
         limit: Option<u8>,
         order: Option<u8>,
     },
+    #[returns(AssetInfo)]
     GetAuctionPayment {
         contract_addr: Addr,
         token_id: String,
         sender: Option<Addr>,
     },
+    #[returns(Vec<PaymentResponse>)]
     GetAuctionPayments {
         offset: Option<Binary>,
         limit: Option<u8>,
         order: Option<u8>,
     },
+    #[returns(PaymentContractInfo)]
     GetContractInfo {},
 }
 
@@ -47,3 +53,21 @@ pub struct PaymentResponse {
     pub sender: Option<Addr>,
     pub asset_info: AssetInfo,
 }
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PaymentContractInfo {
+    pub governance: Addr,
+    pub creator: Addr,
+    pub default_denom: String,
+}
+
+/// schema of the responses of the wrapped payment queries
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(untagged)]
+pub enum PaymentQueryResponse {
+    GetOfferingPayment(AssetInfo),
+    GetOfferingPayments(Vec<PaymentResponse>),
+    GetAuctionPayment(AssetInfo),
+    GetAuctionPayments(Vec<PaymentResponse>),
+    GetContractInfo(PaymentContractInfo),
+}
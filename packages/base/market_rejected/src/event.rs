@@ -1,5 +1,7 @@
 use cosmwasm_std::{attr, Response};
 
+use crate::RejectReason;
+
 pub trait Event {
     /// Append attributes to response
     fn add_attributes(&self, response: &mut Response);
@@ -11,6 +13,9 @@ pub struct RejectAllEvent<'a> {
     pub contract_addr: &'a str,
     pub token_id: &'a str,
     pub rejected: bool,
+    pub moderator: &'a str,
+    /// only set on rejects
+    pub reason: Option<RejectReason>,
 }
 
 impl<'a> Event for RejectAllEvent<'a> {
@@ -23,5 +28,10 @@ impl<'a> Event for RejectAllEvent<'a> {
             .push(attr("token_id", self.token_id.to_string()));
         rsp.attributes
             .push(attr("rejected", (self.rejected as u32).to_string()));
+        rsp.attributes
+            .push(attr("moderator", self.moderator.to_string()));
+        if let Some(reason) = self.reason {
+            rsp.attributes.push(attr("reason", reason.to_string()));
+        }
     }
 }
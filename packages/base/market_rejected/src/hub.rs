@@ -0,0 +1,89 @@
+use cosmwasm_std::{Binary, CosmosMsg, QuerierWrapper, StdResult};
+use cw_utils::Expiration;
+use market::{MarketHubContract, StorageMsg, REJECTED_STORAGE};
+use serde::de::DeserializeOwned;
+
+use crate::{
+    IsRejectedForAllResponse, MarketRejectedExecuteMsg, MarketRejectedQueryMsg, NftInfo,
+    RejectReason,
+};
+
+/// typed client of the rejected storage, every update is routed through the hub
+pub trait RejectedStorage {
+    fn rejected_msg(&self, msg: MarketRejectedExecuteMsg) -> StdResult<CosmosMsg>;
+
+    fn query_rejected<R: DeserializeOwned>(
+        &self,
+        querier: &QuerierWrapper,
+        msg: MarketRejectedQueryMsg,
+    ) -> StdResult<R>;
+
+    fn query_rejected_binary(
+        &self,
+        querier: &QuerierWrapper,
+        msg: MarketRejectedQueryMsg,
+    ) -> StdResult<Binary>;
+
+    fn release_all(
+        &self,
+        nft_info: NftInfo,
+        note: Option<String>,
+        moderator: Option<String>,
+    ) -> StdResult<CosmosMsg> {
+        self.rejected_msg(MarketRejectedExecuteMsg::ReleaseAll {
+            nft_info,
+            note,
+            moderator,
+        })
+    }
+
+    fn reject_all(
+        &self,
+        nft_info: NftInfo,
+        expires: Option<Expiration>,
+        reason: Option<RejectReason>,
+        note: Option<String>,
+        moderator: Option<String>,
+    ) -> StdResult<CosmosMsg> {
+        self.rejected_msg(MarketRejectedExecuteMsg::RejectAll {
+            nft_info,
+            expires,
+            reason,
+            note,
+            moderator,
+        })
+    }
+
+    fn is_rejected_for_all(
+        &self,
+        querier: &QuerierWrapper,
+        nft_info: NftInfo,
+    ) -> StdResult<IsRejectedForAllResponse> {
+        self.query_rejected(
+            querier,
+            MarketRejectedQueryMsg::IsRejectedForAll { nft_info },
+        )
+    }
+}
+
+impl RejectedStorage for MarketHubContract {
+    fn rejected_msg(&self, msg: MarketRejectedExecuteMsg) -> StdResult<CosmosMsg> {
+        self.execute_storage(REJECTED_STORAGE, &StorageMsg::Msg(msg))
+    }
+
+    fn query_rejected<R: DeserializeOwned>(
+        &self,
+        querier: &QuerierWrapper,
+        msg: MarketRejectedQueryMsg,
+    ) -> StdResult<R> {
+        self.query_storage_msg(querier, REJECTED_STORAGE, &StorageMsg::Msg(msg))
+    }
+
+    fn query_rejected_binary(
+        &self,
+        querier: &QuerierWrapper,
+        msg: MarketRejectedQueryMsg,
+    ) -> StdResult<Binary> {
+        self.query_storage_binary(querier, REJECTED_STORAGE, &StorageMsg::Msg(msg))
+    }
+}
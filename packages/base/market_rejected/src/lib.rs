@@ -1,9 +1,11 @@
 mod event;
+mod hub;
 mod msg;
 mod query;
 pub use cw_utils::Expiration;
 
 pub use crate::event::*;
+pub use crate::hub::*;
 pub use crate::msg::*;
 pub use crate::query::*;
 pub use market::*;
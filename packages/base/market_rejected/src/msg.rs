@@ -1,5 +1,6 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::fmt;
 
 use cw_utils::Expiration;
 
@@ -11,11 +12,19 @@ pub enum MarketRejectedExecuteMsg {
     // in our storage, release means allowing to sell / auction on the marketplace. By default release aka not in the list
     ReleaseAll {
         nft_info: NftInfo,
+        note: Option<String>,
+        /// who released the token, the sender when None
+        moderator: Option<String>,
     },
     // add in list if we revoke the right the sell / auction on the marketplace
     RejectAll {
         nft_info: NftInfo,
         expires: Option<Expiration>,
+        reason: Option<RejectReason>,
+        note: Option<String>,
+        /// who rejected the token, the sender when None. the storage only sees the hub
+        /// as sender, so implementations relaying a moderator action pass it here
+        moderator: Option<String>,
     },
 }
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
@@ -23,3 +32,26 @@ pub struct NftInfo {
     pub contract_addr: String,
     pub token_id: String,
 }
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, JsonSchema, Debug, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum RejectReason {
+    Spam,
+    Copyright,
+    Fraud,
+    Inappropriate,
+    #[default]
+    Other,
+}
+
+impl fmt::Display for RejectReason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RejectReason::Spam => write!(f, "spam"),
+            RejectReason::Copyright => write!(f, "copyright"),
+            RejectReason::Fraud => write!(f, "fraud"),
+            RejectReason::Inappropriate => write!(f, "inappropriate"),
+            RejectReason::Other => write!(f, "other"),
+        }
+    }
+}
@@ -1,25 +1,61 @@
-use cosmwasm_std::Binary;
+use cosmwasm_schema::QueryResponses;
+use cosmwasm_std::{Binary, Timestamp};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use cw_utils::Expiration;
 
-use crate::NftInfo;
+use market::PageResponse;
 
-#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+use crate::{NftInfo, RejectReason};
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug, QueryResponses)]
 #[serde(rename_all = "snake_case")]
 pub enum MarketRejectedQueryMsg {
     /// List all operators that can access all of the owner's tokens.
     /// Return type: ApprovedForAllResponse.
+    #[returns(RejectedForAllResponse)]
     RejectedForAll {
         /// unset or false will filter out expired approvals, you must set to true to see them
         include_expired: Option<bool>,
         start_after: Option<Binary>,
         limit: Option<u32>,
     },
-    /// Query approved status `owner` granted to `operator`.
+    /// Query approved status `owner` granted to `operator`.
     /// Return type: IsApprovedForAllResponse
+    #[returns(IsRejectedForAllResponse)]
     IsRejectedForAll { nft_info: NftInfo },
+    /// rejected tokens of an nft contract.
+    /// Return type: PageResponse<Rejection>
+    #[returns(PageResponse<Rejection>)]
+    RejectedByContract {
+        contract_addr: String,
+        /// unset or false will filter out expired rejections
+        include_expired: Option<bool>,
+        start_after: Option<Binary>,
+        limit: Option<u32>,
+        with_total: Option<bool>,
+    },
+    /// tokens rejected by a moderator, used to review appeals.
+    /// Return type: PageResponse<Rejection>
+    #[returns(PageResponse<Rejection>)]
+    RejectedByModerator {
+        moderator: String,
+        /// unset or false will filter out expired rejections
+        include_expired: Option<bool>,
+        start_after: Option<Binary>,
+        limit: Option<u32>,
+        with_total: Option<bool>,
+    },
+    /// reject and release actions of a token, newest first by default.
+    /// Return type: PageResponse<RejectAction>
+    #[returns(PageResponse<RejectAction>)]
+    RejectHistory {
+        nft_info: NftInfo,
+        start_after: Option<Binary>,
+        limit: Option<u32>,
+        order: Option<u8>,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
@@ -30,6 +66,34 @@ pub struct Rejected {
     pub expires: Expiration,
 }
 
+/// the current rejection of a token
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct Rejection {
+    pub contract_addr: String,
+    pub token_id: String,
+    pub expires: Expiration,
+    pub reason: RejectReason,
+    pub note: Option<String>,
+    pub moderator: String,
+    pub rejected_at: Timestamp,
+}
+
+/// one entry of the reject / release history of a token
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct RejectAction {
+    pub contract_addr: String,
+    pub token_id: String,
+    /// true for a reject, false for a release
+    pub rejected: bool,
+    /// only set on rejects
+    pub reason: Option<RejectReason>,
+    pub note: Option<String>,
+    pub moderator: String,
+    /// only set on rejects
+    pub expires: Option<Expiration>,
+    pub at: Timestamp,
+}
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
 pub struct RejectedForAllResponse {
     pub operators: Vec<Rejected>,
@@ -38,4 +102,18 @@ pub struct RejectedForAllResponse {
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
 pub struct IsRejectedForAllResponse {
     pub rejected: bool,
+    /// the active rejection, none when the token is not rejected
+    #[serde(default)]
+    pub rejection: Option<Rejection>,
+}
+
+/// schema of the responses of the wrapped rejected queries
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(untagged)]
+pub enum MarketRejectedQueryResponse {
+    RejectedForAll(RejectedForAllResponse),
+    IsRejectedForAll(IsRejectedForAllResponse),
+    RejectedByContract(PageResponse<Rejection>),
+    RejectedByModerator(PageResponse<Rejection>),
+    RejectHistory(PageResponse<RejectAction>),
 }
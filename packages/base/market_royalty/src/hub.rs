@@ -0,0 +1,95 @@
+use cosmwasm_std::{Addr, Binary, CosmosMsg, QuerierWrapper, StdResult};
+use market::{storage_update, MarketHubContract, StorageMsg, StorageUpdate, OFFERING_STORAGE};
+use serde::de::DeserializeOwned;
+
+use crate::{
+    Offering, OfferingExecuteMsg, OfferingQueryMsg, OfferingRoyalty, QueryOfferingsResult,
+};
+
+/// typed client of the offering storage, every update is routed through the hub
+pub trait OfferingStorage {
+    fn offering_msg(&self, msg: OfferingExecuteMsg) -> StdResult<CosmosMsg>;
+
+    /// the update without sending it, so a settlement can batch it with its other updates
+    fn offering_update(&self, msg: OfferingExecuteMsg) -> StdResult<StorageUpdate>;
+
+    fn query_offering<R: DeserializeOwned>(
+        &self,
+        querier: &QuerierWrapper,
+        msg: OfferingQueryMsg,
+    ) -> StdResult<R>;
+
+    fn query_offering_binary(
+        &self,
+        querier: &QuerierWrapper,
+        msg: OfferingQueryMsg,
+    ) -> StdResult<Binary>;
+
+    fn update_offering(&self, offering: Offering) -> StdResult<CosmosMsg> {
+        self.offering_msg(OfferingExecuteMsg::UpdateOffering { offering })
+    }
+
+    fn update_offering_royalty(&self, offering: OfferingRoyalty) -> StdResult<CosmosMsg> {
+        self.offering_msg(OfferingExecuteMsg::UpdateOfferingRoyalty { offering })
+    }
+
+    fn remove_offering(&self, id: u64) -> StdResult<CosmosMsg> {
+        self.offering_msg(OfferingExecuteMsg::RemoveOffering { id })
+    }
+
+    fn get_offering_by_contract_token_id(
+        &self,
+        querier: &QuerierWrapper,
+        contract: Addr,
+        token_id: &str,
+    ) -> StdResult<QueryOfferingsResult> {
+        self.query_offering(
+            querier,
+            OfferingQueryMsg::GetOfferingByContractTokenId {
+                contract,
+                token_id: token_id.to_string(),
+            },
+        )
+    }
+
+    fn get_offering_royalty_by_contract_token_id(
+        &self,
+        querier: &QuerierWrapper,
+        contract: Addr,
+        token_id: &str,
+    ) -> StdResult<OfferingRoyalty> {
+        self.query_offering(
+            querier,
+            OfferingQueryMsg::GetOfferingRoyaltyByContractTokenId {
+                contract,
+                token_id: token_id.to_string(),
+            },
+        )
+    }
+}
+
+impl OfferingStorage for MarketHubContract {
+    fn offering_msg(&self, msg: OfferingExecuteMsg) -> StdResult<CosmosMsg> {
+        self.execute_storage(OFFERING_STORAGE, &StorageMsg::Offering(msg))
+    }
+
+    fn offering_update(&self, msg: OfferingExecuteMsg) -> StdResult<StorageUpdate> {
+        storage_update(OFFERING_STORAGE, &StorageMsg::Offering(msg))
+    }
+
+    fn query_offering<R: DeserializeOwned>(
+        &self,
+        querier: &QuerierWrapper,
+        msg: OfferingQueryMsg,
+    ) -> StdResult<R> {
+        self.query_storage_msg(querier, OFFERING_STORAGE, &StorageMsg::Offering(msg))
+    }
+
+    fn query_offering_binary(
+        &self,
+        querier: &QuerierWrapper,
+        msg: OfferingQueryMsg,
+    ) -> StdResult<Binary> {
+        self.query_storage_binary(querier, OFFERING_STORAGE, &StorageMsg::Offering(msg))
+    }
+}
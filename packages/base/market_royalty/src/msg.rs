@@ -9,6 +9,7 @@ use serde::{Deserialize, Serialize};
 pub enum Cw20HookMsg {
     BuyNft { offering_id: u64 },
     BidNft { auction_id: u64 },
+    FulfillOrder(SignedOrder),
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -55,6 +56,11 @@ pub struct Offering {
     pub seller: CanonicalAddr,
     pub price: Uint128,
     // percentage for seller(previous-owner) of the NFT
+    /// only these addresses can buy the nft, anyone when None
+    pub reserved_for: Option<Vec<Addr>>,
+    /// the asset the price is paid in, None for offerings listed before it was recorded
+    #[serde(default)]
+    pub asset_info: Option<AssetInfo>,
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
@@ -88,6 +94,32 @@ pub enum OfferingExecuteMsg {
     // RemoveOfferingRoyalty { id: u64 },
 }
 
+/// A sell order signed off-chain by the seller. Its hash is the sha256 of the json encoded order,
+/// so the fields must be serialized in this order by the signer
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct Order {
+    /// the market implementation allowed to settle the order
+    pub market: Addr,
+    pub contract_addr: Addr,
+    pub token_id: String,
+    pub seller: Addr,
+    pub price: Uint128,
+    pub asset_info: AssetInfo,
+    /// timestamp in seconds after which the order cannot be fulfilled
+    pub expiration: u64,
+    /// must be greater than or equal to the current order nonce of the seller
+    pub nonce: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct SignedOrder {
+    pub order: Order,
+    /// compressed secp256k1 public key of the seller
+    pub pubkey: Binary,
+    /// 64 bytes secp256k1 signature over the order hash
+    pub signature: Binary,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InfoMsg {
     pub name: Option<String>,
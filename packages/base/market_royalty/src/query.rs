@@ -1,74 +1,124 @@
-use cosmwasm_schema::{cw_serde};
-use cosmwasm_std::{Addr, Binary, ContractInfo, Uint128};
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Addr, Binary, Uint128};
 
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use crate::{Offering, OfferingRoyalty};
+use market::{AssetInfo, PageResponse, StorageContractInfo};
+
+use crate::{Offering, OfferingRoyalty, OfferingRoyaltyResponse};
 
 #[cw_serde]
+#[derive(QueryResponses)]
 pub enum OfferingQueryMsg {
     // GetOfferings returns a list of all offerings
+    #[returns(OfferingsResponse)]
     GetOfferings {
         offset: Option<u64>,
-        limit: Option<u8>,
+        start_after: Option<Binary>,
+        limit: Option<u32>,
         order: Option<u8>,
+        with_total: Option<bool>,
     },
+    #[returns(OfferingsResponse)]
     GetOfferingsBySeller {
         seller: Addr,
         offset: Option<u64>,
-        limit: Option<u8>,
+        start_after: Option<Binary>,
+        limit: Option<u32>,
         order: Option<u8>,
+        with_total: Option<bool>,
     },
+    #[returns(OfferingsResponse)]
     GetOfferingsByContract {
         contract: Addr,
         offset: Option<u64>,
-        limit: Option<u8>,
+        start_after: Option<Binary>,
+        limit: Option<u32>,
         order: Option<u8>,
+        with_total: Option<bool>,
     },
+    #[returns(QueryOfferingsResult)]
     GetOffering {
         offering_id: u64,
     },
+    #[returns(Offering)]
     GetOfferingState {
         offering_id: u64,
     },
+    #[returns(QueryOfferingsResult)]
     GetOfferingByContractTokenId {
         contract: Addr,
         token_id: String,
     },
+    /// cheapest offering of the contract, optionally only those paid in the given asset
+    #[returns(Option<QueryOfferingsResult>)]
+    GetFloorOffering {
+        contract: Addr,
+        asset_info: Option<AssetInfo>,
+    },
+    /// offerings of the contract ordered by price, ascending by default. both bounds are inclusive
+    #[returns(OfferingsResponse)]
+    GetOfferingsByPriceRange {
+        contract: Addr,
+        min_price: Option<Uint128>,
+        max_price: Option<Uint128>,
+        asset_info: Option<AssetInfo>,
+        start_after: Option<Binary>,
+        limit: Option<u32>,
+        order: Option<u8>,
+        with_total: Option<bool>,
+    },
+    #[returns(PageResponse<OfferingRoyalty>)]
     GetOfferingsRoyalty {
         offset: Option<OffsetMsg>,
-        limit: Option<u8>,
+        start_after: Option<Binary>,
+        limit: Option<u32>,
         order: Option<u8>,
+        with_total: Option<bool>,
     },
+    #[returns(PageResponse<OfferingRoyaltyResponse>)]
     GetOfferingsRoyaltyWithKeys {
         offset: Option<OffsetMsg>,
-        limit: Option<u8>,
+        start_after: Option<Binary>,
+        limit: Option<u32>,
         order: Option<u8>,
+        with_total: Option<bool>,
     },
+    #[returns(PageResponse<OfferingRoyalty>)]
     GetOfferingsRoyaltyByCurrentOwner {
         current_owner: Addr,
         offset: Option<OffsetMsg>,
-        limit: Option<u8>,
+        start_after: Option<Binary>,
+        limit: Option<u32>,
         order: Option<u8>,
+        with_total: Option<bool>,
     },
+    #[returns(PageResponse<OfferingRoyalty>)]
     GetOfferingsRoyaltyByContract {
         contract: Addr,
         offset: Option<OffsetMsg>,
-        limit: Option<u8>,
+        start_after: Option<Binary>,
+        limit: Option<u32>,
         order: Option<u8>,
+        with_total: Option<bool>,
     },
+    #[returns(OfferingRoyalty)]
     GetOfferingRoyalty {
         offering_id: Binary,
     },
+    #[returns(OfferingRoyalty)]
     GetOfferingRoyaltyByContractTokenId {
         contract: Addr,
         token_id: String,
     },
+    #[returns(StorageContractInfo)]
     GetContractInfo {},
 }
 
+/// schema of the responses of the wrapped offering queries
 #[cw_serde]
+#[serde(untagged)]
 pub enum OfferingQueryResponse {
     GetOfferings(OfferingsResponse),
     GetOfferingsBySeller(OfferingsResponse),
@@ -76,12 +126,15 @@ pub enum OfferingQueryResponse {
     GetOffering(QueryOfferingsResult),
     GetOfferingState(Offering),
     GetOfferingByContractTokenId(QueryOfferingsResult),
-    GetOfferingsRoyalty(Vec<OfferingRoyalty>),
-    GetOfferingsRoyaltyByCurrentOwner(Vec<OfferingRoyalty>),
-    GetOfferingsRoyaltyByContract(Vec<OfferingRoyalty>),
+    GetFloorOffering(Option<QueryOfferingsResult>),
+    GetOfferingsByPriceRange(OfferingsResponse),
+    GetOfferingsRoyalty(PageResponse<OfferingRoyalty>),
+    GetOfferingsRoyaltyWithKeys(PageResponse<OfferingRoyaltyResponse>),
+    GetOfferingsRoyaltyByCurrentOwner(PageResponse<OfferingRoyalty>),
+    GetOfferingsRoyaltyByContract(PageResponse<OfferingRoyalty>),
     GetOfferingRoyalty(OfferingRoyalty),
     GetOfferingRoyaltyByContractTokenId(OfferingRoyalty),
-    GetContractInfo(ContractInfo),
+    GetContractInfo(StorageContractInfo),
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
@@ -91,11 +144,20 @@ pub struct QueryOfferingsResult {
     pub price: Uint128,
     pub contract_addr: Addr,
     pub seller: Addr,
+    pub reserved_for: Option<Vec<Addr>>,
+    #[serde(default)]
+    pub asset_info: Option<AssetInfo>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct OfferingsResponse {
     pub offerings: Vec<QueryOfferingsResult>,
+    /// pass it as start_after to get the next page, none on the last page
+    #[serde(default)]
+    pub next_cursor: Option<Binary>,
+    /// only counted when with_total is set
+    #[serde(default)]
+    pub total: Option<u64>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
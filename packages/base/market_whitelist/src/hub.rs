@@ -0,0 +1,105 @@
+use cosmwasm_std::{Binary, CosmosMsg, QuerierWrapper, StdResult};
+use cw_utils::Expiration;
+use market::{MarketHubContract, StorageMsg, WHITELIST_STORAGE};
+use serde::de::DeserializeOwned;
+
+use crate::{
+    Collection, IsApprovedForAllResponse, MarketWhiteListExecuteMsg, MarketWhiteListdQueryMsg,
+};
+
+/// typed client of the whitelist storage, every update is routed through the hub
+pub trait WhitelistStorage {
+    fn whitelist_msg(&self, msg: MarketWhiteListExecuteMsg) -> StdResult<CosmosMsg>;
+
+    fn query_whitelist<R: DeserializeOwned>(
+        &self,
+        querier: &QuerierWrapper,
+        msg: MarketWhiteListdQueryMsg,
+    ) -> StdResult<R>;
+
+    fn query_whitelist_binary(
+        &self,
+        querier: &QuerierWrapper,
+        msg: MarketWhiteListdQueryMsg,
+    ) -> StdResult<Binary>;
+
+    fn approve_all(&self, nft_addr: &str, expires: Option<Expiration>) -> StdResult<CosmosMsg> {
+        self.whitelist_msg(MarketWhiteListExecuteMsg::ApproveAll {
+            nft_addr: nft_addr.to_string(),
+            expires,
+        })
+    }
+
+    fn revoke_all(&self, nft_addr: &str) -> StdResult<CosmosMsg> {
+        self.whitelist_msg(MarketWhiteListExecuteMsg::RevokeAll {
+            nft_addr: nft_addr.to_string(),
+        })
+    }
+
+    fn approve_collection(
+        &self,
+        nft_addr: &str,
+        verified: bool,
+        expires: Option<Expiration>,
+    ) -> StdResult<CosmosMsg> {
+        self.whitelist_msg(MarketWhiteListExecuteMsg::ApproveCollection {
+            nft_addr: nft_addr.to_string(),
+            verified,
+            expires,
+        })
+    }
+
+    fn reject_application(&self, nft_addr: &str) -> StdResult<CosmosMsg> {
+        self.whitelist_msg(MarketWhiteListExecuteMsg::RejectApplication {
+            nft_addr: nft_addr.to_string(),
+        })
+    }
+
+    fn get_collection(
+        &self,
+        querier: &QuerierWrapper,
+        nft_addr: &str,
+    ) -> StdResult<Option<Collection>> {
+        self.query_whitelist(
+            querier,
+            MarketWhiteListdQueryMsg::Collection {
+                nft_addr: nft_addr.to_string(),
+            },
+        )
+    }
+
+    fn is_approved_for_all(
+        &self,
+        querier: &QuerierWrapper,
+        nft_addr: &str,
+    ) -> StdResult<IsApprovedForAllResponse> {
+        self.query_whitelist(
+            querier,
+            MarketWhiteListdQueryMsg::IsApprovedForAll {
+                nft_addr: nft_addr.to_string(),
+            },
+        )
+    }
+}
+
+impl WhitelistStorage for MarketHubContract {
+    fn whitelist_msg(&self, msg: MarketWhiteListExecuteMsg) -> StdResult<CosmosMsg> {
+        self.execute_storage(WHITELIST_STORAGE, &StorageMsg::Msg(msg))
+    }
+
+    fn query_whitelist<R: DeserializeOwned>(
+        &self,
+        querier: &QuerierWrapper,
+        msg: MarketWhiteListdQueryMsg,
+    ) -> StdResult<R> {
+        self.query_storage_msg(querier, WHITELIST_STORAGE, &StorageMsg::Msg(msg))
+    }
+
+    fn query_whitelist_binary(
+        &self,
+        querier: &QuerierWrapper,
+        msg: MarketWhiteListdQueryMsg,
+    ) -> StdResult<Binary> {
+        self.query_storage_binary(querier, WHITELIST_STORAGE, &StorageMsg::Msg(msg))
+    }
+}
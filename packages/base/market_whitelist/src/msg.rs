@@ -1,5 +1,8 @@
+use cosmwasm_std::{Timestamp, Uint128};
+use market::AssetInfo;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::fmt;
 
 use cw_utils::Expiration;
 
@@ -15,4 +18,96 @@ pub enum MarketWhiteListExecuteMsg {
     RevokeAll {
         nft_addr: String,
     },
+    /// creators send it to the storage directly, the application waits for the governance
+    ApplyCollection {
+        nft_addr: String,
+        metadata: CollectionMetadata,
+        listing_types: Vec<ListingType>,
+        min_prices: Option<Vec<MinPrice>>,
+    },
+    /// whitelists the collection with the details of its pending application
+    ApproveCollection {
+        nft_addr: String,
+        verified: bool,
+        expires: Option<Expiration>,
+    },
+    RejectApplication {
+        nft_addr: String,
+    },
+    UpdateCollection {
+        nft_addr: String,
+        verified: Option<bool>,
+        metadata: Option<CollectionMetadata>,
+        listing_types: Option<Vec<ListingType>>,
+        /// replaces the minimum prices, an empty list lifts them all
+        min_prices: Option<Vec<MinPrice>>,
+    },
+}
+
+/// how a token of a collection can be put on the marketplace
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum ListingType {
+    FixedPrice,
+    Auction,
+    Rental,
+}
+
+impl fmt::Display for ListingType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ListingType::FixedPrice => write!(f, "fixed_price"),
+            ListingType::Auction => write!(f, "auction"),
+            ListingType::Rental => write!(f, "rental"),
+        }
+    }
+}
+
+/// lowest price of a listing paid in the asset, in the smallest unit of the asset
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct MinPrice {
+    pub asset_info: AssetInfo,
+    pub amount: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct CollectionMetadata {
+    pub name: String,
+    pub category: Option<String>,
+    /// the applicant when None
+    pub creator: Option<String>,
+}
+
+/// a curated collection of the registry
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct Collection {
+    pub nft_addr: String,
+    pub verified: bool,
+    pub metadata: CollectionMetadata,
+    pub listing_types: Vec<ListingType>,
+    /// at most one per asset, the listings in other assets have no minimum
+    pub min_prices: Vec<MinPrice>,
+}
+
+impl Collection {
+    pub fn allows(&self, listing_type: ListingType) -> bool {
+        self.listing_types.contains(&listing_type)
+    }
+
+    pub fn min_price(&self, asset_info: &AssetInfo) -> Option<Uint128> {
+        self.min_prices
+            .iter()
+            .find(|min_price| min_price.asset_info.eq(asset_info))
+            .map(|min_price| min_price.amount)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct CollectionApplication {
+    pub nft_addr: String,
+    pub applicant: String,
+    pub metadata: CollectionMetadata,
+    pub listing_types: Vec<ListingType>,
+    pub min_prices: Vec<MinPrice>,
+    pub applied_at: Timestamp,
 }
@@ -1,13 +1,20 @@
+use cosmwasm_schema::QueryResponses;
+use cosmwasm_std::Binary;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use cw_utils::Expiration;
 
-#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+use market::PageResponse;
+
+use crate::{Collection, CollectionApplication};
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug, QueryResponses)]
 #[serde(rename_all = "snake_case")]
 pub enum MarketWhiteListdQueryMsg {
     /// List all operators that can access all of the owner's tokens.
     /// Return type: ApprovedForAllResponse.
+    #[returns(ApprovedForAllResponse)]
     ApprovedForAll {
         /// unset or false will filter out expired approvals, you must set to true to see them
         include_expired: Option<bool>,
@@ -16,7 +23,28 @@ pub enum MarketWhiteListdQueryMsg {
     },
     /// Query approved status `owner` granted to `operator`.
     /// Return type: IsApprovedForAllResponse
+    #[returns(IsApprovedForAllResponse)]
     IsApprovedForAll { nft_addr: String },
+    /// Return type: Option<Collection>
+    #[returns(Option<Collection>)]
+    Collection { nft_addr: String },
+    /// registered collections, optionally of one category.
+    /// Return type: PageResponse<Collection>
+    #[returns(PageResponse<Collection>)]
+    Collections {
+        category: Option<String>,
+        start_after: Option<Binary>,
+        limit: Option<u32>,
+        with_total: Option<bool>,
+    },
+    /// applications waiting for the governance.
+    /// Return type: PageResponse<CollectionApplication>
+    #[returns(PageResponse<CollectionApplication>)]
+    PendingApplications {
+        start_after: Option<Binary>,
+        limit: Option<u32>,
+        with_total: Option<bool>,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
@@ -35,4 +63,18 @@ pub struct ApprovedForAllResponse {
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
 pub struct IsApprovedForAllResponse {
     pub approved: bool,
+    /// registry details of an approved collection, none for the collections approved with ApproveAll
+    #[serde(default)]
+    pub collection: Option<Collection>,
+}
+
+/// schema of the responses of the wrapped whitelist queries
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(untagged)]
+pub enum MarketWhiteListQueryResponse {
+    ApprovedForAll(ApprovedForAllResponse),
+    IsApprovedForAll(IsApprovedForAllResponse),
+    Collection(Option<Collection>),
+    Collections(PageResponse<Collection>),
+    PendingApplications(PageResponse<CollectionApplication>),
 }
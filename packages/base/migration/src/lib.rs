@@ -0,0 +1,235 @@
+use cosmwasm_std::{from_json, DepsMut, Env, Response, StdError, StdResult, Storage};
+use cw2::{set_contract_version, ContractVersion};
+use semver::Version;
+
+/// cw2 keeps the version of the contract under this key
+pub const VERSION_KEY: &str = "contract_info";
+
+/// the version contracts deployed before they tracked one are migrated from, so every step runs on them
+pub const LEGACY_VERSION: &str = "0.0.0";
+
+/// a change of the state layout, introduced by a release of the contract. The steps of a release
+/// run in the order they are listed
+pub struct MigrationStep {
+    /// the release that introduced the layout, the step runs when migrating from an older version
+    pub version: &'static str,
+    /// reported in the attributes of the migrate response
+    pub name: &'static str,
+    pub run: fn(DepsMut, &Env) -> StdResult<()>,
+}
+
+/// checks the stored version against the one being deployed, runs the steps introduced since the
+/// stored version in order and saves the new version. Downgrades and migrating from another
+/// contract are refused, migrating to the same version only saves the version again
+pub fn migrate_version(
+    mut deps: DepsMut,
+    env: &Env,
+    contract: &str,
+    version: &str,
+    steps: &[MigrationStep],
+) -> StdResult<Response> {
+    let to = parse_version(version)?;
+    let stored = stored_version(deps.storage)?;
+    let from_version = match &stored {
+        Some(stored) => {
+            if stored.contract != contract {
+                return Err(StdError::generic_err(format!(
+                    "cannot migrate {} to {}",
+                    stored.contract, contract
+                )));
+            }
+            stored.version.clone()
+        }
+        None => LEGACY_VERSION.to_string(),
+    };
+    let from = parse_version(&from_version)?;
+    if from > to {
+        return Err(StdError::generic_err(format!(
+            "cannot downgrade {} from {} to {}",
+            contract, from_version, version
+        )));
+    }
+
+    let mut res = Response::new()
+        .add_attribute("action", "migrate")
+        .add_attribute("contract", contract)
+        .add_attribute("from_version", from_version)
+        .add_attribute("to_version", version);
+    let mut versions: Vec<Version> = vec![];
+    for step in steps {
+        let step_version = parse_version(step.version)?;
+        if versions.last().is_some_and(|prev| step_version < *prev) || step_version > to {
+            return Err(StdError::generic_err(format!(
+                "migration step {} of {} is out of order",
+                step.name, step.version
+            )));
+        }
+        versions.push(step_version);
+    }
+    for (step, step_version) in steps.iter().zip(versions) {
+        if step_version > from {
+            (step.run)(deps.branch(), env)?;
+            res = res.add_attribute("migration_step", format!("{}:{}", step.version, step.name));
+        }
+    }
+
+    set_contract_version(deps.storage, contract, version)?;
+    Ok(res)
+}
+
+/// the version saved by cw2. Contracts that kept their own info under the cw2 key before they
+/// tracked a version have none, same as the ones that kept nothing there
+pub fn stored_version(storage: &dyn Storage) -> StdResult<Option<ContractVersion>> {
+    Ok(storage
+        .get(VERSION_KEY.as_bytes())
+        .and_then(|raw| from_json(raw).ok()))
+}
+
+/// moves a singleton to another key, used to free the cw2 key from the contracts that stored their
+/// info there. Does nothing when there is no value to move
+pub fn move_item(storage: &mut dyn Storage, from: &str, to: &str) -> StdResult<()> {
+    if let Some(raw) = storage.get(from.as_bytes()) {
+        storage.set(to.as_bytes(), &raw);
+        storage.remove(from.as_bytes());
+    }
+    Ok(())
+}
+
+/// the first releases were tagged with a leading v, like v0.1.0
+fn parse_version(version: &str) -> StdResult<Version> {
+    Version::parse(version.trim_start_matches('v'))
+        .map_err(|err| StdError::generic_err(format!("invalid version {}: {}", version, err)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::{mock_dependencies, mock_env};
+    use cw2::get_contract_version;
+
+    const CONTRACT: &str = "crates.io:market_offering_storage";
+
+    fn first(deps: DepsMut, _env: &Env) -> StdResult<()> {
+        deps.storage.set(b"first", b"1");
+        Ok(())
+    }
+
+    fn second(deps: DepsMut, _env: &Env) -> StdResult<()> {
+        // only runs after the first step
+        let first = deps.storage.get(b"first").unwrap_or(b"0".to_vec());
+        deps.storage.set(b"second", &first);
+        Ok(())
+    }
+
+    const STEPS: &[MigrationStep] = &[
+        MigrationStep {
+            version: "0.2.0",
+            name: "first",
+            run: first,
+        },
+        MigrationStep {
+            version: "0.3.0",
+            name: "second",
+            run: second,
+        },
+    ];
+
+    fn steps_ran(res: &Response) -> Vec<String> {
+        res.attributes
+            .iter()
+            .filter(|attr| attr.key == "migration_step")
+            .map(|attr| attr.value.clone())
+            .collect()
+    }
+
+    #[test]
+    fn legacy_contract_runs_every_step() {
+        let mut deps = mock_dependencies();
+        // the contract info of a contract that did not track its version
+        deps.storage
+            .set(VERSION_KEY.as_bytes(), br#"{"governance":"hub"}"#);
+        move_item(&mut deps.storage, VERSION_KEY, "info").unwrap();
+        assert_eq!(
+            deps.storage.get(b"info").unwrap(),
+            br#"{"governance":"hub"}"#.to_vec()
+        );
+
+        let res = migrate_version(deps.as_mut(), &mock_env(), CONTRACT, "0.3.0", STEPS).unwrap();
+        assert_eq!(steps_ran(&res), vec!["0.2.0:first", "0.3.0:second"]);
+        assert_eq!(deps.storage.get(b"second"), Some(b"1".to_vec()));
+        assert!(res
+            .attributes
+            .iter()
+            .any(|attr| attr.key == "from_version" && attr.value == LEGACY_VERSION));
+        assert_eq!(
+            get_contract_version(&deps.storage).unwrap(),
+            ContractVersion {
+                contract: CONTRACT.to_string(),
+                version: "0.3.0".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn only_newer_steps_run() {
+        let mut deps = mock_dependencies();
+        set_contract_version(&mut deps.storage, CONTRACT, "v0.2.0").unwrap();
+        let res = migrate_version(deps.as_mut(), &mock_env(), CONTRACT, "0.3.1", STEPS).unwrap();
+        assert_eq!(steps_ran(&res), vec!["0.3.0:second"]);
+        assert_eq!(deps.storage.get(b"first"), None);
+        assert_eq!(deps.storage.get(b"second"), Some(b"0".to_vec()));
+
+        // nothing left to run on the same version
+        let res = migrate_version(deps.as_mut(), &mock_env(), CONTRACT, "0.3.1", STEPS).unwrap();
+        assert!(steps_ran(&res).is_empty());
+    }
+
+    #[test]
+    fn refuses_downgrade_and_other_contracts() {
+        let mut deps = mock_dependencies();
+        set_contract_version(&mut deps.storage, CONTRACT, "0.3.0").unwrap();
+        let err =
+            migrate_version(deps.as_mut(), &mock_env(), CONTRACT, "0.2.0", STEPS).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "Generic error: cannot downgrade {} from 0.3.0 to 0.2.0",
+                CONTRACT
+            )
+        );
+        let err = migrate_version(deps.as_mut(), &mock_env(), "crates.io:ow20", "0.4.0", &[])
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "Generic error: cannot migrate {} to crates.io:ow20",
+                CONTRACT
+            )
+        );
+        // the version is kept
+        assert_eq!(
+            get_contract_version(&deps.storage).unwrap().version,
+            "0.3.0"
+        );
+    }
+
+    #[test]
+    fn refuses_unordered_steps() {
+        let mut deps = mock_dependencies();
+        let unordered = [
+            MigrationStep {
+                version: "0.3.0",
+                name: "second",
+                run: second,
+            },
+            MigrationStep {
+                version: "0.2.0",
+                name: "first",
+                run: first,
+            },
+        ];
+        migrate_version(deps.as_mut(), &mock_env(), CONTRACT, "0.3.0", &unordered).unwrap_err();
+        // a step of a release that is not deployed yet
+        migrate_version(deps.as_mut(), &mock_env(), CONTRACT, "0.2.0", STEPS).unwrap_err();
+    }
+}
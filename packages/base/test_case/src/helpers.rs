@@ -108,16 +108,16 @@ pub fn query_testcase(
             offset,
             order,
         } => to_json_binary(&query_testcases(deps, limit, offset, order)?),
-        QueryMsg::GetOwner {} => query_owner(deps),
+        QueryMsg::GetOwner {} => to_json_binary(&query_owner(deps)?),
         QueryMsg::Assert { assert_inputs } => {
             to_json_binary(&assert(env, assert_inputs, assert_handler)?)
         }
     }
 }
 
-fn query_owner(deps: Deps) -> StdResult<Binary> {
+fn query_owner(deps: Deps) -> StdResult<Addr> {
     let state = OWNER.load(deps.storage)?;
-    to_json_binary(&state)
+    Ok(state)
 }
 
 fn assert(
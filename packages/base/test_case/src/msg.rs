@@ -1,3 +1,4 @@
+use cosmwasm_schema::QueryResponses;
 use cosmwasm_std::{Addr, Binary, Coin};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -16,18 +17,19 @@ pub enum ExecuteMsg {
     RemoveTestCase { input: Vec<String> },
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, QueryResponses)]
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
+    #[returns(Addr)]
     GetOwner {},
+    #[returns(TestCaseResponse)]
     GetTestCases {
         offset: Option<Binary>,
         limit: Option<u8>,
         order: Option<u8>,
     },
-    Assert {
-        assert_inputs: Vec<String>,
-    },
+    #[returns(ContractResponse)]
+    Assert { assert_inputs: Vec<String> },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]